@@ -0,0 +1,135 @@
+//! Contract ABIs and bytecode bundled into the binary.
+//!
+//! The files under `abis/` (see `make refresh-abis`) are embedded at compile time, so
+//! the binary runs without that directory next to it. Setting `ABI_DIR` reads the same
+//! file names from another directory instead, e.g. to try refreshed ABIs without a
+//! rebuild.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use alloy::json_abi::JsonAbi;
+use alloy::primitives::Bytes;
+
+/// Directory to read ABIs and bytecode from instead of the embedded copies.
+pub const ABI_DIR_ENV: &str = "ABI_DIR";
+
+/// ABI snapshots bundled at compile time, as `(contract, json)`.
+pub const BUNDLED_ABIS: &[(&str, &str)] = &[
+    ("Perp", include_str!("../abis/Perp.json")),
+    ("PerpFactory", include_str!("../abis/PerpFactory.json")),
+    (
+        "BeaconRegistry",
+        include_str!("../abis/BeaconRegistry.json"),
+    ),
+    (
+        "ProtocolFeeManager",
+        include_str!("../abis/ProtocolFeeManager.json"),
+    ),
+    (
+        "ModuleRegistry",
+        include_str!("../abis/ModuleRegistry.json"),
+    ),
+    ("Multicall3", include_str!("../abis/Multicall3.json")),
+];
+
+/// `abis/IdentityBeacon.bytecode`: hex creation code, with or without `0x`.
+pub const IDENTITY_BEACON_BYTECODE: &str = include_str!("../abis/IdentityBeacon.bytecode");
+
+/// A contract ABI that could not be read or parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiLoadError {
+    /// The ABI's file, e.g. `abis/Perp.json`.
+    pub file: String,
+    pub reason: String,
+}
+
+impl fmt::Display for AbiLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to load {}: {}", self.file, self.reason)
+    }
+}
+
+impl std::error::Error for AbiLoadError {}
+
+/// `ABI_DIR`, when set and not blank.
+pub fn abi_dir_from_env() -> Option<PathBuf> {
+    std::env::var(ABI_DIR_ENV)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+fn parse_abi(file: String, json: &str) -> Result<JsonAbi, AbiLoadError> {
+    serde_json::from_str(json).map_err(|e| AbiLoadError {
+        file,
+        reason: e.to_string(),
+    })
+}
+
+/// Parse the ABI JSON of `contract` (bundled as `abis/{contract}.json`).
+pub fn load_abi(contract: &str, json: &str) -> Result<JsonAbi, AbiLoadError> {
+    parse_abi(format!("abis/{contract}.json"), json)
+}
+
+/// Every bundled contract's ABI, read from `ABI_DIR` when set and from the embedded
+/// copies otherwise. Each ABI is tried, so the error lists every file that failed.
+pub fn load_bundled_abis() -> Result<Vec<(&'static str, JsonAbi)>, Vec<AbiLoadError>> {
+    load_bundled_abis_from(abi_dir_from_env().as_deref())
+}
+
+/// [`load_bundled_abis`] reading from `dir` instead of the embedded copies when given.
+pub fn load_bundled_abis_from(
+    dir: Option<&Path>,
+) -> Result<Vec<(&'static str, JsonAbi)>, Vec<AbiLoadError>> {
+    let mut abis = Vec::new();
+    let mut errors = Vec::new();
+    for &(contract, embedded) in BUNDLED_ABIS {
+        let result = match dir {
+            Some(dir) => {
+                let path = dir.join(format!("{contract}.json"));
+                let file = path.display().to_string();
+                match std::fs::read_to_string(&path) {
+                    Ok(json) => parse_abi(file, &json),
+                    Err(e) => Err(AbiLoadError {
+                        file,
+                        reason: e.to_string(),
+                    }),
+                }
+            }
+            None => load_abi(contract, embedded),
+        };
+        match result {
+            Ok(abi) => abis.push((contract, abi)),
+            Err(e) => errors.push(e),
+        }
+    }
+    if errors.is_empty() {
+        Ok(abis)
+    } else {
+        Err(errors)
+    }
+}
+
+/// IdentityBeacon creation code, from `ABI_DIR` when set and embedded otherwise.
+pub fn identity_beacon_bytecode() -> Result<Bytes, String> {
+    identity_beacon_bytecode_from(abi_dir_from_env().as_deref())
+}
+
+/// [`identity_beacon_bytecode`] reading from `dir` instead of the embedded copy when
+/// given.
+pub fn identity_beacon_bytecode_from(dir: Option<&Path>) -> Result<Bytes, String> {
+    let bytecode_hex = match dir {
+        Some(dir) => {
+            let path = dir.join("IdentityBeacon.bytecode");
+            std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {e}", path.display()))?
+        }
+        None => IDENTITY_BEACON_BYTECODE.to_string(),
+    };
+    let bytecode_hex = bytecode_hex.trim();
+    hex::decode(bytecode_hex.strip_prefix("0x").unwrap_or(bytecode_hex))
+        .map(Bytes::from)
+        .map_err(|e| format!("Failed to decode IdentityBeacon bytecode hex: {e}"))
+}
@@ -0,0 +1,473 @@
+//! Startup configuration errors.
+//!
+//! `create_rocket` reads its environment through a [`ConfigReader`], which records every
+//! missing or unparseable variable instead of stopping at the first one, then reports
+//! them together as one [`ConfigError`]. Startup dependencies that fail later (Redis,
+//! KMS, the RPC provider) surface as a single-problem `ConfigError`, so a misconfigured
+//! deploy exits with a clear message rather than a panic.
+//!
+//! Contract addresses can also come from a [`ContractRegistry`] file (`CONTRACTS_FILE`),
+//! so one file per network replaces a dozen `*_ADDRESS` variables. A variable that is set
+//! still wins over the file.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use alloy::primitives::Address;
+
+/// Everything wrong with the startup configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    pub problems: Vec<String>,
+}
+
+impl ConfigError {
+    /// An error with a single problem.
+    pub fn new(problem: impl Into<String>) -> Self {
+        Self {
+            problems: vec![problem.into()],
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.problems.as_slice() {
+            [problem] => write!(f, "Invalid configuration: {problem}"),
+            problems => {
+                write!(f, "Invalid configuration ({} problems):", problems.len())?;
+                for problem in problems {
+                    write!(f, "\n  - {problem}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Env var naming the JSON contract address file read by [`ContractRegistry::from_env`].
+pub const CONTRACTS_FILE_ENV: &str = "CONTRACTS_FILE";
+
+/// Names a [`ContractRegistry`] file may use. Each stands in for the env var of the same
+/// name upper-cased plus `_ADDRESS`: `perp_factory` for `PERP_FACTORY_ADDRESS`.
+pub const CONTRACT_NAMES: &[&str] = &[
+    "perpcity_registry",
+    "ecdsa_verifier_factory",
+    "perp_factory",
+    "fees_module",
+    "funding_module",
+    "margin_ratios_module",
+    "price_impact_module",
+    "pricing_module",
+    "usdc",
+    "multicall3",
+    "lbcgbm_factory",
+    "weighted_sum_composite_factory",
+    "safe",
+    "protocol_fee_manager",
+    "module_registry",
+];
+
+/// Contract addresses by name, loaded from a JSON object such as
+/// `{"chain_id": 421614, "perp_factory": "0x...", "usdc": "0x..."}`. Every entry is
+/// validated at load: unknown names and unparseable addresses are reported together as
+/// one error. The optional `chain_id` ties the file to one network.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContractRegistry {
+    addresses: BTreeMap<String, Address>,
+    chain_id: Option<u64>,
+}
+
+impl ContractRegistry {
+    /// Parse a registry file's contents.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let entries: BTreeMap<String, serde_json::Value> = serde_json::from_str(json)
+            .map_err(|e| format!("not a JSON object of names to addresses: {e}"))?;
+
+        let mut registry = Self::default();
+        let mut problems = Vec::new();
+        for (name, value) in entries {
+            if name == "chain_id" {
+                match value.as_u64() {
+                    Some(chain_id) => registry.chain_id = Some(chain_id),
+                    None => problems.push(format!("'chain_id' is not a chain id: {value}")),
+                }
+                continue;
+            }
+            if !CONTRACT_NAMES.contains(&name.as_str()) {
+                problems.push(format!("unknown contract name '{name}'"));
+                continue;
+            }
+            let Some(raw) = value.as_str() else {
+                problems.push(format!("'{name}' is not a valid address: {value}"));
+                continue;
+            };
+            match Address::from_str(raw.trim()) {
+                Ok(address) => {
+                    registry.addresses.insert(name, address);
+                }
+                Err(e) => problems.push(format!("'{name}' is not a valid address: {e}")),
+            }
+        }
+        if !problems.is_empty() {
+            return Err(problems.join("; "));
+        }
+        Ok(registry)
+    }
+
+    /// Read and parse the registry file at `path`.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            format!(
+                "{CONTRACTS_FILE_ENV} {} could not be read: {e}",
+                path.display()
+            )
+        })?;
+        Self::from_json(&json)
+            .map_err(|e| format!("{CONTRACTS_FILE_ENV} {} is invalid: {e}", path.display()))
+    }
+
+    /// The registry named by `CONTRACTS_FILE`, or an empty one when it is unset.
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var(CONTRACTS_FILE_ENV) {
+            Ok(path) if !path.trim().is_empty() => Self::load(Path::new(path.trim())),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// The file's address for `name`.
+    pub fn get(&self, name: &str) -> Option<Address> {
+        self.addresses.get(name).copied()
+    }
+
+    /// The chain the file is for, when it names one.
+    pub fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
+
+    /// Err when the file names a chain other than `chain_id`.
+    pub fn check_chain_id(&self, chain_id: u64) -> Result<(), String> {
+        match self.chain_id {
+            Some(file_chain_id) if file_chain_id != chain_id => Err(format!(
+                "{CONTRACTS_FILE_ENV} is for chain {file_chain_id}, but the server runs on chain {chain_id}"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    /// The registry name standing in for env var `key` (`PERP_FACTORY_ADDRESS` ->
+    /// `perp_factory`).
+    pub fn name_for_env_key(key: &str) -> String {
+        key.strip_suffix("_ADDRESS").unwrap_or(key).to_lowercase()
+    }
+
+    /// The address for env var `key`: the variable when set (env takes precedence), the
+    /// file's entry otherwise, `None` when neither has one.
+    pub fn resolve(&self, key: &str) -> Option<Result<Address, String>> {
+        match std::env::var(key) {
+            Ok(raw) if !raw.trim().is_empty() => Some(
+                Address::from_str(raw.trim())
+                    .map_err(|e| format!("{key} is not a valid address: {e}")),
+            ),
+            _ => self.get(&Self::name_for_env_key(key)).map(Ok),
+        }
+    }
+}
+
+/// Reads environment variables, collecting a problem for each one that is missing or
+/// invalid. Failed reads return a placeholder (empty string, zero address, the
+/// default) so loading can carry on; check [`ConfigReader::finish`] before using any
+/// value.
+#[derive(Debug, Default)]
+pub struct ConfigReader {
+    problems: Vec<String>,
+    /// Fallback for unset address variables (see [`ContractRegistry::resolve`]).
+    contracts: ContractRegistry,
+}
+
+impl ConfigReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a problem found outside the reader's own helpers.
+    pub fn problem(&mut self, problem: impl Into<String>) {
+        self.problems.push(problem.into());
+    }
+
+    /// The value of `result`, or `None` after recording its error.
+    pub fn check<T>(&mut self, result: Result<T, String>) -> Option<T> {
+        result.map_err(|e| self.problem(e)).ok()
+    }
+
+    /// A required variable, trimmed. Unset and blank are both missing.
+    pub fn required(&mut self, key: &str) -> String {
+        match std::env::var(key) {
+            Ok(v) if !v.trim().is_empty() => v.trim().to_string(),
+            _ => {
+                self.problem(format!("{key} is not set"));
+                String::new()
+            }
+        }
+    }
+
+    /// Look up unset address variables in `contracts` from now on.
+    pub fn set_contracts(&mut self, contracts: ContractRegistry) {
+        self.contracts = contracts;
+    }
+
+    /// A required address, from the variable or the contract registry. The parse error
+    /// never echoes the raw value.
+    pub fn address(&mut self, key: &str) -> Address {
+        match self.contracts.resolve(key) {
+            Some(Ok(address)) => address,
+            Some(Err(e)) => {
+                self.problem(e);
+                Address::ZERO
+            }
+            None if self.contracts.is_empty() => {
+                self.problem(format!("{key} is not set"));
+                Address::ZERO
+            }
+            None => {
+                self.problem(format!(
+                    "{key} is not set and {CONTRACTS_FILE_ENV} has no '{}' entry",
+                    ContractRegistry::name_for_env_key(key)
+                ));
+                Address::ZERO
+            }
+        }
+    }
+
+    /// An optional address, from the variable or the contract registry. An unparseable
+    /// variable is logged and treated as unset, as optional addresses always have been.
+    pub fn optional_address(&self, key: &str) -> Option<Address> {
+        match self.contracts.resolve(key)? {
+            Ok(address) => Some(address),
+            Err(e) => {
+                tracing::warn!("{e}");
+                None
+            }
+        }
+    }
+
+    /// An optional variable parsed as `T`, or `default` when unset.
+    pub fn parse_or<T>(&mut self, key: &str, default: T) -> T
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        match std::env::var(key) {
+            Ok(v) => v.trim().parse::<T>().unwrap_or_else(|e| {
+                self.problem(format!("Invalid {key} '{}': {e}", v.trim()));
+                default
+            }),
+            Err(_) => default,
+        }
+    }
+
+    /// An optional on/off variable: `1`, `true`, `yes` or `on` (any case) is on, `0`,
+    /// `false`, `no`, `off` or blank is off, and unset is `false`.
+    pub fn flag(&mut self, key: &str) -> bool {
+        let Ok(v) = std::env::var(key) else {
+            return false;
+        };
+        match v.trim().to_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "0" | "false" | "no" | "off" | "" => false,
+            _ => {
+                self.problem(format!(
+                    "Invalid {key} '{}': expected true or false",
+                    v.trim()
+                ));
+                false
+            }
+        }
+    }
+
+    /// The collected problems as one error.
+    pub fn into_error(self) -> ConfigError {
+        ConfigError {
+            problems: self.problems,
+        }
+    }
+
+    /// `Ok` when no problem was recorded.
+    pub fn finish(self) -> Result<(), ConfigError> {
+        if self.problems.is_empty() {
+            Ok(())
+        } else {
+            Err(self.into_error())
+        }
+    }
+}
+
+/// Chain id for an `ENV` value: Arbitrum One for mainnet, Arbitrum Sepolia for testnet,
+/// and the testnet id for localnet (local development / CI).
+pub fn chain_id_for_env(env_type: &str) -> Result<u64, String> {
+    match env_type.trim().to_lowercase().as_str() {
+        "testnet" | "localnet" => Ok(421614),
+        "mainnet" => Ok(42161),
+        _ => Err(format!(
+            "Invalid ENV value '{env_type}'. Must be either 'mainnet', 'testnet', or 'localnet'"
+        )),
+    }
+}
+
+/// Env var that, when truthy, lets mainnet transfer limits exceed their mainnet defaults.
+pub const ALLOW_HIGH_MAINNET_LIMITS_ENV: &str = "ALLOW_HIGH_MAINNET_LIMITS";
+
+/// Per-request caps on funding transfers, in token base units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferLimits {
+    /// `/fund_guest_wallet` USDC (6 decimals).
+    pub usdc_transfer: u128,
+    /// `/fund_guest_wallet` ETH (wei).
+    pub eth_transfer: u128,
+    /// `/fund_bonus_wallet` USDC (6 decimals).
+    pub usdc_bonus: u128,
+}
+
+impl TransferLimits {
+    /// Testnet and localnet defaults: 1000 USDC, 0.01 ETH and a 50 USDC bonus.
+    pub const TESTNET_DEFAULTS: Self = Self {
+        usdc_transfer: 1_000_000_000,
+        eth_transfer: 10_000_000_000_000_000,
+        usdc_bonus: 50_000_000,
+    };
+
+    /// Mainnet defaults: 10 USDC, 0.001 ETH and the 50 USDC bonus. Anything higher needs
+    /// `ALLOW_HIGH_MAINNET_LIMITS`.
+    pub const MAINNET_DEFAULTS: Self = Self {
+        usdc_transfer: 10_000_000,
+        eth_transfer: 1_000_000_000_000_000,
+        usdc_bonus: 50_000_000,
+    };
+
+    pub fn defaults_for_env(env_type: &str) -> Self {
+        if is_mainnet_env(env_type) {
+            Self::MAINNET_DEFAULTS
+        } else {
+            Self::TESTNET_DEFAULTS
+        }
+    }
+}
+
+fn is_mainnet_env(env_type: &str) -> bool {
+    env_type.trim().eq_ignore_ascii_case("mainnet")
+}
+
+impl ConfigReader {
+    /// Transfer limits for `env_type`. Each limit reads the network-scoped variable
+    /// (`MAINNET_USDC_TRANSFER_LIMIT`, `TESTNET_ETH_TRANSFER_LIMIT`, ...; localnet uses
+    /// `TESTNET_`), then the unscoped one (`USDC_TRANSFER_LIMIT`), then the network
+    /// default. On mainnet a limit above its mainnet default is a problem unless
+    /// `ALLOW_HIGH_MAINNET_LIMITS` is set, so a testnet-scale value carried into a mainnet
+    /// deploy stops startup instead of paying out.
+    pub fn transfer_limits(&mut self, env_type: &str) -> TransferLimits {
+        let mainnet = is_mainnet_env(env_type);
+        let scope = if mainnet { "MAINNET" } else { "TESTNET" };
+        let defaults = TransferLimits::defaults_for_env(env_type);
+        let allow_high = std::env::var(ALLOW_HIGH_MAINNET_LIMITS_ENV).is_ok_and(|v| {
+            matches!(
+                v.trim().to_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        });
+
+        let mut limit = |name: &str, default: u128| {
+            let scoped = format!("{scope}_{name}");
+            let key = if std::env::var(&scoped).is_ok() {
+                scoped
+            } else {
+                name.to_string()
+            };
+            let value = self.parse_or(&key, default);
+            if mainnet && value > default && !allow_high {
+                self.problem(format!(
+                    "{key}={value} exceeds the mainnet default of {default}; set \
+                     {ALLOW_HIGH_MAINNET_LIMITS_ENV}=true to allow it"
+                ));
+            }
+            value
+        };
+
+        TransferLimits {
+            usdc_transfer: limit("USDC_TRANSFER_LIMIT", defaults.usdc_transfer),
+            eth_transfer: limit("ETH_TRANSFER_LIMIT", defaults.eth_transfer),
+            usdc_bonus: limit("USDC_BONUS_LIMIT", defaults.usdc_bonus),
+        }
+    }
+}
+
+/// Env var listing chain ids, besides the one `ENV` implies, that the RPC may report:
+/// comma-separated, for custom local chains such as a default Anvil (`31337`).
+pub const ALLOWED_CHAIN_IDS_ENV: &str = "ALLOWED_CHAIN_IDS";
+
+/// Production chains (Ethereum, Optimism, Polygon, Base, Arbitrum One). Only `ENV=mainnet`
+/// may run on one: its transfer limits and `ALLOW_HIGH_MAINNET_LIMITS` interlock follow
+/// `ENV`, so an allowlisted production chain under `ENV=testnet` would pay out at testnet
+/// scale.
+pub const KNOWN_PRODUCTION_CHAIN_IDS: [u64; 5] = [1, 10, 137, 8453, 42161];
+
+/// Reject `ALLOWED_CHAIN_IDS` entries naming a production chain unless `ENV=mainnet`.
+pub fn check_allowed_chain_ids(env_type: &str, allowed: &[u64]) -> Result<(), String> {
+    match allowed
+        .iter()
+        .find(|id| KNOWN_PRODUCTION_CHAIN_IDS.contains(id))
+    {
+        Some(id) if !is_mainnet_env(env_type) => Err(format!(
+            "{ALLOWED_CHAIN_IDS_ENV} lists production chain id {id}, but ENV={env_type}; only \
+             ENV=mainnet may run on a production chain"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Parse an `ALLOWED_CHAIN_IDS` value. Blank entries are skipped.
+pub fn parse_chain_ids(raw: &str) -> Result<Vec<u64>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            id.parse::<u64>()
+                .map_err(|e| format!("{ALLOWED_CHAIN_IDS_ENV} entry '{id}' is not a chain id: {e}"))
+        })
+        .collect()
+}
+
+/// The chain id to run on, given the id `ENV` implies (`expected`) and the one the RPC
+/// reports. A chain in `allowed` replaces the `ENV` default, unless it is a production
+/// chain and `ENV` is not mainnet; any other mismatch means the RPC points at the wrong
+/// network.
+pub fn resolve_chain_id(
+    env_type: &str,
+    expected: u64,
+    allowed: &[u64],
+    reported: u64,
+) -> Result<u64, String> {
+    if reported == expected {
+        Ok(reported)
+    } else if allowed.contains(&reported) {
+        check_allowed_chain_ids(env_type, &[reported])?;
+        Ok(reported)
+    } else {
+        Err(format!(
+            "RPC reports chain id {reported}, but ENV={env_type} expects {expected}; point the \
+             RPC at the right network, or add {reported} to {ALLOWED_CHAIN_IDS_ENV} for a custom \
+             local chain"
+        ))
+    }
+}
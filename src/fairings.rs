@@ -1,9 +1,117 @@
 use rocket::fairing::{Fairing, Info, Kind};
-use rocket::{Data, Request, Response};
+use rocket::http::{Method, Status};
+use rocket::request::{FromRequest, Outcome};
+use rocket::route::{self, Handler, Route};
+use rocket::{Build, Data, Request, Response, Rocket};
+use rocket_okapi::{
+    r#gen::OpenApiGenerator,
+    request::{OpenApiFromRequest, RequestHeaderInput},
+};
+use std::convert::Infallible;
+use tracing::Instrument;
+
+/// Header carrying the per-request correlation id, in both directions.
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Correlation id tying a request to the logs and chain operations it spawns.
+///
+/// A well-formed incoming `X-Request-Id` (set by a load balancer or calling
+/// service) is kept; otherwise a UUID v4 is generated. The id lives in
+/// request-local state, so the fairing, guards, handlers and catchers all see
+/// the same value, and it is echoed back in the `X-Request-Id` response header.
+/// Handlers run inside its [`RequestId::span`] (see [`in_request_span`]), so the
+/// events they and the services they await log carry it; handlers can also take it
+/// as a request guard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    /// The id of `request`, assigned on first access.
+    pub fn of<'r>(request: &'r Request<'_>) -> &'r RequestId {
+        request.local_cache(|| {
+            request
+                .headers()
+                .get_one(REQUEST_ID_HEADER)
+                .filter(|id| is_valid_request_id(id))
+                .map(|id| RequestId(id.to_string()))
+                .unwrap_or_else(|| RequestId(uuid::Uuid::new_v4().to_string()))
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Span carrying the id as a `request_id` field, for `.instrument(...)` on
+    /// service futures so their events are correlated with the request.
+    pub fn span(&self) -> tracing::Span {
+        tracing::info_span!("request", request_id = %self.0)
+    }
+}
+
+/// Route handler running the wrapped one inside the request's [`RequestId::span`].
+#[derive(Clone)]
+struct InRequestSpan(Box<dyn Handler>);
+
+#[rocket::async_trait]
+impl Handler for InRequestSpan {
+    async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> route::Outcome<'r> {
+        let span = RequestId::of(request).span();
+        self.0.handle(request, data).instrument(span).await
+    }
+}
+
+/// `routes` with each handler run inside its request's [`RequestId::span`], so every
+/// event logged while handling a request carries its `request_id`.
+pub fn in_request_span(routes: Vec<Route>) -> Vec<Route> {
+    routes
+        .into_iter()
+        .map(|mut route| {
+            route.handler = Box::new(InRequestSpan(route.handler));
+            route
+        })
+        .collect()
+}
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Whether a client-supplied request id is safe to adopt and echo: 1-128 chars
+/// of ASCII letters, digits, `-`, `_` or `.`.
+pub fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 128
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RequestId {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(RequestId::of(request).clone())
+    }
+}
+
+impl<'r> OpenApiFromRequest<'r> for RequestId {
+    fn from_request_input(
+        _gen: &mut OpenApiGenerator,
+        _name: String,
+        _required: bool,
+    ) -> rocket_okapi::Result<RequestHeaderInput> {
+        Ok(RequestHeaderInput::None)
+    }
+}
 
 /// Logs incoming requests and outgoing responses.
 ///
 /// Captures method, URI, remote address, and response status for monitoring and debugging.
+/// Also assigns each request its [`RequestId`] and echoes it in `X-Request-Id`.
 pub struct RequestLogger;
 
 #[rocket::async_trait]
@@ -16,8 +124,10 @@ impl Fairing for RequestLogger {
     }
 
     async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
-        // ECS / ALB health checks hit /health every few seconds; don't log them.
-        if request.uri().path() == "/health" {
+        let request_id = RequestId::of(request).clone();
+
+        // ECS / ALB health checks hit /health and /ready every few seconds; don't log them.
+        if matches!(request.uri().path().as_str(), "/health" | "/ready") {
             return;
         }
 
@@ -28,7 +138,13 @@ impl Fairing for RequestLogger {
             .map(|r| r.to_string())
             .unwrap_or_else(|| "unknown".to_string());
 
-        tracing::info!("Incoming request: {} {} from {}", method, uri, remote);
+        tracing::info!(
+            request_id = %request_id,
+            "Incoming request: {} {} from {}",
+            method,
+            uri,
+            remote
+        );
 
         // Log authentication header presence only
         if request.headers().get_one("authorization").is_some() {
@@ -37,8 +153,11 @@ impl Fairing for RequestLogger {
     }
 
     async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
-        // ECS / ALB health checks hit /health every few seconds; don't log them.
-        if request.uri().path() == "/health" {
+        let request_id = RequestId::of(request);
+        response.set_raw_header(REQUEST_ID_HEADER, request_id.as_str().to_string());
+
+        // ECS / ALB health checks hit /health and /ready every few seconds; don't log them.
+        if matches!(request.uri().path().as_str(), "/health" | "/ready") {
             return;
         }
 
@@ -47,20 +166,43 @@ impl Fairing for RequestLogger {
         let status = response.status();
 
         // Log the response
-        tracing::info!("Response: {} {} - Status: {}", method, uri, status);
+        tracing::info!(
+            request_id = %request_id,
+            "Response: {} {} - Status: {}",
+            method,
+            uri,
+            status
+        );
 
         // If it's an error, log more details
         if !status.class().is_success() {
-            tracing::error!("Error response: {} {} returned {}", method, uri, status);
+            tracing::error!(
+                request_id = %request_id,
+                "Error response: {} {} returned {}",
+                method,
+                uri,
+                status
+            );
         }
     }
 }
 
 /// Catches and logs internal server errors that may indicate panics.
 ///
-/// Response-side hook kept for symmetry; 500 logging lives in lib.rs's catchers.
+/// Response-side hook kept for symmetry; 500 logging lives in lib.rs's catchers, which
+/// use [`PanicCatcher::tag_request`] so their Sentry events say which request failed.
 pub struct PanicCatcher;
 
+impl PanicCatcher {
+    /// Tag `scope` with the request's method, path (never the query string, which may
+    /// carry credentials) and [`RequestId`].
+    pub fn tag_request(scope: &mut sentry::Scope, request: &Request<'_>) {
+        scope.set_tag("http.method", request.method());
+        scope.set_tag("http.path", request.uri().path());
+        scope.set_tag("request_id", RequestId::of(request));
+    }
+}
+
 #[rocket::async_trait]
 impl Fairing for PanicCatcher {
     fn info(&self) -> Info {
@@ -76,3 +218,143 @@ impl Fairing for PanicCatcher {
         // in log-based error metrics.
     }
 }
+
+/// Env var listing the origins allowed to call the API from a browser: comma-separated
+/// (`https://app.example.com,https://staging.example.com`) or `*` for any. Unset or blank
+/// leaves CORS off.
+pub const CORS_ALLOWED_ORIGINS_ENV: &str = "CORS_ALLOWED_ORIGINS";
+
+/// Methods advertised to preflight requests (every method a route is mounted with).
+const CORS_ALLOWED_METHODS: &str = "GET, POST, PUT, DELETE, OPTIONS";
+
+/// Request headers advertised to preflight requests: auth, the request id, idempotency
+/// keys and signed-request headers.
+const CORS_ALLOWED_HEADERS: &str =
+    "Authorization, Content-Type, X-Request-Id, Idempotency-Key, X-Signature, X-Timestamp";
+
+/// How long browsers may cache a preflight answer, in seconds.
+const CORS_MAX_AGE_SECS: u32 = 600;
+
+/// Origins a [`Cors`] fairing answers for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllowedOrigins {
+    Any,
+    /// Exact origins (`scheme://host[:port]`), lowercased, without a trailing slash.
+    List(Vec<String>),
+}
+
+/// Cross-origin support for browser front-ends.
+///
+/// Adds `Access-Control-Allow-*` headers to responses for allowed origins, and mounts a
+/// catch-all `OPTIONS` route on ignite so preflight requests to any mounted path get
+/// `204 No Content`. Requests from other origins get no CORS headers, so browsers keep
+/// blocking them. Auth is a bearer header, not a cookie, so credentials are not allowed.
+#[derive(Debug, Clone)]
+pub struct Cors {
+    origins: AllowedOrigins,
+}
+
+impl Cors {
+    pub fn new(origins: AllowedOrigins) -> Self {
+        Self { origins }
+    }
+
+    /// Parse a `CORS_ALLOWED_ORIGINS` value. `None` when it names no origin.
+    pub fn parse(value: &str) -> Option<Self> {
+        let origins: Vec<String> = value
+            .split(',')
+            .map(|o| o.trim().trim_end_matches('/').to_ascii_lowercase())
+            .filter(|o| !o.is_empty())
+            .collect();
+        if origins.is_empty() {
+            None
+        } else if origins.iter().any(|o| o == "*") {
+            Some(Self::new(AllowedOrigins::Any))
+        } else {
+            Some(Self::new(AllowedOrigins::List(origins)))
+        }
+    }
+
+    /// Read [`CORS_ALLOWED_ORIGINS_ENV`]. `None` (CORS off) when unset or blank.
+    pub fn from_env() -> Option<Self> {
+        std::env::var(CORS_ALLOWED_ORIGINS_ENV)
+            .ok()
+            .and_then(|v| Self::parse(&v))
+    }
+
+    pub fn origins(&self) -> &AllowedOrigins {
+        &self.origins
+    }
+
+    /// Whether a request from `origin` may read the response.
+    pub fn allows(&self, origin: &str) -> bool {
+        match &self.origins {
+            AllowedOrigins::Any => true,
+            AllowedOrigins::List(list) => {
+                let origin = origin.trim_end_matches('/');
+                list.iter().any(|o| o.eq_ignore_ascii_case(origin))
+            }
+        }
+    }
+}
+
+/// Answers CORS preflight requests for every path; [`Cors`] adds the headers.
+#[rocket::options("/<_..>")]
+fn cors_preflight() -> Status {
+    Status::NoContent
+}
+
+#[rocket::async_trait]
+impl Fairing for Cors {
+    fn info(&self) -> Info {
+        Info {
+            name: "CORS",
+            kind: Kind::Ignite | Kind::Response,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        Ok(rocket.mount("/", rocket::routes![cors_preflight]))
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let Some(origin) = request.headers().get_one("Origin") else {
+            return;
+        };
+        if !self.allows(origin) {
+            return;
+        }
+
+        match self.origins {
+            AllowedOrigins::Any => {
+                response.set_raw_header("Access-Control-Allow-Origin", "*");
+            }
+            AllowedOrigins::List(_) => {
+                response.set_raw_header("Access-Control-Allow-Origin", origin.to_string());
+                // Keep whatever the route already varies on.
+                let vary = match response.headers().get_one("Vary") {
+                    None => Some("Origin".to_string()),
+                    Some(existing)
+                        if existing
+                            .split(',')
+                            .map(str::trim)
+                            .any(|field| field == "*" || field.eq_ignore_ascii_case("Origin")) =>
+                    {
+                        None
+                    }
+                    Some(existing) => Some(format!("{existing}, Origin")),
+                };
+                if let Some(vary) = vary {
+                    response.set_raw_header("Vary", vary);
+                }
+            }
+        }
+        response.set_raw_header("Access-Control-Expose-Headers", REQUEST_ID_HEADER);
+
+        if request.method() == Method::Options {
+            response.set_raw_header("Access-Control-Allow-Methods", CORS_ALLOWED_METHODS);
+            response.set_raw_header("Access-Control-Allow-Headers", CORS_ALLOWED_HEADERS);
+            response.set_raw_header("Access-Control-Max-Age", CORS_MAX_AGE_SECS.to_string());
+        }
+    }
+}
@@ -1,10 +1,21 @@
-use crate::models::AppState;
+use crate::models::{AppState, AuthConfig, RequestSigning, TokenScope};
+use hmac::{Hmac, Mac};
+use rocket::data::{self, Data, FromData, Limits};
+use rocket::http::Method;
+use rocket::serde::json::Json;
 use rocket::{Request, State, http::Status, request::FromRequest, request::Outcome};
 use rocket_okapi::{
     r#gen::OpenApiGenerator,
-    okapi::openapi3::{Object, SecurityRequirement, SecurityScheme, SecuritySchemeData},
-    request::{OpenApiFromRequest, RequestHeaderInput},
+    okapi::openapi3::{
+        Object, Parameter, ParameterValue, RequestBody, SecurityRequirement, SecurityScheme,
+        SecuritySchemeData,
+    },
+    request::{OpenApiFromData, OpenApiFromRequest, RequestHeaderInput},
 };
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use sha2::Sha256;
+use std::collections::HashSet;
 use subtle::ConstantTimeEq;
 use tracing;
 
@@ -13,11 +24,274 @@ fn token_matches(provided: &str, expected: &str) -> bool {
     provided.as_bytes().ct_eq(expected.as_bytes()).into()
 }
 
+/// Scope a scoped access token needs for the given route (by handler name).
+///
+/// Returns `None` for routes with no scope mapping; those stay reachable only with the
+/// legacy BEACONATOR_ACCESS_TOKEN, so a new endpoint is closed to scoped tokens until it
+/// is mapped here.
+pub fn required_scope_for_route(route_name: &str) -> Option<TokenScope> {
+    match route_name {
+        "get_perp_endpoint"
+        | "get_maker_position_endpoint"
+        | "preview_deposit"
+        | "preview_create_perp"
+        | "list_recipes"
+        | "get_recipe"
+        | "list_component_factories"
+        | "gas_metrics"
+        | "estimate_gas"
+        | "gas_price"
+        | "get_beacon_events"
+        | "batch_beacon_data" => Some(TokenScope::Read),
+        "create_beacon"
+        | "batch_create_beacon"
+        | "create_beacon_with_ecdsa"
+        | "create_lbcgbm_beacon_endpoint"
+        | "create_weighted_sum_composite_beacon_endpoint"
+        | "create_modular_beacon" => Some(TokenScope::BeaconCreate),
+        "register_beacon" | "unregister_beacon" => Some(TokenScope::BeaconRegister),
+        "update_beacon" | "batch_update_beacon" | "update_beacon_with_ecdsa_adapter" => {
+            Some(TokenScope::BeaconUpdate)
+        }
+        "deploy_perp_for_beacon_endpoint" => Some(TokenScope::PerpDeploy),
+        "deposit_liquidity_for_perp_endpoint"
+        | "deposit_liquidity_by_price_endpoint"
+        | "batch_deposit_liquidity_for_perps_endpoint"
+        | "close_maker_position_endpoint" => Some(TokenScope::PerpDeposit),
+        "fund_guest_wallet" | "fund_bonus_wallet" => Some(TokenScope::Fund),
+        _ => None,
+    }
+}
+
+/// Scopes a bearer token holds: every scope for the legacy BEACONATOR_ACCESS_TOKEN, the
+/// configured ones for a scoped token, `None` for an unknown token. Every configured
+/// token is compared in constant time.
+pub fn granted_scopes(auth: &AuthConfig, token: &str) -> Option<HashSet<TokenScope>> {
+    if token_matches(token, &auth.access_token) {
+        return Some(TokenScope::ALL.into_iter().collect());
+    }
+
+    let mut granted = None;
+    for (candidate, scopes) in &auth.scoped_tokens {
+        if token_matches(token, candidate) {
+            granted = Some(scopes.clone());
+        }
+    }
+    granted
+}
+
+/// Check a bearer token against the configured access tokens.
+///
+/// The legacy BEACONATOR_ACCESS_TOKEN grants every scope, and also reaches routes with no
+/// scope mapping. A scoped token is accepted only when it holds `required`; a known token
+/// without that scope yields `403 Forbidden`, an unknown token `401 Unauthorized`.
+pub fn authorize_api_token(
+    auth: &AuthConfig,
+    token: &str,
+    required: Option<TokenScope>,
+) -> Result<(), Status> {
+    if token_matches(token, &auth.access_token) {
+        return Ok(());
+    }
+
+    match (granted_scopes(auth, token), required) {
+        (Some(scopes), Some(scope)) if scopes.contains(&scope) => Ok(()),
+        (Some(_), _) => Err(Status::Forbidden),
+        (None, _) => Err(Status::Unauthorized),
+    }
+}
+
+/// Token carried by an `Authorization` header value: `Bearer <token>` (scheme matched
+/// case-insensitively) or, for older clients, the raw token on its own.
+pub fn api_token_from_header(header: &str) -> &str {
+    let header = header.trim();
+    match header.split_once(' ') {
+        Some((scheme, token)) if scheme.eq_ignore_ascii_case("Bearer") => token.trim(),
+        // `Bearer ` with nothing after it (trimmed above): no token, not the token "Bearer".
+        None if header.eq_ignore_ascii_case("Bearer") => "",
+        _ => header,
+    }
+}
+
+/// Header carrying a signed request's hex HMAC-SHA256 signature.
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Header carrying the Unix time, in seconds, a request was signed at.
+pub const TIMESTAMP_HEADER: &str = "X-Timestamp";
+
+/// Shortest BEACONATOR_SIGNING_SECRET accepted at startup, in bytes.
+pub const MIN_SIGNING_SECRET_LEN: usize = 32;
+
+/// Signature a client sends as `X-Signature`: hex HMAC-SHA256 under `secret` of the
+/// timestamp (as sent in `X-Timestamp`), the method, the path with its query, and the
+/// raw body, concatenated in that order.
+pub fn sign_request(secret: &str, timestamp: u64, method: &str, path: &str, body: &[u8]) -> String {
+    hex::encode(
+        request_mac(secret, timestamp, method, path, body)
+            .finalize()
+            .into_bytes(),
+    )
+}
+
+fn request_mac(
+    secret: &str,
+    timestamp: u64,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(method.as_bytes());
+    mac.update(path.as_bytes());
+    mac.update(body);
+    mac
+}
+
+/// Parse an `X-Timestamp` value and check it is within `max_age_secs` of `now`, so a
+/// captured request cannot be replayed later.
+pub fn check_signature_timestamp(
+    signing: &RequestSigning,
+    timestamp: &str,
+    now: u64,
+) -> Result<u64, String> {
+    let timestamp: u64 = timestamp
+        .trim()
+        .parse()
+        .map_err(|_| format!("{TIMESTAMP_HEADER} is not a Unix time in seconds"))?;
+    if timestamp.abs_diff(now) > signing.max_age_secs {
+        return Err(format!(
+            "{TIMESTAMP_HEADER} is more than {}s from the server clock",
+            signing.max_age_secs
+        ));
+    }
+    Ok(timestamp)
+}
+
+/// Check a signed request: a fresh `timestamp` and a `signature` matching
+/// [`sign_request`] over the same parts. The signature is compared in constant time.
+pub fn verify_request_signature(
+    signing: &RequestSigning,
+    timestamp: &str,
+    signature: &str,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    now: u64,
+) -> Result<(), String> {
+    let timestamp = check_signature_timestamp(signing, timestamp, now)?;
+    let signature =
+        hex::decode(signature.trim()).map_err(|_| format!("{SIGNATURE_HEADER} is not hex"))?;
+    request_mac(&signing.secret, timestamp, method, path, body)
+        .verify_slice(&signature)
+        .map_err(|_| "Request signature does not match".to_string())
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Signature headers of a request whose body [`SignedJson`] has yet to check.
+#[derive(Clone)]
+struct PendingSignature {
+    signing: RequestSigning,
+    timestamp: String,
+    signature: String,
+}
+
+impl PendingSignature {
+    fn verify(&self, request: &Request<'_>, body: &[u8]) -> Result<(), String> {
+        verify_request_signature(
+            &self.signing,
+            &self.timestamp,
+            &self.signature,
+            request.method().as_str(),
+            &request.uri().to_string(),
+            body,
+            now_secs(),
+        )
+    }
+}
+
+/// [`ApiToken`] outcome for a request carrying `X-Signature`. A GET has no body, so its
+/// signature is checked here; for other methods only the timestamp is, and the signature
+/// is left to [`SignedJson`] once the body has been read.
+fn authorize_signed_request(
+    request: &Request<'_>,
+    signing: &RequestSigning,
+    signature: &str,
+) -> Outcome<ApiToken, String> {
+    let endpoint = request.uri().to_string();
+    let Some(timestamp) = request.headers().get_one(TIMESTAMP_HEADER) else {
+        tracing::warn!("Signed request without {TIMESTAMP_HEADER} for: {endpoint}");
+        return Outcome::Error((
+            Status::Unauthorized,
+            format!("Missing {TIMESTAMP_HEADER} header"),
+        ));
+    };
+    let pending = PendingSignature {
+        signing: signing.clone(),
+        timestamp: timestamp.to_string(),
+        signature: signature.to_string(),
+    };
+
+    let checked = if matches!(request.method(), Method::Get | Method::Head) {
+        pending.verify(request, &[])
+    } else {
+        check_signature_timestamp(signing, timestamp, now_secs()).map(|_| {
+            request.local_cache(|| Some(pending));
+        })
+    };
+    match checked {
+        Ok(()) => Outcome::Success(ApiToken::Signed),
+        Err(e) => {
+            tracing::warn!("Rejected signed request for {endpoint}: {e}");
+            Outcome::Error((Status::Unauthorized, e))
+        }
+    }
+}
+
 /// API token guard for request authentication.
 ///
-/// Validates that requests include a valid Bearer token in the Authorization header.
-/// The token must match the configured BEACONATOR_ACCESS_TOKEN.
-pub struct ApiToken(pub String);
+/// Validates that requests include a valid token in the Authorization header, either as
+/// `Bearer <token>` or as the raw token (see [`api_token_from_header`]).
+/// The token must match the configured BEACONATOR_ACCESS_TOKEN, or be a scoped token
+/// (BEACONATOR_SCOPED_TOKENS_JSON) holding the scope the matched route requires.
+///
+/// With BEACONATOR_SIGNING_SECRET set, a request may instead carry `X-Signature` and
+/// `X-Timestamp` (see [`sign_request`]); it then gets full access, like the legacy token.
+/// A signed request's body is checked by [`SignedJson`], so every non-GET route taking
+/// this guard must read its body through it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiToken {
+    /// A bearer token: the legacy BEACONATOR_ACCESS_TOKEN or a scoped token.
+    Token(String),
+    /// A request authenticated by `X-Signature` instead of a token.
+    Signed,
+}
+
+impl ApiToken {
+    /// Scopes the request was granted: all of them for the legacy token and signed
+    /// requests, the configured ones for a scoped token.
+    pub fn scopes(&self, auth: &AuthConfig) -> HashSet<TokenScope> {
+        match self {
+            Self::Token(token) => granted_scopes(auth, token).unwrap_or_default(),
+            Self::Signed => TokenScope::ALL.into_iter().collect(),
+        }
+    }
+
+    /// The bearer token the request carried; `None` for a signed request.
+    pub fn bearer(&self) -> Option<&str> {
+        match self {
+            Self::Token(token) => Some(token),
+            Self::Signed => None,
+        }
+    }
+}
 
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for ApiToken {
@@ -29,25 +303,43 @@ impl<'r> FromRequest<'r> for ApiToken {
         let state = request.guard::<&State<AppState>>().await;
         match state {
             Outcome::Success(state) => {
+                if let Some(signing) = &state.auth.request_signing
+                    && let Some(signature) = request.headers().get_one(SIGNATURE_HEADER)
+                {
+                    return authorize_signed_request(request, signing, signature);
+                }
+
                 let auth_header = request.headers().get_one("Authorization");
-                match auth_header {
-                    Some(header) if header.starts_with("Bearer ") => {
-                        let token = &header[7..]; // Remove "Bearer " prefix
-                        if token_matches(token, &state.auth.access_token) {
-                            Outcome::Success(ApiToken(token.to_string()))
-                        } else {
-                            tracing::warn!("Invalid API token provided for: {}", endpoint);
-                            Outcome::Error((Status::Unauthorized, "Invalid API token".to_string()))
+                match auth_header.map(api_token_from_header) {
+                    Some(token) if !token.is_empty() => {
+                        let required = request
+                            .route()
+                            .and_then(|route| route.name.as_deref())
+                            .and_then(required_scope_for_route);
+                        match authorize_api_token(&state.auth, token, required) {
+                            Ok(()) => Outcome::Success(ApiToken::Token(token.to_string())),
+                            Err(status) if status == Status::Forbidden => {
+                                tracing::warn!(
+                                    "API token lacks required scope {:?} for: {}",
+                                    required,
+                                    endpoint
+                                );
+                                Outcome::Error((
+                                    Status::Forbidden,
+                                    "API token lacks the required scope".to_string(),
+                                ))
+                            }
+                            Err(status) => {
+                                tracing::warn!("Invalid API token provided for: {}", endpoint);
+                                Outcome::Error((status, "Invalid API token".to_string()))
+                            }
                         }
                     }
-                    Some(_header) => {
-                        tracing::warn!(
-                            "Authorization header doesn't start with 'Bearer ' for: {}",
-                            endpoint
-                        );
+                    Some(_) => {
+                        tracing::warn!("Empty Authorization header for: {}", endpoint);
                         Outcome::Error((
                             Status::Unauthorized,
-                            "Authorization header must start with 'Bearer '".to_string(),
+                            "Authorization header carries no token".to_string(),
                         ))
                     }
                     None => {
@@ -80,7 +372,7 @@ impl<'r> OpenApiFromRequest<'r> for ApiToken {
         let security_scheme = SecurityScheme {
             description: Some(
                 "Bearer token authentication. Include your API token in the Authorization header \
-                 as: `Authorization: Bearer YOUR_TOKEN`"
+                 as: `Authorization: Bearer YOUR_TOKEN` (the bare token is also accepted)"
                     .to_string(),
             ),
             data: SecuritySchemeData::Http {
@@ -102,6 +394,80 @@ impl<'r> OpenApiFromRequest<'r> for ApiToken {
     }
 }
 
+/// JSON request body, read like Rocket's `Json`, that also completes the check of a
+/// signed request.
+///
+/// When [`ApiToken`] accepted the request on its `X-Signature`, the signature is verified
+/// against the exact body bytes before they are parsed; a mismatch is a 401. Otherwise
+/// this is plain JSON: 422 for a body that does not fit `T`, 400 for malformed JSON.
+#[derive(Debug)]
+pub struct SignedJson<T>(pub T);
+
+impl<T> SignedJson<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::ops::Deref for SignedJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for SignedJson<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, T: DeserializeOwned> FromData<'r> for SignedJson<T> {
+    type Error = String;
+
+    async fn from_data(request: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        let limit = request.limits().get("json").unwrap_or(Limits::JSON);
+        let body = match data.open(limit).into_bytes().await {
+            Ok(body) if body.is_complete() => body.into_inner(),
+            Ok(_) => {
+                return data::Outcome::Error((
+                    Status::PayloadTooLarge,
+                    "Request body is too large".to_string(),
+                ));
+            }
+            Err(e) => {
+                return data::Outcome::Error((
+                    Status::BadRequest,
+                    format!("Failed to read request body: {e}"),
+                ));
+            }
+        };
+
+        if let Some(pending) = request.local_cache(|| None::<PendingSignature>)
+            && let Err(e) = pending.verify(request, &body)
+        {
+            tracing::warn!("Rejected signed request for {}: {}", request.uri(), e);
+            return data::Outcome::Error((Status::Unauthorized, e));
+        }
+
+        match serde_json::from_slice(&body) {
+            Ok(value) => data::Outcome::Success(SignedJson(value)),
+            Err(e) if e.is_data() => {
+                data::Outcome::Error((Status::UnprocessableEntity, e.to_string()))
+            }
+            Err(e) => data::Outcome::Error((Status::BadRequest, e.to_string())),
+        }
+    }
+}
+
+impl<'r, T: JsonSchema + DeserializeOwned> OpenApiFromData<'r> for SignedJson<T> {
+    fn request_body(r#gen: &mut OpenApiGenerator) -> rocket_okapi::Result<RequestBody> {
+        Json::<T>::request_body(r#gen)
+    }
+}
+
 /// Admin token guard for admin-only endpoints.
 ///
 /// Validates that requests include a valid Bearer token matching BEACONATOR_ADMIN_TOKEN.
@@ -191,3 +557,75 @@ impl<'r> OpenApiFromRequest<'r> for AdminToken {
         ))
     }
 }
+
+/// Maximum accepted length of an `Idempotency-Key` header value.
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Optional `Idempotency-Key` request header.
+///
+/// Absent header yields `IdempotencyKey(None)`. A present key must be 1-255 visible ASCII
+/// characters; anything else is rejected with `400 Bad Request` so a malformed key never
+/// silently disables deduplication.
+pub struct IdempotencyKey(pub Option<String>);
+
+/// Validate a raw `Idempotency-Key` header value.
+pub fn validate_idempotency_key(raw: &str) -> Result<String, String> {
+    if raw.is_empty() || raw.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(format!(
+            "Idempotency-Key must be 1-{MAX_IDEMPOTENCY_KEY_LEN} characters"
+        ));
+    }
+    if !raw.chars().all(|c| c.is_ascii_graphic()) {
+        return Err("Idempotency-Key must contain only visible ASCII characters".to_string());
+    }
+    Ok(raw.to_string())
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for IdempotencyKey {
+    type Error = String;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.headers().get_one("Idempotency-Key") {
+            None => Outcome::Success(IdempotencyKey(None)),
+            Some(raw) => match validate_idempotency_key(raw) {
+                Ok(key) => Outcome::Success(IdempotencyKey(Some(key))),
+                Err(e) => {
+                    tracing::warn!("Rejected Idempotency-Key for {}: {}", request.uri(), e);
+                    Outcome::Error((Status::BadRequest, e))
+                }
+            },
+        }
+    }
+}
+
+impl<'r> OpenApiFromRequest<'r> for IdempotencyKey {
+    fn from_request_input(
+        r#gen: &mut OpenApiGenerator,
+        _name: String,
+        _required: bool,
+    ) -> rocket_okapi::Result<RequestHeaderInput> {
+        Ok(RequestHeaderInput::Parameter(Parameter {
+            name: "Idempotency-Key".to_string(),
+            location: "header".to_string(),
+            description: Some(
+                "Optional client-chosen key (1-255 visible ASCII chars). Retrying with the same \
+                 key and access token within the idempotency TTL returns the original result \
+                 instead of repeating the operation."
+                    .to_string(),
+            ),
+            required: false,
+            deprecated: false,
+            allow_empty_value: false,
+            value: ParameterValue::Schema {
+                style: None,
+                explode: None,
+                allow_reserved: false,
+                schema: r#gen.json_schema::<String>(),
+                example: None,
+                examples: None,
+            },
+            extensions: Object::default(),
+        }))
+    }
+}
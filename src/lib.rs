@@ -1,40 +1,56 @@
+#[cfg(feature = "wallet-pool")]
+use alloy::signers::aws::AwsSigner;
 use alloy::{
     primitives::{Address, Bytes},
-    signers::{Signer, aws::AwsSigner, local::PrivateKeySigner},
+    signers::{Signer, local::PrivateKeySigner},
 };
 use rocket::{Build, Rocket};
 use rocket_okapi::{openapi_get_routes_spec, settings::OpenApiSettings};
 use std::env;
 use std::str::FromStr;
 
+pub mod abis;
+pub mod config;
 pub mod fairings;
 pub mod guards;
+pub mod logging;
 pub mod models;
 pub mod routes;
 pub mod services;
+pub mod telemetry;
 
+use crate::config::{
+    ALLOWED_CHAIN_IDS_ENV, CONTRACTS_FILE_ENV, ConfigError, ConfigReader, ContractRegistry,
+    TransferLimits, chain_id_for_env, check_allowed_chain_ids, parse_chain_ids, resolve_chain_id,
+};
 use crate::models::beacon_type::{BeaconTypeConfig, FactoryType};
+#[cfg(feature = "wallet-pool")]
 use crate::models::wallet::WalletManagerConfig;
 use crate::models::{
-    AppState, AuthConfig, ContractAddresses, ProviderConfig, Registries, SafeConfig, WalletConfig,
+    AppState, AuthConfig, ContractAddresses, LiveContracts, PerpConfig, ProviderConfig, Registries,
+    RequestSigning, SafeConfig, WalletConfig,
 };
 use crate::services::beacon::BeaconTypeRegistry;
 use crate::services::beacon::ComponentFactoryRegistry;
 use crate::services::beacon::RecipeRegistry;
-use crate::services::wallet::{BalanceTracker, PoolSigner, WalletManager, WalletSyncService};
+#[cfg(feature = "wallet-pool")]
+use crate::services::wallet::{BalanceTracker, PoolSigner, WalletSyncService};
+use crate::services::wallet::{FundingRateLimiter, WalletManager};
 use rocket::{Request, catch, catchers};
 
-// Provider type with embedded wallet for signing transactions
+// Provider type with embedded wallet for signing transactions. Nonces come from
+// PoolNonceManager (shared through Redis for pool wallets) and gas follows GAS_MODE /
+// GAS_LIMIT_MULTIPLIER (GasModeFiller); build these with services::wallet::nonce::signing_provider.
 pub type AlloyProvider = alloy::providers::fillers::FillProvider<
     alloy::providers::fillers::JoinFill<
         alloy::providers::fillers::JoinFill<
             alloy::providers::Identity,
             alloy::providers::fillers::JoinFill<
-                alloy::providers::fillers::GasFiller,
+                crate::services::wallet::gas::GasModeFiller,
                 alloy::providers::fillers::JoinFill<
                     alloy::providers::fillers::BlobGasFiller,
                     alloy::providers::fillers::JoinFill<
-                        alloy::providers::fillers::NonceFiller,
+                        crate::services::wallet::nonce::GasGatedNonceFiller,
                         alloy::providers::fillers::ChainIdFiller,
                     >,
                 >,
@@ -76,14 +92,165 @@ fn serve_openapi_spec(
     )
 }
 
-/// Liveness probe for container orchestrators (ECS health checks, ALB).
+/// Env toggle for the Swagger UI at `/docs`. Off unless set to a truthy value
+/// (`1`/`true`/`yes`/`on`), so production only mounts it deliberately.
+pub const API_DOCS_ENABLED_ENV: &str = "API_DOCS_ENABLED";
+
+/// Whether a raw `API_DOCS_ENABLED` value turns the Swagger UI on (unset means off).
+pub fn api_docs_enabled(value: Option<&str>) -> bool {
+    value.is_some_and(|v| {
+        matches!(
+            v.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        )
+    })
+}
+
+/// Largest JSON request body accepted. A full `/batch_update_beacon` (100 updates, each
+/// with an 8 KiB proof and 1 KiB of public signals, hex-encoded) is about 1.9 MB; larger
+/// bodies are rejected with 413 Payload Too Large before they are parsed.
+pub const MAX_JSON_BODY_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Rocket configuration for the API server: the defaults plus any `Rocket.toml` /
+/// `ROCKET_*` overrides, with the JSON body limit pinned to [`MAX_JSON_BODY_BYTES`]. Only
+/// `limits.json` is set here, so operator limits for other data types are kept.
+pub fn server_figment() -> rocket::figment::Figment {
+    rocket::Config::figment().merge(("limits.json", MAX_JSON_BODY_BYTES))
+}
+
+/// Swagger UI page: loads the spec from `/openapi.json`. Protected endpoints carry the
+/// `bearerAuth` / `adminBearerAuth` security schemes from the token guards, so the UI's
+/// "Authorize" dialog prompts for the bearer token before "Try it out" calls them. The
+/// script and stylesheet are the copies bundled with rocket_okapi, served from `/docs/`,
+/// so the page pulls no third-party code.
+const SWAGGER_UI_HTML: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>The Beaconator API</title>
+  <link rel="stylesheet" href="/docs/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="/docs/swagger-ui-bundle.js"></script>
+  <script>
+    window.onload = () => {
+      window.ui = SwaggerUIBundle({
+        url: "/openapi.json",
+        dom_id: "#swagger-ui",
+        persistAuthorization: true,
+      });
+    };
+  </script>
+</body>
+</html>
+"##;
+
+/// Serves the Swagger UI at /docs (mounted only when API_DOCS_ENABLED is on)
+#[rocket::get("/docs")]
+fn swagger_ui() -> rocket::response::content::RawHtml<&'static str> {
+    rocket::response::content::RawHtml(SWAGGER_UI_HTML)
+}
+
+/// Swagger UI assets the page loads, out of the set rocket_okapi bundles.
+const SWAGGER_UI_ASSETS: [&str; 2] = ["/swagger-ui.css", "/swagger-ui-bundle.js"];
+
+/// Routes for the interactive API docs; `create_rocket` mounts them behind
+/// [`API_DOCS_ENABLED_ENV`].
+pub fn api_docs_routes() -> Vec<rocket::Route> {
+    let assets: Vec<rocket::Route> =
+        rocket_okapi::swagger_ui::make_swagger_ui(&Default::default()).into();
+    let assets = assets
+        .into_iter()
+        .filter(|route| SWAGGER_UI_ASSETS.contains(&route.uri.path()))
+        .map(|route| {
+            route
+                .map_base(|_| "/docs".to_string())
+                .expect("/docs is a valid route base")
+        });
+    rocket::routes![swagger_ui]
+        .into_iter()
+        .chain(assets)
+        .collect()
+}
+
+/// Liveness probe for container orchestrators (ECS container health checks).
 ///
 /// No auth, no Redis, no RPC — returns 200 as long as the Rocket worker is
-/// serving requests. Per-request logging for this path is suppressed in the
+/// serving requests. The body reports each RPC endpoint's circuit breaker from
+/// memory (`status` is "degraded" while one is not closed) without failing the
+/// probe. `?rpc=true` adds a live `eth_blockNumber` through the read-only
+/// provider, for operators; a failed read marks the body degraded but still
+/// returns 200. While the instance is draining for maintenance
+/// (`POST /maintenance/drain`) the body reports `ready: false` but the probe still
+/// passes, so a draining task is not replaced mid-transaction; load balancers
+/// check [`ready`] instead. Per-request logging for this path is suppressed in the
 /// RequestLogger fairing so health checks don't spam the logs.
-#[rocket::get("/health")]
-fn health() -> (rocket::http::ContentType, &'static str) {
-    (rocket::http::ContentType::JSON, r#"{"status":"ok"}"#)
+#[rocket::get("/health?<rpc>")]
+async fn health(
+    rpc: Option<bool>,
+    state: &rocket::State<AppState>,
+) -> rocket::serde::json::Json<models::HealthResponse> {
+    let mut report = instance_health(state);
+    if rpc.unwrap_or(false) {
+        let probe = services::rpc::probe_read_provider(
+            &state.provider.read_provider,
+            services::rpc::READ_PROBE_TIMEOUT,
+        )
+        .await;
+        if !probe.ok {
+            report.status = "degraded".to_string();
+        }
+        report.read_provider = Some(probe);
+    }
+    rocket::serde::json::Json(report)
+}
+
+/// Readiness probe for load balancers (ALB target group health checks).
+///
+/// The `/health` body, served with 200 while the instance takes new work and 503
+/// while it is draining for maintenance, so the load balancer stops routing to it
+/// while in-flight transactions finish. No auth, no RPC; not logged per request.
+#[rocket::get("/ready")]
+async fn ready(
+    state: &rocket::State<AppState>,
+) -> (
+    rocket::http::Status,
+    rocket::serde::json::Json<models::HealthResponse>,
+) {
+    let report = instance_health(state);
+    let status = if report.ready {
+        rocket::http::Status::Ok
+    } else {
+        rocket::http::Status::ServiceUnavailable
+    };
+    (status, rocket::serde::json::Json(report))
+}
+
+/// [`health_report`] plus this instance's drain flag.
+fn instance_health(state: &AppState) -> models::HealthResponse {
+    let mut report = health_report(&state.provider.endpoints);
+    if state.wallets.manager.is_draining() {
+        report.draining = true;
+        report.ready = false;
+    }
+    report
+}
+
+/// Body of `GET /health` for `endpoints`.
+pub fn health_report(endpoints: &services::rpc::RpcEndpoints) -> models::HealthResponse {
+    let rpc_endpoints = endpoints.health();
+    let degraded = rpc_endpoints
+        .iter()
+        .any(|e| e.state != services::rpc::BreakerState::Closed.label());
+    models::HealthResponse {
+        status: if degraded { "degraded" } else { "ok" }.to_string(),
+        live: true,
+        ready: true,
+        draining: false,
+        rpc_endpoints,
+        read_provider: None,
+    }
 }
 
 /// Creates and configures the Rocket application.
@@ -139,6 +306,8 @@ fn audit_environment() {
         // Governance / diagnostic; not on the deploy/open path
         "PROTOCOL_FEE_MANAGER_ADDRESS",
         "MODULE_REGISTRY_ADDRESS",
+        // Default /sweep_wallet destination
+        "COLD_WALLET_ADDRESS",
     ];
     const SECRET_VARS_REQUIRED: &[&str] = &[
         "RPC_URL",
@@ -155,9 +324,16 @@ fn audit_environment() {
         "WALLET_PRIVATE_KEYS",
         "WALLET_KMS_KEY_IDS",
         "WALLET_KMS_ALIAS_PREFIX",
+        // JSON map of additional access tokens -> allowed scopes (src/guards.rs)
+        "BEACONATOR_SCOPED_TOKENS_JSON",
+        // HMAC request signing secret and allowed clock skew (src/guards.rs)
+        "BEACONATOR_SIGNING_SECRET",
+        "BEACONATOR_SIGNATURE_MAX_AGE_SECS",
         // perpcity-bot-api key for the touch-on-update beacon->perps lookup
         // (src/services/touch). Only needed when TOUCH_ON_UPDATE_ENABLED.
         "BOT_API_KEY",
+        // Enables Sentry error reporting (src/telemetry.rs)
+        "SENTRY_DSN",
     ];
     // Other env vars the-beaconator reads. We don't log their values either; we only
     // check presence (for required) and whitespace cleanliness.
@@ -166,8 +342,77 @@ fn audit_environment() {
         "USDC_TRANSFER_LIMIT",
         "ETH_TRANSFER_LIMIT",
         "USDC_BONUS_LIMIT",
+        // Network-scoped overrides of the three limits above, and the flag that lets
+        // mainnet limits exceed their defaults (src/config.rs TransferLimits).
+        "MAINNET_USDC_TRANSFER_LIMIT",
+        "MAINNET_ETH_TRANSFER_LIMIT",
+        "MAINNET_USDC_BONUS_LIMIT",
+        "TESTNET_USDC_TRANSFER_LIMIT",
+        "TESTNET_ETH_TRANSFER_LIMIT",
+        "TESTNET_USDC_BONUS_LIMIT",
+        "ALLOW_HIGH_MAINNET_LIMITS",
         "BEACONATOR_INSTANCE_ID",
         "RUST_LOG",
+        // pretty | compact | json (src/logging.rs)
+        "LOG_FORMAT",
+        // Read ABIs and bytecode from this directory instead of the copies embedded
+        // at build time (src/abis.rs).
+        "ABI_DIR",
+        // Sentry sampling and environment tag (src/telemetry.rs)
+        "SENTRY_TRACES_SAMPLE_RATE",
+        "SENTRY_ENVIRONMENT",
+        // Swagger UI at /docs (off unless truthy).
+        "API_DOCS_ENABLED",
+        // Browser origins allowed by the CORS fairing (src/fairings.rs); unset is off.
+        "CORS_ALLOWED_ORIGINS",
+        // Chain ids besides ENV's that the RPC may report (src/config.rs).
+        "ALLOWED_CHAIN_IDS",
+        // Recent write operations for GET /transactions (src/services/history.rs).
+        "TRANSACTION_HISTORY_ENABLED",
+        "TRANSACTION_HISTORY_MAX_ENTRIES",
+        // Total primary + fallback attempts per receipt confirmation
+        // (src/services/transaction/execution.rs AttemptBudget).
+        "RPC_MAX_TOTAL_ATTEMPTS",
+        // Doubling pause between on-chain receipt lookups, and its cap
+        // (src/services/transaction/execution.rs ReceiptWaitConfig).
+        "RECEIPT_POLL_INTERVAL_SECS",
+        "RECEIPT_POLL_MAX_INTERVAL_SECS",
+        // Comma-separated fallback RPC endpoints (src/services/rpc.rs RpcEndpoints).
+        "RPC_URLS",
+        // WebSocket endpoint for beacon event subscriptions
+        // (src/services/beacon/events.rs); sends stay on RPC_URL.
+        "RPC_WS_URL",
+        // eip1559 (default) or legacy gasPrice pricing for signing providers
+        // (src/services/wallet/gas.rs).
+        "GAS_MODE",
+        // Headroom factor (1.0-3.0) for estimated gas limits (src/services/wallet/gas.rs).
+        "GAS_LIMIT_MULTIPLIER",
+        // Blocks to wait on top of a receipt before returning success
+        // (src/services/transaction/confirmations.rs).
+        "CONFIRMATION_BLOCKS",
+        // Retry/backoff for idempotent reads (src/services/rpc.rs ReadRetryPolicy).
+        "RPC_MAX_RETRIES",
+        "RPC_BACKOFF_MS",
+        // Per-endpoint circuit breaker (src/services/rpc.rs CircuitBreakerPolicy).
+        "RPC_BREAKER_THRESHOLD",
+        "RPC_BREAKER_COOLDOWN_SECS",
+        // /batch_create_beacon count cap and in-flight deployments
+        // (src/services/beacon/batch_create.rs).
+        "BATCH_CREATE_MAX_COUNT",
+        "BATCH_CREATE_CONCURRENCY",
+        // Idempotency-Key / request_id replay caches for /batch_create_beacon and
+        // /fund_guest_wallet (src/services/idempotency.rs).
+        "IDEMPOTENCY_TTL_SECS",
+        "IDEMPOTENCY_MAX_ENTRIES",
+        // Per-operation gas histograms served at GET /metrics/gas
+        // (src/services/metrics.rs).
+        "GAS_METRICS_ENABLED",
+        "GAS_METRICS_BUCKETS",
+        // Per-recipient rolling-window guest funding caps
+        // (src/services/wallet/funding_limits.rs).
+        "GUEST_FUNDING_MAX_USDC_PER_WINDOW",
+        "GUEST_FUNDING_MAX_ETH_PER_WINDOW",
+        "GUEST_FUNDING_WINDOW_SECS",
         // JSON map of component factory addresses seeded into Redis at startup
         // (set by the AWS deployment; see perpcity-client/sst.config.ts)
         "COMPONENT_FACTORIES_JSON",
@@ -176,6 +421,32 @@ fn audit_environment() {
         // selection, and how often the sweep refreshes cached balances.
         "WALLET_MIN_ETH_WEI",
         "WALLET_BALANCE_SWEEP_SECS",
+        // Slippage buffer (bps) over estimated openMaker amounts when a deposit
+        // omits max_amt0_in / max_amt1_in (src/services/perp/slippage.rs).
+        "DEPOSIT_SLIPPAGE_BPS",
+        // Deposit tick defaults and margin bounds (src/models/perp_config.rs).
+        "PERP_TICK_SPACING",
+        "PERP_TICK_LOWER",
+        "PERP_TICK_UPPER",
+        "PERP_MIN_MARGIN_USDC",
+        "PERP_MAX_MARGIN_USDC",
+        // Approve U256::MAX once per wallet/Perp instead of the margin per deposit.
+        "PERP_APPROVE_MAX",
+        // PerpCreated log scan that finds a beacon's existing perp before deploying
+        // (src/services/perp/core.rs).
+        "PERP_FACTORY_DEPLOY_BLOCK",
+        // IndexUpdated history checked against ema_window before deploying
+        // (src/services/perp/cadence.rs).
+        "PERP_BEACON_CADENCE_LOOKBACK_BLOCKS",
+        // Blocks per eth_getLogs in chunked log scans
+        // (src/services/transaction/events.rs scan_logs_chunked).
+        "LOG_SCAN_CHUNK_BLOCKS",
+        // Let operations on a designated beacon use another wallet when the
+        // designated one is busy (src/services/wallet/manager.rs acquire_for_beacon).
+        "WALLET_DESIGNATION_FALLBACK",
+        // Require EIP-55 checksummed beacon/perp/wallet addresses in requests
+        // (src/services/address.rs).
+        "STRICT_ADDRESS_CHECKSUM",
         // Touch-on-update side-loop (src/services/touch). All optional; the
         // feature is off unless TOUCH_ON_UPDATE_ENABLED is truthy, and BOT_API_URL
         // + BOT_API_KEY + MULTICALL3_ADDRESS are then required (checked at spawn).
@@ -185,9 +456,20 @@ fn audit_environment() {
         "TOUCH_MAX_BATCH",
         "TOUCH_MAPPING_TTL_SECONDS",
         "TOUCH_MAPPING_EMPTY_TTL_SECONDS",
+        // IndexUpdated change feed (src/services/beacon/events.rs). Off unless
+        // BEACON_EVENTS_BEACONS lists at least one beacon.
+        "BEACON_EVENTS_BEACONS",
+        "BEACON_EVENTS_START_BLOCK",
+        "BEACON_EVENTS_POLL_INTERVAL_SECS",
+        "BEACON_EVENTS_MAX_BLOCK_RANGE",
+        // Offline mock mode for front-end development (src/services/mock.rs).
+        "BEACONATOR_MODE",
+        // JSON file of contract addresses by name (src/config.rs ContractRegistry).
+        "CONTRACTS_FILE",
     ];
 
     let mut problems = 0usize;
+    let contracts_file = env::var(CONTRACTS_FILE_ENV).is_ok();
 
     // Required presence + whitespace checks (no value logging).
     for &key in ADDRESS_VARS_REQUIRED
@@ -206,6 +488,9 @@ fn audit_environment() {
                     problems += 1;
                 }
             }
+            // Contract addresses may come from CONTRACTS_FILE instead; create_rocket
+            // reports any the file lacks.
+            Err(_) if contracts_file && ADDRESS_VARS_REQUIRED.contains(&key) => {}
             Err(_) => {
                 tracing::error!("{key} is required but not set");
                 problems += 1;
@@ -301,11 +586,15 @@ fn audit_environment() {
 /// "alias/perpcity/testnet/wallet-") via kms:ListAliases. Returns the matching
 /// alias names, sorted for deterministic pool ordering. Aliases without a
 /// target key are skipped. Requires kms:ListAliases on the caller's role.
-async fn discover_wallet_aliases(client: &aws_sdk_kms::Client, prefix: &str) -> Vec<String> {
+#[cfg(feature = "wallet-pool")]
+async fn discover_wallet_aliases(
+    client: &aws_sdk_kms::Client,
+    prefix: &str,
+) -> Result<Vec<String>, String> {
     let mut aliases = Vec::new();
     let mut pages = client.list_aliases().into_paginator().send();
     while let Some(page) = pages.next().await {
-        let page = page.unwrap_or_else(|e| panic!("kms:ListAliases failed: {e}"));
+        let page = page.map_err(|e| format!("kms:ListAliases failed: {e}"))?;
         for entry in page.aliases() {
             let Some(name) = entry.alias_name() else {
                 continue;
@@ -316,195 +605,19 @@ async fn discover_wallet_aliases(client: &aws_sdk_kms::Client, prefix: &str) ->
         }
     }
     aliases.sort();
-    aliases
+    Ok(aliases)
 }
 
-pub async fn create_rocket() -> Rocket<Build> {
-    // Load and cache environment variables
-    dotenvy::dotenv().ok();
-
-    // Verbose pre-flight audit of every env var the-beaconator reads. Runs BEFORE any
-    // parse attempt so the operator can see every problem in one log dump even when the
-    // next step is going to panic. Secrets are never logged in plaintext (only lengths +
-    // whitespace warnings). See `audit_environment` above.
-    audit_environment();
-
-    // Load RPC configuration from environment
-    let rpc_config = services::rpc::RpcConfig::from_env()
-        .unwrap_or_else(|e| panic!("Failed to load RPC configuration: {e}"));
-
-    let access_token = env::var("BEACONATOR_ACCESS_TOKEN")
-        .expect("BEACONATOR_ACCESS_TOKEN environment variable not set");
-
-    // Load contract addresses
-    let perpcity_registry_address = Address::from_str(
-        &env::var("PERPCITY_REGISTRY_ADDRESS")
-            .expect("PERPCITY_REGISTRY_ADDRESS environment variable not set"),
-    )
-    .expect("Failed to parse perpcity registry address");
-
-    // PerpFactory deploys per-market `Perp` contracts. v0.1.0 architecture.
-    let perp_factory_address = Address::from_str(
-        &env::var("PERP_FACTORY_ADDRESS")
-            .expect("PERP_FACTORY_ADDRESS environment variable not set"),
-    )
-    .expect("Failed to parse perp factory address");
-
-    // Module addresses for the v0.1.0 perp Modules struct. All required at startup so
-    // /deploy_perp_for_beacon never has to ask the caller for them.
-    let parse_module_addr = |key: &str| -> Address {
-        Address::from_str(
-            &env::var(key).unwrap_or_else(|_| panic!("{key} environment variable not set")),
-        )
-        .unwrap_or_else(|e| panic!("Failed to parse {key}: {e}"))
-    };
-    let fees_module_address = parse_module_addr("FEES_MODULE_ADDRESS");
-    let funding_module_address = parse_module_addr("FUNDING_MODULE_ADDRESS");
-    let margin_ratios_module_address = parse_module_addr("MARGIN_RATIOS_MODULE_ADDRESS");
-    let price_impact_module_address = parse_module_addr("PRICE_IMPACT_MODULE_ADDRESS");
-    let pricing_module_address = parse_module_addr("PRICING_MODULE_ADDRESS");
-
-    // Optional governance / diagnostic addresses — not on the deploy path.
-    let parse_optional_addr = |key: &str| -> Option<Address> {
-        env::var(key).ok().and_then(|s| {
-            Address::from_str(&s)
-                .map_err(|e| tracing::warn!("Invalid {} '{}': {}", key, s, e))
-                .ok()
-        })
-    };
-    let protocol_fee_manager_address = parse_optional_addr("PROTOCOL_FEE_MANAGER_ADDRESS");
-    let module_registry_address = parse_optional_addr("MODULE_REGISTRY_ADDRESS");
-
-    let usdc_address = Address::from_str(
-        &env::var("USDC_ADDRESS").expect("USDC_ADDRESS environment variable not set"),
-    )
-    .expect("Failed to parse USDC address");
-
-    // Optional multicall3 address for batch operations
-    let multicall3_address = env::var("MULTICALL3_ADDRESS").ok().and_then(|addr_str| {
-        Address::from_str(&addr_str)
-            .map_err(|e| tracing::warn!("Invalid MULTICALL3_ADDRESS '{}': {}", addr_str, e))
-            .ok()
-    });
-
-    if let Some(multicall_addr) = multicall3_address {
-        tracing::info!("Multicall3 address configured: {:?}", multicall_addr);
-    } else {
-        tracing::warn!(
-            "MULTICALL3_ADDRESS not set - batch operations disabled and the wallet \
-             balance sweep will use per-wallet reads"
-        );
-    }
-
-    // Load ECDSA verifier factory address
-    let ecdsa_verifier_factory_address = Address::from_str(
-        &env::var("ECDSA_VERIFIER_FACTORY_ADDRESS")
-            .expect("ECDSA_VERIFIER_FACTORY_ADDRESS environment variable not set"),
-    )
-    .expect("Failed to parse ECDSA verifier factory address");
-
-    tracing::info!(
-        "ECDSA verifier factory address: {:?}",
-        ecdsa_verifier_factory_address
-    );
-
-    // Load optional factory addresses for other beacon types
-    let lbcgbm_factory_address = env::var("LBCGBM_FACTORY_ADDRESS").ok().and_then(|s| {
-        Address::from_str(&s)
-            .map_err(|e| tracing::warn!("Invalid LBCGBM_FACTORY_ADDRESS '{}': {}", s, e))
-            .ok()
-    });
-
-    if let Some(addr) = lbcgbm_factory_address {
-        tracing::info!("LBCGBM factory address: {:?}", addr);
-    }
-
-    let weighted_sum_composite_factory_address = env::var("WEIGHTED_SUM_COMPOSITE_FACTORY_ADDRESS")
-        .ok()
-        .and_then(|s| {
-            Address::from_str(&s)
-                .map_err(|e| {
-                    tracing::warn!(
-                        "Invalid WEIGHTED_SUM_COMPOSITE_FACTORY_ADDRESS '{}': {}",
-                        s,
-                        e
-                    )
-                })
-                .ok()
-        });
-
-    if let Some(addr) = weighted_sum_composite_factory_address {
-        tracing::info!("WeightedSumComposite factory address: {:?}", addr);
-    }
-
-    let usdc_transfer_limit = env::var("USDC_TRANSFER_LIMIT")
-        .unwrap_or_else(|_| "1000000000".to_string()) // Default 1000 USDC
-        .parse::<u128>()
-        .expect("Failed to parse USDC_TRANSFER_LIMIT");
-
-    let eth_transfer_limit = env::var("ETH_TRANSFER_LIMIT")
-        .unwrap_or_else(|_| "10000000000000000".to_string()) // Default 0.01 ETH
-        .parse::<u128>()
-        .expect("Failed to parse ETH_TRANSFER_LIMIT");
-
-    let usdc_bonus_limit = env::var("USDC_BONUS_LIMIT")
-        .unwrap_or_else(|_| "50000000".to_string()) // Default 50 USDC
-        .parse::<u128>()
-        .expect("Failed to parse USDC_BONUS_LIMIT");
-
-    // Post-transfer ETH reserve for guest funding. Default 0.02 ETH — above
-    // the 0.01 ETH BeaconatorWalletGasLow paging threshold, so the faucet
-    // refuses before beacon gas is at risk.
-    let faucet_reserve_eth_wei = env::var("FAUCET_RESERVE_ETH_WEI")
-        .unwrap_or_else(|_| "20000000000000000".to_string()) // Default 0.02 ETH
-        .parse::<u128>()
-        .expect("Failed to parse FAUCET_RESERVE_ETH_WEI");
-
-    // Get environment configuration and chain ID
-    let env_type = &rpc_config.env_type;
-    let chain_id = match env_type.to_lowercase().as_str() {
-        "testnet" => 421614u64,  // Arbitrum Sepolia
-        "mainnet" => 42161u64,   // Arbitrum One
-        "localnet" => 421614u64, // Use testnet chain ID for local development/CI
-        _ => panic!(
-            "Invalid ENV value '{env_type}'. Must be either 'mainnet', 'testnet', or 'localnet'"
-        ),
-    };
-
-    // Get the RPC URL for storing in AppState (used by WalletHandle to build providers)
-    let rpc_url = rpc_config.rpc_url().to_string();
-
-    // Build read-only provider (no wallet, for queries only)
-    let read_provider = std::sync::Arc::new(
-        rpc_config
-            .build_read_only_provider_from_config()
-            .unwrap_or_else(|e| panic!("Failed to build read-only RPC provider: {e}")),
-    );
-
-    // Parse the measurement signer private key. This signer ONLY signs EIP-712
-    // digests for ECDSA beacon updates — it never holds or sends funds. All
-    // on-chain sends (gas + guest funding transfers) go through the KMS-capable
-    // pool wallets configured below.
-    let private_key = env::var("PRIVATE_KEY").expect("PRIVATE_KEY environment variable not set");
-
-    // Get measurement signer address
-    let signer_address = services::rpc::RpcConfig::get_wallet_address(&private_key)
-        .expect("Failed to get measurement signer address");
-
-    // Parse the private key into a signer for ECDSA operations
-    let signer = private_key
-        .parse::<PrivateKeySigner>()
-        .expect("Failed to parse private key into signer")
-        .with_chain_id(Some(chain_id));
-
-    // Log measurement signer configuration. No balance check here by design: this
-    // signer holds no funds — the pool wallets carry the float for gas and guest
-    // funding transfers.
-    tracing::info!("Measurement signer configured (EIP-712 signing only, holds no funds):");
-    tracing::info!("  - Address: {:?}", signer_address);
-    tracing::info!("  - Chain ID: {:?}", chain_id);
-    tracing::info!("  - ENV: {}", env_type);
-
+/// Build the gas-payer wallet pool from KMS keys or WALLET_PRIVATE_KEYS, start its
+/// balance tracker and register its wallets in Redis. Returns the manager and the
+/// REDIS_URL it connected to.
+#[cfg(feature = "wallet-pool")]
+async fn init_wallet_pool(
+    chain_id: u64,
+    read_provider: std::sync::Arc<ReadOnlyProvider>,
+    usdc_address: Address,
+    multicall3_address: Option<Address>,
+) -> Result<(WalletManager, String), ConfigError> {
     // Build the gas-payer pool signers, in precedence order:
     //   1. WALLET_KMS_KEY_IDS - explicit comma-separated KMS key ids / aliases / ARNs.
     //   2. WALLET_KMS_ALIAS_PREFIX - discover pool keys by KMS alias prefix
@@ -532,7 +645,9 @@ pub async fn create_rocket() -> Rocket<Build> {
             // Fail fast on a present-but-blank value ("", ","), which would
             // otherwise boot the service with an empty wallet pool.
             if ids.is_empty() {
-                panic!("WALLET_KMS_KEY_IDS is set but contains no usable KMS key ids");
+                return Err(ConfigError::new(
+                    "WALLET_KMS_KEY_IDS is set but contains no usable KMS key ids",
+                ));
             }
             (ids, "WALLET_KMS_KEY_IDS")
         } else {
@@ -540,11 +655,15 @@ pub async fn create_rocket() -> Rocket<Build> {
             let prefix = prefix.trim();
             // A blank prefix would starts_with-match EVERY alias in the account.
             if prefix.is_empty() {
-                panic!("WALLET_KMS_ALIAS_PREFIX is set but blank");
+                return Err(ConfigError::new("WALLET_KMS_ALIAS_PREFIX is set but blank"));
             }
-            let ids = discover_wallet_aliases(&kms_client, prefix).await;
+            let ids = discover_wallet_aliases(&kms_client, prefix)
+                .await
+                .map_err(ConfigError::new)?;
             if ids.is_empty() {
-                panic!("WALLET_KMS_ALIAS_PREFIX '{prefix}' matched no KMS aliases");
+                return Err(ConfigError::new(format!(
+                    "WALLET_KMS_ALIAS_PREFIX '{prefix}' matched no KMS aliases"
+                )));
             }
             (ids, "WALLET_KMS_ALIAS_PREFIX")
         };
@@ -553,9 +672,11 @@ pub async fn create_rocket() -> Rocket<Build> {
         for id in &ids {
             let signer = AwsSigner::new(kms_client.clone(), id.clone(), Some(chain_id))
                 .await
-                .unwrap_or_else(|e| {
-                    panic!("Failed to build AwsSigner for {source} entry '{id}': {e}")
-                });
+                .map_err(|e| {
+                    ConfigError::new(format!(
+                        "Failed to build AwsSigner for {source} entry '{id}': {e}"
+                    ))
+                })?;
             tracing::info!("Pool wallet {} <- {id} (KMS)", signer.address());
             signers.push(PoolSigner::Kms(signer));
         }
@@ -565,22 +686,25 @@ pub async fn create_rocket() -> Rocket<Build> {
         );
         signers
     } else {
-        let wallet_keys_str = env::var("WALLET_PRIVATE_KEYS").expect(
-            "One of WALLET_KMS_KEY_IDS, WALLET_KMS_ALIAS_PREFIX, or WALLET_PRIVATE_KEYS must be set for the wallet pool",
-        );
-        let signers: Vec<PoolSigner> = wallet_keys_str
-            .split(',')
-            .map(|k| {
-                PoolSigner::Local(
-                    k.trim()
-                        .parse::<PrivateKeySigner>()
-                        .unwrap_or_else(|e| {
-                            panic!("Invalid private key in WALLET_PRIVATE_KEYS: {e}")
-                        })
-                        .with_chain_id(Some(chain_id)),
-                )
-            })
-            .collect();
+        let wallet_keys_str = env::var("WALLET_PRIVATE_KEYS").map_err(|_| {
+            ConfigError::new(
+                "One of WALLET_KMS_KEY_IDS, WALLET_KMS_ALIAS_PREFIX, or WALLET_PRIVATE_KEYS must be set for the wallet pool",
+            )
+        })?;
+        // Report every bad entry by index; never echo key material.
+        let mut signers = Vec::new();
+        let mut problems = Vec::new();
+        for (i, key) in wallet_keys_str.split(',').enumerate() {
+            match key.trim().parse::<PrivateKeySigner>() {
+                Ok(signer) => signers.push(PoolSigner::Local(signer.with_chain_id(Some(chain_id)))),
+                Err(e) => problems.push(format!(
+                    "Invalid private key in WALLET_PRIVATE_KEYS[{i}]: {e}"
+                )),
+            }
+        }
+        if !problems.is_empty() {
+            return Err(ConfigError { problems });
+        }
         tracing::info!(
             "Loaded {} wallet signers from WALLET_PRIVATE_KEYS (local)",
             signers.len()
@@ -592,9 +716,11 @@ pub async fn create_rocket() -> Rocket<Build> {
     let pool_addresses: Vec<Address> = pool_signers.iter().map(PoolSigner::address).collect();
 
     // Initialize WalletManager (REQUIRED for contract operations)
-    let mut wallet_config = WalletManagerConfig::from_env().unwrap_or_else(|e| {
-        panic!("WalletManager configuration is required: {e}. Required env vars: REDIS_URL")
-    });
+    let mut wallet_config = WalletManagerConfig::from_env().map_err(|e| {
+        ConfigError::new(format!(
+            "WalletManager configuration is required: {e}. Required env vars: REDIS_URL"
+        ))
+    })?;
     let redis_url = wallet_config.redis_url.clone();
 
     // Set chain_id from the already-determined chain_id
@@ -602,9 +728,11 @@ pub async fn create_rocket() -> Rocket<Build> {
 
     let mut wallet_manager = WalletManager::new(wallet_config, pool_signers)
         .await
-        .unwrap_or_else(|e| {
-            panic!("WalletManager failed to initialize: {e}. Check Redis connectivity.")
-        });
+        .map_err(|e| {
+            ConfigError::new(format!(
+                "WalletManager failed to initialize: {e}. Check Redis connectivity."
+            ))
+        })?;
 
     tracing::info!("WalletManager initialized for contract operations");
 
@@ -662,33 +790,510 @@ pub async fn create_rocket() -> Rocket<Build> {
         }
     }
 
-    // Load admin token
-    let admin_token = env::var("BEACONATOR_ADMIN_TOKEN")
-        .expect("BEACONATOR_ADMIN_TOKEN environment variable not set");
-
-    // Load IdentityBeacon bytecode for on-chain deployment
-    let identity_beacon_bytecode = {
-        let bytecode_hex = std::fs::read_to_string("abis/IdentityBeacon.bytecode")
-            .expect("Failed to read abis/IdentityBeacon.bytecode");
-        let bytecode_hex = bytecode_hex
-            .trim()
-            .strip_prefix("0x")
-            .unwrap_or(bytecode_hex.trim());
-        let bytes =
-            hex::decode(bytecode_hex).expect("Failed to decode IdentityBeacon bytecode hex");
-        Bytes::from(bytes)
+    Ok((wallet_manager, redis_url))
+}
+
+/// Single-signer wallet for builds without the `wallet-pool` feature: the PRIVATE_KEY
+/// wallet pays gas for every transaction, so it must be funded. Redis (REDIS_URL) is
+/// still required for the registries and shared rate limits.
+#[cfg(not(feature = "wallet-pool"))]
+fn init_single_wallet(signer: &PrivateKeySigner) -> Result<(WalletManager, String), ConfigError> {
+    let redis_url = env::var("REDIS_URL").map_err(|_| {
+        ConfigError::new("REDIS_URL environment variable is required (e.g. redis://host:6379)")
+    })?;
+    tracing::warn!(
+        "Built without wallet-pool: all transactions are sent from PRIVATE_KEY wallet {}",
+        signer.address()
+    );
+    Ok((WalletManager::new(signer.clone()), redis_url))
+}
+
+/// Check the chain id each RPC endpoint reports against the one `ENV` implies (or an
+/// `ALLOWED_CHAIN_IDS` entry) and return the chain id to run on. The primary endpoint
+/// must answer; an unreachable fallback is only logged, as it is for reads. Each read is
+/// retried per `policy`.
+pub async fn verify_rpc_chain_id(
+    endpoints: &services::rpc::RpcEndpoints,
+    policy: &services::rpc::ReadRetryPolicy,
+    env_type: &str,
+    expected: u64,
+    allowed: &[u64],
+) -> Result<u64, ConfigError> {
+    use alloy::providers::Provider;
+
+    let mut chain_id = None;
+    for index in 0..endpoints.len() {
+        let url = routes::info::redact_url(endpoints.url(index));
+        let provider = &endpoints.provider(index);
+        let reported = match services::rpc::retry_read(policy, "eth_chainId", move || async move {
+            provider.get_chain_id().await
+        })
+        .await
+        {
+            Ok(reported) => reported,
+            Err(e) if index > 0 => {
+                tracing::warn!("Could not read chain id from fallback RPC {url}: {e}");
+                continue;
+            }
+            Err(e) => {
+                return Err(ConfigError::new(format!(
+                    "Failed to read chain id from RPC {url}: {e}"
+                )));
+            }
+        };
+        let resolved = resolve_chain_id(env_type, expected, allowed, reported)
+            .map_err(|e| ConfigError::new(format!("{url}: {e}")))?;
+        match chain_id {
+            None => chain_id = Some(resolved),
+            Some(primary) if primary != resolved => {
+                return Err(ConfigError::new(format!(
+                    "{url} reports chain id {resolved}, but the primary RPC reports {primary}"
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+    if let Some(id) = chain_id
+        && id != expected
+    {
+        tracing::warn!(
+            "Running on chain id {id} from {ALLOWED_CHAIN_IDS_ENV} (ENV={env_type} implies {expected})"
+        );
+    }
+    chain_id.ok_or_else(|| ConfigError::new("No RPC endpoint configured"))
+}
+
+/// Read the contract addresses from `*_ADDRESS` variables, falling back to the reader's
+/// `CONTRACTS_FILE` entries. The IdentityBeacon bytecode and Safe settings are left empty
+/// for the caller to fill in.
+fn read_contract_addresses(config: &mut ConfigReader) -> ContractAddresses {
+    let perpcity_registry = config.address("PERPCITY_REGISTRY_ADDRESS");
+
+    // PerpFactory deploys per-market `Perp` contracts. v0.1.0 architecture.
+    let perp_factory = config.address("PERP_FACTORY_ADDRESS");
+
+    // Module addresses for the v0.1.0 perp Modules struct. All required at startup so
+    // /deploy_perp_for_beacon never has to ask the caller for them.
+    let fees_module = config.address("FEES_MODULE_ADDRESS");
+    let funding_module = config.address("FUNDING_MODULE_ADDRESS");
+    let margin_ratios_module = config.address("MARGIN_RATIOS_MODULE_ADDRESS");
+    let price_impact_module = config.address("PRICE_IMPACT_MODULE_ADDRESS");
+    let pricing_module = config.address("PRICING_MODULE_ADDRESS");
+
+    // Optional governance / diagnostic addresses — not on the deploy path.
+    let protocol_fee_manager = config.optional_address("PROTOCOL_FEE_MANAGER_ADDRESS");
+    let module_registry = config.optional_address("MODULE_REGISTRY_ADDRESS");
+
+    let usdc = config.address("USDC_ADDRESS");
+
+    // Optional multicall3 address for batch operations
+    let multicall3 = config.optional_address("MULTICALL3_ADDRESS");
+
+    if let Some(multicall_addr) = multicall3 {
+        tracing::info!("Multicall3 address configured: {:?}", multicall_addr);
+    } else {
+        tracing::warn!(
+            "MULTICALL3_ADDRESS not set - batch operations disabled and the wallet \
+             balance sweep will use per-wallet reads"
+        );
+    }
+
+    // Load ECDSA verifier factory address
+    let ecdsa_verifier_factory = config.address("ECDSA_VERIFIER_FACTORY_ADDRESS");
+
+    tracing::info!(
+        "ECDSA verifier factory address: {:?}",
+        ecdsa_verifier_factory
+    );
+
+    ContractAddresses {
+        perpcity_registry,
+        perp_factory,
+        usdc,
+        ecdsa_verifier_factory,
+        multicall3,
+        identity_beacon_bytecode: Bytes::new(),
+        safe: None,
+        fees_module,
+        funding_module,
+        margin_ratios_module,
+        price_impact_module,
+        pricing_module,
+        protocol_fee_manager,
+        module_registry,
+    }
+}
+
+/// The Safe multisig settings (`SAFE_ADDRESS`, `SAFE_TX_SERVICE_URL`), if a Safe is
+/// configured. An invalid address is logged and leaves the Safe off.
+fn read_safe_config(contracts: &ContractRegistry, chain_id: u64) -> Option<SafeConfig> {
+    let address = match contracts.resolve("SAFE_ADDRESS")? {
+        Ok(addr) => addr,
+        Err(e) => {
+            tracing::warn!("{e}");
+            return None;
+        }
     };
+    let tx_service_url = env::var("SAFE_TX_SERVICE_URL")
+        .ok()
+        .or_else(|| services::safe::SafeTransactionService::default_url_for_chain(chain_id));
+    if let Some(ref url) = tx_service_url {
+        tracing::info!("Safe multisig configured:");
+        tracing::info!("  - Safe address: {:?}", address);
+        tracing::info!("  - TX Service URL: {}", url);
+    }
+    Some(SafeConfig {
+        address,
+        tx_service_url,
+    })
+}
+
+/// Re-read the contract addresses (`*_ADDRESS` variables and `CONTRACTS_FILE`) and swap
+/// them into `state` for `POST /admin/reload_config`.
+///
+/// The new set is validated as at startup, and `CONTRACTS_FILE` must be for the chain the
+/// server runs on; on any problem the current addresses stay in place. Operations already
+/// running keep the snapshot they started with. Addresses captured at startup by the
+/// wallet pool, the touch worker and the beacon type registry are not reloaded.
+pub fn reload_contract_addresses(
+    state: &AppState,
+) -> Result<std::sync::Arc<ContractAddresses>, ConfigError> {
+    let mut config = ConfigReader::new();
+    let contracts = config
+        .check(ContractRegistry::from_env())
+        .unwrap_or_default();
+    config.check(contracts.check_chain_id(state.provider.chain_id));
+    config.set_contracts(contracts.clone());
+    let addresses = read_contract_addresses(&mut config);
+    config.finish()?;
+
+    let current = state.contracts.load();
+    state.contracts.store(ContractAddresses {
+        identity_beacon_bytecode: current.identity_beacon_bytecode.clone(),
+        safe: read_safe_config(&contracts, state.provider.chain_id),
+        ..addresses
+    });
+    Ok(state.contracts.load())
+}
+
+/// Build the server from the environment. A missing or invalid setting, or a startup
+/// dependency (Redis, KMS) that cannot be reached, is returned as a [`ConfigError`]
+/// rather than a panic.
+pub async fn create_rocket() -> Result<Rocket<Build>, ConfigError> {
+    // Load and cache environment variables
+    dotenvy::dotenv().ok();
+
+    // Verbose pre-flight audit of every env var the-beaconator reads. Runs BEFORE any
+    // parse attempt so the operator sees every problem in one log dump, including shape
+    // warnings that don't stop startup. Secrets are never logged in plaintext (only
+    // lengths + whitespace warnings). See `audit_environment` above.
+    audit_environment();
+
+    // BEACONATOR_MODE=mock serves canned results offline for front-end development.
+    if services::mock::mock_mode(
+        env::var(services::mock::BEACONATOR_MODE_ENV)
+            .ok()
+            .as_deref(),
+    )
+    .map_err(ConfigError::new)?
+    {
+        return create_mock_rocket();
+    }
+
+    // Read every setting up front, recording each missing or invalid var so a
+    // misconfigured deploy reports them all at once rather than panicking on the first.
+    // Nothing here touches the network; startup stops at `config.finish()` on any problem.
+    let mut config = ConfigReader::new();
+
+    // Optional CONTRACTS_FILE: contract addresses by name, used for any *_ADDRESS var
+    // that is unset (src/config.rs ContractRegistry).
+    let contracts = config
+        .check(ContractRegistry::from_env())
+        .unwrap_or_default();
+    if !contracts.is_empty() {
+        tracing::info!(
+            "Loaded {} contract address(es) from {CONTRACTS_FILE_ENV}",
+            contracts.len()
+        );
+    }
+    config.set_contracts(contracts.clone());
+
+    // Load RPC configuration from environment
+    let rpc_config = config.check(
+        services::rpc::RpcConfig::from_env()
+            .map_err(|e| format!("Failed to load RPC configuration: {e}")),
+    );
+
+    let access_token = config.required("BEACONATOR_ACCESS_TOKEN");
+
+    // Optional scoped tokens; BEACONATOR_ACCESS_TOKEN keeps full access alongside them.
+    let scoped_tokens = match env::var("BEACONATOR_SCOPED_TOKENS_JSON") {
+        Ok(json) if !json.trim().is_empty() => config
+            .check(
+                AuthConfig::parse_scoped_tokens(&json)
+                    .map_err(|e| format!("Invalid BEACONATOR_SCOPED_TOKENS_JSON: {e}")),
+            )
+            .unwrap_or_default(),
+        _ => std::collections::HashMap::new(),
+    };
+    if !scoped_tokens.is_empty() {
+        tracing::info!("Loaded {} scoped access token(s)", scoped_tokens.len());
+    }
+
+    // Optional HMAC request signing, accepted alongside the access tokens.
+    let request_signing = match env::var("BEACONATOR_SIGNING_SECRET") {
+        Ok(secret) if !secret.trim().is_empty() => {
+            if secret.len() < guards::MIN_SIGNING_SECRET_LEN {
+                config.problem(format!(
+                    "BEACONATOR_SIGNING_SECRET must be at least {} bytes",
+                    guards::MIN_SIGNING_SECRET_LEN
+                ));
+            }
+            let max_age_secs = config.parse_or("BEACONATOR_SIGNATURE_MAX_AGE_SECS", 300u64);
+            tracing::info!("Signed requests enabled (max clock skew {max_age_secs}s)");
+            Some(RequestSigning {
+                secret,
+                max_age_secs,
+            })
+        }
+        _ => None,
+    };
+
+    // Load contract addresses
+    let addresses = read_contract_addresses(&mut config);
+
+    // Load optional factory addresses for other beacon types
+    let lbcgbm_factory_address = config.optional_address("LBCGBM_FACTORY_ADDRESS");
+
+    if let Some(addr) = lbcgbm_factory_address {
+        tracing::info!("LBCGBM factory address: {:?}", addr);
+    }
+
+    let weighted_sum_composite_factory_address =
+        config.optional_address("WEIGHTED_SUM_COMPOSITE_FACTORY_ADDRESS");
+
+    if let Some(addr) = weighted_sum_composite_factory_address {
+        tracing::info!("WeightedSumComposite factory address: {:?}", addr);
+    }
+
+    // Per-request funding caps for the active network; mainnet defaults are far lower
+    // and exceeding them needs ALLOW_HIGH_MAINNET_LIMITS (src/config.rs TransferLimits).
+    let transfer_limits = config.transfer_limits(
+        rpc_config
+            .as_ref()
+            .map_or("testnet", |rpc| rpc.env_type.as_str()),
+    );
+    let TransferLimits {
+        usdc_transfer: usdc_transfer_limit,
+        eth_transfer: eth_transfer_limit,
+        usdc_bonus: usdc_bonus_limit,
+    } = transfer_limits;
+
+    // Post-transfer ETH reserve for guest funding. Default 0.02 ETH — above
+    // the 0.01 ETH BeaconatorWalletGasLow paging threshold, so the faucet
+    // refuses before beacon gas is at risk.
+    let faucet_reserve_eth_wei =
+        config.parse_or("FAUCET_RESERVE_ETH_WEI", 20_000_000_000_000_000u128);
+
+    // Default /sweep_wallet destination. Validated here so a typo fails at startup
+    // rather than mid-incident, when the sweep is needed.
+    let cold_wallet_address =
+        env::var("COLD_WALLET_ADDRESS")
+            .ok()
+            .and_then(|raw| match Address::from_str(raw.trim()) {
+                Ok(addr) if addr == Address::ZERO => {
+                    config.problem("COLD_WALLET_ADDRESS must not be the zero address");
+                    None
+                }
+                Ok(addr) => Some(addr),
+                Err(e) => {
+                    config.problem(format!("COLD_WALLET_ADDRESS is not a valid address: {e}"));
+                    None
+                }
+            });
+    if let Some(addr) = cold_wallet_address {
+        tracing::info!("Cold wallet (default sweep destination): {:?}", addr);
+    }
+
+    // Get environment configuration and chain ID
+    let chain_id = rpc_config
+        .as_ref()
+        .and_then(|rpc| config.check(chain_id_for_env(&rpc.env_type)))
+        .unwrap_or_default();
+
+    // Chain ids besides ENV's that the RPC may report (custom local chains); the RPC
+    // itself is checked once the providers are built.
+    let allowed_chain_ids = env::var(ALLOWED_CHAIN_IDS_ENV)
+        .ok()
+        .and_then(|raw| config.check(parse_chain_ids(&raw)))
+        .unwrap_or_default();
+    if let Some(rpc) = &rpc_config {
+        config.check(check_allowed_chain_ids(&rpc.env_type, &allowed_chain_ids));
+    }
+
+    // Parse the measurement signer private key. This signer ONLY signs EIP-712
+    // digests for ECDSA beacon updates — it never holds or sends funds. All
+    // on-chain sends (gas + guest funding transfers) go through the KMS-capable
+    // pool wallets configured below.
+    let private_key = config.required("PRIVATE_KEY");
+    let signer = if private_key.is_empty() {
+        None
+    } else {
+        config.check(
+            private_key
+                .trim()
+                .parse::<PrivateKeySigner>()
+                .map_err(|e| format!("PRIVATE_KEY does not parse as a private key: {e}")),
+        )
+    };
+
+    let admin_token = config.required("BEACONATOR_ADMIN_TOKEN");
+
+    // The pool needs one signer source; the entries themselves are checked as the
+    // pool is built.
+    #[cfg(feature = "wallet-pool")]
+    if [
+        "WALLET_KMS_KEY_IDS",
+        "WALLET_KMS_ALIAS_PREFIX",
+        "WALLET_PRIVATE_KEYS",
+    ]
+    .iter()
+    .all(|key| env::var(key).is_err())
+    {
+        config.problem(
+            "One of WALLET_KMS_KEY_IDS, WALLET_KMS_ALIAS_PREFIX, or WALLET_PRIVATE_KEYS must be set for the wallet pool",
+        );
+    }
+
+    // IdentityBeacon bytecode for on-chain deployment
+    let identity_beacon_bytecode = config.check(abis::identity_beacon_bytecode());
+
+    // Factory addresses to seed the component factory registry with, when provided.
+    let component_factories = env::var("COMPONENT_FACTORIES_JSON").ok().and_then(|json| {
+        config.check(
+            models::component_factory::parse_component_factories_json(&json)
+                .map_err(|e| format!("COMPONENT_FACTORIES_JSON is invalid: {e}")),
+        )
+    });
+
+    // Deposit defaults and margin bounds; invalid PERP_* overrides stop startup here.
+    let perp_config = config
+        .check(PerpConfig::from_env().map_err(|e| format!("Invalid perp configuration: {e}")));
+
+    // Read retries, receipt polling, confirmation depth and request address checksums,
+    // shared by every request through AppState; invalid values stop startup here.
+    let read_retry = services::rpc::ReadRetryPolicy::from_config(&mut config);
+    let receipt_wait = services::transaction::ReceiptWaitConfig::from_config(&mut config);
+    let confirmations = services::transaction::ConfirmationPolicy::from_config(&mut config);
+    let strict_address_checksum = config.flag("STRICT_ADDRESS_CHECKSUM");
+
+    // Custom-error selector table from the bundled ABIs, so reverts of errors without a
+    // hand-written decoder case still come back named and decoded. Each ABI that fails
+    // to parse is reported by file name.
+    let error_registry = match services::perp::ErrorSelectorRegistry::from_bundled_abis() {
+        Ok(registry) => Some(registry),
+        Err(errors) => {
+            for e in errors {
+                config.problem(e.to_string());
+            }
+            None
+        }
+    };
+
+    // Every `None` here recorded its problem above.
+    let (
+        Some(rpc_config),
+        Some(signer),
+        Some(identity_beacon_bytecode),
+        Some(perp_config),
+        Some(error_registry),
+    ) = (
+        rpc_config,
+        signer,
+        identity_beacon_bytecode,
+        perp_config,
+        error_registry,
+    )
+    else {
+        return Err(config.into_error());
+    };
+    config.finish()?;
+
+    let env_type = &rpc_config.env_type;
+
+    // Get the RPC URL for storing in AppState (used by WalletHandle to build providers)
+    let rpc_url = rpc_config.rpc_url().to_string();
+
+    // Build a read-only provider (no wallet, for queries only) per endpoint. The
+    // shared read provider sends every call through those endpoints, so reads
+    // rotate across RPC_URLS, fail over, and skip endpoints whose breaker is open.
+    let rpc_endpoints = std::sync::Arc::new(
+        services::rpc::RpcEndpoints::from_config(&rpc_config).map_err(|e| {
+            ConfigError::new(format!("Failed to build read-only RPC provider: {e}"))
+        })?,
+    );
+    let read_provider = std::sync::Arc::new(services::rpc::RpcEndpoints::failover_read_provider(
+        &rpc_endpoints,
+    ));
+
+    // Refuse to start against the wrong network: a testnet config pointed at a mainnet
+    // RPC would otherwise sign for one chain and send to another.
+    let chain_id = verify_rpc_chain_id(
+        &rpc_endpoints,
+        &read_retry,
+        env_type,
+        chain_id,
+        &allowed_chain_ids,
+    )
+    .await?;
+    contracts
+        .check_chain_id(chain_id)
+        .map_err(ConfigError::new)?;
+    let signer = signer.with_chain_id(Some(chain_id));
+    let signer_address = signer.address();
+
+    // Log measurement signer configuration. No balance check here by design: this
+    // signer holds no funds — the pool wallets carry the float for gas and guest
+    // funding transfers.
+    tracing::info!("Measurement signer configured (EIP-712 signing only, holds no funds):");
+    tracing::info!("  - Address: {:?}", signer_address);
+    tracing::info!("  - Chain ID: {:?}", chain_id);
+    telemetry::configure_sentry_scope(chain_id, env_type, signer_address);
+    tracing::info!("  - ENV: {}", env_type);
+
+    // Gas payers: the KMS/Redis wallet pool, or the PRIVATE_KEY wallet alone when
+    // built without the `wallet-pool` feature.
+    #[cfg(feature = "wallet-pool")]
+    let (wallet_manager, redis_url) = init_wallet_pool(
+        chain_id,
+        read_provider.clone(),
+        addresses.usdc,
+        addresses.multicall3,
+    )
+    .await?;
+    #[cfg(not(feature = "wallet-pool"))]
+    let (wallet_manager, redis_url) = init_single_wallet(&signer)?;
+
+    if admin_token == access_token || scoped_tokens.contains_key(&admin_token) {
+        // AdminToken and ApiToken are meant to be separate credentials; sharing one
+        // hands every API client the admin routes (sweep, top-up, config snapshot).
+        tracing::warn!(
+            "BEACONATOR_ADMIN_TOKEN is also configured as an access token; \
+             admin routes are reachable with API credentials"
+        );
+    }
+
     tracing::info!(
         "Loaded IdentityBeacon bytecode ({} bytes)",
         identity_beacon_bytecode.len()
     );
 
     // Initialize BeaconTypeRegistry (Redis-backed)
-    let beacon_type_registry = BeaconTypeRegistry::new(&redis_url)
-        .await
-        .unwrap_or_else(|e| {
-            panic!("BeaconTypeRegistry failed to initialize: {e}. Check Redis connectivity.")
-        });
+    let beacon_type_registry = BeaconTypeRegistry::new(&redis_url).await.map_err(|e| {
+        ConfigError::new(format!(
+            "BeaconTypeRegistry failed to initialize: {e}. Check Redis connectivity."
+        ))
+    })?;
 
     // Seed default beacon types from env vars (only writes if slug doesn't exist)
     let now_ts = std::time::SystemTime::now()
@@ -703,9 +1308,9 @@ pub async fn create_rocket() -> Rocket<Build> {
             "ECDSA-verified identity beacon that directly stores signed data as its index"
                 .to_string(),
         ),
-        factory_address: ecdsa_verifier_factory_address,
+        factory_address: addresses.ecdsa_verifier_factory,
         factory_type: FactoryType::Identity,
-        registry_address: Some(perpcity_registry_address),
+        registry_address: Some(addresses.perpcity_registry),
         enabled: true,
         created_at: now_ts,
         updated_at: now_ts,
@@ -721,7 +1326,7 @@ pub async fn create_rocket() -> Rocket<Build> {
             ),
             factory_address: addr,
             factory_type: FactoryType::LBCGBM,
-            registry_address: Some(perpcity_registry_address),
+            registry_address: Some(addresses.perpcity_registry),
             enabled: true,
             created_at: now_ts,
             updated_at: now_ts,
@@ -738,7 +1343,7 @@ pub async fn create_rocket() -> Rocket<Build> {
             ),
             factory_address: addr,
             factory_type: FactoryType::WeightedSumComposite,
-            registry_address: Some(perpcity_registry_address),
+            registry_address: Some(addresses.perpcity_registry),
             enabled: true,
             created_at: now_ts,
             updated_at: now_ts,
@@ -778,19 +1383,20 @@ pub async fn create_rocket() -> Rocket<Build> {
     }
 
     // Initialize ComponentFactoryRegistry (Redis-backed)
-    let component_factory_registry = ComponentFactoryRegistry::new(&redis_url)
-        .await
-        .unwrap_or_else(|e| {
-            panic!("ComponentFactoryRegistry failed to initialize: {e}. Check Redis connectivity.")
-        });
+    let component_factory_registry =
+        ComponentFactoryRegistry::new(&redis_url)
+            .await
+            .map_err(|e| {
+                ConfigError::new(format!(
+                    "ComponentFactoryRegistry failed to initialize: {e}. Check Redis connectivity."
+                ))
+            })?;
 
     // Seed factory addresses from COMPONENT_FACTORIES_JSON when provided (the AWS
     // deployment sets it because ElastiCache is VPC-internal and cannot be seeded by
     // hand the way the Railway Redis was). Existing entries are never overwritten, so
     // re-deploys and registry edits made through Redis stay intact.
-    if let Ok(factories_json) = env::var("COMPONENT_FACTORIES_JSON") {
-        let configs = models::component_factory::parse_component_factories_json(&factories_json)
-            .unwrap_or_else(|e| panic!("COMPONENT_FACTORIES_JSON is invalid: {e}"));
+    if let Some(configs) = component_factories {
         match component_factory_registry.seed_defaults(&configs).await {
             Ok(result) => {
                 tracing::info!(
@@ -800,7 +1406,9 @@ pub async fn create_rocket() -> Rocket<Build> {
                 );
             }
             Err(e) => {
-                panic!("Failed to seed component factories from COMPONENT_FACTORIES_JSON: {e}");
+                return Err(ConfigError::new(format!(
+                    "Failed to seed component factories from COMPONENT_FACTORIES_JSON: {e}"
+                )));
             }
         }
     }
@@ -818,9 +1426,11 @@ pub async fn create_rocket() -> Rocket<Build> {
     }
 
     // Initialize RecipeRegistry and seed standard recipes (Redis-backed)
-    let recipe_registry = RecipeRegistry::new(&redis_url).await.unwrap_or_else(|e| {
-        panic!("RecipeRegistry failed to initialize: {e}. Check Redis connectivity.")
-    });
+    let recipe_registry = RecipeRegistry::new(&redis_url).await.map_err(|e| {
+        ConfigError::new(format!(
+            "RecipeRegistry failed to initialize: {e}. Check Redis connectivity."
+        ))
+    })?;
 
     match recipe_registry.seed_standard_recipes().await {
         Ok(result) => {
@@ -855,30 +1465,64 @@ pub async fn create_rocket() -> Rocket<Build> {
     }
 
     // Optional Safe multisig configuration for beacon registration
-    let safe_config = env::var("SAFE_ADDRESS").ok().and_then(|addr_str| {
-        let address = match Address::from_str(&addr_str) {
-            Ok(addr) => addr,
-            Err(e) => {
-                tracing::warn!("Invalid SAFE_ADDRESS '{}': {}", addr_str, e);
-                return None;
-            }
-        };
-        let tx_service_url = env::var("SAFE_TX_SERVICE_URL")
-            .ok()
-            .or_else(|| services::safe::SafeTransactionService::default_url_for_chain(chain_id));
-        if let Some(ref url) = tx_service_url {
-            tracing::info!("Safe multisig configured:");
-            tracing::info!("  - Safe address: {:?}", address);
-            tracing::info!("  - TX Service URL: {}", url);
-        }
-        Some(SafeConfig {
-            address,
-            tx_service_url,
-        })
-    });
+    let safe_config = read_safe_config(&contracts, chain_id);
+
+    // Redis connection and key prefix for state shared across instances: the wallet
+    // pool's own connection, or a dedicated one when built without the pool.
+    #[cfg(feature = "wallet-pool")]
+    let (redis_conn, redis_prefix) = (
+        wallet_manager.pool().connection().clone(),
+        wallet_manager.pool().keys().prefix().to_string(),
+    );
+    #[cfg(not(feature = "wallet-pool"))]
+    let (redis_conn, redis_prefix) = {
+        let client = redis::Client::open(redis_url.as_str())
+            .map_err(|e| ConfigError::new(format!("Invalid REDIS_URL: {e}")))?;
+        let conn = redis::aio::ConnectionManager::new(client)
+            .await
+            .map_err(|e| ConfigError::new(format!("Failed to connect to Redis: {e}")))?;
+        (
+            conn,
+            crate::models::wallet::PrefixedRedisKeys::default()
+                .prefix()
+                .to_string(),
+        )
+    };
 
     // Share the wallet manager (behind an Arc) between AppState and the touch
     // worker. Wrapped here, after set_balance_tracker/sync, which need &mut/owned.
+    // Per-recipient guest funding caps, shared across instances through Redis.
+    let funding_limiter = std::sync::Arc::new(
+        FundingRateLimiter::from_env().with_redis(redis_conn.clone(), &redis_prefix),
+    );
+
+    // Idempotency replay caches, shared across instances through Redis: batch creation
+    // results by Idempotency-Key, fund_guest_wallet results by request_id.
+    let batch_create_replays = std::sync::Arc::new(
+        services::idempotency::IdempotencyStore::from_env().with_redis(
+            redis_conn.clone(),
+            &redis_prefix,
+            "batch_create_beacon",
+        ),
+    );
+    let funding_replays = std::sync::Arc::new(
+        services::idempotency::IdempotencyStore::from_env().with_redis(
+            redis_conn.clone(),
+            &redis_prefix,
+            "fund_guest_wallet",
+        ),
+    );
+
+    // Recent write operations for GET /transactions, shared across instances through
+    // Redis (no-op unless TRANSACTION_HISTORY_ENABLED).
+    let transaction_history = std::sync::Arc::new(
+        services::history::TransactionHistory::from_env()
+            .with_redis(redis_conn.clone(), &redis_prefix),
+    );
+    if transaction_history.is_enabled() {
+        tracing::info!("Transaction history enabled");
+    }
+
     let wallet_manager = std::sync::Arc::new(wallet_manager);
 
     // Best-effort funding refresh: touch() every perp backed by a beacon after a
@@ -886,8 +1530,35 @@ pub async fn create_rocket() -> Rocket<Build> {
     // off); a no-op dispatcher when disabled or misconfigured.
     let touch = services::touch::spawn_from_env(
         std::sync::Arc::clone(&wallet_manager),
-        rpc_url.clone(),
-        multicall3_address,
+        std::sync::Arc::clone(&rpc_endpoints),
+        addresses.multicall3,
+    );
+
+    // IndexUpdated change feed behind GET /beacon_events. Disabled unless
+    // BEACON_EVENTS_BEACONS is set; cursor and values persist in the pool's Redis.
+    // Streams over RPC_WS_URL when set, otherwise polls the HTTP read provider.
+    let beacon_events = services::beacon::events::spawn_from_env(
+        read_provider.clone(),
+        rpc_config.ws_url.clone(),
+        redis_conn,
+        &redis_prefix,
+        read_retry,
+    )
+    .await;
+
+    let error_registry = std::sync::Arc::new(error_registry);
+    tracing::info!(
+        "Loaded {} custom error selectors from bundled ABIs",
+        error_registry.len()
+    );
+
+    tracing::info!(
+        "Perp config: tick_spacing={}, ticks=[{}, {}], margin=[{}, {}]",
+        perp_config.tick_spacing,
+        perp_config.tick_lower,
+        perp_config.tick_upper,
+        perp_config.min_margin_usdc,
+        perp_config.max_margin_usdc
     );
 
     let app_state = AppState {
@@ -895,6 +1566,13 @@ pub async fn create_rocket() -> Rocket<Build> {
             read_provider,
             rpc_url,
             chain_id,
+            env_type: env_type.to_lowercase(),
+            endpoints: rpc_endpoints,
+            receipts: std::sync::Arc::new(services::transaction::ReceiptCache::default()),
+            gas_prices: std::sync::Arc::new(services::gas_price::GasPriceCache::default()),
+            read_retry,
+            receipt_wait,
+            confirmations,
         },
         wallets: WalletConfig {
             manager: wallet_manager,
@@ -904,26 +1582,20 @@ pub async fn create_rocket() -> Rocket<Build> {
             eth_transfer_limit,
             usdc_bonus_limit,
             faucet_reserve_eth_wei,
+            funding_limiter,
+            funding_replays,
+            cold_wallet_address,
         },
-        contracts: ContractAddresses {
-            perpcity_registry: perpcity_registry_address,
-            perp_factory: perp_factory_address,
-            usdc: usdc_address,
-            ecdsa_verifier_factory: ecdsa_verifier_factory_address,
-            multicall3: multicall3_address,
+        contracts: LiveContracts::new(ContractAddresses {
             identity_beacon_bytecode,
             safe: safe_config,
-            fees_module: fees_module_address,
-            funding_module: funding_module_address,
-            margin_ratios_module: margin_ratios_module_address,
-            price_impact_module: price_impact_module_address,
-            pricing_module: pricing_module_address,
-            protocol_fee_manager: protocol_fee_manager_address,
-            module_registry: module_registry_address,
-        },
+            ..addresses
+        }),
         auth: AuthConfig {
             access_token,
             admin_token,
+            scoped_tokens,
+            request_signing,
         },
         registries: Registries {
             beacon_types: std::sync::Arc::new(beacon_type_registry),
@@ -931,29 +1603,160 @@ pub async fn create_rocket() -> Rocket<Build> {
             recipes: std::sync::Arc::new(recipe_registry),
         },
         touch,
+        beacon_events,
+        idempotency: batch_create_replays,
+        gas_metrics: std::sync::Arc::new(services::metrics::GasMetrics::from_env()),
+        history: transaction_history,
+        perp: perp_config,
+        error_registry,
+        allowances: std::sync::Arc::new(services::perp::AllowanceCache::new()),
+        strict_address_checksum,
+    };
+
+    let (routes, openapi_spec) = api_routes_and_spec();
+    Ok(assemble_rocket(app_state, routes, &openapi_spec))
+}
+
+/// Build the offline mock-mode server (`BEACONATOR_MODE=mock`, see [`services::mock`])
+/// from `ENV` (default `localnet`, never `mainnet`), `RPC_URL` (optional) and the two
+/// access tokens. No chain, Redis or KMS is contacted.
+pub fn create_mock_rocket() -> Result<Rocket<Build>, ConfigError> {
+    let mut config = ConfigReader::new();
+    let env_type = env::var("ENV").unwrap_or_else(|_| "localnet".to_string());
+    let rpc_url = env::var("RPC_URL").unwrap_or_else(|_| services::mock::MOCK_RPC_URL.to_string());
+    let access_token = config.required("BEACONATOR_ACCESS_TOKEN");
+    let admin_token = config.required("BEACONATOR_ADMIN_TOKEN");
+    let app_state = config.check(services::mock::mock_app_state(
+        &env_type,
+        &rpc_url,
+        AuthConfig {
+            access_token,
+            admin_token,
+            scoped_tokens: std::collections::HashMap::new(),
+            request_signing: None,
+        },
+    ));
+    let Some(app_state) = app_state else {
+        return Err(config.into_error());
+    };
+    config.finish()?;
+
+    tracing::warn!(
+        "Running in MOCK mode (ENV={env_type}): write endpoints return canned results and \
+         nothing is sent on-chain"
+    );
+    Ok(mock_rocket(app_state))
+}
+
+/// The mock-mode server for `app_state`: the real routes, with the write endpoints that
+/// have a mock in [`routes::mock`] replaced by it.
+pub fn mock_rocket(app_state: AppState) -> Rocket<Build> {
+    let mocks = rocket::routes![
+        routes::mock::create_beacon,
+        routes::mock::update_beacon,
+        routes::mock::deploy_perp_for_beacon_endpoint,
+    ];
+    let (real, openapi_spec) = api_routes_and_spec();
+    let mut routes: Vec<rocket::Route> = real
+        .into_iter()
+        .filter(|route| {
+            !mocks
+                .iter()
+                .any(|mock| mock.method == route.method && mock.uri.path() == route.uri.path())
+        })
+        .collect();
+    routes.extend(mocks);
+    assemble_rocket(app_state, routes, &openapi_spec)
+}
+
+/// Mount `routes` over `app_state` with the server's fairings, catchers and spec.
+fn assemble_rocket(
+    app_state: AppState,
+    routes: Vec<rocket::Route>,
+    openapi_spec: &rocket_okapi::okapi::openapi3::OpenApi,
+) -> Rocket<Build> {
+    // Serve the OpenAPI spec at /openapi.json
+    let openapi_json =
+        serde_json::to_string(openapi_spec).expect("Failed to serialize OpenAPI spec");
+
+    // Create rocket instance with OpenAPI support. Responses are compressed when the
+    // client sends Accept-Encoding (batch responses list up to 100 beacons).
+    let rocket = rocket::custom(server_figment())
+        .manage(app_state)
+        .attach(fairings::RequestLogger)
+        .attach(fairings::PanicCatcher)
+        .attach(rocket_async_compression::Compression::fairing())
+        .mount("/", fairings::in_request_span(routes))
+        .mount(
+            "/",
+            fairings::in_request_span(rocket::routes![serve_openapi_spec, health, ready]),
+        )
+        .manage(openapi_json)
+        .register("/", api_catchers());
+
+    let rocket = match fairings::Cors::from_env() {
+        Some(cors) => {
+            tracing::info!("CORS enabled for {:?}", cors.origins());
+            rocket.attach(cors)
+        }
+        None => rocket,
     };
 
-    // Configure OpenAPI settings
+    if api_docs_enabled(env::var(API_DOCS_ENABLED_ENV).ok().as_deref()) {
+        tracing::info!("Swagger UI enabled at /docs");
+        rocket.mount("/", fairings::in_request_span(api_docs_routes()))
+    } else {
+        rocket
+    }
+}
+
+/// Every OpenAPI-documented route, plus the spec generated from them (served at
+/// `/openapi.json`).
+pub fn api_routes_and_spec() -> (Vec<rocket::Route>, rocket_okapi::okapi::openapi3::OpenApi) {
     let openapi_settings = OpenApiSettings::new();
 
-    // Generate routes and OpenAPI specification
-    let (routes, openapi_spec) = openapi_get_routes_spec![
+    openapi_get_routes_spec![
         openapi_settings:
         routes::info::index,
+        routes::info::version,
+        routes::info::config_snapshot,
+        routes::info::reload_config,
+        routes::info::gas_metrics,
+        routes::info::transactions,
         routes::beacon::create_beacon,
+        routes::beacon::batch_create_beacon,
         routes::beacon::create_beacon_with_ecdsa,
         routes::beacon::register_beacon,
         routes::beacon::unregister_beacon,
         routes::beacon::update_beacon,
         routes::beacon::batch_update_beacon,
+        routes::beacon::batch_beacon_data,
+        routes::beacon::get_beacon_events,
         routes::beacon::update_beacon_with_ecdsa_adapter,
         routes::beacon::create_lbcgbm_beacon_endpoint,
         routes::beacon::create_weighted_sum_composite_beacon_endpoint,
         routes::perp::deploy_perp_for_beacon_endpoint,
         routes::perp::deposit_liquidity_for_perp_endpoint,
+        routes::perp::deposit_liquidity_by_price_endpoint,
+        routes::perp::batch_deposit_liquidity_for_perps_endpoint,
+        routes::perp::close_maker_position_endpoint,
+        routes::perp::get_perp_endpoint,
+        routes::perp::get_maker_position_endpoint,
+        routes::perp::get_perp_config,
+        routes::perp::preview_create_perp,
+        routes::perp::preview_deposit,
+        routes::estimate::estimate_gas,
+        routes::estimate::gas_price,
         routes::wallet::fund_guest_wallet,
         routes::wallet::fund_bonus_wallet,
         routes::wallet::top_up_pool,
+        routes::wallet::sweep_wallet,
+        routes::wallet::wallet_pool_status,
+        routes::wallet::funding_wallet_status,
+        routes::wallet::force_unlock_wallet,
+        routes::wallet::set_beacon_designation,
+        routes::wallet::drain_wallets,
+        routes::wallet::resume_wallets,
         routes::beacon_type::list_beacon_types,
         routes::beacon_type::get_beacon_type,
         routes::beacon_type::register_beacon_type,
@@ -963,55 +1766,74 @@ pub async fn create_rocket() -> Rocket<Build> {
         routes::recipe::get_recipe,
         routes::recipe::list_component_factories,
         routes::beacon::create_modular_beacon,
-    ];
-
-    // Serve the OpenAPI spec at /openapi.json
-    let openapi_json =
-        serde_json::to_string(&openapi_spec).expect("Failed to serialize OpenAPI spec");
+    ]
+}
 
-    // Create rocket instance with OpenAPI support
-    rocket::build()
-        .manage(app_state)
-        .attach(fairings::RequestLogger)
-        .attach(fairings::PanicCatcher)
-        .mount("/", routes)
-        .mount("/", rocket::routes![serve_openapi_spec, health])
-        .manage(openapi_json)
-        .register("/", catchers![catch_all_errors, catch_panic])
+/// The server's error catchers: a JSON body for 500s, plain text for everything else.
+pub fn api_catchers() -> Vec<rocket::Catcher> {
+    catchers![catch_all_errors, catch_panic]
 }
 
 /// Catches all unhandled errors and returns a formatted error response.
 ///
-/// Emits a structured tracing event (status_code/method/uri fields) so the 5xx
+/// Emits a structured tracing event (status_code/method/uri/request_id fields) so the 5xx
 /// path can be filtered and aggregated in CloudWatch.
 #[catch(default)]
 fn catch_all_errors(status: rocket::http::Status, request: &Request) -> String {
-    tracing::error!(
-        status_code = status.code,
-        method = %request.method(),
-        uri = %request.uri(),
-        "Unhandled error response"
+    let request_id = fairings::RequestId::of(request);
+    sentry::with_scope(
+        |scope| fairings::PanicCatcher::tag_request(scope, request),
+        || {
+            tracing::error!(
+                status_code = status.code,
+                method = %request.method(),
+                uri = %request.uri(),
+                request_id = %request_id,
+                "Unhandled error response"
+            )
+        },
     );
 
     format!(
-        "Error {}: {}",
+        "Error {}: {} (request id: {})",
         status.code,
-        status.reason().unwrap_or("Unknown error")
+        status.reason().unwrap_or("Unknown error"),
+        request_id
     )
 }
 
 /// Catches panic-related internal server errors.
 ///
-/// Structured fields (status_code/method/uri) keep the 500 path aggregatable
-/// in CloudWatch; this catcher is the single logging point for plain 500s.
+/// Structured fields (status_code/method/uri/request_id) keep the 500 path aggregatable
+/// in CloudWatch; this catcher is the single logging point for plain 500s. The client
+/// gets a JSON [`models::ErrorResponse`] with a generic message: panic details reach
+/// Sentry through its panic hook, tagged with the same request, never the response.
 #[catch(500)]
-fn catch_panic(request: &Request) -> String {
-    tracing::error!(
-        status_code = 500,
-        method = %request.method(),
-        uri = %request.uri(),
-        "Internal Server Error (possible panic)"
+fn catch_panic(
+    request: &Request,
+) -> rocket::response::status::Custom<rocket::serde::json::Json<models::ErrorResponse>> {
+    let request_id = fairings::RequestId::of(request);
+    sentry::with_scope(
+        |scope| fairings::PanicCatcher::tag_request(scope, request),
+        || {
+            tracing::error!(
+                status_code = 500,
+                method = %request.method(),
+                uri = %request.uri(),
+                request_id = %request_id,
+                "Internal Server Error (possible panic)"
+            )
+        },
     );
 
-    "Internal Server Error".to_string()
+    rocket::response::status::Custom(
+        rocket::http::Status::InternalServerError,
+        rocket::serde::json::Json(models::ErrorResponse {
+            success: false,
+            data: None,
+            message: "Internal Server Error".to_string(),
+            error_code: "internal_error".to_string(),
+            request_id: request_id.to_string(),
+        }),
+    )
 }
@@ -0,0 +1,76 @@
+//! Tracing subscriber setup.
+//!
+//! `LOG_FORMAT` picks the output format: `pretty` (the default, human-readable),
+//! `compact`, or `json` for collectors that ingest structured lines. Thread ids,
+//! file and line number are kept in every format.
+//!
+//! The subscriber also carries Sentry's tracing layer, which does nothing until
+//! [`crate::telemetry::init_sentry`] starts a client.
+
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, fmt};
+
+/// Default filter when `RUST_LOG` is unset.
+const DEFAULT_FILTER: &str = "info,the_beaconator=info,rocket=warn";
+
+/// Output format of the process-wide tracing subscriber.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// `tracing_subscriber::fmt()` default output, as used before `LOG_FORMAT` existed.
+    #[default]
+    Pretty,
+    /// Single-line abbreviated output.
+    Compact,
+    /// Newline-delimited JSON objects.
+    Json,
+}
+
+impl LogFormat {
+    /// Parse a `LOG_FORMAT` value. Unset, empty or unknown values fall back to
+    /// [`LogFormat::Pretty`] so a typo never silences logging.
+    pub fn parse(value: Option<&str>) -> Self {
+        match value.map(|v| v.trim().to_lowercase()).as_deref() {
+            Some("json") => LogFormat::Json,
+            Some("compact") => LogFormat::Compact,
+            _ => LogFormat::Pretty,
+        }
+    }
+
+    /// Read `LOG_FORMAT` from the environment.
+    pub fn from_env() -> Self {
+        Self::parse(std::env::var("LOG_FORMAT").ok().as_deref())
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogFormat::Pretty => "pretty",
+            LogFormat::Compact => "compact",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+/// Install the global subscriber with `RUST_LOG` filtering and the given format.
+///
+/// Must be called once, before anything logs.
+pub fn init_tracing(format: LogFormat) {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+
+    let layer = fmt::layer()
+        .with_thread_ids(true)
+        .with_file(true)
+        .with_line_number(true);
+    let layer = match format {
+        LogFormat::Pretty => layer.boxed(),
+        LogFormat::Compact => layer.compact().boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(layer)
+        .with(sentry::integrations::tracing::layer())
+        .init();
+}
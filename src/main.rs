@@ -1,7 +1,9 @@
 use the_beaconator::create_rocket;
+use the_beaconator::logging::{LogFormat, init_tracing};
+use the_beaconator::telemetry::init_sentry;
 
-#[rocket::launch]
-async fn rocket() -> _ {
+#[rocket::main]
+async fn main() -> Result<(), Box<rocket::Error>> {
     // Pin the process-level rustls CryptoProvider BEFORE anything opens a TLS
     // connection. The dependency tree carries rustls via both redis
     // (tls-rustls, for ElastiCache rediss://) and reqwest (rustls-tls), and
@@ -10,27 +12,21 @@ async fn rocket() -> _ {
     // already installed, which is the desired end state.
     let _ = rustls::crypto::ring::default_provider().install_default();
 
-    // Initialize logging first with environment variable support
-    use tracing_subscriber::{EnvFilter, fmt};
+    // Initialize logging first. RUST_LOG sets the filter; LOG_FORMAT picks
+    // pretty (default), compact or json output.
+    let log_format = LogFormat::from_env();
+    init_tracing(log_format);
 
-    // Set up logging with RUST_LOG environment variable support
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("info,the_beaconator=info,rocket=warn"));
-
-    fmt()
-        .with_env_filter(filter)
-        .with_thread_ids(true)
-        .with_file(true)
-        .with_line_number(true)
-        .init();
-
-    tracing::info!("Starting the Beaconator server...");
+    tracing::info!(
+        "Starting the Beaconator server (log format: {})...",
+        log_format.as_str()
+    );
 
     // Environment check — presence only, never values. The full audit (with shape /
     // length checks for every var) runs inside `create_rocket()` via `audit_environment`,
     // which emits ERROR lines per problem and a one-line summary.
     tracing::info!("Environment check:");
-    for key in ["RUST_LOG", "ENV"] {
+    for key in ["RUST_LOG", "LOG_FORMAT", "ENV", "SENTRY_DSN"] {
         tracing::info!(
             "  - {key}: {}",
             std::env::var(key).map(|_| "Set").unwrap_or("Not set")
@@ -59,5 +55,20 @@ async fn rocket() -> _ {
         tracing::error!("PANIC at {}: {}", location_str, message);
     }));
 
-    create_rocket().await
+    // After the panic hook so Sentry's hook wraps it. Held until the server stops so
+    // pending events are flushed on shutdown.
+    let sentry = init_sentry();
+
+    // A bad configuration exits non-zero with every problem listed, not a panic.
+    let rocket = match create_rocket().await {
+        Ok(rocket) => rocket,
+        Err(e) => {
+            tracing::error!("{e}");
+            // process::exit skips destructors; flush pending Sentry events first.
+            drop(sentry);
+            std::process::exit(1);
+        }
+    };
+    rocket.launch().await?;
+    Ok(())
 }
@@ -4,29 +4,49 @@ use alloy::{
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
 
 use crate::ReadOnlyProvider;
+use crate::models::perp_config::PerpConfig;
+use crate::models::responses::BatchCreateBeaconResponse;
+use crate::services::beacon::BeaconEventFeed;
 use crate::services::beacon::BeaconTypeRegistry;
 use crate::services::beacon::ComponentFactoryRegistry;
 use crate::services::beacon::RecipeRegistry;
+use crate::services::gas_price::GasPriceCache;
+use crate::services::history::TransactionHistory;
+use crate::services::idempotency::IdempotencyStore;
+use crate::services::metrics::GasMetrics;
+use crate::services::perp::{AllowanceCache, ErrorSelectorRegistry};
+use crate::services::rpc::{ReadRetryPolicy, RpcEndpoints};
 use crate::services::touch::TouchDispatcher;
-use crate::services::wallet::WalletManager;
+use crate::services::transaction::{ConfirmationPolicy, ReceiptCache, ReceiptWaitConfig};
+use crate::services::wallet::{FundingRateLimiter, WalletManager};
 
 /// API endpoint information for documentation
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EndpointInfo {
+    /// HTTP method, e.g. "GET" or "POST".
     pub method: String,
+    /// Route path, e.g. "/create_beacon".
     pub path: String,
+    /// One-line summary of what the endpoint does.
     pub description: String,
+    /// Whether the endpoint needs a bearer token.
     pub requires_auth: bool,
+    /// Implementation status.
     pub status: EndpointStatus,
 }
 
+/// Implementation status of an endpoint listed in [`ApiSummary`].
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum EndpointStatus {
+    /// Implemented and mounted.
     Working,
+    /// Listed for completeness; not mounted yet.
     NotImplemented,
+    /// Still served but scheduled for removal.
     Deprecated,
 }
 
@@ -43,6 +63,41 @@ impl ApiEndpoints {
                 requires_auth: false,
                 status: EndpointStatus::Working,
             },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/version".to_string(),
+                description: "Build and release information".to_string(),
+                requires_auth: false,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/admin/config_snapshot".to_string(),
+                description: "Resolved configuration with secrets redacted (admin)".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/admin/reload_config".to_string(),
+                description: "Reload contract addresses without a restart (admin)".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/metrics/gas".to_string(),
+                description: "Per-operation gas usage histograms".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/transactions".to_string(),
+                description: "Recent write operations, filterable by kind (admin)".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
             EndpointInfo {
                 method: "POST".to_string(),
                 path: "/create_beacon".to_string(),
@@ -50,6 +105,14 @@ impl ApiEndpoints {
                 requires_auth: true,
                 status: EndpointStatus::Working,
             },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/batch_create_beacon".to_string(),
+                description: "Create up to 100 beacons of one type (supports Idempotency-Key)"
+                    .to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
             EndpointInfo {
                 method: "POST".to_string(),
                 path: "/create_beacon_with_ecdsa".to_string(),
@@ -78,10 +141,82 @@ impl ApiEndpoints {
                 requires_auth: true,
                 status: EndpointStatus::Working,
             },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/deposit_liquidity_by_price".to_string(),
+                description: "Deposit liquidity between two prices (snapped to tick spacing)".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/batch_deposit_liquidity_for_perps".to_string(),
+                description: "Deposit liquidity into several perps, reporting each result".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/close_maker_position".to_string(),
+                description: "Close a maker position and return its margin".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/perp/<perp_address>".to_string(),
+                description: "Look up a deployed perpetual (404 if not from PerpFactory)".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/maker_position/<perp_address>/<maker_position_id>".to_string(),
+                description: "Read a maker position's current state (404 if not found)".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/perp_config".to_string(),
+                description: "Deposit margin limits and default ticks".to_string(),
+                requires_auth: false,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/perp_config/preview".to_string(),
+                description: "Show the createPerp arguments a deploy would send".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/preview_deposit".to_string(),
+                description: "Check a deposit's margin and ticks without sending it".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/estimate_gas".to_string(),
+                description: "Estimate gas and fees for create_beacon, deploy_perp or deposit_liquidity"
+                    .to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/gas_price".to_string(),
+                description: "Current EIP-1559 fees, base fee and block utilization".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
             EndpointInfo {
                 method: "POST".to_string(),
                 path: "/update_beacon".to_string(),
-                description: "Update beacon data (supports both ownable and verifiable beacons)".to_string(),
+                description: "Update beacon data (detects verifiable, ECDSA and composite beacons)"
+                    .to_string(),
                 requires_auth: true,
                 status: EndpointStatus::Working,
             },
@@ -92,6 +227,20 @@ impl ApiEndpoints {
                 requires_auth: true,
                 status: EndpointStatus::Working,
             },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/batch_beacon_data".to_string(),
+                description: "Read the current index of multiple beacons".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/beacon_events/<beacon_address>".to_string(),
+                description: "Newest IndexUpdated seen for a tracked beacon".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
             EndpointInfo {
                 method: "POST".to_string(),
                 path: "/fund_guest_wallet".to_string(),
@@ -100,6 +249,58 @@ impl ApiEndpoints {
                 requires_auth: true,
                 status: EndpointStatus::Working,
             },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/sweep_wallet".to_string(),
+                description: "Sweep a pool wallet's USDC + ETH back to a funding wallet (admin)"
+                    .to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/wallet_pool/status".to_string(),
+                description: "Wallet pool status: locks, designations, counts (admin)".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "GET".to_string(),
+                path: "/funding_wallet/status".to_string(),
+                description: "Live ETH/USDC balances and nonces of funding wallets (admin)"
+                    .to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/wallet_pool/force_unlock".to_string(),
+                description: "Release a stuck wallet lock left by a crashed instance (admin)"
+                    .to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/wallet_pool/designation".to_string(),
+                description: "Set or clear a beacon's designated pool wallet (admin)".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/maintenance/drain".to_string(),
+                description: "Stop leasing wallets ahead of maintenance (admin)".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
+            EndpointInfo {
+                method: "POST".to_string(),
+                path: "/maintenance/resume".to_string(),
+                description: "Resume leasing wallets after maintenance (admin)".to_string(),
+                requires_auth: true,
+                status: EndpointStatus::Working,
+            },
             EndpointInfo {
                 method: "GET".to_string(),
                 path: "/beacon_types".to_string(),
@@ -172,12 +373,18 @@ impl ApiEndpoints {
     }
 }
 
+/// Endpoint catalogue returned by `GET /`.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ApiSummary {
+    /// Number of endpoints listed.
     pub total_endpoints: usize,
+    /// Endpoints with status `Working`.
     pub working_endpoints: usize,
+    /// Endpoints with status `NotImplemented`.
     pub not_implemented: usize,
+    /// Endpoints with status `Deprecated`.
     pub deprecated: usize,
+    /// Every endpoint, in documentation order.
     pub endpoints: Vec<EndpointInfo>,
 }
 
@@ -185,19 +392,55 @@ pub struct ApiSummary {
 pub struct AppState {
     pub provider: ProviderConfig,
     pub wallets: WalletConfig,
-    pub contracts: ContractAddresses,
+    /// Contract addresses; `POST /admin/reload_config` can swap them while running.
+    pub contracts: LiveContracts,
     pub auth: AuthConfig,
     pub registries: Registries,
     /// Dispatches beacon addresses to the background touch worker after a
     /// confirmed ECDSA update (no-op when the feature is disabled).
     pub touch: TouchDispatcher,
+    /// Newest `IndexUpdated` per tracked beacon (empty when the feed is disabled).
+    pub beacon_events: Arc<BeaconEventFeed>,
+    /// Replays batch beacon creation results for retried `Idempotency-Key`s.
+    pub idempotency: Arc<IdempotencyStore<BatchCreateBeaconResponse>>,
+    /// Per-operation gas histograms fed from confirmed write receipts.
+    pub gas_metrics: Arc<GasMetrics>,
+    /// Recent write operations for `GET /transactions` (no-op unless
+    /// `TRANSACTION_HISTORY_ENABLED`).
+    pub history: Arc<TransactionHistory>,
+    /// Deposit tick defaults and margin bounds (`PERP_*` overrides).
+    pub perp: PerpConfig,
+    /// Custom-error selectors from the bundled contract ABIs, for revert decoding.
+    pub error_registry: Arc<ErrorSelectorRegistry>,
+    /// USDC allowances of pool wallets towards Perp contracts read on the deposit path,
+    /// dropped once an approve or openMaker is sent.
+    pub allowances: Arc<AllowanceCache>,
+    /// Whether request addresses must carry a valid EIP-55 checksum
+    /// (`STRICT_ADDRESS_CHECKSUM`).
+    pub strict_address_checksum: bool,
 }
 
 #[derive(Clone)]
 pub struct ProviderConfig {
+    /// Read-only provider on the primary endpoint (`rpc_url`).
     pub read_provider: Arc<ReadOnlyProvider>,
     pub rpc_url: String,
     pub chain_id: u64,
+    /// Normalized `ENV` (`mainnet`, `testnet`, or `localnet`).
+    pub env_type: String,
+    /// Primary plus any `RPC_URLS` fallbacks, for reads that should fail over.
+    pub endpoints: Arc<RpcEndpoints>,
+    /// Confirmed receipts seen by the receipt fallbacks, shared across requests.
+    pub receipts: Arc<ReceiptCache>,
+    /// Latest network fees for `GET /gas_price`, refreshed at most every few seconds.
+    pub gas_prices: Arc<GasPriceCache>,
+    /// Retries of idempotent reads (`RPC_MAX_RETRIES`, `RPC_BACKOFF_MS`).
+    pub read_retry: ReadRetryPolicy,
+    /// Receipt polling after a send (`RECEIPT_POLL_*`, `RPC_MAX_TOTAL_ATTEMPTS`); each
+    /// caller picks the initial timeout with [`ReceiptWaitConfig::with_initial_timeout`].
+    pub receipt_wait: ReceiptWaitConfig,
+    /// Depth a receipt must reach before it is final (`CONFIRMATION_BLOCKS`).
+    pub confirmations: ConfirmationPolicy,
 }
 
 #[derive(Clone)]
@@ -222,6 +465,15 @@ pub struct WalletConfig {
     /// BeaconatorWalletGasLow alarm threshold (0.01 ETH) so the faucet
     /// throttles before the on-call gets paged.
     pub faucet_reserve_eth_wei: u128,
+    /// Per-recipient rolling-window caps on total guest funding, so repeated
+    /// `fund_guest_wallet` calls cannot drain the pool into a single address.
+    pub funding_limiter: Arc<FundingRateLimiter>,
+    /// Replays `fund_guest_wallet` results for retried `request_id`s, so a retry
+    /// returns the original transaction hashes instead of funding again.
+    pub funding_replays: Arc<IdempotencyStore<String>>,
+    /// Trusted cold wallet (`COLD_WALLET_ADDRESS`) used as the `/sweep_wallet`
+    /// destination when the request does not name one.
+    pub cold_wallet_address: Option<Address>,
 }
 
 #[derive(Clone)]
@@ -242,6 +494,41 @@ pub struct ContractAddresses {
     pub module_registry: Option<Address>,
 }
 
+/// The current [`ContractAddresses`], swappable at runtime.
+///
+/// Readers take a snapshot with [`LiveContracts::load`] and keep using it for the rest of
+/// the operation, so a reload never changes addresses halfway through a deploy.
+#[derive(Clone)]
+pub struct LiveContracts(Arc<RwLock<Arc<ContractAddresses>>>);
+
+impl LiveContracts {
+    pub fn new(contracts: ContractAddresses) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(contracts))))
+    }
+
+    /// Snapshot of the current addresses.
+    pub fn load(&self) -> Arc<ContractAddresses> {
+        self.0.read().unwrap().clone()
+    }
+
+    /// Replace every address at once, returning the previous set.
+    pub fn store(&self, contracts: ContractAddresses) -> Arc<ContractAddresses> {
+        std::mem::replace(&mut *self.0.write().unwrap(), Arc::new(contracts))
+    }
+
+    /// Change some addresses in place, e.g. to point a test state at a deployed contract.
+    pub fn update(&self, change: impl FnOnce(&mut ContractAddresses)) {
+        let mut current = self.0.write().unwrap();
+        change(Arc::make_mut(&mut current));
+    }
+}
+
+impl From<ContractAddresses> for LiveContracts {
+    fn from(contracts: ContractAddresses) -> Self {
+        Self::new(contracts)
+    }
+}
+
 #[derive(Clone)]
 pub struct SafeConfig {
     pub address: Address,
@@ -250,8 +537,87 @@ pub struct SafeConfig {
 
 #[derive(Clone)]
 pub struct AuthConfig {
+    /// Legacy single access token (BEACONATOR_ACCESS_TOKEN); grants every API scope.
     pub access_token: String,
     pub admin_token: String,
+    /// Additional access tokens restricted to a set of scopes
+    /// (BEACONATOR_SCOPED_TOKENS_JSON). Empty when not configured.
+    pub scoped_tokens: HashMap<String, HashSet<TokenScope>>,
+    /// HMAC request signing (BEACONATOR_SIGNING_SECRET), accepted alongside the tokens.
+    /// `None` when not configured.
+    pub request_signing: Option<RequestSigning>,
+}
+
+/// Shared secret for HMAC-signed requests (see [`crate::guards::sign_request`]). A valid
+/// signature grants the same access as BEACONATOR_ACCESS_TOKEN.
+#[derive(Clone)]
+pub struct RequestSigning {
+    pub secret: String,
+    /// How far `X-Timestamp` may be from the server clock, in seconds, before the request
+    /// is refused as stale (BEACONATOR_SIGNATURE_MAX_AGE_SECS).
+    pub max_age_secs: u64,
+}
+
+/// Operation scope an access token may be granted.
+///
+/// Scoped tokens are configured as a JSON object mapping token to scope list, e.g.
+/// `{"tok-faucet": ["wallet:fund"], "tok-ops": ["read", "beacon:create", "perp:deploy"]}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum TokenScope {
+    /// Read-only lookups (perps, recipes, component factories).
+    #[serde(rename = "read")]
+    Read,
+    /// Beacon creation endpoints.
+    #[serde(rename = "beacon:create")]
+    BeaconCreate,
+    /// Beacon registry writes (register / unregister).
+    #[serde(rename = "beacon:register")]
+    BeaconRegister,
+    /// Beacon data updates.
+    #[serde(rename = "beacon:update")]
+    BeaconUpdate,
+    /// Perp market deployment.
+    #[serde(rename = "perp:deploy")]
+    PerpDeploy,
+    /// Liquidity deposits into perps, and closing the maker positions they open.
+    #[serde(rename = "perp:deposit")]
+    PerpDeposit,
+    /// Guest / bonus wallet funding. `fund` is accepted as an older name.
+    #[serde(rename = "wallet:fund", alias = "fund")]
+    Fund,
+}
+
+impl TokenScope {
+    /// Every scope, as held by BEACONATOR_ACCESS_TOKEN.
+    pub const ALL: [TokenScope; 7] = [
+        TokenScope::Read,
+        TokenScope::BeaconCreate,
+        TokenScope::BeaconRegister,
+        TokenScope::BeaconUpdate,
+        TokenScope::PerpDeploy,
+        TokenScope::PerpDeposit,
+        TokenScope::Fund,
+    ];
+}
+
+impl AuthConfig {
+    /// Parse the BEACONATOR_SCOPED_TOKENS_JSON mapping (token -> list of scopes).
+    ///
+    /// Empty tokens and tokens with no scopes are rejected: both are almost certainly
+    /// configuration mistakes, and an empty token would match a bare `Bearer ` header.
+    pub fn parse_scoped_tokens(json: &str) -> Result<HashMap<String, HashSet<TokenScope>>, String> {
+        let tokens: HashMap<String, HashSet<TokenScope>> =
+            serde_json::from_str(json).map_err(|e| format!("Invalid scoped tokens JSON: {e}"))?;
+        for (token, scopes) in &tokens {
+            if token.trim().is_empty() {
+                return Err("Scoped tokens JSON contains an empty token".to_string());
+            }
+            if scopes.is_empty() {
+                return Err("Scoped tokens JSON contains a token with no scopes".to_string());
+            }
+        }
+        Ok(tokens)
+    }
 }
 
 #[derive(Clone)]
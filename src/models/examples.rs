@@ -0,0 +1,24 @@
+//! Example values shown in the OpenAPI schema (`#[schemars(example = "...")]`).
+//!
+//! Kept in one place so every address and amount field in `/openapi.json` uses the
+//! same, internally consistent values.
+
+/// A checksummed contract / wallet address.
+pub fn address() -> &'static str {
+    "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+}
+
+/// A USDC amount in 6 decimals (50 USDC).
+pub fn usdc_amount() -> &'static str {
+    "50000000"
+}
+
+/// An ETH amount in wei (0.001 ETH).
+pub fn eth_amount_wei() -> &'static str {
+    "1000000000000000"
+}
+
+/// A transaction hash.
+pub fn transaction_hash() -> &'static str {
+    "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b"
+}
@@ -1,6 +1,8 @@
 pub mod app_state;
 pub mod beacon_type;
 pub mod component_factory;
+pub mod examples;
+pub mod perp_config;
 pub mod recipe;
 pub mod requests;
 pub mod responses;
@@ -8,24 +10,40 @@ pub mod wallet;
 
 pub use app_state::{
     ApiEndpoints, ApiSummary, AppState, AuthConfig, ContractAddresses, EndpointInfo,
-    EndpointStatus, ProviderConfig, Registries, SafeConfig, WalletConfig,
+    EndpointStatus, LiveContracts, ProviderConfig, Registries, RequestSigning, SafeConfig,
+    TokenScope, WalletConfig,
 };
 pub use beacon_type::{BeaconTypeConfig, FactoryType, SeedResult};
 pub use component_factory::{ComponentFactoryConfig, ComponentFactoryType};
+pub use perp_config::PerpConfig;
 pub use recipe::{BeaconKind, BeaconRecipe};
 pub use requests::{
-    BatchUpdateBeaconRequest, BeaconCreationParams, BeaconUpdateData, CreateBeaconByTypeRequest,
-    CreateBeaconWithEcdsaRequest, CreateLBCGBMBeaconRequest,
-    CreateWeightedSumCompositeBeaconRequest, DeployPerpForBeaconRequest,
-    DepositLiquidityForPerpRequest, FundBonusWalletRequest, FundGuestWalletRequest,
-    RegisterBeaconRequest, RegisterBeaconTypeRequest, TopUpPoolRequest, UnregisterBeaconRequest,
-    UpdateBeaconRequest, UpdateBeaconTypeRequest, UpdateBeaconWithEcdsaRequest,
+    BatchBeaconDataRequest, BatchCreateBeaconByTypeRequest, BatchDepositLiquidityForPerpsRequest,
+    BatchUpdateBeaconRequest, BeaconCreationParams, BeaconDesignationRequest, BeaconUpdateData,
+    CloseMakerPositionRequest, CreateBeaconByTypeRequest, CreateBeaconWithEcdsaRequest,
+    CreateLBCGBMBeaconRequest, CreateWeightedSumCompositeBeaconRequest, DeployPerpForBeaconRequest,
+    DepositLiquidityByPriceRequest, DepositLiquidityForPerpRequest, EstimateGasRequest,
+    ForceUnlockWalletRequest, FundBonusWalletRequest, FundGuestWalletRequest,
+    PreviewDepositRequest, RegisterBeaconRequest, RegisterBeaconTypeRequest, SweepWalletRequest,
+    TopUpPoolRequest, UnregisterBeaconRequest, UpdateBeaconRequest, UpdateBeaconTypeRequest,
+    UpdateBeaconWithEcdsaRequest,
 };
 pub use requests::{CreateModularBeaconRequest, ModularBeaconParams};
 pub use responses::{
-    ApiResponse, BatchUpdateBeaconResponse, BeaconComponentAddresses, BeaconTypeListResponse,
-    BeaconUpdateResult, CreateBeaconResponse, CreateBeaconWithEcdsaResponse,
-    CreateModularBeaconResponse, DeployPerpForBeaconResponse, DepositLiquidityForPerpResponse,
-    EcdsaUpdateResponse,
+    ApiResponse, BatchBeaconDataResponse, BatchCreateBeaconResponse,
+    BatchDepositLiquidityForPerpsResponse, BatchUpdateBeaconResponse, BeaconComponentAddresses,
+    BeaconDataResult, BeaconDesignationResponse, BeaconEventResponse, BeaconTypeListResponse,
+    BeaconUpdateResult, CloseMakerPositionResponse, ConfigSnapshotResponse, ContractsSnapshot,
+    CreateBeaconResponse, CreateBeaconWithEcdsaResponse, CreateModularBeaconResponse,
+    CreatePerpModules, CreatePerpPreviewResponse, DeployPerpForBeaconResponse,
+    DepositLiquidityByPriceResponse, DepositLiquidityForPerpResponse, EcdsaUpdateResponse,
+    ErrorCategory, ErrorResponse, EstimateGasResponse, FieldError, ForceUnlockWalletResponse,
+    FundingWalletBalance, FundingWalletStatusResponse, GasHistogramBucket, GasMetricsResponse,
+    GasOperationHistogram, GasPriceResponse, HealthResponse, LimitsSnapshot, MaintenanceResponse,
+    MakerInfoResponse, NetworkSnapshot, PerpConfigResponse, PerpInfoResponse,
+    PreviewDepositResponse, REDACTED, ReadProviderHealth, ReloadConfigResponse, RpcEndpointHealth,
+    RuntimeSnapshot, SecretsSnapshot, SweepWalletResponse, TransactionGasEstimate,
+    TransactionHistoryResponse, TransactionRecord, TransactionStatus, TroubleshootingReport,
+    VersionResponse, WalletPoolEntry, WalletPoolStatusResponse,
 };
 pub use wallet::{RedisKeys, WalletInfo, WalletManagerConfig, WalletStatus};
@@ -0,0 +1,171 @@
+//! Operator-tunable defaults and limits for liquidity deposits
+//!
+//! Every field has a built-in default (the values the deposit endpoints have always
+//! used) and a `PERP_*` environment override. [`PerpConfig::from_env`] validates the
+//! result, so a bad combination stops startup instead of surfacing as reverts later.
+
+use alloy::primitives::U256;
+
+use crate::services::perp::liquidity::{calculate_liquidity_from_margin, tick_to_sqrt_price_x96};
+use crate::services::perp::validation::{
+    DEFAULT_TICK_LOWER, DEFAULT_TICK_SPACING, DEFAULT_TICK_UPPER, format_usdc, validate_tick_range,
+};
+
+/// Smallest margin accepted by default: 10 USDC (6 decimals).
+pub const DEFAULT_MIN_MARGIN_USDC: u128 = 10_000_000;
+
+/// Largest margin accepted by default: 1,000,000 USDC (6 decimals).
+pub const DEFAULT_MAX_MARGIN_USDC: u128 = 1_000_000_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerpConfig {
+    /// Tick spacing for deposits that omit one (`PERP_TICK_SPACING`).
+    pub tick_spacing: i32,
+    /// Lower tick for deposits that omit one (`PERP_TICK_LOWER`).
+    pub tick_lower: i32,
+    /// Upper tick for deposits that omit one (`PERP_TICK_UPPER`).
+    pub tick_upper: i32,
+    /// Smallest accepted deposit margin, 6 decimals (`PERP_MIN_MARGIN_USDC`).
+    pub min_margin_usdc: u128,
+    /// Largest accepted deposit margin, 6 decimals (`PERP_MAX_MARGIN_USDC`).
+    pub max_margin_usdc: u128,
+    /// Approve `U256::MAX` instead of the margin when a deposit needs an allowance
+    /// (`PERP_APPROVE_MAX`), so later deposits into the same Perp skip the approval.
+    ///
+    /// Trade-off: the Perp contract can then pull any amount of the wallet's USDC, now
+    /// and for as long as the approval stands. A bug or compromise in that contract is
+    /// no longer bounded by one deposit's margin. Off by default.
+    pub approve_max: bool,
+}
+
+impl Default for PerpConfig {
+    fn default() -> Self {
+        Self {
+            tick_spacing: DEFAULT_TICK_SPACING,
+            tick_lower: DEFAULT_TICK_LOWER,
+            tick_upper: DEFAULT_TICK_UPPER,
+            min_margin_usdc: DEFAULT_MIN_MARGIN_USDC,
+            max_margin_usdc: DEFAULT_MAX_MARGIN_USDC,
+            approve_max: false,
+        }
+    }
+}
+
+impl PerpConfig {
+    /// Load from `PERP_*` environment variables, keeping the default for any that are
+    /// unset. Unparseable values and invalid combinations are errors.
+    pub fn from_env() -> Result<Self, String> {
+        fn parse<T: std::str::FromStr>(key: &str, default: T) -> Result<T, String>
+        where
+            T::Err: std::fmt::Display,
+        {
+            match std::env::var(key) {
+                Ok(v) => v
+                    .trim()
+                    .parse::<T>()
+                    .map_err(|e| format!("Invalid {key} '{v}': {e}")),
+                Err(_) => Ok(default),
+            }
+        }
+
+        let defaults = Self::default();
+        let config = Self {
+            tick_spacing: parse("PERP_TICK_SPACING", defaults.tick_spacing)?,
+            tick_lower: parse("PERP_TICK_LOWER", defaults.tick_lower)?,
+            tick_upper: parse("PERP_TICK_UPPER", defaults.tick_upper)?,
+            min_margin_usdc: parse("PERP_MIN_MARGIN_USDC", defaults.min_margin_usdc)?,
+            max_margin_usdc: parse("PERP_MAX_MARGIN_USDC", defaults.max_margin_usdc)?,
+            approve_max: std::env::var("PERP_APPROVE_MAX")
+                .map(|v| {
+                    matches!(
+                        v.trim().to_ascii_lowercase().as_str(),
+                        "1" | "true" | "yes" | "on"
+                    )
+                })
+                .unwrap_or(defaults.approve_max),
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check the default tick range is usable and the margin bounds are consistent.
+    pub fn validate(&self) -> Result<(), String> {
+        validate_tick_range(self.tick_spacing, self.tick_lower, self.tick_upper)
+            .map_err(|e| format!("Invalid perp tick defaults: {e}"))?;
+        if self.min_margin_usdc == 0 {
+            return Err("PERP_MIN_MARGIN_USDC must be greater than zero".to_string());
+        }
+        if self.min_margin_usdc > self.max_margin_usdc {
+            return Err(format!(
+                "PERP_MIN_MARGIN_USDC ({}) must not exceed PERP_MAX_MARGIN_USDC ({})",
+                self.min_margin_usdc, self.max_margin_usdc
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check a deposit margin lies within `[min_margin_usdc, max_margin_usdc]`.
+    pub fn validate_margin(&self, margin_amount_usdc: u128) -> Result<(), String> {
+        if margin_amount_usdc < self.min_margin_usdc || margin_amount_usdc > self.max_margin_usdc {
+            return Err(format!(
+                "margin {margin_amount_usdc} is outside [{}, {}]",
+                self.min_margin_usdc, self.max_margin_usdc
+            ));
+        }
+        Ok(())
+    }
+
+    /// Run every off-chain check a deposit goes through (tick range, margin bounds, and
+    /// non-zero liquidity) and return the liquidity it would open.
+    pub fn check_deposit(
+        &self,
+        margin_amount_usdc: u128,
+        tick_spacing: i32,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Result<u128, String> {
+        if margin_amount_usdc == 0 {
+            return Err("margin must be greater than zero".to_string());
+        }
+        validate_tick_range(tick_spacing, tick_lower, tick_upper)?;
+        self.validate_margin(margin_amount_usdc)?;
+        calculate_liquidity_from_margin(margin_amount_usdc, tick_spacing, tick_lower, tick_upper)
+    }
+
+    /// Amount a deposit of `margin` should approve given the current `allowance`, or
+    /// `None` when the allowance already covers it. With [`approve_max`](Self::approve_max)
+    /// this is `U256::MAX`, otherwise exactly the margin.
+    pub fn usdc_approval(&self, allowance: U256, margin: U256) -> Option<U256> {
+        if allowance >= margin {
+            None
+        } else if self.approve_max {
+            Some(U256::MAX)
+        } else {
+            Some(margin)
+        }
+    }
+
+    /// Smallest margin (6 decimals) a deposit over the default tick range can use.
+    ///
+    /// The larger of `min_margin_usdc` and the least margin whose liquidity over
+    /// `[tick_lower, tick_upper]` does not round down to zero.
+    pub fn calculate_minimum_margin_usdc(&self) -> Result<u128, String> {
+        let sqrt_lower = tick_to_sqrt_price_x96(self.tick_lower)?;
+        let sqrt_upper = tick_to_sqrt_price_x96(self.tick_upper)?;
+        // liquidity = margin * 2^96 / width >= 1  <=>  margin >= ceil(width / 2^96)
+        let width = sqrt_upper.saturating_sub(sqrt_lower);
+        let q96 = U256::from(1u8) << 96;
+        let for_nonzero_liquidity = width.div_ceil(q96);
+        let for_nonzero_liquidity = u128::try_from(for_nonzero_liquidity)
+            .map_err(|e| format!("Minimum margin out of range: {e}"))?;
+        Ok(self.min_margin_usdc.max(for_nonzero_liquidity))
+    }
+
+    /// [`calculate_minimum_margin_usdc`](Self::calculate_minimum_margin_usdc) in USDC,
+    /// e.g. `"10.000000"`.
+    pub fn minimum_margin_usdc_decimal(&self) -> Result<String, String> {
+        Ok(format_usdc(U256::from(
+            self.calculate_minimum_margin_usdc()?,
+        )))
+    }
+}
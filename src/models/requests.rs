@@ -1,11 +1,21 @@
-use alloy::primitives::Bytes;
+use alloy::primitives::{Bytes, U256};
 use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
 
+use crate::models::perp_config::PerpConfig;
+use crate::models::responses::FieldError;
+use crate::services::address::parse_address;
+use crate::services::perp::liquidity::{
+    calculate_liquidity_bounds, calculate_liquidity_from_margin,
+};
+use crate::services::perp::params::resolve_deposit_ticks;
+use crate::services::perp::validation::validate_tick_range;
+
 /// Update an existing beacon with new data using a zero-knowledge proof
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UpdateBeaconRequest {
     /// Ethereum address of the beacon contract (with or without 0x prefix)
+    #[schemars(example = "crate::models::examples::address")]
     pub beacon_address: String,
     /// Zero-knowledge proof data as hex string (with 0x prefix)
     #[schemars(with = "String")]
@@ -19,6 +29,7 @@ pub struct UpdateBeaconRequest {
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct BeaconUpdateData {
     /// Ethereum address of the beacon contract (with or without 0x prefix)
+    #[schemars(example = "crate::models::examples::address")]
     pub beacon_address: String,
     /// Zero-knowledge proof data as hex string (with 0x prefix)
     #[schemars(with = "String")]
@@ -33,6 +44,17 @@ pub struct BeaconUpdateData {
 pub struct BatchUpdateBeaconRequest {
     /// List of beacon updates to process
     pub updates: Vec<BeaconUpdateData>,
+    /// Simulate each update before batching it and leave out the ones that would revert
+    /// (one extra `eth_call` per update; Multicall3 batches only). Defaults to false.
+    #[serde(default)]
+    pub prevalidate: bool,
+}
+
+/// Read the current index of several beacons
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BatchBeaconDataRequest {
+    /// Beacon contract addresses (with or without 0x prefix)
+    pub beacon_addresses: Vec<String>,
 }
 
 /// Create a beacon by type slug (unified endpoint)
@@ -57,7 +79,7 @@ pub struct BeaconCreationParams {
 pub struct BatchCreateBeaconByTypeRequest {
     /// Beacon type slug
     pub beacon_type: String,
-    /// Number of beacons to create (1-100)
+    /// Number of beacons to create (1 to `BATCH_CREATE_MAX_COUNT`, default 100)
     pub count: u32,
     /// Type-specific creation parameters (shared across all beacons in batch)
     pub params: Option<BeaconCreationParams>,
@@ -69,6 +91,11 @@ pub struct CreateBeaconWithEcdsaRequest {
     /// Initial beacon index value
     #[schemars(with = "String")]
     pub initial_index: u128,
+    /// Existing ECDSA verifier to reuse instead of deploying a new one. Its `SIGNER()`
+    /// must be the beaconator's PRIVATE_KEY signer.
+    #[serde(default)]
+    #[schemars(example = "crate::models::examples::address")]
+    pub verifier_address: Option<String>,
 }
 
 /// Create an LBCGBM standalone beacon via the LBCGBMFactory
@@ -122,8 +149,10 @@ pub struct CreateWeightedSumCompositeBeaconRequest {
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RegisterBeaconRequest {
     /// Ethereum address of the beacon contract
+    #[schemars(example = "crate::models::examples::address")]
     pub beacon_address: String,
     /// Ethereum address of the beacon registry contract
+    #[schemars(example = "crate::models::examples::address")]
     pub registry_address: String,
 }
 
@@ -131,8 +160,10 @@ pub struct RegisterBeaconRequest {
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UnregisterBeaconRequest {
     /// Ethereum address of the beacon contract to remove
+    #[schemars(example = "crate::models::examples::address")]
     pub beacon_address: String,
     /// Optional beacon registry address; defaults to the server-configured registry
+    #[schemars(example = "crate::models::examples::address")]
     pub registry_address: Option<String>,
 }
 
@@ -146,10 +177,12 @@ pub struct RegisterBeaconTypeRequest {
     /// Optional description
     pub description: Option<String>,
     /// Factory contract address (hex with 0x prefix)
+    #[schemars(example = "crate::models::examples::address")]
     pub factory_address: String,
     /// Factory interface type
     pub factory_type: crate::models::beacon_type::FactoryType,
     /// Optional registry address for auto-registration (hex with 0x prefix)
+    #[schemars(example = "crate::models::examples::address")]
     pub registry_address: Option<String>,
     /// Whether this type is enabled (defaults to true)
     pub enabled: Option<bool>,
@@ -163,10 +196,12 @@ pub struct UpdateBeaconTypeRequest {
     /// Updated description
     pub description: Option<String>,
     /// Updated factory contract address
+    #[schemars(example = "crate::models::examples::address")]
     pub factory_address: Option<String>,
     /// Updated factory interface type
     pub factory_type: Option<crate::models::beacon_type::FactoryType>,
     /// Updated registry address
+    #[schemars(example = "crate::models::examples::address")]
     pub registry_address: Option<String>,
     /// Updated enabled status
     pub enabled: Option<bool>,
@@ -177,8 +212,10 @@ pub struct UpdateBeaconTypeRequest {
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct DeployPerpForBeaconRequest {
     /// Ethereum address of the beacon contract (must be registered with BeaconRegistry)
+    #[schemars(example = "crate::models::examples::address")]
     pub beacon_address: String,
     /// Owner of the new Perp contract (governance address). Receives `Ownable` role.
+    #[schemars(example = "crate::models::examples::address")]
     pub owner: String,
     /// Display name for the market (used by ERC721.name()). Example: "Citibike Utilization Perp"
     pub name: String,
@@ -190,6 +227,10 @@ pub struct DeployPerpForBeaconRequest {
     pub ema_window: u32,
     /// Optional 32-byte salt (hex with or without 0x). Server generates a random salt if omitted.
     pub salt: Option<String>,
+    /// Return the troubleshooting report (error category, probable causes, suggested
+    /// actions) as the error body's `data` when the deployment fails.
+    #[serde(default)]
+    pub verbose: bool,
 }
 
 /// Batch deploy perpetual market contracts. One owner/name/symbol/tokenUri/emaWindow per beacon.
@@ -203,28 +244,175 @@ pub struct BatchDeployPerpsForBeaconsRequest {
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DepositLiquidityForPerpRequest {
     /// Address of the per-market `Perp` contract (returned by /deploy_perp_for_beacon).
+    #[schemars(example = "crate::models::examples::address")]
     pub perp_address: String,
     /// USDC margin amount in 6 decimals (e.g., "50000000" for 50 USDC).
     ///
-    /// Margin constraints are enforced by on-chain modules. The MarginRatios module
-    /// defines minimum and maximum allowed margins based on market configuration.
+    /// Must lie within `PERP_MIN_MARGIN_USDC`..=`PERP_MAX_MARGIN_USDC` (10 to 1,000,000 USDC
+    /// by default); the on-chain MarginRatios module may constrain it further.
     ///
-    /// Current liquidity scaling: margin × 500,000 = final liquidity amount
+    /// Liquidity is the amount this margin funds as token1 over the tick range.
+    #[schemars(example = "crate::models::examples::usdc_amount")]
     pub margin_amount_usdc: String,
     /// Optional holder address (defaults to wallet address if not provided)
+    #[schemars(example = "crate::models::examples::address")]
     pub holder: Option<String>,
-    /// Maximum amount of token0 (perp accounting) to deposit, decimal string. Optional.
+    /// Maximum amount of token0 (perp accounting) to deposit, decimal string. Optional:
+    /// defaults to the amount needed at the current pool price plus `DEPOSIT_SLIPPAGE_BPS`
+    /// (1% unless configured). The deposit fails with "Slippage exceeded" past this limit.
     pub max_amt0_in: Option<String>,
-    /// Maximum amount of token1 (USD accounting) to deposit, decimal string. Optional.
+    /// Maximum amount of token1 (USD accounting) to deposit, decimal string. Optional,
+    /// defaulted like `max_amt0_in`.
     pub max_amt1_in: Option<String>,
-    /// Tick spacing for the liquidity position (defaults to 30)
+    /// Tick spacing for the liquidity position (defaults to `PERP_TICK_SPACING`, 30)
     pub tick_spacing: Option<i32>,
-    /// Lower tick bound for the liquidity position (defaults to 24390)
+    /// Lower tick bound for the liquidity position (defaults to `PERP_TICK_LOWER`, 24390).
+    /// Must be aligned to `tick_spacing`, at least MIN_TICK (-887272), and below `tick_upper`.
     pub tick_lower: Option<i32>,
-    /// Upper tick bound for the liquidity position (defaults to 53850)
+    /// Upper tick bound for the liquidity position (defaults to `PERP_TICK_UPPER`, 53850).
+    /// Must be aligned to `tick_spacing` and at most MAX_TICK (887272).
     pub tick_upper: Option<i32>,
 }
 
+/// Close a maker position opened through /deposit_liquidity_for_perp.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CloseMakerPositionRequest {
+    /// Address of the per-market `Perp` contract holding the position.
+    #[schemars(example = "crate::models::examples::address")]
+    pub perp_address: String,
+    /// Maker position id, decimal string (`maker_position_id` of the deposit response).
+    pub maker_position_id: String,
+}
+
+impl DepositLiquidityForPerpRequest {
+    /// Run every off-chain parse and range check against `config` and return all the
+    /// problems found (empty when the request is valid), so a caller fixes them in one
+    /// round trip. Omitted ticks take the `config` defaults. Liquidity bounds are only
+    /// checked once the margin and tick range are themselves valid. `strict_checksum` is
+    /// `AppState::strict_address_checksum`.
+    pub fn validate(&self, config: &PerpConfig, strict_checksum: bool) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+
+        if let Err(e) = parse_address(&self.perp_address, strict_checksum) {
+            errors.push(FieldError::new(
+                "perp_address",
+                format!("not a valid address: {e}"),
+            ));
+        }
+
+        let margin = match self.margin_amount_usdc.parse::<u128>() {
+            Ok(0) => {
+                errors.push(FieldError::new(
+                    "margin_amount_usdc",
+                    "must be greater than zero",
+                ));
+                None
+            }
+            Ok(margin) => match config.validate_margin(margin) {
+                Ok(()) => Some(margin),
+                Err(e) => {
+                    errors.push(FieldError::new("margin_amount_usdc", e));
+                    None
+                }
+            },
+            Err(e) => {
+                errors.push(FieldError::new(
+                    "margin_amount_usdc",
+                    format!("not a USDC amount with 6 decimals: {e}"),
+                ));
+                None
+            }
+        };
+
+        for (field, value) in [
+            ("max_amt0_in", &self.max_amt0_in),
+            ("max_amt1_in", &self.max_amt1_in),
+        ] {
+            if let Some(value) = value
+                && let Err(e) = U256::from_str_radix(value.trim(), 10)
+            {
+                errors.push(FieldError::new(field, format!("not a decimal amount: {e}")));
+            }
+        }
+
+        let (tick_spacing, tick_lower, tick_upper) =
+            resolve_deposit_ticks(config, self.tick_spacing, self.tick_lower, self.tick_upper);
+        let ticks_valid = match calculate_liquidity_bounds(tick_spacing) {
+            Err(e) => {
+                errors.push(FieldError::new("tick_spacing", e));
+                false
+            }
+            Ok(_) => match validate_tick_range(tick_spacing, tick_lower, tick_upper) {
+                Ok(()) => true,
+                Err(e) => {
+                    // validate_tick_range names the offending tick first.
+                    let field = if e.starts_with("tick_upper") {
+                        "tick_upper"
+                    } else {
+                        "tick_lower"
+                    };
+                    errors.push(FieldError::new(field, e));
+                    false
+                }
+            },
+        };
+
+        if let Some(margin) = margin
+            && ticks_valid
+            && let Err(e) =
+                calculate_liquidity_from_margin(margin, tick_spacing, tick_lower, tick_upper)
+        {
+            errors.push(FieldError::new("margin_amount_usdc", e));
+        }
+
+        errors
+    }
+}
+
+/// Deposit liquidity on a per-market Perp between two prices instead of two ticks.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DepositLiquidityByPriceRequest {
+    /// Address of the per-market `Perp` contract (returned by /deploy_perp_for_beacon).
+    #[schemars(example = "crate::models::examples::address")]
+    pub perp_address: String,
+    /// USDC margin amount in 6 decimals (e.g., "50000000" for 50 USDC).
+    #[schemars(example = "crate::models::examples::usdc_amount")]
+    pub margin_amount_usdc: String,
+    /// Lower bound of the position's price range (token1 per token0, e.g. 12.5).
+    pub price_lower: f64,
+    /// Upper bound of the position's price range; must be above `price_lower`.
+    pub price_upper: f64,
+    /// Tick spacing the prices are snapped to (defaults to `PERP_TICK_SPACING`, 30)
+    pub tick_spacing: Option<i32>,
+}
+
+/// Check a deposit's margin and ticks without sending anything.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PreviewDepositRequest {
+    /// USDC margin amount in 6 decimals (e.g., "50000000" for 50 USDC).
+    #[schemars(example = "crate::models::examples::usdc_amount")]
+    pub margin_amount_usdc: String,
+    /// Tick spacing (defaults to `PERP_TICK_SPACING`, 30)
+    pub tick_spacing: Option<i32>,
+    /// Lower tick (defaults to `PERP_TICK_LOWER`, 24390)
+    pub tick_lower: Option<i32>,
+    /// Upper tick (defaults to `PERP_TICK_UPPER`, 53850)
+    pub tick_upper: Option<i32>,
+}
+
+/// Operation to estimate gas for: the same body its endpoint takes, tagged with the
+/// operation name, e.g. `{"operation": "deploy_perp", "params": {...}}`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "operation", content = "params", rename_all = "snake_case")]
+pub enum EstimateGasRequest {
+    /// `/create_beacon_with_ecdsa`: verifier creation (unless reused) + IdentityBeacon deploy
+    CreateBeacon(CreateBeaconWithEcdsaRequest),
+    /// `/deploy_perp_for_beacon`: PerpFactory.createPerp
+    DeployPerp(DeployPerpForBeaconRequest),
+    /// `/deposit_liquidity_for_perp`: USDC approval (if needed) + Perp.openMaker
+    DepositLiquidity(DepositLiquidityForPerpRequest),
+}
+
 /// Batch deposit liquidity for multiple perpetual contracts
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BatchDepositLiquidityForPerpsRequest {
@@ -236,11 +424,19 @@ pub struct BatchDepositLiquidityForPerpsRequest {
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct FundGuestWalletRequest {
     /// Ethereum address of the wallet to fund
+    #[schemars(example = "crate::models::examples::address")]
     pub wallet_address: String,
     /// USDC amount in 6 decimals (e.g., "100000000" for 100 USDC)
+    #[schemars(example = "crate::models::examples::usdc_amount")]
     pub usdc_amount: String,
     /// ETH amount in wei (e.g., "1000000000000000" for 0.001 ETH)
+    #[schemars(example = "crate::models::examples::eth_amount_wei")]
     pub eth_amount: String,
+    /// Optional client-chosen id (1-255 visible ASCII characters) making retries safe: a
+    /// repeat request with the same id and access token within the idempotency TTL returns
+    /// the original result instead of sending new transfers
+    #[serde(default)]
+    pub request_id: Option<String>,
 }
 
 /// Fund a wallet with the new-user bonus USDC.
@@ -251,11 +447,58 @@ pub struct FundGuestWalletRequest {
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct FundBonusWalletRequest {
     /// Smart-account address to fund (counterfactual is fine)
+    #[schemars(example = "crate::models::examples::address")]
     pub wallet_address: String,
     /// USDC amount in 6 decimals (e.g., "50000000" for 50 USDC)
+    #[schemars(example = "crate::models::examples::usdc_amount")]
     pub usdc_amount: String,
 }
 
+/// Sweep a service-controlled wallet's USDC and ETH to a destination (admin).
+///
+/// Backs the `/sweep_wallet` route. Only wallets whose keys the service holds
+/// (the pool signers) can be swept.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SweepWalletRequest {
+    /// Wallet to drain; must be one of the service's pool wallets.
+    #[schemars(example = "crate::models::examples::address")]
+    pub wallet_address: String,
+    /// Address that receives the swept USDC and ETH. Defaults to the configured
+    /// `COLD_WALLET_ADDRESS` when omitted.
+    #[schemars(example = "crate::models::examples::address")]
+    pub destination_address: Option<String>,
+    /// Confirmation token: must repeat `wallet_address` (case-insensitive). Required
+    /// even when the destination comes from configuration.
+    pub confirmation: String,
+}
+
+/// Force-release a stuck wallet lock (admin).
+///
+/// Backs the `/wallet_pool/force_unlock` route.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ForceUnlockWalletRequest {
+    /// Wallet whose distributed lock should be released.
+    #[schemars(example = "crate::models::examples::address")]
+    pub wallet_address: String,
+    /// Owner token currently on the lock (the holding instance id, as reported in
+    /// `lock_holder` by `/wallet_pool/status`). The lock is only released while it
+    /// still carries this token.
+    pub expected_holder: String,
+}
+
+/// Set or clear a beacon's designated pool wallet (admin).
+///
+/// Backs the `/wallet_pool/designation` route.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BeaconDesignationRequest {
+    /// Beacon whose operations should use a fixed wallet.
+    #[schemars(example = "crate::models::examples::address")]
+    pub beacon_address: String,
+    /// Pool wallet to designate. Omit (or null) to clear the beacon's designation.
+    #[schemars(example = "crate::models::examples::address")]
+    pub wallet_address: Option<String>,
+}
+
 /// Top up pool wallets with testnet USDC (admin, testnet-only).
 ///
 /// The deployed testnet USDC has a permissionless `mint`, so the pool can
@@ -265,6 +508,7 @@ pub struct TopUpPoolRequest {
     /// Per-wallet USDC balance target in 6 decimals (e.g., "10000000000" for
     /// 10,000 USDC). Wallets already at or above the target are skipped.
     /// Defaults to 10,000 USDC when omitted.
+    #[schemars(example = "crate::models::examples::usdc_amount")]
     pub usdc_target: Option<String>,
 }
 
@@ -279,6 +523,7 @@ pub struct TopUpPoolRequest {
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UpdateBeaconWithEcdsaRequest {
     /// Ethereum address of the beacon contract (with or without 0x prefix)
+    #[schemars(example = "crate::models::examples::address")]
     pub beacon_address: String,
     /// Measurement value(s) as uint256 decimal string(s).
     /// A single string is treated as a one-element array for backwards compatibility.
@@ -291,7 +536,9 @@ pub struct UpdateBeaconWithEcdsaRequest {
 #[derive(Deserialize, JsonSchema)]
 #[serde(untagged)]
 enum MeasurementInput {
+    /// One uint256 decimal string (standalone beacons).
     Single(String),
+    /// One uint256 decimal string per group member (group beacons).
     Multiple(Vec<String>),
 }
 
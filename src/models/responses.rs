@@ -1,5 +1,6 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Standard API response wrapper
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -12,6 +13,22 @@ pub struct ApiResponse<T> {
     pub message: String,
 }
 
+/// Body the server's 500 catcher returns. Same `success` / `data` / `message` fields as
+/// [`ApiResponse`], plus a machine-readable code and the request id to quote in reports.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ErrorResponse {
+    /// Always `false`
+    pub success: bool,
+    /// Always null
+    pub data: Option<()>,
+    /// Generic description; never includes internal error or panic details
+    pub message: String,
+    /// Machine-readable error code, e.g. `internal_error`
+    pub error_code: String,
+    /// Correlation id, also sent in the `X-Request-Id` header
+    pub request_id: String,
+}
+
 /// Response for `/update_beacon_with_ecdsa_adapter`.
 ///
 /// Same shape as `ApiResponse<String>` plus a `confirmed` flag: `true` when the
@@ -29,17 +46,26 @@ pub struct EcdsaUpdateResponse {
     pub message: String,
     /// true = mined and succeeded; false = sent but unconfirmed at timeout
     pub confirmed: bool,
+    /// Gas used by the update transaction (null while unconfirmed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<u64>,
 }
 
 /// Result of updating a single beacon
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct BeaconUpdateResult {
     /// Address of the beacon that was updated
+    #[schemars(example = "crate::models::examples::address")]
     pub beacon_address: String,
     /// Whether the update succeeded
     pub success: bool,
     /// Transaction hash (if successful)
+    #[schemars(example = "crate::models::examples::transaction_hash")]
     pub transaction_hash: Option<String>,
+    /// Gas used by the transaction that carried this update (if confirmed). Updates batched
+    /// through Multicall3 share one transaction, so each reports the batch's total.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<u64>,
     /// Error message (if failed)
     pub error: Option<String>,
 }
@@ -57,15 +83,48 @@ pub struct BatchUpdateBeaconResponse {
     pub failed_updates: usize,
 }
 
+/// Current index of a single beacon
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BeaconDataResult {
+    /// Address of the beacon that was read
+    #[schemars(example = "crate::models::examples::address")]
+    pub beacon_address: String,
+    /// Whether the read succeeded
+    pub success: bool,
+    /// Beacon index (decimal string, if successful)
+    pub index: Option<String>,
+    /// Error message (if failed)
+    pub error: Option<String>,
+}
+
+/// Response from reading several beacons at once
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BatchBeaconDataResponse {
+    /// Individual results for each beacon, in request order
+    pub results: Vec<BeaconDataResult>,
+    /// Timestamp of the block every index was read at (null when read one by one)
+    pub timestamp: Option<u64>,
+    /// Whether the reads went out as a single Multicall3 call
+    pub via_multicall: bool,
+    /// Total number of beacons requested
+    pub total_requested: usize,
+    /// Number of successful reads
+    pub successful_reads: usize,
+    /// Number of failed reads
+    pub failed_reads: usize,
+}
+
 /// Response from deploying a perpetual market contract via PerpFactory.createPerp.
 /// perpcity-contracts@v0.1.0: each market is its own `Perp` contract with its own pool.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct DeployPerpForBeaconResponse {
     /// Address of the per-market Perp contract (use this for subsequent open / adjust calls).
+    #[schemars(example = "crate::models::examples::address")]
     pub perp_address: String,
     /// 32-byte Uniswap V4 PoolId for this market (hex string with 0x prefix).
     pub pool_id: String,
     /// Address of the PerpFactory contract used to deploy.
+    #[schemars(example = "crate::models::examples::address")]
     pub perp_factory_address: String,
     /// Beacon's initial index value at the moment of deployment.
     pub initial_index: String,
@@ -73,6 +132,8 @@ pub struct DeployPerpForBeaconResponse {
     pub ema_window: u32,
     /// sqrt(price) in Q96 fixed-point format at deployment.
     pub sqrt_price_x96: String,
+    /// Initial mark price as a decimal string (`sqrt_price_x96^2 / 2^192`, up to 18 places).
+    pub mark_price: String,
     /// Initial AMM tick.
     pub tick: i32,
     /// 32-byte salt actually used in PerpFactory.createPerp. When the request omits `salt`,
@@ -81,7 +142,89 @@ pub struct DeployPerpForBeaconResponse {
     /// it so clients can persist + re-use it on explicit retries.
     pub salt: String,
     /// Transaction hash for the createPerp transaction.
+    #[schemars(example = "crate::models::examples::transaction_hash")]
     pub transaction_hash: String,
+    /// Gas used by the createPerp transaction.
+    pub gas_used: u64,
+    /// Blocks mined on top of the createPerp tx before success was returned (0 unless
+    /// `CONFIRMATION_BLOCKS` is set).
+    pub confirmations: u64,
+    /// True when the beacon already had a perp: the fields above describe that perp and
+    /// its original createPerp transaction, no transaction was sent (`gas_used` is 0), and
+    /// `salt` echoes the request.
+    pub already_deployed: bool,
+}
+
+/// Broad cause of a failed write, used to pick troubleshooting hints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum ErrorCategory {
+    #[serde(rename = "Wallet Unavailable")]
+    WalletUnavailable,
+    #[serde(rename = "Insufficient Funds")]
+    InsufficientFunds,
+    #[serde(rename = "Nonce Conflict")]
+    NonceConflict,
+    #[serde(rename = "Contract Execution Reverted")]
+    ContractExecutionReverted,
+    #[serde(rename = "Invalid Contract Address")]
+    InvalidContractAddress,
+    #[serde(rename = "Transaction Timeout")]
+    TransactionTimeout,
+    #[serde(rename = "RPC Connection Error")]
+    RpcConnectionError,
+    #[serde(rename = "Unknown Error")]
+    Unknown,
+}
+
+impl ErrorCategory {
+    /// Human-readable name, as serialized.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::WalletUnavailable => "Wallet Unavailable",
+            Self::InsufficientFunds => "Insufficient Funds",
+            Self::NonceConflict => "Nonce Conflict",
+            Self::ContractExecutionReverted => "Contract Execution Reverted",
+            Self::InvalidContractAddress => "Invalid Contract Address",
+            Self::TransactionTimeout => "Transaction Timeout",
+            Self::RpcConnectionError => "RPC Connection Error",
+            Self::Unknown => "Unknown Error",
+        }
+    }
+}
+
+/// One invalid request field, so every problem can be reported in a single response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FieldError {
+    /// Request field the problem is about, e.g. `margin_amount_usdc`.
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Structured diagnosis of a failed operation. Logged once per failure, and returned as
+/// the error body's `data` when the request sets `verbose`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TroubleshootingReport {
+    /// Operation that failed (e.g. "deploy_perp_for_beacon")
+    pub operation: String,
+    /// Error category the hints below are chosen by
+    pub category: ErrorCategory,
+    /// The underlying error message
+    pub error: String,
+    /// Likely reasons for this category of failure
+    pub probable_causes: Vec<String>,
+    /// What to check or change before retrying
+    pub suggested_actions: Vec<String>,
+    /// Addresses and parameters involved in the operation
+    pub context: BTreeMap<String, String>,
 }
 
 /// Response from batch perpetual deployment
@@ -101,20 +244,26 @@ pub struct BatchDeployPerpsForBeaconsResponse {
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CreateBeaconResponse {
     /// Address of the created beacon
+    #[schemars(example = "crate::models::examples::address")]
     pub beacon_address: String,
     /// Beacon type slug used
     pub beacon_type: String,
     /// Factory address used for creation
+    #[schemars(example = "crate::models::examples::address")]
     pub factory_address: String,
     /// Whether the beacon was registered with a registry
     pub registered: bool,
     /// Safe multisig tx hash if registration was proposed (not yet executed)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub safe_proposal_hash: Option<String>,
+    /// Blocks mined on top of the creation tx before success was returned (0 unless
+    /// `CONFIRMATION_BLOCKS` is set). Omitted by routes that do not wait for depth.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<u64>,
 }
 
 /// Response from batch beacon creation
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BatchCreateBeaconResponse {
     /// Beacon type slug used
     pub beacon_type: String,
@@ -132,8 +281,10 @@ pub struct BatchCreateBeaconResponse {
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CreateBeaconWithEcdsaResponse {
     /// Address of the created beacon
+    #[schemars(example = "crate::models::examples::address")]
     pub beacon_address: String,
     /// Address of the deployed ECDSA verifier adapter
+    #[schemars(example = "crate::models::examples::address")]
     pub verifier_address: String,
     /// Beacon type slug used
     pub beacon_type: String,
@@ -156,10 +307,296 @@ pub struct BeaconTypeListResponse {
 pub struct DepositLiquidityForPerpResponse {
     /// Maker position ID from MakerPositionOpened event
     pub maker_position_id: String,
-    /// USDC approval transaction hash
-    pub approval_transaction_hash: String,
+    /// USDC approval transaction hash; `None` when the wallet's existing allowance
+    /// already covered the margin and no approval was sent
+    #[schemars(example = "crate::models::examples::transaction_hash")]
+    pub approval_transaction_hash: Option<String>,
     /// Liquidity deposit transaction hash
+    #[schemars(example = "crate::models::examples::transaction_hash")]
     pub deposit_transaction_hash: String,
+    /// Gas used by the USDC approval transaction (`None` when it was skipped)
+    pub approval_gas_used: Option<u64>,
+    /// Gas used by the liquidity deposit transaction
+    pub deposit_gas_used: u64,
+    /// Token0 limit sent as `maxAmt0In` (requested, or estimated plus the slippage buffer)
+    pub max_amt0_in: String,
+    /// Token1 limit sent as `maxAmt1In` (requested, or estimated plus the slippage buffer)
+    pub max_amt1_in: String,
+    /// Blocks mined on top of the deposit tx before success was returned (0 unless
+    /// `CONFIRMATION_BLOCKS` is set).
+    pub confirmations: u64,
+}
+
+/// Response from closing a maker position
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CloseMakerPositionResponse {
+    /// The closed maker position id
+    pub maker_position_id: String,
+    /// Pool wallet that held the position and received the payout
+    #[schemars(example = "crate::models::examples::address")]
+    pub wallet_address: String,
+    /// Close (`adjustMaker`) transaction hash
+    #[schemars(example = "crate::models::examples::transaction_hash")]
+    pub transaction_hash: String,
+    /// Gas used by the close transaction
+    pub gas_used: u64,
+    /// Margin recorded on the position before closing, USDC in 6 decimals
+    pub margin_usdc: String,
+    /// USDC the Perp transferred to the wallet, parsed from the receipt's `Transfer` logs
+    pub returned_usdc: String,
+    /// `returned_usdc - margin_usdc`: fees earned, funding and PnL realized by the close
+    /// (negative on a loss)
+    pub realized_pnl_usdc: String,
+    /// Blocks mined on top of the close tx before success was returned (0 unless
+    /// `CONFIRMATION_BLOCKS` is set).
+    pub confirmations: u64,
+}
+
+/// Response from depositing liquidity between two prices
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DepositLiquidityByPriceResponse {
+    /// The deposit itself, as returned by /deposit_liquidity_for_perp
+    pub deposit: DepositLiquidityForPerpResponse,
+    /// Lower tick actually used, after snapping `price_lower` to the tick spacing
+    pub tick_lower: i32,
+    /// Upper tick actually used, after snapping `price_upper` to the tick spacing
+    pub tick_upper: i32,
+    /// Tick spacing the prices were snapped to
+    pub tick_spacing: i32,
+}
+
+/// Deposit limits and defaults, for validating deposits before submitting them
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PerpConfigResponse {
+    /// Smallest accepted margin, USDC with 6 decimals (e.g. "10000000")
+    #[schemars(example = "crate::models::examples::usdc_amount")]
+    pub min_margin_usdc: String,
+    /// Smallest accepted margin in USDC (e.g. "10.000000")
+    pub min_margin_usdc_decimal: String,
+    /// Largest accepted margin, USDC with 6 decimals
+    #[schemars(example = "crate::models::examples::usdc_amount")]
+    pub max_margin_usdc: String,
+    /// Largest accepted margin in USDC
+    pub max_margin_usdc_decimal: String,
+    /// Tick spacing used when a deposit omits `tick_spacing`
+    pub tick_spacing: i32,
+    /// Lower tick used when a deposit omits `tick_lower`
+    pub tick_lower: i32,
+    /// Upper tick used when a deposit omits `tick_upper`
+    pub tick_upper: i32,
+    /// Lowest tick any position may use (MIN_TICK)
+    pub min_tick: i32,
+    /// Highest tick any position may use (MAX_TICK)
+    pub max_tick: i32,
+    /// Whether deposits approve unlimited USDC to the Perp instead of the margin
+    pub approve_max: bool,
+}
+
+/// Server-configured modules of a createPerp call
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreatePerpModules {
+    pub beacon: String,
+    pub fees: String,
+    pub funding: String,
+    pub margin_ratios: String,
+    pub price_impact: String,
+    pub pricing: String,
+}
+
+/// `PerpFactory.createPerp` arguments a deploy would send
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CreatePerpPreviewResponse {
+    /// PerpFactory the call would be sent to
+    #[schemars(example = "crate::models::examples::address")]
+    pub perp_factory_address: String,
+    #[schemars(example = "crate::models::examples::address")]
+    pub owner: String,
+    pub name: String,
+    pub symbol: String,
+    pub token_uri: String,
+    pub modules: CreatePerpModules,
+    /// `emaWindow` as encoded (uint24)
+    pub ema_window: u32,
+    /// Salt the deploy would use, 0x-prefixed 32-byte hex
+    pub salt: String,
+    /// Whether the salt was derived from the inputs because none was given
+    pub salt_derived: bool,
+    /// ABI-encoded createPerp call, the exact transaction input
+    pub calldata: String,
+}
+
+/// Outcome of a deposit preview
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PreviewDepositResponse {
+    /// Whether /deposit_liquidity_for_perp would accept this margin and tick range
+    pub valid: bool,
+    /// Why the deposit would be rejected (`None` when `valid`)
+    pub reason: Option<String>,
+    /// Liquidity the deposit would open (`None` when not `valid`)
+    pub liquidity: Option<String>,
+    /// Smallest accepted margin in USDC (e.g. "10.000000")
+    pub min_margin_usdc_decimal: String,
+    /// Largest accepted margin in USDC
+    pub max_margin_usdc_decimal: String,
+    /// Tick spacing checked (request value or default)
+    pub tick_spacing: i32,
+    /// Lower tick checked (request value or default)
+    pub tick_lower: i32,
+    /// Upper tick checked (request value or default)
+    pub tick_upper: i32,
+}
+
+/// Gas estimate for one transaction of an operation.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionGasEstimate {
+    /// What the transaction does, e.g. "PerpFactory.createPerp"
+    pub description: String,
+    /// `eth_estimateGas` result
+    pub gas: u64,
+}
+
+/// Gas estimate for an operation, built from the same transactions its endpoint sends.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EstimateGasResponse {
+    /// Operation estimated (`create_beacon`, `deploy_perp` or `deposit_liquidity`)
+    pub operation: String,
+    /// Pool wallet the transactions were estimated from
+    #[schemars(example = "crate::models::examples::address")]
+    pub from: String,
+    /// Transactions in the order the endpoint sends them
+    pub transactions: Vec<TransactionGasEstimate>,
+    /// Sum of `transactions[].gas`
+    pub total_gas: u64,
+    /// Current `eth_gasPrice` in wei
+    pub gas_price_wei: String,
+    /// EIP-1559 `max_fee_per_gas` in wei
+    pub max_fee_per_gas_wei: String,
+    /// `total_gas * gas_price_wei`: rough expected fee
+    pub estimated_fee_wei: String,
+    /// `total_gas * max_fee_per_gas_wei`: upper bound on the fee
+    pub max_fee_wei: String,
+}
+
+/// Current network fees, for deciding when to submit.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GasPriceResponse {
+    /// Block the base fee and utilization were read from
+    pub block_number: u64,
+    /// Base fee of that block in wei (null on chains without EIP-1559)
+    pub base_fee_per_gas_wei: Option<String>,
+    /// EIP-1559 `max_fee_per_gas` estimate in wei
+    pub max_fee_per_gas_wei: String,
+    /// EIP-1559 `max_priority_fee_per_gas` estimate in wei
+    pub max_priority_fee_per_gas_wei: String,
+    /// Gas used / gas limit of that block
+    pub gas_used_ratio: f64,
+    /// Whether the block was fuller than the EIP-1559 target (50%), so fees are rising
+    pub congested: bool,
+}
+
+/// On-chain info for a per-market Perp contract deployed by the trusted PerpFactory.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PerpInfoResponse {
+    /// Address of the per-market Perp contract.
+    #[schemars(example = "crate::models::examples::address")]
+    pub perp_address: String,
+    /// Beacon backing this market (from `Perp.modules()`).
+    #[schemars(example = "crate::models::examples::address")]
+    pub beacon_address: String,
+    /// 32-byte Uniswap V4 PoolId for this market (hex string with 0x prefix).
+    pub pool_id: String,
+    /// Market name.
+    pub name: String,
+    /// Market symbol.
+    pub symbol: String,
+    /// Current owner of the Perp contract.
+    pub owner: String,
+    /// Address of the PerpFactory that deployed this market.
+    #[schemars(example = "crate::models::examples::address")]
+    pub perp_factory_address: String,
+}
+
+/// Current state of a maker position, from `Perp.positions`, `Perp.makerDetails` and
+/// `Perp.ownerOf`. X96 fixed-point accumulators are rendered as decimal strings.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MakerInfoResponse {
+    /// Address of the per-market Perp contract.
+    #[schemars(example = "crate::models::examples::address")]
+    pub perp_address: String,
+    /// Maker position id.
+    pub maker_position_id: String,
+    /// Current holder of the position NFT.
+    #[schemars(example = "crate::models::examples::address")]
+    pub owner: String,
+    /// Lower tick of the position's range.
+    pub tick_lower: i32,
+    /// Upper tick of the position's range.
+    pub tick_upper: i32,
+    /// Liquidity still provided by the position.
+    pub liquidity: String,
+    /// Margin held by the position, USDC in 6 decimals.
+    pub margin_usdc: String,
+    /// Perp delta recorded on the position (signed `delta`).
+    pub perp_delta: String,
+    /// Liquidation margin ratio recorded on the position (`liqMarginRatio`, raw uint24).
+    pub liquidation_margin_ratio: u32,
+    /// Backstop margin ratio recorded on the position (raw uint24).
+    pub backstop_margin_ratio: u32,
+    /// Long capacity recorded for the position (`capacity_.long`).
+    pub long_capacity: String,
+    /// Short capacity recorded for the position (`capacity_.short`).
+    pub short_capacity: String,
+    /// Long utilization earnings per unit at entry (decimal, from X96).
+    pub last_long_util_earnings: String,
+    /// Short utilization earnings per unit at entry (decimal, from X96).
+    pub last_short_util_earnings: String,
+    /// Cumulative funding at entry (decimal, from X96).
+    pub last_cuml_funding: String,
+    /// Maker funding accumulated below the range at entry (decimal, from X96).
+    pub funding_below: String,
+    /// Maker funding accumulated within the range at entry (decimal, from X96).
+    pub funding_within: String,
+    /// Within-range funding divided by sqrt price at entry (decimal, from X96).
+    pub funding_div_sqrt_price_within: String,
+}
+
+/// Newest `IndexUpdated` event the change feed has seen for a beacon.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BeaconEventResponse {
+    #[schemars(example = "crate::models::examples::address")]
+    pub beacon_address: String,
+    /// Index emitted by the event (decimal string).
+    pub index: String,
+    /// Block containing the event.
+    pub block_number: u64,
+    /// Transaction that emitted the event.
+    #[schemars(example = "crate::models::examples::transaction_hash")]
+    pub transaction_hash: String,
+    /// Last block the feed has scanned; later updates are not reflected yet.
+    pub scanned_through_block: Option<u64>,
+}
+
+/// Result of sweeping a wallet's USDC and ETH to a destination.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SweepWalletResponse {
+    /// Wallet that was drained.
+    #[schemars(example = "crate::models::examples::address")]
+    pub wallet_address: String,
+    /// Address that received the funds.
+    #[schemars(example = "crate::models::examples::address")]
+    pub destination_address: String,
+    /// USDC transferred, in 6 decimals ("0" when the wallet held none).
+    pub usdc_swept: String,
+    /// USDC transfer transaction hash, if a transfer was sent.
+    #[schemars(example = "crate::models::examples::transaction_hash")]
+    pub usdc_transaction_hash: Option<String>,
+    /// ETH transferred, in wei ("0" when skipped).
+    pub eth_swept_wei: String,
+    /// ETH transfer transaction hash, if a transfer was sent.
+    #[schemars(example = "crate::models::examples::transaction_hash")]
+    pub eth_transaction_hash: Option<String>,
+    /// Why the ETH transfer was skipped (balance at or below the gas cost).
+    pub eth_skipped_reason: Option<String>,
 }
 
 /// Response from batch liquidity deposit operation
@@ -167,11 +604,11 @@ pub struct DepositLiquidityForPerpResponse {
 pub struct BatchDepositLiquidityForPerpsResponse {
     /// Number of successful deposits
     pub deposited_count: u32,
-    /// List of maker position IDs
+    /// Maker position IDs of the successful deposits, in request order
     pub maker_position_ids: Vec<String>,
     /// Number of failed deposits
     pub failed_count: u32,
-    /// Error messages for failed deposits
+    /// One message per failed deposit, naming its 1-based position in the request
     pub errors: Vec<String>,
 }
 
@@ -202,9 +639,11 @@ pub struct BeaconComponentAddresses {
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CreateModularBeaconResponse {
     /// Address of the created beacon
+    #[schemars(example = "crate::models::examples::address")]
     pub beacon_address: String,
     /// Address of the deployed verifier (if applicable)
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(example = "crate::models::examples::address")]
     pub verifier_address: Option<String>,
     /// Recipe slug used for creation
     pub recipe: String,
@@ -216,3 +655,360 @@ pub struct CreateModularBeaconResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub safe_proposal_hash: Option<String>,
 }
+
+/// Placeholder emitted in place of any secret value in a config snapshot.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Fully-resolved runtime configuration with secrets redacted, safe to attach to a
+/// support ticket. Returned by the admin `GET /admin/config_snapshot` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConfigSnapshotResponse {
+    /// Chain and RPC settings.
+    pub network: NetworkSnapshot,
+    /// Configured contract addresses.
+    pub contracts: ContractsSnapshot,
+    /// Funding limits and reserves (base units, as strings).
+    pub limits: LimitsSnapshot,
+    /// Timeouts, retry budgets, and optional feature flags.
+    pub runtime: RuntimeSnapshot,
+    /// Secrets: always `[REDACTED]`, present only to show they are configured.
+    pub secrets: SecretsSnapshot,
+}
+
+/// Contract addresses after the admin `POST /admin/reload_config` endpoint swapped them in.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReloadConfigResponse {
+    /// Fields of `contracts` whose value changed with the reload (e.g. `perp_factory`).
+    pub changed: Vec<String>,
+    /// Contract addresses now in use.
+    pub contracts: ContractsSnapshot,
+}
+
+/// Network section of [`ConfigSnapshotResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NetworkSnapshot {
+    /// Chain ID the server is configured for.
+    pub chain_id: u64,
+    /// RPC URL with any path, query, or credentials redacted (API keys live there).
+    pub rpc_url: String,
+}
+
+/// Contracts section of [`ConfigSnapshotResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ContractsSnapshot {
+    /// PerpCity BeaconRegistry address (`PERPCITY_REGISTRY_ADDRESS`).
+    pub perpcity_registry: String,
+    /// PerpFactory address (`PERP_FACTORY_ADDRESS`).
+    pub perp_factory: String,
+    /// USDC token address (`USDC_ADDRESS`).
+    pub usdc: String,
+    /// ECDSA verifier factory address (`ECDSA_VERIFIER_FACTORY_ADDRESS`).
+    pub ecdsa_verifier_factory: String,
+    /// Multicall3 address, when configured.
+    pub multicall3: Option<String>,
+    /// Fees module passed to `PerpFactory.createPerp`.
+    pub fees_module: String,
+    /// Funding module passed to `PerpFactory.createPerp`.
+    pub funding_module: String,
+    /// MarginRatios module passed to `PerpFactory.createPerp`.
+    pub margin_ratios_module: String,
+    /// PriceImpact module passed to `PerpFactory.createPerp`.
+    pub price_impact_module: String,
+    /// Pricing module passed to `PerpFactory.createPerp`.
+    pub pricing_module: String,
+    /// ProtocolFeeManager address, when configured.
+    pub protocol_fee_manager: Option<String>,
+    /// ModuleRegistry address, when configured.
+    pub module_registry: Option<String>,
+    /// Safe multisig address, when registry writes are proposed via Safe.
+    #[schemars(example = "crate::models::examples::address")]
+    pub safe_address: Option<String>,
+    /// Safe transaction service URL (path and credentials redacted).
+    pub safe_tx_service_url: Option<String>,
+    /// Address of the PRIVATE_KEY measurement signer (public; the key itself is redacted).
+    #[schemars(example = "crate::models::examples::address")]
+    pub signer_address: String,
+    /// Default `/sweep_wallet` destination, when configured.
+    #[schemars(example = "crate::models::examples::address")]
+    pub cold_wallet_address: Option<String>,
+}
+
+/// Limits section of [`ConfigSnapshotResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LimitsSnapshot {
+    /// Max USDC per `/fund_guest_wallet` call (6 decimals).
+    pub usdc_transfer_limit: String,
+    /// Max ETH per `/fund_guest_wallet` call (wei).
+    pub eth_transfer_limit: String,
+    /// Max USDC per `/fund_bonus_wallet` call (6 decimals).
+    pub usdc_bonus_limit: String,
+    /// Minimum ETH a pool wallet must keep after a guest transfer (wei).
+    pub faucet_reserve_eth_wei: String,
+}
+
+/// Runtime section of [`ConfigSnapshotResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RuntimeSnapshot {
+    /// Total primary + fallback attempts per receipt confirmation.
+    pub rpc_max_total_attempts: u32,
+    /// Wallet lock TTL in seconds (null when the wallet manager is a test stub).
+    pub wallet_lock_ttl_secs: Option<u64>,
+    /// Number of gas-payer wallets held by this instance.
+    pub wallet_pool_size: usize,
+    /// Whether the touch-on-update side-loop is running.
+    pub touch_on_update_enabled: bool,
+    /// Whether registry writes are proposed via a Safe instead of sent directly.
+    pub safe_enabled: bool,
+}
+
+/// Secrets section of [`ConfigSnapshotResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SecretsSnapshot {
+    /// Measurement signer key (`PRIVATE_KEY`).
+    pub private_key: String,
+    /// Full-access API token (`BEACONATOR_ACCESS_TOKEN`).
+    pub access_token: String,
+    /// Admin API token (`BEACONATOR_ADMIN_TOKEN`).
+    pub admin_token: String,
+}
+
+/// One cumulative bucket of a gas histogram.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GasHistogramBucket {
+    /// Upper bound in gas units ("+Inf" for the overflow bucket).
+    pub le: String,
+    /// Samples at or below `le`.
+    pub count: u64,
+}
+
+/// Gas histogram for one operation type.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GasOperationHistogram {
+    /// Operation label (e.g. "beacon_update", "perp_deploy").
+    pub operation: String,
+    /// Number of recorded transactions.
+    pub count: u64,
+    /// Total gas used (string: may exceed 2^53).
+    pub sum: String,
+    /// Smallest gas used by a single transaction.
+    pub min: u64,
+    /// Largest gas used by a single transaction.
+    pub max: u64,
+    /// Mean gas used, rounded down.
+    pub mean: u64,
+    /// Cumulative buckets in ascending `le` order, ending with "+Inf".
+    pub buckets: Vec<GasHistogramBucket>,
+}
+
+/// Response for `GET /metrics/gas`: per-operation gas histograms since startup.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GasMetricsResponse {
+    /// Whether gas recording is enabled (`GAS_METRICS_ENABLED`).
+    pub enabled: bool,
+    /// One histogram per operation with at least one sample.
+    pub operations: Vec<GasOperationHistogram>,
+}
+
+/// Response for `GET /health` and `GET /ready`. `/health` always answers 200: RPC
+/// incidents are reported, not treated as the service being down. `/ready` answers 503
+/// while the instance is draining.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HealthResponse {
+    /// "ok", or "degraded" while any RPC endpoint's circuit breaker is not closed.
+    pub status: String,
+    /// Always true: the process is serving requests.
+    pub live: bool,
+    /// False while the instance is draining for maintenance; `/ready` then answers 503
+    /// so load balancers stop routing new work to it.
+    pub ready: bool,
+    /// Whether `POST /maintenance/drain` is in effect (wallet leases are refused).
+    pub draining: bool,
+    /// Circuit breaker state per RPC endpoint.
+    pub rpc_endpoints: Vec<RpcEndpointHealth>,
+    /// Live read through the read-only provider; only with `?rpc=true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_provider: Option<ReadProviderHealth>,
+}
+
+/// `eth_blockNumber` probe of the read-only provider in [`HealthResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReadProviderHealth {
+    /// Whether the probe returned a block number in time.
+    pub ok: bool,
+    /// Block number the endpoint reported.
+    pub block_number: Option<u64>,
+    /// How long the probe took, in milliseconds.
+    pub latency_ms: u64,
+    /// "timed out" or "request failed"; details are logged, not returned, since
+    /// transport errors can carry the endpoint URL.
+    pub error: Option<String>,
+}
+
+/// One RPC endpoint's circuit breaker in [`HealthResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RpcEndpointHealth {
+    /// Endpoint index: 0 is the primary (`RPC_URL`), then `RPC_URLS` in order.
+    pub endpoint: usize,
+    /// "closed", "open" (skipped) or "half_open" (the next call probes it).
+    pub state: String,
+    /// Consecutive transient failures.
+    pub consecutive_failures: u32,
+    /// Milliseconds until an open breaker lets a probe through.
+    pub retry_in_ms: Option<u64>,
+}
+
+/// One wallet in [`WalletPoolStatusResponse`].
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WalletPoolEntry {
+    /// Wallet address.
+    #[schemars(example = "crate::models::examples::address")]
+    pub address: String,
+    /// Status recorded in the Redis pool: "Available", "Locked" or "Reserved".
+    /// Informational only; `lock_holder` says whether the wallet is in use right now.
+    pub status: String,
+    /// Instance currently holding the wallet's distributed lock, if any.
+    pub lock_holder: Option<String>,
+    /// Whether this instance holds a signer (local key or KMS) for the wallet.
+    pub has_signer: bool,
+    /// Beacons designated to this wallet.
+    pub designated_beacons: Vec<String>,
+}
+
+/// One funding wallet in [`FundingWalletStatusResponse`], read live from the chain.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FundingWalletBalance {
+    /// Wallet address.
+    #[schemars(example = "crate::models::examples::address")]
+    pub address: String,
+    /// ETH balance in wei.
+    pub eth_balance_wei: String,
+    /// USDC balance in base units (6 decimals).
+    pub usdc_balance: String,
+    /// Transaction count (`eth_getTransactionCount`, latest block).
+    pub nonce: u64,
+    /// Whether the ETH balance is at or below the faucet reserve, i.e. the wallet can no
+    /// longer fund guests.
+    pub below_eth_reserve: bool,
+}
+
+/// Response for the admin `GET /funding_wallet/status` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FundingWalletStatusResponse {
+    /// ETH each wallet keeps back for beacon gas (`FAUCET_RESERVE_ETH_WEI`).
+    pub eth_reserve_wei: String,
+    /// Sum of `eth_balance_wei` across wallets.
+    pub total_eth_wei: String,
+    /// Sum of `usdc_balance` across wallets.
+    pub total_usdc: String,
+    /// Wallets that fund guests (this instance's signers), sorted by address.
+    pub wallets: Vec<FundingWalletBalance>,
+}
+
+/// Response for the admin `GET /wallet_pool/status` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WalletPoolStatusResponse {
+    /// True when the Redis pool could not be read; only the local signer view is filled in.
+    pub degraded: bool,
+    /// Why the pool could not be read, when `degraded`.
+    pub error: Option<String>,
+    /// This instance's id, as written into the wallet locks it holds.
+    pub instance_id: Option<String>,
+    /// Number of signers this instance holds.
+    pub signer_count: usize,
+    /// Number of wallets registered in the Redis pool.
+    pub total_wallets: usize,
+    /// Wallet count per recorded status ("Available", "Locked", "Reserved").
+    pub status_counts: BTreeMap<String, usize>,
+    /// Wallets whose distributed lock is currently held by some instance.
+    pub locked_wallets: usize,
+    /// Signers of this instance that are missing from the Redis pool.
+    pub unregistered_signers: Vec<String>,
+    /// Beacon → designated wallet.
+    pub designations: BTreeMap<String, String>,
+    /// Every pool wallet, sorted by address.
+    pub wallets: Vec<WalletPoolEntry>,
+}
+
+/// Response for the admin `POST /wallet_pool/force_unlock` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ForceUnlockWalletResponse {
+    /// Wallet whose lock was released.
+    #[schemars(example = "crate::models::examples::address")]
+    pub wallet_address: String,
+    /// Owner token the released lock carried.
+    pub released_holder: String,
+}
+
+/// Response for the admin `POST /maintenance/drain` and `POST /maintenance/resume`
+/// endpoints.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MaintenanceResponse {
+    /// Whether this instance is now draining.
+    pub draining: bool,
+    /// Whether it was draining before the request.
+    pub was_draining: bool,
+}
+
+/// Response for the admin `POST /wallet_pool/designation` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BeaconDesignationResponse {
+    /// Beacon whose designation changed.
+    #[schemars(example = "crate::models::examples::address")]
+    pub beacon_address: String,
+    /// Wallet now designated for the beacon (null after a clear).
+    #[schemars(example = "crate::models::examples::address")]
+    pub wallet_address: Option<String>,
+    /// Wallet the beacon was designated to before this call, if any other.
+    pub previous_wallet_address: Option<String>,
+}
+
+/// Response for the public `GET /version` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VersionResponse {
+    /// Crate version (`CARGO_PKG_VERSION`).
+    pub version: String,
+    /// Release name reported to Sentry, e.g. `the-beaconator@0.1.0`.
+    pub release: String,
+    /// Git commit the binary was built from, or `unknown`.
+    pub git_commit: String,
+    /// Build time, RFC 3339 UTC.
+    pub build_timestamp: String,
+    /// Active `ENV` (`mainnet`, `testnet`, or `localnet`).
+    pub env: String,
+    /// Chain ID the server is configured for.
+    pub chain_id: u64,
+}
+
+/// Outcome of a [`TransactionRecord`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionStatus {
+    Success,
+    Failed,
+}
+
+/// One write operation kept by the transaction history (`TRANSACTION_HISTORY_ENABLED`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionRecord {
+    /// Operation label, as in `GET /metrics/gas` (e.g. `beacon_update`, `perp_deploy`).
+    pub kind: String,
+    /// Identifying inputs of the request, e.g. `beacon_address`.
+    pub inputs: BTreeMap<String, String>,
+    /// Resulting transaction hash; null when the operation failed or sent nothing.
+    #[schemars(example = "crate::models::examples::transaction_hash")]
+    pub transaction_hash: Option<String>,
+    pub status: TransactionStatus,
+    /// Error message when `status` is `failed`.
+    pub error: Option<String>,
+    /// Unix seconds at which the operation finished.
+    pub recorded_at: u64,
+}
+
+/// Response for the admin `GET /transactions` endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionHistoryResponse {
+    /// False when `TRANSACTION_HISTORY_ENABLED` is off; `transactions` is then empty.
+    pub enabled: bool,
+    /// Most recent first.
+    pub transactions: Vec<TransactionRecord>,
+}
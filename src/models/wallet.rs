@@ -20,6 +20,17 @@ pub enum WalletStatus {
     },
 }
 
+impl WalletStatus {
+    /// Variant name without its payload, e.g. "Locked".
+    pub fn label(&self) -> &'static str {
+        match self {
+            WalletStatus::Available => "Available",
+            WalletStatus::Locked { .. } => "Locked",
+            WalletStatus::Reserved { .. } => "Reserved",
+        }
+    }
+}
+
 /// Information about a wallet in the pool
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WalletInfo {
@@ -50,6 +61,10 @@ pub struct WalletManagerConfig {
     pub instance_id: Option<String>,
     /// Chain ID for EIP-155 signatures (e.g., 42161 for Arbitrum One)
     pub chain_id: Option<u64>,
+    /// Whether an operation on a designated beacon may fall back to a general pool
+    /// wallet when the designated one cannot be acquired (`WALLET_DESIGNATION_FALLBACK`).
+    /// Off by default: the designated wallet is required.
+    pub designation_fallback: bool,
 }
 
 impl WalletManagerConfig {
@@ -72,6 +87,14 @@ impl WalletManagerConfig {
             lock_retry_delay: Duration::from_millis(500),
             instance_id: std::env::var("BEACONATOR_INSTANCE_ID").ok(),
             chain_id,
+            designation_fallback: std::env::var("WALLET_DESIGNATION_FALLBACK")
+                .map(|v| {
+                    matches!(
+                        v.trim().to_ascii_lowercase().as_str(),
+                        "1" | "true" | "yes" | "on"
+                    )
+                })
+                .unwrap_or(false),
         })
     }
 }
@@ -113,6 +136,12 @@ impl PrefixedRedisKeys {
         format!("{}wallet_lock:{address}", self.prefix)
     }
 
+    /// Next transaction nonce for a pool wallet, shared by every instance:
+    /// wallet_nonce:{address}. Only written while holding the wallet's lock.
+    pub fn wallet_nonce(&self, address: &Address) -> String {
+        format!("{}wallet_nonce:{address}", self.prefix)
+    }
+
     /// ZSET tracking least-recently-used wallet selection order: wallet_lru.
     /// Score is the millisecond timestamp of the wallet's last successful
     /// acquisition; members absent from the set (never acquired) sort first.
@@ -167,6 +196,16 @@ impl PrefixedRedisKeys {
     pub fn beacon_recipe_config(&self, slug: &str) -> String {
         format!("{}beacon_recipe:{slug}", self.prefix)
     }
+
+    /// Last block scanned by the IndexUpdated feed: beacon_events:cursor
+    pub fn beacon_events_cursor(&self) -> String {
+        format!("{}beacon_events:cursor", self.prefix)
+    }
+
+    /// Newest IndexUpdated per beacon: beacon_events:latest -> {beacon: ObservedIndex JSON}
+    pub fn beacon_events_latest(&self) -> String {
+        format!("{}beacon_events:latest", self.prefix)
+    }
 }
 
 impl Default for PrefixedRedisKeys {
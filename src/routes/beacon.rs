@@ -1,11 +1,11 @@
 use alloy::primitives::Address;
 use rocket::serde::json::Json;
-use rocket::{State, http::Status, post};
+use rocket::{State, get, http::Status, post};
 use rocket_okapi::openapi;
 use std::str::FromStr;
 use tracing;
 
-use crate::guards::ApiToken;
+use crate::guards::{ApiToken, IdempotencyKey, SignedJson};
 use crate::models::beacon_type::FactoryType;
 use crate::models::component_factory::ComponentFactoryType;
 use crate::models::recipe::{
@@ -14,20 +14,28 @@ use crate::models::recipe::{
 use crate::models::requests::{CreateModularBeaconRequest, ModularBeaconParams};
 use crate::models::responses::CreateModularBeaconResponse;
 use crate::models::{
-    ApiResponse, AppState, BatchUpdateBeaconRequest, BatchUpdateBeaconResponse,
-    CreateBeaconByTypeRequest, CreateBeaconResponse, CreateBeaconWithEcdsaRequest,
-    CreateBeaconWithEcdsaResponse, CreateLBCGBMBeaconRequest,
-    CreateWeightedSumCompositeBeaconRequest, EcdsaUpdateResponse, RegisterBeaconRequest,
-    UnregisterBeaconRequest, UpdateBeaconRequest, UpdateBeaconWithEcdsaRequest,
+    ApiResponse, AppState, BatchBeaconDataRequest, BatchBeaconDataResponse,
+    BatchCreateBeaconByTypeRequest, BatchCreateBeaconResponse, BatchUpdateBeaconRequest,
+    BatchUpdateBeaconResponse, BeaconEventResponse, CreateBeaconByTypeRequest,
+    CreateBeaconResponse, CreateBeaconWithEcdsaRequest, CreateBeaconWithEcdsaResponse,
+    CreateLBCGBMBeaconRequest, CreateWeightedSumCompositeBeaconRequest, EcdsaUpdateResponse,
+    RegisterBeaconRequest, UnregisterBeaconRequest, UpdateBeaconRequest,
+    UpdateBeaconWithEcdsaRequest,
 };
+use crate::routes::beacon_error_status;
+use crate::services::address::parse_address;
 use crate::services::beacon::modular::create_modular_beacon as service_create_modular_beacon;
+use crate::services::beacon::read::{MAX_BATCH_BEACON_READS, read_beacon_data};
 use crate::services::beacon::{
-    RegistrationOutcome, UnregistrationOutcome, batch_update_beacon as service_batch_update_beacon,
-    create_and_register_beacon_by_type, create_and_register_factory_beacon, create_identity_beacon,
-    create_weighted_sum_composite_beacon, register_beacon_with_registry,
+    IDENTITY_BEACON_UNAVAILABLE, RegistrationOutcome, UnregistrationOutcome, batch_concurrency,
+    batch_create_concurrency_from_env, batch_create_max_count_from_env,
+    batch_update_beacon as service_batch_update_beacon, create_and_register_beacon_by_type,
+    create_and_register_factory_beacon, create_identity_beacon_with_verifier,
+    create_weighted_sum_composite_beacon, register_beacon_with_registry, run_bounded,
     unregister_beacon_with_registry, update_beacon as service_update_beacon,
     update_beacon_with_ecdsa as service_update_beacon_with_ecdsa,
 };
+use crate::services::idempotency::{IdempotencyStore, Reservation};
 
 /// Creates a new beacon using a registered beacon type.
 ///
@@ -36,7 +44,7 @@ use crate::services::beacon::{
 #[openapi(tag = "Beacon")]
 #[post("/create_beacon", data = "<request>")]
 pub async fn create_beacon(
-    request: Json<CreateBeaconByTypeRequest>,
+    request: SignedJson<CreateBeaconByTypeRequest>,
     _token: ApiToken,
     state: &State<AppState>,
 ) -> Result<Json<ApiResponse<CreateBeaconResponse>>, Status> {
@@ -98,14 +106,188 @@ pub async fn create_beacon(
     }
 }
 
+/// Creates multiple beacons of one registered beacon type.
+///
+/// Each beacon is created (and registered, if the type has a registry) independently; failures
+/// are collected instead of aborting the batch. Up to `BATCH_CREATE_CONCURRENCY` beacons
+/// (capped at the wallet pool size) are deployed at once, and `count` is limited to
+/// `BATCH_CREATE_MAX_COUNT`. Send an `Idempotency-Key` header to make retries safe: a
+/// repeat request with the same key and access token within the idempotency TTL returns the
+/// original result instead of deploying a second set of beacons, or 409 while the first
+/// request is still running.
+#[openapi(tag = "Beacon")]
+#[post("/batch_create_beacon", data = "<request>")]
+pub async fn batch_create_beacon(
+    request: SignedJson<BatchCreateBeaconByTypeRequest>,
+    token: ApiToken,
+    idempotency_key: IdempotencyKey,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<BatchCreateBeaconResponse>>, Status> {
+    tracing::info!(
+        "Received request: POST /batch_create_beacon (type={}, count={})",
+        request.beacon_type,
+        request.count
+    );
+
+    let max_count = batch_create_max_count_from_env();
+    if request.count == 0 || request.count > max_count {
+        tracing::warn!(
+            "Batch create request count {} outside 1..={}",
+            request.count,
+            max_count
+        );
+        return Err(Status::BadRequest);
+    }
+
+    let cache_key = idempotency_key
+        .0
+        .as_deref()
+        .map(|key| IdempotencyStore::<BatchCreateBeaconResponse>::scoped_key(token.bearer(), key));
+
+    if let Some(cache_key) = &cache_key {
+        match state.idempotency.reserve(cache_key).await {
+            Ok(Reservation::Reserved) => {}
+            Ok(Reservation::Completed(cached)) => {
+                tracing::info!("Replaying cached batch create result for idempotency key");
+                return Ok(Json(ApiResponse {
+                    success: cached.failed_count == 0,
+                    data: Some(cached),
+                    message: "Returning original result for repeated Idempotency-Key".to_string(),
+                }));
+            }
+            Ok(Reservation::InFlight) => {
+                tracing::warn!("Batch create with this Idempotency-Key is still in progress");
+                return Err(Status::Conflict);
+            }
+            Err(e) => {
+                tracing::error!("Failed to reserve Idempotency-Key: {}", e);
+                return Err(Status::ServiceUnavailable);
+            }
+        }
+    }
+
+    let result = run_batch_create(state.inner(), &request).await;
+    if let Some(cache_key) = &cache_key {
+        match &result {
+            // Cache whenever anything was deployed: replaying a partial result is what
+            // prevents a retry from duplicating the beacons that did get created.
+            Ok(Json(ApiResponse {
+                data: Some(response),
+                ..
+            })) if response.created_count > 0 => {
+                state
+                    .idempotency
+                    .insert(cache_key.clone(), response.clone())
+                    .await;
+            }
+            _ => state.idempotency.release(cache_key).await,
+        }
+    }
+    result
+}
+
+/// Look up the beacon type and create `request.count` beacons of it.
+async fn run_batch_create(
+    state: &AppState,
+    request: &BatchCreateBeaconByTypeRequest,
+) -> Result<Json<ApiResponse<BatchCreateBeaconResponse>>, Status> {
+    let config = match state
+        .registries
+        .beacon_types
+        .get_type(&request.beacon_type)
+        .await
+    {
+        Ok(Some(config)) => config,
+        Ok(None) => {
+            let msg = format!("Unknown beacon type: '{}'", request.beacon_type);
+            tracing::warn!("{}", msg);
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                message: msg,
+            }));
+        }
+        Err(e) => {
+            tracing::error!("Failed to look up beacon type: {}", e);
+            return Err(Status::InternalServerError);
+        }
+    };
+
+    if !config.enabled {
+        return Ok(Json(ApiResponse {
+            success: false,
+            data: None,
+            message: format!("Beacon type '{}' is disabled", request.beacon_type),
+        }));
+    }
+
+    // Each in-flight creation leases its own pool wallet, so the limit never exceeds
+    // the pool size.
+    let concurrency = batch_concurrency(
+        batch_create_concurrency_from_env(),
+        state.wallets.manager.signer_addresses().len(),
+    );
+    let results = run_bounded(request.count as usize, concurrency, |i| {
+        let config = &config;
+        let request = &request;
+        async move {
+            let result =
+                create_and_register_beacon_by_type(state, config, request.params.as_ref()).await;
+            match &result {
+                Ok(response) => tracing::info!(
+                    "Batch beacon {}/{} created at {}",
+                    i + 1,
+                    request.count,
+                    response.beacon_address
+                ),
+                Err(e) => {
+                    tracing::error!("Batch beacon {}/{} failed: {}", i + 1, request.count, e)
+                }
+            }
+            result
+        }
+    })
+    .await;
+
+    let mut beacon_addresses = Vec::new();
+    let mut errors = Vec::new();
+    for (i, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(response) => beacon_addresses.push(response.beacon_address),
+            Err(e) => errors.push(format!("Beacon {}: {e}", i + 1)),
+        }
+    }
+
+    let response = BatchCreateBeaconResponse {
+        beacon_type: config.slug.clone(),
+        created_count: beacon_addresses.len() as u32,
+        beacon_addresses,
+        failed_count: errors.len() as u32,
+        errors,
+    };
+
+    let message = format!(
+        "Created {} of {} '{}' beacons",
+        response.created_count, request.count, config.slug
+    );
+    Ok(Json(ApiResponse {
+        success: response.failed_count == 0,
+        data: Some(response),
+        message,
+    }))
+}
+
 /// Creates an IdentityBeacon with an auto-deployed ECDSA verifier.
 ///
 /// Creates an ECDSAVerifier via the factory contract with the beaconator's PRIVATE_KEY signer,
-/// then deploys an IdentityBeacon using the verifier. Optionally registers with the default registry.
+/// or reuses `verifier_address` if given, then deploys an IdentityBeacon using the verifier.
+/// Optionally registers with the default registry.
+/// Returns 400 for a malformed `verifier_address` and 503 if the IdentityBeacon bytecode was
+/// not loaded at startup.
 #[openapi(tag = "Beacon")]
 #[post("/create_beacon_with_ecdsa", data = "<request>")]
 pub async fn create_beacon_with_ecdsa(
-    request: Json<CreateBeaconWithEcdsaRequest>,
+    request: SignedJson<CreateBeaconWithEcdsaRequest>,
     _token: ApiToken,
     state: &State<AppState>,
 ) -> Result<Json<ApiResponse<CreateBeaconWithEcdsaResponse>>, Status> {
@@ -114,23 +296,43 @@ pub async fn create_beacon_with_ecdsa(
         request.initial_index
     );
 
+    let existing_verifier = match request.verifier_address.as_deref().map(Address::from_str) {
+        None => None,
+        Some(Ok(addr)) => Some(addr),
+        Some(Err(e)) => {
+            tracing::error!("Invalid verifier address: {}", e);
+            return Err(Status::BadRequest);
+        }
+    };
+
+    // Refuse up front rather than deploying a verifier for a beacon we cannot deploy
+    if state.contracts.load().identity_beacon_bytecode.is_empty() {
+        tracing::error!("{}", IDENTITY_BEACON_UNAVAILABLE);
+        return Err(Status::ServiceUnavailable);
+    }
+
     // Create IdentityBeacon with ECDSA verifier (handles verifier creation + beacon deployment)
-    let (beacon_address, verifier_address) =
-        match create_identity_beacon(state.inner(), request.initial_index).await {
-            Ok(result) => result,
-            Err(e) => {
-                let detailed_error = format!("ECDSA beacon creation failed: {e}");
-                tracing::error!("{}", detailed_error);
-                return Ok(Json(ApiResponse {
-                    success: false,
-                    data: None,
-                    message: "Beacon creation failed".to_string(),
-                }));
-            }
-        };
+    let (beacon_address, verifier_address) = match create_identity_beacon_with_verifier(
+        state.inner(),
+        request.initial_index,
+        existing_verifier,
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            let detailed_error = format!("ECDSA beacon creation failed: {e}");
+            tracing::error!("{}", detailed_error);
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                message: "Beacon creation failed".to_string(),
+            }));
+        }
+    };
 
     // Register with the perpcity registry
-    let registry_address = state.contracts.perpcity_registry;
+    let registry_address = state.contracts.load().perpcity_registry;
     let (registered, safe_proposal_hash) = match register_beacon_with_registry(
         state.inner(),
         beacon_address,
@@ -163,8 +365,8 @@ pub async fn create_beacon_with_ecdsa(
     };
 
     let response = CreateBeaconWithEcdsaResponse {
-        beacon_address: format!("{beacon_address:#x}"),
-        verifier_address: format!("{verifier_address:#x}"),
+        beacon_address: beacon_address.to_checksum(None),
+        verifier_address: verifier_address.to_checksum(None),
         beacon_type: "identity".to_string(),
         registered,
         safe_proposal_hash,
@@ -190,7 +392,7 @@ pub async fn create_beacon_with_ecdsa(
 #[openapi(tag = "Beacon")]
 #[post("/register_beacon", data = "<request>")]
 pub async fn register_beacon(
-    request: Json<RegisterBeaconRequest>,
+    request: SignedJson<RegisterBeaconRequest>,
     _token: ApiToken,
     state: &State<AppState>,
 ) -> Result<Json<ApiResponse<String>>, Status> {
@@ -207,7 +409,8 @@ pub async fn register_beacon(
     }
 
     // Parse the beacon address
-    let beacon_address = match Address::from_str(&request.beacon_address) {
+    let beacon_address = match parse_address(&request.beacon_address, state.strict_address_checksum)
+    {
         Ok(addr) => addr,
         Err(e) => {
             let error_msg = format!("Invalid beacon address '{}': {}", request.beacon_address, e);
@@ -284,7 +487,7 @@ pub async fn register_beacon(
 #[openapi(tag = "Beacon")]
 #[post("/unregister_beacon", data = "<request>")]
 pub async fn unregister_beacon(
-    request: Json<UnregisterBeaconRequest>,
+    request: SignedJson<UnregisterBeaconRequest>,
     _token: ApiToken,
     state: &State<AppState>,
 ) -> Result<Json<ApiResponse<String>>, Status> {
@@ -300,7 +503,8 @@ pub async fn unregister_beacon(
     }
 
     // Parse the beacon address
-    let beacon_address = match Address::from_str(&request.beacon_address) {
+    let beacon_address = match parse_address(&request.beacon_address, state.strict_address_checksum)
+    {
         Ok(addr) => addr,
         Err(e) => {
             tracing::error!("Invalid beacon address '{}': {}", request.beacon_address, e);
@@ -326,7 +530,7 @@ pub async fn unregister_beacon(
                 }
             }
         }
-        None => state.contracts.perpcity_registry,
+        None => state.contracts.load().perpcity_registry,
     };
 
     // Unregister the beacon from the specified registry
@@ -369,11 +573,15 @@ pub async fn unregister_beacon(
 /// Updates a beacon with new data using a zero-knowledge proof.
 ///
 /// Validates the provided proof and public signals, then updates the beacon's data.
-/// Returns the transaction hash on success.
+/// The beacon's shape is detected on-chain: verifier-backed (proof or ECDSA) beacons get
+/// `update(proof, public_signals)`, composite beacons a plain `update()`.
+/// Returns the transaction hash on success, or 400 if the address is invalid or not a
+/// beacon or the verifier rejects the proof (`ProofAlreadyUsed` / `InvalidProof`); other
+/// failures map as in [`beacon_error_status`].
 #[openapi(tag = "Beacon")]
 #[post("/update_beacon", data = "<request>")]
 pub async fn update_beacon(
-    request: Json<UpdateBeaconRequest>,
+    request: SignedJson<UpdateBeaconRequest>,
     _token: ApiToken,
     state: &State<AppState>,
 ) -> Result<Json<ApiResponse<String>>, Status> {
@@ -389,9 +597,8 @@ pub async fn update_beacon(
             }))
         }
         Err(e) => {
-            let error_msg = format!("Failed to update beacon: {e}");
-            tracing::error!("{}", error_msg);
-            Err(Status::InternalServerError)
+            tracing::error!(code = e.code(), "Failed to update beacon: {e}");
+            Err(beacon_error_status(&e))
         }
     }
 }
@@ -399,11 +606,14 @@ pub async fn update_beacon(
 /// Updates multiple beacons with new data using zero-knowledge proofs.
 ///
 /// Processes a batch of beacon updates, each with their own proof and public signals.
-/// Returns detailed results for each update attempt.
+/// With `MULTICALL3_ADDRESS` set, each wallet's updates share one `aggregate3` transaction;
+/// otherwise they are sent one at a time. With `prevalidate: true`, updates that would
+/// revert are reported as failed without being sent. Returns detailed results for each
+/// update attempt.
 #[openapi(tag = "Beacon")]
 #[post("/batch_update_beacon", data = "<request>")]
 pub async fn batch_update_beacon(
-    request: Json<BatchUpdateBeaconRequest>,
+    request: SignedJson<BatchUpdateBeaconRequest>,
     _token: ApiToken,
     state: &State<AppState>,
 ) -> Result<Json<ApiResponse<BatchUpdateBeaconResponse>>, Status> {
@@ -421,7 +631,7 @@ pub async fn batch_update_beacon(
     }
 
     // Use the extracted service function
-    match service_batch_update_beacon(state.inner(), &request.updates).await {
+    match service_batch_update_beacon(state.inner(), &request.updates, request.prevalidate).await {
         Ok(response) => {
             let message = format!(
                 "Batch update completed: {}/{} successful",
@@ -442,6 +652,49 @@ pub async fn batch_update_beacon(
     }
 }
 
+/// Reads the current index of several beacons at once.
+///
+/// With `MULTICALL3_ADDRESS` set, every index is read in one `tryAggregate` call and the
+/// response carries the timestamp of the block they were read at; otherwise beacons are
+/// read one at a time. Returns 400 for an empty request or more than 100 addresses; a
+/// malformed address or a failed read is reported on its own entry.
+#[openapi(tag = "Beacon")]
+#[post("/batch_beacon_data", data = "<request>")]
+pub async fn batch_beacon_data(
+    request: SignedJson<BatchBeaconDataRequest>,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<BatchBeaconDataResponse>>, Status> {
+    tracing::info!(
+        "Received request: POST /batch_beacon_data ({} beacons)",
+        request.beacon_addresses.len()
+    );
+
+    if request.beacon_addresses.is_empty() {
+        tracing::warn!("Batch beacon data request with no beacons");
+        return Err(Status::BadRequest);
+    }
+
+    if request.beacon_addresses.len() > MAX_BATCH_BEACON_READS {
+        tracing::warn!(
+            "Batch beacon data request exceeds maximum of {MAX_BATCH_BEACON_READS} beacons"
+        );
+        return Err(Status::BadRequest);
+    }
+
+    let response = read_beacon_data(state.inner(), &request.beacon_addresses).await;
+    let message = format!(
+        "Batch beacon read completed: {}/{} successful",
+        response.successful_reads, response.total_requested
+    );
+
+    Ok(Json(ApiResponse {
+        success: response.successful_reads > 0,
+        data: Some(response),
+        message,
+    }))
+}
+
 /// Updates a beacon using ECDSA signature from the beaconator wallet.
 ///
 /// This endpoint is for beacons that use an ECDSAVerifierAdapter for verification.
@@ -450,7 +703,7 @@ pub async fn batch_update_beacon(
 #[openapi(tag = "Beacon")]
 #[post("/update_beacon_with_ecdsa_adapter", data = "<request>")]
 pub async fn update_beacon_with_ecdsa_adapter(
-    request: Json<UpdateBeaconWithEcdsaRequest>,
+    request: SignedJson<UpdateBeaconWithEcdsaRequest>,
     _token: ApiToken,
     state: &State<AppState>,
 ) -> Result<Json<EcdsaUpdateResponse>, Status> {
@@ -488,6 +741,7 @@ pub async fn update_beacon_with_ecdsa_adapter(
                 data: Some(format!("Transaction hash: {tx_hash:?}")),
                 message,
                 confirmed: outcome.confirmed,
+                gas_used: outcome.gas_used,
             }))
         }
         Err(e) => {
@@ -505,7 +759,7 @@ pub async fn update_beacon_with_ecdsa_adapter(
 #[openapi(tag = "Beacon")]
 #[post("/create_lbcgbm_beacon", data = "<request>")]
 pub async fn create_lbcgbm_beacon_endpoint(
-    request: Json<CreateLBCGBMBeaconRequest>,
+    request: SignedJson<CreateLBCGBMBeaconRequest>,
     _token: ApiToken,
     state: &State<AppState>,
 ) -> Result<Json<ApiResponse<CreateBeaconResponse>>, Status> {
@@ -563,7 +817,7 @@ pub async fn create_lbcgbm_beacon_endpoint(
     let beacon_address = result.beacon_address;
 
     // Register with perpcity registry
-    let registry_address = state.contracts.perpcity_registry;
+    let registry_address = state.contracts.load().perpcity_registry;
     let (registered, safe_proposal_hash) = match register_beacon_with_registry(
         state.inner(),
         beacon_address,
@@ -602,15 +856,16 @@ pub async fn create_lbcgbm_beacon_endpoint(
         .component_factories
         .get_factory_address(&ComponentFactoryType::StandaloneBeaconFactory)
         .await
-        .map(|a| format!("{a:#x}"))
+        .map(|a| a.to_checksum(None))
         .unwrap_or_else(|_| "unknown".to_string());
 
     let response = CreateBeaconResponse {
-        beacon_address: format!("{beacon_address:#x}"),
+        beacon_address: beacon_address.to_checksum(None),
         beacon_type: "lbcgbm".to_string(),
         factory_address,
         registered,
         safe_proposal_hash,
+        confirmations: None,
     };
 
     tracing::info!(
@@ -633,7 +888,7 @@ pub async fn create_lbcgbm_beacon_endpoint(
 #[openapi(tag = "Beacon")]
 #[post("/create_weighted_sum_composite_beacon", data = "<request>")]
 pub async fn create_weighted_sum_composite_beacon_endpoint(
-    request: Json<CreateWeightedSumCompositeBeaconRequest>,
+    request: SignedJson<CreateWeightedSumCompositeBeaconRequest>,
     _token: ApiToken,
     state: &State<AppState>,
 ) -> Result<Json<ApiResponse<CreateBeaconResponse>>, Status> {
@@ -684,9 +939,9 @@ pub async fn create_weighted_sum_composite_beacon_endpoint(
     };
 
     // Create the beacon via factory
-    let beacon_address =
+    let (beacon_address, confirmations) =
         match create_weighted_sum_composite_beacon(state.inner(), &config, &request).await {
-            Ok(addr) => addr,
+            Ok(created) => created,
             Err(e) => {
                 let detailed_error = format!("WeightedSumComposite beacon creation failed: {e}");
                 tracing::error!("{}", detailed_error);
@@ -700,7 +955,8 @@ pub async fn create_weighted_sum_composite_beacon_endpoint(
 
     // Register with registry
     match create_and_register_factory_beacon(state.inner(), &config, beacon_address).await {
-        Ok(response) => {
+        Ok(mut response) => {
+            response.confirmations = Some(confirmations);
             tracing::info!(
                 "WeightedSumComposite beacon created: beacon={}, registered={}",
                 response.beacon_address,
@@ -720,11 +976,12 @@ pub async fn create_weighted_sum_composite_beacon_endpoint(
             Ok(Json(ApiResponse {
                 success: true,
                 data: Some(CreateBeaconResponse {
-                    beacon_address: format!("{beacon_address:#x}"),
+                    beacon_address: beacon_address.to_checksum(None),
                     beacon_type: config.slug.clone(),
-                    factory_address: format!("{:#x}", config.factory_address),
+                    factory_address: config.factory_address.to_checksum(None),
                     registered: false,
                     safe_proposal_hash: None,
+                    confirmations: Some(confirmations),
                 }),
                 message: warn_msg,
             }))
@@ -739,7 +996,7 @@ pub async fn create_weighted_sum_composite_beacon_endpoint(
 #[openapi(tag = "Beacon")]
 #[post("/create_modular_beacon", data = "<request>")]
 pub async fn create_modular_beacon(
-    request: Json<CreateModularBeaconRequest>,
+    request: SignedJson<CreateModularBeaconRequest>,
     _token: ApiToken,
     state: &State<AppState>,
 ) -> Result<Json<ApiResponse<CreateModularBeaconResponse>>, Status> {
@@ -796,7 +1053,7 @@ pub async fn create_modular_beacon(
     let beacon_address = result.beacon_address;
 
     // Register with perpcity registry
-    let registry_address = state.contracts.perpcity_registry;
+    let registry_address = state.contracts.load().perpcity_registry;
     let (registered, safe_proposal_hash) = match register_beacon_with_registry(
         state.inner(),
         beacon_address,
@@ -830,8 +1087,8 @@ pub async fn create_modular_beacon(
     };
 
     let response = CreateModularBeaconResponse {
-        beacon_address: format!("{beacon_address:#x}"),
-        verifier_address: result.verifier_address.map(|a| format!("{a:#x}")),
+        beacon_address: beacon_address.to_checksum(None),
+        verifier_address: result.verifier_address.map(|a| a.to_checksum(None)),
         recipe: recipe.slug.clone(),
         components: result.components,
         registered,
@@ -851,3 +1108,52 @@ pub async fn create_modular_beacon(
         message: "Modular beacon created successfully".to_string(),
     }))
 }
+
+/// Returns the newest `IndexUpdated` event seen for a beacon by the change feed.
+///
+/// Only beacons listed in `BEACON_EVENTS_BEACONS` are tracked. Returns 400 for a malformed
+/// address, 404 for an untracked beacon or one with no update since the feed started, and
+/// 503 when the feed is disabled.
+#[openapi(tag = "Beacon")]
+#[get("/beacon_events/<beacon_address>")]
+pub async fn get_beacon_events(
+    beacon_address: &str,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<BeaconEventResponse>>, Status> {
+    tracing::info!("Received request: GET /beacon_events/{}", beacon_address);
+
+    let feed = &state.beacon_events;
+    if !feed.is_enabled() {
+        tracing::warn!("Beacon event feed is disabled (BEACON_EVENTS_BEACONS unset)");
+        return Err(Status::ServiceUnavailable);
+    }
+
+    let address = match parse_address(beacon_address, state.strict_address_checksum) {
+        Ok(addr) => addr,
+        Err(e) => {
+            tracing::error!("Invalid beacon address '{}': {e}", beacon_address);
+            return Err(Status::BadRequest);
+        }
+    };
+
+    if !feed.is_tracked(&address) {
+        tracing::warn!("Beacon {} is not tracked by the event feed", address);
+        return Err(Status::NotFound);
+    }
+    let Some(observed) = feed.latest(&address).await else {
+        return Err(Status::NotFound);
+    };
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(BeaconEventResponse {
+            beacon_address: address.to_checksum(None),
+            index: observed.index.to_string(),
+            block_number: observed.block_number,
+            transaction_hash: format!("{:#x}", observed.transaction_hash),
+            scanned_through_block: feed.scanned_through().await,
+        }),
+        message: "Latest beacon event retrieved".to_string(),
+    }))
+}
@@ -0,0 +1,195 @@
+use alloy::primitives::Address;
+use rocket::serde::json::Json;
+use rocket::{State, get, http::Status, post};
+use rocket_okapi::openapi;
+use std::str::FromStr;
+use tracing;
+
+use crate::guards::{ApiToken, SignedJson};
+use crate::models::{
+    ApiResponse, AppState, EstimateGasRequest, EstimateGasResponse, GasPriceResponse,
+};
+use crate::routes::perp::{
+    parse_deploy_request, parse_margin_amount, parse_max_amount, parse_perp_address,
+};
+use crate::services::beacon::core::IDENTITY_BEACON_UNAVAILABLE;
+use crate::services::estimate::{
+    current_fees, estimate_create_beacon, estimate_deploy_perp, estimate_deposit_liquidity,
+    estimate_sender, summarize_estimate,
+};
+use crate::services::gas_price::fetch_network_fees;
+use crate::services::perp::resolve_deposit_ticks;
+
+/// Estimates gas for a write operation without sending it.
+///
+/// Takes `{"operation": ..., "params": ...}` where `operation` is `create_beacon`,
+/// `deploy_perp` or `deposit_liquidity` and `params` is the body of the matching endpoint.
+/// Builds the transactions that endpoint would send, runs `eth_estimateGas` on each from a
+/// pool wallet, and prices the total at the current gas price and EIP-1559 max fee.
+/// Returns 400 for params the endpoint would reject and 503 when `create_beacon` is
+/// requested without IdentityBeacon bytecode. A transaction that would revert returns
+/// `success: false` with the reason.
+#[openapi(tag = "Information")]
+#[post("/estimate_gas", data = "<request>")]
+pub async fn estimate_gas(
+    request: SignedJson<EstimateGasRequest>,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<EstimateGasResponse>>, Status> {
+    let from = estimate_sender(state.inner());
+
+    let (operation, estimates) = match request.into_inner() {
+        EstimateGasRequest::CreateBeacon(params) => {
+            tracing::info!("Received request: POST /estimate_gas (create_beacon)");
+            let existing_verifier = match params.verifier_address.as_deref().map(Address::from_str)
+            {
+                None => None,
+                Some(Ok(addr)) => Some(addr),
+                Some(Err(e)) => {
+                    tracing::error!("Invalid verifier address: {}", e);
+                    return Err(Status::BadRequest);
+                }
+            };
+            if state.contracts.load().identity_beacon_bytecode.is_empty() {
+                tracing::error!("{}", IDENTITY_BEACON_UNAVAILABLE);
+                return Err(Status::ServiceUnavailable);
+            }
+            (
+                "create_beacon",
+                estimate_create_beacon(
+                    state.inner(),
+                    from,
+                    params.initial_index,
+                    existing_verifier,
+                )
+                .await,
+            )
+        }
+        EstimateGasRequest::DeployPerp(params) => {
+            tracing::info!("Received request: POST /estimate_gas (deploy_perp)");
+            let (beacon_address, owner, salt) =
+                parse_deploy_request(&params, state.strict_address_checksum)?;
+            (
+                "deploy_perp",
+                estimate_deploy_perp(
+                    state.inner(),
+                    from,
+                    beacon_address,
+                    owner,
+                    params.name,
+                    params.symbol,
+                    params.token_uri,
+                    params.ema_window,
+                    salt,
+                )
+                .await,
+            )
+        }
+        EstimateGasRequest::DepositLiquidity(params) => {
+            tracing::info!("Received request: POST /estimate_gas (deposit_liquidity)");
+            let perp_address =
+                parse_perp_address(&params.perp_address, state.strict_address_checksum)?;
+            let margin_amount = parse_margin_amount(&params.margin_amount_usdc)?;
+            let max_amt0_in = parse_max_amount("max_amt0_in", params.max_amt0_in.as_deref())?;
+            let max_amt1_in = parse_max_amount("max_amt1_in", params.max_amt1_in.as_deref())?;
+            let (tick_spacing, tick_lower, tick_upper) = resolve_deposit_ticks(
+                &state.perp,
+                params.tick_spacing,
+                params.tick_lower,
+                params.tick_upper,
+            );
+            if let Err(e) =
+                state
+                    .perp
+                    .check_deposit(margin_amount, tick_spacing, tick_lower, tick_upper)
+            {
+                tracing::error!("Invalid deposit: {}", e);
+                return Err(Status::BadRequest);
+            }
+            (
+                "deposit_liquidity",
+                estimate_deposit_liquidity(
+                    state.inner(),
+                    from,
+                    perp_address,
+                    margin_amount,
+                    tick_spacing,
+                    tick_lower,
+                    tick_upper,
+                    max_amt0_in,
+                    max_amt1_in,
+                )
+                .await,
+            )
+        }
+    };
+
+    let estimates = match estimates {
+        Ok(estimates) => estimates,
+        Err(e) => {
+            tracing::warn!("Gas estimate for {} failed: {}", operation, e);
+            return Ok(Json(ApiResponse {
+                success: false,
+                data: None,
+                message: format!("Gas estimate failed: {e}"),
+            }));
+        }
+    };
+
+    let (gas_price, max_fee_per_gas) = current_fees(state.inner()).await.map_err(|e| {
+        tracing::error!("{}", e);
+        Status::InternalServerError
+    })?;
+
+    let response = summarize_estimate(operation, from, estimates, gas_price, max_fee_per_gas);
+    tracing::info!(
+        "Estimated {} gas for {} ({} wei at current gas price)",
+        response.total_gas,
+        operation,
+        response.estimated_fee_wei
+    );
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(response),
+        message: "Gas estimated successfully".to_string(),
+    }))
+}
+
+/// Returns the current EIP-1559 fee estimate and the latest block's base fee and
+/// utilization, so clients can decide when to submit.
+///
+/// Served from a cache refreshed at most every 5 seconds. `congested` is set when the
+/// latest block used more than half its gas limit, i.e. the base fee is rising. Returns
+/// 503 when the RPC endpoint cannot be read.
+#[openapi(tag = "Information")]
+#[get("/gas_price")]
+pub async fn gas_price(
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<GasPriceResponse>>, Status> {
+    tracing::info!("Received request: GET /gas_price");
+
+    let provider = &state.provider.read_provider;
+    let fees = state
+        .provider
+        .gas_prices
+        .get_or_fetch(|| fetch_network_fees(provider, &state.provider.read_retry))
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch network fees: {e}");
+            Status::ServiceUnavailable
+        })?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(GasPriceResponse {
+            block_number: fees.block_number,
+            base_fee_per_gas_wei: fees.base_fee_per_gas.map(|fee| fee.to_string()),
+            max_fee_per_gas_wei: fees.max_fee_per_gas.to_string(),
+            max_priority_fee_per_gas_wei: fees.max_priority_fee_per_gas.to_string(),
+            gas_used_ratio: fees.gas_used_ratio,
+            congested: fees.is_congested(),
+        }),
+        message: "Current network fees".to_string(),
+    }))
+}
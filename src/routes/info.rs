@@ -1,9 +1,21 @@
-use rocket::get;
+use rocket::http::Status;
+use rocket::response::status::Custom;
 use rocket::serde::json::Json;
+use rocket::{State, get, post};
 use rocket_okapi::openapi;
 use tracing;
 
-use crate::models::{ApiEndpoints, ApiResponse};
+use crate::guards::{AdminToken, ApiToken};
+use crate::models::{
+    ApiEndpoints, ApiResponse, AppState, ConfigSnapshotResponse, ContractAddresses,
+    ContractsSnapshot, GasMetricsResponse, LimitsSnapshot, NetworkSnapshot, REDACTED,
+    ReloadConfigResponse, RuntimeSnapshot, SecretsSnapshot, TransactionHistoryResponse,
+    VersionResponse,
+};
+use crate::services::metrics::GasOperation;
+
+/// Records returned by `GET /transactions` when `limit` is omitted.
+pub const DEFAULT_TRANSACTIONS_LIMIT: usize = 50;
 
 /// Returns API summary and available endpoints.
 ///
@@ -26,3 +38,267 @@ pub fn index() -> Json<ApiResponse<crate::models::ApiSummary>> {
         message,
     })
 }
+
+/// Returns build and release information.
+///
+/// Reports the crate version, the git commit and time the binary was built, and the
+/// active `ENV` and chain ID, so operators and clients can tell which build is serving.
+/// This endpoint does not require authentication.
+#[openapi(tag = "Information")]
+#[get("/version")]
+pub fn version(state: &State<AppState>) -> Json<ApiResponse<VersionResponse>> {
+    tracing::info!("Received request: GET /version");
+
+    let version = build_version_info(state);
+    let message = format!(
+        "The Beaconator {} ({})",
+        version.version, version.git_commit
+    );
+
+    Json(ApiResponse {
+        success: true,
+        data: Some(version),
+        message,
+    })
+}
+
+/// Build the `/version` payload from compile-time metadata and the live state.
+pub fn build_version_info(state: &AppState) -> VersionResponse {
+    VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        release: sentry::release_name!()
+            .map(|r| r.into_owned())
+            .unwrap_or_default(),
+        git_commit: env!("BEACONATOR_GIT_COMMIT").to_string(),
+        build_timestamp: env!("BEACONATOR_BUILD_TIMESTAMP").to_string(),
+        env: state.provider.env_type.clone(),
+        chain_id: state.provider.chain_id,
+    }
+}
+
+/// Reduce a URL to `scheme://host[:port]`, dropping credentials, path, and query.
+///
+/// Private RPC providers embed the API key in the path (`/v2/<key>`) or query, so only the
+/// origin is safe to show. Anything after the origin is replaced with `/[REDACTED]`.
+pub fn redact_url(url: &str) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return REDACTED.to_string();
+    };
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let authority = &rest[..authority_end];
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    if authority_end < rest.len() || host.len() != authority.len() {
+        format!("{scheme}://{host}/{REDACTED}")
+    } else {
+        format!("{scheme}://{host}")
+    }
+}
+
+/// Build the redacted configuration snapshot from the live application state.
+pub fn build_config_snapshot(state: &AppState) -> ConfigSnapshotResponse {
+    let contracts = state.contracts.load();
+    let wallets = &state.wallets;
+    let manager = &wallets.manager;
+
+    ConfigSnapshotResponse {
+        network: NetworkSnapshot {
+            chain_id: state.provider.chain_id,
+            rpc_url: redact_url(&state.provider.rpc_url),
+        },
+        contracts: contracts_snapshot(state, &contracts),
+        limits: LimitsSnapshot {
+            usdc_transfer_limit: wallets.usdc_transfer_limit.to_string(),
+            eth_transfer_limit: wallets.eth_transfer_limit.to_string(),
+            usdc_bonus_limit: wallets.usdc_bonus_limit.to_string(),
+            faucet_reserve_eth_wei: wallets.faucet_reserve_eth_wei.to_string(),
+        },
+        runtime: RuntimeSnapshot {
+            rpc_max_total_attempts: state.provider.receipt_wait.max_attempts,
+            wallet_lock_ttl_secs: (!manager.is_test_stub()).then(|| manager.lock_ttl().as_secs()),
+            wallet_pool_size: manager.signer_addresses().len(),
+            touch_on_update_enabled: state.touch.is_enabled(),
+            safe_enabled: contracts.safe.is_some(),
+        },
+        secrets: SecretsSnapshot {
+            private_key: REDACTED.to_string(),
+            access_token: REDACTED.to_string(),
+            admin_token: REDACTED.to_string(),
+        },
+    }
+}
+
+/// The contracts section of the config snapshot for `contracts`.
+fn contracts_snapshot(state: &AppState, contracts: &ContractAddresses) -> ContractsSnapshot {
+    ContractsSnapshot {
+        perpcity_registry: contracts.perpcity_registry.to_string(),
+        perp_factory: contracts.perp_factory.to_string(),
+        usdc: contracts.usdc.to_string(),
+        ecdsa_verifier_factory: contracts.ecdsa_verifier_factory.to_string(),
+        multicall3: contracts.multicall3.map(|a| a.to_string()),
+        fees_module: contracts.fees_module.to_string(),
+        funding_module: contracts.funding_module.to_string(),
+        margin_ratios_module: contracts.margin_ratios_module.to_string(),
+        price_impact_module: contracts.price_impact_module.to_string(),
+        pricing_module: contracts.pricing_module.to_string(),
+        protocol_fee_manager: contracts.protocol_fee_manager.map(|a| a.to_string()),
+        module_registry: contracts.module_registry.map(|a| a.to_string()),
+        safe_address: contracts.safe.as_ref().map(|s| s.address.to_string()),
+        safe_tx_service_url: contracts
+            .safe
+            .as_ref()
+            .and_then(|s| s.tx_service_url.as_deref())
+            .map(redact_url),
+        signer_address: state.wallets.signer_address.to_string(),
+        cold_wallet_address: state.wallets.cold_wallet_address.map(|a| a.to_string()),
+    }
+}
+
+/// Returns the fully-resolved configuration with secrets redacted (admin).
+///
+/// Intended for support tickets: addresses, limits, timeouts, feature flags, and network are
+/// reported as resolved at startup; the private key, API tokens, and any credentials embedded
+/// in URLs are never included.
+#[openapi(tag = "Information")]
+#[get("/admin/config_snapshot")]
+pub fn config_snapshot(
+    _token: AdminToken,
+    state: &State<AppState>,
+) -> Json<ApiResponse<ConfigSnapshotResponse>> {
+    tracing::info!("Received request: GET /admin/config_snapshot");
+
+    Json(ApiResponse {
+        success: true,
+        data: Some(build_config_snapshot(state)),
+        message: "Configuration snapshot (secrets redacted)".to_string(),
+    })
+}
+
+/// Error from `POST /admin/reload_config`, with every configuration problem as `data`.
+pub type ReloadConfigError = Custom<Json<ApiResponse<Vec<String>>>>;
+
+/// Reloads contract addresses without a restart (admin).
+///
+/// Re-reads the `*_ADDRESS` variables and `CONTRACTS_FILE`, validates them as at startup,
+/// and swaps the new set in. An invalid address, a missing required one, or a
+/// `CONTRACTS_FILE` for another chain rejects the reload with 422 and leaves the current
+/// addresses in place. Requests already running finish with the addresses they started with.
+#[openapi(tag = "Information")]
+#[post("/admin/reload_config")]
+pub fn reload_config(
+    _token: AdminToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<ReloadConfigResponse>>, ReloadConfigError> {
+    tracing::info!("Received request: POST /admin/reload_config");
+
+    let before = contracts_snapshot(state, &state.contracts.load());
+    let contracts = match crate::reload_contract_addresses(state) {
+        Ok(contracts) => contracts_snapshot(state, &contracts),
+        Err(e) => {
+            tracing::warn!(audit = "config_reload", "Config reload rejected: {e}");
+            return Err(Custom(
+                Status::UnprocessableEntity,
+                Json(ApiResponse {
+                    success: false,
+                    data: Some(e.problems.clone()),
+                    message: e.to_string(),
+                }),
+            ));
+        }
+    };
+    let changed = changed_fields(&before, &contracts);
+    tracing::info!(
+        audit = "config_reload",
+        "Contract addresses reloaded; changed: [{}]",
+        changed.join(", ")
+    );
+
+    let message = match changed.len() {
+        0 => "Contract addresses reloaded; nothing changed".to_string(),
+        n => format!("Contract addresses reloaded; {n} changed"),
+    };
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(ReloadConfigResponse { changed, contracts }),
+        message,
+    }))
+}
+
+/// Names of the fields that differ between two snapshots.
+fn changed_fields(before: &ContractsSnapshot, after: &ContractsSnapshot) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(before)), Ok(serde_json::Value::Object(after))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return Vec::new();
+    };
+    after
+        .iter()
+        .filter(|(name, value)| before.get(*name) != Some(value))
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// Returns per-operation gas usage histograms.
+///
+/// Covers every confirmed write since this instance started (beacon creation,
+/// registration, updates, perp deployment, deposits, wallet funding). Bucket counts
+/// are cumulative: each bucket counts the transactions at or below its `le` bound.
+#[openapi(tag = "Information")]
+#[get("/metrics/gas")]
+pub fn gas_metrics(
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Json<ApiResponse<GasMetricsResponse>> {
+    tracing::info!("Received request: GET /metrics/gas");
+
+    Json(ApiResponse {
+        success: true,
+        data: Some(state.gas_metrics.snapshot()),
+        message: "Gas usage by operation".to_string(),
+    })
+}
+
+/// Returns recent write operations, most recent first (admin).
+///
+/// `limit` defaults to 50 and is capped by `TRANSACTION_HISTORY_MAX_ENTRIES`; `kind` filters
+/// by operation (`beacon_register`, `beacon_update`, `perp_deploy`, `deposit`, ...) and an
+/// unknown kind is a 400. With `TRANSACTION_HISTORY_ENABLED` off the list is always empty.
+#[openapi(tag = "Information")]
+#[get("/transactions?<limit>&<kind>")]
+pub async fn transactions(
+    _token: AdminToken,
+    state: &State<AppState>,
+    limit: Option<usize>,
+    kind: Option<String>,
+) -> Result<Json<ApiResponse<TransactionHistoryResponse>>, Status> {
+    tracing::info!("Received request: GET /transactions");
+
+    let kind = match kind.as_deref().map(str::trim).filter(|k| !k.is_empty()) {
+        Some(label) => match GasOperation::from_label(label) {
+            Some(op) => Some(op),
+            None => {
+                tracing::warn!("Unknown transaction kind: {label}");
+                return Err(Status::BadRequest);
+            }
+        },
+        None => None,
+    };
+    let limit = limit.unwrap_or(DEFAULT_TRANSACTIONS_LIMIT);
+    let enabled = state.history.is_enabled();
+    let transactions = state.history.recent(limit, kind).await;
+
+    let message = if enabled {
+        format!("{} recent transactions", transactions.len())
+    } else {
+        "Transaction history is disabled (TRANSACTION_HISTORY_ENABLED)".to_string()
+    };
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(TransactionHistoryResponse {
+            enabled,
+            transactions,
+        }),
+        message,
+    }))
+}
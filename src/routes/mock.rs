@@ -0,0 +1,115 @@
+//! Canned write endpoints for `BEACONATOR_MODE=mock` (see [`crate::services::mock`]).
+//!
+//! Each handler takes the same path, body and token as the real one it replaces, and has
+//! its name so scoped tokens map the same way, and returns a response of the same shape
+//! without touching a chain or Redis.
+
+use alloy::primitives::U256;
+use rocket::serde::json::Json;
+use rocket::{State, http::Status, post};
+use tracing;
+
+use crate::guards::{ApiToken, SignedJson};
+use crate::models::{
+    ApiResponse, AppState, CreateBeaconByTypeRequest, CreateBeaconResponse,
+    DeployPerpForBeaconRequest, DeployPerpForBeaconResponse, UpdateBeaconRequest,
+};
+use crate::routes::perp::parse_deploy_request;
+use crate::services::address::parse_address;
+use crate::services::mock::{mock_address, mock_hash};
+use crate::services::perp::liquidity::x96_to_decimal;
+
+/// Mock `POST /create_beacon`: the beacon address is derived from the type and params,
+/// so repeating a request returns the same beacon.
+#[post("/create_beacon", data = "<request>")]
+pub async fn create_beacon(
+    request: SignedJson<CreateBeaconByTypeRequest>,
+    _token: ApiToken,
+) -> Json<ApiResponse<CreateBeaconResponse>> {
+    let initial_index = request
+        .params
+        .as_ref()
+        .and_then(|params| params.initial_index)
+        .unwrap_or_default();
+    let seed = format!("{}:{initial_index}", request.beacon_type);
+    let beacon_address = mock_address("beacon", seed.as_bytes());
+    tracing::info!(
+        "Mock mode: created '{}' beacon at {beacon_address}",
+        request.beacon_type
+    );
+
+    Json(ApiResponse {
+        success: true,
+        data: Some(CreateBeaconResponse {
+            beacon_address: beacon_address.to_checksum(None),
+            beacon_type: request.beacon_type.clone(),
+            factory_address: mock_address("factory", request.beacon_type.as_bytes())
+                .to_checksum(None),
+            registered: true,
+            safe_proposal_hash: None,
+            confirmations: Some(0),
+        }),
+        message: "Beacon created successfully".to_string(),
+    })
+}
+
+/// Mock `POST /update_beacon`: accepts any proof for a well-formed beacon address.
+#[post("/update_beacon", data = "<request>")]
+pub async fn update_beacon(
+    request: SignedJson<UpdateBeaconRequest>,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<String>>, Status> {
+    let beacon_address = parse_address(&request.beacon_address, state.strict_address_checksum)
+        .map_err(|e| {
+            tracing::error!("Invalid beacon address '{}': {}", request.beacon_address, e);
+            Status::BadRequest
+        })?;
+    let seed = [
+        beacon_address.as_slice(),
+        &request.proof,
+        &request.public_signals,
+    ]
+    .concat();
+    let tx_hash = mock_hash("update", &seed);
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(format!("Transaction hash: {tx_hash:?}")),
+        message: "Beacon updated successfully".to_string(),
+    }))
+}
+
+/// Mock `POST /deploy_perp_for_beacon`: the perp address is derived from the salt (given
+/// or derived from the request, as in the real endpoint), at a starting price of 1.
+#[post("/deploy_perp_for_beacon", data = "<request>")]
+pub async fn deploy_perp_for_beacon_endpoint(
+    request: SignedJson<DeployPerpForBeaconRequest>,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<DeployPerpForBeaconResponse>>, Status> {
+    let (_, _, salt) = parse_deploy_request(&request, state.strict_address_checksum)?;
+    let perp = mock_address("perp", salt.as_slice());
+    let sqrt_price_x96 = U256::from(1u8) << 96usize;
+    tracing::info!("Mock mode: deployed perp at {perp}");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(DeployPerpForBeaconResponse {
+            perp_address: perp.to_string(),
+            pool_id: format!("{:#x}", mock_hash("pool", perp.as_slice())),
+            perp_factory_address: state.contracts.load().perp_factory.to_string(),
+            initial_index: "0".to_string(),
+            ema_window: request.ema_window,
+            sqrt_price_x96: sqrt_price_x96.to_string(),
+            mark_price: x96_to_decimal(sqrt_price_x96),
+            tick: 0,
+            salt: format!("{salt:#x}"),
+            transaction_hash: mock_hash("createPerp", salt.as_slice()).to_string(),
+            gas_used: 0,
+            confirmations: 0,
+            already_deployed: false,
+        }),
+        message: "Perp deployed successfully!".to_string(),
+    }))
+}
@@ -1,6 +1,8 @@
 pub mod beacon;
 pub mod beacon_type;
+pub mod estimate;
 pub mod info;
+pub mod mock;
 pub mod perp;
 pub mod recipe;
 pub mod wallet;
@@ -10,10 +12,31 @@ pub mod wallet;
 // Re-export all route functions for easy access
 pub use beacon::*;
 pub use beacon_type::*;
+pub use estimate::*;
 pub use info::*;
 pub use perp::*;
 pub use wallet::*;
 
+use rocket::http::Status;
+
+use crate::services::error::BeaconError;
+
+/// HTTP status for a failed beacon or perp operation: 400 for a bad address, a non-beacon
+/// or a rejected proof, 404 for an unregistered address, 503 when no wallet or RPC
+/// endpoint is available, 504 when the receipt timed out, 500 otherwise.
+pub fn beacon_error_status(error: &BeaconError) -> Status {
+    match error {
+        BeaconError::NotRegistered(_) => Status::NotFound,
+        BeaconError::InvalidState(_) => Status::Conflict,
+        e if e.is_client_error() => Status::BadRequest,
+        BeaconError::WalletUnavailable(_) | BeaconError::RpcUnavailable(_) => {
+            Status::ServiceUnavailable
+        }
+        BeaconError::Timeout(_) => Status::GatewayTimeout,
+        _ => Status::InternalServerError,
+    }
+}
+
 // Define contract interfaces using Alloy's sol! macro - shared across all route modules.
 // `#[allow(clippy::too_many_arguments)]` is needed for generated builder/call methods like
 // PerpFactory.createPerp(owner, name, symbol, tokenUri, modules, emaWindow, salt) which
@@ -31,6 +54,10 @@ mod root_sol_interfaces {
         function increaseCardinalityCap(uint16 newCap) external;
         function verifier() external view returns (address);
         event IndexUpdated(uint256 index);
+
+        // Verifier reverts bubbled up through update(); decoded by ContractErrorDecoder.
+        error ProofAlreadyUsed();
+        error InvalidProof();
     }
 
     #[sol(rpc)]
@@ -80,6 +107,9 @@ mod root_sol_interfaces {
         function approve(address spender, uint256 amount) external returns (bool);
         function balanceOf(address account) external view returns (uint256 balance);
         function allowance(address owner, address spender) external view returns (uint256);
+
+        // Standard ERC20 event; the maker close flow sums the USDC paid out by the Perp.
+        event Transfer(address indexed from, address indexed to, uint256 value);
     }
 
     // The deployed testnet USDC (Arbitrum Sepolia) exposes a permissionless
@@ -90,7 +120,7 @@ mod root_sol_interfaces {
         function mint(address to, uint256 amount) external;
     }
 
-    #[sol(rpc)]
+    #[sol(rpc, all_derives)]
     interface IMulticall3 {
         struct Call {
             address target;
@@ -112,6 +142,7 @@ mod root_sol_interfaces {
         function aggregate3(Call3[] calldata calls) external payable returns (Result[] memory returnData);
         function tryAggregate(bool requireSuccess, Call[] calldata calls) external payable returns (Result[] memory returnData);
         function getEthBalance(address addr) external view returns (uint256 balance);
+        function getCurrentBlockTimestamp() external view returns (uint256 timestamp);
     }
 
     // PerpFactory: deploys a per-market `Perp` contract for each beacon. v0.1.0 architecture
@@ -189,13 +220,78 @@ mod root_sol_interfaces {
             uint256 amt1Limit;
         }
 
+        // v0.1.0 has no dedicated close: a maker position is closed by removing all of its
+        // liquidity through adjustMaker, which settles the position and pays out its margin.
+        struct AdjustMakerParams {
+            uint256 posId;
+            int128 marginDelta;
+            int128 liquidityDelta;
+            uint256 amt0Limit;
+            uint256 amt1Limit;
+        }
+
+        // None of the v0.1.0 write entry points (openMaker / openTaker / adjustMaker here,
+        // createPerp on PerpFactory) takes a deadline, so writes carry none: price
+        // protection comes only from maxAmt0In / maxAmt1In and the amt*Limit fields. If a
+        // later release adds one, set it from the latest block's timestamp.
         function openMaker(OpenMakerParams calldata params) external returns (uint256 posId);
         function openTaker(OpenTakerParams calldata params) external returns (uint256 posId);
+        function adjustMaker(AdjustMakerParams calldata params) external;
 
         // Permissionless funding/EMA accrual (selector 0xa55526db). Called after a
         // beacon update to refresh funding for every perp backed by that beacon.
         function touch() external;
 
+        // Read-only market metadata, used by GET /perp/<perp_address>.
+        function POOL_ID() external view returns (bytes32);
+        function name() external view returns (string memory);
+        function symbol() external view returns (string memory);
+        function owner() external view returns (address result);
+        function modules() external view returns (
+            address beacon,
+            address fees,
+            address funding,
+            address marginRatios,
+            address priceImpact,
+            address pricing
+        );
+        // Current AMM state; the deposit flow prices its slippage limits off sqrtPriceX96.
+        function poolState() external view returns (
+            int24 tick,
+            uint160 sqrtPriceX96,
+            uint256 ammPriceX96,
+            uint128 liquidity
+        );
+
+        // Position reads used by POST /close_maker_position. makerDetails returns zeroed
+        // details for taker positions; ownerOf reverts with TokenDoesNotExist for unknown ids.
+        struct Capacity {
+            uint128 long;
+            uint128 short;
+        }
+        struct MakerFunding {
+            int256 belowX96;
+            int256 withinX96;
+            int256 divSqrtPriceWithinX96;
+        }
+        function makerDetails(uint256 posId) external view returns (
+            int24 tickLower,
+            int24 tickUpper,
+            uint128 liquidity,
+            uint256 lastLongUtilEarningsX96,
+            uint256 lastShortUtilEarningsX96,
+            Capacity memory capacity_,
+            MakerFunding memory lastCumlFunding
+        );
+        function positions(uint256 posId) external view returns (
+            int256 delta,
+            uint128 margin,
+            uint24 liqMarginRatio,
+            uint24 backstopMarginRatio,
+            int256 lastCumlFundingX96
+        );
+        function ownerOf(uint256 id) external view returns (address result);
+
         event MakerOpened(uint256 posId);
         event TakerOpened(uint256 posId, SwapResult sr);
 
@@ -211,7 +307,8 @@ mod root_sol_interfaces {
             uint256 insuranceFeeAmt;
         }
 
-        // Errors from src/libraries/Errors.sol@v0.1.0 reachable from openMaker / openTaker.
+        // Errors from src/libraries/Errors.sol@v0.1.0 reachable from openMaker / openTaker /
+        // adjustMaker.
         // All parameterless — see ContractErrorDecoder in services/perp/validation.rs.
         error ZeroDelta();
         error MinAmtUnmet();
@@ -226,7 +323,12 @@ mod root_sol_interfaces {
         error LongUtilizationExceeded();
         error ShortUtilizationExceeded();
         error InsufficientLiquidityToFill();
+        error NonMakerPosition();
         error Abdicated();
+
+        // Solady ERC721 errors from the position NFT (ownerOf / adjustMaker).
+        error TokenDoesNotExist();
+        error NotOwnerNorApproved();
     }
     }
 }
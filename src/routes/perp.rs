@@ -1,23 +1,41 @@
-use alloy::primitives::{Address, FixedBytes, keccak256};
-use alloy::sol_types::SolValue;
+use alloy::primitives::{Address, FixedBytes, U256, keccak256};
+use alloy::sol_types::{SolCall, SolValue};
+use rocket::response::status::Custom;
 use rocket::serde::json::Json;
-use rocket::{State, http::Status, post};
+use rocket::{State, get, http::Status, post};
 use rocket_okapi::openapi;
 use std::str::FromStr;
 use tracing;
 
-use crate::guards::ApiToken;
+use crate::guards::{ApiToken, SignedJson};
 use crate::models::{
-    ApiResponse, AppState, DeployPerpForBeaconRequest, DeployPerpForBeaconResponse,
-    DepositLiquidityForPerpRequest, DepositLiquidityForPerpResponse,
+    ApiResponse, AppState, BatchDepositLiquidityForPerpsRequest,
+    BatchDepositLiquidityForPerpsResponse, CloseMakerPositionRequest, CloseMakerPositionResponse,
+    CreatePerpModules, CreatePerpPreviewResponse, DeployPerpForBeaconRequest,
+    DeployPerpForBeaconResponse, DepositLiquidityByPriceRequest, DepositLiquidityByPriceResponse,
+    DepositLiquidityForPerpRequest, DepositLiquidityForPerpResponse, FieldError, MakerInfoResponse,
+    PerpConfigResponse, PerpInfoResponse, PreviewDepositRequest, PreviewDepositResponse,
+    TroubleshootingReport,
+};
+use crate::routes::{IPerpFactory, beacon_error_status};
+use crate::services::address::parse_address;
+use crate::services::error::BeaconError;
+use crate::services::perp::cadence::EMA_WINDOW_TOO_SHORT;
+use crate::services::perp::liquidity::ticks_for_price_range;
+use crate::services::perp::slippage::SLIPPAGE_EXCEEDED;
+use crate::services::perp::{
+    MAX_TICK, MIN_TICK, build_create_perp_call, close_maker_position, deploy_perp_for_beacon,
+    deposit_liquidity_for_perp, ema_window_u24, format_usdc, get_maker_position, get_perp_info,
+    resolve_deposit_ticks,
+};
+use crate::services::transaction::troubleshooting::{
+    log_troubleshooting_report, troubleshooting_report,
 };
-use crate::routes::IPerpFactory;
-use crate::services::perp::{deploy_perp_for_beacon, deposit_liquidity_for_perp};
 
-/// Derive a deterministic 32-byte salt from the deploy request. Reusing this salt on retry
-/// causes `LibClone.cloneDeterministic` inside PerpFactory.createPerp to revert if the previous
-/// call already minted the accounting-token clones — making /deploy_perp_for_beacon idempotent
-/// instead of silently creating a duplicate market when the client retries after a timeout.
+/// Derive a deterministic 32-byte salt from the deploy request. Retries are answered by the
+/// beacon lookup in `deploy_perp_for_beacon` before anything is sent; the salt is the backstop
+/// when two deploys race past it, since reusing it makes `LibClone.cloneDeterministic` inside
+/// PerpFactory.createPerp revert instead of creating a duplicate market.
 ///
 /// Includes every user-controllable createPerp input so that distinct intents produce distinct
 /// salts.
@@ -41,22 +59,14 @@ fn deterministic_salt(
     keccak256(encoded)
 }
 
-/// Deploys a perpetual market contract for a specific beacon via PerpFactory.createPerp.
-///
-/// perpcity-contracts@v0.1.0 architecture: each market is its own `Perp` contract.
-/// Module addresses (Fees / Funding / MarginRatios / PriceImpact / Pricing) are resolved
-/// from the server's environment, not the request body.
-#[openapi(tag = "Perpetual")]
-#[post("/deploy_perp_for_beacon", data = "<request>")]
-pub async fn deploy_perp_for_beacon_endpoint(
-    request: Json<DeployPerpForBeaconRequest>,
-    _token: ApiToken,
-    state: &State<AppState>,
-) -> Result<Json<ApiResponse<DeployPerpForBeaconResponse>>, Status> {
-    tracing::info!("Received request: POST /deploy_perp_for_beacon");
-    tracing::info!("Requested beacon address: {}", request.beacon_address);
-
-    let beacon_address = match Address::from_str(&request.beacon_address) {
+/// Parse and validate a deploy request: beacon and owner addresses (checksums enforced
+/// when `strict`), `ema_window`, and the salt (derived from the request when omitted).
+/// Shared with `/estimate_gas`.
+pub(crate) fn parse_deploy_request(
+    request: &DeployPerpForBeaconRequest,
+    strict: bool,
+) -> Result<(Address, Address, FixedBytes<32>), Status> {
+    let beacon_address = match parse_address(&request.beacon_address, strict) {
         Ok(addr) => addr,
         Err(e) => {
             let error_msg = format!("Invalid beacon address '{}': {}", request.beacon_address, e);
@@ -65,7 +75,7 @@ pub async fn deploy_perp_for_beacon_endpoint(
         }
     };
 
-    let owner = match Address::from_str(&request.owner) {
+    let owner = match parse_address(&request.owner, strict) {
         Ok(addr) => addr,
         Err(e) => {
             let error_msg = format!("Invalid owner address '{}': {}", request.owner, e);
@@ -77,12 +87,8 @@ pub async fn deploy_perp_for_beacon_endpoint(
     // Validate ema_window fits in uint24 and is non-zero (matches IPerpFactory.EmaWindowTooLow).
     // Defensive: also enforced inside deploy_perp_for_beacon, but rejecting here gives a clearer
     // BadRequest instead of a 500 from the service layer.
-    if request.ema_window == 0 || request.ema_window > 0x00FF_FFFF {
-        let error_msg = format!(
-            "Invalid ema_window {}: must be in 1..=16777215 (uint24 non-zero)",
-            request.ema_window
-        );
-        tracing::error!("{}", error_msg);
+    if let Err(e) = ema_window_u24(request.ema_window) {
+        tracing::error!("Invalid ema_window: {}", e);
         return Err(Status::BadRequest);
     }
 
@@ -105,6 +111,53 @@ pub async fn deploy_perp_for_beacon_endpoint(
         },
     };
 
+    Ok((beacon_address, owner, salt))
+}
+
+/// Error response of /deploy_perp_for_beacon: the status code with a JSON body whose
+/// `data` is the troubleshooting report for verbose requests.
+pub type DeployPerpError = Custom<Json<ApiResponse<TroubleshootingReport>>>;
+
+fn deploy_error(
+    status: Status,
+    message: String,
+    report: Option<TroubleshootingReport>,
+) -> DeployPerpError {
+    Custom(
+        status,
+        Json(ApiResponse {
+            success: false,
+            data: report,
+            message,
+        }),
+    )
+}
+
+/// Deploys a perpetual market contract for a specific beacon via PerpFactory.createPerp.
+///
+/// perpcity-contracts@v0.1.0 architecture: each market is its own `Perp` contract.
+/// Module addresses (Fees / Funding / MarginRatios / PriceImpact / Pricing) are resolved
+/// from the server's environment, not the request body.
+///
+/// Before deploying, `ema_window` is checked against how often the beacon has been
+/// updating; a window shorter than that interval is a 400.
+///
+/// Failures are logged as one troubleshooting report (error category, probable causes,
+/// suggested actions); set `verbose` in the request to get it back as the error body's `data`.
+#[openapi(tag = "Perpetual")]
+#[post("/deploy_perp_for_beacon", data = "<request>")]
+pub async fn deploy_perp_for_beacon_endpoint(
+    request: SignedJson<DeployPerpForBeaconRequest>,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<DeployPerpForBeaconResponse>>, DeployPerpError> {
+    tracing::info!("Received request: POST /deploy_perp_for_beacon");
+    tracing::info!("Requested beacon address: {}", request.beacon_address);
+
+    let (beacon_address, owner, salt) =
+        parse_deploy_request(&request, state.strict_address_checksum)
+            .map_err(|status| deploy_error(status, "Invalid deploy request".to_string(), None))?;
+
     tracing::info!("Starting perp deployment process...");
     match deploy_perp_for_beacon(
         state,
@@ -119,7 +172,11 @@ pub async fn deploy_perp_for_beacon_endpoint(
     .await
     {
         Ok(response) => {
-            let message = "Perp deployed successfully!";
+            let message = if response.already_deployed {
+                "Perp already deployed for beacon"
+            } else {
+                "Perp deployed successfully!"
+            };
             tracing::info!("{}", message);
             tracing::info!("Perp address: {}", response.perp_address);
             tracing::info!("PerpFactory address: {}", response.perp_factory_address);
@@ -132,62 +189,180 @@ pub async fn deploy_perp_for_beacon_endpoint(
             }))
         }
         Err(e) => {
-            let error_msg = format!("Failed to deploy perp for beacon {beacon_address}: {e}");
-            tracing::error!("{}", error_msg);
-            tracing::error!("Error context:");
-            tracing::error!("  - Beacon address: {}", beacon_address);
-            tracing::error!("  - PerpFactory address: {}", state.contracts.perp_factory);
-            tracing::error!("  - USDC address: {}", state.contracts.usdc);
+            let report = troubleshooting_report(
+                "deploy_perp_for_beacon",
+                &e,
+                [
+                    ("beacon", beacon_address),
+                    ("owner", owner),
+                    ("perp_factory", state.contracts.load().perp_factory),
+                    ("usdc", state.contracts.load().usdc),
+                ],
+            );
+            log_troubleshooting_report(&report);
 
-            Err(Status::InternalServerError)
+            // An ema_window shorter than the beacon's update interval is the caller's to fix.
+            let status = if e.starts_with(EMA_WINDOW_TOO_SHORT) {
+                Status::BadRequest
+            } else {
+                Status::InternalServerError
+            };
+            let message = format!(
+                "Failed to deploy perp for beacon {beacon_address} ({})",
+                report.category.label()
+            );
+            Err(deploy_error(
+                status,
+                message,
+                request.verbose.then_some(report),
+            ))
         }
     }
 }
 
+/// Parse the `perp_address` of a deposit request, enforcing its checksum when `strict`.
+pub(crate) fn parse_perp_address(value: &str, strict: bool) -> Result<Address, Status> {
+    parse_address(value, strict).map_err(|e| {
+        tracing::error!("Invalid perp address '{}': {e}", value);
+        Status::BadRequest
+    })
+}
+
+/// Parse a deposit's `margin_amount_usdc` (6 decimals).
+pub(crate) fn parse_margin_amount(value: &str) -> Result<u128, Status> {
+    let margin_amount = value.parse::<u128>().map_err(|e| {
+        tracing::error!("Invalid margin amount '{}': {e}", value);
+        tracing::error!("Margin amount must be a valid number in USDC with 6 decimals");
+        tracing::error!("  Examples: '1000000' = 1 USDC, '500000000' = 500 USDC");
+        Status::BadRequest
+    })?;
+    tracing::info!("Margin amount: {} USDC", margin_amount as f64 / 1_000_000.0);
+    Ok(margin_amount)
+}
+
+/// Parse an optional decimal `max_amt*_in` limit from a deposit request.
+pub(crate) fn parse_max_amount(field: &str, value: Option<&str>) -> Result<Option<U256>, Status> {
+    value
+        .map(|v| {
+            U256::from_str_radix(v.trim(), 10).map_err(|e| {
+                tracing::error!("Invalid {} '{}': {e}", field, v);
+                Status::BadRequest
+            })
+        })
+        .transpose()
+}
+
+/// Error response of /deposit_liquidity_for_perp: the status code with a JSON body whose
+/// `data` lists every invalid field of a rejected request.
+pub type DepositError = Custom<Json<ApiResponse<Vec<FieldError>>>>;
+
+fn deposit_error(status: Status, message: String, errors: Option<Vec<FieldError>>) -> DepositError {
+    Custom(
+        status,
+        Json(ApiResponse {
+            success: false,
+            data: errors,
+            message,
+        }),
+    )
+}
+
 /// Deposits liquidity (opens a maker position) on a per-market `Perp` contract.
 ///
 /// Approves USDC spending against the per-Perp contract address and calls
 /// `Perp.openMaker(OpenMakerParams)`. Returns the maker position ID and transaction hashes.
+/// Token amounts are capped by `max_amt0_in` / `max_amt1_in` (defaulted from the current pool
+/// price plus `DEPOSIT_SLIPPAGE_BPS`); a deposit that would exceed them returns 409.
+/// An invalid request returns 400 with every invalid field listed in `data`.
 #[openapi(tag = "Perpetual")]
 #[post("/deposit_liquidity_for_perp", data = "<request>")]
 pub async fn deposit_liquidity_for_perp_endpoint(
-    request: Json<DepositLiquidityForPerpRequest>,
+    request: SignedJson<DepositLiquidityForPerpRequest>,
     _token: ApiToken,
     state: &State<AppState>,
-) -> Result<Json<ApiResponse<DepositLiquidityForPerpResponse>>, Status> {
+) -> Result<Json<ApiResponse<DepositLiquidityForPerpResponse>>, DepositError> {
     tracing::info!("Received request: POST /deposit_liquidity_for_perp");
 
-    let perp_address = match Address::from_str(&request.perp_address) {
-        Ok(addr) => addr,
-        Err(e) => {
-            let error_msg = format!("Invalid perp address '{}': {e}", request.perp_address);
-            tracing::error!("{}", error_msg);
-            return Err(Status::BadRequest);
-        }
-    };
+    let response = deposit_from_request(state, &request).await?;
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(response),
+        message: "Liquidity deposited successfully".to_string(),
+    }))
+}
 
-    let margin_amount = match request.margin_amount_usdc.parse::<u128>() {
-        Ok(amount) => amount,
-        Err(e) => {
-            let error_msg = format!(
-                "Invalid margin amount '{}': {e}",
-                request.margin_amount_usdc
-            );
-            tracing::error!("{}", error_msg);
-            tracing::error!("Margin amount must be a valid number in USDC with 6 decimals");
-            tracing::error!("  Examples: '1000000' = 1 USDC, '500000000' = 500 USDC");
-            return Err(Status::BadRequest);
+/// Validates one deposit request and opens its maker position. Shared by the single and
+/// batch deposit endpoints.
+async fn deposit_from_request(
+    state: &AppState,
+    request: &DepositLiquidityForPerpRequest,
+) -> Result<DepositLiquidityForPerpResponse, DepositError> {
+    let errors = request.validate(&state.perp, state.strict_address_checksum);
+    if !errors.is_empty() {
+        for error in &errors {
+            tracing::warn!("Invalid deposit {}: {}", error.field, error.message);
         }
-    };
+        return Err(deposit_error(
+            Status::BadRequest,
+            format!("Invalid deposit request ({} problems)", errors.len()),
+            Some(errors),
+        ));
+    }
 
-    tracing::info!(
-        "Margin amount: {} USDC (validation delegated to on-chain modules)",
-        margin_amount as f64 / 1_000_000.0
+    // Validated above; these only convert.
+    let invalid =
+        |status: Status| deposit_error(status, "Invalid deposit request".to_string(), None);
+    let perp_address = parse_perp_address(&request.perp_address, state.strict_address_checksum)
+        .map_err(invalid)?;
+    let margin_amount = parse_margin_amount(&request.margin_amount_usdc).map_err(invalid)?;
+
+    let max_amt0_in =
+        parse_max_amount("max_amt0_in", request.max_amt0_in.as_deref()).map_err(invalid)?;
+    let max_amt1_in =
+        parse_max_amount("max_amt1_in", request.max_amt1_in.as_deref()).map_err(invalid)?;
+
+    let (tick_spacing, tick_lower, tick_upper) = resolve_deposit_ticks(
+        &state.perp,
+        request.tick_spacing,
+        request.tick_lower,
+        request.tick_upper,
     );
 
-    let tick_spacing = request.tick_spacing.unwrap_or(30);
-    let tick_lower = request.tick_lower.unwrap_or(24390);
-    let tick_upper = request.tick_upper.unwrap_or(53850);
+    deposit_at_ticks(
+        state,
+        perp_address,
+        margin_amount,
+        tick_spacing,
+        tick_lower,
+        tick_upper,
+        max_amt0_in,
+        max_amt1_in,
+    )
+    .await
+    .map_err(|(status, message)| deposit_error(status, message, None))
+}
+
+/// Validates a deposit's tick range and margin, checks the Perp is one of ours, and opens
+/// the maker position. Shared by the tick- and price-based deposit endpoints; errors carry
+/// the status to answer with and the reason.
+#[allow(clippy::too_many_arguments)]
+async fn deposit_at_ticks(
+    state: &AppState,
+    perp_address: Address,
+    margin_amount: u128,
+    tick_spacing: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+    max_amt0_in: Option<U256>,
+    max_amt1_in: Option<U256>,
+) -> Result<DepositLiquidityForPerpResponse, (Status, String)> {
+    if let Err(e) = state
+        .perp
+        .check_deposit(margin_amount, tick_spacing, tick_lower, tick_upper)
+    {
+        tracing::error!("Invalid deposit: {}", e);
+        return Err((Status::BadRequest, e));
+    }
 
     // Defense in depth: refuse to approve USDC against any address that wasn't deployed by the
     // trusted PerpFactory. The endpoint is gated by the API token, but a caller typo or a
@@ -195,24 +370,27 @@ pub async fn deposit_liquidity_for_perp_endpoint(
     //
     // The on-chain check is `PerpFactory.perps(address)` (boolean mapping populated in
     // createPerp). Run AFTER cheap input validation so 400-class errors are surfaced first.
-    let factory = IPerpFactory::new(state.contracts.perp_factory, &state.provider.read_provider);
+    let factory = IPerpFactory::new(
+        state.contracts.load().perp_factory,
+        &state.provider.read_provider,
+    );
     match factory.perps(perp_address).call().await {
         Ok(is_known_perp) => {
             if !is_known_perp {
                 let error_msg = format!(
                     "perp_address {perp_address} is not registered with PerpFactory \
                      {} — refusing to approve USDC to an untrusted address",
-                    state.contracts.perp_factory
+                    state.contracts.load().perp_factory
                 );
                 tracing::error!("{}", error_msg);
-                return Err(Status::BadRequest);
+                return Err((Status::BadRequest, error_msg));
             }
         }
         Err(e) => {
             let error_msg =
                 format!("Failed to verify perp_address {perp_address} with factory: {e}");
             tracing::error!("{}", error_msg);
-            return Err(Status::InternalServerError);
+            return Err((Status::InternalServerError, error_msg));
         }
     }
 
@@ -223,36 +401,544 @@ pub async fn deposit_liquidity_for_perp_endpoint(
         tick_spacing,
         tick_lower,
         tick_upper,
+        max_amt0_in,
+        max_amt1_in,
     )
     .await
     {
         Ok(response) => {
-            let message = "Liquidity deposited successfully";
-            tracing::info!("{}", message);
+            tracing::info!("Liquidity deposited successfully");
             tracing::info!("Maker position ID: {}", response.maker_position_id);
+            match &response.approval_transaction_hash {
+                Some(hash) => tracing::info!("Approval transaction: {}", hash),
+                None => tracing::info!("Approval skipped: existing allowance covers the margin"),
+            }
+            tracing::info!("Deposit transaction: {}", response.deposit_transaction_hash);
+            Ok(response)
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to deposit liquidity for perp {perp_address}: {e}");
+            tracing::error!("{}", error_msg);
+            tracing::error!("Error context:");
+            tracing::error!("  - Perp address: {}", perp_address);
+            tracing::error!("  - Margin amount: {} USDC", margin_amount);
+            tracing::error!(
+                "  - PerpFactory address: {}",
+                state.contracts.load().perp_factory
+            );
+
+            // The price moved past the caller's limits: retryable, not a server fault.
+            if e.starts_with(SLIPPAGE_EXCEEDED) {
+                return Err((Status::Conflict, e));
+            }
+            Err((Status::InternalServerError, error_msg))
+        }
+    }
+}
+
+/// Most deposits one /batch_deposit_liquidity_for_perps request may carry.
+pub const MAX_BATCH_DEPOSITS: usize = 100;
+
+/// Opens several maker positions, reporting each deposit's outcome.
+///
+/// Deposits run one after another, each validated and sent exactly as
+/// /deposit_liquidity_for_perp would (its own approval and `openMaker` transactions); a
+/// failed deposit is recorded in `errors` and the rest still run. They are not aggregated
+/// through Multicall3: `openMaker` pulls the margin from `msg.sender`, which inside
+/// `aggregate3` would be the Multicall3 contract rather than a pool wallet.
+/// Returns 400 for an empty batch or one over [`MAX_BATCH_DEPOSITS`].
+#[openapi(tag = "Perpetual")]
+#[post("/batch_deposit_liquidity_for_perps", data = "<request>")]
+pub async fn batch_deposit_liquidity_for_perps_endpoint(
+    request: SignedJson<BatchDepositLiquidityForPerpsRequest>,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<BatchDepositLiquidityForPerpsResponse>>, Status> {
+    let total = request.liquidity_deposits.len();
+    tracing::info!(
+        "Received request: POST /batch_deposit_liquidity_for_perps ({} deposits)",
+        total
+    );
+
+    if total == 0 || total > MAX_BATCH_DEPOSITS {
+        tracing::warn!(
+            "Batch deposit request with {} deposits outside 1..={}",
+            total,
+            MAX_BATCH_DEPOSITS
+        );
+        return Err(Status::BadRequest);
+    }
+
+    let mut maker_position_ids = Vec::new();
+    let mut errors = Vec::new();
+    for (i, deposit) in request.liquidity_deposits.iter().enumerate() {
+        match deposit_from_request(state, deposit).await {
+            Ok(response) => {
+                tracing::info!(
+                    "Batch deposit {}/{} opened maker position {}",
+                    i + 1,
+                    total,
+                    response.maker_position_id
+                );
+                maker_position_ids.push(response.maker_position_id);
+            }
+            Err(Custom(status, body)) => {
+                let body = body.into_inner();
+                let mut reason = body.message;
+                if let Some(fields) = body.data {
+                    let details: Vec<String> = fields
+                        .iter()
+                        .map(|e| format!("{}: {}", e.field, e.message))
+                        .collect();
+                    reason = format!("{reason}: {}", details.join("; "));
+                }
+                tracing::error!(
+                    "Batch deposit {}/{} failed ({}): {}",
+                    i + 1,
+                    total,
+                    status,
+                    reason
+                );
+                errors.push(format!(
+                    "Deposit {} (perp {}): {reason}",
+                    i + 1,
+                    deposit.perp_address
+                ));
+            }
+        }
+    }
+
+    let response = BatchDepositLiquidityForPerpsResponse {
+        deposited_count: maker_position_ids.len() as u32,
+        maker_position_ids,
+        failed_count: errors.len() as u32,
+        errors,
+    };
+    let message = format!(
+        "Deposited {} of {} liquidity positions",
+        response.deposited_count, total
+    );
+    Ok(Json(ApiResponse {
+        success: response.failed_count == 0,
+        data: Some(response),
+        message,
+    }))
+}
+
+/// Error response of /close_maker_position: the status code with a JSON body whose
+/// `message` says why the position was not closed.
+pub type CloseMakerError = Custom<Json<ApiResponse<()>>>;
+
+fn close_error(status: Status, message: String) -> CloseMakerError {
+    Custom(
+        status,
+        Json(ApiResponse {
+            success: false,
+            data: None,
+            message,
+        }),
+    )
+}
+
+/// Closes a maker position opened through /deposit_liquidity_for_perp.
+///
+/// Removes all of the position's liquidity with `Perp.adjustMaker`, sent from the pool wallet
+/// holding the position, and returns the USDC paid out with the PnL realized against the
+/// position's margin. A malformed address or id returns 400, an unknown position 404, and a
+/// taker, already-closed or foreign-held position 409.
+#[openapi(tag = "Perpetual")]
+#[post("/close_maker_position", data = "<request>")]
+pub async fn close_maker_position_endpoint(
+    request: SignedJson<CloseMakerPositionRequest>,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<CloseMakerPositionResponse>>, CloseMakerError> {
+    tracing::info!("Received request: POST /close_maker_position");
+
+    let perp_address = parse_perp_address(&request.perp_address, state.strict_address_checksum)
+        .map_err(|status| {
+            close_error(
+                status,
+                format!("Invalid perp_address '{}'", request.perp_address),
+            )
+        })?;
+    let raw_id = request.maker_position_id.trim();
+    let pos_id = match U256::from_str_radix(raw_id, 10) {
+        Ok(id) if !raw_id.is_empty() => id,
+        _ => {
+            let error_msg = format!(
+                "Invalid maker_position_id '{}': expected a decimal integer",
+                request.maker_position_id
+            );
+            tracing::error!("{}", error_msg);
+            return Err(close_error(Status::BadRequest, error_msg));
+        }
+    };
+
+    // Only positions on our own markets: the close is sent from a pool wallet.
+    let factory = IPerpFactory::new(
+        state.contracts.load().perp_factory,
+        &state.provider.read_provider,
+    );
+    match factory.perps(perp_address).call().await {
+        Ok(true) => {}
+        Ok(false) => {
+            let error_msg = format!(
+                "perp_address {perp_address} is not registered with PerpFactory {}",
+                state.contracts.load().perp_factory
+            );
+            tracing::error!("{}", error_msg);
+            return Err(close_error(Status::BadRequest, error_msg));
+        }
+        Err(e) => {
+            let error_msg =
+                format!("Failed to verify perp_address {perp_address} with factory: {e}");
+            tracing::error!("{}", error_msg);
+            return Err(close_error(Status::InternalServerError, error_msg));
+        }
+    }
+
+    match close_maker_position(state, perp_address, pos_id).await {
+        Ok(response) => {
             tracing::info!(
-                "Approval transaction: {}",
-                response.approval_transaction_hash
+                "Maker position {} closed in {}",
+                response.maker_position_id,
+                response.transaction_hash
             );
-            tracing::info!("Deposit transaction: {}", response.deposit_transaction_hash);
             Ok(Json(ApiResponse {
                 success: true,
                 data: Some(response),
-                message: message.to_string(),
+                message: "Maker position closed successfully".to_string(),
             }))
         }
-        Err(e) => {
-            let error_msg = format!(
-                "Failed to deposit liquidity for perp {}: {e}",
-                request.perp_address
+        Err(error) if error.is_client_error() => {
+            tracing::warn!(code = error.code(), "{error}");
+            Err(close_error(beacon_error_status(&error), error.to_string()))
+        }
+        Err(error) => {
+            tracing::error!(
+                code = error.code(),
+                "Failed to close maker position {} on perp {}: {}",
+                pos_id,
+                perp_address,
+                error
             );
-            tracing::error!("{}", error_msg);
-            tracing::error!("Error context:");
-            tracing::error!("  - Perp address: {}", request.perp_address);
-            tracing::error!("  - Margin amount: {} USDC", request.margin_amount_usdc);
-            tracing::error!("  - PerpFactory address: {}", state.contracts.perp_factory);
+            Err(close_error(beacon_error_status(&error), error.to_string()))
+        }
+    }
+}
+
+/// Deposits liquidity between two prices instead of two ticks.
+///
+/// Converts `price_lower` / `price_upper` to ticks, snaps them to the nearest multiple of
+/// `tick_spacing`, and deposits as /deposit_liquidity_for_perp would. The response carries
+/// the ticks actually used. A price range narrower than one tick spacing returns 400.
+#[openapi(tag = "Perpetual")]
+#[post("/deposit_liquidity_by_price", data = "<request>")]
+pub async fn deposit_liquidity_by_price_endpoint(
+    request: SignedJson<DepositLiquidityByPriceRequest>,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<DepositLiquidityByPriceResponse>>, Status> {
+    tracing::info!("Received request: POST /deposit_liquidity_by_price");
+
+    let perp_address = parse_perp_address(&request.perp_address, state.strict_address_checksum)?;
+    let margin_amount = parse_margin_amount(&request.margin_amount_usdc)?;
+    let tick_spacing = request.tick_spacing.unwrap_or(state.perp.tick_spacing);
+
+    let (tick_lower, tick_upper) =
+        match ticks_for_price_range(request.price_lower, request.price_upper, tick_spacing) {
+            Ok(ticks) => ticks,
+            Err(e) => {
+                tracing::error!("Invalid price range: {}", e);
+                return Err(Status::BadRequest);
+            }
+        };
+    tracing::info!(
+        "Price range [{}, {}] snapped to ticks [{}, {}] (spacing {})",
+        request.price_lower,
+        request.price_upper,
+        tick_lower,
+        tick_upper,
+        tick_spacing
+    );
+
+    let deposit = deposit_at_ticks(
+        state,
+        perp_address,
+        margin_amount,
+        tick_spacing,
+        tick_lower,
+        tick_upper,
+        None,
+        None,
+    )
+    .await
+    .map_err(|(status, _)| status)?;
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(DepositLiquidityByPriceResponse {
+            deposit,
+            tick_lower,
+            tick_upper,
+            tick_spacing,
+        }),
+        message: "Liquidity deposited successfully".to_string(),
+    }))
+}
+
+/// Returns the deposit limits and defaults this instance enforces.
+///
+/// Public: the values are non-sensitive configuration, and front-ends use them to check
+/// a deposit's margin and ticks before submitting it.
+#[openapi(tag = "Perpetual")]
+#[get("/perp_config")]
+pub fn get_perp_config(
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<PerpConfigResponse>>, Status> {
+    tracing::info!("Received request: GET /perp_config");
+
+    let config = &state.perp;
+    let min_margin = config.calculate_minimum_margin_usdc().map_err(|e| {
+        tracing::error!("Failed to compute minimum margin: {}", e);
+        Status::InternalServerError
+    })?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(PerpConfigResponse {
+            min_margin_usdc: min_margin.to_string(),
+            min_margin_usdc_decimal: format_usdc(U256::from(min_margin)),
+            max_margin_usdc: config.max_margin_usdc.to_string(),
+            max_margin_usdc_decimal: format_usdc(U256::from(config.max_margin_usdc)),
+            tick_spacing: config.tick_spacing,
+            tick_lower: config.tick_lower,
+            tick_upper: config.tick_upper,
+            min_tick: MIN_TICK,
+            max_tick: MAX_TICK,
+            approve_max: config.approve_max,
+        }),
+        message: "Perp deposit configuration".to_string(),
+    }))
+}
+
+/// Shows the `PerpFactory.createPerp` arguments /deploy_perp_for_beacon would send,
+/// without sending anything.
+///
+/// Takes the deploy request's fields as query parameters and builds the call with the
+/// same code as the deploy, so operators can check the server-configured modules, the
+/// encoded `emaWindow` and the salt (derived when omitted) before committing.
+/// `calldata` is the exact transaction input.
+#[openapi(tag = "Perpetual")]
+#[get(
+    "/perp_config/preview?<beacon_address>&<owner>&<name>&<symbol>&<token_uri>&<ema_window>&<salt>"
+)]
+#[allow(clippy::too_many_arguments)]
+pub fn preview_create_perp(
+    beacon_address: String,
+    owner: String,
+    name: String,
+    symbol: String,
+    token_uri: String,
+    ema_window: u32,
+    salt: Option<String>,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<CreatePerpPreviewResponse>>, Status> {
+    tracing::info!("Received request: GET /perp_config/preview");
+
+    let request = DeployPerpForBeaconRequest {
+        beacon_address,
+        owner,
+        name,
+        symbol,
+        token_uri,
+        ema_window,
+        salt,
+        verbose: false,
+    };
+    let (beacon_address, owner, salt) =
+        parse_deploy_request(&request, state.strict_address_checksum)?;
+    let contracts = state.contracts.load();
+    let call = build_create_perp_call(
+        &contracts,
+        beacon_address,
+        owner,
+        request.name.clone(),
+        request.symbol.clone(),
+        request.token_uri.clone(),
+        request.ema_window,
+        salt,
+    )
+    .map_err(|e| {
+        tracing::error!("Failed to build createPerp call: {}", e);
+        Status::BadRequest
+    })?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(CreatePerpPreviewResponse {
+            perp_factory_address: contracts.perp_factory.to_string(),
+            owner: call.owner.to_string(),
+            modules: CreatePerpModules {
+                beacon: call.modules.beacon.to_string(),
+                fees: call.modules.fees.to_string(),
+                funding: call.modules.funding.to_string(),
+                margin_ratios: call.modules.marginRatios.to_string(),
+                price_impact: call.modules.priceImpact.to_string(),
+                pricing: call.modules.pricing.to_string(),
+            },
+            ema_window: call.emaWindow.to::<u32>(),
+            salt: format!("{:#x}", call.salt),
+            salt_derived: request.salt.is_none(),
+            calldata: format!("0x{}", hex::encode(SolCall::abi_encode(&call))),
+            name: call.name,
+            symbol: call.symbol,
+            token_uri: call.tokenUri,
+        }),
+        message: "createPerp parameters for this deploy".to_string(),
+    }))
+}
+
+/// Checks a deposit's margin and ticks without sending anything.
+///
+/// Runs the same off-chain checks as /deposit_liquidity_for_perp (tick range, margin
+/// bounds, non-zero liquidity). A deposit that would be rejected still returns 200 with
+/// `valid: false` and the reason; only an unparseable margin is a 400.
+#[openapi(tag = "Perpetual")]
+#[post("/preview_deposit", data = "<request>")]
+pub fn preview_deposit(
+    request: SignedJson<PreviewDepositRequest>,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<PreviewDepositResponse>>, Status> {
+    tracing::info!("Received request: POST /preview_deposit");
+
+    let margin_amount = parse_margin_amount(&request.margin_amount_usdc)?;
+    let config = &state.perp;
+    let (tick_spacing, tick_lower, tick_upper) = resolve_deposit_ticks(
+        config,
+        request.tick_spacing,
+        request.tick_lower,
+        request.tick_upper,
+    );
+
+    let (liquidity, reason) =
+        match config.check_deposit(margin_amount, tick_spacing, tick_lower, tick_upper) {
+            Ok(liquidity) => (Some(liquidity.to_string()), None),
+            Err(e) => (None, Some(e)),
+        };
+    let message = match &reason {
+        None => "Deposit would be accepted".to_string(),
+        Some(reason) => format!("Deposit would be rejected: {reason}"),
+    };
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(PreviewDepositResponse {
+            valid: reason.is_none(),
+            reason,
+            liquidity,
+            min_margin_usdc_decimal: format_usdc(U256::from(config.min_margin_usdc)),
+            max_margin_usdc_decimal: format_usdc(U256::from(config.max_margin_usdc)),
+            tick_spacing,
+            tick_lower,
+            tick_upper,
+        }),
+        message,
+    }))
+}
+
+/// Looks up a per-market `Perp` contract deployed by the configured PerpFactory.
+///
+/// Read-only existence check: returns 404 when the address is not registered with the
+/// factory, so clients can verify a market before attempting a liquidity deposit.
+#[openapi(tag = "Perpetual")]
+#[get("/perp/<perp_address>")]
+pub async fn get_perp_endpoint(
+    perp_address: &str,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<PerpInfoResponse>>, Status> {
+    tracing::info!("Received request: GET /perp/{}", perp_address);
 
-            Err(Status::InternalServerError)
+    let address = match parse_address(perp_address, state.strict_address_checksum) {
+        Ok(addr) => addr,
+        Err(e) => {
+            tracing::error!("Invalid perp address '{}': {e}", perp_address);
+            return Err(Status::BadRequest);
+        }
+    };
+
+    match get_perp_info(state, address).await {
+        Ok(info) => Ok(Json(ApiResponse {
+            success: true,
+            data: Some(info),
+            message: "Perp retrieved".to_string(),
+        })),
+        Err(e @ BeaconError::NotRegistered(_)) => {
+            tracing::warn!("{e}");
+            Err(beacon_error_status(&e))
+        }
+        Err(e) => {
+            tracing::error!(code = e.code(), "Failed to look up perp {}: {}", address, e);
+            Err(beacon_error_status(&e))
+        }
+    }
+}
+
+/// Reads the current state of a maker position: range, liquidity, margin, capacity and the
+/// funding / earnings accumulators recorded at entry (X96 values rendered as decimals).
+///
+/// Returns 404 when the Perp is not from PerpFactory or the id is not an open maker position.
+#[openapi(tag = "Perpetual")]
+#[get("/maker_position/<perp_address>/<maker_position_id>")]
+pub async fn get_maker_position_endpoint(
+    perp_address: &str,
+    maker_position_id: &str,
+    _token: ApiToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<MakerInfoResponse>>, Status> {
+    tracing::info!(
+        "Received request: GET /maker_position/{}/{}",
+        perp_address,
+        maker_position_id
+    );
+
+    let address = match parse_address(perp_address, state.strict_address_checksum) {
+        Ok(addr) => addr,
+        Err(e) => {
+            tracing::error!("Invalid perp address '{}': {e}", perp_address);
+            return Err(Status::BadRequest);
+        }
+    };
+    let pos_id = match U256::from_str_radix(maker_position_id, 10) {
+        Ok(id) if !maker_position_id.is_empty() => id,
+        _ => {
+            tracing::error!("Invalid maker_position_id '{}'", maker_position_id);
+            return Err(Status::BadRequest);
+        }
+    };
+
+    match get_maker_position(state, address, pos_id).await {
+        Ok(info) => Ok(Json(ApiResponse {
+            success: true,
+            data: Some(info),
+            message: "Maker position retrieved".to_string(),
+        })),
+        Err(e @ BeaconError::NotRegistered(_)) => {
+            tracing::warn!("{e}");
+            Err(beacon_error_status(&e))
+        }
+        Err(e) => {
+            tracing::error!(
+                code = e.code(),
+                "Failed to read maker position {} on perp {}: {}",
+                pos_id,
+                address,
+                e
+            );
+            Err(beacon_error_status(&e))
         }
     }
 }
@@ -2,7 +2,7 @@ use alloy::primitives::{Address, U256};
 use alloy::providers::Provider;
 use alloy::rpc::types::TransactionRequest;
 use rocket::serde::json::Json;
-use rocket::{State, http::Status, post};
+use rocket::{State, get, http::Status, post};
 use rocket_okapi::openapi;
 use std::str::FromStr;
 use std::time::Duration;
@@ -13,10 +13,18 @@ use tracing;
 const FUNDING_RECEIPT_TIMEOUT: Duration = Duration::from_secs(120);
 
 use super::{IERC20, ITestnetUSDC};
-use crate::guards::{AdminToken, ApiToken};
+use crate::guards::{AdminToken, ApiToken, SignedJson, validate_idempotency_key};
 use crate::models::{
-    ApiResponse, AppState, FundBonusWalletRequest, FundGuestWalletRequest, TopUpPoolRequest,
+    ApiResponse, AppState, BeaconDesignationRequest, BeaconDesignationResponse,
+    ForceUnlockWalletRequest, ForceUnlockWalletResponse, FundBonusWalletRequest,
+    FundGuestWalletRequest, FundingWalletBalance, FundingWalletStatusResponse, MaintenanceResponse,
+    SweepWalletRequest, SweepWalletResponse, TopUpPoolRequest, WalletPoolStatusResponse,
 };
+use crate::services::address::parse_address;
+use crate::services::idempotency::{IdempotencyStore, Reservation};
+use crate::services::metrics::GasOperation;
+use crate::services::rpc::retry_read;
+use crate::services::wallet::{ForceUnlockOutcome, FundingRateLimiter};
 
 /// Default per-wallet USDC balance target for `/top_up_pool`: 10,000 USDC.
 const DEFAULT_TOP_UP_USDC_TARGET: u128 = 10_000_000_000;
@@ -29,16 +37,29 @@ fn is_production_chain(chain_id: u64) -> bool {
     !matches!(chain_id, 421614 | 31337)
 }
 
+/// Whether a wallet holding `balance` wei can send `amount` wei and still keep `reserve`
+/// (`FAUCET_RESERVE_ETH_WEI`) for its own beacon/perp gas. Landing exactly on the
+/// reserve is allowed.
+pub fn eth_send_keeps_reserve(balance: U256, amount: U256, reserve: U256) -> bool {
+    amount
+        .checked_add(reserve)
+        .is_some_and(|required| balance >= required)
+}
+
 /// Funds a guest wallet with USDC and ETH.
 ///
 /// Transfers the specified amounts of USDC and ETH from the beaconator wallet
-/// to the guest wallet address. Validates transfer limits and available balances.
+/// to the guest wallet address. Validates per-request transfer limits, the
+/// per-recipient rolling-window caps (429 once exhausted), and available balances.
+/// Send a `request_id` to make retries safe: a repeat request with the same id and
+/// access token within the idempotency TTL returns the original transaction hashes
+/// without sending new transfers, or 409 while the first request is still running.
 #[openapi(tag = "Wallet")]
 #[post("/fund_guest_wallet", format = "json", data = "<request>")]
 pub async fn fund_guest_wallet(
     state: &State<AppState>,
-    request: Json<FundGuestWalletRequest>,
-    _token: ApiToken,
+    request: SignedJson<FundGuestWalletRequest>,
+    token: ApiToken,
 ) -> Result<Json<ApiResponse<String>>, (Status, Json<ApiResponse<String>>)> {
     tracing::info!("Received request: POST /fund_guest_wallet");
 
@@ -62,17 +83,113 @@ pub async fn fund_guest_wallet(
             }),
         ));
     }
-    let wallet_address = match Address::from_str(&request.wallet_address) {
+
+    let cache_key = match request.request_id.as_deref().map(validate_idempotency_key) {
+        Some(Ok(request_id)) => Some(IdempotencyStore::<String>::scoped_key(
+            token.bearer(),
+            &request_id,
+        )),
+        Some(Err(e)) => {
+            return Err((
+                Status::BadRequest,
+                Json(ApiResponse {
+                    success: false,
+                    data: None,
+                    message: format!("Invalid request_id: {e}"),
+                }),
+            ));
+        }
+        None => None,
+    };
+
+    if let Some(cache_key) = &cache_key {
+        match state.wallets.funding_replays.reserve(cache_key).await {
+            Ok(Reservation::Reserved) => {}
+            Ok(Reservation::Completed(cached)) => {
+                tracing::info!("Replaying cached guest funding result for request_id");
+                return Ok(Json(ApiResponse {
+                    success: true,
+                    data: Some(cached),
+                    message: "Returning original result for repeated request_id".to_string(),
+                }));
+            }
+            Ok(Reservation::InFlight) => {
+                return Err((
+                    Status::Conflict,
+                    Json(ApiResponse {
+                        success: false,
+                        data: None,
+                        message: "A request with this request_id is still in progress".to_string(),
+                    }),
+                ));
+            }
+            Err(e) => {
+                tracing::error!("Failed to reserve funding request_id: {}", e);
+                return Err((
+                    Status::ServiceUnavailable,
+                    Json(ApiResponse {
+                        success: false,
+                        data: None,
+                        message: "Idempotency store unavailable; retry shortly".to_string(),
+                    }),
+                ));
+            }
+        }
+    }
+
+    match send_guest_funding(state.inner(), &request).await {
+        Ok(result) => {
+            if let Some(cache_key) = cache_key {
+                state
+                    .wallets
+                    .funding_replays
+                    .insert(cache_key, result.clone())
+                    .await;
+            }
+            Ok(Json(ApiResponse {
+                success: true,
+                data: Some(result),
+                message: "Guest wallet funded successfully".to_string(),
+            }))
+        }
+        Err(FundingFailure::NothingSent(rejection)) => {
+            if let Some(cache_key) = &cache_key {
+                state.wallets.funding_replays.release(cache_key).await;
+            }
+            Err(rejection)
+        }
+        // The request_id stays claimed until it expires, so a blind retry cannot send
+        // the transfers a second time.
+        Err(FundingFailure::Sent(rejection)) => Err(rejection),
+    }
+}
+
+/// A failed guest funding, split by whether any transfer may have left the pool.
+enum FundingFailure {
+    /// Rejected, or failed before a transfer took effect: a retry sends afresh.
+    NothingSent((Status, Json<ApiResponse<String>>)),
+    /// A transfer was broadcast; the caller must verify on-chain before retrying.
+    Sent((Status, Json<ApiResponse<String>>)),
+}
+
+/// Validate a guest funding request against the transfer limits and the recipient's
+/// rolling-window caps, then send it. Returns the summary `fund_guest_wallet` replies with.
+async fn send_guest_funding(
+    state: &AppState,
+    request: &FundGuestWalletRequest,
+) -> Result<String, FundingFailure> {
+    let wallet_address = match parse_address(&request.wallet_address, state.strict_address_checksum)
+    {
         Ok(addr) => addr,
         Err(e) => {
-            return Err((
+            return Err(FundingFailure::NothingSent((
                 Status::BadRequest,
                 Json(ApiResponse {
                     success: false,
                     data: None,
                     message: format!("Invalid wallet address: {e}"),
                 }),
-            ));
+            )));
         }
     };
 
@@ -80,34 +197,34 @@ pub async fn fund_guest_wallet(
     let usdc_amount = match request.usdc_amount.parse::<u128>() {
         Ok(amount) => amount,
         Err(e) => {
-            return Err((
+            return Err(FundingFailure::NothingSent((
                 Status::BadRequest,
                 Json(ApiResponse {
                     success: false,
                     data: None,
                     message: format!("Invalid USDC amount: {e}"),
                 }),
-            ));
+            )));
         }
     };
 
     let eth_amount = match request.eth_amount.parse::<u128>() {
         Ok(amount) => amount,
         Err(e) => {
-            return Err((
+            return Err(FundingFailure::NothingSent((
                 Status::BadRequest,
                 Json(ApiResponse {
                     success: false,
                     data: None,
                     message: format!("Invalid ETH amount: {e}"),
                 }),
-            ));
+            )));
         }
     };
 
     // Check transfer limits
     if usdc_amount > state.wallets.usdc_transfer_limit {
-        return Err((
+        return Err(FundingFailure::NothingSent((
             Status::BadRequest,
             Json(ApiResponse {
                 success: false,
@@ -118,11 +235,11 @@ pub async fn fund_guest_wallet(
                     state.wallets.usdc_transfer_limit / 1_000_000
                 ),
             }),
-        ));
+        )));
     }
 
     if eth_amount > state.wallets.eth_transfer_limit {
-        return Err((
+        return Err(FundingFailure::NothingSent((
             Status::BadRequest,
             Json(ApiResponse {
                 success: false,
@@ -135,9 +252,59 @@ pub async fn fund_guest_wallet(
                     ))
                 ),
             }),
-        ));
+        )));
+    }
+
+    // Per-recipient rolling-window cap: the per-request limits above bound a single
+    // call, this bounds the total any one address can pull from the pool.
+    // The grant counts from here, so concurrent requests for this recipient cannot all
+    // pass against the same remaining room; it is given back if nothing is sent.
+    let funding_limiter = &state.wallets.funding_limiter;
+    let grant = match funding_limiter
+        .reserve(
+            wallet_address,
+            usdc_amount,
+            eth_amount,
+            FundingRateLimiter::now_secs(),
+        )
+        .await
+    {
+        Ok(grant) => grant,
+        Err(exceeded) => {
+            tracing::warn!(
+                "Guest funding refused for {}: {} (resets in {}s)",
+                wallet_address,
+                exceeded.reason,
+                exceeded.retry_after_secs
+            );
+            return Err(FundingFailure::NothingSent((
+                Status::TooManyRequests,
+                Json(ApiResponse {
+                    success: false,
+                    data: None,
+                    message: format!(
+                        "{}. Try again in {}s.",
+                        exceeded.reason, exceeded.retry_after_secs
+                    ),
+                }),
+            )));
+        }
+    };
+
+    let result = transfer_guest_funding(state, wallet_address, usdc_amount, eth_amount).await;
+    if let Err(FundingFailure::NothingSent(_)) = &result {
+        funding_limiter.release(&grant).await;
     }
+    result
+}
 
+/// Pick a pool wallet that can cover the funding and send the ETH and USDC transfers.
+async fn transfer_guest_funding(
+    state: &AppState,
+    wallet_address: Address,
+    usdc_amount: u128,
+    eth_amount: u128,
+) -> Result<String, FundingFailure> {
     tracing::info!(
         "Funding guest wallet: {} with {} USDC and {} ETH",
         wallet_address,
@@ -182,24 +349,32 @@ pub async fn fund_guest_wallet(
                         message: "Funding wallet temporarily unavailable".to_string(),
                     }),
                 )
-            })?;
+            })
+            .map_err(FundingFailure::NothingSent)?;
         let candidate = handle.address();
         let last_attempt = attempt == max_wallet_attempts;
 
         // Check pool wallet ETH balance using read provider
-        let eth_balance = match state.provider.read_provider.get_balance(candidate).await {
+        let read_provider = &*state.provider.read_provider;
+        let eth_balance = match retry_read(
+            &state.provider.read_retry,
+            "eth_getBalance",
+            move || async move { read_provider.get_balance(candidate).await },
+        )
+        .await
+        {
             Ok(balance) => balance,
             Err(e) => {
                 let detailed_error = format!("Failed to get ETH balance: {e}");
                 tracing::error!("{}", detailed_error);
-                return Err((
+                return Err(FundingFailure::NothingSent((
                     Status::InternalServerError,
                     Json(ApiResponse {
                         success: false,
                         data: None,
                         message: "Failed to retrieve ETH balance".to_string(),
                     }),
-                ));
+                )));
             }
         };
 
@@ -207,55 +382,54 @@ pub async fn fund_guest_wallet(
         // floor the wallet must retain for beacon-update gas. Without the
         // reserve, faucet traffic can drain the pool below the
         // BeaconatorWalletGasLow paging threshold and freeze beacon updates.
-        let eth_required =
-            U256::from(eth_amount) + U256::from(state.wallets.faucet_reserve_eth_wei);
-        if eth_balance < eth_required {
+        let eth_reserve = U256::from(state.wallets.faucet_reserve_eth_wei);
+        let eth_required = U256::from(eth_amount) + eth_reserve;
+        if !eth_send_keeps_reserve(eth_balance, U256::from(eth_amount), eth_reserve) {
             tracing::warn!(
                 "Pool wallet {} cannot fund guest without breaching the ETH reserve. \
                  Have: {} ETH, Need: {} ETH (transfer + {} ETH reserve)",
                 candidate,
                 alloy::primitives::utils::format_ether(eth_balance),
                 alloy::primitives::utils::format_ether(eth_required),
-                alloy::primitives::utils::format_ether(U256::from(
-                    state.wallets.faucet_reserve_eth_wei
-                ))
+                alloy::primitives::utils::format_ether(eth_reserve)
             );
             if !last_attempt {
                 excluded_wallets.insert(candidate);
                 drop(handle);
                 continue;
             }
-            return Err((
+            return Err(FundingFailure::NothingSent((
                 Status::ServiceUnavailable,
                 Json(ApiResponse {
                     success: false,
                     data: None,
                     message: format!(
-                        "Guest funding refused: every pool wallet is at its ETH reserve floor \
-                         ({} ETH, kept for beacon gas). Top up the pool and retry.",
-                        alloy::primitives::utils::format_ether(U256::from(
-                            state.wallets.faucet_reserve_eth_wei
-                        ))
+                        "Faucet reserve exhausted: sending {} ETH would take every pool \
+                         wallet below its {} ETH reserve (kept for beacon gas). Top up the \
+                         pool and retry.",
+                        alloy::primitives::utils::format_ether(U256::from(eth_amount)),
+                        alloy::primitives::utils::format_ether(eth_reserve)
                     ),
                 }),
-            ));
+            )));
         }
 
         // Check USDC balance using read provider
-        let usdc_read_contract = IERC20::new(state.contracts.usdc, &*state.provider.read_provider);
+        let usdc_read_contract =
+            IERC20::new(state.contracts.load().usdc, &*state.provider.read_provider);
         let usdc_balance = match usdc_read_contract.balanceOf(candidate).call().await {
             Ok(result) => result,
             Err(e) => {
                 let detailed_error = format!("Failed to get USDC balance: {e}");
                 tracing::error!("{}", detailed_error);
-                return Err((
+                return Err(FundingFailure::NothingSent((
                     Status::InternalServerError,
                     Json(ApiResponse {
                         success: false,
                         data: None,
                         message: "Failed to retrieve USDC balance".to_string(),
                     }),
-                ));
+                )));
             }
         };
 
@@ -272,7 +446,7 @@ pub async fn fund_guest_wallet(
                 drop(handle);
                 continue;
             }
-            return Err((
+            return Err(FundingFailure::NothingSent((
                 Status::InternalServerError,
                 Json(ApiResponse {
                     success: false,
@@ -283,7 +457,7 @@ pub async fn fund_guest_wallet(
                         usdc_amount / 1_000_000
                     ),
                 }),
-            ));
+            )));
         }
 
         wallet_handle = Some(handle);
@@ -296,7 +470,7 @@ pub async fn fund_guest_wallet(
     // Build a provider from the pool wallet's signer (local key or KMS, depending on
     // deployment) to send the two on-chain transfers below.
     let funding_provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| {
             let detailed_error = format!("Failed to build funding provider: {e}");
             tracing::error!("{}", detailed_error);
@@ -308,22 +482,54 @@ pub async fn fund_guest_wallet(
                     message: "Server RPC configuration is invalid".to_string(),
                 }),
             )
-        })?;
+        })
+        .map_err(FundingFailure::NothingSent)?;
 
     // Send ETH using funding provider
     let tx_request = TransactionRequest::default()
         .to(wallet_address)
         .value(U256::from(eth_amount));
 
-    let eth_tx_hash = match funding_provider.send_transaction(tx_request).await {
+    let (eth_tx_hash, eth_gas_used) = match funding_provider.send_transaction(tx_request).await {
         Ok(pending) => {
             let tx_hash = *pending.tx_hash();
-            match timeout(FUNDING_RECEIPT_TIMEOUT, pending.get_receipt()).await {
-                Ok(Ok(receipt)) => receipt.transaction_hash,
+            let receipt = timeout(FUNDING_RECEIPT_TIMEOUT, pending.get_receipt()).await;
+            if let Ok(Ok(receipt)) = &receipt {
+                state
+                    .history
+                    .record_receipt(
+                        GasOperation::Funding,
+                        &[
+                            ("action", "fund_guest_wallet".to_string()),
+                            ("asset", "ETH".to_string()),
+                            ("wallet_address", wallet_address.to_string()),
+                            ("amount", eth_amount.to_string()),
+                        ],
+                        receipt,
+                    )
+                    .await;
+            }
+            match receipt {
+                // A reverted transfer still comes back with a receipt; no ETH moved.
+                Ok(Ok(receipt)) if !receipt.status() => {
+                    tracing::error!("ETH transfer reverted on-chain (tx {tx_hash:?})");
+                    return Err(FundingFailure::NothingSent((
+                        Status::InternalServerError,
+                        Json(ApiResponse {
+                            success: false,
+                            data: None,
+                            message: format!(
+                                "ETH transfer reverted on-chain (tx {tx_hash:?}); no ETH or USDC \
+                                 was sent"
+                            ),
+                        }),
+                    )));
+                }
+                Ok(Ok(receipt)) => (receipt.transaction_hash, receipt.gas_used),
                 Ok(Err(e)) => {
                     let detailed_error = format!("Failed to get ETH transaction receipt: {e}");
                     tracing::error!("{}", detailed_error);
-                    return Err((
+                    return Err(FundingFailure::Sent((
                         Status::InternalServerError,
                         Json(ApiResponse {
                             success: false,
@@ -334,7 +540,7 @@ pub async fn fund_guest_wallet(
                                  double-funding"
                             ),
                         }),
-                    ));
+                    )));
                 }
                 Err(_) => {
                     let detailed_error = format!(
@@ -342,7 +548,7 @@ pub async fn fund_guest_wallet(
                         FUNDING_RECEIPT_TIMEOUT.as_secs()
                     );
                     tracing::error!("{}", detailed_error);
-                    return Err((
+                    return Err(FundingFailure::Sent((
                         Status::InternalServerError,
                         Json(ApiResponse {
                             success: false,
@@ -353,21 +559,21 @@ pub async fn fund_guest_wallet(
                                 FUNDING_RECEIPT_TIMEOUT.as_secs()
                             ),
                         }),
-                    ));
+                    )));
                 }
             }
         }
         Err(e) => {
             let detailed_error = format!("Failed to send ETH: {e}");
             tracing::error!("{}", detailed_error);
-            return Err((
+            return Err(FundingFailure::NothingSent((
                 Status::InternalServerError,
                 Json(ApiResponse {
                     success: false,
                     data: None,
                     message: "Failed to send ETH".to_string(),
                 }),
-            ));
+            )));
         }
     };
 
@@ -378,7 +584,7 @@ pub async fn fund_guest_wallet(
     if let Err(e) = wallet_handle.ensure_lock_held() {
         let detailed_error = format!("Pool wallet lock lost before USDC transfer: {e}");
         tracing::error!("{}", detailed_error);
-        return Err((
+        return Err(FundingFailure::Sent((
             Status::InternalServerError,
             Json(ApiResponse {
                 success: false,
@@ -387,11 +593,11 @@ pub async fn fund_guest_wallet(
                     "ETH sent (tx {eth_tx_hash:?}), but USDC transfer was aborted: {e}"
                 ),
             }),
-        ));
+        )));
     }
 
     // Send USDC using funding provider
-    let usdc_send_contract = IERC20::new(state.contracts.usdc, &funding_provider);
+    let usdc_send_contract = IERC20::new(state.contracts.load().usdc, &funding_provider);
     let usdc_receipt = match usdc_send_contract
         .transfer(wallet_address, U256::from(usdc_amount))
         .send()
@@ -399,12 +605,42 @@ pub async fn fund_guest_wallet(
     {
         Ok(pending) => {
             let usdc_tx_hash = *pending.tx_hash();
-            match timeout(FUNDING_RECEIPT_TIMEOUT, pending.get_receipt()).await {
+            let receipt = timeout(FUNDING_RECEIPT_TIMEOUT, pending.get_receipt()).await;
+            if let Ok(Ok(receipt)) = &receipt {
+                state
+                    .history
+                    .record_receipt(
+                        GasOperation::Funding,
+                        &[
+                            ("action", "fund_guest_wallet".to_string()),
+                            ("asset", "USDC".to_string()),
+                            ("wallet_address", wallet_address.to_string()),
+                            ("amount", usdc_amount.to_string()),
+                        ],
+                        receipt,
+                    )
+                    .await;
+            }
+            match receipt {
+                Ok(Ok(receipt)) if !receipt.status() => {
+                    tracing::error!("USDC transfer reverted on-chain (tx {usdc_tx_hash:?})");
+                    return Err(FundingFailure::Sent((
+                        Status::InternalServerError,
+                        Json(ApiResponse {
+                            success: false,
+                            data: None,
+                            message: format!(
+                                "ETH sent (tx {eth_tx_hash:?}), but the USDC transfer reverted \
+                                 on-chain (tx {usdc_tx_hash:?}); no USDC moved"
+                            ),
+                        }),
+                    )));
+                }
                 Ok(Ok(receipt)) => receipt,
                 Ok(Err(e)) => {
                     let detailed_error = format!("Failed to get USDC transaction receipt: {e}");
                     tracing::error!("{}", detailed_error);
-                    return Err((
+                    return Err(FundingFailure::Sent((
                         Status::InternalServerError,
                         Json(ApiResponse {
                             success: false,
@@ -415,7 +651,7 @@ pub async fn fund_guest_wallet(
                                  to avoid double-funding"
                             ),
                         }),
-                    ));
+                    )));
                 }
                 Err(_) => {
                     let detailed_error = format!(
@@ -423,7 +659,7 @@ pub async fn fund_guest_wallet(
                         FUNDING_RECEIPT_TIMEOUT.as_secs()
                     );
                     tracing::error!("{}", detailed_error);
-                    return Err((
+                    return Err(FundingFailure::Sent((
                         Status::InternalServerError,
                         Json(ApiResponse {
                             success: false,
@@ -435,38 +671,43 @@ pub async fn fund_guest_wallet(
                                 FUNDING_RECEIPT_TIMEOUT.as_secs()
                             ),
                         }),
-                    ));
+                    )));
                 }
             }
         }
         Err(e) => {
             let detailed_error = format!("Failed to send USDC: {e}");
             tracing::error!("{}", detailed_error);
-            return Err((
+            return Err(FundingFailure::Sent((
                 Status::InternalServerError,
                 Json(ApiResponse {
                     success: false,
                     data: None,
                     message: format!("ETH sent (tx {eth_tx_hash:?}), but USDC send failed"),
                 }),
-            ));
+            )));
         }
     };
 
     tracing::info!("USDC transfer hash: {:?}", usdc_receipt.transaction_hash);
+    state
+        .gas_metrics
+        .record(GasOperation::Funding, eth_gas_used);
+    state
+        .gas_metrics
+        .record(GasOperation::Funding, usdc_receipt.gas_used);
 
-    Ok(Json(ApiResponse {
-        success: true,
-        data: Some(format!(
-            "Successfully funded wallet {} with {} USDC and {} ETH. ETH tx: {:?}, USDC tx: {:?}",
-            wallet_address,
-            usdc_amount / 1_000_000,
-            alloy::primitives::utils::format_ether(U256::from(eth_amount)),
-            eth_tx_hash,
-            usdc_receipt.transaction_hash
-        )),
-        message: "Guest wallet funded successfully".to_string(),
-    }))
+    Ok(format!(
+        "Successfully funded wallet {} with {} USDC and {} ETH. ETH tx: {:?}, USDC tx: {:?}, \
+         gas used: {} (ETH) + {} (USDC)",
+        wallet_address,
+        usdc_amount / 1_000_000,
+        alloy::primitives::utils::format_ether(U256::from(eth_amount)),
+        eth_tx_hash,
+        usdc_receipt.transaction_hash,
+        eth_gas_used,
+        usdc_receipt.gas_used
+    ))
 }
 
 /// Funds a wallet with the new-user bonus USDC (mainnet-capable).
@@ -483,12 +724,13 @@ pub async fn fund_guest_wallet(
 #[post("/fund_bonus_wallet", format = "json", data = "<request>")]
 pub async fn fund_bonus_wallet(
     state: &State<AppState>,
-    request: Json<FundBonusWalletRequest>,
+    request: SignedJson<FundBonusWalletRequest>,
     _token: ApiToken,
 ) -> Result<Json<ApiResponse<String>>, (Status, Json<ApiResponse<String>>)> {
     tracing::info!("Received request: POST /fund_bonus_wallet");
 
-    let wallet_address = match Address::from_str(&request.wallet_address) {
+    let wallet_address = match parse_address(&request.wallet_address, state.strict_address_checksum)
+    {
         Ok(addr) => addr,
         Err(e) => {
             return Err((
@@ -575,7 +817,8 @@ pub async fn fund_bonus_wallet(
         let last_attempt = attempt == max_wallet_attempts;
 
         // Check pool wallet USDC balance using read provider
-        let usdc_read_contract = IERC20::new(state.contracts.usdc, &*state.provider.read_provider);
+        let usdc_read_contract =
+            IERC20::new(state.contracts.load().usdc, &*state.provider.read_provider);
         let usdc_balance = match usdc_read_contract.balanceOf(candidate).call().await {
             Ok(result) => result,
             Err(e) => {
@@ -628,7 +871,7 @@ pub async fn fund_bonus_wallet(
     // Build a provider from the pool wallet's signer (local key or KMS, depending on
     // deployment) to send the transfer below.
     let funding_provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| {
             let detailed_error = format!("Failed to build funding provider: {e}");
             tracing::error!("{}", detailed_error);
@@ -657,7 +900,7 @@ pub async fn fund_bonus_wallet(
     }
 
     // Send USDC using funding provider.
-    let usdc_send_contract = IERC20::new(state.contracts.usdc, &funding_provider);
+    let usdc_send_contract = IERC20::new(state.contracts.load().usdc, &funding_provider);
     let usdc_receipt = match usdc_send_contract
         .transfer(wallet_address, U256::from(usdc_amount))
         .send()
@@ -665,7 +908,23 @@ pub async fn fund_bonus_wallet(
     {
         Ok(pending) => {
             let usdc_tx_hash = *pending.tx_hash();
-            match timeout(FUNDING_RECEIPT_TIMEOUT, pending.get_receipt()).await {
+            let receipt = timeout(FUNDING_RECEIPT_TIMEOUT, pending.get_receipt()).await;
+            if let Ok(Ok(receipt)) = &receipt {
+                state
+                    .history
+                    .record_receipt(
+                        GasOperation::Funding,
+                        &[
+                            ("action", "fund_bonus_wallet".to_string()),
+                            ("asset", "USDC".to_string()),
+                            ("wallet_address", wallet_address.to_string()),
+                            ("amount", usdc_amount.to_string()),
+                        ],
+                        receipt,
+                    )
+                    .await;
+            }
+            match receipt {
                 // A receipt can come back for a REVERTED transfer — accepting it
                 // would report a successful payout when no USDC moved. Treat a
                 // non-success status as a failure (no funds moved on a revert, so
@@ -741,6 +1000,9 @@ pub async fn fund_bonus_wallet(
         "Bonus USDC transfer hash: {:?}",
         usdc_receipt.transaction_hash
     );
+    state
+        .gas_metrics
+        .record(GasOperation::Funding, usdc_receipt.gas_used);
 
     // `data` is the transaction hash itself (not a sentence) so callers can
     // consume it directly without parsing prose. The human-readable summary
@@ -749,8 +1011,9 @@ pub async fn fund_bonus_wallet(
         success: true,
         data: Some(usdc_receipt.transaction_hash.to_string()),
         message: format!(
-            "Successfully funded wallet {wallet_address} with {} USDC",
-            usdc_amount / 1_000_000
+            "Successfully funded wallet {wallet_address} with {} USDC (gas used: {})",
+            usdc_amount / 1_000_000,
+            usdc_receipt.gas_used
         ),
     }))
 }
@@ -819,7 +1082,8 @@ pub async fn top_up_pool(
     }
 
     // Determine deficits from fresh on-chain balances.
-    let usdc_read_contract = IERC20::new(state.contracts.usdc, &*state.provider.read_provider);
+    let usdc_read_contract =
+        IERC20::new(state.contracts.load().usdc, &*state.provider.read_provider);
     let mut deficits: Vec<(Address, U256)> = Vec::new();
     for &wallet in &pool_addresses {
         let balance = match usdc_read_contract.balanceOf(wallet).call().await {
@@ -868,7 +1132,7 @@ pub async fn top_up_pool(
         })?;
 
     let minter_provider = minter_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| {
             let detailed_error = format!("Failed to build minter provider: {e}");
             tracing::error!("{}", detailed_error);
@@ -882,7 +1146,7 @@ pub async fn top_up_pool(
             )
         })?;
 
-    let usdc_mint_contract = ITestnetUSDC::new(state.contracts.usdc, &minter_provider);
+    let usdc_mint_contract = ITestnetUSDC::new(state.contracts.load().usdc, &minter_provider);
     let mut results: Vec<String> = Vec::new();
     let mut failures = 0usize;
 
@@ -950,4 +1214,681 @@ pub async fn top_up_pool(
     }))
 }
 
+/// Error body shared by the admin wallet routes (sweep, force-unlock).
+fn admin_error(status: Status, message: String) -> (Status, Json<ApiResponse<String>>) {
+    (
+        status,
+        Json(ApiResponse {
+            success: false,
+            data: None,
+            message,
+        }),
+    )
+}
+
+/// Pick the sweep destination: an explicit request address overrides the configured
+/// cold wallet; with neither, the sweep is refused.
+pub fn resolve_sweep_destination(
+    requested: Option<&str>,
+    cold_wallet: Option<Address>,
+) -> Result<Address, String> {
+    let destination = match requested.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(raw) => {
+            Address::from_str(raw).map_err(|e| format!("Invalid destination address: {e}"))?
+        }
+        None => cold_wallet.ok_or_else(|| {
+            "destination_address is required when COLD_WALLET_ADDRESS is not configured".to_string()
+        })?,
+    };
+    if destination == Address::ZERO {
+        return Err("Destination must be a non-zero address".to_string());
+    }
+    Ok(destination)
+}
+
+/// Sweeps a pool wallet's full USDC balance and remaining ETH to a destination (admin).
+///
+/// The destination defaults to `COLD_WALLET_ADDRESS` and can be overridden per request;
+/// either way the request must carry the confirmation token (the wallet address again).
+/// Only wallets whose keys the service holds can be swept; any other address is rejected
+/// with 422. USDC moves first (it needs gas), then the ETH balance minus the transfer's
+/// worst-case gas cost. The ETH leg is skipped when the balance does not cover that cost.
+#[openapi(tag = "Wallet")]
+#[post("/sweep_wallet", format = "json", data = "<request>")]
+pub async fn sweep_wallet(
+    state: &State<AppState>,
+    request: Json<SweepWalletRequest>,
+    _token: AdminToken,
+) -> Result<Json<ApiResponse<SweepWalletResponse>>, (Status, Json<ApiResponse<String>>)> {
+    tracing::info!("Received request: POST /sweep_wallet");
+
+    let wallet_address = parse_address(&request.wallet_address, state.strict_address_checksum)
+        .map_err(|e| admin_error(Status::BadRequest, format!("Invalid wallet address: {e}")))?;
+    if !request
+        .confirmation
+        .trim()
+        .eq_ignore_ascii_case(request.wallet_address.trim())
+    {
+        return Err(admin_error(
+            Status::BadRequest,
+            "confirmation must repeat wallet_address exactly".to_string(),
+        ));
+    }
+    let destination = resolve_sweep_destination(
+        request.destination_address.as_deref(),
+        state.wallets.cold_wallet_address,
+    )
+    .map_err(|e| admin_error(Status::BadRequest, e))?;
+    if destination == wallet_address {
+        return Err(admin_error(
+            Status::BadRequest,
+            "Destination must be different from the swept wallet".to_string(),
+        ));
+    }
+
+    if !state
+        .wallets
+        .manager
+        .signer_addresses()
+        .contains(&wallet_address)
+    {
+        return Err(admin_error(
+            Status::UnprocessableEntity,
+            format!(
+                "Wallet {wallet_address} is not controlled by this service (not a pool wallet); \
+                 it cannot be swept"
+            ),
+        ));
+    }
+
+    // Lock the wallet for the whole sweep so no beacon update or funding request sends
+    // from it concurrently (which would race the nonce and the balance we drain).
+    let wallet_handle = state
+        .wallets
+        .manager
+        .acquire_specific_wallet(&wallet_address)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to acquire wallet {wallet_address} for sweep: {e}");
+            admin_error(
+                Status::ServiceUnavailable,
+                format!("Wallet {wallet_address} is busy; retry the sweep shortly"),
+            )
+        })?;
+    let sweep_provider = wallet_handle
+        .build_provider(&state.provider.endpoints)
+        .map_err(|e| {
+            tracing::error!("Failed to build sweep provider: {e}");
+            admin_error(
+                Status::InternalServerError,
+                "Server RPC configuration is invalid".to_string(),
+            )
+        })?;
+
+    // USDC leg: transfer the full balance.
+    let usdc_read_contract =
+        IERC20::new(state.contracts.load().usdc, &*state.provider.read_provider);
+    let usdc_balance = usdc_read_contract
+        .balanceOf(wallet_address)
+        .call()
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get USDC balance of {wallet_address}: {e}");
+            admin_error(
+                Status::InternalServerError,
+                "Failed to retrieve USDC balance".to_string(),
+            )
+        })?;
+
+    let mut usdc_transaction_hash = None;
+    if usdc_balance > U256::ZERO {
+        let usdc_send_contract = IERC20::new(state.contracts.load().usdc, &sweep_provider);
+        let pending = usdc_send_contract
+            .transfer(destination, usdc_balance)
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to send USDC sweep from {wallet_address}: {e}");
+                admin_error(
+                    Status::InternalServerError,
+                    "Failed to send USDC".to_string(),
+                )
+            })?;
+        let tx_hash = *pending.tx_hash();
+        let receipt = timeout(FUNDING_RECEIPT_TIMEOUT, pending.get_receipt()).await;
+        if let Ok(Ok(receipt)) = &receipt {
+            state
+                .history
+                .record_receipt(
+                    GasOperation::Sweep,
+                    &[
+                        ("asset", "USDC".to_string()),
+                        ("wallet_address", wallet_address.to_string()),
+                        ("destination", destination.to_string()),
+                        ("amount", usdc_balance.to_string()),
+                    ],
+                    receipt,
+                )
+                .await;
+        }
+        match receipt {
+            Ok(Ok(receipt)) if receipt.status() => {
+                usdc_transaction_hash = Some(format!("{:?}", receipt.transaction_hash));
+            }
+            Ok(Ok(_)) => {
+                return Err(admin_error(
+                    Status::InternalServerError,
+                    format!("USDC sweep reverted (tx {tx_hash:?}); ETH was NOT swept"),
+                ));
+            }
+            Ok(Err(_)) | Err(_) => {
+                tracing::error!("USDC sweep receipt unavailable (tx {tx_hash:?})");
+                return Err(admin_error(
+                    Status::InternalServerError,
+                    format!(
+                        "USDC sweep unconfirmed (tx {tx_hash:?}); ETH was NOT swept — verify \
+                         on-chain before retrying"
+                    ),
+                ));
+            }
+        }
+    }
+
+    if let Err(e) = wallet_handle.ensure_lock_held() {
+        tracing::error!("Wallet lock lost before ETH sweep: {e}");
+        return Err(admin_error(
+            Status::InternalServerError,
+            format!("USDC swept, but ETH sweep was aborted: {e}"),
+        ));
+    }
+
+    // ETH leg: read the balance AFTER the USDC transfer paid its gas, and send everything
+    // above the worst-case cost of this transfer.
+    let eth_balance = state
+        .provider
+        .read_provider
+        .get_balance(wallet_address)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get ETH balance of {wallet_address}: {e}");
+            admin_error(
+                Status::InternalServerError,
+                "Failed to retrieve ETH balance".to_string(),
+            )
+        })?;
+    // Worst-case cost of the ETH transfer: its estimated gas (more than 21k when the
+    // destination is a contract, plus the L1 data component on Arbitrum) at the
+    // EIP-1559 max fee it is sent with.
+    let read_provider = &*state.provider.read_provider;
+    let fees = read_provider.estimate_eip1559_fees().await.map_err(|e| {
+        tracing::error!("Failed to estimate fees: {e}");
+        admin_error(
+            Status::InternalServerError,
+            "Failed to retrieve gas price".to_string(),
+        )
+    })?;
+    let gas_limit = read_provider
+        .estimate_gas(
+            TransactionRequest::default()
+                .from(wallet_address)
+                .to(destination)
+                .value(eth_balance),
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to estimate ETH sweep gas: {e}");
+            admin_error(
+                Status::InternalServerError,
+                "Failed to estimate ETH transfer gas".to_string(),
+            )
+        })?;
+    let gas_cost = U256::from(fees.max_fee_per_gas) * U256::from(gas_limit);
+
+    let mut eth_swept = U256::ZERO;
+    let mut eth_transaction_hash = None;
+    let mut eth_skipped_reason = None;
+    if eth_balance <= gas_cost {
+        eth_skipped_reason = Some(format!(
+            "ETH balance {} is at or below the transfer gas cost {}",
+            alloy::primitives::utils::format_ether(eth_balance),
+            alloy::primitives::utils::format_ether(gas_cost)
+        ));
+    } else {
+        let amount = eth_balance - gas_cost;
+        let tx_request = TransactionRequest::default()
+            .to(destination)
+            .value(amount)
+            .gas_limit(gas_limit)
+            .max_fee_per_gas(fees.max_fee_per_gas)
+            .max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+        let pending = sweep_provider
+            .send_transaction(tx_request)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to send ETH sweep from {wallet_address}: {e}");
+                admin_error(
+                    Status::InternalServerError,
+                    "USDC swept, but failed to send ETH".to_string(),
+                )
+            })?;
+        let tx_hash = *pending.tx_hash();
+        let receipt = timeout(FUNDING_RECEIPT_TIMEOUT, pending.get_receipt()).await;
+        if let Ok(Ok(receipt)) = &receipt {
+            state
+                .history
+                .record_receipt(
+                    GasOperation::Sweep,
+                    &[
+                        ("asset", "ETH".to_string()),
+                        ("wallet_address", wallet_address.to_string()),
+                        ("destination", destination.to_string()),
+                        ("amount", amount.to_string()),
+                    ],
+                    receipt,
+                )
+                .await;
+        }
+        match receipt {
+            Ok(Ok(receipt)) if receipt.status() => {
+                eth_swept = amount;
+                eth_transaction_hash = Some(format!("{:?}", receipt.transaction_hash));
+            }
+            Ok(Ok(_)) => {
+                return Err(admin_error(
+                    Status::InternalServerError,
+                    format!("USDC swept, but ETH sweep reverted (tx {tx_hash:?})"),
+                ));
+            }
+            Ok(Err(_)) | Err(_) => {
+                tracing::error!("ETH sweep receipt unavailable (tx {tx_hash:?})");
+                return Err(admin_error(
+                    Status::InternalServerError,
+                    format!(
+                        "USDC swept, but ETH sweep unconfirmed (tx {tx_hash:?}) — verify \
+                         on-chain before retrying"
+                    ),
+                ));
+            }
+        }
+    }
+
+    // Audit trail for every sweep (the service has no error-tracker integration; this
+    // event is what log-based alerting and incident review pick up).
+    tracing::info!(
+        event = "wallet_sweep",
+        wallet = %wallet_address,
+        destination = %destination,
+        usdc_swept = %usdc_balance,
+        eth_swept_wei = %eth_swept,
+        usdc_tx = ?usdc_transaction_hash,
+        eth_tx = ?eth_transaction_hash,
+        "Swept wallet {} to {}",
+        wallet_address,
+        destination
+    );
+
+    Ok(Json(ApiResponse {
+        success: true,
+        message: format!(
+            "Swept {} USDC and {} ETH from {wallet_address} to {destination}",
+            usdc_balance / U256::from(1_000_000),
+            alloy::primitives::utils::format_ether(eth_swept)
+        ),
+        data: Some(SweepWalletResponse {
+            wallet_address: format!("{wallet_address:?}"),
+            destination_address: format!("{destination:?}"),
+            usdc_swept: usdc_balance.to_string(),
+            usdc_transaction_hash,
+            eth_swept_wei: eth_swept.to_string(),
+            eth_transaction_hash,
+            eth_skipped_reason,
+        }),
+    }))
+}
+
 // Tests moved to tests/integration_tests/wallet_test.rs
+
+/// Reports the wallet pool as this instance sees it (admin).
+///
+/// Lists every wallet registered in Redis with its recorded status, current lock holder
+/// and designated beacons, plus counts by status and any local signers missing from the
+/// pool. If Redis is unreachable the response is still 200, with `degraded: true` and
+/// only the local signer count filled in.
+#[openapi(tag = "Wallet")]
+#[get("/wallet_pool/status")]
+pub async fn wallet_pool_status(
+    _token: AdminToken,
+    state: &State<AppState>,
+) -> Json<ApiResponse<WalletPoolStatusResponse>> {
+    tracing::info!("Received request: GET /wallet_pool/status");
+
+    let status = state.wallets.manager.pool_status().await;
+    let message = match &status.error {
+        Some(e) => format!("Wallet pool status degraded: {e}"),
+        None => format!(
+            "{} wallet(s) in pool, {} locked",
+            status.total_wallets, status.locked_wallets
+        ),
+    };
+    Json(ApiResponse {
+        success: !status.degraded,
+        data: Some(status),
+        message,
+    })
+}
+
+/// Reports live ETH / USDC balances and nonces of the funding wallets (admin).
+///
+/// Guests are funded from this instance's signers (the pool wallets, or the single
+/// wallet without the pool), so each one is listed, flagged when its ETH is at or below
+/// the faucet reserve. Monitoring can alert on that before `/fund_guest_wallet` starts
+/// refusing requests. Any failed read returns 503.
+#[openapi(tag = "Wallet")]
+#[get("/funding_wallet/status")]
+pub async fn funding_wallet_status(
+    _token: AdminToken,
+    state: &State<AppState>,
+) -> Result<Json<ApiResponse<FundingWalletStatusResponse>>, (Status, Json<ApiResponse<String>>)> {
+    tracing::info!("Received request: GET /funding_wallet/status");
+
+    let mut wallets = state.wallets.manager.signer_addresses();
+    wallets.sort();
+    let status = read_funding_wallet_status(state, &wallets)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read funding wallet status: {e}");
+            admin_error(
+                Status::ServiceUnavailable,
+                "Failed to read funding wallet balances".to_string(),
+            )
+        })?;
+
+    let low = status
+        .wallets
+        .iter()
+        .filter(|w| w.below_eth_reserve)
+        .count();
+    if low > 0 {
+        tracing::warn!(
+            "{} of {} funding wallet(s) at or below the ETH reserve",
+            low,
+            status.wallets.len()
+        );
+    }
+    let message = format!(
+        "{} funding wallet(s), {} at or below the ETH reserve",
+        status.wallets.len(),
+        low
+    );
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(status),
+        message,
+    }))
+}
+
+/// Read the ETH balance, USDC balance and nonce of each of `wallets`, in order.
+pub async fn read_funding_wallet_status(
+    state: &AppState,
+    wallets: &[Address],
+) -> Result<FundingWalletStatusResponse, String> {
+    let policy = state.provider.read_retry;
+    let read_provider = &*state.provider.read_provider;
+    let usdc = &IERC20::new(state.contracts.load().usdc, read_provider);
+    let reserve = U256::from(state.wallets.faucet_reserve_eth_wei);
+
+    let mut total_eth = U256::ZERO;
+    let mut total_usdc = U256::ZERO;
+    let mut entries = Vec::with_capacity(wallets.len());
+    for &wallet in wallets {
+        let eth = retry_read(&policy, "eth_getBalance", move || async move {
+            read_provider.get_balance(wallet).await
+        })
+        .await
+        .map_err(|e| format!("Failed to get ETH balance of {wallet}: {e}"))?;
+        let usdc_balance = retry_read(&policy, "USDC.balanceOf", move || async move {
+            usdc.balanceOf(wallet).call().await
+        })
+        .await
+        .map_err(|e| format!("Failed to get USDC balance of {wallet}: {e}"))?;
+        let nonce = retry_read(&policy, "eth_getTransactionCount", move || async move {
+            read_provider.get_transaction_count(wallet).await
+        })
+        .await
+        .map_err(|e| format!("Failed to get nonce of {wallet}: {e}"))?;
+
+        total_eth = total_eth.saturating_add(eth);
+        total_usdc = total_usdc.saturating_add(usdc_balance);
+        entries.push(FundingWalletBalance {
+            address: wallet.to_string(),
+            eth_balance_wei: eth.to_string(),
+            usdc_balance: usdc_balance.to_string(),
+            nonce,
+            below_eth_reserve: eth <= reserve,
+        });
+    }
+
+    Ok(FundingWalletStatusResponse {
+        eth_reserve_wei: reserve.to_string(),
+        total_eth_wei: total_eth.to_string(),
+        total_usdc: total_usdc.to_string(),
+        wallets: entries,
+    })
+}
+
+/// Force-releases a wallet lock left behind by a crashed instance (admin).
+///
+/// The caller passes the lock's current owner token (`lock_holder` from
+/// `/wallet_pool/status`); the lock is deleted only while it still carries that token,
+/// so a lock re-acquired by a live instance is never touched. Returns 404 when the wallet
+/// is not locked, 409 when the owner differs or the lock belongs to this instance, and
+/// 503 when the Redis pool is unavailable. Every release is logged as an
+/// `audit = "wallet_force_unlock"` event.
+#[openapi(tag = "Wallet")]
+#[post("/wallet_pool/force_unlock", format = "json", data = "<request>")]
+pub async fn force_unlock_wallet(
+    state: &State<AppState>,
+    request: Json<ForceUnlockWalletRequest>,
+    _token: AdminToken,
+) -> Result<Json<ApiResponse<ForceUnlockWalletResponse>>, (Status, Json<ApiResponse<String>>)> {
+    tracing::info!("Received request: POST /wallet_pool/force_unlock");
+
+    let wallet_address = parse_address(&request.wallet_address, state.strict_address_checksum)
+        .map_err(|e| admin_error(Status::BadRequest, format!("Invalid wallet address: {e}")))?;
+    let expected_holder = request.expected_holder.trim();
+    if expected_holder.is_empty() {
+        return Err(admin_error(
+            Status::BadRequest,
+            "expected_holder must name the lock's current owner".to_string(),
+        ));
+    }
+
+    let manager = &state.wallets.manager;
+    if manager.is_test_stub() {
+        return Err(admin_error(
+            Status::ServiceUnavailable,
+            "Wallet pool is not configured".to_string(),
+        ));
+    }
+
+    let outcome = manager
+        .force_unlock(&wallet_address, expected_holder)
+        .await
+        .map_err(|e| {
+            tracing::error!("force_unlock failed for {}: {}", wallet_address, e);
+            admin_error(Status::ServiceUnavailable, e)
+        })?;
+
+    match outcome {
+        ForceUnlockOutcome::Released => Ok(Json(ApiResponse {
+            success: true,
+            data: Some(ForceUnlockWalletResponse {
+                wallet_address: wallet_address.to_string(),
+                released_holder: expected_holder.to_string(),
+            }),
+            message: format!("Released lock on {wallet_address} held by {expected_holder}"),
+        })),
+        ForceUnlockOutcome::NotLocked => Err(admin_error(
+            Status::NotFound,
+            format!("Wallet {wallet_address} is not locked"),
+        )),
+        ForceUnlockOutcome::HolderMismatch { holder } => Err(admin_error(
+            Status::Conflict,
+            format!(
+                "Lock on {wallet_address} is held by {holder}, not {expected_holder}; \
+                 refusing to release"
+            ),
+        )),
+        ForceUnlockOutcome::HeldByThisInstance => Err(admin_error(
+            Status::Conflict,
+            format!(
+                "Lock on {wallet_address} is held by this live instance; \
+                 refusing to release"
+            ),
+        )),
+    }
+}
+
+/// Sets or clears the pool wallet designated for a beacon (admin).
+///
+/// With `wallet_address`, every wallet-scoped operation on the beacon (batch updates,
+/// perp deployment) uses that wallet, replacing any earlier designation; the wallet must
+/// be in the pool (422 otherwise). Without it, the designation is cleared (404 when there
+/// was none). If the designated wallet is busy the operation fails rather than using
+/// another wallet, unless `WALLET_DESIGNATION_FALLBACK` is on.
+#[openapi(tag = "Wallet")]
+#[post("/wallet_pool/designation", format = "json", data = "<request>")]
+pub async fn set_beacon_designation(
+    state: &State<AppState>,
+    request: Json<BeaconDesignationRequest>,
+    _token: AdminToken,
+) -> Result<Json<ApiResponse<BeaconDesignationResponse>>, (Status, Json<ApiResponse<String>>)> {
+    tracing::info!("Received request: POST /wallet_pool/designation");
+
+    let beacon_address = parse_address(&request.beacon_address, state.strict_address_checksum)
+        .map_err(|e| admin_error(Status::BadRequest, format!("Invalid beacon address: {e}")))?;
+    let wallet_address = request
+        .wallet_address
+        .as_deref()
+        .map(|raw| parse_address(raw, state.strict_address_checksum))
+        .transpose()
+        .map_err(|e| admin_error(Status::BadRequest, format!("Invalid wallet address: {e}")))?;
+
+    let manager = &state.wallets.manager;
+    if manager.is_test_stub() {
+        return Err(admin_error(
+            Status::ServiceUnavailable,
+            "Wallet pool is not configured".to_string(),
+        ));
+    }
+
+    let (previous, message) = match wallet_address {
+        Some(wallet) => {
+            let previous = manager
+                .designate_beacon(&beacon_address, &wallet)
+                .await
+                .map_err(|e| {
+                    let status = if e.contains("is not in the pool") {
+                        Status::UnprocessableEntity
+                    } else {
+                        Status::ServiceUnavailable
+                    };
+                    admin_error(status, e)
+                })?;
+            (
+                previous,
+                format!("Beacon {beacon_address} designated to wallet {wallet}"),
+            )
+        }
+        None => {
+            let previous = manager
+                .clear_beacon_designation(&beacon_address)
+                .await
+                .map_err(|e| admin_error(Status::ServiceUnavailable, e))?;
+            if previous.is_none() {
+                return Err(admin_error(
+                    Status::NotFound,
+                    format!("Beacon {beacon_address} has no designated wallet"),
+                ));
+            }
+            (
+                previous,
+                format!("Cleared wallet designation for beacon {beacon_address}"),
+            )
+        }
+    };
+
+    tracing::info!(
+        audit = "wallet_designation",
+        beacon = %beacon_address,
+        wallet = ?wallet_address,
+        previous = ?previous,
+        "{}",
+        message
+    );
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(BeaconDesignationResponse {
+            beacon_address: beacon_address.to_string(),
+            wallet_address: wallet_address.map(|w| w.to_string()),
+            previous_wallet_address: previous.map(|w| w.to_string()),
+        }),
+        message,
+    }))
+}
+
+/// Puts this instance into maintenance drain (admin).
+///
+/// Every new wallet lease is refused (the operations needing one fail with 503) while
+/// transactions already holding a wallet finish normally, and `/ready` answers 503 so the
+/// load balancer stops routing to the instance (`/health` reports `ready: false` but
+/// stays 200). The flag lives in
+/// this process only: drain each instance before taking it down, and call
+/// `/maintenance/resume` to undo. Logged as an `audit = "maintenance_drain"` event.
+#[openapi(tag = "Wallet")]
+#[post("/maintenance/drain")]
+pub async fn drain_wallets(
+    state: &State<AppState>,
+    _token: AdminToken,
+) -> Json<ApiResponse<MaintenanceResponse>> {
+    tracing::info!("Received request: POST /maintenance/drain");
+    set_draining(state, true)
+}
+
+/// Takes this instance out of maintenance drain, so wallets are leased again (admin).
+#[openapi(tag = "Wallet")]
+#[post("/maintenance/resume")]
+pub async fn resume_wallets(
+    state: &State<AppState>,
+    _token: AdminToken,
+) -> Json<ApiResponse<MaintenanceResponse>> {
+    tracing::info!("Received request: POST /maintenance/resume");
+    set_draining(state, false)
+}
+
+fn set_draining(state: &AppState, draining: bool) -> Json<ApiResponse<MaintenanceResponse>> {
+    let was_draining = state.wallets.manager.set_draining(draining);
+    let message = match (was_draining, draining) {
+        (false, true) => "Draining: new wallet leases are refused",
+        (true, false) => "Resumed: wallets are leased again",
+        (true, true) => "Already draining",
+        (false, false) => "Not draining",
+    };
+    tracing::info!(
+        audit = "maintenance_drain",
+        draining,
+        was_draining,
+        "{}",
+        message
+    );
+
+    Json(ApiResponse {
+        success: true,
+        data: Some(MaintenanceResponse {
+            draining,
+            was_draining,
+        }),
+        message: message.to_string(),
+    })
+}
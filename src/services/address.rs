@@ -0,0 +1,40 @@
+//! Address parsing for request fields
+//!
+//! `Address::from_str` accepts any casing, so on its own a mixed-case address whose
+//! checksum was mistyped would still parse. Mixed case means the caller meant an EIP-55
+//! checksum, so a wrong one is always rejected; all-lowercase and all-uppercase input
+//! carries no checksum and is accepted. With `STRICT_ADDRESS_CHECKSUM` set (read once at
+//! startup into `AppState::strict_address_checksum`), beacon, perp and wallet addresses
+//! in requests must carry a valid checksum. Either way, responses return addresses in
+//! checksummed form.
+
+use alloy::primitives::Address;
+use std::str::FromStr;
+
+/// Parse a request address. Mixed-case input must match its EIP-55 checksum; with
+/// `strict`, all-lowercase and all-uppercase input (which carry no checksum) are rejected
+/// too.
+pub fn parse_address(value: &str, strict: bool) -> Result<Address, String> {
+    let address = Address::from_str(value).map_err(|e| e.to_string())?;
+    let hex = value.strip_prefix("0x").unwrap_or(value);
+    let checksummed = address.to_checksum(None);
+    if hex != &checksummed[2..] {
+        let mixed_case = hex.bytes().any(|b| b.is_ascii_lowercase())
+            && hex.bytes().any(|b| b.is_ascii_uppercase());
+        if mixed_case {
+            return Err(format!("invalid EIP-55 checksum (expected {checksummed})"));
+        }
+        if strict {
+            return Err(format!("not EIP-55 checksummed (expected {checksummed})"));
+        }
+    }
+    Ok(address)
+}
+
+/// Checksummed form of a request address for echoing back; `raw` unchanged if it does
+/// not parse.
+pub fn normalize_address(raw: &str) -> String {
+    Address::from_str(raw)
+        .map(|address| address.to_checksum(None))
+        .unwrap_or_else(|_| raw.to_string())
+}
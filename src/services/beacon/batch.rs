@@ -6,21 +6,39 @@ use tokio::time::timeout;
 use crate::AlloyProvider;
 use crate::models::{AppState, BatchUpdateBeaconResponse, BeaconUpdateData, BeaconUpdateResult};
 use crate::routes::{IBeacon, IMulticall3};
-
-/// Execute batch updates of beacon data with multicall3
+use crate::services::address::{normalize_address, parse_address};
+use crate::services::metrics::GasOperation;
+use crate::services::perp::validation::try_decode_revert_reason;
+use crate::services::transaction::events::parse_index_updated_event;
+use crate::services::transaction::execution::{retry_once_on_nonce_error, wait_for_receipt};
+use crate::services::transaction::multicall::prevalidate_aggregate3;
+use crate::services::wallet::WalletHandle;
+
+/// Outcome of one beacon in a batch update: the beacon address, then the transaction
+/// hash and gas used, or the error.
+type BeaconUpdateOutcome = (String, Result<(String, u64), String>);
+
+/// Execute batch updates of beacon data
 ///
 /// This function handles the complete business logic for batch beacon updates,
-/// including validation, multicall execution, and result processing.
+/// including validation, execution, and result processing. Each wallet's updates go out
+/// as one Multicall3 `aggregate3` transaction (`allowFailure: true`, so one bad proof
+/// does not block the rest) when `MULTICALL3_ADDRESS` is set, and one by one otherwise.
+/// With `prevalidate`, each update in a Multicall3 batch is first run alone as an
+/// `eth_call`; those that would revert are reported as failed and left out of the batch,
+/// so they cost no gas.
 ///
 /// # Arguments
 /// * `state` - Application state
 /// * `updates` - Vector of beacon update data
+/// * `prevalidate` - Simulate each update before batching it
 ///
 /// # Returns
 /// BatchUpdateBeaconResponse with results
 pub async fn batch_update_beacon(
     state: &AppState,
     updates: &[BeaconUpdateData],
+    prevalidate: bool,
 ) -> Result<BatchUpdateBeaconResponse, String> {
     tracing::info!("Starting batch update of {} beacons", updates.len());
 
@@ -40,7 +58,7 @@ pub async fn batch_update_beacon(
 
     for update in updates {
         // Parse beacon address
-        match Address::from_str(&update.beacon_address) {
+        match parse_address(&update.beacon_address, state.strict_address_checksum) {
             Ok(beacon_addr) => {
                 // Get the wallet that owns this beacon (or any available wallet if no owner set)
                 match state.wallets.manager.acquire_for_beacon(&beacon_addr).await {
@@ -71,7 +89,7 @@ pub async fn batch_update_beacon(
     }
 
     // Process each wallet's updates separately
-    let mut batch_results: Vec<(String, Result<String, String>)> = Vec::new();
+    let mut batch_results: Vec<BeaconUpdateOutcome> = Vec::new();
 
     // Add parse errors to results
     for (beacon_addr, error) in parse_errors {
@@ -106,7 +124,7 @@ pub async fn batch_update_beacon(
         );
 
         // Build provider with the acquired wallet
-        let provider = match wallet_handle.build_provider(&state.provider.rpc_url) {
+        let provider = match wallet_handle.build_provider(&state.provider.endpoints) {
             Ok(p) => p,
             Err(e) => {
                 let error_msg = format!("Failed to build provider for wallet {wallet_addr}: {e}");
@@ -118,31 +136,37 @@ pub async fn batch_update_beacon(
             }
         };
 
-        // Process this wallet's updates using multicall
-        if let Some(multicall_address) = state.contracts.multicall3 {
-            // Abort before sending if the distributed wallet lock was lost.
-            if let Err(e) = wallet_handle.ensure_lock_held() {
-                tracing::error!("{}", e);
-                for update in wallet_updates {
-                    batch_results.push((update.beacon_address.clone(), Err(e.clone())));
-                }
-                continue;
-            }
-            // Convert &[&BeaconUpdateData] to &[BeaconUpdateData] for the function call
-            let updates_slice: Vec<BeaconUpdateData> =
-                wallet_updates.iter().map(|u| (*u).clone()).collect();
-            let wallet_batch_results =
-                batch_update_with_multicall3(state, &provider, multicall_address, &updates_slice)
-                    .await;
-            batch_results.extend(wallet_batch_results);
-        } else {
-            let error_msg =
-                "Batch operations require Multicall3 contract address to be configured".to_string();
-            tracing::error!("{}", error_msg);
+        // Abort before sending if the distributed wallet lock was lost.
+        if let Err(e) = wallet_handle.ensure_lock_held() {
+            tracing::error!("{}", e);
             for update in wallet_updates {
-                batch_results.push((update.beacon_address.clone(), Err(error_msg.clone())));
+                batch_results.push((update.beacon_address.clone(), Err(e.clone())));
             }
+            continue;
         }
+
+        // Convert &[&BeaconUpdateData] to &[BeaconUpdateData] for the function calls
+        let updates_slice: Vec<BeaconUpdateData> =
+            wallet_updates.iter().map(|u| (*u).clone()).collect();
+
+        // One aggregate3 transaction per wallet when Multicall3 is configured; otherwise
+        // one update transaction per beacon.
+        let wallet_batch_results = match state.contracts.load().multicall3 {
+            Some(multicall_address) => {
+                batch_update_with_multicall3(
+                    state,
+                    &provider,
+                    multicall_address,
+                    &updates_slice,
+                    prevalidate,
+                )
+                .await
+            }
+            None => {
+                batch_update_sequentially(state, &wallet_handle, &provider, &updates_slice).await
+            }
+        };
+        batch_results.extend(wallet_batch_results);
     }
 
     // Process the results
@@ -151,13 +175,29 @@ pub async fn batch_update_beacon(
     let mut failed_updates = 0;
 
     for (beacon_address, result) in batch_results {
+        let beacon_address = normalize_address(&beacon_address);
+        state
+            .history
+            .record_transaction(
+                GasOperation::BeaconUpdate,
+                &[
+                    ("beacon_address", beacon_address.clone()),
+                    ("batch", "true".to_string()),
+                ],
+                result
+                    .as_ref()
+                    .map(|(tx_hash, _)| Some(tx_hash.clone()))
+                    .map_err(Clone::clone),
+            )
+            .await;
         match result {
-            Ok(tx_hash) => {
+            Ok((tx_hash, gas_used)) => {
                 successful_updates += 1;
                 results.push(BeaconUpdateResult {
                     beacon_address: beacon_address.clone(),
                     success: true,
                     transaction_hash: Some(tx_hash.clone()),
+                    gas_used: Some(gas_used),
                     error: None,
                 });
                 tracing::info!(
@@ -172,6 +212,7 @@ pub async fn batch_update_beacon(
                     beacon_address: beacon_address.clone(),
                     success: false,
                     transaction_hash: None,
+                    gas_used: None,
                     error: Some(error.clone()),
                 });
                 tracing::error!("Failed to update beacon {}: {}", beacon_address, error);
@@ -187,13 +228,93 @@ pub async fn batch_update_beacon(
     })
 }
 
+/// Execute batch updates one transaction at a time, for when Multicall3 is not configured
+///
+/// Each update is sent and confirmed before the next, so a failing beacon only fails its
+/// own entry.
+async fn batch_update_sequentially(
+    state: &AppState,
+    wallet_handle: &WalletHandle,
+    provider: &AlloyProvider,
+    updates: &[BeaconUpdateData],
+) -> Vec<BeaconUpdateOutcome> {
+    tracing::info!(
+        "Multicall3 not configured; updating {} beacons sequentially",
+        updates.len()
+    );
+
+    let mut results = Vec::with_capacity(updates.len());
+    for update_data in updates {
+        let result = update_one(state, wallet_handle, provider, update_data).await;
+        results.push((update_data.beacon_address.clone(), result));
+    }
+    results
+}
+
+/// Send one `update(proof, publicSignals)` and wait for its `IndexUpdated` event.
+async fn update_one(
+    state: &AppState,
+    wallet_handle: &WalletHandle,
+    provider: &AlloyProvider,
+    update_data: &BeaconUpdateData,
+) -> Result<(String, u64), String> {
+    let beacon_address = parse_address(&update_data.beacon_address, state.strict_address_checksum)
+        .map_err(|e| format!("Invalid beacon address: {e}"))?;
+
+    wallet_handle.ensure_lock_held()?;
+    let contract = &IBeacon::new(beacon_address, provider);
+    let pending_tx = retry_once_on_nonce_error(
+        "update",
+        move || async move {
+            contract
+                .update(
+                    update_data.proof.clone(),
+                    update_data.public_signals.clone(),
+                )
+                .send()
+                .await
+                .map_err(|e| match try_decode_revert_reason(&e) {
+                    Some(reason) => format!("Failed to send update transaction: {reason}"),
+                    None => format!("Failed to send update transaction: {e}"),
+                })
+        },
+        move || wallet_handle.resync_nonce(provider),
+    )
+    .await?;
+
+    let tx_hash = *pending_tx.tx_hash();
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        "Update",
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(60)),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    state
+        .gas_metrics
+        .record(GasOperation::BeaconUpdate, receipt.gas_used);
+
+    if !receipt.status() {
+        return Err(format!("Transaction reverted: {tx_hash:?}"));
+    }
+    parse_index_updated_event(&receipt, beacon_address)
+        .map_err(|e| format!("No IndexUpdated event emitted in tx {tx_hash:?}: {e}"))?;
+
+    Ok((format!("{tx_hash:?}"), receipt.gas_used))
+}
+
 /// Execute batch updates using multicall3 - single transaction with multiple calls
 async fn batch_update_with_multicall3(
     state: &AppState,
     provider: &AlloyProvider,
     multicall_address: Address,
     updates: &[BeaconUpdateData],
-) -> Vec<(String, Result<String, String>)> {
+    prevalidate: bool,
+) -> Vec<BeaconUpdateOutcome> {
     tracing::info!(
         "Using Multicall3 for batch update of {} beacons",
         updates.len()
@@ -206,17 +327,18 @@ async fn batch_update_with_multicall3(
 
     for update_data in updates {
         // Parse beacon address
-        let beacon_address = match Address::from_str(&update_data.beacon_address) {
-            Ok(addr) => addr,
-            Err(e) => {
-                // Track invalid address for error reporting
-                invalid_addresses.push((
-                    update_data.beacon_address.clone(),
-                    format!("Invalid beacon address: {e}"),
-                ));
-                continue; // Skip this update but continue processing others
-            }
-        };
+        let beacon_address =
+            match parse_address(&update_data.beacon_address, state.strict_address_checksum) {
+                Ok(addr) => addr,
+                Err(e) => {
+                    // Track invalid address for error reporting
+                    invalid_addresses.push((
+                        update_data.beacon_address.clone(),
+                        format!("Invalid beacon address: {e}"),
+                    ));
+                    continue; // Skip this update but continue processing others
+                }
+            };
 
         // proof and inputs are already Bytes (from 0x-hex JSON)
         let proof_bytes = update_data.proof.clone();
@@ -240,6 +362,35 @@ async fn batch_update_with_multicall3(
         beacon_addresses.push(update_data.beacon_address.clone());
     }
 
+    // Leave out updates that would revert on their own (opt-in: one eth_call each)
+    let batch = prevalidate_aggregate3(
+        &*state.provider.read_provider,
+        &state.provider.read_retry,
+        multicall_address,
+        calls,
+        prevalidate,
+    )
+    .await;
+    for (index, reason) in &batch.predicted_reverts {
+        invalid_addresses.push((
+            beacon_addresses[*index].clone(),
+            format!("Update predicted to revert, not sent: {reason}"),
+        ));
+    }
+    let beacon_addresses: Vec<String> = batch
+        .kept
+        .iter()
+        .map(|&index| beacon_addresses[index].clone())
+        .collect();
+    let calls = batch.calls;
+    if calls.is_empty() {
+        tracing::warn!("Every update in the batch was predicted to revert; nothing sent");
+        return invalid_addresses
+            .into_iter()
+            .map(|(beacon_address, error)| (beacon_address, Err(error)))
+            .collect();
+    }
+
     // Execute the multicall3 transaction - single transaction containing all beacon updates
     let multicall_contract = IMulticall3::new(multicall_address, provider);
 
@@ -256,6 +407,9 @@ async fn batch_update_with_multicall3(
                     );
 
                     let tx_hash = format!("{:?}", receipt.transaction_hash);
+                    state
+                        .gas_metrics
+                        .record(GasOperation::BeaconUpdate, receipt.gas_used);
 
                     // First check transaction status
                     if !receipt.status() {
@@ -292,7 +446,10 @@ async fn batch_update_with_multicall3(
                                 && log.topics()[0] == index_updated_topic
                         });
                         if emitted {
-                            results.push((beacon_addr_str.clone(), Ok(tx_hash.clone())));
+                            results.push((
+                                beacon_addr_str.clone(),
+                                Ok((tx_hash.clone(), receipt.gas_used)),
+                            ));
                         } else {
                             results.push((
                                 beacon_addr_str.clone(),
@@ -0,0 +1,54 @@
+use std::future::Future;
+
+use tokio::sync::Semaphore;
+
+/// Default for `BATCH_CREATE_MAX_COUNT`.
+pub const DEFAULT_BATCH_CREATE_MAX_COUNT: u32 = 100;
+
+/// Default for `BATCH_CREATE_CONCURRENCY`.
+pub const DEFAULT_BATCH_CREATE_CONCURRENCY: usize = 4;
+
+/// Largest `count` accepted by `/batch_create_beacon`, read from
+/// `BATCH_CREATE_MAX_COUNT`. Unset, unparseable or zero keeps the default.
+pub fn batch_create_max_count_from_env() -> u32 {
+    std::env::var("BATCH_CREATE_MAX_COUNT")
+        .ok()
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_BATCH_CREATE_MAX_COUNT)
+}
+
+/// Beacons `/batch_create_beacon` deploys at once, read from `BATCH_CREATE_CONCURRENCY`.
+/// Unset, unparseable or zero keeps the default; `1` is strictly sequential.
+pub fn batch_create_concurrency_from_env() -> usize {
+    std::env::var("BATCH_CREATE_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_BATCH_CREATE_CONCURRENCY)
+}
+
+/// In-flight limit for a batch: `configured`, but no more than the pool has wallets.
+/// Each task leases its own wallet, so extra tasks would only wait on wallet locks.
+pub fn batch_concurrency(configured: usize, wallets: usize) -> usize {
+    configured.min(wallets).max(1)
+}
+
+/// Run `task(0..count)` with at most `limit` running at once, returning the results in
+/// index order whatever order they finish in.
+pub async fn run_bounded<T, F, Fut>(count: usize, limit: usize, task: F) -> Vec<T>
+where
+    F: Fn(usize) -> Fut,
+    Fut: Future<Output = T>,
+{
+    let semaphore = Semaphore::new(limit.max(1));
+    let semaphore = &semaphore;
+    let task = &task;
+    futures::future::join_all((0..count).map(|i| async move {
+        // The semaphore is never closed, so this is always Ok; the permit it holds is
+        // released when the task finishes.
+        let _permit = semaphore.acquire().await;
+        task(i).await
+    }))
+    .await
+}
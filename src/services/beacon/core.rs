@@ -1,19 +1,25 @@
 use alloy::primitives::{Address, B256};
 use alloy::providers::Provider;
-use std::{str::FromStr, time::Duration};
-use tokio::time::timeout;
+use std::time::Duration;
 use tracing;
 
 use crate::models::beacon_type::{BeaconTypeConfig, FactoryType};
 use crate::models::requests::BeaconCreationParams;
 use crate::models::responses::CreateBeaconResponse;
 use crate::models::{AppState, UpdateBeaconRequest};
-use crate::routes::{IBeacon, IBeaconRegistry};
-use crate::services::beacon::ecdsa_deploy::create_ecdsa_verifier;
+use crate::routes::{IBeacon, IBeaconRegistry, ICompositeBeacon};
+use crate::services::address::parse_address;
+use crate::services::beacon::detect::detect_beacon_kind;
+use crate::services::beacon::ecdsa_deploy::{check_existing_ecdsa_verifier, create_ecdsa_verifier};
 use crate::services::beacon::verifiable::deploy_identity_beacon;
+use crate::services::error::BeaconError;
+use crate::services::metrics::GasOperation;
+use crate::services::perp::validation::try_decode_revert_reason;
 use crate::services::safe::SafeTransactionService;
 use crate::services::transaction::events::parse_index_updated_event;
-use crate::services::transaction::execution::is_nonce_error;
+use crate::services::transaction::execution::{
+    fetch_receipt, retry_once_on_nonce_error, wait_for_receipt,
+};
 
 /// Outcome of a beacon registration attempt.
 #[derive(Debug)]
@@ -37,6 +43,10 @@ pub enum UnregistrationOutcome {
     OnChainConfirmed(B256),
 }
 
+/// Error returned when `abis/IdentityBeacon.bytecode` was empty at startup.
+pub const IDENTITY_BEACON_UNAVAILABLE: &str =
+    "IdentityBeacon bytecode is not loaded - check abis/IdentityBeacon.bytecode";
+
 /// Create an IdentityBeacon with an ECDSA verifier.
 ///
 /// This function handles:
@@ -44,11 +54,32 @@ pub enum UnregistrationOutcome {
 /// - ECDSA verifier creation via factory
 /// - IdentityBeacon deployment via bytecode
 ///
-/// Returns (beacon_address, verifier_address).
+/// Returns (beacon_address, verifier_address). Fails before acquiring a wallet if the
+/// IdentityBeacon bytecode is not loaded, so no verifier is deployed for a beacon that
+/// cannot be.
 pub async fn create_identity_beacon(
     state: &AppState,
     initial_index: u128,
 ) -> Result<(Address, Address), String> {
+    create_identity_beacon_with_verifier(state, initial_index, None).await
+}
+
+/// [`create_identity_beacon`], reusing `existing_verifier` instead of creating a new
+/// verifier when one is given. The existing verifier must be deployed and designate the
+/// PRIVATE_KEY signer (see [`check_existing_ecdsa_verifier`]).
+pub async fn create_identity_beacon_with_verifier(
+    state: &AppState,
+    initial_index: u128,
+    existing_verifier: Option<Address>,
+) -> Result<(Address, Address), String> {
+    if state.contracts.load().identity_beacon_bytecode.is_empty() {
+        return Err(IDENTITY_BEACON_UNAVAILABLE.to_string());
+    }
+
+    if let Some(verifier_address) = existing_verifier {
+        check_existing_ecdsa_verifier(state, verifier_address).await?;
+    }
+
     // Acquire a wallet from the pool
     let wallet_handle = state
         .wallets
@@ -60,9 +91,18 @@ pub async fn create_identity_beacon(
     let wallet_address = wallet_handle.address();
     tracing::info!("Acquired wallet {} for beacon creation", wallet_address);
 
-    // Step 1: Create ECDSA verifier via factory
-    let verifier_address = create_ecdsa_verifier(state, &wallet_handle).await?;
-    tracing::info!("ECDSA verifier created at {}", verifier_address);
+    // Step 1: Create ECDSA verifier via factory, unless reusing one
+    let verifier_address = match existing_verifier {
+        Some(verifier_address) => {
+            tracing::info!("Reusing ECDSA verifier at {}", verifier_address);
+            verifier_address
+        }
+        None => {
+            let verifier_address = create_ecdsa_verifier(state, &wallet_handle).await?;
+            tracing::info!("ECDSA verifier created at {}", verifier_address);
+            verifier_address
+        }
+    };
 
     // Step 2: Deploy IdentityBeacon with the verifier
     let beacon_address =
@@ -82,12 +122,7 @@ pub async fn is_transaction_confirmed(
         tx_hash
     );
 
-    match state
-        .provider
-        .read_provider
-        .get_transaction_receipt(tx_hash)
-        .await
-    {
+    match fetch_receipt(state, tx_hash).await {
         Ok(Some(receipt)) => {
             tracing::info!(
                 "Transaction {} is confirmed in block {}",
@@ -174,6 +209,34 @@ pub async fn register_beacon_with_registry(
     state: &AppState,
     beacon_address: Address,
     registry_address: Address,
+) -> Result<RegistrationOutcome, String> {
+    let result = register_beacon_inner(state, beacon_address, registry_address).await;
+    state
+        .history
+        .record_transaction(
+            GasOperation::BeaconRegister,
+            &[
+                ("action", "register".to_string()),
+                ("beacon_address", beacon_address.to_string()),
+                ("registry_address", registry_address.to_string()),
+            ],
+            match &result {
+                Ok(RegistrationOutcome::AlreadyRegistered) => Ok(None),
+                Ok(
+                    RegistrationOutcome::SafeProposed(hash)
+                    | RegistrationOutcome::OnChainConfirmed(hash),
+                ) => Ok(Some(format!("{hash:?}"))),
+                Err(e) => Err(e.clone()),
+            },
+        )
+        .await;
+    result
+}
+
+async fn register_beacon_inner(
+    state: &AppState,
+    beacon_address: Address,
+    registry_address: Address,
 ) -> Result<RegistrationOutcome, String> {
     tracing::info!(
         "Registering beacon {} with registry {}",
@@ -219,7 +282,7 @@ pub async fn register_beacon_with_registry(
     }
 
     // If Safe is configured, propose via Safe instead of direct execution
-    if let Some(safe) = &state.contracts.safe
+    if let Some(safe) = &state.contracts.load().safe
         && let Some(safe_url) = &safe.tx_service_url
     {
         tracing::info!(
@@ -286,170 +349,47 @@ pub async fn register_beacon_with_registry(
 
     // Build provider with the acquired wallet
     let provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| format!("Failed to build provider: {e}"))?;
 
     // Create contract instance using the wallet's provider
-    let contract = IBeaconRegistry::new(registry_address, &provider);
+    let contract = &IBeaconRegistry::new(registry_address, &provider);
 
-    // Send the registration transaction
+    // Send the registration transaction, resending once after a nonce error
     tracing::info!("Registering beacon with wallet {}", wallet_address);
     wallet_handle.ensure_lock_held()?;
-    let pending_tx = match contract.registerBeacon(beacon_address).send().await {
-        Ok(pending) => Ok(pending),
-        Err(e) => {
-            let error_msg = format!("Failed to send registerBeacon transaction: {e}");
-            tracing::error!("{}", error_msg);
-
-            // Check if nonce error
-            if is_nonce_error(&error_msg) {
-                tracing::warn!("Nonce error detected, transaction failed");
-            }
-
-            Err(error_msg)
-        }
-    }?;
-
-    tracing::info!("Registration transaction sent, waiting for receipt...");
-
-    // Get the transaction hash before calling get_receipt() (which takes ownership)
-    let tx_hash = *pending_tx.tx_hash();
-    tracing::info!("Registration transaction hash: {:?}", tx_hash);
-
-    // Use get_receipt() with timeout and fallback to on-chain check
-    let receipt = match timeout(Duration::from_secs(60), pending_tx.get_receipt()).await {
-        Ok(Ok(receipt)) => {
-            tracing::info!("Registration confirmed via get_receipt()");
-            receipt
-        }
-        Ok(Err(e)) => {
-            tracing::warn!("get_receipt() failed for registration: {}", e);
-            tracing::info!("Falling back to on-chain registration check...");
-
-            tracing::info!("Checking registration transaction {} on-chain...", tx_hash);
-
-            // Try to get the receipt directly from the read provider with timeout
-            match timeout(
-                Duration::from_secs(30),
-                state
-                    .provider
-                    .read_provider
-                    .get_transaction_receipt(tx_hash),
-            )
-            .await
-            {
-                Ok(Ok(Some(receipt))) => {
-                    tracing::info!("Registration found on-chain via direct receipt lookup");
-                    receipt
-                }
-                Ok(Ok(None)) => {
-                    let error_msg = format!(
-                        "Registration transaction {tx_hash} not found on-chain after timeout"
-                    );
-                    tracing::error!("{}", error_msg);
-                    tracing::error!("This could indicate:");
-                    tracing::error!("  - Registration transaction was dropped/replaced");
-                    tracing::error!("  - Network issues prevented confirmation");
-                    tracing::error!("  - Registration is still pending");
-                    return Err(error_msg);
-                }
-                Ok(Err(e)) => {
-                    let error_msg =
-                        format!("Failed to check registration transaction {tx_hash} on-chain: {e}");
-                    tracing::error!("{}", error_msg);
-                    tracing::error!("Original get_receipt() error: {}", e);
-                    return Err(error_msg);
-                }
-                Err(_) => {
-                    let error_msg =
-                        format!("Timeout checking registration transaction {tx_hash} on-chain");
+    let pending_tx = retry_once_on_nonce_error(
+        "registerBeacon",
+        move || async move {
+            contract
+                .registerBeacon(beacon_address)
+                .send()
+                .await
+                .map_err(|e| {
+                    let error_msg = format!("Failed to send registerBeacon transaction: {e}");
                     tracing::error!("{}", error_msg);
-                    tracing::error!("Network may be slow or unresponsive");
-                    return Err(error_msg);
-                }
-            }
-        }
-        Err(_) => {
-            tracing::warn!(
-                "Initial get_receipt() timed out for registration transaction, trying extended fallback..."
-            );
-            tracing::info!(
-                "Checking registration transaction {} on-chain with progressive timeouts...",
-                tx_hash
-            );
-
-            // Extended fallback: retry with progressive timeouts (15s, 30s, 60s) for Base network
-            let mut retry_count = 0;
-            let max_retries = 3;
-            let timeout_seconds = [15u64, 30u64, 60u64]; // Progressive timeout pattern
-
-            loop {
-                retry_count += 1;
-                let current_timeout = timeout_seconds[retry_count - 1];
-                tracing::info!(
-                    "Registration transaction receipt attempt {}/{} ({}s timeout)",
-                    retry_count,
-                    max_retries,
-                    current_timeout
-                );
+                    error_msg
+                })
+        },
+        || wallet_handle.resync_nonce(&provider),
+    )
+    .await?;
 
-                match timeout(
-                    Duration::from_secs(current_timeout),
-                    is_transaction_confirmed(state, tx_hash),
-                )
-                .await
-                {
-                    Ok(Ok(Some(receipt))) => {
-                        tracing::info!(
-                            "Registration transaction found on-chain via extended fallback (attempt {})",
-                            retry_count
-                        );
-                        break receipt;
-                    }
-                    Ok(Ok(None)) => {
-                        if retry_count >= max_retries {
-                            let error_msg = format!(
-                                "Registration transaction {tx_hash} not found on-chain after {max_retries} attempts"
-                            );
-                            tracing::error!("{}", error_msg);
-                            tracing::error!("This could indicate:");
-                            tracing::error!("  - Registration transaction was dropped/replaced");
-                            tracing::error!("  - Network issues prevented confirmation");
-                            tracing::error!("  - Transaction is still pending (check gas price)");
-                            tracing::error!("  - Base network congestion causing delays");
-                            return Err(error_msg);
-                        }
-                        tracing::warn!(
-                            "Registration transaction not found on attempt {}, retrying...",
-                            retry_count
-                        );
-                        tokio::time::sleep(Duration::from_secs(3)).await; // Brief pause between retries
-                    }
-                    Ok(Err(e)) => {
-                        let error_msg = format!(
-                            "Failed to check registration transaction {tx_hash} on-chain: {e}"
-                        );
-                        tracing::error!("{}", error_msg);
-                        return Err(error_msg);
-                    }
-                    Err(_) => {
-                        if retry_count >= max_retries {
-                            let error_msg = format!(
-                                "Final timeout waiting for registration transaction receipt {tx_hash} after {max_retries} attempts"
-                            );
-                            tracing::error!("{}", error_msg);
-                            tracing::error!(
-                                "All fallback methods exhausted for registration transaction"
-                            );
-                            return Err(error_msg);
-                        }
-                        tracing::warn!("Timeout on attempt {}, retrying...", retry_count);
-                        tokio::time::sleep(Duration::from_secs(3)).await; // Brief pause between retries
-                    }
-                }
-            }
-        }
-    };
+    tracing::info!(
+        "Registration transaction sent, hash: {:?}; waiting for receipt...",
+        pending_tx.tx_hash()
+    );
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        "Registration",
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(60)),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
 
     let tx_hash = receipt.transaction_hash;
     tracing::info!(
@@ -457,6 +397,9 @@ pub async fn register_beacon_with_registry(
         tx_hash
     );
     tracing::info!("Registration confirmed in block {:?}", receipt.block_number);
+    state
+        .gas_metrics
+        .record(GasOperation::BeaconRegister, receipt.gas_used);
 
     // Check transaction status - only success if true
     if receipt.status() {
@@ -470,54 +413,6 @@ pub async fn register_beacon_with_registry(
     }
 }
 
-/// Look up a transaction receipt on-chain after `get_receipt()` fails or times out.
-///
-/// A submitted-but-still-pending transaction returns `Ok(None)` from a single lookup, so this
-/// polls with progressive timeouts (mirroring the registration flow) before declaring the
-/// transaction missing — otherwise a slow-to-confirm tx would produce a spurious error and a
-/// client retry could duplicate it. `op` is a human-readable label used in error messages.
-async fn confirm_tx_on_chain(
-    state: &AppState,
-    tx_hash: B256,
-    op: &str,
-) -> Result<alloy::rpc::types::TransactionReceipt, String> {
-    const TIMEOUTS_SECS: [u64; 3] = [15, 30, 60];
-    for (attempt, secs) in TIMEOUTS_SECS.iter().enumerate() {
-        let last_attempt = attempt + 1 == TIMEOUTS_SECS.len();
-        match timeout(
-            Duration::from_secs(*secs),
-            is_transaction_confirmed(state, tx_hash),
-        )
-        .await
-        {
-            Ok(Ok(Some(receipt))) => return Ok(receipt),
-            // A propagated RPC error is terminal — do not keep polling.
-            Ok(Err(e)) => {
-                return Err(format!(
-                    "Failed to check {op} transaction {tx_hash} on-chain: {e}"
-                ));
-            }
-            // Not found yet, or this lookup timed out: retry unless the budget is exhausted.
-            Ok(Ok(None)) | Err(_) => {
-                if last_attempt {
-                    return Err(format!(
-                        "{op} transaction {tx_hash} not found on-chain after {} attempts",
-                        TIMEOUTS_SECS.len()
-                    ));
-                }
-                tracing::warn!(
-                    "{op} transaction {tx_hash} not yet confirmed (attempt {}/{}), retrying...",
-                    attempt + 1,
-                    TIMEOUTS_SECS.len()
-                );
-                tokio::time::sleep(Duration::from_secs(3)).await;
-            }
-        }
-    }
-    // Unreachable: the final iteration always returns, but keep the type-checker happy.
-    Err(format!("{op} transaction {tx_hash} confirmation exhausted"))
-}
-
 /// Unregister (remove) a beacon from a registry.
 ///
 /// Mirrors [`register_beacon_with_registry`] with an inverted pre-check: a beacon that is
@@ -529,6 +424,34 @@ pub async fn unregister_beacon_with_registry(
     state: &AppState,
     beacon_address: Address,
     registry_address: Address,
+) -> Result<UnregistrationOutcome, String> {
+    let result = unregister_beacon_inner(state, beacon_address, registry_address).await;
+    state
+        .history
+        .record_transaction(
+            GasOperation::BeaconRegister,
+            &[
+                ("action", "unregister".to_string()),
+                ("beacon_address", beacon_address.to_string()),
+                ("registry_address", registry_address.to_string()),
+            ],
+            match &result {
+                Ok(UnregistrationOutcome::AlreadyUnregistered) => Ok(None),
+                Ok(
+                    UnregistrationOutcome::SafeProposed(hash)
+                    | UnregistrationOutcome::OnChainConfirmed(hash),
+                ) => Ok(Some(format!("{hash:?}"))),
+                Err(e) => Err(e.clone()),
+            },
+        )
+        .await;
+    result
+}
+
+async fn unregister_beacon_inner(
+    state: &AppState,
+    beacon_address: Address,
+    registry_address: Address,
 ) -> Result<UnregistrationOutcome, String> {
     tracing::info!(
         "Unregistering beacon {} from registry {}",
@@ -550,7 +473,7 @@ pub async fn unregister_beacon_with_registry(
     }
 
     // If a Safe is configured, propose via Safe instead of direct execution.
-    if let Some(safe) = &state.contracts.safe
+    if let Some(safe) = &state.contracts.load().safe
         && let Some(safe_url) = &safe.tx_service_url
     {
         tracing::info!(
@@ -620,44 +543,52 @@ pub async fn unregister_beacon_with_registry(
 
     // Build provider with the acquired wallet
     let provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| format!("Failed to build provider: {e}"))?;
 
     // Create contract instance using the wallet's provider
-    let contract = IBeaconRegistry::new(registry_address, &provider);
+    let contract = &IBeaconRegistry::new(registry_address, &provider);
 
-    // Send the unregistration transaction
+    // Send the unregistration transaction, resending once after a nonce error
     tracing::info!("Unregistering beacon with wallet {}", wallet_address);
     wallet_handle.ensure_lock_held()?;
-    let pending_tx = match contract.unregisterBeacon(beacon_address).send().await {
-        Ok(pending) => Ok(pending),
-        Err(e) => {
-            let error_msg = format!("Failed to send unregisterBeacon transaction: {e}");
-            tracing::error!("{}", error_msg);
-            if is_nonce_error(&error_msg) {
-                tracing::warn!("Nonce error detected, transaction failed");
-            }
-            Err(error_msg)
-        }
-    }?;
+    let pending_tx = retry_once_on_nonce_error(
+        "unregisterBeacon",
+        move || async move {
+            contract
+                .unregisterBeacon(beacon_address)
+                .send()
+                .await
+                .map_err(|e| {
+                    let error_msg = format!("Failed to send unregisterBeacon transaction: {e}");
+                    tracing::error!("{}", error_msg);
+                    error_msg
+                })
+        },
+        || wallet_handle.resync_nonce(&provider),
+    )
+    .await?;
 
-    let tx_hash = *pending_tx.tx_hash();
-    tracing::info!("Unregistration transaction sent, hash: {:?}", tx_hash);
-
-    // Wait for the receipt, falling back to a direct on-chain lookup.
-    let receipt = match timeout(Duration::from_secs(60), pending_tx.get_receipt()).await {
-        Ok(Ok(receipt)) => receipt,
-        Ok(Err(e)) => {
-            tracing::warn!("get_receipt() failed for unregistration: {e}; checking on-chain");
-            confirm_tx_on_chain(state, tx_hash, "Unregistration").await?
-        }
-        Err(_) => {
-            tracing::warn!("get_receipt() timed out for unregistration; checking on-chain");
-            confirm_tx_on_chain(state, tx_hash, "Unregistration").await?
-        }
-    };
+    tracing::info!(
+        "Unregistration transaction sent, hash: {:?}",
+        pending_tx.tx_hash()
+    );
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        "Unregistration",
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(60)),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
 
     let tx_hash = receipt.transaction_hash;
+    state
+        .gas_metrics
+        .record(GasOperation::BeaconRegister, receipt.gas_used);
     if receipt.status() {
         tracing::info!(
             "Unregistration transaction {:?} confirmed in block {:?}",
@@ -673,24 +604,69 @@ pub async fn unregister_beacon_with_registry(
     }
 }
 
+/// Whether an update failure is the verifier rejecting the submitted proof
+/// (`ProofAlreadyUsed` / `InvalidProof`) rather than an infrastructure fault.
+pub fn is_proof_rejection(error: &str) -> bool {
+    error.contains("ProofAlreadyUsed") || error.contains("InvalidProof")
+}
+
 /// Updates a beacon with new data using a proof.
 ///
 /// This function handles:
 /// - Address validation
+/// - Beacon shape detection: composite beacons get a plain `update()` and the
+///   request's proof/public signals are ignored (see [`detect_beacon_kind`])
 /// - Wallet acquisition from WalletManager
 /// - Transaction execution with error handling
 /// - Transaction confirmation with timeouts
-pub async fn update_beacon(state: &AppState, request: UpdateBeaconRequest) -> Result<B256, String> {
+pub async fn update_beacon(
+    state: &AppState,
+    request: UpdateBeaconRequest,
+) -> Result<B256, BeaconError> {
+    let beacon_address = request.beacon_address.clone();
+    let result = update_beacon_inner(state, request).await;
+    state
+        .history
+        .record_transaction(
+            GasOperation::BeaconUpdate,
+            &[("beacon_address", beacon_address)],
+            match &result {
+                Ok(hash) => Ok(Some(format!("{hash:?}"))),
+                Err(e) => Err(e.to_string()),
+            },
+        )
+        .await;
+    result
+}
+
+async fn update_beacon_inner(
+    state: &AppState,
+    request: UpdateBeaconRequest,
+) -> Result<B256, BeaconError> {
     // Parse the beacon address
-    let beacon_address = match Address::from_str(&request.beacon_address) {
+    let beacon_address = match parse_address(&request.beacon_address, state.strict_address_checksum)
+    {
         Ok(addr) => addr,
         Err(e) => {
             tracing::error!("Invalid beacon address: {}", e);
-            return Err("Invalid beacon address".to_string());
+            return Err(BeaconError::InvalidAddress(
+                "Invalid beacon address".to_string(),
+            ));
         }
     };
 
-    tracing::info!("Updating beacon {} with proof data", beacon_address);
+    // Pick the calldata shape before taking a wallet; an unreachable or non-beacon
+    // address fails here without parking one.
+    let kind = detect_beacon_kind(state, beacon_address).await?;
+    if kind.takes_proof() {
+        tracing::info!("Updating beacon {} with proof data", beacon_address);
+    } else {
+        tracing::info!(
+            "Updating {:?} beacon {}; proof and public signals are ignored",
+            kind,
+            beacon_address
+        );
+    }
 
     // proof and inputs are already Bytes (from 0x-hex JSON)
     let proof_bytes = request.proof;
@@ -702,109 +678,83 @@ pub async fn update_beacon(state: &AppState, request: UpdateBeaconRequest) -> Re
         .manager
         .acquire_for_beacon(&beacon_address)
         .await
-        .map_err(|e| format!("Failed to acquire wallet: {e}"))?;
+        .map_err(|e| BeaconError::WalletUnavailable(format!("Failed to acquire wallet: {e}")))?;
 
     let wallet_address = wallet_handle.address();
     tracing::info!("Acquired wallet {} for beacon update", wallet_address);
 
     // Build provider with the acquired wallet
     let provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
-        .map_err(|e| format!("Failed to build provider: {e}"))?;
-
-    // Create contract instance using the wallet's provider
-    let contract = IBeacon::new(beacon_address, &provider);
+        .build_provider(&state.provider.endpoints)
+        .map_err(|e| BeaconError::Other(format!("Failed to build provider: {e}")))?;
 
-    // Send the update transaction
+    // Send the update transaction, resending once after a nonce error
     tracing::info!("Updating beacon with wallet {}", wallet_address);
-    wallet_handle.ensure_lock_held()?;
-    let pending_tx = match contract
-        .update(proof_bytes.clone(), inputs_bytes.clone())
-        .send()
-        .await
-    {
-        Ok(pending) => Ok(pending),
-        Err(e) => {
-            let error_msg = format!("Failed to send update transaction: {e}");
-            tracing::error!("{}", error_msg);
-
-            // Check if nonce error
-            if is_nonce_error(&error_msg) {
-                tracing::warn!("Nonce error detected, transaction failed");
-            }
-
-            Err(error_msg)
-        }
-    }?;
+    wallet_handle
+        .ensure_lock_held()
+        .map_err(BeaconError::WalletUnavailable)?;
+    let beacon = &IBeacon::new(beacon_address, &provider);
+    let composite = &ICompositeBeacon::new(beacon_address, &provider);
+    let (proof_bytes, inputs_bytes) = (&proof_bytes, &inputs_bytes);
+    let pending_tx = retry_once_on_nonce_error(
+        "update",
+        move || async move {
+            let send_result = if kind.takes_proof() {
+                beacon
+                    .update(proof_bytes.clone(), inputs_bytes.clone())
+                    .send()
+                    .await
+            } else {
+                composite.update().send().await
+            };
+            send_result.map_err(|e| {
+                let decoded = try_decode_revert_reason(&e);
+                let error_msg = match &decoded {
+                    Some(reason) => format!("Failed to send update transaction: {reason}"),
+                    None => format!("Failed to send update transaction: {e}"),
+                };
+                tracing::error!("{}", error_msg);
+                BeaconError::from_send_error(error_msg, &e, decoded)
+            })
+        },
+        || wallet_handle.resync_nonce(&provider),
+    )
+    .await?;
 
     tracing::info!("Transaction sent, waiting for receipt...");
 
-    // Get the transaction hash before calling get_receipt() (which takes ownership)
+    // Get the transaction hash before wait_for_receipt() takes the pending transaction
     let tx_hash = *pending_tx.tx_hash();
     tracing::info!("Transaction hash: {:?}", tx_hash);
 
-    // Use get_receipt() with timeout and fallback to on-chain check
-    let receipt = match timeout(Duration::from_secs(60), pending_tx.get_receipt()).await {
-        Ok(Ok(receipt)) => {
-            tracing::info!("Transaction confirmed via get_receipt()");
-            receipt
-        }
-        Ok(Err(e)) => {
-            tracing::warn!("get_receipt() failed: {}", e);
-            tracing::info!("Falling back to on-chain transaction check...");
-
-            tracing::info!("Checking transaction {} on-chain...", tx_hash);
-
-            // Try to get the receipt directly from the read provider with timeout
-            match timeout(
-                Duration::from_secs(30),
-                state
-                    .provider
-                    .read_provider
-                    .get_transaction_receipt(tx_hash),
-            )
-            .await
-            {
-                Ok(Ok(Some(receipt))) => {
-                    tracing::info!("Transaction found on-chain via direct receipt lookup");
-                    receipt
-                }
-                Ok(Ok(None)) => {
-                    let error_msg =
-                        format!("Transaction {tx_hash} not found on-chain after timeout");
-                    tracing::error!("{}", error_msg);
-                    return Err(error_msg);
-                }
-                Ok(Err(e)) => {
-                    let error_msg = format!("Failed to check transaction {tx_hash} on-chain: {e}");
-                    tracing::error!("{}", error_msg);
-                    return Err(error_msg);
-                }
-                Err(_) => {
-                    let error_msg = format!("Timeout checking transaction {tx_hash} on-chain");
-                    tracing::error!("{}", error_msg);
-                    return Err(error_msg);
-                }
-            }
-        }
-        Err(_) => {
-            let error_msg = format!("Timeout waiting for transaction {tx_hash} receipt");
-            tracing::error!("{}", error_msg);
-            return Err(error_msg);
-        }
-    };
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        "Update",
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(60)),
+    )
+    .await?;
 
     tracing::info!(
         "Update transaction confirmed with hash: {:?}",
         receipt.transaction_hash
     );
+    state
+        .gas_metrics
+        .record(GasOperation::BeaconUpdate, receipt.gas_used);
 
     // First check transaction status
     if !receipt.status() {
         let error_msg = format!("Update transaction {tx_hash} reverted (status: false)");
         tracing::error!("{}", error_msg);
         tracing::error!("Receipt: {:?}", receipt);
-        return Err(error_msg);
+        return Err(BeaconError::ContractReverted {
+            message: error_msg,
+            decoded: None,
+        });
     }
 
     // Parse and validate IndexUpdated event was emitted
@@ -822,7 +772,7 @@ pub async fn update_beacon(state: &AppState, request: UpdateBeaconRequest) -> Re
                 "Transaction succeeded but IndexUpdated event not found: {e}. This indicates the update may not have been applied."
             );
             tracing::error!("{}", error_msg);
-            Err(error_msg)
+            Err(BeaconError::Other(error_msg))
         }
     }
 }
@@ -891,10 +841,11 @@ pub async fn create_and_register_beacon_by_type(
     };
 
     Ok(CreateBeaconResponse {
-        beacon_address: format!("{beacon_address:#x}"),
+        beacon_address: beacon_address.to_checksum(None),
         beacon_type: config.slug.clone(),
-        factory_address: format!("{:#x}", config.factory_address),
+        factory_address: config.factory_address.to_checksum(None),
         registered,
         safe_proposal_hash,
+        confirmations: None,
     })
 }
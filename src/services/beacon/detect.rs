@@ -0,0 +1,149 @@
+//! Beacon shape detection
+//!
+//! Beacons in this deployment come in three update shapes. Verifier-backed beacons take
+//! `update(proof, inputs)` and expose `verifier()`; when that verifier also answers
+//! `SIGNER()` it is an ECDSAVerifier. Composite beacons expose neither and recompute
+//! their index from their references on a plain `update()`. Detection probes those
+//! functions with read-only calls, so `/update_beacon` can send the right calldata
+//! without the caller naming the type.
+
+use alloy::primitives::Address;
+
+use crate::models::AppState;
+use crate::routes::{IBeacon, IEcdsaVerifier};
+use crate::services::error::BeaconError;
+use crate::services::rpc::retry_read;
+
+/// How a beacon expects to be updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BeaconUpdateKind {
+    /// `update(proof, inputs)` checked by a proof verifier.
+    Verifiable,
+    /// `update(signature, inputs)` checked by an ECDSAVerifier.
+    Ecdsa,
+    /// `update()` with no arguments; the index is derived from reference beacons.
+    Composite,
+}
+
+impl BeaconUpdateKind {
+    /// Whether updates carry caller-supplied `(proof, inputs)` calldata.
+    pub fn takes_proof(self) -> bool {
+        !matches!(self, Self::Composite)
+    }
+}
+
+/// Outcome of one read-only probe call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProbeOutcome {
+    /// The call returned a decodable value.
+    Answered,
+    /// The contract has no such function: the call reverted or returned no data.
+    Missing,
+    /// The probe could not be answered (transport error, timeout, ...).
+    Failed(String),
+}
+
+impl ProbeOutcome {
+    /// Classify a failed probe call by its error text.
+    pub fn from_call_error(error: &str) -> Self {
+        if is_missing_function_error(error) {
+            Self::Missing
+        } else {
+            Self::Failed(error.to_string())
+        }
+    }
+}
+
+/// Whether a call error means the function does not exist on the target, as opposed to
+/// the call not reaching the chain.
+pub fn is_missing_function_error(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("execution reverted")
+        || error.contains("returned no data")
+        || error.contains("buffer overrun")
+}
+
+/// Decide the update shape from the probes.
+///
+/// `signer` is only consulted when `verifier` answered, and `index` only when it was
+/// missing, so callers may pass `ProbeOutcome::Missing` for probes they skipped.
+///
+/// Fails with [`BeaconError::NotABeacon`] when both shape probes are missing, and with
+/// [`BeaconError::RpcUnavailable`] when a probe could not be answered.
+pub fn classify_beacon(
+    beacon_address: Address,
+    verifier: ProbeOutcome,
+    signer: ProbeOutcome,
+    index: ProbeOutcome,
+) -> Result<BeaconUpdateKind, BeaconError> {
+    match verifier {
+        ProbeOutcome::Answered => match signer {
+            ProbeOutcome::Answered => Ok(BeaconUpdateKind::Ecdsa),
+            ProbeOutcome::Missing => Ok(BeaconUpdateKind::Verifiable),
+            ProbeOutcome::Failed(e) => Err(BeaconError::RpcUnavailable(format!(
+                "Failed to probe verifier SIGNER() for beacon {beacon_address}: {e}"
+            ))),
+        },
+        ProbeOutcome::Missing => match index {
+            ProbeOutcome::Answered => Ok(BeaconUpdateKind::Composite),
+            ProbeOutcome::Missing => Err(BeaconError::NotABeacon(format!(
+                "{beacon_address} is not a beacon: it answers neither verifier() nor index()"
+            ))),
+            ProbeOutcome::Failed(e) => Err(BeaconError::RpcUnavailable(format!(
+                "Failed to probe index() for beacon {beacon_address}: {e}"
+            ))),
+        },
+        ProbeOutcome::Failed(e) => Err(BeaconError::RpcUnavailable(format!(
+            "Failed to probe verifier() for beacon {beacon_address}: {e}"
+        ))),
+    }
+}
+
+/// Probe `beacon_address` on the read provider and classify its update shape.
+pub async fn detect_beacon_kind(
+    state: &AppState,
+    beacon_address: Address,
+) -> Result<BeaconUpdateKind, BeaconError> {
+    let retry = state.provider.read_retry;
+    let beacon = &IBeacon::new(beacon_address, &*state.provider.read_provider);
+
+    let verifier_result = retry_read(&retry, "Beacon.verifier", move || async move {
+        beacon.verifier().call().await
+    })
+    .await;
+
+    let (verifier, signer, index) = match verifier_result {
+        Ok(verifier_address) => {
+            let verifier = &IEcdsaVerifier::new(verifier_address, &*state.provider.read_provider);
+            let signer = match retry_read(&retry, "EcdsaVerifier.SIGNER", move || async move {
+                verifier.SIGNER().call().await
+            })
+            .await
+            {
+                Ok(_) => ProbeOutcome::Answered,
+                Err(e) => ProbeOutcome::from_call_error(&e.to_string()),
+            };
+            (ProbeOutcome::Answered, signer, ProbeOutcome::Missing)
+        }
+        Err(e) => {
+            let verifier = ProbeOutcome::from_call_error(&e.to_string());
+            let index = if verifier == ProbeOutcome::Missing {
+                match retry_read(&retry, "Beacon.index", move || async move {
+                    beacon.index().call().await
+                })
+                .await
+                {
+                    Ok(_) => ProbeOutcome::Answered,
+                    Err(e) => ProbeOutcome::from_call_error(&e.to_string()),
+                }
+            } else {
+                ProbeOutcome::Missing
+            };
+            (verifier, ProbeOutcome::Missing, index)
+        }
+    };
+
+    let kind = classify_beacon(beacon_address, verifier, signer, index)?;
+    tracing::info!("Detected beacon {} as {:?}", beacon_address, kind);
+    Ok(kind)
+}
@@ -1,6 +1,7 @@
 use alloy::primitives::{Address, B256, Bytes, U256};
 use alloy::providers::Provider;
 use alloy::signers::Signer;
+use alloy::signers::local::PrivateKeySigner;
 use alloy::sol_types::SolType;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -11,8 +12,11 @@ use tracing;
 use crate::ReadOnlyProvider;
 use crate::models::{AppState, UpdateBeaconWithEcdsaRequest};
 use crate::routes::{IBeacon, IEcdsaVerifier};
+use crate::services::address::parse_address;
+use crate::services::metrics::GasOperation;
+use crate::services::rpc::retry_read;
 use crate::services::transaction::execution::is_insufficient_funds_error;
-use crate::services::wallet::{LockHeartbeat, WalletHandle, WalletLockGuard};
+use crate::services::wallet::{BeaconUpdateLock, WalletHandle};
 
 /// How long a sent-but-unresolved update tx keeps its beacon lock alive while a
 /// background watcher polls for the receipt, and how often it polls.
@@ -27,7 +31,7 @@ const PENDING_TX_POLL_TIMEOUT: Duration = Duration::from_secs(10);
 /// lock is released (guard drop) as soon as the tx gets a receipt, or when the
 /// grace window ends; if this instance dies, the lock's Redis TTL expires it.
 fn hold_beacon_lock_until_receipt(
-    lock: (LockHeartbeat, WalletLockGuard),
+    lock: BeaconUpdateLock,
     provider: Arc<ReadOnlyProvider>,
     tx_hash: B256,
     beacon_address: Address,
@@ -80,6 +84,28 @@ pub struct EcdsaUpdateOutcome {
     /// The beacon that was updated. The route uses it to dispatch a follow-up
     /// touch of the perps backed by this beacon (only when `confirmed`).
     pub beacon_address: Address,
+    /// Gas used by the update transaction (`None` while unconfirmed).
+    pub gas_used: Option<u64>,
+}
+
+/// Signs an ECDSAVerifier EIP-712 digest and packs the signature as the `proof`
+/// bytes the verifier expects: r || s || v (65 bytes, v = 27/28).
+pub async fn sign_ecdsa_proof(signer: &PrivateKeySigner, digest: B256) -> Result<Bytes, String> {
+    let signature = signer
+        .sign_hash(&digest)
+        .await
+        .map_err(|e| format!("Failed to sign digest with PRIVATE_KEY signer: {e}"))?;
+    // Alloy signature.as_bytes() returns [r (32 bytes) | s (32 bytes) | v (1 byte)]
+    Ok(Bytes::from(signature.as_bytes().to_vec()))
+}
+
+/// ABI-encodes an update's `inputs` as Solidity's `abi.encode(uint256[] measurement, uint256 nonce)`.
+pub fn encode_ecdsa_inputs(measurement: &[U256], nonce: U256) -> Bytes {
+    let inputs = <(
+        alloy::sol_types::sol_data::Array<alloy::sol_types::sol_data::Uint<256>>,
+        alloy::sol_types::sol_data::Uint<256>,
+    )>::abi_encode_params(&(measurement.to_vec(), nonce));
+    Bytes::from(inputs)
 }
 
 /// Updates a beacon using ECDSA signature from the PRIVATE_KEY wallet.
@@ -101,7 +127,7 @@ pub async fn update_beacon_with_ecdsa(
     request: UpdateBeaconWithEcdsaRequest,
 ) -> Result<EcdsaUpdateOutcome, String> {
     // 1. Parse beacon address and measurement(s)
-    let beacon_address = Address::from_str(&request.beacon_address)
+    let beacon_address = parse_address(&request.beacon_address, state.strict_address_checksum)
         .map_err(|e| format!("Invalid beacon address: {e}"))?;
 
     let measurement_array: Vec<U256> = request
@@ -125,23 +151,24 @@ pub async fn update_beacon_with_ecdsa(
     );
 
     // 2. Get verifier address from beacon using read provider
-    let beacon_read = IBeacon::new(beacon_address, &*state.provider.read_provider);
-    let verifier_address_raw = beacon_read
-        .verifier()
-        .call()
-        .await
-        .map_err(|e| format!("Failed to get verifier address: {e}"))?;
+    let retry = state.provider.read_retry;
+    let beacon_read = &IBeacon::new(beacon_address, &*state.provider.read_provider);
+    let verifier_address_raw = retry_read(&retry, "Beacon.verifier", move || async move {
+        beacon_read.verifier().call().await
+    })
+    .await
+    .map_err(|e| format!("Failed to get verifier address: {e}"))?;
     let verifier_address = Address::from(verifier_address_raw.0);
 
     tracing::info!("Beacon verifier: {}", verifier_address);
 
     // Get the designated signer from the verifier using read provider
-    let verifier = IEcdsaVerifier::new(verifier_address, &*state.provider.read_provider);
-    let designated_signer_raw = verifier
-        .SIGNER()
-        .call()
-        .await
-        .map_err(|e| format!("Failed to get designated signer: {e}"))?;
+    let verifier = &IEcdsaVerifier::new(verifier_address, &*state.provider.read_provider);
+    let designated_signer_raw = retry_read(&retry, "EcdsaVerifier.SIGNER", move || async move {
+        verifier.SIGNER().call().await
+    })
+    .await
+    .map_err(|e| format!("Failed to get designated signer: {e}"))?;
     let designated_signer = Address::from(designated_signer_raw.0);
 
     tracing::info!("Designated signer for this beacon: {}", designated_signer);
@@ -202,20 +229,11 @@ pub async fn update_beacon_with_ecdsa(
 
     tracing::info!("Got EIP-712 digest: {:?}", digest);
 
-    // 8. Sign the digest with PRIVATE_KEY signer (state.wallets.signer)
-    let signature = state
-        .wallets
-        .signer
-        .sign_hash(&digest)
-        .await
-        .map_err(|e| format!("Failed to sign digest with PRIVATE_KEY signer: {e}"))?;
+    // 8 + 9. Sign the digest with PRIVATE_KEY signer (state.wallets.signer), packed r || s || v
+    let sig_bytes = sign_ecdsa_proof(&state.wallets.signer, digest).await?;
 
     tracing::info!("Signed digest successfully");
 
-    // 9. Pack signature as r || s || v (65 bytes)
-    // Alloy signature.as_bytes() returns [r (32 bytes) | s (32 bytes) | v (1 byte)]
-    let sig_bytes = Bytes::from(signature.as_bytes().to_vec());
-
     let proof_hash = alloy::primitives::keccak256(sig_bytes.as_ref());
 
     tracing::info!(
@@ -223,20 +241,10 @@ pub async fn update_beacon_with_ecdsa(
         sig_bytes.len(),
         proof_hash
     );
-    tracing::debug!(
-        "Signature details: v={}, r={:#x}, s={:#x}",
-        if signature.v() { 28u8 } else { 27u8 },
-        signature.r(),
-        signature.s()
-    );
+    tracing::debug!("Signature details: {}", sig_bytes);
 
     // 10. ABI-encode inputs as (uint256[] measurement, uint256 nonce)
-    // Use abi_encode_params to match Solidity's abi.encode(uint256[], uint256)
-    let inputs = <(
-        alloy::sol_types::sol_data::Array<alloy::sol_types::sol_data::Uint<256>>,
-        alloy::sol_types::sol_data::Uint<256>,
-    )>::abi_encode_params(&(measurement_array.clone(), nonce));
-    let inputs_bytes = Bytes::from(inputs);
+    let inputs_bytes = encode_ecdsa_inputs(&measurement_array, nonce);
     let inputs_hash = alloy::primitives::keccak256(inputs_bytes.as_ref());
 
     tracing::info!(
@@ -276,7 +284,7 @@ pub async fn update_beacon_with_ecdsa(
 
         // Build provider with the acquired wallet for sending transactions
         let provider = handle
-            .build_provider(&state.provider.rpc_url)
+            .build_provider(&state.provider.endpoints)
             .map_err(|e| format!("Failed to build provider: {e}"))?;
 
         // 11. Simulate the update call first to get revert reason if it would fail
@@ -405,6 +413,7 @@ pub async fn update_beacon_with_ecdsa(
                 tx_hash,
                 confirmed: false,
                 beacon_address,
+                gas_used: None,
             });
         }
         Err(_) => {
@@ -425,10 +434,15 @@ pub async fn update_beacon_with_ecdsa(
                 tx_hash,
                 confirmed: false,
                 beacon_address,
+                gas_used: None,
             });
         }
     };
 
+    state
+        .gas_metrics
+        .record(GasOperation::BeaconUpdate, receipt.gas_used);
+
     // 14. Validate transaction status
     if !receipt.status() {
         let error_msg = format!("update() transaction {tx_hash} reverted (status: false)");
@@ -455,6 +469,7 @@ pub async fn update_beacon_with_ecdsa(
             tx_hash,
             confirmed: true,
             beacon_address,
+            gas_used: Some(receipt.gas_used),
         })
     } else {
         // Transaction succeeded but event not found - still consider it a success
@@ -468,6 +483,7 @@ pub async fn update_beacon_with_ecdsa(
             tx_hash,
             confirmed: true,
             beacon_address,
+            gas_used: Some(receipt.gas_used),
         })
     }
 }
@@ -5,10 +5,12 @@
 
 use alloy::primitives::Address;
 use std::time::Duration;
-use tokio::time::timeout;
 
 use crate::models::AppState;
-use crate::routes::IEcdsaVerifierFactory;
+use crate::routes::{IEcdsaVerifier, IEcdsaVerifierFactory};
+use crate::services::metrics::GasOperation;
+use crate::services::rpc::retry_read;
+use crate::services::transaction::execution::wait_for_receipt;
 use crate::services::wallet::WalletHandle;
 
 /// Creates an ECDSAVerifier via the ECDSAVerifierFactory contract.
@@ -30,10 +32,11 @@ pub async fn create_ecdsa_verifier(
 
     // Build provider from wallet handle
     let provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| format!("Failed to build provider for verifier creation: {e}"))?;
 
-    let factory = IEcdsaVerifierFactory::new(state.contracts.ecdsa_verifier_factory, &provider);
+    let factory =
+        IEcdsaVerifierFactory::new(state.contracts.load().ecdsa_verifier_factory, &provider);
 
     // Simulate the call first to get the return address (deterministic via CREATE opcode)
     let simulated = factory
@@ -59,18 +62,29 @@ pub async fn create_ecdsa_verifier(
     let tx_hash = *pending_tx.tx_hash();
     tracing::info!("Verifier creation tx sent: {:?}", tx_hash);
 
-    // Wait for receipt
-    let receipt = match timeout(Duration::from_secs(120), pending_tx.get_receipt()).await {
-        Ok(Ok(receipt)) => receipt,
-        Ok(Err(e)) => {
-            return Err(format!("Failed to get verifier creation receipt: {e}"));
-        }
-        Err(_) => {
-            return Err(format!(
-                "Timeout waiting for verifier creation receipt (tx: {tx_hash})"
-            ));
-        }
-    };
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        "verifier creation",
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(120)),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    state
+        .gas_metrics
+        .record(GasOperation::BeaconCreate, receipt.gas_used);
+    state
+        .history
+        .record_receipt(
+            GasOperation::BeaconCreate,
+            &[("step", "verifier creation".to_string())],
+            &receipt,
+        )
+        .await;
 
     // Check transaction status
     if !receipt.status() {
@@ -78,7 +92,12 @@ pub async fn create_ecdsa_verifier(
     }
 
     // The address came from a pre-send simulation; verify code actually exists there.
-    super::verify_deployed(&provider, verifier_address, "ECDSAVerifier").await?;
+    super::verify_deployed(
+        &*state.provider.read_provider,
+        verifier_address,
+        "ECDSAVerifier",
+    )
+    .await?;
 
     tracing::info!(
         "ECDSAVerifier created at {} (signer={})",
@@ -88,3 +107,31 @@ pub async fn create_ecdsa_verifier(
 
     Ok(verifier_address)
 }
+
+/// Checks that `verifier_address` is a deployed ECDSAVerifier whose `SIGNER()` is
+/// `state.wallets.signer.address()`, so a beacon pointed at it can be updated by this service.
+pub async fn check_existing_ecdsa_verifier(
+    state: &AppState,
+    verifier_address: Address,
+) -> Result<(), String> {
+    let read_provider = &*state.provider.read_provider;
+    super::verify_deployed(read_provider, verifier_address, "ECDSAVerifier").await?;
+
+    let verifier = &IEcdsaVerifier::new(verifier_address, read_provider);
+    let designated_signer = retry_read(
+        &state.provider.read_retry,
+        "EcdsaVerifier.SIGNER",
+        move || async move { verifier.SIGNER().call().await },
+    )
+    .await
+    .map_err(|e| format!("Failed to read SIGNER() from verifier {verifier_address}: {e}"))?;
+    let designated_signer = Address::from(designated_signer.0);
+
+    let signer_address = state.wallets.signer.address();
+    if designated_signer != signer_address {
+        return Err(format!(
+            "Verifier {verifier_address} expects signer {designated_signer}, but PRIVATE_KEY signs as {signer_address}"
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,498 @@
+//! `IndexUpdated` change feed
+//!
+//! A background poller scans `eth_getLogs` for `IndexUpdated(uint256)` emitted by a
+//! configured set of beacons (`BEACON_EVENTS_BEACONS`) and keeps the newest index seen
+//! per beacon, served by `GET /beacon_events/<address>`. The block cursor and the
+//! latest values are persisted in Redis under the wallet pool's prefix, so a restart
+//! resumes where it stopped instead of re-scanning. Like the touch worker, the feed is
+//! best-effort: a failed poll is logged and retried on the next tick, and missing or
+//! invalid config leaves it disabled rather than stopping startup.
+//!
+//! When `RPC_WS_URL` is set the feed streams logs over `eth_subscribe` instead of
+//! polling, catching up over HTTP after every (re)connect and polling over HTTP while
+//! the WebSocket is unavailable.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::{Address, B256, U256};
+use alloy::providers::Provider;
+use alloy::pubsub::Subscription;
+use alloy::rpc::types::{Filter, Log};
+use alloy::sol_types::SolEvent;
+use redis::AsyncCommands;
+use redis::aio::ConnectionManager;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::{MissedTickBehavior, interval};
+
+use crate::ReadOnlyProvider;
+use crate::models::wallet::PrefixedRedisKeys;
+use crate::routes::IBeacon;
+use crate::services::rpc::{ReadRetryPolicy, RpcConfig, retry_read};
+
+pub const DEFAULT_POLL_INTERVAL_SECS: u64 = 15;
+/// Blocks per `eth_getLogs` request; providers commonly reject wider ranges.
+pub const DEFAULT_MAX_BLOCK_RANGE: u64 = 2_000;
+
+/// The newest `IndexUpdated` seen for one beacon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObservedIndex {
+    pub index: U256,
+    pub block_number: u64,
+    pub log_index: u64,
+    pub transaction_hash: B256,
+}
+
+impl ObservedIndex {
+    /// Chain order of the log that produced this value.
+    fn position(&self) -> (u64, u64) {
+        (self.block_number, self.log_index)
+    }
+}
+
+/// Decode an `IndexUpdated` log into its emitter and observation. Returns `None` for
+/// other events and for pending logs without a block position.
+pub fn decode_index_updated(log: &Log) -> Option<(Address, ObservedIndex)> {
+    let decoded = log.log_decode::<IBeacon::IndexUpdated>().ok()?;
+    Some((
+        log.address(),
+        ObservedIndex {
+            index: decoded.inner.data.index,
+            block_number: log.block_number?,
+            log_index: log.log_index?,
+            transaction_hash: log.transaction_hash?,
+        },
+    ))
+}
+
+/// Fold `observed` into `latest`, keeping the newest value per beacon in chain order.
+/// Returns the entries that changed.
+pub fn merge_observations(
+    latest: &mut HashMap<Address, ObservedIndex>,
+    observed: impl IntoIterator<Item = (Address, ObservedIndex)>,
+) -> Vec<(Address, ObservedIndex)> {
+    let mut changed: HashMap<Address, ObservedIndex> = HashMap::new();
+    for (beacon, observation) in observed {
+        let newer = latest
+            .get(&beacon)
+            .is_none_or(|current| observation.position() > current.position());
+        if newer {
+            latest.insert(beacon, observation.clone());
+            changed.insert(beacon, observation);
+        }
+    }
+    changed.into_iter().collect()
+}
+
+/// Inclusive block range to scan after `cursor` (the last fully scanned block), at most
+/// `max_range` blocks wide. `None` once the cursor has reached `head`.
+pub fn next_scan_range(cursor: u64, head: u64, max_range: u64) -> Option<(u64, u64)> {
+    if cursor >= head {
+        return None;
+    }
+    let from = cursor + 1;
+    let to = head.min(from.saturating_add(max_range.max(1) - 1));
+    Some((from, to))
+}
+
+/// Latest `IndexUpdated` value per tracked beacon, fed by [`BeaconEventFeed::poll_once`].
+pub struct BeaconEventFeed {
+    tracked: Vec<Address>,
+    latest: RwLock<HashMap<Address, ObservedIndex>>,
+    /// Last fully scanned block; `None` until the first poll sets it. The lock also
+    /// serializes polls.
+    cursor: Mutex<Option<u64>>,
+    redis: Option<(ConnectionManager, PrefixedRedisKeys)>,
+    retry: ReadRetryPolicy,
+}
+
+impl BeaconEventFeed {
+    /// In-memory feed over `tracked`, starting after block `start_after` if given
+    /// (otherwise at the chain head on the first poll).
+    pub fn new(tracked: Vec<Address>, start_after: Option<u64>) -> Self {
+        Self {
+            tracked,
+            latest: RwLock::new(HashMap::new()),
+            cursor: Mutex::new(start_after),
+            redis: None,
+            retry: ReadRetryPolicy::default(),
+        }
+    }
+
+    /// A feed that tracks nothing (feature disabled).
+    pub fn disabled() -> Self {
+        Self::new(Vec::new(), None)
+    }
+
+    /// Persist the cursor and latest values in Redis, under
+    /// `<prefix>beacon_events:cursor` and `<prefix>beacon_events:latest`.
+    pub fn with_redis(mut self, conn: ConnectionManager, prefix: &str) -> Self {
+        self.redis = Some((conn, PrefixedRedisKeys::new(prefix)));
+        self
+    }
+
+    /// Retry each poll's RPC reads per `retry` instead of the defaults.
+    pub fn with_read_retry(mut self, retry: ReadRetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.tracked.is_empty()
+    }
+
+    pub fn is_tracked(&self, beacon: &Address) -> bool {
+        self.tracked.contains(beacon)
+    }
+
+    pub fn tracked(&self) -> &[Address] {
+        &self.tracked
+    }
+
+    /// Newest observed value for `beacon`, if any has been seen.
+    pub async fn latest(&self, beacon: &Address) -> Option<ObservedIndex> {
+        self.latest.read().await.get(beacon).cloned()
+    }
+
+    /// Last fully scanned block, if a poll has run.
+    pub async fn scanned_through(&self) -> Option<u64> {
+        *self.cursor.lock().await
+    }
+
+    /// Record observations, keeping the newest per tracked beacon. Logs from
+    /// untracked addresses are ignored. Returns the entries that changed.
+    pub async fn apply(
+        &self,
+        observed: impl IntoIterator<Item = (Address, ObservedIndex)>,
+    ) -> Vec<(Address, ObservedIndex)> {
+        let observed = observed
+            .into_iter()
+            .filter(|(beacon, _)| self.is_tracked(beacon));
+        merge_observations(&mut *self.latest.write().await, observed)
+    }
+
+    /// Restore the cursor and latest values persisted by a previous run. A persisted
+    /// cursor wins over the `start_after` given to [`Self::new`].
+    pub async fn restore(&self) -> Result<(), String> {
+        let Some((conn, keys)) = &self.redis else {
+            return Ok(());
+        };
+        let mut conn = conn.clone();
+
+        let cursor: Option<u64> = conn
+            .get(keys.beacon_events_cursor())
+            .await
+            .map_err(|e| format!("Failed to read beacon event cursor: {e}"))?;
+        let stored: HashMap<String, String> = conn
+            .hgetall(keys.beacon_events_latest())
+            .await
+            .map_err(|e| format!("Failed to read beacon event values: {e}"))?;
+
+        let mut observed: Vec<(Address, ObservedIndex)> = Vec::new();
+        for (beacon, json) in stored {
+            match (beacon.parse::<Address>(), serde_json::from_str(&json)) {
+                (Ok(beacon), Ok(observation)) => observed.push((beacon, observation)),
+                _ => tracing::warn!("Skipping unreadable beacon event entry for {beacon}"),
+            }
+        }
+        let restored = self.apply(observed).await.len();
+
+        if let Some(cursor) = cursor {
+            *self.cursor.lock().await = Some(cursor);
+        }
+        tracing::info!(
+            "Restored beacon event feed: cursor={:?}, {} beacon value(s)",
+            cursor,
+            restored
+        );
+        Ok(())
+    }
+
+    /// Scan the next block range for `IndexUpdated` logs from the tracked beacons.
+    ///
+    /// The cursor only advances after the new values are persisted, so a failed poll
+    /// re-scans the same range next time. Returns `true` while more blocks remain
+    /// before the head.
+    pub async fn poll_once(
+        &self,
+        provider: &ReadOnlyProvider,
+        max_range: u64,
+    ) -> Result<bool, String> {
+        if !self.is_enabled() {
+            return Ok(false);
+        }
+        let retry = &self.retry;
+        let mut cursor = self.cursor.lock().await;
+
+        let head = retry_read(retry, "get_block_number", || provider.get_block_number())
+            .await
+            .map_err(|e| format!("Failed to get block number: {e}"))?;
+
+        let Some(scanned) = *cursor else {
+            // First run with no persisted cursor: start from the head, not genesis.
+            self.persist(&[], head).await?;
+            *cursor = Some(head);
+            tracing::info!("Beacon event feed starting at block {head}");
+            return Ok(false);
+        };
+
+        let Some((from, to)) = next_scan_range(scanned, head, max_range) else {
+            return Ok(false);
+        };
+
+        let filter = self.log_filter().from_block(from).to_block(to);
+        let filter = &filter;
+        let logs = retry_read(retry, "get_logs(IndexUpdated)", move || {
+            provider.get_logs(filter)
+        })
+        .await
+        .map_err(|e| format!("Failed to get IndexUpdated logs for blocks {from}..={to}: {e}"))?;
+
+        let changed = self
+            .apply(logs.iter().filter_map(decode_index_updated))
+            .await;
+        self.persist(&changed, to).await?;
+        *cursor = Some(to);
+
+        if !changed.is_empty() {
+            tracing::info!(
+                "Beacon event feed: {} beacon(s) updated in blocks {from}..={to}",
+                changed.len()
+            );
+        }
+        Ok(to < head)
+    }
+
+    /// Record one log delivered by an `eth_subscribe` stream. Subscriptions deliver logs
+    /// in chain order, so every block before this log's has been seen and the cursor
+    /// moves up to the previous block. Reorged-out (`removed`) logs are skipped.
+    pub async fn observe_streamed(&self, log: &Log) -> Result<(), String> {
+        if log.removed {
+            return Ok(());
+        }
+        let Some((beacon, observation)) = decode_index_updated(log) else {
+            return Ok(());
+        };
+        let block_number = observation.block_number;
+        let mut cursor = self.cursor.lock().await;
+        let changed = self.apply([(beacon, observation)]).await;
+        let scanned = cursor.unwrap_or(0).max(block_number.saturating_sub(1));
+        self.persist(&changed, scanned).await?;
+        *cursor = Some(scanned);
+
+        if !changed.is_empty() {
+            tracing::info!("Beacon event feed: {beacon} updated in block {block_number}");
+        }
+        Ok(())
+    }
+
+    /// `IndexUpdated` logs from the tracked beacons, without a block range.
+    fn log_filter(&self) -> Filter {
+        Filter::new()
+            .address(self.tracked.clone())
+            .event_signature(IBeacon::IndexUpdated::SIGNATURE_HASH)
+    }
+
+    async fn persist(
+        &self,
+        changed: &[(Address, ObservedIndex)],
+        cursor: u64,
+    ) -> Result<(), String> {
+        let Some((conn, keys)) = &self.redis else {
+            return Ok(());
+        };
+        let mut conn = conn.clone();
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for (beacon, observation) in changed {
+            let json = serde_json::to_string(observation)
+                .map_err(|e| format!("Failed to serialize beacon event: {e}"))?;
+            pipe.hset(keys.beacon_events_latest(), beacon.to_string(), json);
+        }
+        pipe.set(keys.beacon_events_cursor(), cursor);
+        let _: () = pipe
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to persist beacon events: {e}"))?;
+        Ok(())
+    }
+}
+
+/// Build the feed and, when `BEACON_EVENTS_BEACONS` lists at least one beacon, restore its
+/// persisted state and spawn the poller. Returns a disabled feed when the list is unset or
+/// invalid - the change feed must never take down the service. Polls retry their reads
+/// per `retry`.
+///
+/// Must be called from within the tokio runtime (it may `tokio::spawn`).
+pub async fn spawn_from_env(
+    provider: Arc<ReadOnlyProvider>,
+    ws_url: Option<String>,
+    conn: ConnectionManager,
+    prefix: &str,
+    retry: ReadRetryPolicy,
+) -> Arc<BeaconEventFeed> {
+    let tracked = match parse_beacon_list(&env::var("BEACON_EVENTS_BEACONS").unwrap_or_default()) {
+        Ok(tracked) if tracked.is_empty() => {
+            tracing::info!("BEACON_EVENTS_BEACONS is unset; beacon event feed disabled");
+            return Arc::new(BeaconEventFeed::disabled());
+        }
+        Ok(tracked) => tracked,
+        Err(e) => {
+            tracing::error!("Invalid BEACON_EVENTS_BEACONS ({e}); beacon event feed disabled");
+            return Arc::new(BeaconEventFeed::disabled());
+        }
+    };
+
+    let start_after = env_parse::<u64>("BEACON_EVENTS_START_BLOCK").map(|b| b.saturating_sub(1));
+    // Floor to 1s: tokio::time::interval panics on a zero period.
+    let poll_interval = Duration::from_secs(
+        env_parse("BEACON_EVENTS_POLL_INTERVAL_SECS")
+            .unwrap_or(DEFAULT_POLL_INTERVAL_SECS)
+            .max(1),
+    );
+    let max_range = env_parse("BEACON_EVENTS_MAX_BLOCK_RANGE")
+        .unwrap_or(DEFAULT_MAX_BLOCK_RANGE)
+        .max(1);
+
+    let feed = Arc::new(
+        BeaconEventFeed::new(tracked, start_after)
+            .with_redis(conn, prefix)
+            .with_read_retry(retry),
+    );
+    if let Err(e) = feed.restore().await {
+        tracing::error!("{e}; beacon event feed disabled");
+        return Arc::new(BeaconEventFeed::disabled());
+    }
+
+    let mode = match ws_url {
+        Some(ws_url) => {
+            tokio::spawn(run_subscriber(
+                Arc::clone(&feed),
+                provider,
+                ws_url,
+                poll_interval,
+                max_range,
+            ));
+            "streaming over RPC_WS_URL".to_string()
+        }
+        None => {
+            tokio::spawn(run_poller(
+                Arc::clone(&feed),
+                provider,
+                poll_interval,
+                max_range,
+            ));
+            format!("polling every {}s", poll_interval.as_secs())
+        }
+    };
+    tracing::info!(
+        "Beacon event feed enabled for {} beacon(s), {mode}",
+        feed.tracked().len()
+    );
+    feed
+}
+
+/// Scan over HTTP until caught up with the head. Returns `false` if a poll failed.
+async fn catch_up(feed: &BeaconEventFeed, provider: &ReadOnlyProvider, max_range: u64) -> bool {
+    loop {
+        match feed.poll_once(provider, max_range).await {
+            Ok(true) => continue,
+            Ok(false) => return true,
+            Err(e) => {
+                tracing::warn!("Beacon event poll failed (retrying next tick): {e}");
+                return false;
+            }
+        }
+    }
+}
+
+/// Poll forever: each tick scans until caught up with the head (or a poll fails).
+async fn run_poller(
+    feed: Arc<BeaconEventFeed>,
+    provider: Arc<ReadOnlyProvider>,
+    poll_interval: Duration,
+    max_range: u64,
+) {
+    let mut tick = interval(poll_interval);
+    tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        tick.tick().await;
+        catch_up(&feed, &provider, max_range).await;
+    }
+}
+
+async fn subscribe(
+    feed: &BeaconEventFeed,
+    ws_url: &str,
+) -> Result<(ReadOnlyProvider, Subscription<Log>), String> {
+    let ws = RpcConfig::build_ws_provider(ws_url).await?;
+    let subscription = ws
+        .subscribe_logs(&feed.log_filter())
+        .await
+        .map_err(|e| format!("Failed to subscribe to IndexUpdated logs: {e}"))?;
+    Ok((ws, subscription))
+}
+
+/// Stream logs over `eth_subscribe` forever. Each (re)connect subscribes first and then
+/// catches up over HTTP, so blocks mined in between are covered by one or the other;
+/// duplicates are dropped by the chain-order merge. While the WebSocket is down the
+/// feed falls back to one HTTP poll per interval.
+async fn run_subscriber(
+    feed: Arc<BeaconEventFeed>,
+    provider: Arc<ReadOnlyProvider>,
+    ws_url: String,
+    poll_interval: Duration,
+    max_range: u64,
+) {
+    loop {
+        // Keep the WebSocket provider alive for as long as the subscription is read.
+        let (_ws, mut subscription) = match subscribe(&feed, &ws_url).await {
+            Ok(connected) => connected,
+            Err(e) => {
+                tracing::warn!("{e}; polling over HTTP until the next attempt");
+                catch_up(&feed, &provider, max_range).await;
+                tokio::time::sleep(poll_interval).await;
+                continue;
+            }
+        };
+
+        if catch_up(&feed, &provider, max_range).await {
+            tracing::info!("Beacon event feed subscribed over WebSocket");
+        }
+        loop {
+            match subscription.recv().await {
+                Ok(log) => {
+                    if let Err(e) = feed.observe_streamed(&log).await {
+                        tracing::warn!("Failed to record streamed beacon event: {e}");
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Beacon event subscription ended ({e}); reconnecting");
+                    break;
+                }
+            }
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Parse a comma-separated beacon address list, ignoring blanks and duplicates.
+pub fn parse_beacon_list(raw: &str) -> Result<Vec<Address>, String> {
+    let mut beacons = Vec::new();
+    for entry in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let beacon = entry
+            .parse::<Address>()
+            .map_err(|e| format!("'{entry}' is not an address: {e}"))?;
+        if !beacons.contains(&beacon) {
+            beacons.push(beacon);
+        }
+    }
+    Ok(beacons)
+}
+
+fn env_parse<T: std::str::FromStr>(key: &str) -> Option<T> {
+    env::var(key).ok().and_then(|v| v.trim().parse::<T>().ok())
+}
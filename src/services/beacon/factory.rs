@@ -5,7 +5,6 @@
 use alloy::primitives::{Address, U256};
 use std::str::FromStr;
 use std::time::Duration;
-use tokio::time::timeout;
 
 use crate::models::AppState;
 use crate::models::beacon_type::BeaconTypeConfig;
@@ -13,15 +12,17 @@ use crate::models::requests::{CreateLBCGBMBeaconRequest, CreateWeightedSumCompos
 use crate::models::responses::CreateBeaconResponse;
 use crate::routes::{ILBCGBMFactory, IWeightedSumCompositeFactory};
 use crate::services::beacon::core::{RegistrationOutcome, register_beacon_with_registry};
+use crate::services::metrics::GasOperation;
+use crate::services::transaction::{wait_for_confirmations, wait_for_receipt};
 
 /// Create an LBCGBM standalone beacon via the on-chain factory.
 ///
-/// Returns the beacon address.
+/// Returns the beacon address and the confirmation depth reached (see `CONFIRMATION_BLOCKS`).
 pub async fn create_lbcgbm_beacon(
     state: &AppState,
     config: &BeaconTypeConfig,
     request: &CreateLBCGBMBeaconRequest,
-) -> Result<Address, String> {
+) -> Result<(Address, u64), String> {
     let signer_address = state.wallets.signer.address();
     tracing::info!(
         "Creating LBCGBM beacon via factory {} with signer={}",
@@ -42,7 +43,7 @@ pub async fn create_lbcgbm_beacon(
     );
 
     let provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| format!("Failed to build provider: {e}"))?;
 
     let factory = ILBCGBMFactory::new(config.factory_address, &provider);
@@ -97,15 +98,29 @@ pub async fn create_lbcgbm_beacon(
     let tx_hash = *pending_tx.tx_hash();
     tracing::info!("LBCGBM beacon creation tx sent: {:?}", tx_hash);
 
-    let receipt = match timeout(Duration::from_secs(120), pending_tx.get_receipt()).await {
-        Ok(Ok(receipt)) => receipt,
-        Ok(Err(e)) => return Err(format!("Failed to get LBCGBM beacon creation receipt: {e}")),
-        Err(_) => {
-            return Err(format!(
-                "Timeout waiting for LBCGBM beacon creation receipt (tx: {tx_hash})"
-            ));
-        }
-    };
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        "LBCGBM beacon creation",
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(120)),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    state
+        .gas_metrics
+        .record(GasOperation::BeaconCreate, receipt.gas_used);
+    state
+        .history
+        .record_receipt(
+            GasOperation::BeaconCreate,
+            &[("step", "LBCGBM beacon creation".to_string())],
+            &receipt,
+        )
+        .await;
 
     if !receipt.status() {
         return Err(format!(
@@ -113,22 +128,36 @@ pub async fn create_lbcgbm_beacon(
         ));
     }
 
+    let confirmations = wait_for_confirmations(
+        &state.provider.read_provider,
+        &state.provider.confirmations,
+        &state.provider.read_retry,
+        tx_hash,
+        receipt.block_number.unwrap_or_default(),
+    )
+    .await?;
+
     // The address came from a pre-send simulation; verify code actually exists there.
-    super::verify_deployed(&provider, beacon_address, "LBCGBM beacon").await?;
+    super::verify_deployed(
+        &*state.provider.read_provider,
+        beacon_address,
+        "LBCGBM beacon",
+    )
+    .await?;
 
     tracing::info!("LBCGBM beacon created at {}", beacon_address);
 
-    Ok(beacon_address)
+    Ok((beacon_address, confirmations))
 }
 
 /// Create a WeightedSumComposite beacon via the on-chain factory.
 ///
-/// Returns the beacon address.
+/// Returns the beacon address and the confirmation depth reached (see `CONFIRMATION_BLOCKS`).
 pub async fn create_weighted_sum_composite_beacon(
     state: &AppState,
     config: &BeaconTypeConfig,
     request: &CreateWeightedSumCompositeBeaconRequest,
-) -> Result<Address, String> {
+) -> Result<(Address, u64), String> {
     if request.reference_beacons.len() != request.weights.len() {
         return Err(format!(
             "reference_beacons length ({}) must match weights length ({})",
@@ -168,7 +197,7 @@ pub async fn create_weighted_sum_composite_beacon(
     );
 
     let provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| format!("Failed to build provider: {e}"))?;
 
     let factory = IWeightedSumCompositeFactory::new(config.factory_address, &provider);
@@ -199,19 +228,29 @@ pub async fn create_weighted_sum_composite_beacon(
     let tx_hash = *pending_tx.tx_hash();
     tracing::info!("Composite beacon creation tx sent: {:?}", tx_hash);
 
-    let receipt = match timeout(Duration::from_secs(120), pending_tx.get_receipt()).await {
-        Ok(Ok(receipt)) => receipt,
-        Ok(Err(e)) => {
-            return Err(format!(
-                "Failed to get composite beacon creation receipt: {e}"
-            ));
-        }
-        Err(_) => {
-            return Err(format!(
-                "Timeout waiting for composite beacon creation receipt (tx: {tx_hash})"
-            ));
-        }
-    };
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        "composite beacon creation",
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(120)),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    state
+        .gas_metrics
+        .record(GasOperation::BeaconCreate, receipt.gas_used);
+    state
+        .history
+        .record_receipt(
+            GasOperation::BeaconCreate,
+            &[("step", "composite beacon creation".to_string())],
+            &receipt,
+        )
+        .await;
 
     if !receipt.status() {
         return Err(format!(
@@ -219,12 +258,26 @@ pub async fn create_weighted_sum_composite_beacon(
         ));
     }
 
+    let confirmations = wait_for_confirmations(
+        &state.provider.read_provider,
+        &state.provider.confirmations,
+        &state.provider.read_retry,
+        tx_hash,
+        receipt.block_number.unwrap_or_default(),
+    )
+    .await?;
+
     // The address came from a pre-send simulation; verify code actually exists there.
-    super::verify_deployed(&provider, beacon_address, "WeightedSumComposite beacon").await?;
+    super::verify_deployed(
+        &*state.provider.read_provider,
+        beacon_address,
+        "WeightedSumComposite beacon",
+    )
+    .await?;
 
     tracing::info!("WeightedSumComposite beacon created at {}", beacon_address);
 
-    Ok(beacon_address)
+    Ok((beacon_address, confirmations))
 }
 
 /// Create a beacon via factory and optionally register it. Returns CreateBeaconResponse.
@@ -266,10 +319,11 @@ pub async fn create_and_register_factory_beacon(
     };
 
     Ok(CreateBeaconResponse {
-        beacon_address: format!("{beacon_address:#x}"),
+        beacon_address: beacon_address.to_checksum(None),
         beacon_type: config.slug.clone(),
-        factory_address: format!("{:#x}", config.factory_address),
+        factory_address: config.factory_address.to_checksum(None),
         registered,
         safe_proposal_hash,
+        confirmations: None,
     })
 }
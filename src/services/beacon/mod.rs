@@ -1,19 +1,26 @@
 pub mod batch;
+pub mod batch_create;
 pub mod component_registry;
 pub mod core;
+pub mod detect;
 pub mod ecdsa;
 pub mod ecdsa_deploy;
+pub mod events;
 pub mod factory;
 pub mod modular;
+pub mod read;
 pub mod recipe_registry;
 pub mod registry;
 pub mod verifiable;
 
 pub use batch::*;
+pub use batch_create::*;
 pub use component_registry::ComponentFactoryRegistry;
 pub use core::*;
+pub use detect::*;
 pub use ecdsa::*;
-pub use ecdsa_deploy::create_ecdsa_verifier;
+pub use ecdsa_deploy::{check_existing_ecdsa_verifier, create_ecdsa_verifier};
+pub use events::{BeaconEventFeed, ObservedIndex};
 pub use factory::*;
 pub use recipe_registry::RecipeRegistry;
 pub use registry::BeaconTypeRegistry;
@@ -6,7 +6,6 @@
 use alloy::primitives::{Address, I256, U256};
 use std::str::FromStr;
 use std::time::Duration;
-use tokio::time::timeout;
 
 use crate::AlloyProvider;
 use crate::models::AppState;
@@ -25,6 +24,8 @@ use crate::routes::{
     IStandaloneBeaconFactory, ITernaryToBinaryFactory, IThresholdFactory, IUnboundedFactory,
     IWeightedSumComponentFactory,
 };
+use crate::services::metrics::GasOperation;
+use crate::services::transaction::execution::wait_for_receipt;
 use crate::services::wallet::WalletHandle;
 
 /// WAD constant (10^18)
@@ -83,7 +84,7 @@ pub async fn create_modular_beacon(
 
     // Build provider from wallet handle
     let provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| format!("Failed to build provider: {e}"))?;
 
     match &recipe.beacon_kind {
@@ -175,8 +176,8 @@ async fn create_identity_beacon_modular(
     let tx_hash = *pending_tx.tx_hash();
     tracing::info!("Identity beacon creation tx sent: {:?}", tx_hash);
 
-    wait_for_receipt("identity beacon creation", tx_hash, pending_tx).await?;
-    super::verify_deployed(provider, beacon_addr, "identity beacon").await?;
+    confirm_creation(state, "identity beacon creation", pending_tx).await?;
+    verify_created(state, beacon_addr, "identity beacon").await?;
 
     tracing::info!("Identity beacon created at {}", beacon_addr);
 
@@ -266,8 +267,8 @@ async fn create_standalone_beacon_modular(
     let tx_hash = *pending_tx.tx_hash();
     tracing::info!("Standalone beacon creation tx sent: {:?}", tx_hash);
 
-    wait_for_receipt("standalone beacon creation", tx_hash, pending_tx).await?;
-    super::verify_deployed(provider, beacon_addr, "standalone beacon").await?;
+    confirm_creation(state, "standalone beacon creation", pending_tx).await?;
+    verify_created(state, beacon_addr, "standalone beacon").await?;
 
     tracing::info!("Standalone beacon created at {}", beacon_addr);
 
@@ -275,9 +276,9 @@ async fn create_standalone_beacon_modular(
         beacon_address: beacon_addr,
         verifier_address: Some(verifier_addr),
         components: BeaconComponentAddresses {
-            preprocessor: Some(format!("{preprocessor_addr:#x}")),
-            base_fn: Some(format!("{basefn_addr:#x}")),
-            transform: Some(format!("{transform_addr:#x}")),
+            preprocessor: Some(preprocessor_addr.to_checksum(None)),
+            base_fn: Some(basefn_addr.to_checksum(None)),
+            transform: Some(transform_addr.to_checksum(None)),
             ..Default::default()
         },
     })
@@ -348,8 +349,8 @@ async fn create_composite_beacon_modular(
     let tx_hash = *pending_tx.tx_hash();
     tracing::info!("Composite beacon creation tx sent: {:?}", tx_hash);
 
-    wait_for_receipt("composite beacon creation", tx_hash, pending_tx).await?;
-    super::verify_deployed(provider, beacon_addr, "composite beacon").await?;
+    confirm_creation(state, "composite beacon creation", pending_tx).await?;
+    verify_created(state, beacon_addr, "composite beacon").await?;
 
     tracing::info!("Composite beacon created at {}", beacon_addr);
 
@@ -357,7 +358,7 @@ async fn create_composite_beacon_modular(
         beacon_address: beacon_addr,
         verifier_address: None,
         components: BeaconComponentAddresses {
-            composer: Some(format!("{composer_addr:#x}")),
+            composer: Some(composer_addr.to_checksum(None)),
             ..Default::default()
         },
     })
@@ -453,8 +454,8 @@ async fn create_group_beacon_modular(
     let tx_hash = *pending_tx.tx_hash();
     tracing::info!("Group manager creation tx sent: {:?}", tx_hash);
 
-    wait_for_receipt("group manager creation", tx_hash, pending_tx).await?;
-    super::verify_deployed(provider, beacon_addr, "group manager").await?;
+    confirm_creation(state, "group manager creation", pending_tx).await?;
+    verify_created(state, beacon_addr, "group manager").await?;
 
     tracing::info!("Group manager created at {}", beacon_addr);
 
@@ -462,8 +463,8 @@ async fn create_group_beacon_modular(
         beacon_address: beacon_addr,
         verifier_address: Some(verifier_addr),
         components: BeaconComponentAddresses {
-            group_fn: Some(format!("{groupfn_addr:#x}")),
-            group_transform: Some(format!("{grouptransform_addr:#x}")),
+            group_fn: Some(groupfn_addr.to_checksum(None)),
+            group_transform: Some(grouptransform_addr.to_checksum(None)),
             ..Default::default()
         },
     })
@@ -515,8 +516,8 @@ async fn create_verifier(
     let tx_hash = *pending_tx.tx_hash();
     tracing::info!("ECDSA verifier creation tx sent: {:?}", tx_hash);
 
-    wait_for_receipt("ECDSA verifier creation", tx_hash, pending_tx).await?;
-    super::verify_deployed(provider, verifier_addr, "ECDSA verifier").await?;
+    confirm_creation(state, "ECDSA verifier creation", pending_tx).await?;
+    verify_created(state, verifier_addr, "ECDSA verifier").await?;
 
     tracing::info!("ECDSAVerifier created at {}", verifier_addr);
     Ok(verifier_addr)
@@ -569,8 +570,8 @@ async fn create_preprocessor(
             let tx_hash = *pending_tx.tx_hash();
             tracing::info!("Identity preprocessor creation tx sent: {:?}", tx_hash);
 
-            wait_for_receipt("identity preprocessor creation", tx_hash, pending_tx).await?;
-            super::verify_deployed(provider, addr, "identity preprocessor creation").await?;
+            confirm_creation(state, "identity preprocessor creation", pending_tx).await?;
+            verify_created(state, addr, "identity preprocessor creation").await?;
             addr
         }
         PreprocessorSpec::Threshold => {
@@ -601,8 +602,8 @@ async fn create_preprocessor(
             let tx_hash = *pending_tx.tx_hash();
             tracing::info!("Threshold preprocessor creation tx sent: {:?}", tx_hash);
 
-            wait_for_receipt("threshold preprocessor creation", tx_hash, pending_tx).await?;
-            super::verify_deployed(provider, addr, "threshold preprocessor creation").await?;
+            confirm_creation(state, "threshold preprocessor creation", pending_tx).await?;
+            verify_created(state, addr, "threshold preprocessor creation").await?;
             addr
         }
         PreprocessorSpec::TernaryToBinary => {
@@ -640,14 +641,8 @@ async fn create_preprocessor(
                 tx_hash
             );
 
-            wait_for_receipt(
-                "ternary-to-binary preprocessor creation",
-                tx_hash,
-                pending_tx,
-            )
-            .await?;
-            super::verify_deployed(provider, addr, "ternary-to-binary preprocessor creation")
-                .await?;
+            confirm_creation(state, "ternary-to-binary preprocessor creation", pending_tx).await?;
+            verify_created(state, addr, "ternary-to-binary preprocessor creation").await?;
             addr
         }
         PreprocessorSpec::Argmax => {
@@ -677,8 +672,8 @@ async fn create_preprocessor(
             let tx_hash = *pending_tx.tx_hash();
             tracing::info!("Argmax preprocessor creation tx sent: {:?}", tx_hash);
 
-            wait_for_receipt("argmax preprocessor creation", tx_hash, pending_tx).await?;
-            super::verify_deployed(provider, addr, "argmax preprocessor creation").await?;
+            confirm_creation(state, "argmax preprocessor creation", pending_tx).await?;
+            verify_created(state, addr, "argmax preprocessor creation").await?;
             addr
         }
     };
@@ -751,8 +746,8 @@ async fn create_base_fn(
             let tx_hash = *pending_tx.tx_hash();
             tracing::info!("CGBM base function creation tx sent: {:?}", tx_hash);
 
-            wait_for_receipt("CGBM base function creation", tx_hash, pending_tx).await?;
-            super::verify_deployed(provider, addr, "CGBM base function creation").await?;
+            confirm_creation(state, "CGBM base function creation", pending_tx).await?;
+            verify_created(state, addr, "CGBM base function creation").await?;
             addr
         }
         BaseFnSpec::DGBM => {
@@ -791,8 +786,8 @@ async fn create_base_fn(
             let tx_hash = *pending_tx.tx_hash();
             tracing::info!("DGBM base function creation tx sent: {:?}", tx_hash);
 
-            wait_for_receipt("DGBM base function creation", tx_hash, pending_tx).await?;
-            super::verify_deployed(provider, addr, "DGBM base function creation").await?;
+            confirm_creation(state, "DGBM base function creation", pending_tx).await?;
+            verify_created(state, addr, "DGBM base function creation").await?;
             addr
         }
     };
@@ -847,8 +842,8 @@ async fn create_transform(
             let tx_hash = *pending_tx.tx_hash();
             tracing::info!("Bounded transform creation tx sent: {:?}", tx_hash);
 
-            wait_for_receipt("bounded transform creation", tx_hash, pending_tx).await?;
-            super::verify_deployed(provider, addr, "bounded transform creation").await?;
+            confirm_creation(state, "bounded transform creation", pending_tx).await?;
+            verify_created(state, addr, "bounded transform creation").await?;
             addr
         }
         TransformSpec::Unbounded => {
@@ -883,8 +878,8 @@ async fn create_transform(
             let tx_hash = *pending_tx.tx_hash();
             tracing::info!("Unbounded transform creation tx sent: {:?}", tx_hash);
 
-            wait_for_receipt("unbounded transform creation", tx_hash, pending_tx).await?;
-            super::verify_deployed(provider, addr, "unbounded transform creation").await?;
+            confirm_creation(state, "unbounded transform creation", pending_tx).await?;
+            verify_created(state, addr, "unbounded transform creation").await?;
             addr
         }
     };
@@ -937,8 +932,8 @@ async fn create_composer(
             let tx_hash = *pending_tx.tx_hash();
             tracing::info!("WeightedSum composer creation tx sent: {:?}", tx_hash);
 
-            wait_for_receipt("weighted sum composer creation", tx_hash, pending_tx).await?;
-            super::verify_deployed(provider, addr, "weighted sum composer creation").await?;
+            confirm_creation(state, "weighted sum composer creation", pending_tx).await?;
+            verify_created(state, addr, "weighted sum composer creation").await?;
             addr
         }
     };
@@ -997,8 +992,8 @@ async fn create_group_fn(
             let tx_hash = *pending_tx.tx_hash();
             tracing::info!("Dominance group function creation tx sent: {:?}", tx_hash);
 
-            wait_for_receipt("dominance group function creation", tx_hash, pending_tx).await?;
-            super::verify_deployed(provider, addr, "dominance group function creation").await?;
+            confirm_creation(state, "dominance group function creation", pending_tx).await?;
+            verify_created(state, addr, "dominance group function creation").await?;
             addr
         }
         GroupFnSpec::RelativeDominance => {
@@ -1060,14 +1055,13 @@ async fn create_group_fn(
                 tx_hash
             );
 
-            wait_for_receipt(
+            confirm_creation(
+                state,
                 "relative dominance group function creation",
-                tx_hash,
                 pending_tx,
             )
             .await?;
-            super::verify_deployed(provider, addr, "relative dominance group function creation")
-                .await?;
+            verify_created(state, addr, "relative dominance group function creation").await?;
             addr
         }
         GroupFnSpec::ContinuousAllocation => {
@@ -1110,18 +1104,13 @@ async fn create_group_fn(
                 tx_hash
             );
 
-            wait_for_receipt(
+            confirm_creation(
+                state,
                 "continuous allocation group function creation",
-                tx_hash,
                 pending_tx,
             )
             .await?;
-            super::verify_deployed(
-                provider,
-                addr,
-                "continuous allocation group function creation",
-            )
-            .await?;
+            verify_created(state, addr, "continuous allocation group function creation").await?;
             addr
         }
         GroupFnSpec::DiscreteAllocation => {
@@ -1164,18 +1153,13 @@ async fn create_group_fn(
                 tx_hash
             );
 
-            wait_for_receipt(
+            confirm_creation(
+                state,
                 "discrete allocation group function creation",
-                tx_hash,
                 pending_tx,
             )
             .await?;
-            super::verify_deployed(
-                provider,
-                addr,
-                "discrete allocation group function creation",
-            )
-            .await?;
+            verify_created(state, addr, "discrete allocation group function creation").await?;
             addr
         }
     };
@@ -1229,8 +1213,8 @@ async fn create_group_transform(
             let tx_hash = *pending_tx.tx_hash();
             tracing::info!("Softmax group transform creation tx sent: {:?}", tx_hash);
 
-            wait_for_receipt("softmax group transform creation", tx_hash, pending_tx).await?;
-            super::verify_deployed(provider, addr, "softmax group transform creation").await?;
+            confirm_creation(state, "softmax group transform creation", pending_tx).await?;
+            verify_created(state, addr, "softmax group transform creation").await?;
             addr
         }
         GroupTransformSpec::GMNormalize => {
@@ -1267,8 +1251,8 @@ async fn create_group_transform(
                 tx_hash
             );
 
-            wait_for_receipt("gm-normalize group transform creation", tx_hash, pending_tx).await?;
-            super::verify_deployed(provider, addr, "gm-normalize group transform creation").await?;
+            confirm_creation(state, "gm-normalize group transform creation", pending_tx).await?;
+            verify_created(state, addr, "gm-normalize group transform creation").await?;
             addr
         }
     };
@@ -1293,33 +1277,55 @@ fn require_param_vec<T: Clone>(val: &Option<Vec<T>>, name: &str) -> Result<Vec<T
         .ok_or_else(|| format!("Missing required parameter: {name}"))
 }
 
-/// Wait for a pending transaction receipt with a 120-second timeout.
+/// Wait for a component creation receipt (120s before polling on-chain).
 ///
-/// Checks the receipt status and returns an error if the transaction reverted.
-async fn wait_for_receipt(
+/// Records the receipt as a beacon creation (gas metrics and history), then checks the
+/// receipt status and returns an error if the transaction reverted.
+async fn confirm_creation(
+    state: &AppState,
     description: &str,
-    tx_hash: alloy::primitives::TxHash,
     pending_tx: alloy::providers::PendingTransactionBuilder<alloy::network::Ethereum>,
 ) -> Result<(), String> {
-    let receipt = match timeout(Duration::from_secs(120), pending_tx.get_receipt()).await {
-        Ok(Ok(receipt)) => receipt,
-        Ok(Err(e)) => {
-            return Err(format!("Failed to get {description} receipt: {e}"));
-        }
-        Err(_) => {
-            return Err(format!(
-                "Timeout waiting for {description} receipt (tx: {tx_hash})",
-            ));
-        }
-    };
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        description,
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(120)),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    state
+        .gas_metrics
+        .record(GasOperation::BeaconCreate, receipt.gas_used);
+    state
+        .history
+        .record_receipt(
+            GasOperation::BeaconCreate,
+            &[("step", description.to_string())],
+            &receipt,
+        )
+        .await;
 
     if !receipt.status() {
-        return Err(format!("{description} transaction {tx_hash} reverted"));
+        return Err(format!(
+            "{description} transaction {} reverted",
+            receipt.transaction_hash
+        ));
     }
 
     Ok(())
 }
 
+/// Check that code exists at a simulated creation address, read through the
+/// read-only provider rather than the wallet that sent the transaction.
+async fn verify_created(state: &AppState, addr: Address, label: &str) -> Result<(), String> {
+    super::verify_deployed(&*state.provider.read_provider, addr, label).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,191 @@
+//! Batched beacon reads
+//!
+//! `POST /batch_beacon_data` reads the current `index()` of many beacons at once. With
+//! `MULTICALL3_ADDRESS` set the reads go out as one `tryAggregate(false, ...)` eth_call,
+//! alongside Multicall3's own `getCurrentBlockTimestamp()`, so every index comes from the
+//! same block and the response can say when that was. Without a Multicall3 address, or
+//! when the aggregated call fails as a whole (e.g. no contract at the address on a bare
+//! test chain), each beacon is read on its own and no timestamp is reported.
+
+use alloy::primitives::{Address, U256};
+use alloy::sol_types::{SolCall, SolValue};
+
+use super::super::rpc::retry_read;
+use crate::models::{AppState, BatchBeaconDataResponse, BeaconDataResult};
+use crate::routes::{IBeacon, IMulticall3};
+use crate::services::address::parse_address;
+
+/// Most beacons one `POST /batch_beacon_data` request may read.
+pub const MAX_BATCH_BEACON_READS: usize = 100;
+
+/// Decode one beacon's `index()` entry from a `tryAggregate` result.
+pub fn decode_index_result(result: &IMulticall3::Result) -> Result<U256, String> {
+    if !result.success {
+        return Err(if result.returnData.is_empty() {
+            "index() reverted".to_string()
+        } else {
+            format!("index() reverted (revert data: {})", result.returnData)
+        });
+    }
+    U256::abi_decode(&result.returnData).map_err(|_| {
+        format!(
+            "index() returned {} bytes, not a uint256 (is this a beacon?)",
+            result.returnData.len()
+        )
+    })
+}
+
+/// Read `index()` from every address in `beacon_addresses`, in request order. An
+/// unparseable address or a failed read marks that entry failed without affecting the rest.
+pub async fn read_beacon_data(
+    state: &AppState,
+    beacon_addresses: &[String],
+) -> BatchBeaconDataResponse {
+    let parsed: Vec<Result<Address, String>> = beacon_addresses
+        .iter()
+        .map(|raw| {
+            parse_address(raw, state.strict_address_checksum)
+                .map_err(|e| format!("Invalid beacon address '{raw}': {e}"))
+        })
+        .collect();
+    let beacons: Vec<Address> = parsed.iter().filter_map(|p| p.clone().ok()).collect();
+
+    let mut timestamp = None;
+    let mut via_multicall = false;
+    let mut reads = None;
+
+    if let Some(multicall3) = state.contracts.load().multicall3
+        && !beacons.is_empty()
+    {
+        match read_via_multicall(state, multicall3, &beacons).await {
+            Ok((block_timestamp, results)) => {
+                timestamp = Some(block_timestamp);
+                via_multicall = true;
+                reads = Some(results);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Beacon data multicall via {multicall3} failed ({e}); \
+                     falling back to per-beacon reads"
+                );
+            }
+        }
+    }
+
+    let reads = match reads {
+        Some(reads) => reads,
+        None => read_sequential(state, &beacons).await,
+    };
+
+    let mut reads = reads.into_iter();
+    let results: Vec<BeaconDataResult> = beacon_addresses
+        .iter()
+        .zip(parsed)
+        .map(|(raw, parsed)| {
+            let (beacon_address, read) = match parsed {
+                Ok(address) => (
+                    address.to_checksum(None),
+                    reads
+                        .next()
+                        .unwrap_or_else(|| Err("index() read missing".to_string())),
+                ),
+                Err(e) => (raw.clone(), Err(e)),
+            };
+            match read {
+                Ok(index) => BeaconDataResult {
+                    beacon_address,
+                    success: true,
+                    index: Some(index.to_string()),
+                    error: None,
+                },
+                Err(e) => BeaconDataResult {
+                    beacon_address,
+                    success: false,
+                    index: None,
+                    error: Some(e),
+                },
+            }
+        })
+        .collect();
+
+    let successful_reads = results.iter().filter(|r| r.success).count();
+    BatchBeaconDataResponse {
+        total_requested: results.len(),
+        successful_reads,
+        failed_reads: results.len() - successful_reads,
+        timestamp,
+        via_multicall,
+        results,
+    }
+}
+
+/// One `tryAggregate(false, ...)` eth_call: `getCurrentBlockTimestamp()` on Multicall3
+/// itself, then `index()` on each beacon.
+async fn read_via_multicall(
+    state: &AppState,
+    multicall3: Address,
+    beacons: &[Address],
+) -> Result<(u64, Vec<Result<U256, String>>), String> {
+    let mut calls = Vec::with_capacity(beacons.len() + 1);
+    calls.push(IMulticall3::Call {
+        target: multicall3,
+        callData: IMulticall3::getCurrentBlockTimestampCall {}
+            .abi_encode()
+            .into(),
+    });
+    for &beacon in beacons {
+        calls.push(IMulticall3::Call {
+            target: beacon,
+            callData: IBeacon::indexCall {}.abi_encode().into(),
+        });
+    }
+
+    let contract = IMulticall3::new(multicall3, &*state.provider.read_provider);
+    let results = contract
+        .tryAggregate(false, calls)
+        .call()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if results.len() != beacons.len() + 1 {
+        return Err(format!(
+            "expected {} multicall results, got {}",
+            beacons.len() + 1,
+            results.len()
+        ));
+    }
+
+    let timestamp = results[0]
+        .success
+        .then(|| U256::abi_decode(&results[0].returnData).ok())
+        .flatten()
+        .and_then(|ts| u64::try_from(ts).ok());
+    // A Multicall3 deployment answers getCurrentBlockTimestamp(); anything else at the
+    // address (an EOA returns empty data for every call) is not one.
+    let Some(timestamp) = timestamp else {
+        return Err(format!(
+            "{multicall3} did not answer getCurrentBlockTimestamp()"
+        ));
+    };
+
+    Ok((
+        timestamp,
+        results[1..].iter().map(decode_index_result).collect(),
+    ))
+}
+
+/// Per-beacon read path: one `index()` call per beacon.
+async fn read_sequential(state: &AppState, beacons: &[Address]) -> Vec<Result<U256, String>> {
+    let retry = state.provider.read_retry;
+    let mut reads = Vec::with_capacity(beacons.len());
+    for &address in beacons {
+        let beacon = &IBeacon::new(address, &*state.provider.read_provider);
+        let read = retry_read(&retry, "Beacon.index", move || async move {
+            beacon.index().call().await
+        })
+        .await
+        .map_err(|e| format!("Failed to read index() from beacon {address}: {e}"));
+        reads.push(read);
+    }
+    reads
+}
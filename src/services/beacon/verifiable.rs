@@ -9,14 +9,32 @@ use alloy::providers::Provider;
 use alloy::rpc::types::TransactionRequest;
 use alloy::sol_types::SolValue;
 use std::time::Duration;
-use tokio::time::timeout;
 
 use crate::models::AppState;
+use crate::services::beacon::core::IDENTITY_BEACON_UNAVAILABLE;
+use crate::services::metrics::GasOperation;
+use crate::services::transaction::execution::wait_for_receipt;
 use crate::services::wallet::WalletHandle;
 
+/// IdentityBeacon creation code: `bytecode` followed by the ABI-encoded constructor args
+/// `(address _verifier, uint256 _initialIndex)`. Fails if the bytecode was not loaded.
+pub fn identity_beacon_deploy_code(
+    bytecode: &Bytes,
+    verifier_address: Address,
+    initial_index: u128,
+) -> Result<Bytes, String> {
+    if bytecode.is_empty() {
+        return Err(IDENTITY_BEACON_UNAVAILABLE.to_string());
+    }
+    let constructor_args = (verifier_address, U256::from(initial_index)).abi_encode();
+    let mut deploy_data = bytecode.to_vec();
+    deploy_data.extend_from_slice(&constructor_args);
+    Ok(Bytes::from(deploy_data))
+}
+
 /// Deploys an IdentityBeacon contract with the given verifier and initial index.
 ///
-/// Uses bytecode from `state.contracts.identity_beacon_bytecode` with ABI-encoded constructor args.
+/// Uses bytecode from `state.contracts.load().identity_beacon_bytecode` with ABI-encoded constructor args.
 pub async fn deploy_identity_beacon(
     state: &AppState,
     wallet_handle: &WalletHandle,
@@ -31,24 +49,17 @@ pub async fn deploy_identity_beacon(
 
     // Build provider from wallet handle
     let provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| format!("Failed to build provider for beacon deployment: {e}"))?;
 
-    if state.contracts.identity_beacon_bytecode.is_empty() {
-        return Err(
-            "IdentityBeacon bytecode is empty - check abis/IdentityBeacon.bytecode".to_string(),
-        );
-    }
-
-    // ABI-encode constructor args: (address _verifier, uint256 _initialIndex)
-    let constructor_args = (verifier_address, U256::from(initial_index)).abi_encode();
-
-    // Concatenate bytecode + constructor args
-    let mut deploy_data = state.contracts.identity_beacon_bytecode.to_vec();
-    deploy_data.extend_from_slice(&constructor_args);
+    let deploy_code = identity_beacon_deploy_code(
+        &state.contracts.load().identity_beacon_bytecode,
+        verifier_address,
+        initial_index,
+    )?;
 
     // Build deployment transaction using with_deploy_code for proper contract creation
-    let tx = TransactionRequest::default().with_deploy_code(Bytes::from(deploy_data));
+    let tx = TransactionRequest::default().with_deploy_code(deploy_code);
 
     // Send deployment transaction
     wallet_handle.ensure_lock_held()?;
@@ -60,18 +71,29 @@ pub async fn deploy_identity_beacon(
     let tx_hash = *pending_tx.tx_hash();
     tracing::info!("Beacon deployment tx sent: {:?}", tx_hash);
 
-    // Wait for receipt
-    let receipt = match timeout(Duration::from_secs(120), pending_tx.get_receipt()).await {
-        Ok(Ok(receipt)) => receipt,
-        Ok(Err(e)) => {
-            return Err(format!("Failed to get beacon deployment receipt: {e}"));
-        }
-        Err(_) => {
-            return Err(format!(
-                "Timeout waiting for beacon deployment receipt (tx: {tx_hash})"
-            ));
-        }
-    };
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        "beacon deployment",
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(120)),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    state
+        .gas_metrics
+        .record(GasOperation::BeaconCreate, receipt.gas_used);
+    state
+        .history
+        .record_receipt(
+            GasOperation::BeaconCreate,
+            &[("step", "beacon deployment".to_string())],
+            &receipt,
+        )
+        .await;
 
     // Check transaction status
     if !receipt.status() {
@@ -0,0 +1,146 @@
+//! Typed errors for beacon and perp operations
+//!
+//! Service functions historically returned `Result<_, String>`, leaving routes to pick
+//! an HTTP status by substring-matching the message. [`BeaconError`] carries the kind of
+//! failure alongside the same message, so callers match on the variant instead. The
+//! `Display` output is the message the string error carried, so logs read as before.
+//!
+//! Failed contract calls are classified from the alloy error, not its text: see
+//! [`BeaconError::from_contract_error`] and [`BeaconError::from_send_error`].
+
+use alloy::contract::Error as ContractError;
+use alloy::transports::RpcError;
+
+use crate::services::beacon::core::is_proof_rejection;
+use crate::services::transaction::execution::{is_insufficient_funds_error, is_nonce_error};
+
+/// Why a beacon or perp operation failed. Each variant holds the human-readable message.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BeaconError {
+    /// A caller-supplied address did not parse.
+    #[error("{0}")]
+    InvalidAddress(String),
+    /// The address answers neither `verifier()` nor `index()`.
+    #[error("{0}")]
+    NotABeacon(String),
+    /// The address (or position id) is not registered with the registry, factory or Perp
+    /// that should know it.
+    #[error("{0}")]
+    NotRegistered(String),
+    /// The target exists, but its current state rules the operation out, e.g. closing a
+    /// taker position.
+    #[error("{0}")]
+    InvalidState(String),
+    /// The contract rejected the call. `decoded` is the revert reason when it could be
+    /// decoded, e.g. `ProofAlreadyUsed()`.
+    #[error("{message}")]
+    ContractReverted {
+        message: String,
+        decoded: Option<String>,
+    },
+    /// No receipt arrived in time; the transaction may still land.
+    #[error("{0}")]
+    Timeout(String),
+    /// The wallet's nonce was stale or already used.
+    #[error("{0}")]
+    NonceConflict(String),
+    /// The sending wallet cannot cover gas.
+    #[error("{0}")]
+    InsufficientFunds(String),
+    /// No pool wallet could be leased.
+    #[error("{0}")]
+    WalletUnavailable(String),
+    /// The RPC endpoint failed or was unreachable.
+    #[error("{0}")]
+    RpcUnavailable(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl BeaconError {
+    /// Stable machine-readable code, e.g. for structured logs.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidAddress(_) => "invalid_address",
+            Self::NotABeacon(_) => "not_a_beacon",
+            Self::NotRegistered(_) => "not_registered",
+            Self::InvalidState(_) => "invalid_state",
+            Self::ContractReverted { .. } if self.is_proof_rejection() => "proof_rejected",
+            Self::ContractReverted { .. } => "contract_reverted",
+            Self::Timeout(_) => "timeout",
+            Self::NonceConflict(_) => "nonce_conflict",
+            Self::InsufficientFunds(_) => "insufficient_funds",
+            Self::WalletUnavailable(_) => "wallet_unavailable",
+            Self::RpcUnavailable(_) => "rpc_unavailable",
+            Self::Other(_) => "internal_error",
+        }
+    }
+
+    /// Whether the verifier rejected the submitted proof (see [`is_proof_rejection`]).
+    pub fn is_proof_rejection(&self) -> bool {
+        matches!(
+            self,
+            Self::ContractReverted { decoded: Some(reason), .. } if is_proof_rejection(reason)
+        )
+    }
+
+    /// Whether the failure is the caller's to fix rather than an infrastructure fault.
+    pub fn is_client_error(&self) -> bool {
+        matches!(
+            self,
+            Self::InvalidAddress(_)
+                | Self::NotABeacon(_)
+                | Self::NotRegistered(_)
+                | Self::InvalidState(_)
+        ) || self.is_proof_rejection()
+    }
+
+    /// Classifies a failed contract call. A `decoded` revert reason, or revert data in the
+    /// node's error response, makes it [`BeaconError::ContractReverted`]; so does an
+    /// EIP-1474 execution error (code 3) without data. A request that got no usable answer
+    /// (transport failure, rate limiting, malformed response) is
+    /// [`BeaconError::RpcUnavailable`]; anything else is [`BeaconError::Other`].
+    pub fn from_contract_error(
+        message: String,
+        error: &ContractError,
+        decoded: Option<String>,
+    ) -> Self {
+        if decoded.is_some() || error.as_revert_data().is_some() {
+            return Self::ContractReverted { message, decoded };
+        }
+        match error {
+            ContractError::TransportError(RpcError::ErrorResp(payload)) if payload.code == 3 => {
+                Self::ContractReverted {
+                    message,
+                    decoded: None,
+                }
+            }
+            ContractError::TransportError(RpcError::ErrorResp(payload))
+                if payload.is_retry_err() =>
+            {
+                Self::RpcUnavailable(message)
+            }
+            ContractError::TransportError(
+                RpcError::Transport(_) | RpcError::NullResp | RpcError::DeserError { .. },
+            ) => Self::RpcUnavailable(message),
+            _ => Self::Other(message),
+        }
+    }
+
+    /// [`BeaconError::from_contract_error`] for a failed `send()`, which the node may also
+    /// refuse for the sending wallet's nonce or balance.
+    pub fn from_send_error(
+        message: String,
+        error: &ContractError,
+        decoded: Option<String>,
+    ) -> Self {
+        let error_text = error.to_string();
+        if decoded.is_none() && is_nonce_error(&error_text) {
+            Self::NonceConflict(message)
+        } else if decoded.is_none() && is_insufficient_funds_error(&error_text) {
+            Self::InsufficientFunds(message)
+        } else {
+            Self::from_contract_error(message, error, decoded)
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Gas estimates for write operations, without sending anything.
+//!
+//! Each estimate builds the same transactions the real endpoint sends (via the shared
+//! builders in beacon and perp core) and runs `eth_estimateGas` on them from a pool
+//! wallet. No wallet lock is taken: estimating is read-only.
+
+use alloy::network::TransactionBuilder;
+use alloy::primitives::{Address, FixedBytes, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol_types::SolCall;
+
+use crate::models::{AppState, EstimateGasResponse, TransactionGasEstimate};
+use crate::routes::{IERC20, IEcdsaVerifierFactory, IPerp};
+use crate::services::beacon::identity_beacon_deploy_code;
+use crate::services::perp::{build_create_perp_call, build_open_maker_params};
+use crate::services::rpc::retry_read;
+
+/// Address estimates are run from: the lowest pool wallet, or the primary signer when
+/// the pool has no signers.
+pub fn estimate_sender(state: &AppState) -> Address {
+    state
+        .wallets
+        .manager
+        .signer_addresses()
+        .into_iter()
+        .min()
+        .unwrap_or_else(|| state.wallets.signer.address())
+}
+
+/// Combine per-transaction estimates with the current fees into a response.
+pub fn summarize_estimate(
+    operation: &str,
+    from: Address,
+    transactions: Vec<TransactionGasEstimate>,
+    gas_price: u128,
+    max_fee_per_gas: u128,
+) -> EstimateGasResponse {
+    let total_gas = transactions
+        .iter()
+        .fold(0u64, |total, tx| total.saturating_add(tx.gas));
+    EstimateGasResponse {
+        operation: operation.to_string(),
+        from: from.to_checksum(None),
+        transactions,
+        total_gas,
+        gas_price_wei: gas_price.to_string(),
+        max_fee_per_gas_wei: max_fee_per_gas.to_string(),
+        estimated_fee_wei: (U256::from(total_gas) * U256::from(gas_price)).to_string(),
+        max_fee_wei: (U256::from(total_gas) * U256::from(max_fee_per_gas)).to_string(),
+    }
+}
+
+/// Current `(gas_price, max_fee_per_gas)` in wei.
+pub async fn current_fees(state: &AppState) -> Result<(u128, u128), String> {
+    let provider = &state.provider.read_provider;
+    let retry = state.provider.read_retry;
+    let gas_price = retry_read(&retry, "get_gas_price", || provider.get_gas_price())
+        .await
+        .map_err(|e| format!("Failed to get gas price: {e}"))?;
+    let fees = retry_read(&retry, "estimate_eip1559_fees", || {
+        provider.estimate_eip1559_fees()
+    })
+    .await
+    .map_err(|e| format!("Failed to estimate EIP-1559 fees: {e}"))?;
+    Ok((gas_price, fees.max_fee_per_gas))
+}
+
+async fn estimate_tx(
+    state: &AppState,
+    description: &str,
+    tx: TransactionRequest,
+) -> Result<TransactionGasEstimate, String> {
+    let gas = state
+        .provider
+        .read_provider
+        .estimate_gas(tx)
+        .await
+        .map_err(|e| format!("{description} would fail: {e}"))?;
+    Ok(TransactionGasEstimate {
+        description: description.to_string(),
+        gas,
+    })
+}
+
+fn call_tx(from: Address, to: Address, call: &impl SolCall) -> TransactionRequest {
+    TransactionRequest::default()
+        .from(from)
+        .to(to)
+        .input(call.abi_encode().into())
+}
+
+/// Estimate `/create_beacon_with_ecdsa`: `createVerifier` (unless `existing_verifier` is
+/// reused) followed by the IdentityBeacon deployment.
+pub async fn estimate_create_beacon(
+    state: &AppState,
+    from: Address,
+    initial_index: u128,
+    existing_verifier: Option<Address>,
+) -> Result<Vec<TransactionGasEstimate>, String> {
+    let mut estimates = Vec::new();
+    let verifier_address = match existing_verifier {
+        Some(addr) => addr,
+        None => {
+            let create_verifier = IEcdsaVerifierFactory::createVerifierCall {
+                signer: state.wallets.signer.address(),
+            };
+            let factory = IEcdsaVerifierFactory::new(
+                state.contracts.load().ecdsa_verifier_factory,
+                &state.provider.read_provider,
+            );
+            let predicted = factory
+                .call_builder(&create_verifier)
+                .from(from)
+                .call()
+                .await
+                .map_err(|e| format!("Failed to simulate createVerifier: {e}"))?;
+            estimates.push(
+                estimate_tx(
+                    state,
+                    "ECDSAVerifierFactory.createVerifier",
+                    call_tx(
+                        from,
+                        state.contracts.load().ecdsa_verifier_factory,
+                        &create_verifier,
+                    ),
+                )
+                .await?,
+            );
+            Address::from(predicted.0)
+        }
+    };
+
+    let deploy_code = identity_beacon_deploy_code(
+        &state.contracts.load().identity_beacon_bytecode,
+        verifier_address,
+        initial_index,
+    )?;
+    let deploy_tx = TransactionRequest::default()
+        .from(from)
+        .with_deploy_code(deploy_code);
+    estimates.push(estimate_tx(state, "IdentityBeacon deployment", deploy_tx).await?);
+    Ok(estimates)
+}
+
+/// Estimate `/deploy_perp_for_beacon`: `PerpFactory.createPerp`.
+#[allow(clippy::too_many_arguments)]
+pub async fn estimate_deploy_perp(
+    state: &AppState,
+    from: Address,
+    beacon_address: Address,
+    owner: Address,
+    name: String,
+    symbol: String,
+    token_uri: String,
+    ema_window: u32,
+    salt: FixedBytes<32>,
+) -> Result<Vec<TransactionGasEstimate>, String> {
+    let contracts = state.contracts.load();
+    let create_perp = build_create_perp_call(
+        &contracts,
+        beacon_address,
+        owner,
+        name,
+        symbol,
+        token_uri,
+        ema_window,
+        salt,
+    )?;
+    let estimate = estimate_tx(
+        state,
+        "PerpFactory.createPerp",
+        call_tx(from, contracts.perp_factory, &create_perp),
+    )
+    .await?;
+    Ok(vec![estimate])
+}
+
+/// Estimate `/deposit_liquidity_for_perp`: the USDC approval when `from`'s allowance does
+/// not cover the margin, then `Perp.openMaker`.
+///
+/// openMaker can only be simulated once the allowance exists, so with an approval still
+/// outstanding its estimate usually fails and the error says so.
+#[allow(clippy::too_many_arguments)]
+pub async fn estimate_deposit_liquidity(
+    state: &AppState,
+    from: Address,
+    perp_address: Address,
+    margin_amount_usdc: u128,
+    tick_spacing: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+    max_amt0_in: Option<U256>,
+    max_amt1_in: Option<U256>,
+) -> Result<Vec<TransactionGasEstimate>, String> {
+    let params = build_open_maker_params(
+        state,
+        from,
+        perp_address,
+        margin_amount_usdc,
+        tick_spacing,
+        tick_lower,
+        tick_upper,
+        max_amt0_in,
+        max_amt1_in,
+    )
+    .await?;
+
+    let margin = U256::from(margin_amount_usdc);
+    let read_usdc = &IERC20::new(state.contracts.load().usdc, &state.provider.read_provider);
+    let retry = state.provider.read_retry;
+    let allowance = retry_read(&retry, "USDC.allowance", move || async move {
+        read_usdc.allowance(from, perp_address).call().await
+    })
+    .await
+    .map_err(|e| format!("Failed to read USDC allowance for {perp_address}: {e}"))?;
+
+    let mut estimates = Vec::new();
+    let approval = state.perp.usdc_approval(allowance, margin);
+    let approval_needed = approval.is_some();
+    if let Some(amount) = approval {
+        let approve = IERC20::approveCall {
+            spender: perp_address,
+            amount,
+        };
+        estimates.push(
+            estimate_tx(
+                state,
+                "USDC.approve",
+                call_tx(from, state.contracts.load().usdc, &approve),
+            )
+            .await?,
+        );
+    }
+
+    let open_maker = IPerp::openMakerCall { params };
+    match estimate_tx(
+        state,
+        "Perp.openMaker",
+        call_tx(from, perp_address, &open_maker),
+    )
+    .await
+    {
+        Ok(estimate) => estimates.push(estimate),
+        Err(e) if approval_needed => {
+            return Err(format!(
+                "{e} (wallet {from} has no USDC allowance for {perp_address} yet; openMaker can \
+                 only be estimated once the approval is mined)"
+            ));
+        }
+        Err(e) => return Err(e),
+    }
+    Ok(estimates)
+}
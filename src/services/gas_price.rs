@@ -0,0 +1,113 @@
+//! Current network fees for `GET /gas_price`
+//!
+//! Front-ends poll this to decide when to submit and to show a "network busy" hint, so
+//! the fees are cached for a few seconds: every poll inside that window is served from
+//! memory, and concurrent polls after it expires share one refresh.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use alloy::eips::BlockNumberOrTag;
+use alloy::providers::Provider;
+use tokio::sync::Mutex;
+
+use crate::ReadOnlyProvider;
+use crate::services::rpc::{ReadRetryPolicy, retry_read};
+
+/// How long fetched fees are served from the cache.
+pub const DEFAULT_GAS_PRICE_CACHE_TTL_SECS: u64 = 5;
+
+/// Share of the block gas limit above which EIP-1559 raises the next base fee.
+pub const BASE_FEE_TARGET_RATIO: f64 = 0.5;
+
+/// Fees and utilization of the latest block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetworkFees {
+    pub block_number: u64,
+    /// `None` on chains without EIP-1559.
+    pub base_fee_per_gas: Option<u64>,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    /// `gas_used / gas_limit` of the latest block.
+    pub gas_used_ratio: f64,
+}
+
+impl NetworkFees {
+    /// Whether the latest block was fuller than the EIP-1559 target, so the base fee is rising.
+    pub fn is_congested(&self) -> bool {
+        self.gas_used_ratio > BASE_FEE_TARGET_RATIO
+    }
+}
+
+/// Fetch the latest block's base fee and utilization plus the provider's EIP-1559 fee
+/// estimate, each read retried per `retry`.
+pub async fn fetch_network_fees(
+    provider: &ReadOnlyProvider,
+    retry: &ReadRetryPolicy,
+) -> Result<NetworkFees, String> {
+    let block = retry_read(retry, "get_block_by_number(latest)", || async move {
+        provider.get_block_by_number(BlockNumberOrTag::Latest).await
+    })
+    .await
+    .map_err(|e| format!("Failed to get latest block: {e}"))?
+    .ok_or_else(|| "Latest block not found".to_string())?;
+
+    let fees = retry_read(retry, "estimate_eip1559_fees", || {
+        provider.estimate_eip1559_fees()
+    })
+    .await
+    .map_err(|e| format!("Failed to estimate EIP-1559 fees: {e}"))?;
+
+    let header = &block.header;
+    Ok(NetworkFees {
+        block_number: header.number,
+        base_fee_per_gas: header.base_fee_per_gas,
+        max_fee_per_gas: fees.max_fee_per_gas,
+        max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+        gas_used_ratio: if header.gas_limit == 0 {
+            0.0
+        } else {
+            header.gas_used as f64 / header.gas_limit as f64
+        },
+    })
+}
+
+/// Single-value TTL cache for [`NetworkFees`]. Failed fetches are not cached.
+pub struct GasPriceCache<T = NetworkFees> {
+    ttl: Duration,
+    /// Held across a refresh, so concurrent callers wait for it instead of fetching too.
+    entry: Mutex<Option<(T, Instant)>>,
+}
+
+impl<T: Clone> GasPriceCache<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: Mutex::new(None),
+        }
+    }
+
+    /// The cached value if it is younger than the TTL, otherwise the result of `fetch`
+    /// (stored on success).
+    pub async fn get_or_fetch<E, F, Fut>(&self, fetch: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut entry = self.entry.lock().await;
+        if let Some((value, fetched_at)) = entry.as_ref()
+            && fetched_at.elapsed() < self.ttl
+        {
+            return Ok(value.clone());
+        }
+        let value = fetch().await?;
+        *entry = Some((value.clone(), Instant::now()));
+        Ok(value)
+    }
+}
+
+impl<T: Clone> Default for GasPriceCache<T> {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_GAS_PRICE_CACHE_TTL_SECS))
+    }
+}
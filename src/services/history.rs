@@ -0,0 +1,223 @@
+//! Recent write operations, served by the admin `GET /transactions` endpoint.
+//!
+//! Successful operations return transaction hashes to the caller, but nothing kept them,
+//! so tracing an operator question ("did that update land?") meant searching logs. With
+//! `TRANSACTION_HISTORY_ENABLED` on, beacon and perp services record each operation's
+//! kind, identifying inputs, resulting hash and outcome here. Off (the default), every
+//! call is a no-op.
+//!
+//! Records go to a capped Redis list (`<prefix>tx_history`) so every instance shares one
+//! history. Without Redis — test stubs, local runs — or when a Redis call fails, they go
+//! to a per-instance in-memory ring of the same size.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use alloy::rpc::types::TransactionReceipt;
+use redis::aio::ConnectionManager;
+use tokio::sync::Mutex;
+
+use crate::models::{TransactionRecord, TransactionStatus};
+use crate::services::metrics::GasOperation;
+
+/// Records kept when `TRANSACTION_HISTORY_MAX_ENTRIES` is unset.
+pub const DEFAULT_TRANSACTION_HISTORY_MAX_ENTRIES: usize = 1000;
+
+/// Capped, most-recent-first log of write operations.
+pub struct TransactionHistory {
+    enabled: bool,
+    max_entries: usize,
+    redis: Option<(ConnectionManager, String)>,
+    memory: Mutex<VecDeque<TransactionRecord>>,
+}
+
+impl TransactionHistory {
+    /// In-memory history holding at most `max_entries` (minimum 1) records.
+    pub fn new(enabled: bool, max_entries: usize) -> Self {
+        Self {
+            enabled,
+            max_entries: max_entries.max(1),
+            redis: None,
+            memory: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// A history that records nothing.
+    pub fn disabled() -> Self {
+        Self::new(false, 1)
+    }
+
+    /// Build from `TRANSACTION_HISTORY_ENABLED` (default off) and
+    /// `TRANSACTION_HISTORY_MAX_ENTRIES`, falling back to the default cap when unset or
+    /// unparseable.
+    pub fn from_env() -> Self {
+        let enabled = std::env::var("TRANSACTION_HISTORY_ENABLED").is_ok_and(|v| {
+            matches!(
+                v.trim().to_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        });
+        let max_entries = std::env::var("TRANSACTION_HISTORY_MAX_ENTRIES")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_TRANSACTION_HISTORY_MAX_ENTRIES);
+        Self::new(enabled, max_entries)
+    }
+
+    /// Share the history across instances through Redis, under `<prefix>tx_history`.
+    pub fn with_redis(mut self, conn: ConnectionManager, prefix: &str) -> Self {
+        self.redis = Some((conn, prefix.to_string()));
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Record the outcome of one `kind` operation: `Ok` carries the transaction hash
+    /// (`None` when nothing was sent, e.g. already registered), `Err` the error message.
+    pub async fn record_transaction(
+        &self,
+        kind: GasOperation,
+        inputs: &[(&str, String)],
+        result: Result<Option<String>, String>,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        let (status, transaction_hash, error) = match result {
+            Ok(hash) => (TransactionStatus::Success, hash, None),
+            Err(e) => (TransactionStatus::Failed, None, Some(e)),
+        };
+        self.insert(TransactionRecord {
+            kind: kind.as_str().to_string(),
+            inputs: inputs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect::<BTreeMap<_, _>>(),
+            transaction_hash,
+            status,
+            error,
+            recorded_at: now_secs(),
+        })
+        .await;
+    }
+
+    /// [`record_transaction`](Self::record_transaction) for a mined transaction: a success
+    /// with its hash, or a failure naming the hash if it reverted.
+    pub async fn record_receipt(
+        &self,
+        kind: GasOperation,
+        inputs: &[(&str, String)],
+        receipt: &TransactionReceipt,
+    ) {
+        let tx_hash = format!("{:?}", receipt.transaction_hash);
+        let result = if receipt.status() {
+            Ok(Some(tx_hash))
+        } else {
+            Err(format!("Transaction {tx_hash} reverted"))
+        };
+        self.record_transaction(kind, inputs, result).await;
+    }
+
+    /// Store `record` as the most recent entry, dropping the oldest past the cap.
+    pub async fn insert(&self, record: TransactionRecord) {
+        if !self.enabled {
+            return;
+        }
+        if let Some((conn, prefix)) = &self.redis {
+            match self.redis_insert(conn.clone(), prefix, &record).await {
+                Ok(()) => return,
+                Err(e) => {
+                    tracing::warn!("Transaction history: Redis insert failed, using in-memory: {e}")
+                }
+            }
+        }
+        let mut memory = self.memory.lock().await;
+        memory.push_front(record);
+        memory.truncate(self.max_entries);
+    }
+
+    /// Up to `limit` most recent records, newest first, optionally only those of `kind`.
+    pub async fn recent(&self, limit: usize, kind: Option<GasOperation>) -> Vec<TransactionRecord> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        let matches = |record: &TransactionRecord| kind.is_none_or(|k| record.kind == k.as_str());
+        if let Some((conn, prefix)) = &self.redis {
+            match self.redis_records(conn.clone(), prefix).await {
+                Ok(records) => {
+                    return records
+                        .into_iter()
+                        .filter(|r| matches(r))
+                        .take(limit)
+                        .collect();
+                }
+                Err(e) => {
+                    tracing::warn!("Transaction history: Redis read failed, using in-memory: {e}")
+                }
+            }
+        }
+        self.memory
+            .lock()
+            .await
+            .iter()
+            .filter(|r| matches(r))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    fn redis_key(prefix: &str) -> String {
+        format!("{prefix}tx_history")
+    }
+
+    async fn redis_insert(
+        &self,
+        mut conn: ConnectionManager,
+        prefix: &str,
+        record: &TransactionRecord,
+    ) -> Result<(), String> {
+        let key = Self::redis_key(prefix);
+        let json = serde_json::to_string(record).map_err(|e| e.to_string())?;
+        let _: () = redis::pipe()
+            .atomic()
+            .cmd("LPUSH")
+            .arg(&key)
+            .arg(json)
+            .ignore()
+            .cmd("LTRIM")
+            .arg(&key)
+            .arg(0)
+            .arg(self.max_entries as i64 - 1)
+            .ignore()
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to record transaction: {e}"))?;
+        Ok(())
+    }
+
+    async fn redis_records(
+        &self,
+        mut conn: ConnectionManager,
+        prefix: &str,
+    ) -> Result<Vec<TransactionRecord>, String> {
+        let raw: Vec<String> = redis::cmd("LRANGE")
+            .arg(Self::redis_key(prefix))
+            .arg(0)
+            .arg(self.max_entries as i64 - 1)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to read transaction history: {e}"))?;
+        Ok(raw
+            .iter()
+            .filter_map(|json| serde_json::from_str(json).ok())
+            .collect())
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,312 @@
+//! Short-lived cache of responses keyed by client-supplied idempotency keys.
+//!
+//! Non-idempotent endpoints (batch beacon creation, guest wallet funding) send
+//! transactions on every call, so a client retrying after a network blip would
+//! create duplicates or double-fund. When the client sends an idempotency key
+//! (the `Idempotency-Key` header, or `request_id` in a funding request), the
+//! first successful response is cached here and replayed for retries with the
+//! same key.
+//!
+//! A key is claimed with [`IdempotencyStore::reserve`] before any transaction is
+//! sent, so a retry that arrives while the first request is still running sees it
+//! in flight instead of sending a second set. The holder then stores the result
+//! ([`IdempotencyStore::insert`]) or, if nothing was sent, gives the key back
+//! ([`IdempotencyStore::release`]).
+//!
+//! Keys are scoped per access token (see [`IdempotencyStore::scoped_key`]) so
+//! two clients can never collide on — or read — each other's results. With
+//! Redis configured ([`IdempotencyStore::with_redis`]) entries are shared by
+//! every instance and expire through Redis TTLs, and a Redis failure is an error:
+//! falling back to this instance's memory would let another instance run the same
+//! key. Without Redis the store is in-memory and per instance: entries expire
+//! after a TTL and the oldest entries are evicted once the capacity is reached.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use alloy::primitives::keccak256;
+use redis::aio::ConnectionManager;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+
+/// How long a cached response is replayed when `IDEMPOTENCY_TTL_SECS` is unset.
+pub const DEFAULT_IDEMPOTENCY_TTL_SECS: u64 = 600;
+/// Maximum cached responses when `IDEMPOTENCY_MAX_ENTRIES` is unset.
+pub const DEFAULT_IDEMPOTENCY_MAX_ENTRIES: usize = 1000;
+
+/// Redis value held by a reserved key until its result is stored. Not valid JSON, so
+/// it can never be mistaken for a stored result.
+const PENDING_MARKER: &str = "pending";
+
+/// Outcome of [`IdempotencyStore::reserve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reservation<T> {
+    /// The caller holds the key and must [`insert`](IdempotencyStore::insert) or
+    /// [`release`](IdempotencyStore::release) it.
+    Reserved,
+    /// A previous request with this key finished; replay its result.
+    Completed(T),
+    /// A request with this key is still running.
+    InFlight,
+}
+
+enum Slot<T> {
+    Pending,
+    Done(T),
+}
+
+struct CacheEntry<T> {
+    slot: Slot<T>,
+    stored_at: Instant,
+}
+
+/// TTL + capacity bounded response cache.
+pub struct IdempotencyStore<T> {
+    ttl: Duration,
+    max_entries: usize,
+    /// Connection and full key prefix (`<prefix>idempotency:<namespace>:`).
+    redis: Option<(ConnectionManager, String)>,
+    entries: Mutex<HashMap<String, CacheEntry<T>>>,
+}
+
+impl<T: Clone + Serialize + DeserializeOwned> IdempotencyStore<T> {
+    /// Create a store that keeps entries for `ttl`, holding at most `max_entries` (minimum 1).
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries: max_entries.max(1),
+            redis: None,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Share entries across instances through Redis, under
+    /// `<prefix>idempotency:<namespace>:<scoped key>`. `namespace` keeps stores for
+    /// different endpoints apart when a client reuses the same key on both.
+    pub fn with_redis(mut self, conn: ConnectionManager, prefix: &str, namespace: &str) -> Self {
+        self.redis = Some((conn, format!("{prefix}idempotency:{namespace}:")));
+        self
+    }
+
+    /// Build from `IDEMPOTENCY_TTL_SECS` / `IDEMPOTENCY_MAX_ENTRIES`, falling back to the
+    /// defaults when unset or unparseable.
+    pub fn from_env() -> Self {
+        let ttl = std::env::var("IDEMPOTENCY_TTL_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_IDEMPOTENCY_TTL_SECS);
+        let max_entries = std::env::var("IDEMPOTENCY_MAX_ENTRIES")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_IDEMPOTENCY_MAX_ENTRIES);
+        Self::new(Duration::from_secs(ttl), max_entries)
+    }
+
+    /// Derive the cache key for `key` sent with access token `token`, or by a signed
+    /// request when `token` is `None`.
+    ///
+    /// The token is hashed rather than embedded so the store never holds a raw credential.
+    /// Signed requests share one namespace, which no token hash can collide with.
+    pub fn scoped_key(token: Option<&str>, key: &str) -> String {
+        match token {
+            Some(token) => format!("{:x}:{key}", keccak256(token.as_bytes())),
+            None => format!("signed:{key}"),
+        }
+    }
+
+    /// Return the cached value for `scoped_key` if it has not expired.
+    pub async fn get(&self, scoped_key: &str) -> Option<T> {
+        if let Some((conn, prefix)) = &self.redis {
+            return match Self::redis_get(conn.clone(), &format!("{prefix}{scoped_key}")).await {
+                Ok(Some(Slot::Done(value))) => Some(value),
+                Ok(_) => None,
+                Err(e) => {
+                    tracing::error!("Idempotency: Redis read failed: {e}");
+                    None
+                }
+            };
+        }
+        let mut entries = self.entries.lock().await;
+        match entries.get(scoped_key) {
+            Some(entry) if entry.stored_at.elapsed() < self.ttl => match &entry.slot {
+                Slot::Done(value) => Some(value.clone()),
+                Slot::Pending => None,
+            },
+            Some(_) => {
+                entries.remove(scoped_key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Claim `scoped_key` for a new request, unless a request with it has finished or
+    /// is still running. Atomic across instances when Redis is configured (`SET NX`);
+    /// a Redis failure is returned rather than claiming the key locally.
+    ///
+    /// An unreleased claim (the holder crashed) expires after the store's TTL.
+    pub async fn reserve(&self, scoped_key: &str) -> Result<Reservation<T>, String> {
+        if let Some((conn, prefix)) = &self.redis {
+            return self
+                .redis_reserve(conn.clone(), &format!("{prefix}{scoped_key}"))
+                .await;
+        }
+        let mut entries = self.entries.lock().await;
+        let ttl = self.ttl;
+        entries.retain(|_, entry| entry.stored_at.elapsed() < ttl);
+        match entries.get(scoped_key).map(|entry| &entry.slot) {
+            Some(Slot::Done(value)) => return Ok(Reservation::Completed(value.clone())),
+            Some(Slot::Pending) => return Ok(Reservation::InFlight),
+            None => {}
+        }
+        Self::make_room(&mut entries, self.max_entries);
+        entries.insert(
+            scoped_key.to_string(),
+            CacheEntry {
+                slot: Slot::Pending,
+                stored_at: Instant::now(),
+            },
+        );
+        Ok(Reservation::Reserved)
+    }
+
+    /// Give back a key claimed with [`reserve`](Self::reserve) whose request sent
+    /// nothing, so a retry runs it again. Failures are logged: the claim then expires
+    /// after the TTL.
+    pub async fn release(&self, scoped_key: &str) {
+        if let Some((conn, prefix)) = &self.redis {
+            let mut conn = conn.clone();
+            // Only the holder releases, and only before storing a result, so the key
+            // still holds the pending marker.
+            let result: redis::RedisResult<()> = redis::cmd("DEL")
+                .arg(format!("{prefix}{scoped_key}"))
+                .query_async(&mut conn)
+                .await;
+            if let Err(e) = result {
+                tracing::error!("Idempotency: failed to release key in Redis: {e}");
+            }
+            return;
+        }
+        let mut entries = self.entries.lock().await;
+        if matches!(
+            entries.get(scoped_key).map(|entry| &entry.slot),
+            Some(Slot::Pending)
+        ) {
+            entries.remove(scoped_key);
+        }
+    }
+
+    /// Cache `value` under `scoped_key`, dropping expired entries and, if still full,
+    /// the oldest entry.
+    pub async fn insert(&self, scoped_key: String, value: T) {
+        if let Some((conn, prefix)) = &self.redis {
+            if let Err(e) = self
+                .redis_set(conn.clone(), &format!("{prefix}{scoped_key}"), &value)
+                .await
+            {
+                tracing::error!("Idempotency: Redis write failed: {e}");
+            }
+            return;
+        }
+        let mut entries = self.entries.lock().await;
+        let ttl = self.ttl;
+        entries.retain(|_, entry| entry.stored_at.elapsed() < ttl);
+        if !entries.contains_key(&scoped_key) {
+            Self::make_room(&mut entries, self.max_entries);
+        }
+        entries.insert(
+            scoped_key,
+            CacheEntry {
+                slot: Slot::Done(value),
+                stored_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Evict the oldest entry if `entries` is at capacity.
+    fn make_room(entries: &mut HashMap<String, CacheEntry<T>>, max_entries: usize) {
+        if entries.len() < max_entries {
+            return;
+        }
+        let oldest = entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.stored_at)
+            .map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            entries.remove(&oldest);
+        }
+    }
+
+    async fn redis_reserve(
+        &self,
+        mut conn: ConnectionManager,
+        key: &str,
+    ) -> Result<Reservation<T>, String> {
+        let claimed: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(PENDING_MARKER)
+            .arg("NX")
+            .arg("EX")
+            .arg(self.ttl.as_secs().max(1))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to reserve idempotency key: {e}"))?;
+        if claimed.is_some() {
+            return Ok(Reservation::Reserved);
+        }
+        match Self::redis_get(conn, key).await? {
+            Some(Slot::Done(value)) => Ok(Reservation::Completed(value)),
+            // Still pending, or released/expired since the SET: either way another
+            // request held it a moment ago; let the client retry.
+            Some(Slot::Pending) | None => Ok(Reservation::InFlight),
+        }
+    }
+
+    async fn redis_get(mut conn: ConnectionManager, key: &str) -> Result<Option<Slot<T>>, String> {
+        let raw: Option<String> = redis::cmd("GET")
+            .arg(key)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to read idempotency entry: {e}"))?;
+        match raw {
+            Some(marker) if marker == PENDING_MARKER => Ok(Some(Slot::Pending)),
+            Some(json) => serde_json::from_str(&json)
+                .map(|value| Some(Slot::Done(value)))
+                .map_err(|e| format!("Corrupt idempotency entry: {e}")),
+            None => Ok(None),
+        }
+    }
+
+    async fn redis_set(
+        &self,
+        mut conn: ConnectionManager,
+        key: &str,
+        value: &T,
+    ) -> Result<(), String> {
+        let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+        let _: () = redis::cmd("SET")
+            .arg(key)
+            .arg(json)
+            .arg("EX")
+            .arg(self.ttl.as_secs().max(1))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to store idempotency entry: {e}"))?;
+        Ok(())
+    }
+
+    /// Number of live (unexpired) in-memory entries, reserved keys included.
+    pub async fn len(&self) -> usize {
+        let entries = self.entries.lock().await;
+        entries
+            .values()
+            .filter(|entry| entry.stored_at.elapsed() < self.ttl)
+            .count()
+    }
+
+    /// Whether the store holds no live in-memory entries.
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+}
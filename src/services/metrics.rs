@@ -0,0 +1,204 @@
+//! Per-operation gas usage histograms.
+//!
+//! Every confirmed write records its receipt's `gas_used` under an operation
+//! label (beacon creation, perp deployment, ...). The histograms are in-memory
+//! and per instance, served by `GET /metrics/gas`; each sample is also logged as
+//! a structured `metric = "GasUsed"` event so CloudWatch log metric filters can
+//! aggregate across instances, like the touch worker's metrics.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::models::{GasHistogramBucket, GasMetricsResponse, GasOperationHistogram};
+
+/// Default upper bucket bounds (gas units) when `GAS_METRICS_BUCKETS` is unset.
+pub const DEFAULT_GAS_BUCKETS: &[u64] = &[
+    25_000, 50_000, 100_000, 250_000, 500_000, 1_000_000, 2_500_000, 5_000_000,
+];
+
+/// Write operations whose gas is tracked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GasOperation {
+    /// Beacon and verifier deployments (identity, factory, modular).
+    BeaconCreate,
+    /// Registry register / unregister calls.
+    BeaconRegister,
+    /// Beacon `update()` calls, single or batched.
+    BeaconUpdate,
+    /// `PerpFactory.createPerp`.
+    PerpDeploy,
+    /// USDC approval ahead of a liquidity deposit.
+    DepositApproval,
+    /// `Perp.openMaker` liquidity deposit.
+    Deposit,
+    /// `Perp.adjustMaker` removing all of a maker position's liquidity.
+    MakerClose,
+    /// Guest / bonus wallet funding transfers (ETH and USDC legs).
+    Funding,
+    /// Admin sweeps of a pool wallet's USDC and ETH.
+    Sweep,
+}
+
+impl GasOperation {
+    /// Every operation.
+    pub const ALL: [GasOperation; 9] = [
+        GasOperation::BeaconCreate,
+        GasOperation::BeaconRegister,
+        GasOperation::BeaconUpdate,
+        GasOperation::PerpDeploy,
+        GasOperation::DepositApproval,
+        GasOperation::Deposit,
+        GasOperation::MakerClose,
+        GasOperation::Funding,
+        GasOperation::Sweep,
+    ];
+
+    /// The operation whose [`as_str`](Self::as_str) label is `label`.
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.as_str() == label)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GasOperation::BeaconCreate => "beacon_create",
+            GasOperation::BeaconRegister => "beacon_register",
+            GasOperation::BeaconUpdate => "beacon_update",
+            GasOperation::PerpDeploy => "perp_deploy",
+            GasOperation::DepositApproval => "deposit_approval",
+            GasOperation::Deposit => "deposit",
+            GasOperation::MakerClose => "maker_close",
+            GasOperation::Funding => "funding",
+            GasOperation::Sweep => "sweep",
+        }
+    }
+}
+
+#[derive(Default)]
+struct Histogram {
+    count: u64,
+    sum: u128,
+    min: u64,
+    max: u64,
+    /// One counter per bucket bound, plus a trailing overflow counter.
+    buckets: Vec<u64>,
+}
+
+/// In-memory gas histograms keyed by [`GasOperation`].
+pub struct GasMetrics {
+    enabled: bool,
+    bounds: Vec<u64>,
+    histograms: Mutex<BTreeMap<GasOperation, Histogram>>,
+}
+
+impl GasMetrics {
+    /// Create a recorder with the given bucket upper bounds (sorted and deduplicated).
+    pub fn new(enabled: bool, mut bounds: Vec<u64>) -> Self {
+        bounds.sort_unstable();
+        bounds.dedup();
+        Self {
+            enabled,
+            bounds,
+            histograms: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Build from `GAS_METRICS_ENABLED` (default on) and `GAS_METRICS_BUCKETS`
+    /// (comma-separated gas bounds; the defaults apply when unset or unparseable).
+    pub fn from_env() -> Self {
+        let enabled = std::env::var("GAS_METRICS_ENABLED")
+            .map(|v| {
+                !matches!(
+                    v.trim().to_lowercase().as_str(),
+                    "0" | "false" | "no" | "off"
+                )
+            })
+            .unwrap_or(true);
+        let bounds = std::env::var("GAS_METRICS_BUCKETS")
+            .ok()
+            .and_then(|raw| {
+                raw.split(',')
+                    .map(|b| b.trim().parse::<u64>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()
+            })
+            .filter(|b| !b.is_empty())
+            .unwrap_or_else(|| DEFAULT_GAS_BUCKETS.to_vec());
+        Self::new(enabled, bounds)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Record one confirmed transaction's gas under `operation`.
+    pub fn record(&self, operation: GasOperation, gas_used: u64) {
+        if !self.enabled {
+            return;
+        }
+        tracing::info!(
+            metric = "GasUsed",
+            operation = operation.as_str(),
+            gas_used,
+            "{} used {} gas",
+            operation.as_str(),
+            gas_used
+        );
+
+        let bucket = self
+            .bounds
+            .iter()
+            .position(|&bound| gas_used <= bound)
+            .unwrap_or(self.bounds.len());
+        let mut histograms = self.histograms.lock().unwrap_or_else(|e| e.into_inner());
+        let histogram = histograms.entry(operation).or_default();
+        if histogram.buckets.is_empty() {
+            histogram.buckets = vec![0; self.bounds.len() + 1];
+            histogram.min = gas_used;
+        }
+        histogram.count += 1;
+        histogram.sum += u128::from(gas_used);
+        histogram.min = histogram.min.min(gas_used);
+        histogram.max = histogram.max.max(gas_used);
+        histogram.buckets[bucket] += 1;
+    }
+
+    /// Point-in-time copy of every operation's histogram (cumulative bucket counts).
+    pub fn snapshot(&self) -> GasMetricsResponse {
+        let histograms = self.histograms.lock().unwrap_or_else(|e| e.into_inner());
+        let operations = histograms
+            .iter()
+            .map(|(operation, h)| {
+                let mut cumulative = 0;
+                let buckets = h
+                    .buckets
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &n)| {
+                        cumulative += n;
+                        GasHistogramBucket {
+                            le: self
+                                .bounds
+                                .get(i)
+                                .map_or_else(|| "+Inf".to_string(), |b| b.to_string()),
+                            count: cumulative,
+                        }
+                    })
+                    .collect();
+                GasOperationHistogram {
+                    operation: operation.as_str().to_string(),
+                    count: h.count,
+                    sum: h.sum.to_string(),
+                    min: h.min,
+                    max: h.max,
+                    mean: (h.sum / u128::from(h.count.max(1))) as u64,
+                    buckets,
+                }
+            })
+            .collect();
+
+        GasMetricsResponse {
+            enabled: self.enabled,
+            operations,
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Offline mock mode (`BEACONATOR_MODE=mock`)
+//!
+//! Lets front-end developers run the API without a chain, Redis or KMS. The server starts
+//! with stub wallets and registries, and the write endpoints front-ends drive
+//! (`/create_beacon`, `/update_beacon`, `/deploy_perp_for_beacon`) answer with canned
+//! results instead of sending transactions. Canned addresses and hashes are hashes of
+//! the request, so the same request always gets the same answer. Endpoints without a
+//! mock still run their real handler and fail as they would with the RPC down.
+//!
+//! Mock mode refuses to start with `ENV=mainnet`.
+
+use std::sync::Arc;
+
+use alloy::primitives::{Address, B256, Bytes, keccak256};
+use alloy::providers::ProviderBuilder;
+use alloy::signers::local::PrivateKeySigner;
+
+use crate::config::chain_id_for_env;
+use crate::models::{
+    AppState, AuthConfig, ContractAddresses, LiveContracts, PerpConfig, ProviderConfig, Registries,
+    WalletConfig,
+};
+use crate::services::beacon::{
+    BeaconEventFeed, BeaconTypeRegistry, ComponentFactoryRegistry, RecipeRegistry,
+};
+use crate::services::wallet::{FundingRateLimiter, WalletManager};
+
+/// Env var selecting the server mode: unset or `live` for the real server, `mock` for
+/// offline mock mode.
+pub const BEACONATOR_MODE_ENV: &str = "BEACONATOR_MODE";
+
+/// RPC URL the mock read provider points at when `RPC_URL` is unset. Nothing mocked
+/// calls it.
+pub const MOCK_RPC_URL: &str = "http://localhost:8545";
+
+/// Anvil's first deterministic key, so the mock signer address is stable across runs.
+const MOCK_SIGNER_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Whether a raw `BEACONATOR_MODE` value selects mock mode. Unset and `live` do not; any
+/// other value is an error so a typo cannot silently start the real server.
+pub fn mock_mode(value: Option<&str>) -> Result<bool, String> {
+    match value.map(|v| v.trim().to_lowercase()).as_deref() {
+        None | Some("") | Some("live") => Ok(false),
+        Some("mock") => Ok(true),
+        Some(other) => Err(format!(
+            "Invalid {BEACONATOR_MODE_ENV} '{other}'. Must be 'live' or 'mock'"
+        )),
+    }
+}
+
+/// Refuse mock mode on a mainnet configuration: canned success responses there would
+/// look like real deployments.
+pub fn check_mock_mode_allowed(env_type: &str) -> Result<(), String> {
+    if env_type.trim().eq_ignore_ascii_case("mainnet") {
+        return Err(format!(
+            "{BEACONATOR_MODE_ENV}=mock cannot be used with ENV=mainnet"
+        ));
+    }
+    Ok(())
+}
+
+/// Deterministic address for a mocked `kind` of contract (`"beacon"`, `"perp"`, ...)
+/// created from `seed`.
+pub fn mock_address(kind: &str, seed: &[u8]) -> Address {
+    Address::from_word(mock_hash(kind, seed))
+}
+
+/// Deterministic 32-byte hash (transaction hash, pool id) for a mocked `kind` of value
+/// derived from `seed`.
+pub fn mock_hash(kind: &str, seed: &[u8]) -> B256 {
+    keccak256([b"beaconator-mock:", kind.as_bytes(), b":", seed].concat())
+}
+
+/// Application state for mock mode: stub wallets and registries, in-memory caches, and
+/// a read provider that is never called by the mocked endpoints.
+pub fn mock_app_state(env_type: &str, rpc_url: &str, auth: AuthConfig) -> Result<AppState, String> {
+    check_mock_mode_allowed(env_type)?;
+    let chain_id = chain_id_for_env(env_type)?;
+
+    let read_provider = Arc::new(
+        ProviderBuilder::new().connect_http(
+            rpc_url
+                .parse()
+                .map_err(|e| format!("Invalid RPC_URL for mock mode: {e}"))?,
+        ),
+    );
+    let signer = MOCK_SIGNER_KEY
+        .parse::<PrivateKeySigner>()
+        .map_err(|e| format!("Mock signer key does not parse: {e}"))?;
+    let error_registry = crate::services::perp::ErrorSelectorRegistry::from_bundled_abis()
+        .map_err(|errors| {
+            errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        })?;
+    let contract = |name: &str| mock_address("contract", name.as_bytes());
+
+    Ok(AppState {
+        provider: ProviderConfig {
+            read_provider: read_provider.clone(),
+            rpc_url: rpc_url.to_string(),
+            chain_id,
+            env_type: env_type.to_lowercase(),
+            endpoints: Arc::new(crate::services::rpc::RpcEndpoints::single(
+                rpc_url.to_string(),
+                read_provider,
+            )),
+            receipts: Arc::new(crate::services::transaction::ReceiptCache::default()),
+            gas_prices: Arc::new(crate::services::gas_price::GasPriceCache::default()),
+            read_retry: crate::services::rpc::ReadRetryPolicy::default(),
+            receipt_wait: crate::services::transaction::ReceiptWaitConfig::default(),
+            confirmations: crate::services::transaction::ConfirmationPolicy::default(),
+        },
+        wallets: WalletConfig {
+            manager: Arc::new(WalletManager::test_stub()),
+            signer_address: signer.address(),
+            signer,
+            usdc_transfer_limit: 1_000_000_000,
+            eth_transfer_limit: 10_000_000_000_000_000,
+            usdc_bonus_limit: 50_000_000,
+            faucet_reserve_eth_wei: 20_000_000_000_000_000,
+            funding_limiter: Arc::new(FundingRateLimiter::from_env()),
+            funding_replays: Arc::new(crate::services::idempotency::IdempotencyStore::from_env()),
+            cold_wallet_address: None,
+        },
+        contracts: LiveContracts::new(ContractAddresses {
+            perpcity_registry: contract("PerpCityRegistry"),
+            perp_factory: contract("PerpFactory"),
+            usdc: contract("USDC"),
+            ecdsa_verifier_factory: contract("ECDSAVerifierFactory"),
+            multicall3: None,
+            identity_beacon_bytecode: Bytes::new(),
+            safe: None,
+            fees_module: contract("FeesModule"),
+            funding_module: contract("FundingModule"),
+            margin_ratios_module: contract("MarginRatiosModule"),
+            price_impact_module: contract("PriceImpactModule"),
+            pricing_module: contract("PricingModule"),
+            protocol_fee_manager: None,
+            module_registry: None,
+        }),
+        auth,
+        registries: Registries {
+            beacon_types: Arc::new(BeaconTypeRegistry::test_stub()),
+            component_factories: Arc::new(ComponentFactoryRegistry::test_stub()),
+            recipes: Arc::new(RecipeRegistry::test_stub()),
+        },
+        touch: crate::services::touch::TouchDispatcher::disabled(),
+        beacon_events: Arc::new(BeaconEventFeed::disabled()),
+        idempotency: Arc::new(crate::services::idempotency::IdempotencyStore::from_env()),
+        gas_metrics: Arc::new(crate::services::metrics::GasMetrics::from_env()),
+        history: Arc::new(crate::services::history::TransactionHistory::from_env()),
+        perp: PerpConfig::default(),
+        error_registry: Arc::new(error_registry),
+        allowances: Arc::new(crate::services::perp::AllowanceCache::new()),
+        strict_address_checksum: false,
+    })
+}
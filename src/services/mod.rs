@@ -1,4 +1,12 @@
+pub mod address;
 pub mod beacon;
+pub mod error;
+pub mod estimate;
+pub mod gas_price;
+pub mod history;
+pub mod idempotency;
+pub mod metrics;
+pub mod mock;
 pub mod perp;
 pub mod rpc;
 pub mod safe;
@@ -0,0 +1,75 @@
+//! Cached USDC allowances of pool wallets towards per-market `Perp` contracts.
+//!
+//! Deposits from the same wallet into the same Perp read `USDC.allowance` each time. The
+//! cache remembers the value read per `(owner, spender)` so a deposit retried after failing
+//! before any transaction went out skips the read. Once an `approve` or `openMaker` is
+//! sent the allowance on chain changes, so the entry is dropped and the next deposit reads
+//! the chain again.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use alloy::primitives::{Address, U256};
+
+/// Last known allowance per `(owner, spender)`.
+#[derive(Default)]
+pub struct AllowanceCache {
+    entries: Mutex<HashMap<(Address, Address), U256>>,
+}
+
+impl AllowanceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached allowance `owner` granted `spender`, if known.
+    pub fn get(&self, owner: Address, spender: Address) -> Option<U256> {
+        self.entries.lock().unwrap().get(&(owner, spender)).copied()
+    }
+
+    /// Remember `allowance` after reading it from the chain.
+    pub fn record(&self, owner: Address, spender: Address, allowance: U256) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert((owner, spender), allowance);
+    }
+
+    /// Forget the allowance for `(owner, spender)`.
+    pub fn invalidate(&self, owner: Address, spender: Address) {
+        self.entries.lock().unwrap().remove(&(owner, spender));
+    }
+
+    /// Guard that invalidates `(owner, spender)` when dropped. Take it before sending
+    /// `approve` or `openMaker` so the entry is forgotten however the deposit ends.
+    pub fn guard(&self, owner: Address, spender: Address) -> AllowanceGuard<'_> {
+        AllowanceGuard {
+            cache: self,
+            owner,
+            spender,
+        }
+    }
+
+    /// Number of cached entries.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Whether nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Invalidates a cached allowance when dropped.
+pub struct AllowanceGuard<'a> {
+    cache: &'a AllowanceCache,
+    owner: Address,
+    spender: Address,
+}
+
+impl Drop for AllowanceGuard<'_> {
+    fn drop(&mut self) {
+        self.cache.invalidate(self.owner, self.spender);
+    }
+}
@@ -0,0 +1,134 @@
+//! Beacon update cadence, observed on-chain
+//!
+//! A Perp's `ema_window` is the span its funding EMA averages the beacon index over.
+//! A window shorter than the gap between beacon updates only ever sees one stale index,
+//! so before `createPerp` the deploy flow measures how often the beacon has actually
+//! updated (beacons do not report a cadence themselves) from its recent `IndexUpdated`
+//! logs and rejects windows below that.
+
+use alloy::eips::BlockNumberOrTag;
+use alloy::primitives::Address;
+use alloy::providers::Provider;
+use alloy::rpc::types::Filter;
+use alloy::sol_types::SolEvent;
+use futures::TryStreamExt;
+
+use super::super::rpc::{ReadRetryPolicy, retry_read};
+use super::super::transaction::events::{log_scan_chunk_blocks_from_env, scan_logs_chunked};
+use crate::ReadOnlyProvider;
+use crate::routes::IBeacon;
+
+/// Blocks of `IndexUpdated` history inspected by default.
+pub const DEFAULT_BEACON_CADENCE_LOOKBACK_BLOCKS: u64 = 2_000;
+
+/// Prefix of the error returned when `ema_window` is shorter than the beacon's cadence.
+pub const EMA_WINDOW_TOO_SHORT: &str = "ema_window too short";
+
+/// Blocks of beacon history the deploy pre-flight inspects, read from
+/// `PERP_BEACON_CADENCE_LOOKBACK_BLOCKS`. `0` disables the check.
+pub fn beacon_cadence_lookback_blocks_from_env() -> u64 {
+    std::env::var("PERP_BEACON_CADENCE_LOOKBACK_BLOCKS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_BEACON_CADENCE_LOOKBACK_BLOCKS)
+}
+
+/// How often a beacon has been updating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeaconCadence {
+    /// Mean seconds between consecutive updates.
+    pub interval_secs: u64,
+    /// Updates the mean was taken over.
+    pub updates: usize,
+}
+
+/// Mean interval of `updates` updates, the first at `first_timestamp` and the last at
+/// `last_timestamp`. `None` with fewer than two updates.
+pub fn estimate_cadence(
+    first_timestamp: u64,
+    last_timestamp: u64,
+    updates: usize,
+) -> Option<BeaconCadence> {
+    if updates < 2 {
+        return None;
+    }
+    Some(BeaconCadence {
+        interval_secs: last_timestamp.saturating_sub(first_timestamp) / (updates as u64 - 1),
+        updates,
+    })
+}
+
+/// Reject an `ema_window` shorter than the beacon's observed update interval. An
+/// unknown cadence passes.
+pub fn check_ema_window(
+    beacon: Address,
+    ema_window: u32,
+    cadence: Option<BeaconCadence>,
+) -> Result<(), String> {
+    match cadence {
+        Some(cadence) if u64::from(ema_window) < cadence.interval_secs => Err(format!(
+            "{EMA_WINDOW_TOO_SHORT}: {ema_window}s is shorter than beacon {beacon}'s update \
+             interval (~{}s over its last {} updates); use an ema_window of at least {}s",
+            cadence.interval_secs, cadence.updates, cadence.interval_secs
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Measure `beacon`'s cadence from its `IndexUpdated` logs in the last `lookback_blocks`
+/// blocks, read `LOG_SCAN_CHUNK_BLOCKS` at a time and retried per `retry`. `Ok(None)`
+/// when fewer than two updates landed in that range.
+pub async fn observe_beacon_cadence(
+    provider: &ReadOnlyProvider,
+    retry: &ReadRetryPolicy,
+    beacon: Address,
+    lookback_blocks: u64,
+) -> Result<Option<BeaconCadence>, String> {
+    let head = retry_read(retry, "get_block_number", || provider.get_block_number())
+        .await
+        .map_err(|e| format!("Failed to get block number: {e}"))?;
+
+    let filter = Filter::new()
+        .address(beacon)
+        .event_signature(IBeacon::IndexUpdated::SIGNATURE_HASH);
+    let blocks: Vec<u64> = scan_logs_chunked(
+        provider,
+        retry,
+        &filter,
+        head.saturating_sub(lookback_blocks.saturating_sub(1)),
+        head,
+        log_scan_chunk_blocks_from_env(),
+        |log| log.block_number,
+    )
+    .try_collect()
+    .await
+    .map_err(|e| format!("Failed to get IndexUpdated logs for beacon {beacon}: {e}"))?;
+
+    if blocks.len() < 2 {
+        return Ok(None);
+    }
+
+    let first_timestamp = block_timestamp(provider, retry, blocks[0]).await?;
+    let last_timestamp = block_timestamp(provider, retry, blocks[blocks.len() - 1]).await?;
+    Ok(estimate_cadence(
+        first_timestamp,
+        last_timestamp,
+        blocks.len(),
+    ))
+}
+
+async fn block_timestamp(
+    provider: &ReadOnlyProvider,
+    retry: &ReadRetryPolicy,
+    number: u64,
+) -> Result<u64, String> {
+    let block = retry_read(retry, "get_block_by_number", move || async move {
+        provider
+            .get_block_by_number(BlockNumberOrTag::Number(number))
+            .await
+    })
+    .await
+    .map_err(|e| format!("Failed to get block {number}: {e}"))?
+    .ok_or_else(|| format!("Block {number} not found"))?;
+    Ok(block.header.timestamp)
+}
@@ -1,20 +1,164 @@
-use alloy::primitives::{Address, FixedBytes, U256};
+use alloy::primitives::{Address, B256, FixedBytes, U256};
 use alloy::providers::Provider;
+use alloy::rpc::types::{Filter, Log};
+use alloy::sol_types::SolEvent;
+use futures::TryStreamExt;
+use std::pin::pin;
 use std::time::Duration;
-use tokio::time::timeout;
 use tracing;
 
-use super::super::transaction::events::{parse_maker_opened_event, parse_perp_created_event};
-use super::super::transaction::execution::is_nonce_error;
-use super::validation::try_decode_revert_reason;
-use crate::models::{AppState, DeployPerpForBeaconResponse, DepositLiquidityForPerpResponse};
+use super::super::metrics::GasOperation;
+use super::super::rpc::retry_read;
+use super::super::transaction::confirmations::wait_for_confirmations;
+use super::super::transaction::events::{
+    PerpCreatedEvent, decode_perp_created, log_scan_chunk_blocks_from_env,
+    parse_maker_opened_event, parse_perp_created_event, scan_logs_chunked, sum_erc20_transfers,
+};
+use super::super::transaction::execution::{retry_once_on_nonce_error, wait_for_receipt};
+use super::cadence::{
+    beacon_cadence_lookback_blocks_from_env, check_ema_window, observe_beacon_cadence,
+};
+use super::liquidity::{calculate_liquidity_from_margin, signed_x96_to_decimal, x96_to_decimal};
+use super::params::{build_create_perp_call, open_maker_params};
+use super::slippage::{
+    deposit_slippage_bps_from_env, estimate_maker_amounts, is_max_amt_exceeded, max_amount_in,
+    slippage_exceeded_message,
+};
+use super::validation::{
+    format_usdc, resimulated_revert_reason, try_decode_revert_reason_with, validate_tick_range,
+    validate_usdc_balance,
+};
+use crate::models::{
+    AppState, CloseMakerPositionResponse, DeployPerpForBeaconResponse,
+    DepositLiquidityForPerpResponse, MakerInfoResponse, PerpInfoResponse,
+};
 use crate::routes::{IERC20, IPerp, IPerpFactory};
+use crate::services::error::BeaconError;
+
+/// First block of the `PerpCreated` scan, read from `PERP_FACTORY_DEPLOY_BLOCK` (the block
+/// PerpFactory was deployed in). `None` when unset or unparseable: the existing-perp
+/// lookup is skipped rather than scanning from genesis.
+pub fn perp_factory_deploy_block_from_env() -> Option<u64> {
+    std::env::var("PERP_FACTORY_DEPLOY_BLOCK")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+}
+
+/// Earliest `PerpCreated` emitted by `perp_factory` for `beacon` among `logs`, with the
+/// hash of the transaction that created it.
+pub fn first_perp_for_beacon(
+    logs: &[Log],
+    perp_factory: Address,
+    beacon: Address,
+) -> Option<(PerpCreatedEvent, B256)> {
+    logs.iter()
+        .filter(|log| log.address() == perp_factory)
+        .filter_map(|log| {
+            let event = decode_perp_created(log)?;
+            let position = (log.block_number?, log.log_index?);
+            Some((position, event, log.transaction_hash?))
+        })
+        .filter(|(_, event, _)| event.beacon == beacon)
+        .min_by_key(|(position, _, _)| *position)
+        .map(|(_, event, tx_hash)| (event, tx_hash))
+}
+
+/// Response for a beacon whose perp already exists: the original `PerpCreated` values
+/// with `already_deployed` set and no gas spent.
+///
+/// The salt the perp was created with is not emitted on-chain, so `salt` echoes the one
+/// from the current request.
+pub fn already_deployed_response(
+    perp_factory: Address,
+    event: &PerpCreatedEvent,
+    transaction_hash: B256,
+    salt: FixedBytes<32>,
+) -> DeployPerpForBeaconResponse {
+    DeployPerpForBeaconResponse {
+        perp_address: event.perp.to_string(),
+        pool_id: format!("{:#x}", event.pool_id),
+        perp_factory_address: perp_factory.to_string(),
+        initial_index: event.initial_index.to_string(),
+        ema_window: event.ema_window,
+        sqrt_price_x96: event.sqrt_price_x96.to_string(),
+        mark_price: x96_to_decimal(event.mark_price_x96),
+        tick: event.tick,
+        salt: format!("{salt:#x}"),
+        transaction_hash: transaction_hash.to_string(),
+        gas_used: 0,
+        confirmations: 0,
+        already_deployed: true,
+    }
+}
+
+/// Find the perp PerpFactory already created for `beacon`, if any.
+///
+/// PerpFactory keeps no beacon -> perp mapping, so this scans its `PerpCreated` logs from
+/// `PERP_FACTORY_DEPLOY_BLOCK` to the head (`LOG_SCAN_CHUNK_BLOCKS` blocks per
+/// request, stopping at the first match) and confirms the match with `perps()`. A failed
+/// scan is an error rather than `None`: deploying on a guess could create a duplicate
+/// market for the beacon. Without `PERP_FACTORY_DEPLOY_BLOCK` the lookup is skipped.
+pub async fn find_perp_for_beacon(
+    state: &AppState,
+    beacon_address: Address,
+) -> Result<Option<(PerpCreatedEvent, B256)>, String> {
+    let Some(from_block) = perp_factory_deploy_block_from_env() else {
+        tracing::warn!(
+            "PERP_FACTORY_DEPLOY_BLOCK is not set; not checking beacon {} for an existing perp",
+            beacon_address
+        );
+        return Ok(None);
+    };
+    let provider = &state.provider.read_provider;
+    let perp_factory = state.contracts.load().perp_factory;
+    let retry = state.provider.read_retry;
+
+    let head = retry_read(&retry, "get_block_number", || provider.get_block_number())
+        .await
+        .map_err(|e| format!("Failed to get block number: {e}"))?;
+
+    let filter = Filter::new()
+        .address(perp_factory)
+        .event_signature(IPerpFactory::PerpCreated::SIGNATURE_HASH);
+    let mut matches = pin!(scan_logs_chunked(
+        provider,
+        &retry,
+        &filter,
+        from_block,
+        head,
+        log_scan_chunk_blocks_from_env(),
+        |log| first_perp_for_beacon(std::slice::from_ref(log), perp_factory, beacon_address),
+    ));
+    let Some((event, tx_hash)) = matches
+        .try_next()
+        .await
+        .map_err(|e| format!("Failed to scan PerpCreated logs: {e}"))?
+    else {
+        return Ok(None);
+    };
+
+    let factory = &IPerpFactory::new(perp_factory, provider);
+    let perp = event.perp;
+    let registered = retry_read(&retry, "PerpFactory.perps", move || async move {
+        factory.perps(perp).call().await
+    })
+    .await
+    .map_err(|e| format!("Failed to check PerpFactory.perps({perp}): {e}"))?;
+    if !registered {
+        return Err(format!(
+            "PerpCreated log names perp {perp} for beacon {beacon_address}, but \
+             PerpFactory {perp_factory} does not list it"
+        ));
+    }
+    Ok(Some((event, tx_hash)))
+}
 
 /// Deploys a per-market `Perp` contract via PerpFactory.createPerp (perpcity-contracts@v0.1.0).
 ///
 /// Module addresses are taken from `state.contracts` (configured via env vars at startup).
 /// On success, returns the new `Perp` contract address along with PoolId / sqrtPrice / tick
-/// extracted from the `PerpCreated` event.
+/// extracted from the `PerpCreated` event. A beacon that already has a perp gets that perp
+/// back with `already_deployed: true` and nothing is sent.
 #[allow(clippy::too_many_arguments)]
 pub async fn deploy_perp_for_beacon(
     state: &AppState,
@@ -25,13 +169,123 @@ pub async fn deploy_perp_for_beacon(
     token_uri: String,
     ema_window: u32,
     salt: FixedBytes<32>,
+) -> Result<DeployPerpForBeaconResponse, String> {
+    let inputs = [
+        ("beacon_address", beacon_address.to_string()),
+        ("owner", owner.to_string()),
+        ("name", name.clone()),
+        ("symbol", symbol.clone()),
+        ("salt", format!("{salt:#x}")),
+    ];
+    let result = deploy_perp_inner(
+        state,
+        beacon_address,
+        owner,
+        name,
+        symbol,
+        token_uri,
+        ema_window,
+        salt,
+    )
+    .await;
+    state
+        .history
+        .record_transaction(
+            GasOperation::PerpDeploy,
+            &inputs,
+            match &result {
+                // An existing perp sent nothing; its hash is the original createPerp.
+                Ok(response) if response.already_deployed => Ok(None),
+                Ok(response) => Ok(Some(response.transaction_hash.clone())),
+                Err(e) => Err(e.clone()),
+            },
+        )
+        .await;
+    result
+}
+
+/// Checks run before a wallet is acquired for `createPerp`.
+///
+/// The tick defaults are re-validated because `AppState` can be built with a custom
+/// `PerpConfig` that never went through `PerpConfig::from_env`. `ema_window` must span at
+/// least one beacon update (see [`cadence`](super::cadence)); a cadence that cannot be
+/// observed is logged and skipped rather than blocking the deploy.
+async fn deploy_preflight(
+    state: &AppState,
+    beacon_address: Address,
+    ema_window: u32,
+) -> Result<(), String> {
+    state.perp.validate()?;
+
+    let lookback = beacon_cadence_lookback_blocks_from_env();
+    if lookback == 0 {
+        return Ok(());
+    }
+    match observe_beacon_cadence(
+        &state.provider.read_provider,
+        &state.provider.read_retry,
+        beacon_address,
+        lookback,
+    )
+    .await
+    {
+        Ok(None) => {
+            tracing::warn!(
+                "Beacon {} has fewer than two updates in the last {} blocks; skipping ema_window check",
+                beacon_address,
+                lookback
+            );
+            Ok(())
+        }
+        Ok(cadence) => check_ema_window(beacon_address, ema_window, cadence),
+        Err(e) => {
+            tracing::warn!(
+                "Could not observe update cadence of beacon {}: {}; skipping ema_window check",
+                beacon_address,
+                e
+            );
+            Ok(())
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn deploy_perp_inner(
+    state: &AppState,
+    beacon_address: Address,
+    owner: Address,
+    name: String,
+    symbol: String,
+    token_uri: String,
+    ema_window: u32,
+    salt: FixedBytes<32>,
 ) -> Result<DeployPerpForBeaconResponse, String> {
     tracing::info!("Starting perp deployment for beacon: {}", beacon_address);
+    // One snapshot for the whole deploy, so a config reload cannot split it.
+    let contracts = state.contracts.load();
+
+    if let Some((event, tx_hash)) = find_perp_for_beacon(state, beacon_address).await? {
+        tracing::info!(
+            "Beacon {} already has perp {} (created in tx {}); skipping createPerp",
+            beacon_address,
+            event.perp,
+            tx_hash
+        );
+        return Ok(already_deployed_response(
+            contracts.perp_factory,
+            &event,
+            tx_hash,
+            salt,
+        ));
+    }
 
+    deploy_preflight(state, beacon_address, ema_window).await?;
+
+    // A beacon designated to a wallet deploys its perps from that wallet too.
     let wallet_handle = state
         .wallets
         .manager
-        .acquire_any_wallet()
+        .acquire_for_beacon(&beacon_address)
         .await
         .map_err(|e| format!("Failed to acquire wallet: {e}"))?;
 
@@ -39,20 +293,20 @@ pub async fn deploy_perp_for_beacon(
     tracing::info!("Acquired wallet {} for perp deployment", wallet_address);
 
     let provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| format!("Failed to build provider: {e}"))?;
 
     tracing::info!("Environment details:");
-    tracing::info!("  - PerpFactory address: {}", state.contracts.perp_factory);
+    tracing::info!("  - PerpFactory address: {}", contracts.perp_factory);
     tracing::info!("  - Wallet address: {}", wallet_address);
-    tracing::info!("  - USDC address: {}", state.contracts.usdc);
+    tracing::info!("  - USDC address: {}", contracts.usdc);
     tracing::info!("Modules struct (server-configured):");
     tracing::info!("  - beacon: {}", beacon_address);
-    tracing::info!("  - fees: {}", state.contracts.fees_module);
-    tracing::info!("  - funding: {}", state.contracts.funding_module);
-    tracing::info!("  - marginRatios: {}", state.contracts.margin_ratios_module);
-    tracing::info!("  - priceImpact: {}", state.contracts.price_impact_module);
-    tracing::info!("  - pricing: {}", state.contracts.pricing_module);
+    tracing::info!("  - fees: {}", contracts.fees_module);
+    tracing::info!("  - funding: {}", contracts.funding_module);
+    tracing::info!("  - marginRatios: {}", contracts.margin_ratios_module);
+    tracing::info!("  - priceImpact: {}", contracts.price_impact_module);
+    tracing::info!("  - pricing: {}", contracts.pricing_module);
 
     if let Ok(balance) = state
         .provider
@@ -74,7 +328,6 @@ pub async fn deploy_perp_for_beacon(
         Ok(code) if code.is_empty() => {
             let error_msg =
                 format!("Beacon address {beacon_address} has no deployed code (not a contract)");
-            tracing::error!("{}", error_msg);
             return Err(error_msg);
         }
         Ok(code) => {
@@ -86,127 +339,80 @@ pub async fn deploy_perp_for_beacon(
         }
         Err(e) => {
             let error_msg = format!("Failed to check beacon address {beacon_address}: {e}");
-            tracing::error!("{}", error_msg);
             return Err(error_msg);
         }
     }
 
-    let factory = IPerpFactory::new(state.contracts.perp_factory, &provider);
-
-    let modules = IPerpFactory::Modules {
-        beacon: beacon_address,
-        fees: state.contracts.fees_module,
-        funding: state.contracts.funding_module,
-        marginRatios: state.contracts.margin_ratios_module,
-        priceImpact: state.contracts.price_impact_module,
-        pricing: state.contracts.pricing_module,
-    };
-
-    // emaWindow is encoded as uint24 on-chain; verify before sending so the revert is local.
-    if ema_window == 0 {
-        return Err("ema_window must be > 0 (uint24)".to_string());
-    }
-    if ema_window > 0xFF_FFFF {
-        return Err(format!(
-            "ema_window {ema_window} exceeds uint24 max (16777215)"
-        ));
-    }
-    let ema_window_u24 = alloy::primitives::Uint::<24, 1>::from(ema_window);
+    let factory = &IPerpFactory::new(contracts.perp_factory, &provider);
+    let create_perp = &build_create_perp_call(
+        &contracts,
+        beacon_address,
+        owner,
+        name,
+        symbol,
+        token_uri,
+        ema_window,
+        salt,
+    )?;
 
     tracing::info!("Sending createPerp transaction to PerpFactory...");
     wallet_handle.ensure_lock_held()?;
-    let pending_tx = factory
-        .createPerp(
-            owner,
-            name.clone(),
-            symbol.clone(),
-            token_uri.clone(),
-            modules.clone(),
-            ema_window_u24,
-            salt,
-        )
-        .send()
-        .await
-        .map_err(|e| {
-            let mut error_msg = format!("createPerp send failed: {e}");
-            if let Some(decoded) = try_decode_revert_reason(&e) {
-                error_msg = format!("createPerp reverted: {decoded}");
-            }
-            tracing::error!("{}", error_msg);
-            tracing::error!("Context:");
-            tracing::error!("  - PerpFactory: {}", state.contracts.perp_factory);
-            tracing::error!("  - Beacon: {}", beacon_address);
-            tracing::error!("  - Owner: {}", owner);
-            error_msg
-        })?;
-
-    let pending_tx_hash = *pending_tx.tx_hash();
-    tracing::info!("createPerp tx hash: {:?}", pending_tx_hash);
-
-    let receipt = match timeout(Duration::from_secs(120), pending_tx.get_receipt()).await {
-        Ok(Ok(receipt)) => receipt,
-        Ok(Err(e)) => {
-            tracing::warn!("get_receipt() failed for createPerp: {}", e);
-            match timeout(
-                Duration::from_secs(30),
-                state
-                    .provider
-                    .read_provider
-                    .get_transaction_receipt(pending_tx_hash),
-            )
-            .await
-            {
-                Ok(Ok(Some(r))) => r,
-                Ok(Ok(None)) => {
-                    let msg =
-                        format!("createPerp transaction {pending_tx_hash} not found on-chain");
-                    return Err(msg);
-                }
-                Ok(Err(e)) => {
-                    let msg =
-                        format!("Failed to check createPerp tx {pending_tx_hash} on-chain: {e}");
-                    return Err(msg);
+    let pending_tx = retry_once_on_nonce_error(
+        "createPerp",
+        move || async move {
+            factory.call_builder(create_perp).send().await.map_err(|e| {
+                let mut error_msg = format!("createPerp send failed: {e}");
+                if let Some(decoded) =
+                    try_decode_revert_reason_with(&e, Some(state.error_registry.as_ref()))
+                {
+                    error_msg = format!("createPerp reverted: {decoded}");
                 }
-                Err(_) => {
-                    let msg = format!("Timeout checking createPerp tx {pending_tx_hash} on-chain");
-                    return Err(msg);
-                }
-            }
-        }
-        Err(_) => {
-            let msg = "Timeout waiting for createPerp receipt".to_string();
-            return Err(msg);
-        }
-    };
+                error_msg
+            })
+        },
+        || wallet_handle.resync_nonce(&provider),
+    )
+    .await?;
+
+    tracing::info!("createPerp tx hash: {:?}", pending_tx.tx_hash());
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        "createPerp",
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(120)),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
 
     let tx_hash = receipt.transaction_hash;
     tracing::info!("createPerp confirmed in block {:?}", receipt.block_number);
+    state
+        .gas_metrics
+        .record(GasOperation::PerpDeploy, receipt.gas_used);
 
     // Reverted transactions still produce receipts; check status before parsing
     // events. Re-simulate to recover the revert reason (best effort).
     if !receipt.status() {
-        let revert_detail = match factory
-            .createPerp(
-                owner,
-                name.clone(),
-                symbol.clone(),
-                token_uri.clone(),
-                modules,
-                ema_window_u24,
-                salt,
-            )
-            .call()
-            .await
-        {
-            Err(e) => try_decode_revert_reason(&e).unwrap_or_else(|| e.to_string()),
-            Ok(_) => "no revert reason available (re-simulation succeeded)".to_string(),
-        };
+        let revert_detail = resimulated_revert_reason(
+            factory.call_builder(create_perp).call().await,
+            Some(state.error_registry.as_ref()),
+        );
         let error_msg = format!("createPerp transaction reverted: {revert_detail} (tx {tx_hash})");
-        tracing::error!("{}", error_msg);
         return Err(error_msg);
     }
 
-    let event = parse_perp_created_event(&receipt, state.contracts.perp_factory)?;
+    let event = parse_perp_created_event(&receipt, contracts.perp_factory)?;
+    let confirmations = wait_for_confirmations(
+        &state.provider.read_provider,
+        &state.provider.confirmations,
+        &state.provider.read_retry,
+        tx_hash,
+        receipt.block_number.unwrap_or_default(),
+    )
+    .await?;
 
     tracing::info!("Deployed Perp at {}", event.perp);
     tracing::info!("PoolId: {}", event.pool_id);
@@ -214,20 +420,112 @@ pub async fn deploy_perp_for_beacon(
     Ok(DeployPerpForBeaconResponse {
         perp_address: event.perp.to_string(),
         pool_id: format!("{:#x}", event.pool_id),
-        perp_factory_address: state.contracts.perp_factory.to_string(),
+        perp_factory_address: contracts.perp_factory.to_string(),
         initial_index: event.initial_index.to_string(),
         ema_window,
         sqrt_price_x96: event.sqrt_price_x96.to_string(),
+        mark_price: x96_to_decimal(event.mark_price_x96),
         tick: event.tick,
         salt: format!("{salt:#x}"),
         transaction_hash: tx_hash.to_string(),
+        gas_used: receipt.gas_used,
+        confirmations,
+        already_deployed: false,
     })
 }
 
+/// Build `Perp.openMaker` params for `holder`: validates the tick range, sizes liquidity
+/// from the margin, and fills any omitted slippage limit from the current pool price.
+/// Shared by the deposit flow and gas estimation.
+#[allow(clippy::too_many_arguments)]
+pub async fn build_open_maker_params(
+    state: &AppState,
+    holder: Address,
+    perp_address: Address,
+    margin_amount_usdc: u128,
+    tick_spacing: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+    max_amt0_in: Option<U256>,
+    max_amt1_in: Option<U256>,
+) -> Result<IPerp::OpenMakerParams, String> {
+    validate_tick_range(tick_spacing, tick_lower, tick_upper)?;
+
+    tracing::info!(
+        "Tick parameters validated: spacing={}, lower={}, upper={}",
+        tick_spacing,
+        tick_lower,
+        tick_upper
+    );
+
+    let liquidity_raw =
+        calculate_liquidity_from_margin(margin_amount_usdc, tick_spacing, tick_lower, tick_upper)?;
+    tracing::info!(
+        "Liquidity for {} USDC over [{}, {}]: {}",
+        margin_amount_usdc as f64 / 1_000_000.0,
+        tick_lower,
+        tick_upper,
+        liquidity_raw
+    );
+
+    // v0.1.0 widened OpenMakerParams.liquidity from uint120 to uint128 — `liquidity_raw` is
+    // already u128, so the contract bound is trivially satisfied. Documented for posterity:
+    // the upstream cap is u128::MAX. The earlier u120 cap that lived here is no longer required.
+
+    // Slippage protection: caller-supplied limits win; any omitted limit is the amount the
+    // position needs at the current pool price, plus the configured buffer.
+    let (max_amt0_in, max_amt1_in) = match (max_amt0_in, max_amt1_in) {
+        (Some(max0), Some(max1)) => (max0, max1),
+        (max0, max1) => {
+            let read_perp = &IPerp::new(perp_address, &state.provider.read_provider);
+            let retry = state.provider.read_retry;
+            let pool_state = retry_read(&retry, "Perp.poolState", move || async move {
+                read_perp.poolState().call().await
+            })
+            .await
+            .map_err(|e| format!("Failed to read pool state for slippage limits: {e}"))?;
+
+            let slippage_bps = deposit_slippage_bps_from_env();
+            let (amount0, amount1) = estimate_maker_amounts(
+                U256::from(pool_state.sqrtPriceX96),
+                tick_lower,
+                tick_upper,
+                liquidity_raw,
+            );
+            tracing::info!(
+                "Estimated openMaker amounts at tick {}: token0={:.0}, token1={:.0} (buffer {} bps)",
+                pool_state.tick,
+                amount0,
+                amount1,
+                slippage_bps
+            );
+            (
+                max0.unwrap_or_else(|| max_amount_in(amount0, slippage_bps)),
+                max1.unwrap_or_else(|| max_amount_in(amount1, slippage_bps)),
+            )
+        }
+    };
+
+    open_maker_params(
+        holder,
+        margin_amount_usdc,
+        tick_lower,
+        tick_upper,
+        liquidity_raw,
+        max_amt0_in,
+        max_amt1_in,
+    )
+}
+
 /// Opens a maker liquidity position on a per-market `Perp` contract.
 ///
 /// Approves USDC against the per-perp contract address (which calls `safeTransferFrom` from
 /// `msg.sender`), then sends `Perp.openMaker(OpenMakerParams)`.
+///
+/// `max_amt0_in` / `max_amt1_in` bound the tokens `openMaker` may pull in. A limit left as
+/// `None` defaults to the amount the position needs at the current pool price plus
+/// `DEPOSIT_SLIPPAGE_BPS`. If the price moves past a limit the call fails with a
+/// [`SLIPPAGE_EXCEEDED`](super::slippage::SLIPPAGE_EXCEEDED) error.
 #[allow(clippy::too_many_arguments)]
 pub async fn deposit_liquidity_for_perp(
     state: &AppState,
@@ -236,6 +534,49 @@ pub async fn deposit_liquidity_for_perp(
     tick_spacing: i32,
     tick_lower: i32,
     tick_upper: i32,
+    max_amt0_in: Option<U256>,
+    max_amt1_in: Option<U256>,
+) -> Result<DepositLiquidityForPerpResponse, String> {
+    let result = deposit_liquidity_inner(
+        state,
+        perp_address,
+        margin_amount_usdc,
+        tick_spacing,
+        tick_lower,
+        tick_upper,
+        max_amt0_in,
+        max_amt1_in,
+    )
+    .await;
+    state
+        .history
+        .record_transaction(
+            GasOperation::Deposit,
+            &[
+                ("perp_address", perp_address.to_string()),
+                ("margin_amount_usdc", margin_amount_usdc.to_string()),
+                ("tick_lower", tick_lower.to_string()),
+                ("tick_upper", tick_upper.to_string()),
+            ],
+            match &result {
+                Ok(response) => Ok(Some(response.deposit_transaction_hash.clone())),
+                Err(e) => Err(e.clone()),
+            },
+        )
+        .await;
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn deposit_liquidity_inner(
+    state: &AppState,
+    perp_address: Address,
+    margin_amount_usdc: u128,
+    tick_spacing: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+    max_amt0_in: Option<U256>,
+    max_amt1_in: Option<U256>,
 ) -> Result<DepositLiquidityForPerpResponse, String> {
     tracing::info!(
         "Opening maker on Perp {} with margin {}",
@@ -254,223 +595,702 @@ pub async fn deposit_liquidity_for_perp(
     tracing::info!("Acquired wallet {} for liquidity deposit", wallet_address);
 
     let provider = wallet_handle
-        .build_provider(&state.provider.rpc_url)
+        .build_provider(&state.provider.endpoints)
         .map_err(|e| format!("Failed to build provider: {e}"))?;
 
-    let perp = IPerp::new(perp_address, &provider);
+    let perp = &IPerp::new(perp_address, &provider);
 
-    if tick_lower % tick_spacing != 0 {
-        return Err(format!(
-            "tick_lower ({tick_lower}) must be divisible by tick_spacing ({tick_spacing})"
-        ));
-    }
-    if tick_upper % tick_spacing != 0 {
-        return Err(format!(
-            "tick_upper ({tick_upper}) must be divisible by tick_spacing ({tick_spacing})"
-        ));
-    }
-    if tick_lower >= tick_upper {
-        return Err(format!(
-            "tick_lower ({tick_lower}) must be less than tick_upper ({tick_upper})"
-        ));
-    }
-
-    tracing::info!(
-        "Tick parameters validated: spacing={}, lower={}, upper={}",
+    let open_maker_params = &build_open_maker_params(
+        state,
+        wallet_address,
+        perp_address,
+        margin_amount_usdc,
         tick_spacing,
         tick_lower,
-        tick_upper
-    );
-
-    // Conservative liquidity scaling: USDC margin (6 decimals) -> AMM liquidity unit.
-    let liquidity_scaling_factor = 500_000u128;
-    let liquidity_raw = margin_amount_usdc
-        .checked_mul(liquidity_scaling_factor)
-        .ok_or_else(|| "liquidity scaling overflow".to_string())?;
-
-    // v0.1.0 widened OpenMakerParams.liquidity from uint120 to uint128 — `liquidity_raw` is
-    // already u128, so the contract bound is trivially satisfied. Documented for posterity:
-    // the upstream cap is u128::MAX. The earlier u120 cap that lived here is no longer required.
-
-    // Slippage protection defaults: u256::MAX = "no limit". Caller-supplied limits could be
-    // wired in once the request DTO carries them through.
-    let max_amt0_in = U256::MAX;
-    let max_amt1_in = U256::MAX;
-
-    let open_maker_params = IPerp::OpenMakerParams {
-        holder: wallet_address,
-        margin: margin_amount_usdc,
-        tickLower: alloy::primitives::Signed::<24, 1>::try_from(tick_lower)
-            .map_err(|e| format!("Invalid tick lower: {e}"))?,
-        tickUpper: alloy::primitives::Signed::<24, 1>::try_from(tick_upper)
-            .map_err(|e| format!("Invalid tick upper: {e}"))?,
-        liquidity: liquidity_raw,
-        maxAmt0In: max_amt0_in,
-        maxAmt1In: max_amt1_in,
-    };
+        tick_upper,
+        max_amt0_in,
+        max_amt1_in,
+    )
+    .await?;
+    // The limits actually sent: the caller's, or the slippage-buffered estimates.
+    let (max_amt0_in, max_amt1_in) = (open_maker_params.maxAmt0In, open_maker_params.maxAmt1In);
 
     tracing::info!(
         "Opening maker position: tick_range=[{}, {}], margin={} USDC, liquidity={}",
         tick_lower,
         tick_upper,
         margin_amount_usdc as f64 / 1_000_000.0,
-        liquidity_raw
+        open_maker_params.liquidity
     );
 
+    // Check funds before paying for an approval: a short balance would otherwise only
+    // surface as a safeTransferFrom revert inside openMaker.
+    let margin = U256::from(margin_amount_usdc);
+    let read_usdc = &IERC20::new(state.contracts.load().usdc, &state.provider.read_provider);
+    let retry = state.provider.read_retry;
+    let usdc_balance = retry_read(&retry, "USDC.balanceOf", move || async move {
+        read_usdc.balanceOf(wallet_address).call().await
+    })
+    .await
+    .map_err(|e| format!("Failed to read USDC balance of {wallet_address}: {e}"))?;
+    validate_usdc_balance(usdc_balance, margin)
+        .map_err(|e| format!("{e} (wallet {wallet_address})"))?;
+
     // The per-Perp contract calls safeTransferFrom(USDC, msg.sender, address(this), ...).
     // So the approve target is the per-Perp contract address, NOT the factory.
-    tracing::info!(
-        "Approving USDC ({} USDC) for Perp contract {}",
-        margin_amount_usdc as f64 / 1_000_000.0,
-        perp_address
-    );
+    let allowance = match state.allowances.get(wallet_address, perp_address) {
+        Some(cached) => {
+            tracing::info!(
+                "Using cached USDC allowance ({} USDC) of {} for {}",
+                format_usdc(cached),
+                wallet_address,
+                perp_address
+            );
+            cached
+        }
+        None => {
+            let allowance = retry_read(&retry, "USDC.allowance", move || async move {
+                read_usdc
+                    .allowance(wallet_address, perp_address)
+                    .call()
+                    .await
+            })
+            .await
+            .map_err(|e| format!("Failed to read USDC allowance for {perp_address}: {e}"))?;
+            state
+                .allowances
+                .record(wallet_address, perp_address, allowance);
+            allowance
+        }
+    };
+    // approve and openMaker both change the allowance: forget the cached value once either
+    // may have been sent, whether the deposit succeeds or not.
+    let _allowance_guard = state.allowances.guard(wallet_address, perp_address);
 
-    let usdc_contract = IERC20::new(state.contracts.usdc, &provider);
-    wallet_handle.ensure_lock_held()?;
-    let pending_approval = usdc_contract
-        .approve(perp_address, U256::from(margin_amount_usdc))
-        .send()
+    let approval_receipt = if let Some(approval_amount) =
+        state.perp.usdc_approval(allowance, margin)
+    {
+        if approval_amount == U256::MAX {
+            tracing::info!(
+                "Approving unlimited USDC for Perp contract {} (PERP_APPROVE_MAX)",
+                perp_address
+            );
+        } else {
+            tracing::info!(
+                "Approving USDC ({} USDC) for Perp contract {}",
+                margin_amount_usdc as f64 / 1_000_000.0,
+                perp_address
+            );
+        }
+
+        let usdc_contract = &IERC20::new(state.contracts.load().usdc, &provider);
+        wallet_handle.ensure_lock_held()?;
+        let pending_approval = retry_once_on_nonce_error(
+            "approve",
+            move || async move {
+                usdc_contract
+                    .approve(perp_address, approval_amount)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = format!("Failed to approve USDC spending: {e}");
+                        tracing::error!("{}", error_msg);
+                        error_msg
+                    })
+            },
+            || wallet_handle.resync_nonce(&provider),
+        )
+        .await?;
+
+        let approval_tx_hash = *pending_approval.tx_hash();
+        tracing::info!("USDC approval tx hash: {:?}", approval_tx_hash);
+        let approval_receipt = wait_for_receipt(
+            state,
+            pending_approval,
+            "USDC approval",
+            &state
+                .provider
+                .receipt_wait
+                .with_initial_timeout(Duration::from_secs(150)),
+        )
         .await
-        .map_err(|e| {
-            let error_msg = format!("Failed to approve USDC spending: {e}");
+        .map_err(|e| e.to_string())?;
+
+        state
+            .gas_metrics
+            .record(GasOperation::DepositApproval, approval_receipt.gas_used);
+
+        // A reverted approval means openMaker's safeTransferFrom would fail too.
+        if !approval_receipt.status() {
+            let revert_detail = resimulated_revert_reason(
+                usdc_contract
+                    .approve(perp_address, approval_amount)
+                    .call()
+                    .await,
+                Some(state.error_registry.as_ref()),
+            );
+            let error_msg = format!(
+                "USDC approval transaction reverted: {revert_detail} (tx {approval_tx_hash})"
+            );
             tracing::error!("{}", error_msg);
-            if is_nonce_error(&error_msg) {
-                tracing::warn!("Nonce error detected, transaction failed");
-            }
-            error_msg
-        })?;
-
-    let approval_tx_hash = *pending_approval.tx_hash();
-    tracing::info!("USDC approval tx hash: {:?}", approval_tx_hash);
-
-    let approval_receipt =
-        match timeout(Duration::from_secs(150), pending_approval.get_receipt()).await {
-            Ok(Ok(r)) => r,
-            Ok(Err(e)) => {
-                tracing::warn!("get_receipt() failed for USDC approval: {}", e);
-                wait_for_receipt(state, approval_tx_hash, "USDC approval").await?
-            }
-            Err(_) => {
-                tracing::warn!("Initial get_receipt() timed out for USDC approval, polling...");
-                wait_for_receipt(state, approval_tx_hash, "USDC approval").await?
-            }
-        };
+            return Err(error_msg);
+        }
 
-    // A reverted approval means openMaker's safeTransferFrom would fail too.
-    if !approval_receipt.status() {
-        let revert_detail = match usdc_contract
-            .approve(perp_address, U256::from(margin_amount_usdc))
-            .call()
-            .await
-        {
-            Err(e) => try_decode_revert_reason(&e).unwrap_or_else(|| e.to_string()),
-            Ok(_) => "no revert reason available (re-simulation succeeded)".to_string(),
-        };
-        let error_msg =
-            format!("USDC approval transaction reverted: {revert_detail} (tx {approval_tx_hash})");
-        tracing::error!("{}", error_msg);
-        return Err(error_msg);
-    }
+        Some(approval_receipt)
+    } else {
+        tracing::info!(
+            "Existing USDC allowance ({} USDC) for Perp contract {} covers the margin; skipping approval",
+            format_usdc(allowance),
+            perp_address
+        );
+        None
+    };
 
     tracing::info!("Opening maker position with wallet {}", wallet_address);
     wallet_handle.ensure_lock_held()?;
-    let pending_tx = perp
-        .openMaker(open_maker_params.clone())
-        .send()
-        .await
-        .map_err(|e| {
-            let mut error_msg = format!("openMaker send failed: {e}");
-            if let Some(decoded) = try_decode_revert_reason(&e) {
-                error_msg = format!("openMaker reverted: {decoded}");
-            }
-            tracing::error!("{}", error_msg);
-            if is_nonce_error(&error_msg) {
-                tracing::warn!("Nonce error detected, transaction failed");
-            }
-            error_msg
-        })?;
+    let pending_tx = retry_once_on_nonce_error(
+        "openMaker",
+        move || async move {
+            perp.openMaker(open_maker_params.clone())
+                .send()
+                .await
+                .map_err(|e| {
+                    let mut error_msg = format!("openMaker send failed: {e}");
+                    if let Some(decoded) =
+                        try_decode_revert_reason_with(&e, Some(state.error_registry.as_ref()))
+                    {
+                        error_msg = if is_max_amt_exceeded(&decoded) {
+                            slippage_exceeded_message(max_amt0_in, max_amt1_in)
+                        } else {
+                            format!("openMaker reverted: {decoded}")
+                        };
+                    }
+                    tracing::error!("{}", error_msg);
+                    error_msg
+                })
+        },
+        || wallet_handle.resync_nonce(&provider),
+    )
+    .await?;
 
     let deposit_tx_hash = *pending_tx.tx_hash();
     tracing::info!("openMaker tx hash: {:?}", deposit_tx_hash);
-
-    let receipt = match timeout(Duration::from_secs(90), pending_tx.get_receipt()).await {
-        Ok(Ok(r)) => r,
-        Ok(Err(e)) => {
-            tracing::warn!("get_receipt() failed for openMaker: {}", e);
-            wait_for_receipt(state, deposit_tx_hash, "openMaker").await?
-        }
-        Err(_) => {
-            let msg = "Timeout waiting for openMaker receipt".to_string();
-            tracing::error!("{}", msg);
-            return Err(msg);
-        }
-    };
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        "openMaker",
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(90)),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
 
     tracing::info!("openMaker confirmed: {:?}", receipt.transaction_hash);
+    state
+        .gas_metrics
+        .record(GasOperation::Deposit, receipt.gas_used);
 
     // Reverted transactions still produce receipts; check status before parsing
     // events. Re-simulate to recover the revert reason (best effort).
     if !receipt.status() {
-        let revert_detail = match perp.openMaker(open_maker_params).call().await {
-            Err(e) => try_decode_revert_reason(&e).unwrap_or_else(|| e.to_string()),
-            Ok(_) => "no revert reason available (re-simulation succeeded)".to_string(),
+        let revert_detail = resimulated_revert_reason(
+            perp.openMaker(open_maker_params.clone()).call().await,
+            Some(state.error_registry.as_ref()),
+        );
+        let error_msg = if is_max_amt_exceeded(&revert_detail) {
+            format!(
+                "{} (tx {deposit_tx_hash})",
+                slippage_exceeded_message(max_amt0_in, max_amt1_in)
+            )
+        } else {
+            format!("openMaker transaction reverted: {revert_detail} (tx {deposit_tx_hash})")
         };
-        let error_msg =
-            format!("openMaker transaction reverted: {revert_detail} (tx {deposit_tx_hash})");
         tracing::error!("{}", error_msg);
         return Err(error_msg);
     }
 
     let pos_id = parse_maker_opened_event(&receipt, perp_address)?;
+    let confirmations = wait_for_confirmations(
+        &state.provider.read_provider,
+        &state.provider.confirmations,
+        &state.provider.read_retry,
+        deposit_tx_hash,
+        receipt.block_number.unwrap_or_default(),
+    )
+    .await?;
     tracing::info!("Maker position opened with posId {}", pos_id);
 
     Ok(DepositLiquidityForPerpResponse {
         maker_position_id: pos_id.to_string(),
-        approval_transaction_hash: approval_receipt.transaction_hash.to_string(),
+        approval_transaction_hash: approval_receipt
+            .as_ref()
+            .map(|r| r.transaction_hash.to_string()),
         deposit_transaction_hash: receipt.transaction_hash.to_string(),
+        approval_gas_used: approval_receipt.as_ref().map(|r| r.gas_used),
+        deposit_gas_used: receipt.gas_used,
+        max_amt0_in: max_amt0_in.to_string(),
+        max_amt1_in: max_amt1_in.to_string(),
+        confirmations,
     })
 }
 
-/// Poll the read provider for a transaction receipt with progressive backoff.
-async fn wait_for_receipt(
+/// The close error for a decoded `ownerOf` / `adjustMaker` revert that blames the position
+/// id: [`BeaconError::NotRegistered`] when no such position exists,
+/// [`BeaconError::InvalidState`] when it is a taker position or the sender does not own it.
+/// `None` for any other revert.
+pub fn close_revert_error(
+    pos_id: U256,
+    perp_address: Address,
+    decoded: &str,
+) -> Option<BeaconError> {
+    if decoded.contains("TokenDoesNotExist") || decoded.contains("PositionDoesNotExist") {
+        Some(BeaconError::NotRegistered(format!(
+            "Maker position not found: Perp {perp_address} has no position {pos_id}"
+        )))
+    } else if decoded.contains("NonMakerPosition") {
+        Some(BeaconError::InvalidState(format!(
+            "Maker position cannot be closed: position {pos_id} is a taker position"
+        )))
+    } else if decoded.contains("NotOwnerNorApproved") || decoded.contains("UnauthorizedCaller") {
+        Some(BeaconError::InvalidState(format!(
+            "Maker position cannot be closed: the sending wallet does not own position {pos_id}"
+        )))
+    } else {
+        None
+    }
+}
+
+/// Closes a maker position by removing all of its liquidity through `Perp.adjustMaker`.
+///
+/// The pool wallet holding the position NFT sends the close and receives the payout. v0.1.0
+/// emits no close event, so the returned USDC is summed from the receipt's USDC `Transfer`
+/// logs from the Perp to that wallet; realized PnL is the payout minus the position's margin.
+pub async fn close_maker_position(
     state: &AppState,
-    tx_hash: alloy::primitives::FixedBytes<32>,
-    label: &str,
-) -> Result<alloy::rpc::types::TransactionReceipt, String> {
-    let timeout_seconds = [15u64, 30u64, 60u64];
-    for (attempt, secs) in timeout_seconds.iter().enumerate() {
-        tracing::info!(
-            "{} receipt attempt {}/{} ({}s timeout)",
-            label,
-            attempt + 1,
-            timeout_seconds.len(),
-            secs
-        );
-        match timeout(
-            Duration::from_secs(*secs),
-            state
-                .provider
-                .read_provider
-                .get_transaction_receipt(tx_hash),
+    perp_address: Address,
+    pos_id: U256,
+) -> Result<CloseMakerPositionResponse, BeaconError> {
+    let result = close_maker_position_inner(state, perp_address, pos_id).await;
+    state
+        .history
+        .record_transaction(
+            GasOperation::MakerClose,
+            &[
+                ("perp_address", perp_address.to_string()),
+                ("maker_position_id", pos_id.to_string()),
+            ],
+            match &result {
+                Ok(response) => Ok(Some(response.transaction_hash.clone())),
+                Err(e) => Err(e.to_string()),
+            },
         )
+        .await;
+    result
+}
+
+async fn close_maker_position_inner(
+    state: &AppState,
+    perp_address: Address,
+    pos_id: U256,
+) -> Result<CloseMakerPositionResponse, BeaconError> {
+    tracing::info!("Closing maker position {} on Perp {}", pos_id, perp_address);
+
+    let retry = state.provider.read_retry;
+    let read_perp = &IPerp::new(perp_address, &state.provider.read_provider);
+    let owner = retry_read(&retry, "Perp.ownerOf", move || async move {
+        read_perp.ownerOf(pos_id).call().await
+    })
+    .await
+    .map_err(|e| {
+        let decoded = try_decode_revert_reason_with(&e, Some(state.error_registry.as_ref()));
+        decoded
+            .as_deref()
+            .and_then(|d| close_revert_error(pos_id, perp_address, d))
+            .unwrap_or_else(|| {
+                BeaconError::from_contract_error(
+                    format!("Failed to read owner of position {pos_id}: {e}"),
+                    &e,
+                    decoded,
+                )
+            })
+    })?;
+    if !state.wallets.manager.signer_addresses().contains(&owner) {
+        return Err(BeaconError::InvalidState(format!(
+            "Maker position cannot be closed: position {pos_id} is held by {owner}, not a pool wallet"
+        )));
+    }
+
+    let details = retry_read(&retry, "Perp.makerDetails", move || async move {
+        read_perp.makerDetails(pos_id).call().await
+    })
+    .await
+    .map_err(|e| {
+        BeaconError::from_contract_error(
+            format!("Failed to read maker details of position {pos_id}: {e}"),
+            &e,
+            None,
+        )
+    })?;
+    // Taker positions have zeroed maker details.
+    if details.liquidity == 0 {
+        return Err(BeaconError::InvalidState(format!(
+            "Maker position cannot be closed: position {pos_id} has no maker liquidity left"
+        )));
+    }
+    let liquidity = i128::try_from(details.liquidity).map_err(|_| {
+        BeaconError::Other(format!(
+            "Liquidity {} of position {pos_id} does not fit adjustMaker's int128",
+            details.liquidity
+        ))
+    })?;
+    let margin = retry_read(&retry, "Perp.positions", move || async move {
+        read_perp.positions(pos_id).call().await
+    })
+    .await
+    .map_err(|e| {
+        BeaconError::from_contract_error(format!("Failed to read position {pos_id}: {e}"), &e, None)
+    })?
+    .margin;
+
+    // The NFT holder must send the close, so lease that specific wallet.
+    let wallet_handle = state
+        .wallets
+        .manager
+        .acquire_specific_wallet(&owner)
         .await
-        {
-            Ok(Ok(Some(receipt))) => return Ok(receipt),
-            Ok(Ok(None)) => {
-                tokio::time::sleep(Duration::from_secs(5)).await;
-            }
-            Ok(Err(e)) => {
-                let msg = format!("Failed to query {label} receipt {tx_hash}: {e}");
-                tracing::error!("{}", msg);
-                return Err(msg);
+        .map_err(|e| BeaconError::WalletUnavailable(format!("Failed to acquire wallet: {e}")))?;
+    tracing::info!("Acquired wallet {} to close position {}", owner, pos_id);
+
+    let provider = wallet_handle
+        .build_provider(&state.provider.endpoints)
+        .map_err(|e| BeaconError::Other(format!("Failed to build provider: {e}")))?;
+    let perp = &IPerp::new(perp_address, &provider);
+    let adjust_params = &IPerp::AdjustMakerParams {
+        posId: pos_id,
+        marginDelta: 0,
+        liquidityDelta: -liquidity,
+        amt0Limit: U256::ZERO,
+        amt1Limit: U256::ZERO,
+    };
+
+    tracing::info!(
+        "Removing liquidity {} (margin {} USDC) from position {}",
+        liquidity,
+        format_usdc(U256::from(margin)),
+        pos_id
+    );
+    wallet_handle
+        .ensure_lock_held()
+        .map_err(BeaconError::WalletUnavailable)?;
+    let pending_tx = retry_once_on_nonce_error(
+        "adjustMaker",
+        move || async move {
+            perp.adjustMaker(adjust_params.clone())
+                .send()
+                .await
+                .map_err(|e| {
+                    let decoded =
+                        try_decode_revert_reason_with(&e, Some(state.error_registry.as_ref()));
+                    let error = match decoded.as_deref() {
+                        Some(reason) => close_revert_error(pos_id, perp_address, reason)
+                            .unwrap_or_else(|| BeaconError::ContractReverted {
+                                message: format!("adjustMaker reverted: {reason}"),
+                                decoded: decoded.clone(),
+                            }),
+                        None => BeaconError::from_send_error(
+                            format!("adjustMaker send failed: {e}"),
+                            &e,
+                            None,
+                        ),
+                    };
+                    tracing::error!("{}", error);
+                    error
+                })
+        },
+        || wallet_handle.resync_nonce(&provider),
+    )
+    .await?;
+
+    let close_tx_hash = *pending_tx.tx_hash();
+    tracing::info!("adjustMaker tx hash: {:?}", close_tx_hash);
+    let receipt = wait_for_receipt(
+        state,
+        pending_tx,
+        "adjustMaker",
+        &state
+            .provider
+            .receipt_wait
+            .with_initial_timeout(Duration::from_secs(90)),
+    )
+    .await?;
+
+    tracing::info!("adjustMaker confirmed: {:?}", receipt.transaction_hash);
+    state
+        .gas_metrics
+        .record(GasOperation::MakerClose, receipt.gas_used);
+
+    if !receipt.status() {
+        let revert_detail = resimulated_revert_reason(
+            perp.adjustMaker(adjust_params.clone()).call().await,
+            Some(state.error_registry.as_ref()),
+        );
+        let error = match close_revert_error(pos_id, perp_address, &revert_detail) {
+            Some(BeaconError::NotRegistered(message)) => {
+                BeaconError::NotRegistered(format!("{message} (tx {close_tx_hash})"))
             }
-            Err(_) => {
-                tracing::warn!("Timeout on attempt {}, retrying...", attempt + 1);
-                tokio::time::sleep(Duration::from_secs(5)).await;
+            Some(BeaconError::InvalidState(message)) => {
+                BeaconError::InvalidState(format!("{message} (tx {close_tx_hash})"))
             }
+            _ => BeaconError::ContractReverted {
+                message: format!(
+                    "adjustMaker transaction reverted: {revert_detail} (tx {close_tx_hash})"
+                ),
+                decoded: None,
+            },
+        };
+        tracing::error!("{}", error);
+        return Err(error);
+    }
+
+    let returned = sum_erc20_transfers(&receipt, state.contracts.load().usdc, perp_address, owner);
+    let margin = U256::from(margin);
+    let realized_pnl = if returned >= margin {
+        (returned - margin).to_string()
+    } else {
+        format!("-{}", margin - returned)
+    };
+    let confirmations = wait_for_confirmations(
+        &state.provider.read_provider,
+        &state.provider.confirmations,
+        &state.provider.read_retry,
+        close_tx_hash,
+        receipt.block_number.unwrap_or_default(),
+    )
+    .await
+    .map_err(BeaconError::Timeout)?;
+    tracing::info!(
+        "Maker position {} closed: returned {} USDC against {} USDC margin",
+        pos_id,
+        format_usdc(returned),
+        format_usdc(margin)
+    );
+
+    Ok(CloseMakerPositionResponse {
+        maker_position_id: pos_id.to_string(),
+        wallet_address: owner.to_string(),
+        transaction_hash: receipt.transaction_hash.to_string(),
+        gas_used: receipt.gas_used,
+        margin_usdc: margin.to_string(),
+        returned_usdc: returned.to_string(),
+        realized_pnl_usdc: realized_pnl,
+        confirmations,
+    })
+}
+
+/// Reads back a per-market Perp's metadata.
+///
+/// Fails with [`BeaconError::NotRegistered`] when `perp_address` is not registered with the
+/// configured PerpFactory — the address may still hold code, but it is not a market this
+/// deployment trusts. Read failures are classified by
+/// [`BeaconError::from_contract_error`].
+pub async fn get_perp_info(
+    state: &AppState,
+    perp_address: Address,
+) -> Result<PerpInfoResponse, BeaconError> {
+    let retry = state.provider.read_retry;
+    let factory = &IPerpFactory::new(
+        state.contracts.load().perp_factory,
+        &state.provider.read_provider,
+    );
+    let is_known_perp = retry_read(&retry, "PerpFactory.perps", move || async move {
+        factory.perps(perp_address).call().await
+    })
+    .await
+    .map_err(|e| {
+        BeaconError::from_contract_error(
+            format!("Failed to verify perp_address {perp_address} with factory: {e}"),
+            &e,
+            None,
+        )
+    })?;
+    if !is_known_perp {
+        return Err(BeaconError::NotRegistered(format!(
+            "Perp {perp_address} is not registered with PerpFactory {}",
+            state.contracts.load().perp_factory
+        )));
+    }
+
+    let perp = &IPerp::new(perp_address, &state.provider.read_provider);
+    let modules = retry_read(&retry, "Perp.modules", move || async move {
+        perp.modules().call().await
+    })
+    .await
+    .map_err(|e| {
+        BeaconError::from_contract_error(
+            format!("Failed to read modules for perp {perp_address}: {e}"),
+            &e,
+            None,
+        )
+    })?;
+    let pool_id = retry_read(&retry, "Perp.POOL_ID", move || async move {
+        perp.POOL_ID().call().await
+    })
+    .await
+    .map_err(|e| {
+        BeaconError::from_contract_error(
+            format!("Failed to read POOL_ID for perp {perp_address}: {e}"),
+            &e,
+            None,
+        )
+    })?;
+    let name = retry_read(&retry, "Perp.name", move || async move {
+        perp.name().call().await
+    })
+    .await
+    .map_err(|e| {
+        BeaconError::from_contract_error(
+            format!("Failed to read name for perp {perp_address}: {e}"),
+            &e,
+            None,
+        )
+    })?;
+    let symbol = retry_read(&retry, "Perp.symbol", move || async move {
+        perp.symbol().call().await
+    })
+    .await
+    .map_err(|e| {
+        BeaconError::from_contract_error(
+            format!("Failed to read symbol for perp {perp_address}: {e}"),
+            &e,
+            None,
+        )
+    })?;
+    let owner = retry_read(&retry, "Perp.owner", move || async move {
+        perp.owner().call().await
+    })
+    .await
+    .map_err(|e| {
+        BeaconError::from_contract_error(
+            format!("Failed to read owner for perp {perp_address}: {e}"),
+            &e,
+            None,
+        )
+    })?;
+
+    Ok(PerpInfoResponse {
+        perp_address: perp_address.to_string(),
+        beacon_address: modules.beacon.to_string(),
+        pool_id: format!("{pool_id:#x}"),
+        name,
+        symbol,
+        owner: owner.to_string(),
+        perp_factory_address: state.contracts.load().perp_factory.to_string(),
+    })
+}
+
+/// Reads the current state of a maker position.
+///
+/// Fails with [`BeaconError::NotRegistered`] when the Perp is not registered with the
+/// configured PerpFactory, when no position NFT has `pos_id`, or when the position is not a
+/// maker (taker positions have no maker liquidity).
+pub async fn get_maker_position(
+    state: &AppState,
+    perp_address: Address,
+    pos_id: U256,
+) -> Result<MakerInfoResponse, BeaconError> {
+    let retry = state.provider.read_retry;
+    let factory = &IPerpFactory::new(
+        state.contracts.load().perp_factory,
+        &state.provider.read_provider,
+    );
+    let is_known_perp = retry_read(&retry, "PerpFactory.perps", move || async move {
+        factory.perps(perp_address).call().await
+    })
+    .await
+    .map_err(|e| {
+        BeaconError::from_contract_error(
+            format!("Failed to verify perp_address {perp_address} with factory: {e}"),
+            &e,
+            None,
+        )
+    })?;
+    if !is_known_perp {
+        return Err(BeaconError::NotRegistered(format!(
+            "Perp {perp_address} is not registered with PerpFactory {}",
+            state.contracts.load().perp_factory
+        )));
+    }
+
+    let perp = &IPerp::new(perp_address, &state.provider.read_provider);
+    let owner = match retry_read(&retry, "Perp.ownerOf", move || async move {
+        perp.ownerOf(pos_id).call().await
+    })
+    .await
+    {
+        Ok(owner) => owner,
+        Err(e) => {
+            let decoded = try_decode_revert_reason_with(&e, Some(state.error_registry.as_ref()));
+            return Err(
+                match decoded
+                    .as_deref()
+                    .and_then(|d| close_revert_error(pos_id, perp_address, d))
+                {
+                    Some(rejection) => BeaconError::NotRegistered(rejection.to_string()),
+                    None => BeaconError::from_contract_error(
+                        format!("Failed to read owner of position {pos_id}: {e}"),
+                        &e,
+                        decoded,
+                    ),
+                },
+            );
         }
+    };
+    let details = retry_read(&retry, "Perp.makerDetails", move || async move {
+        perp.makerDetails(pos_id).call().await
+    })
+    .await
+    .map_err(|e| {
+        BeaconError::from_contract_error(
+            format!("Failed to read maker details of position {pos_id}: {e}"),
+            &e,
+            None,
+        )
+    })?;
+    if details.liquidity == 0 {
+        return Err(BeaconError::NotRegistered(format!(
+            "Position {pos_id} on Perp {perp_address} is not an open maker position"
+        )));
     }
-    let msg = format!("{label} receipt {tx_hash} not found after retries");
-    tracing::error!("{}", msg);
-    Err(msg)
+    let position = retry_read(&retry, "Perp.positions", move || async move {
+        perp.positions(pos_id).call().await
+    })
+    .await
+    .map_err(|e| {
+        BeaconError::from_contract_error(format!("Failed to read position {pos_id}: {e}"), &e, None)
+    })?;
+
+    Ok(MakerInfoResponse {
+        perp_address: perp_address.to_string(),
+        maker_position_id: pos_id.to_string(),
+        owner: owner.to_string(),
+        tick_lower: details.tickLower.as_i32(),
+        tick_upper: details.tickUpper.as_i32(),
+        liquidity: details.liquidity.to_string(),
+        margin_usdc: position.margin.to_string(),
+        perp_delta: position.delta.to_string(),
+        liquidation_margin_ratio: position.liqMarginRatio.to::<u32>(),
+        backstop_margin_ratio: position.backstopMarginRatio.to::<u32>(),
+        long_capacity: details.capacity_.long.to_string(),
+        short_capacity: details.capacity_.short.to_string(),
+        last_long_util_earnings: x96_to_decimal(details.lastLongUtilEarningsX96),
+        last_short_util_earnings: x96_to_decimal(details.lastShortUtilEarningsX96),
+        last_cuml_funding: signed_x96_to_decimal(position.lastCumlFundingX96),
+        funding_below: signed_x96_to_decimal(details.lastCumlFunding.belowX96),
+        funding_within: signed_x96_to_decimal(details.lastCumlFunding.withinX96),
+        funding_div_sqrt_price_within: signed_x96_to_decimal(
+            details.lastCumlFunding.divSqrtPriceWithinX96,
+        ),
+    })
 }
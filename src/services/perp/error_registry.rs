@@ -0,0 +1,153 @@
+use alloy::dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy::json_abi::{Error as AbiError, JsonAbi};
+use alloy::primitives::Selector;
+use std::collections::HashMap;
+
+use crate::abis::{AbiLoadError, load_abi, load_bundled_abis};
+
+/// One custom error declared in a contract ABI.
+#[derive(Debug, Clone)]
+pub struct KnownError {
+    pub name: String,
+    /// Canonical parameter types, e.g. `["address", "uint256"]`.
+    pub param_types: Vec<String>,
+    /// Contracts whose ABI declares this error.
+    pub contracts: Vec<String>,
+    abi: AbiError,
+}
+
+impl KnownError {
+    /// Solidity signature, e.g. `InsufficientBalance(address,uint256)`.
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, self.param_types.join(","))
+    }
+}
+
+/// 4-byte selector table of every `error` entry in the bundled contract ABIs.
+///
+/// Lets `ContractErrorDecoder` name and ABI-decode custom errors it has no hand-written
+/// case for, so errors added in a contract bump are readable as soon as the ABIs are
+/// refreshed.
+#[derive(Debug, Clone, Default)]
+pub struct ErrorSelectorRegistry {
+    errors: HashMap<Selector, KnownError>,
+}
+
+impl ErrorSelectorRegistry {
+    /// Build from the bundled ABIs (see [`crate::abis`]).
+    pub fn from_bundled_abis() -> Result<Self, Vec<AbiLoadError>> {
+        let mut registry = Self::default();
+        for (contract, abi) in load_bundled_abis()? {
+            registry.add_abi(contract, &abi);
+        }
+        Ok(registry)
+    }
+
+    /// Build from `(contract, json)` ABIs. Every ABI is tried, so the error lists each
+    /// one that fails to parse.
+    pub fn from_abis(abis: &[(&str, &str)]) -> Result<Self, Vec<AbiLoadError>> {
+        let mut registry = Self::default();
+        let mut errors = Vec::new();
+        for (contract, json) in abis {
+            match load_abi(contract, json) {
+                Ok(abi) => registry.add_abi(contract, &abi),
+                Err(e) => errors.push(e),
+            }
+        }
+        if errors.is_empty() {
+            Ok(registry)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Register every error declared in `abi` under `contract`.
+    pub fn add_abi(&mut self, contract: &str, abi: &JsonAbi) {
+        for error in abi.errors() {
+            let entry = self
+                .errors
+                .entry(error.selector())
+                .or_insert_with(|| KnownError {
+                    name: error.name.clone(),
+                    param_types: error
+                        .inputs
+                        .iter()
+                        .map(|p| p.selector_type().into_owned())
+                        .collect(),
+                    contracts: Vec::new(),
+                    abi: error.clone(),
+                });
+            if !entry.contracts.iter().any(|c| c == contract) {
+                entry.contracts.push(contract.to_string());
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn lookup(&self, selector: Selector) -> Option<&KnownError> {
+        self.errors.get(&selector)
+    }
+
+    /// Decode `0x`-prefixed revert data into `Name(arg, ...)` if its selector is known.
+    ///
+    /// Returns `None` for unknown selectors, malformed hex, or parameters that fail to decode.
+    pub fn decode(&self, error_data: &str) -> Option<String> {
+        let bytes = hex::decode(error_data.strip_prefix("0x").unwrap_or(error_data)).ok()?;
+        if bytes.len() < 4 {
+            return None;
+        }
+        let known = self.lookup(Selector::from_slice(&bytes[..4]))?;
+        let values = known.abi.abi_decode_input(&bytes[4..]).ok()?;
+
+        let args: Vec<String> = known
+            .abi
+            .inputs
+            .iter()
+            .zip(&values)
+            .map(|(param, value)| {
+                let value = format_value(value);
+                if param.name.is_empty() {
+                    value
+                } else {
+                    format!("{}: {value}", param.name)
+                }
+            })
+            .collect();
+        Some(format!("{}({})", known.name, args.join(", ")))
+    }
+}
+
+/// Render a decoded ABI value for an error message.
+fn format_value(value: &DynSolValue) -> String {
+    if let Some(address) = value.as_address() {
+        address.to_checksum(None)
+    } else if let Some((uint, _)) = value.as_uint() {
+        uint.to_string()
+    } else if let Some((int, _)) = value.as_int() {
+        int.to_string()
+    } else if let Some(flag) = value.as_bool() {
+        flag.to_string()
+    } else if let Some(text) = value.as_str() {
+        format!("{text:?}")
+    } else if let Some(bytes) = value.as_bytes() {
+        format!("0x{}", hex::encode(bytes))
+    } else if let Some((word, size)) = value.as_fixed_bytes() {
+        format!("0x{}", hex::encode(&word[..size]))
+    } else if let Some(items) = value
+        .as_array()
+        .or_else(|| value.as_fixed_array())
+        .or_else(|| value.as_tuple())
+    {
+        let inner: Vec<String> = items.iter().map(format_value).collect();
+        format!("[{}]", inner.join(", "))
+    } else {
+        format!("{value:?}")
+    }
+}
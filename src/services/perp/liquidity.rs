@@ -0,0 +1,294 @@
+use alloy::primitives::ruint::UintTryFrom;
+use alloy::primitives::{I256, U256, U512};
+use std::str::FromStr;
+
+use super::validation::{MAX_TICK, MIN_TICK};
+
+/// `TickMath.getSqrtPriceAtTick` multipliers: `(bit of |tick|, Q128 factor)`.
+const SQRT_PRICE_FACTORS: [(u32, u128); 19] = [
+    (0x2, 0xfff97272373d413259a46990580e213a),
+    (0x4, 0xfff2e50f5f656932ef12357cf3c7fdcc),
+    (0x8, 0xffe5caca7e10e4e61c3624eaa0941cd0),
+    (0x10, 0xffcb9843d60f6159c9db58835c926644),
+    (0x20, 0xff973b41fa98c081472e6896dfb254c0),
+    (0x40, 0xff2ea16466c96a3843ec78b326b52861),
+    (0x80, 0xfe5dee046a99a2a811c461f1969c3053),
+    (0x100, 0xfcbe86c7900a88aedcffc83b479aa3a4),
+    (0x200, 0xf987a7253ac413176f2b074cf7815e54),
+    (0x400, 0xf3392b0822b70005940c7a398e4b70f3),
+    (0x800, 0xe7159475a2c29b7443b29c7fa6e889d9),
+    (0x1000, 0xd097f3bdfd2022b8845ad8f792aa5825),
+    (0x2000, 0xa9f746462d870fdf8a65dc1f90e061e5),
+    (0x4000, 0x70d869a156d2a1b890bb3df62baf32f7),
+    (0x8000, 0x31be135f97d08fd981231505542fcfa6),
+    (0x10000, 0x9aa508b5b7a84e1c677de54f3e99bc9),
+    (0x20000, 0x5d6af8dedb81196699c329225ee604),
+    (0x40000, 0x2216e584f5fa1ea926041bedfe98),
+    (0x80000, 0x48a170391f7dc42444e8fa2),
+];
+
+/// Exact port of Uniswap's `TickMath.getSqrtPriceAtTick`: `sqrt(1.0001^tick) * 2^96`.
+pub fn tick_to_sqrt_price_x96(tick: i32) -> Result<U256, String> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return Err(format!("tick ({tick}) is outside [{MIN_TICK}, {MAX_TICK}]"));
+    }
+
+    let abs_tick = tick.unsigned_abs();
+    let mut ratio = if abs_tick & 0x1 != 0 {
+        U256::from(0xfffcb933bd6fad37aa2d162d1a594001u128)
+    } else {
+        U256::from(1u8) << 128
+    };
+    for (bit, factor) in SQRT_PRICE_FACTORS {
+        if abs_tick & bit != 0 {
+            ratio = (ratio * U256::from(factor)) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    // Q128.128 -> Q64.96, rounding up so the result is never below the true price.
+    let round_up = if ratio % (U256::from(1u8) << 32) == U256::ZERO {
+        U256::ZERO
+    } else {
+        U256::from(1u8)
+    };
+    Ok((ratio >> 32) + round_up)
+}
+
+/// Inverse of [`tick_to_sqrt_price_x96`] (Uniswap's `TickMath.getTickAtSqrtPrice`): the
+/// greatest tick whose sqrt price is at most `sqrt_price_x96`.
+///
+/// Accepts `[sqrt price at MIN_TICK, sqrt price at MAX_TICK)`, the same range as Uniswap.
+/// Found by binary search over the tick range, so it is exact by construction.
+pub fn sqrt_price_x96_to_tick(sqrt_price_x96: U256) -> Result<i32, String> {
+    let min_sqrt_price = tick_to_sqrt_price_x96(MIN_TICK)?;
+    let max_sqrt_price = tick_to_sqrt_price_x96(MAX_TICK)?;
+    if sqrt_price_x96 < min_sqrt_price || sqrt_price_x96 >= max_sqrt_price {
+        return Err(format!(
+            "sqrt_price_x96 ({sqrt_price_x96}) is outside [{min_sqrt_price}, {max_sqrt_price})"
+        ));
+    }
+
+    // Invariant: price(low) <= sqrt_price_x96 < price(high).
+    let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if tick_to_sqrt_price_x96(mid)? <= sqrt_price_x96 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+/// Converts a human price (token1 per token0) to a Q64.96 sqrt price.
+///
+/// Computed in floating point, which is far finer than one tick (a 0.01% price step).
+pub fn price_to_sqrt_price_x96(price: f64) -> Result<U256, String> {
+    if !price.is_finite() || price <= 0.0 {
+        return Err(format!("price ({price}) must be a positive number"));
+    }
+    let sqrt_price_x96 = price.sqrt() * 2f64.powi(96);
+    U256::from_str(&format!("{sqrt_price_x96:.0}"))
+        .map_err(|e| format!("price ({price}) is out of range: {e}"))
+}
+
+/// Rounds `tick` to the nearest multiple of `tick_spacing` (halves round up), kept within
+/// the aligned ticks inside [`MIN_TICK`, `MAX_TICK`].
+pub fn snap_tick_to_spacing(tick: i32, tick_spacing: i32) -> Result<i32, String> {
+    if tick_spacing <= 0 {
+        return Err(format!(
+            "tick_spacing ({tick_spacing}) must be greater than zero"
+        ));
+    }
+    let remainder = tick.rem_euclid(tick_spacing);
+    let snapped = if remainder * 2 >= tick_spacing {
+        tick - remainder + tick_spacing
+    } else {
+        tick - remainder
+    };
+    let min_usable = (MIN_TICK / tick_spacing) * tick_spacing;
+    let max_usable = (MAX_TICK / tick_spacing) * tick_spacing;
+    Ok(snapped.clamp(min_usable, max_usable))
+}
+
+/// Tick range for a maker position between two human prices, snapped to `tick_spacing`.
+///
+/// Fails if the prices are out of order or outside the representable range, or if the
+/// range is narrower than one tick spacing so both bounds snap to the same tick.
+pub fn ticks_for_price_range(
+    price_lower: f64,
+    price_upper: f64,
+    tick_spacing: i32,
+) -> Result<(i32, i32), String> {
+    if price_lower >= price_upper {
+        return Err(format!(
+            "price_lower ({price_lower}) must be less than price_upper ({price_upper})"
+        ));
+    }
+    let tick_lower = sqrt_price_x96_to_tick(price_to_sqrt_price_x96(price_lower)?)?;
+    let tick_upper = sqrt_price_x96_to_tick(price_to_sqrt_price_x96(price_upper)?)?;
+    let tick_lower = snap_tick_to_spacing(tick_lower, tick_spacing)?;
+    let tick_upper = snap_tick_to_spacing(tick_upper, tick_spacing)?;
+    if tick_lower >= tick_upper {
+        return Err(format!(
+            "price range [{price_lower}, {price_upper}] rounds to an empty tick span at \
+             tick_spacing {tick_spacing} (both bounds snap to tick {tick_lower})"
+        ));
+    }
+    Ok((tick_lower, tick_upper))
+}
+
+/// Price (token1 per token0) in Q96 from a pool's `sqrtPriceX96`: `sqrt_price_x96^2 / 2^96`,
+/// rounded down. A `uint160` sqrt price squares to under 2^320, so the result fits a U256.
+pub fn sqrt_price_x96_to_price_x96(sqrt_price_x96: U256) -> U256 {
+    let squared: U512 = sqrt_price_x96.widening_mul(sqrt_price_x96);
+    (squared >> 96usize).saturating_to::<U256>()
+}
+
+/// Decimal places kept by [`x96_to_decimal`].
+pub const X96_DECIMALS: usize = 18;
+
+/// Renders a Q96 fixed-point value (`value / 2^96`) as a decimal string with up to
+/// [`X96_DECIMALS`] places, truncated and without trailing zeros.
+pub fn x96_to_decimal(value: U256) -> String {
+    let integer = value >> 96usize;
+    let fraction = value - (integer << 96);
+    // fraction < 2^96, so the product stays far below 2^256.
+    let scaled = (fraction * U256::from(10u8).pow(U256::from(X96_DECIMALS))) >> 96;
+    let digits = format!("{scaled:0>width$}", width = X96_DECIMALS);
+    let digits = digits.trim_end_matches('0');
+    if digits.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{integer}.{digits}")
+    }
+}
+
+/// Signed [`x96_to_decimal`], for `int256` X96 accumulators.
+pub fn signed_x96_to_decimal(value: I256) -> String {
+    let magnitude = x96_to_decimal(value.unsigned_abs());
+    if value.is_negative() && magnitude != "0" {
+        format!("-{magnitude}")
+    } else {
+        magnitude
+    }
+}
+
+/// `a * b / denominator` with a full-width intermediate product, rounded down
+/// (Uniswap's `FullMath.mulDiv`).
+fn mul_div(a: U256, b: U256, denominator: U256) -> Result<U256, String> {
+    if denominator.is_zero() {
+        return Err("liquidity range is empty".to_string());
+    }
+    let product: U512 = a.widening_mul(b);
+    U256::uint_try_from(product / U512::from(denominator))
+        .map_err(|_| "liquidity calculation overflow".to_string())
+}
+
+/// Orders two sqrt prices as `(lower, upper)`.
+fn sorted(sqrt_price_a_x96: U256, sqrt_price_b_x96: U256) -> (U256, U256) {
+    if sqrt_price_a_x96 <= sqrt_price_b_x96 {
+        (sqrt_price_a_x96, sqrt_price_b_x96)
+    } else {
+        (sqrt_price_b_x96, sqrt_price_a_x96)
+    }
+}
+
+/// Uniswap's `LiquidityAmounts.getLiquidityForAmount0`: the liquidity `amount0` buys over
+/// the price range `[sqrt_price_a_x96, sqrt_price_b_x96]` (in either order), rounded down.
+pub fn liquidity_for_amount0(
+    sqrt_price_a_x96: U256,
+    sqrt_price_b_x96: U256,
+    amount0: U256,
+) -> Result<U256, String> {
+    let (lower, upper) = sorted(sqrt_price_a_x96, sqrt_price_b_x96);
+    let intermediate = mul_div(lower, upper, U256::from(1u8) << 96)?;
+    mul_div(amount0, intermediate, upper - lower)
+}
+
+/// Uniswap's `LiquidityAmounts.getLiquidityForAmount1`: the liquidity `amount1` buys over
+/// the price range `[sqrt_price_a_x96, sqrt_price_b_x96]` (in either order), rounded down.
+pub fn liquidity_for_amount1(
+    sqrt_price_a_x96: U256,
+    sqrt_price_b_x96: U256,
+    amount1: U256,
+) -> Result<U256, String> {
+    let (lower, upper) = sorted(sqrt_price_a_x96, sqrt_price_b_x96);
+    mul_div(amount1, U256::from(1u8) << 96, upper - lower)
+}
+
+/// Uniswap's `LiquidityAmounts.getLiquidityForAmounts`: the most liquidity `amount0` and
+/// `amount1` can fund over the range at the current `sqrt_price_x96`.
+///
+/// Below the range only token0 is used, above it only token1; inside it the scarcer token
+/// sets the limit.
+pub fn liquidity_for_amounts(
+    sqrt_price_x96: U256,
+    sqrt_price_a_x96: U256,
+    sqrt_price_b_x96: U256,
+    amount0: U256,
+    amount1: U256,
+) -> Result<U256, String> {
+    let (lower, upper) = sorted(sqrt_price_a_x96, sqrt_price_b_x96);
+    if sqrt_price_x96 <= lower {
+        liquidity_for_amount0(lower, upper, amount0)
+    } else if sqrt_price_x96 < upper {
+        let liquidity0 = liquidity_for_amount0(sqrt_price_x96, upper, amount0)?;
+        let liquidity1 = liquidity_for_amount1(lower, sqrt_price_x96, amount1)?;
+        Ok(liquidity0.min(liquidity1))
+    } else {
+        liquidity_for_amount1(lower, upper, amount1)
+    }
+}
+
+/// Valid `openMaker` liquidity for a pool with `tick_spacing`: `(min, max)` inclusive.
+///
+/// The minimum is one unit (zero liquidity reverts); the maximum is the pool's per-tick
+/// cap (`Pool.tickSpacingToMaxLiquidityPerTick`), above which the deposit would revert
+/// with `TickLiquidityOverflow`.
+pub fn calculate_liquidity_bounds(tick_spacing: i32) -> Result<(u128, u128), String> {
+    if tick_spacing <= 0 {
+        return Err(format!(
+            "tick_spacing ({tick_spacing}) must be greater than zero"
+        ));
+    }
+    let min_tick = (MIN_TICK / tick_spacing) * tick_spacing;
+    let max_tick = (MAX_TICK / tick_spacing) * tick_spacing;
+    let num_ticks = ((max_tick - min_tick) / tick_spacing) as u128 + 1;
+    Ok((1, u128::MAX / num_ticks))
+}
+
+/// Liquidity for a maker position funded by `margin_amount_usdc` (6 decimals) over
+/// `[tick_lower, tick_upper)`, treating the margin as the position's token1 amount.
+///
+/// Rejects results outside [`calculate_liquidity_bounds`] for `tick_spacing`, so a margin
+/// too small for the range (liquidity rounds to zero) fails here rather than on-chain.
+pub fn calculate_liquidity_from_margin(
+    margin_amount_usdc: u128,
+    tick_spacing: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<u128, String> {
+    let sqrt_lower = tick_to_sqrt_price_x96(tick_lower)?;
+    let sqrt_upper = tick_to_sqrt_price_x96(tick_upper)?;
+    let liquidity = liquidity_for_amount1(sqrt_lower, sqrt_upper, U256::from(margin_amount_usdc))?;
+
+    let (min, max) = calculate_liquidity_bounds(tick_spacing)?;
+    if liquidity < U256::from(min) {
+        return Err(format!(
+            "margin {margin_amount_usdc} is too small for tick range [{tick_lower}, {tick_upper}]: \
+             liquidity rounds to zero"
+        ));
+    }
+    if liquidity > U256::from(max) {
+        return Err(format!(
+            "liquidity {liquidity} for margin {margin_amount_usdc} exceeds the per-tick maximum \
+             {max} for tick_spacing {tick_spacing}"
+        ));
+    }
+    Ok(liquidity.to::<u128>())
+}
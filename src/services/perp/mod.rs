@@ -1,5 +1,14 @@
+pub mod allowance;
+pub mod cadence;
 pub mod core;
+pub mod error_registry;
+pub mod liquidity;
+pub mod params;
+pub mod slippage;
 pub mod validation;
 
+pub use allowance::AllowanceCache;
 pub use core::*;
+pub use error_registry::{ErrorSelectorRegistry, KnownError};
+pub use params::*;
 pub use validation::*;
@@ -0,0 +1,107 @@
+//! Transaction parameters for `PerpFactory.createPerp` and `Perp.openMaker`.
+//!
+//! Pure builders shared by the deploy and deposit flows, gas estimation, and the deposit
+//! routes, so the uint24/int24 conversions and tick defaults live in one place and can be
+//! tested without a network.
+
+use alloy::primitives::{Address, FixedBytes, Signed, U256, Uint};
+
+use crate::models::{ContractAddresses, PerpConfig};
+use crate::routes::{IPerp, IPerpFactory};
+
+/// Largest value of a Solidity `uint24` (`emaWindow`).
+pub const UINT24_MAX: u32 = 0xFF_FFFF;
+
+/// A deposit's `(tick_spacing, tick_lower, tick_upper)`, taking each value omitted from the
+/// request from `config`.
+pub fn resolve_deposit_ticks(
+    config: &PerpConfig,
+    tick_spacing: Option<i32>,
+    tick_lower: Option<i32>,
+    tick_upper: Option<i32>,
+) -> (i32, i32, i32) {
+    (
+        tick_spacing.unwrap_or(config.tick_spacing),
+        tick_lower.unwrap_or(config.tick_lower),
+        tick_upper.unwrap_or(config.tick_upper),
+    )
+}
+
+/// Convert `ema_window` to the `uint24` createPerp takes, rejecting 0
+/// (`EmaWindowTooLow` on-chain) and values that do not fit.
+pub fn ema_window_u24(ema_window: u32) -> Result<Uint<24, 1>, String> {
+    if ema_window == 0 {
+        return Err("ema_window must be > 0 (uint24)".to_string());
+    }
+    if ema_window > UINT24_MAX {
+        return Err(format!(
+            "ema_window {ema_window} exceeds uint24 max ({UINT24_MAX})"
+        ));
+    }
+    Ok(Uint::<24, 1>::from(ema_window))
+}
+
+/// Convert a tick to the `int24` openMaker takes. `label` names the field in the error.
+pub fn tick_i24(label: &str, tick: i32) -> Result<Signed<24, 1>, String> {
+    Signed::<24, 1>::try_from(tick).map_err(|e| format!("Invalid {label} {tick}: {e}"))
+}
+
+/// The server-configured createPerp modules for a market on `beacon_address`.
+pub fn perp_modules(
+    contracts: &ContractAddresses,
+    beacon_address: Address,
+) -> IPerpFactory::Modules {
+    IPerpFactory::Modules {
+        beacon: beacon_address,
+        fees: contracts.fees_module,
+        funding: contracts.funding_module,
+        marginRatios: contracts.margin_ratios_module,
+        priceImpact: contracts.price_impact_module,
+        pricing: contracts.pricing_module,
+    }
+}
+
+/// Build the `PerpFactory.createPerp` call for a market on `beacon_address`, using the
+/// server-configured modules.
+#[allow(clippy::too_many_arguments)]
+pub fn build_create_perp_call(
+    contracts: &ContractAddresses,
+    beacon_address: Address,
+    owner: Address,
+    name: String,
+    symbol: String,
+    token_uri: String,
+    ema_window: u32,
+    salt: FixedBytes<32>,
+) -> Result<IPerpFactory::createPerpCall, String> {
+    Ok(IPerpFactory::createPerpCall {
+        owner,
+        name,
+        symbol,
+        tokenUri: token_uri,
+        modules: perp_modules(contracts, beacon_address),
+        emaWindow: ema_window_u24(ema_window)?,
+        salt,
+    })
+}
+
+/// Assemble `Perp.openMaker` params once liquidity and the slippage limits are known.
+pub fn open_maker_params(
+    holder: Address,
+    margin_amount_usdc: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    max_amt0_in: U256,
+    max_amt1_in: U256,
+) -> Result<IPerp::OpenMakerParams, String> {
+    Ok(IPerp::OpenMakerParams {
+        holder,
+        margin: margin_amount_usdc,
+        tickLower: tick_i24("tick lower", tick_lower)?,
+        tickUpper: tick_i24("tick upper", tick_upper)?,
+        liquidity,
+        maxAmt0In: max_amt0_in,
+        maxAmt1In: max_amt1_in,
+    })
+}
@@ -0,0 +1,76 @@
+use alloy::primitives::U256;
+use std::str::FromStr;
+
+/// Buffer over the estimated token amounts used when a deposit request omits
+/// `max_amt0_in` / `max_amt1_in` (100 bps = 1%).
+pub const DEFAULT_DEPOSIT_SLIPPAGE_BPS: u32 = 100;
+
+/// Largest accepted `DEPOSIT_SLIPPAGE_BPS` (a 100% buffer).
+pub const MAX_DEPOSIT_SLIPPAGE_BPS: u32 = 10_000;
+
+/// Prefix of the error returned when `openMaker` reverts with `MaxAmtExceeded`.
+pub const SLIPPAGE_EXCEEDED: &str = "Slippage exceeded";
+
+/// Relative headroom added to every estimate so floating-point rounding alone can
+/// never trip `MaxAmtExceeded`, even with a zero buffer.
+const ESTIMATE_TOLERANCE: f64 = 1e-9;
+
+/// Deposit slippage buffer in basis points, read from `DEPOSIT_SLIPPAGE_BPS`.
+///
+/// Falls back to [`DEFAULT_DEPOSIT_SLIPPAGE_BPS`] when unset or unparseable; values
+/// above [`MAX_DEPOSIT_SLIPPAGE_BPS`] are clamped.
+pub fn deposit_slippage_bps_from_env() -> u32 {
+    std::env::var("DEPOSIT_SLIPPAGE_BPS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .unwrap_or(DEFAULT_DEPOSIT_SLIPPAGE_BPS)
+        .min(MAX_DEPOSIT_SLIPPAGE_BPS)
+}
+
+/// Estimates the `(token0, token1)` amounts a maker position of `liquidity` over
+/// `[tick_lower, tick_upper)` pulls in at the pool's current `sqrt_price_x96`.
+///
+/// Standard concentrated-liquidity math: below the range the position is all token0,
+/// above it all token1, in between a mix. Computed in floating point; it only has to
+/// be close enough to put a slippage buffer on.
+pub fn estimate_maker_amounts(
+    sqrt_price_x96: U256,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+) -> (f64, f64) {
+    let sqrt_price = f64::from_str(&sqrt_price_x96.to_string()).unwrap_or(0.0) / 2f64.powi(96);
+    let sqrt_lower = 1.0001f64.powf(tick_lower as f64 / 2.0);
+    let sqrt_upper = 1.0001f64.powf(tick_upper as f64 / 2.0);
+    let liquidity = liquidity as f64;
+
+    let sqrt_current = sqrt_price.clamp(sqrt_lower, sqrt_upper);
+    let amount0 = liquidity * (sqrt_upper - sqrt_current) / (sqrt_current * sqrt_upper);
+    let amount1 = liquidity * (sqrt_current - sqrt_lower);
+    (amount0, amount1)
+}
+
+/// Upper bound for an `openMaker` amount: `estimate` plus `slippage_bps`, rounded up.
+///
+/// Saturates at `U256::MAX` for estimates beyond `u128`.
+pub fn max_amount_in(estimate: f64, slippage_bps: u32) -> U256 {
+    let buffered =
+        (estimate * (1.0 + slippage_bps as f64 / 10_000.0 + ESTIMATE_TOLERANCE)).ceil() + 1.0;
+    if !buffered.is_finite() || buffered >= u128::MAX as f64 {
+        return U256::MAX;
+    }
+    U256::from(buffered.max(0.0) as u128)
+}
+
+/// Whether a decoded `openMaker` revert is the contract's slippage check.
+pub fn is_max_amt_exceeded(revert_detail: &str) -> bool {
+    revert_detail.contains("MaxAmtExceeded")
+}
+
+/// The error returned to callers when `openMaker` exceeded the requested maxima.
+pub fn slippage_exceeded_message(max_amt0_in: U256, max_amt1_in: U256) -> String {
+    format!(
+        "{SLIPPAGE_EXCEEDED}: the pool price moved and openMaker needed more than \
+         max_amt0_in={max_amt0_in} / max_amt1_in={max_amt1_in}; retry, or pass wider limits"
+    )
+}
@@ -1,7 +1,8 @@
-use alloy::primitives::Address;
+use alloy::primitives::{Address, U256};
 use alloy::providers::Provider;
 use std::sync::Arc;
 
+use super::error_registry::ErrorSelectorRegistry;
 use crate::ReadOnlyProvider;
 
 /// Decodes 4-byte error selectors emitted by perpcity-contracts@v0.1.0 (`Perp.sol`,
@@ -9,6 +10,9 @@ use crate::ReadOnlyProvider;
 ///
 /// Selectors are derived from the v0.1.0 contracts via `cast sig "<ErrorName>()"` (and similar
 /// for parameterized errors). Update this list whenever the pinned contracts version bumps.
+/// The ABI-standard `Error(string)` and `Panic(uint256)` reverts are decoded as well. Selectors
+/// without a case here can still be decoded from the bundled ABIs via
+/// [`ContractErrorDecoder::decode_error_data_with`].
 pub struct ContractErrorDecoder;
 
 impl ContractErrorDecoder {
@@ -46,9 +50,37 @@ impl ContractErrorDecoder {
     // From src/interfaces/IProtocolFeeManager.sol@v0.1.0.
     const PROTOCOL_FEE_TOO_HIGH: &'static str = "0x499fddb1";
 
+    // Solady ERC721, inherited by Perp for its position NFTs — both parameterless.
+    const TOKEN_DOES_NOT_EXIST: &'static str = "0xceea21b6";
+    const NOT_OWNER_NOR_APPROVED: &'static str = "0x4b6e7f18";
+
+    // Beacon verifiers (`IBeacon.update` -> `verifier.verify`), both parameterless.
+    const PROOF_ALREADY_USED: &'static str = "0xc9838a65";
+    const INVALID_PROOF: &'static str = "0x09bde339";
+
     // Solady SafeCastLib — has parameter (the offending uint).
     const SAFECAST_OVERFLOW: &'static str = "0x24775e06";
 
+    // ABI-standard reverts emitted by the compiler: `require(cond, "msg")` / `revert("msg")`,
+    // and checked-arithmetic / assert failures.
+    const ERROR_STRING: &'static str = "0x08c379a0";
+    const PANIC: &'static str = "0x4e487b71";
+
+    /// Like [`Self::decode_error_data`], but falls back to the ABI error registry for
+    /// selectors the hand-written table does not know.
+    pub fn decode_error_data_with(
+        error_data: &str,
+        registry: Option<&ErrorSelectorRegistry>,
+    ) -> Option<String> {
+        let decoded = Self::decode_error_data(error_data)?;
+        if decoded.starts_with("Unknown contract error")
+            && let Some(abi_decoded) = registry.and_then(|r| r.decode(error_data))
+        {
+            return Some(abi_decoded);
+        }
+        Some(decoded)
+    }
+
     pub fn decode_error_data(error_data: &str) -> Option<String> {
         if error_data.len() < 10 {
             return None;
@@ -146,7 +178,24 @@ impl ContractErrorDecoder {
                 "ProtocolFeeTooHigh: requested protocol fee exceeds the configured maximum"
                     .to_string(),
             ),
+            Self::TOKEN_DOES_NOT_EXIST => {
+                Some("TokenDoesNotExist: no position NFT with this id".to_string())
+            }
+            Self::NOT_OWNER_NOR_APPROVED => Some(
+                "NotOwnerNorApproved: caller neither owns nor is approved for the position"
+                    .to_string(),
+            ),
+            Self::PROOF_ALREADY_USED => Some(
+                "ProofAlreadyUsed: this proof has already been submitted to the verifier"
+                    .to_string(),
+            ),
+            Self::INVALID_PROOF => Some(
+                "InvalidProof: proof does not verify against the supplied public signals"
+                    .to_string(),
+            ),
             Self::SAFECAST_OVERFLOW => Self::decode_safecast_overflow(params_data),
+            Self::ERROR_STRING => Self::decode_error_string(params_data),
+            Self::PANIC => Self::decode_panic(params_data),
             _ => Some(format!("Unknown contract error: {selector}")),
         }
     }
@@ -163,6 +212,135 @@ impl ContractErrorDecoder {
             "SafeCastOverflowedUintToInt: value {value} overflows when casting to int"
         ))
     }
+
+    /// ABI-decode `Error(string)`: a head word holding the string's offset, then at that
+    /// offset a length word followed by the UTF-8 bytes.
+    fn decode_error_string(params_data: &str) -> Option<String> {
+        let bytes = hex::decode(params_data).ok()?;
+        let offset = Self::abi_word_as_usize(bytes.get(0..32)?)?;
+        let len = Self::abi_word_as_usize(bytes.get(offset..offset.checked_add(32)?)?)?;
+        let start = offset + 32;
+        let data = bytes.get(start..start.checked_add(len)?)?;
+        let reason = String::from_utf8_lossy(data);
+
+        Some(format!("Revert reason: {reason}"))
+    }
+
+    /// Decode `Panic(uint256)`, naming the compiler-defined panic codes.
+    fn decode_panic(params_data: &str) -> Option<String> {
+        if params_data.len() < 64 {
+            return None;
+        }
+        let code_hex = params_data[0..64].trim_start_matches('0');
+        let code = if code_hex.is_empty() {
+            0
+        } else if code_hex.len() <= 16 {
+            u64::from_str_radix(code_hex, 16).ok()?
+        } else {
+            return Some(format!("Panic(0x{code_hex}): unknown panic code"));
+        };
+
+        let description = match code {
+            0x00 => "generic compiler panic",
+            0x01 => "assertion failed",
+            0x11 => "arithmetic overflow or underflow",
+            0x12 => "division or modulo by zero",
+            0x21 => "invalid enum value",
+            0x22 => "incorrectly encoded storage byte array",
+            0x31 => "pop() on an empty array",
+            0x32 => "array index out of bounds",
+            0x41 => "out of memory (allocation too large)",
+            0x51 => "call to a zero-initialized internal function",
+            _ => "unknown panic code",
+        };
+
+        Some(format!("Panic(0x{code:02x}): {description}"))
+    }
+
+    /// Read a 32-byte ABI word as a `usize`, rejecting values that do not fit.
+    fn abi_word_as_usize(word: &[u8]) -> Option<usize> {
+        let (high, low) = word.split_at(24);
+        if high.iter().any(|&b| b != 0) {
+            return None;
+        }
+        usize::try_from(u64::from_be_bytes(low.try_into().ok()?)).ok()
+    }
+}
+
+/// Lowest tick representable by Uniswap v4 `TickMath` (log base 1.0001 of 2^-128).
+pub const MIN_TICK: i32 = -887272;
+/// Highest tick representable by Uniswap v4 `TickMath`.
+pub const MAX_TICK: i32 = 887272;
+
+/// Tick spacing used when a deposit request omits `tick_spacing`.
+pub const DEFAULT_TICK_SPACING: i32 = 30;
+/// Lower tick used when a deposit request omits `tick_lower`.
+pub const DEFAULT_TICK_LOWER: i32 = 24390;
+/// Upper tick used when a deposit request omits `tick_upper`.
+pub const DEFAULT_TICK_UPPER: i32 = 53850;
+
+/// Validates a maker position's tick range before any chain interaction.
+///
+/// Both bounds must lie within [`MIN_TICK`, `MAX_TICK`], be aligned to `tick_spacing`, and
+/// satisfy `tick_lower < tick_upper`. A non-positive spacing is rejected up front so the
+/// alignment check never divides by zero.
+pub fn validate_tick_range(
+    tick_spacing: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<(), String> {
+    if tick_spacing <= 0 {
+        return Err(format!(
+            "tick_spacing ({tick_spacing}) must be greater than zero"
+        ));
+    }
+    if tick_lower >= tick_upper {
+        return Err(format!(
+            "tick_lower ({tick_lower}) must be less than tick_upper ({tick_upper})"
+        ));
+    }
+    if tick_lower < MIN_TICK {
+        return Err(format!(
+            "tick_lower ({tick_lower}) is below MIN_TICK ({MIN_TICK})"
+        ));
+    }
+    if tick_upper > MAX_TICK {
+        return Err(format!(
+            "tick_upper ({tick_upper}) is above MAX_TICK ({MAX_TICK})"
+        ));
+    }
+    if tick_lower % tick_spacing != 0 {
+        return Err(format!(
+            "tick_lower ({tick_lower}) must be divisible by tick_spacing ({tick_spacing})"
+        ));
+    }
+    if tick_upper % tick_spacing != 0 {
+        return Err(format!(
+            "tick_upper ({tick_upper}) must be divisible by tick_spacing ({tick_spacing})"
+        ));
+    }
+    Ok(())
+}
+
+/// Formats a raw 6-decimal USDC amount, e.g. `1500000` -> `"1.500000"`.
+pub fn format_usdc(amount: U256) -> String {
+    let scale = U256::from(1_000_000u64);
+    format!("{}.{:06}", amount / scale, (amount % scale).to::<u64>())
+}
+
+/// Checks a wallet's USDC balance covers a deposit's margin before anything is sent.
+///
+/// Without this the shortfall only surfaces as a `safeTransferFrom` revert inside
+/// `openMaker`, after an approval has already been paid for.
+pub fn validate_usdc_balance(balance: U256, required: U256) -> Result<(), String> {
+    if balance < required {
+        return Err(format!(
+            "insufficient USDC balance: have {} USDC, need {} USDC",
+            format_usdc(balance),
+            format_usdc(required)
+        ));
+    }
+    Ok(())
 }
 
 /// Validates that a module address has deployed bytecode (i.e. is actually a contract).
@@ -207,6 +385,28 @@ pub async fn validate_module_address(
 /// first one, since revert data is typically longer than the 20-byte address that might appear
 /// earlier in the message.
 pub fn try_decode_revert_reason(error: &impl std::fmt::Display) -> Option<String> {
+    try_decode_revert_reason_with(error, None)
+}
+
+/// Revert reason of a mined-but-reverted transaction, recovered from `resimulation`: the same
+/// call re-run with `call()`. Best effort, since state may have moved since the transaction
+/// mined; a re-simulation that now succeeds reports that no reason is available.
+pub fn resimulated_revert_reason<T, E: std::fmt::Display>(
+    resimulation: Result<T, E>,
+    registry: Option<&ErrorSelectorRegistry>,
+) -> String {
+    match resimulation {
+        Err(e) => try_decode_revert_reason_with(&e, registry).unwrap_or_else(|| e.to_string()),
+        Ok(_) => "no revert reason available (re-simulation succeeded)".to_string(),
+    }
+}
+
+/// [`try_decode_revert_reason`] with the ABI error registry consulted for custom errors the
+/// hand-written decoder does not know.
+pub fn try_decode_revert_reason_with(
+    error: &impl std::fmt::Display,
+    registry: Option<&ErrorSelectorRegistry>,
+) -> Option<String> {
     let error_str = error.to_string();
 
     // First try explicit revert-data markers used by common providers / Alloy.
@@ -225,7 +425,7 @@ pub fn try_decode_revert_reason(error: &impl std::fmt::Display) -> Option<String
     for marker in markers {
         if let Some(idx) = error_str.find(marker) {
             let hex_start = idx + marker.len() - 2; // back up to the "0x"
-            if let Some(decoded) = decode_hex_blob_at(&error_str[hex_start..]) {
+            if let Some(decoded) = decode_hex_blob_at(&error_str[hex_start..], registry) {
                 return Some(decoded);
             }
         }
@@ -260,7 +460,7 @@ pub fn try_decode_revert_reason(error: &impl std::fmt::Display) -> Option<String
         if blob.len() == 42 || blob.len() == 66 {
             continue;
         }
-        if let Some(decoded) = ContractErrorDecoder::decode_error_data(blob)
+        if let Some(decoded) = ContractErrorDecoder::decode_error_data_with(blob, registry)
             && !decoded.starts_with("Unknown contract error")
         {
             return Some(decoded);
@@ -268,7 +468,7 @@ pub fn try_decode_revert_reason(error: &impl std::fmt::Display) -> Option<String
     }
     // Last resort: try the first selector-length blob (even an address) so we surface *something*.
     if let Some(blob) = candidates.first()
-        && let Some(decoded) = ContractErrorDecoder::decode_error_data(blob)
+        && let Some(decoded) = ContractErrorDecoder::decode_error_data_with(blob, registry)
     {
         return Some(decoded);
     }
@@ -288,7 +488,7 @@ pub fn try_decode_revert_reason(error: &impl std::fmt::Display) -> Option<String
 
 /// Read a `0x<hex>` blob starting at `s[0..]` and feed it to `ContractErrorDecoder`. Returns
 /// the decoded reason if the blob is at least a 4-byte selector (10 chars including `0x`).
-fn decode_hex_blob_at(s: &str) -> Option<String> {
+fn decode_hex_blob_at(s: &str, registry: Option<&ErrorSelectorRegistry>) -> Option<String> {
     if !s.starts_with("0x") {
         return None;
     }
@@ -300,5 +500,5 @@ fn decode_hex_blob_at(s: &str) -> Option<String> {
     if hex_len < 8 {
         return None;
     }
-    ContractErrorDecoder::decode_error_data(&s[..hex_len + 2])
+    ContractErrorDecoder::decode_error_data_with(&s[..hex_len + 2], registry)
 }
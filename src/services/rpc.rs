@@ -1,17 +1,74 @@
 use alloy::network::EthereumWallet;
 use alloy::primitives::Address;
-use alloy::providers::ProviderBuilder;
+use alloy::providers::{Provider, ProviderBuilder, WsConnect};
+use alloy::rpc::client::RpcClient;
+use alloy::rpc::json_rpc::{RequestPacket, ResponsePacket};
 use alloy::signers::{Signer, local::PrivateKeySigner};
+use alloy::transports::utils::guess_local_url;
+use alloy::transports::{TransportError, TransportFut};
 use std::env;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::Service;
 
 // Import provider types from lib.rs
+use crate::config::ConfigReader;
+use crate::models::{ReadProviderHealth, RpcEndpointHealth};
+use crate::services::wallet::gas::{GasConfig, GasMode};
+use crate::services::wallet::nonce::{PoolNonceManager, signing_provider_with_gas};
 use crate::{AlloyProvider, ReadOnlyProvider};
 
 /// Configuration for RPC endpoints
 #[derive(Debug, Clone)]
 pub struct RpcConfig {
     pub env_type: String,
+    /// Primary endpoint: used for every send and tried first for confirmations.
     pub rpc_url: String,
+    /// Additional endpoints from `RPC_URLS`, in configured order.
+    pub fallback_urls: Vec<String>,
+    /// Optional `RPC_WS_URL` for log subscriptions. Never used to send transactions.
+    pub ws_url: Option<String>,
+    /// `GAS_MODE` and `GAS_LIMIT_MULTIPLIER`: how signing providers price transactions
+    /// and size their gas limits.
+    pub gas: GasConfig,
+}
+
+/// Transport an endpoint URL selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcTransport {
+    Http,
+    WebSocket,
+}
+
+/// Infer the transport from the URL scheme: `http(s)://` is HTTP and `ws(s)://` is
+/// WebSocket. Anything else is rejected rather than guessed.
+pub fn infer_transport(url: &str) -> Result<RpcTransport, String> {
+    let scheme = url
+        .split_once("://")
+        .map(|(scheme, _)| scheme.to_ascii_lowercase())
+        .ok_or_else(|| "RPC URL has no scheme (expected http(s):// or ws(s)://)".to_string())?;
+    match scheme.as_str() {
+        "http" | "https" => Ok(RpcTransport::Http),
+        "ws" | "wss" => Ok(RpcTransport::WebSocket),
+        other => Err(format!(
+            "Unsupported RPC URL scheme '{other}' (expected http(s):// or ws(s)://)"
+        )),
+    }
+}
+
+/// Split a comma-separated URL list, trimming entries and dropping empties and duplicates.
+pub fn parse_rpc_urls(raw: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for url in raw.split(',').map(str::trim).filter(|u| !u.is_empty()) {
+        if !urls.iter().any(|u| u == url) {
+            urls.push(url.to_string());
+        }
+    }
+    urls
 }
 
 impl RpcConfig {
@@ -32,14 +89,83 @@ impl RpcConfig {
             }
         }
 
-        let rpc_url = env::var("RPC_URL").map_err(|_| {
-            "RPC_URL environment variable not set. Must be a complete RPC URL with API key."
-                .to_string()
-        })?;
+        // RPC_URL stays the primary for backward compatibility; RPC_URLS adds
+        // fallbacks (or, when RPC_URL is unset, supplies the primary as its first entry).
+        let mut urls: Vec<String> = env::var("RPC_URL")
+            .ok()
+            .map(|u| u.trim().to_string())
+            .filter(|u| !u.is_empty())
+            .into_iter()
+            .collect();
+        if let Ok(raw) = env::var("RPC_URLS") {
+            for url in parse_rpc_urls(&raw) {
+                if !urls.contains(&url) {
+                    urls.push(url);
+                }
+            }
+        }
+        if urls.is_empty() {
+            return Err(
+                "RPC_URL environment variable not set. Must be a complete RPC URL with API key."
+                    .to_string(),
+            );
+        }
+        let rpc_url = urls.remove(0);
 
-        tracing::info!("Using RPC endpoint from RPC_URL");
+        tracing::info!(
+            "Using RPC endpoint from RPC_URL with {} fallback endpoint(s)",
+            urls.len()
+        );
+
+        // RPC_WS_URL only feeds event subscriptions; sends and reads stay on HTTP.
+        let ws_url = env::var("RPC_WS_URL")
+            .ok()
+            .map(|u| u.trim().to_string())
+            .filter(|u| !u.is_empty());
+        if let Some(url) = &ws_url {
+            match infer_transport(url)? {
+                RpcTransport::WebSocket => {}
+                RpcTransport::Http => {
+                    return Err("RPC_WS_URL must be a ws:// or wss:// URL".to_string());
+                }
+            }
+        }
 
-        Ok(Self { env_type, rpc_url })
+        let gas = GasConfig::from_env()?;
+        if gas.mode == GasMode::Legacy {
+            tracing::info!("GAS_MODE=legacy: transactions are priced with gasPrice");
+        }
+        if gas.limit_multiplier != 1.0 {
+            tracing::info!(
+                "Estimated gas limits are scaled by GAS_LIMIT_MULTIPLIER={}",
+                gas.limit_multiplier
+            );
+        }
+
+        Ok(Self {
+            env_type,
+            rpc_url,
+            fallback_urls: urls,
+            ws_url,
+            gas,
+        })
+    }
+
+    /// Transport used for event streaming: WebSocket when `RPC_WS_URL` is set, otherwise
+    /// HTTP polling.
+    pub fn event_transport(&self) -> RpcTransport {
+        if self.ws_url.is_some() {
+            RpcTransport::WebSocket
+        } else {
+            RpcTransport::Http
+        }
+    }
+
+    /// Primary followed by the fallbacks.
+    pub fn all_urls(&self) -> Vec<String> {
+        std::iter::once(self.rpc_url.clone())
+            .chain(self.fallback_urls.iter().cloned())
+            .collect()
     }
 
     /// Helper function to build a provider from a URL and private key
@@ -47,6 +173,7 @@ impl RpcConfig {
         private_key: &str,
         chain_id: u64,
         url: &str,
+        gas: GasConfig,
     ) -> Result<AlloyProvider, String> {
         let signer = private_key
             .parse::<PrivateKeySigner>()
@@ -55,12 +182,8 @@ impl RpcConfig {
 
         let wallet = EthereumWallet::from(signer);
 
-        let provider = ProviderBuilder::new().wallet(wallet).connect_http(
-            url.parse()
-                .map_err(|e| format!("Invalid RPC URL '{url}': {e}"))?,
-        );
-
-        Ok(provider)
+        // Single-key provider: not a pool wallet, so alloy's per-provider nonce cache.
+        signing_provider_with_gas(wallet, url, PoolNonceManager::default(), gas)
     }
 
     /// Build a read-only provider from a URL (no wallet, for queries only)
@@ -73,6 +196,17 @@ impl RpcConfig {
         Ok(provider)
     }
 
+    /// Build a read-only provider over a WebSocket (no wallet, for subscriptions)
+    pub async fn build_ws_provider(url: &str) -> Result<ReadOnlyProvider, String> {
+        if infer_transport(url)? != RpcTransport::WebSocket {
+            return Err("WebSocket provider needs a ws:// or wss:// URL".to_string());
+        }
+        ProviderBuilder::new()
+            .connect_ws(WsConnect::new(url))
+            .await
+            .map_err(|e| format!("Failed to connect WebSocket RPC: {e}"))
+    }
+
     /// Build a read-only RPC provider (no wallet, for queries only)
     pub fn build_read_only_provider_from_config(&self) -> Result<ReadOnlyProvider, String> {
         let provider = Self::build_read_only_provider(&self.rpc_url)?;
@@ -91,7 +225,8 @@ impl RpcConfig {
         private_key: &str,
         chain_id: u64,
     ) -> Result<AlloyProvider, String> {
-        let provider = Self::build_provider_from_url(private_key, chain_id, &self.rpc_url)?;
+        let provider =
+            Self::build_provider_from_url(private_key, chain_id, &self.rpc_url, self.gas)?;
         tracing::info!("RPC provider setup successful");
         Ok(provider)
     }
@@ -105,6 +240,681 @@ impl RpcConfig {
     }
 }
 
+/// Default retries (after the first attempt) for a transient read failure.
+pub const DEFAULT_RPC_MAX_RETRIES: u32 = 3;
+
+/// Default base backoff; attempt `n` waits roughly `base * 2^n`, jittered.
+pub const DEFAULT_RPC_BACKOFF_MS: u64 = 250;
+
+/// Upper bound on a single backoff sleep.
+pub const MAX_RPC_BACKOFF_MS: u64 = 10_000;
+
+/// HTTP statuses worth retrying: rate limited, or a gateway that could not reach (or
+/// wait for) the node behind it.
+pub const TRANSIENT_HTTP_STATUSES: [u16; 4] = [429, 502, 503, 504];
+
+/// Detect transient transport errors (rate limits, 5xx gateways, dropped or
+/// refused connections, timeouts) that are worth retrying.
+///
+/// Status codes only count where the message reports one (see [`http_status`]), so a
+/// number that merely contains `429` or `503` (an amount, a block, an address) never
+/// matches. Revert and decode errors are deterministic and never match.
+pub fn is_transient_rpc_error(error_msg: &str) -> bool {
+    let error_lower = error_msg.to_lowercase();
+    http_status(&error_lower).is_some_and(|status| TRANSIENT_HTTP_STATUSES.contains(&status))
+        || error_lower.contains("too many requests")
+        || error_lower.contains("rate limit")
+        || error_lower.contains("bad gateway")
+        || error_lower.contains("service unavailable")
+        || error_lower.contains("gateway timeout")
+        || error_lower.contains("timed out")
+        || error_lower.contains("connection refused")
+        || error_lower.contains("connection reset")
+        || error_lower.contains("connection closed")
+        || error_lower.contains("error sending request")
+        || error_lower.contains("temporarily unavailable")
+}
+
+/// HTTP status reported by an error message: after alloy's `HTTP error `, after
+/// `status `, `status: ` or `status code `, or as the message's own status line
+/// (`503 Service Unavailable`).
+pub fn http_status(error_msg: &str) -> Option<u16> {
+    let error_lower = error_msg.to_lowercase();
+    leading_status(&error_lower).or_else(|| {
+        ["http error ", "status code ", "status: ", "status "]
+            .iter()
+            .find_map(|marker| {
+                error_lower
+                    .match_indices(marker)
+                    .find_map(|(at, _)| leading_status(&error_lower[at + marker.len()..]))
+            })
+    })
+}
+
+/// A three-digit status at the start of `text`, not followed by another digit.
+fn leading_status(text: &str) -> Option<u16> {
+    let bytes = text.as_bytes();
+    let is_status = bytes.len() >= 3
+        && bytes[..3].iter().all(u8::is_ascii_digit)
+        && !bytes.get(3).is_some_and(u8::is_ascii_digit);
+    if is_status {
+        text[..3].parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Retry policy for idempotent RPC reads, from `RPC_MAX_RETRIES` and `RPC_BACKOFF_MS`.
+///
+/// Only reads (`eth_call`, balances, code, receipts lookups) go through
+/// [`retry_read`]. Sends are never retried here: a send that timed out may
+/// still have been broadcast, and resending it risks a double spend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadRetryPolicy {
+    pub max_retries: u32,
+    pub base_backoff_ms: u64,
+}
+
+impl Default for ReadRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_RPC_MAX_RETRIES,
+            base_backoff_ms: DEFAULT_RPC_BACKOFF_MS,
+        }
+    }
+}
+
+impl ReadRetryPolicy {
+    pub fn new(max_retries: u32, base_backoff_ms: u64) -> Self {
+        Self {
+            max_retries,
+            base_backoff_ms,
+        }
+    }
+
+    /// Read `RPC_MAX_RETRIES` and `RPC_BACKOFF_MS`, using the defaults when unset. An
+    /// unparseable value is recorded on `config` and stops startup.
+    pub fn from_config(config: &mut ConfigReader) -> Self {
+        Self::new(
+            config.parse_or("RPC_MAX_RETRIES", DEFAULT_RPC_MAX_RETRIES),
+            config.parse_or("RPC_BACKOFF_MS", DEFAULT_RPC_BACKOFF_MS),
+        )
+    }
+
+    /// Sleep before retry number `retry` (0-based): exponential, capped at
+    /// [`MAX_RPC_BACKOFF_MS`], with "equal jitter" (a random point in the upper
+    /// half) so instances hitting the same 429 don't retry in lockstep.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let ceiling = self
+            .base_backoff_ms
+            .saturating_mul(1u64 << retry.min(20))
+            .min(MAX_RPC_BACKOFF_MS);
+        let half = ceiling / 2;
+        let jitter = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish()
+            % (ceiling - half + 1);
+        Duration::from_millis(half + jitter)
+    }
+}
+
+/// Run an idempotent read, retrying transient failures per `policy`.
+///
+/// Non-transient errors return immediately; after `max_retries` retries the
+/// last error is returned. Never wrap a transaction send in this.
+pub async fn retry_read<T, E, F, Fut>(
+    policy: &ReadRetryPolicy,
+    operation: &str,
+    call: F,
+) -> Result<T, E>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    let mut retry = 0;
+    loop {
+        match call().await {
+            Ok(value) => return Ok(value),
+            Err(e) if retry < policy.max_retries && is_transient_rpc_error(&e.to_string()) => {
+                let delay = policy.backoff(retry);
+                retry += 1;
+                tracing::warn!(
+                    "Transient RPC error on {} (retry {}/{} in {}ms): {}",
+                    operation,
+                    retry,
+                    policy.max_retries,
+                    delay.as_millis(),
+                    e
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Default consecutive transient failures that open an endpoint's breaker.
+pub const DEFAULT_RPC_BREAKER_THRESHOLD: u32 = 3;
+
+/// Default time an open breaker skips its endpoint before probing it again.
+pub const DEFAULT_RPC_BREAKER_COOLDOWN_SECS: u64 = 30;
+
+/// Circuit breaker settings, from `RPC_BREAKER_THRESHOLD` and
+/// `RPC_BREAKER_COOLDOWN_SECS`. A threshold of 0 disables the breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerPolicy {
+    pub failure_threshold: u32,
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerPolicy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: DEFAULT_RPC_BREAKER_THRESHOLD,
+            cooldown: Duration::from_secs(DEFAULT_RPC_BREAKER_COOLDOWN_SECS),
+        }
+    }
+}
+
+impl CircuitBreakerPolicy {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cooldown,
+        }
+    }
+
+    /// Build from `RPC_BREAKER_THRESHOLD` and `RPC_BREAKER_COOLDOWN_SECS`, falling
+    /// back to the defaults when unset or unparseable.
+    pub fn from_env() -> Self {
+        let failure_threshold = env::var("RPC_BREAKER_THRESHOLD")
+            .ok()
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .unwrap_or(DEFAULT_RPC_BREAKER_THRESHOLD);
+        let cooldown_secs = env::var("RPC_BREAKER_COOLDOWN_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_RPC_BREAKER_COOLDOWN_SECS);
+        Self::new(failure_threshold, Duration::from_secs(cooldown_secs))
+    }
+}
+
+/// State of one endpoint's [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Calls go through.
+    Closed,
+    /// Calls skip the endpoint until the cooldown ends.
+    Open,
+    /// The cooldown ended; the next call probes the endpoint.
+    HalfOpen,
+}
+
+impl BreakerState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BreakerState::Closed => "closed",
+            BreakerState::Open => "open",
+            BreakerState::HalfOpen => "half_open",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct BreakerInner {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+/// Per-endpoint circuit breaker.
+///
+/// After `failure_threshold` consecutive transient failures the endpoint is skipped
+/// for `cooldown`, so calls go straight to the next endpoint instead of waiting out
+/// the dead one's timeout. Once the cooldown ends, one call is let through as a
+/// probe (re-arming the cooldown for everyone else): a success closes the breaker,
+/// a failure keeps it open for another cooldown.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    policy: CircuitBreakerPolicy,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(policy: CircuitBreakerPolicy) -> Self {
+        Self {
+            policy,
+            inner: Mutex::new(BreakerInner::default()),
+        }
+    }
+
+    /// Whether a call may use the endpoint now. Claims the probe when the cooldown
+    /// has ended, so only the caller that gets `true` should call the endpoint.
+    pub fn allow_request(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.open_until {
+            None => true,
+            Some(until) if Instant::now() >= until => {
+                inner.open_until = Some(Instant::now() + self.policy.cooldown);
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    /// The endpoint answered: close the breaker. Returns whether it was open.
+    pub fn record_success(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures = 0;
+        inner.open_until.take().is_some()
+    }
+
+    /// The endpoint failed with a transient error. Returns whether this failure
+    /// opened (or, for a failed probe, re-opened) the breaker.
+    pub fn record_failure(&self) -> bool {
+        if self.policy.failure_threshold == 0 {
+            return false;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        if inner.consecutive_failures < self.policy.failure_threshold {
+            return false;
+        }
+        inner.open_until = Some(Instant::now() + self.policy.cooldown);
+        true
+    }
+
+    pub fn state(&self) -> BreakerState {
+        match self.inner.lock().unwrap().open_until {
+            None => BreakerState::Closed,
+            Some(until) if Instant::now() >= until => BreakerState::HalfOpen,
+            Some(_) => BreakerState::Open,
+        }
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.inner.lock().unwrap().consecutive_failures
+    }
+
+    /// Time left before an open breaker lets a probe through.
+    pub fn remaining_cooldown(&self) -> Option<Duration> {
+        self.inner
+            .lock()
+            .unwrap()
+            .open_until
+            .map(|until| until.saturating_duration_since(Instant::now()))
+    }
+}
+
+/// Every configured RPC endpoint with a read-only provider each.
+///
+/// Reads rotate round-robin across the endpoints, so load spreads and a dead
+/// endpoint only costs the reads that land on it first. Transaction confirmation
+/// lookups are sticky to the primary (the endpoint the transaction was sent
+/// through) and fall back in configured order. Failover only moves on after a
+/// transient transport error; a deterministic error is returned immediately.
+/// Each endpoint has a [`CircuitBreaker`]: an endpoint whose breaker is open is
+/// tried only after every other endpoint has failed.
+pub struct RpcEndpoints {
+    urls: Vec<String>,
+    providers: Vec<Arc<ReadOnlyProvider>>,
+    breakers: Vec<CircuitBreaker>,
+    cursor: AtomicUsize,
+}
+
+impl RpcEndpoints {
+    pub fn new(urls: Vec<String>, providers: Vec<Arc<ReadOnlyProvider>>) -> Result<Self, String> {
+        if urls.is_empty() || urls.len() != providers.len() {
+            return Err(format!(
+                "RpcEndpoints needs one provider per URL (got {} URLs, {} providers)",
+                urls.len(),
+                providers.len()
+            ));
+        }
+        let breakers = Self::breakers(urls.len(), CircuitBreakerPolicy::default());
+        Ok(Self {
+            urls,
+            providers,
+            breakers,
+            cursor: AtomicUsize::new(0),
+        })
+    }
+
+    /// A single endpoint: no failover.
+    pub fn single(url: String, provider: Arc<ReadOnlyProvider>) -> Self {
+        Self {
+            urls: vec![url],
+            providers: vec![provider],
+            breakers: Self::breakers(1, CircuitBreakerPolicy::default()),
+            cursor: AtomicUsize::new(0),
+        }
+    }
+
+    /// Build a read-only provider for every URL in `config`, with the breaker policy
+    /// from the environment.
+    pub fn from_config(config: &RpcConfig) -> Result<Self, String> {
+        let urls = config.all_urls();
+        let providers = urls
+            .iter()
+            .map(|url| RpcConfig::build_read_only_provider(url).map(Arc::new))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(urls, providers)?.with_breaker_policy(CircuitBreakerPolicy::from_env()))
+    }
+
+    /// Replace every endpoint's breaker (resetting its state) with one using `policy`.
+    pub fn with_breaker_policy(mut self, policy: CircuitBreakerPolicy) -> Self {
+        self.breakers = Self::breakers(self.urls.len(), policy);
+        self
+    }
+
+    fn breakers(count: usize, policy: CircuitBreakerPolicy) -> Vec<CircuitBreaker> {
+        (0..count).map(|_| CircuitBreaker::new(policy)).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+
+    pub fn url(&self, index: usize) -> &str {
+        &self.urls[index]
+    }
+
+    pub fn provider(&self, index: usize) -> Arc<ReadOnlyProvider> {
+        self.providers[index].clone()
+    }
+
+    pub fn breaker(&self, index: usize) -> &CircuitBreaker {
+        &self.breakers[index]
+    }
+
+    /// Breaker state of every endpoint, for `/health`. Endpoints are identified by
+    /// index (0 is the primary), never by URL, which usually embeds an API key.
+    pub fn health(&self) -> Vec<RpcEndpointHealth> {
+        self.breakers
+            .iter()
+            .enumerate()
+            .map(|(index, breaker)| RpcEndpointHealth {
+                endpoint: index,
+                state: breaker.state().label().to_string(),
+                consecutive_failures: breaker.consecutive_failures(),
+                retry_in_ms: breaker
+                    .remaining_cooldown()
+                    .map(|left| left.as_millis() as u64),
+            })
+            .collect()
+    }
+
+    /// Endpoint indices for the next read: every endpoint once, starting one
+    /// past where the previous read started.
+    pub fn read_order(&self) -> Vec<usize> {
+        let n = self.urls.len();
+        let start = self.cursor.fetch_add(1, Ordering::Relaxed) % n;
+        (0..n).map(|i| (start + i) % n).collect()
+    }
+
+    /// Endpoint indices for write-path lookups: primary first, then fallbacks in order.
+    pub fn write_order(&self) -> Vec<usize> {
+        (0..self.urls.len()).collect()
+    }
+
+    /// Run an idempotent read, failing over round-robin across endpoints.
+    pub async fn read_with_failover<T, E, F, Fut>(&self, operation: &str, call: F) -> Result<T, E>
+    where
+        F: Fn(Arc<ReadOnlyProvider>) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        self.try_in_order(self.read_order(), operation, call).await
+    }
+
+    /// Run a write-path lookup (e.g. a receipt), sticky to the primary with fallback.
+    pub async fn sticky_with_fallback<T, E, F, Fut>(&self, operation: &str, call: F) -> Result<T, E>
+    where
+        F: Fn(Arc<ReadOnlyProvider>) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        self.try_in_order(self.write_order(), operation, call).await
+    }
+
+    /// Run a transaction broadcast exactly once, on the first endpoint in write order
+    /// whose breaker admits it: the primary, unless its breaker is open. The outcome
+    /// feeds that endpoint's breaker like any other call, but a failed broadcast is
+    /// never repeated on another endpoint — it may have reached the node before the
+    /// connection dropped, and the caller decides what a resend means.
+    pub async fn send_once<T, E, F, Fut>(&self, operation: &str, call: F) -> Result<T, E>
+    where
+        F: Fn(Arc<ReadOnlyProvider>) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        let index = self
+            .write_order()
+            .into_iter()
+            .find(|&index| {
+                self.breakers[index].state() != BreakerState::Open
+                    && self.breakers[index].allow_request()
+            })
+            .unwrap_or(0);
+        self.attempt(index, operation, &call)
+            .await
+            .map_err(|(e, _)| e)
+    }
+
+    /// Read-only provider whose every call runs through these endpoints: round-robin
+    /// with failover, skipping endpoints whose breaker is open.
+    pub fn failover_read_provider(endpoints: &Arc<Self>) -> ReadOnlyProvider {
+        ProviderBuilder::new().connect_client(FailoverTransport::client(
+            endpoints.clone(),
+            FailoverOrder::Reads,
+        ))
+    }
+
+    /// RPC client for signing providers: lookups stick to the primary and fall back in
+    /// order, and broadcasts go out once through [`RpcEndpoints::send_once`].
+    pub fn send_client(endpoints: &Arc<Self>) -> RpcClient {
+        FailoverTransport::client(endpoints.clone(), FailoverOrder::Writes)
+    }
+
+    async fn try_in_order<T, E, F, Fut>(
+        &self,
+        order: Vec<usize>,
+        operation: &str,
+        call: F,
+    ) -> Result<T, E>
+    where
+        F: Fn(Arc<ReadOnlyProvider>) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        // Endpoints whose breaker is open go last: they are only tried once every
+        // other endpoint has failed.
+        let (preferred, mut deferred): (Vec<usize>, Vec<usize>) = order
+            .into_iter()
+            .partition(|&index| self.breakers[index].state() != BreakerState::Open);
+        let mut last_error = None;
+        for index in preferred {
+            // A half-open endpoint admits one probe at a time.
+            if !self.breakers[index].allow_request() {
+                deferred.push(index);
+                continue;
+            }
+            match self.attempt(index, operation, &call).await {
+                Ok(value) => return Ok(value),
+                Err((e, true)) => last_error = Some(e),
+                Err((e, false)) => return Err(e),
+            }
+        }
+        for index in deferred {
+            match self.attempt(index, operation, &call).await {
+                Ok(value) => return Ok(value),
+                Err((e, true)) => last_error = Some(e),
+                Err((e, false)) => return Err(e),
+            }
+        }
+        Err(last_error.expect("RpcEndpoints always holds at least one endpoint"))
+    }
+
+    /// Call one endpoint and feed the outcome to its breaker. The error comes back
+    /// with whether it was transient (worth failing over).
+    async fn attempt<T, E, F, Fut>(
+        &self,
+        index: usize,
+        operation: &str,
+        call: &F,
+    ) -> Result<T, (E, bool)>
+    where
+        F: Fn(Arc<ReadOnlyProvider>) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        match call(self.provider(index)).await {
+            Ok(value) => {
+                self.record_success(index);
+                Ok(value)
+            }
+            Err(e) if is_transient_rpc_error(&e.to_string()) => {
+                // Never log the URL itself: it usually embeds the provider API key.
+                tracing::warn!(
+                    "RPC endpoint #{} failed {} with a transient error: {}",
+                    index,
+                    operation,
+                    e
+                );
+                self.record_failure(index);
+                Err((e, true))
+            }
+            Err(e) => {
+                // A deterministic error still means the endpoint answered.
+                self.record_success(index);
+                Err((e, false))
+            }
+        }
+    }
+
+    fn record_success(&self, index: usize) {
+        if self.breakers[index].record_success() {
+            tracing::info!("RPC endpoint #{} recovered; circuit breaker closed", index);
+        }
+    }
+
+    fn record_failure(&self, index: usize) {
+        if self.breakers[index].record_failure() {
+            tracing::warn!(
+                "RPC endpoint #{} circuit breaker open after {} consecutive failures; \
+                 skipping it for {:?}",
+                index,
+                self.breakers[index].consecutive_failures(),
+                self.breakers[index].policy.cooldown
+            );
+        }
+    }
+}
+
+/// Endpoint order a [`FailoverTransport`] uses for calls other than broadcasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailoverOrder {
+    /// [`RpcEndpoints::read_order`]: round-robin, every endpoint once.
+    Reads,
+    /// [`RpcEndpoints::write_order`]: primary first, then fallbacks in order.
+    Writes,
+}
+
+/// JSON-RPC methods that broadcast a transaction; see [`RpcEndpoints::send_once`].
+const BROADCAST_METHODS: [&str; 2] = ["eth_sendRawTransaction", "eth_sendTransaction"];
+
+/// alloy transport that sends each request packet through [`RpcEndpoints`], so every
+/// provider call gets the endpoints' failover and circuit breakers without the call
+/// site knowing about either.
+#[derive(Clone)]
+pub struct FailoverTransport {
+    endpoints: Arc<RpcEndpoints>,
+    order: FailoverOrder,
+}
+
+impl FailoverTransport {
+    pub fn new(endpoints: Arc<RpcEndpoints>, order: FailoverOrder) -> Self {
+        Self { endpoints, order }
+    }
+
+    /// RPC client over a new transport; local when the primary is (faster polling).
+    pub fn client(endpoints: Arc<RpcEndpoints>, order: FailoverOrder) -> RpcClient {
+        let is_local = guess_local_url(endpoints.url(0));
+        RpcClient::new(Self::new(endpoints, order), is_local)
+    }
+}
+
+impl Service<RequestPacket> for FailoverTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness is per endpoint and checked when a call picks one.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let endpoints = self.endpoints.clone();
+        let order = self.order;
+        Box::pin(async move {
+            let operation = request.method_names().collect::<Vec<_>>().join(",");
+            let call = |provider: Arc<ReadOnlyProvider>| {
+                let mut transport = provider.client().transport().clone();
+                let request = request.clone();
+                async move { transport.call(request).await }
+            };
+            if request
+                .method_names()
+                .any(|method| BROADCAST_METHODS.contains(&method))
+            {
+                return endpoints.send_once(&operation, call).await;
+            }
+            match order {
+                FailoverOrder::Reads => endpoints.read_with_failover(&operation, call).await,
+                FailoverOrder::Writes => endpoints.sticky_with_fallback(&operation, call).await,
+            }
+        })
+    }
+}
+
+/// Budget for the `/health?rpc=true` read probe; a slower endpoint reports "timed out".
+pub const READ_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// `eth_blockNumber` through the read-only `provider`, for `/health?rpc=true`. The
+/// error reported is a fixed class; the underlying message is only logged because
+/// transport errors can embed the endpoint URL (and its API key).
+pub async fn probe_read_provider(
+    provider: &ReadOnlyProvider,
+    budget: Duration,
+) -> ReadProviderHealth {
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(budget, provider.get_block_number()).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let (block_number, error) = match outcome {
+        Ok(Ok(number)) => (Some(number), None),
+        Ok(Err(e)) => {
+            tracing::warn!("Health read probe failed: {e}");
+            (None, Some("request failed"))
+        }
+        Err(_) => {
+            tracing::warn!("Health read probe timed out after {budget:?}");
+            (None, Some("timed out"))
+        }
+    };
+    ReadProviderHealth {
+        ok: block_number.is_some(),
+        block_number,
+        latency_ms,
+        error: error.map(str::to_string),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,6 +932,9 @@ mod tests {
         RpcConfig {
             env_type: env_type.to_string(),
             rpc_url: rpc_url.to_string(),
+            fallback_urls: Vec::new(),
+            ws_url: None,
+            gas: GasConfig::default(),
         }
     }
 
@@ -159,6 +972,135 @@ mod tests {
         }
     }
 
+    #[test]
+    #[serial]
+    fn test_from_env_rpc_urls_adds_fallbacks() {
+        unsafe {
+            std::env::set_var("ENV", "mainnet");
+            std::env::set_var("RPC_URL", "https://primary.example.com");
+            std::env::set_var(
+                "RPC_URLS",
+                "https://b.example.com, https://primary.example.com,,https://c.example.com",
+            );
+        }
+
+        let config = RpcConfig::from_env().unwrap();
+        assert_eq!(config.rpc_url(), "https://primary.example.com");
+        assert_eq!(
+            config.fallback_urls,
+            vec!["https://b.example.com", "https://c.example.com"]
+        );
+        assert_eq!(config.all_urls().len(), 3);
+
+        unsafe {
+            std::env::remove_var("ENV");
+            std::env::remove_var("RPC_URL");
+            std::env::remove_var("RPC_URLS");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_from_env_rpc_urls_without_rpc_url() {
+        unsafe {
+            std::env::set_var("ENV", "testnet");
+            std::env::remove_var("RPC_URL");
+            std::env::set_var("RPC_URLS", "https://a.example.com,https://b.example.com");
+        }
+
+        let config = RpcConfig::from_env().unwrap();
+        assert_eq!(config.rpc_url(), "https://a.example.com");
+        assert_eq!(config.fallback_urls, vec!["https://b.example.com"]);
+
+        unsafe {
+            std::env::remove_var("ENV");
+            std::env::remove_var("RPC_URLS");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_from_env_ws_url_selects_websocket() {
+        unsafe {
+            std::env::set_var("ENV", "mainnet");
+            std::env::set_var("RPC_URL", "https://rpc.example.com");
+            std::env::set_var("RPC_WS_URL", " wss://ws.example.com/key ");
+        }
+
+        let config = RpcConfig::from_env().unwrap();
+        assert_eq!(config.ws_url.as_deref(), Some("wss://ws.example.com/key"));
+        assert_eq!(config.event_transport(), RpcTransport::WebSocket);
+        // Sends stay on the HTTP primary.
+        assert_eq!(config.rpc_url(), "https://rpc.example.com");
+
+        unsafe {
+            std::env::remove_var("ENV");
+            std::env::remove_var("RPC_URL");
+            std::env::remove_var("RPC_WS_URL");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_from_env_without_ws_url_polls_over_http() {
+        unsafe {
+            std::env::set_var("ENV", "mainnet");
+            std::env::set_var("RPC_URL", "https://rpc.example.com");
+            std::env::set_var("RPC_WS_URL", "");
+        }
+
+        let config = RpcConfig::from_env().unwrap();
+        assert_eq!(config.ws_url, None);
+        assert_eq!(config.event_transport(), RpcTransport::Http);
+
+        unsafe {
+            std::env::remove_var("ENV");
+            std::env::remove_var("RPC_URL");
+            std::env::remove_var("RPC_WS_URL");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_from_env_rejects_http_ws_url() {
+        unsafe {
+            std::env::set_var("ENV", "mainnet");
+            std::env::set_var("RPC_URL", "https://rpc.example.com");
+            std::env::set_var("RPC_WS_URL", "https://rpc.example.com");
+        }
+
+        let result = RpcConfig::from_env();
+        assert!(result.unwrap_err().contains("RPC_WS_URL must be a ws://"));
+
+        unsafe {
+            std::env::remove_var("ENV");
+            std::env::remove_var("RPC_URL");
+            std::env::remove_var("RPC_WS_URL");
+        }
+    }
+
+    #[test]
+    fn test_infer_transport() {
+        assert_eq!(
+            infer_transport("https://rpc.example.com"),
+            Ok(RpcTransport::Http)
+        );
+        assert_eq!(
+            infer_transport("http://localhost:8545"),
+            Ok(RpcTransport::Http)
+        );
+        assert_eq!(
+            infer_transport("WSS://ws.example.com"),
+            Ok(RpcTransport::WebSocket)
+        );
+        assert_eq!(
+            infer_transport("ws://localhost:8546"),
+            Ok(RpcTransport::WebSocket)
+        );
+        assert!(infer_transport("ipc:///tmp/geth.ipc").is_err());
+        assert!(infer_transport("localhost:8545").is_err());
+    }
+
     #[test]
     #[serial]
     fn test_from_env_valid_env_types() {
@@ -217,6 +1159,7 @@ mod tests {
         unsafe {
             std::env::set_var("ENV", "mainnet");
             std::env::remove_var("RPC_URL");
+            std::env::remove_var("RPC_URLS");
         }
         let result = RpcConfig::from_env();
         assert!(result.is_err());
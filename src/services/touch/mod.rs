@@ -31,6 +31,7 @@ use std::time::Duration;
 use alloy::primitives::Address;
 use tokio::sync::mpsc;
 
+use crate::services::rpc::RpcEndpoints;
 use crate::services::wallet::WalletManager;
 
 /// Bounded queue depth of pending beacon signals. A full channel means the
@@ -61,6 +62,11 @@ impl TouchDispatcher {
         Self { tx: Some(tx) }
     }
 
+    /// Whether the touch worker is running (false for [`TouchDispatcher::disabled`]).
+    pub fn is_enabled(&self) -> bool {
+        self.tx.is_some()
+    }
+
     /// Non-blocking: enqueue `beacon` for a follow-up touch of its perps. Never
     /// blocks the caller and never fails the update path.
     pub fn dispatch(&self, beacon: Address) {
@@ -98,7 +104,7 @@ impl TouchDispatcher {
 /// Must be called from within the tokio runtime (it may `tokio::spawn`).
 pub fn spawn_from_env(
     manager: Arc<WalletManager>,
-    rpc_url: String,
+    endpoints: Arc<RpcEndpoints>,
     multicall3: Option<Address>,
 ) -> TouchDispatcher {
     if !env_bool("TOUCH_ON_UPDATE_ENABLED", false) {
@@ -160,7 +166,7 @@ pub fn spawn_from_env(
         rx,
         resolver,
         manager,
-        rpc_url,
+        endpoints,
         multicall3,
         flush_interval,
         max_batch,
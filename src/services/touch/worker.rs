@@ -22,6 +22,7 @@ use tokio::sync::mpsc;
 use tokio::time::{MissedTickBehavior, interval, timeout};
 
 use crate::routes::{IMulticall3, IPerp};
+use crate::services::rpc::RpcEndpoints;
 use crate::services::wallet::WalletManager;
 
 use super::resolver::PerpResolver;
@@ -54,7 +55,7 @@ pub struct TouchWorker {
     rx: mpsc::Receiver<Address>,
     resolver: PerpResolver,
     manager: Arc<WalletManager>,
-    rpc_url: String,
+    endpoints: Arc<RpcEndpoints>,
     multicall3: Address,
     flush_interval: Duration,
     max_batch: usize,
@@ -66,7 +67,7 @@ impl TouchWorker {
         rx: mpsc::Receiver<Address>,
         resolver: PerpResolver,
         manager: Arc<WalletManager>,
-        rpc_url: String,
+        endpoints: Arc<RpcEndpoints>,
         multicall3: Address,
         flush_interval: Duration,
         max_batch: usize,
@@ -75,7 +76,7 @@ impl TouchWorker {
             rx,
             resolver,
             manager,
-            rpc_url,
+            endpoints,
             multicall3,
             flush_interval,
             max_batch: max_batch.clamp(1, MAX_BATCH_CEILING),
@@ -144,7 +145,7 @@ impl TouchWorker {
             }
         };
 
-        let provider = match handle.build_provider(&self.rpc_url) {
+        let provider = match handle.build_provider(&self.endpoints) {
             Ok(p) => p,
             Err(e) => {
                 tracing::warn!(
@@ -0,0 +1,149 @@
+//! Confirmation depth before a receipt is treated as final.
+//!
+//! By default the first receipt is final. With `CONFIRMATION_BLOCKS=N` the flows that
+//! return on-chain results (factory beacon creation, perp deployment, deposits) wait
+//! until `head - receipt.block_number >= N` before reporting success, so a shallow
+//! reorg cannot silently undo a result the caller was told about. Once the depth is
+//! reached the receipt is fetched again: if the transaction moved to another block
+//! the wait restarts from there, and if it vanished the operation fails.
+
+use std::time::{Duration, Instant};
+
+use alloy::primitives::B256;
+use alloy::providers::Provider;
+
+use crate::ReadOnlyProvider;
+use crate::config::ConfigReader;
+use crate::services::rpc::{ReadRetryPolicy, retry_read};
+
+/// Blocks required on top of the receipt's block when `CONFIRMATION_BLOCKS` is unset.
+pub const DEFAULT_CONFIRMATION_BLOCKS: u64 = 0;
+/// How often the chain head is checked while waiting.
+pub const DEFAULT_CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Upper bound on one wait, so a stalled chain cannot hold a request forever.
+pub const DEFAULT_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Blocks mined on top of `receipt_block` at `head` (0 while the head lags behind).
+pub fn confirmation_depth(head: u64, receipt_block: u64) -> u64 {
+    head.saturating_sub(receipt_block)
+}
+
+/// How deep a receipt must be before it is final.
+#[derive(Debug, Clone)]
+pub struct ConfirmationPolicy {
+    blocks: u64,
+    poll_interval: Duration,
+    timeout: Duration,
+}
+
+impl Default for ConfirmationPolicy {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_CONFIRMATION_BLOCKS,
+            DEFAULT_CONFIRMATION_POLL_INTERVAL,
+            DEFAULT_CONFIRMATION_TIMEOUT,
+        )
+    }
+}
+
+impl ConfirmationPolicy {
+    /// Require `blocks` confirmations, polling every `poll_interval` for at most `timeout`.
+    pub fn new(blocks: u64, poll_interval: Duration, timeout: Duration) -> Self {
+        Self {
+            blocks,
+            poll_interval,
+            timeout,
+        }
+    }
+
+    /// Read `CONFIRMATION_BLOCKS`, using [`DEFAULT_CONFIRMATION_BLOCKS`] when unset. An
+    /// unparseable value is recorded on `config` and stops startup.
+    pub fn from_config(config: &mut ConfigReader) -> Self {
+        Self::new(
+            config.parse_or("CONFIRMATION_BLOCKS", DEFAULT_CONFIRMATION_BLOCKS),
+            DEFAULT_CONFIRMATION_POLL_INTERVAL,
+            DEFAULT_CONFIRMATION_TIMEOUT,
+        )
+    }
+
+    /// Confirmations required.
+    pub fn blocks(&self) -> u64 {
+        self.blocks
+    }
+
+    /// Whether a receipt in `receipt_block` is final at `head`.
+    pub fn is_final(&self, head: u64, receipt_block: u64) -> bool {
+        confirmation_depth(head, receipt_block) >= self.blocks
+    }
+}
+
+/// Wait until `tx_hash`, mined in `receipt_block`, is `policy.blocks()` deep. Head and
+/// receipt reads are retried per `retry`.
+///
+/// Returns the depth observed when the wait ended. With a policy of 0 blocks this
+/// returns `Ok(0)` at once without touching the provider.
+pub async fn wait_for_confirmations(
+    provider: &ReadOnlyProvider,
+    policy: &ConfirmationPolicy,
+    retry: &ReadRetryPolicy,
+    tx_hash: B256,
+    receipt_block: u64,
+) -> Result<u64, String> {
+    if policy.blocks == 0 {
+        return Ok(0);
+    }
+    let deadline = Instant::now() + policy.timeout;
+    let mut receipt_block = receipt_block;
+    tracing::info!(
+        "Waiting for {} confirmation(s) of tx {} (mined in block {})",
+        policy.blocks,
+        tx_hash,
+        receipt_block
+    );
+
+    loop {
+        let head = retry_read(retry, "get_block_number", || provider.get_block_number())
+            .await
+            .map_err(|e| format!("Failed to get block number: {e}"))?;
+        let depth = confirmation_depth(head, receipt_block);
+
+        if policy.is_final(head, receipt_block) {
+            // Re-read the receipt: a reorg while waiting may have moved or dropped the tx.
+            let receipt = retry_read(retry, "get_transaction_receipt", || {
+                provider.get_transaction_receipt(tx_hash)
+            })
+            .await
+            .map_err(|e| format!("Failed to re-check receipt for tx {tx_hash}: {e}"))?;
+            match receipt.and_then(|r| r.block_number) {
+                Some(block) if block == receipt_block => {
+                    tracing::info!("Tx {} has {} confirmation(s)", tx_hash, depth);
+                    return Ok(depth);
+                }
+                Some(block) => {
+                    tracing::warn!(
+                        "Tx {} moved from block {} to {} (reorg); waiting again",
+                        tx_hash,
+                        receipt_block,
+                        block
+                    );
+                    receipt_block = block;
+                    continue;
+                }
+                None => {
+                    return Err(format!(
+                        "Transaction {tx_hash} disappeared from the chain (reorg) while waiting for {} confirmation(s)",
+                        policy.blocks
+                    ));
+                }
+            }
+        }
+
+        if Instant::now() >= deadline {
+            return Err(format!(
+                "Transaction {tx_hash} reached only {depth}/{} confirmation(s) within {:?}",
+                policy.blocks, policy.timeout
+            ));
+        }
+        tokio::time::sleep(policy.poll_interval).await;
+    }
+}
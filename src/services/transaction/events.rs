@@ -1,16 +1,119 @@
 use alloy::primitives::{Address, FixedBytes, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::{Filter, Log};
+use futures::stream::{self, Stream};
+use std::collections::VecDeque;
+use std::future::Future;
 use tracing;
 
-use crate::routes::{IBeacon, IPerp, IPerpFactory};
+use super::super::perp::liquidity::sqrt_price_x96_to_price_x96;
+use super::super::rpc::{ReadRetryPolicy, retry_read};
+use crate::ReadOnlyProvider;
+use crate::routes::{IBeacon, IERC20, IPerp, IPerpFactory};
+
+/// Blocks per `eth_getLogs` request in chunked log scans by default.
+pub const DEFAULT_LOG_SCAN_CHUNK_BLOCKS: u64 = 2_000;
 
 /// Subset of `PerpFactory.PerpCreated` event fields surfaced to API callers.
 #[derive(Debug, Clone)]
 pub struct PerpCreatedEvent {
     pub perp: Address,
+    /// `modules.beacon` the perp was created for.
+    pub beacon: Address,
     pub pool_id: FixedBytes<32>,
     pub initial_index: U256,
     pub sqrt_price_x96: U256,
+    /// Initial mark price in Q96, from `sqrt_price_x96` (the event carries no mark price).
+    pub mark_price_x96: U256,
     pub tick: i32,
+    pub ema_window: u32,
+}
+
+/// Decode a `PerpCreated` log, whatever its emitter. Returns `None` for other events.
+pub fn decode_perp_created(log: &alloy::rpc::types::Log) -> Option<PerpCreatedEvent> {
+    let data = log
+        .log_decode::<IPerpFactory::PerpCreated>()
+        .ok()?
+        .inner
+        .data;
+    let sqrt_price_x96 = U256::from(data.sqrtPriceX96);
+    Some(PerpCreatedEvent {
+        perp: data.perp,
+        beacon: data.modules.beacon,
+        pool_id: data.poolId,
+        initial_index: data.initialIndex,
+        sqrt_price_x96,
+        mark_price_x96: sqrt_price_x96_to_price_x96(sqrt_price_x96),
+        tick: data.tick.as_i32(),
+        ema_window: data.emaWindow.to::<u32>(),
+    })
+}
+
+/// Logs in `receipt` emitted by `emitter`, in order.
+///
+/// Every receipt parser below starts from this: a multicall receipt carries the logs of
+/// every contract it touched, and another contract can emit an event with the same
+/// signature (two beacons' `IndexUpdated`, a second Perp's `MakerOpened`).
+pub fn logs_emitted_by(
+    receipt: &alloy::rpc::types::TransactionReceipt,
+    emitter: Address,
+) -> impl Iterator<Item = &Log> {
+    receipt
+        .logs()
+        .iter()
+        .filter(move |log| log.address() == emitter)
+}
+
+/// Fail on a mined receipt whose transaction reverted (`status: false`).
+///
+/// A reverted transaction emits no logs, so looking for its event would otherwise end in a
+/// misleading "event not found". `label` names the call, e.g. `"createPerp"`.
+pub fn ensure_receipt_succeeded(
+    receipt: &alloy::rpc::types::TransactionReceipt,
+    label: &str,
+) -> Result<(), String> {
+    if receipt.status() {
+        return Ok(());
+    }
+    Err(format!(
+        "{label} transaction {} reverted (status: false)",
+        receipt.transaction_hash
+    ))
+}
+
+/// Build the "event not found" error for a successful receipt that lacks the expected event.
+///
+/// A missing event on a succeeded transaction almost always means a wrong ABI or address, so
+/// the message lists how many logs the receipt holds and which addresses emitted them.
+pub fn event_not_found_error(
+    event_name: &str,
+    expected_emitter: Address,
+    receipt: &alloy::rpc::types::TransactionReceipt,
+) -> String {
+    let logs = receipt.logs();
+    if logs.is_empty() {
+        return format!(
+            "{event_name} event not found in transaction receipt \
+             (expected emitter {expected_emitter}; receipt contains no logs)"
+        );
+    }
+
+    let mut emitters: Vec<Address> = Vec::new();
+    for log in logs {
+        if !emitters.contains(&log.address()) {
+            emitters.push(log.address());
+        }
+    }
+    let emitters = emitters
+        .iter()
+        .map(|a| a.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{event_name} event not found in transaction receipt \
+         (expected emitter {expected_emitter}; receipt contains {} log(s) emitted by [{emitters}])",
+        logs.len()
+    )
 }
 
 /// Parse the IndexUpdated event from a beacon transaction receipt.
@@ -18,23 +121,21 @@ pub fn parse_index_updated_event(
     receipt: &alloy::rpc::types::TransactionReceipt,
     beacon_address: Address,
 ) -> Result<U256, String> {
-    for log in receipt.logs().iter() {
-        if log.address() == beacon_address
-            && let Ok(decoded_log) = log.log_decode::<IBeacon::IndexUpdated>()
-        {
-            let index = decoded_log.inner.data.index;
-            tracing::info!(
-                "Successfully parsed IndexUpdated event - new index: {}",
-                index
-            );
-            return Ok(index);
-        }
+    ensure_receipt_succeeded(receipt, "Beacon update")?;
+    if let Some(index) = logs_emitted_by(receipt, beacon_address)
+        .find_map(|log| log.log_decode::<IBeacon::IndexUpdated>().ok())
+        .map(|decoded| decoded.inner.data.index)
+    {
+        tracing::info!(
+            "Successfully parsed IndexUpdated event - new index: {}",
+            index
+        );
+        return Ok(index);
     }
 
-    let error_msg = "IndexUpdated event not found in transaction receipt";
+    let error_msg = event_not_found_error("IndexUpdated", beacon_address, receipt);
     tracing::error!("{}", error_msg);
-    tracing::error!("Total logs in receipt: {}", receipt.logs().len());
-    Err(error_msg.to_string())
+    Err(error_msg)
 }
 
 /// Parse the `PerpCreated` event emitted by `PerpFactory.createPerp`. perpcity-contracts@v0.1.0.
@@ -42,27 +143,19 @@ pub fn parse_perp_created_event(
     receipt: &alloy::rpc::types::TransactionReceipt,
     perp_factory_address: Address,
 ) -> Result<PerpCreatedEvent, String> {
-    for log in receipt.logs() {
-        if log.address() == perp_factory_address
-            && let Ok(decoded) = log.log_decode::<IPerpFactory::PerpCreated>()
-        {
-            let data = decoded.inner.data;
-            tracing::info!(
-                "Successfully parsed PerpCreated event - perp: {}, pool_id: {}",
-                data.perp,
-                data.poolId
-            );
-            return Ok(PerpCreatedEvent {
-                perp: data.perp,
-                pool_id: data.poolId,
-                initial_index: data.initialIndex,
-                sqrt_price_x96: U256::from(data.sqrtPriceX96),
-                tick: data.tick.as_i32(),
-            });
-        }
+    ensure_receipt_succeeded(receipt, "createPerp")?;
+    if let Some(event) =
+        logs_emitted_by(receipt, perp_factory_address).find_map(decode_perp_created)
+    {
+        tracing::info!(
+            "Successfully parsed PerpCreated event - perp: {}, pool_id: {}",
+            event.perp,
+            event.pool_id
+        );
+        return Ok(event);
     }
 
-    let msg = "PerpCreated event not found in transaction receipt".to_string();
+    let msg = event_not_found_error("PerpCreated", perp_factory_address, receipt);
     tracing::error!("{}", msg);
     Err(msg)
 }
@@ -73,17 +166,131 @@ pub fn parse_maker_opened_event(
     receipt: &alloy::rpc::types::TransactionReceipt,
     perp_address: Address,
 ) -> Result<U256, String> {
-    for log in receipt.logs() {
-        if log.address() == perp_address
-            && let Ok(decoded) = log.log_decode::<IPerp::MakerOpened>()
-        {
-            return Ok(decoded.inner.data.posId);
-        }
+    ensure_receipt_succeeded(receipt, "openMaker")?;
+    if let Some(pos_id) = logs_emitted_by(receipt, perp_address)
+        .find_map(|log| log.log_decode::<IPerp::MakerOpened>().ok())
+        .map(|decoded| decoded.inner.data.posId)
+    {
+        return Ok(pos_id);
     }
 
-    let msg = "MakerOpened event not found in transaction receipt".to_string();
+    let msg = event_not_found_error("MakerOpened", perp_address, receipt);
     tracing::error!("{}", msg);
     Err(msg)
 }
 
+/// Sum the ERC20 `Transfer` amounts of `token` from `from` to `to` in a receipt. Used to read
+/// back how much USDC a Perp paid out, since v0.1.0 emits no event for it.
+pub fn sum_erc20_transfers(
+    receipt: &alloy::rpc::types::TransactionReceipt,
+    token: Address,
+    from: Address,
+    to: Address,
+) -> U256 {
+    logs_emitted_by(receipt, token)
+        .filter_map(|log| log.log_decode::<IERC20::Transfer>().ok())
+        .map(|decoded| decoded.inner.data)
+        .filter(|transfer| transfer.from == from && transfer.to == to)
+        .fold(U256::ZERO, |total, transfer| {
+            total.saturating_add(transfer.value)
+        })
+}
+
+/// Blocks per `eth_getLogs` request in chunked log scans, read from `LOG_SCAN_CHUNK_BLOCKS`.
+/// `0` scans the whole range in one request.
+pub fn log_scan_chunk_blocks_from_env() -> u64 {
+    std::env::var("LOG_SCAN_CHUNK_BLOCKS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_LOG_SCAN_CHUNK_BLOCKS)
+}
+
+/// Split `from..=to` into consecutive inclusive ranges of at most `chunk_size` blocks.
+/// A `chunk_size` of 0 yields the whole range at once; `from > to` yields nothing.
+pub fn log_scan_chunks(from: u64, to: u64, chunk_size: u64) -> impl Iterator<Item = (u64, u64)> {
+    let mut next = (from <= to).then_some(from);
+    std::iter::from_fn(move || {
+        let start = next?;
+        let end = match chunk_size {
+            0 => to,
+            size => to.min(start.saturating_add(size - 1)),
+        };
+        next = (end < to).then(|| end + 1);
+        Some((start, end))
+    })
+}
+
+/// Stream the logs matching `filter` in blocks `from..=to`, decoded by `decode` (logs it
+/// returns `None` for are skipped), fetching `chunk_size` blocks per `eth_getLogs`.
+///
+/// Providers cap the blocks or results one `eth_getLogs` may cover ("query returned more
+/// than 10000 results"), so long ranges are read chunk by chunk, in order, each retried
+/// per `retry` on transient errors. Chunks are fetched only as the stream is polled, so a caller that
+/// stops at the first match skips the rest of the range. The filter's own block range is
+/// ignored.
+pub fn scan_logs_chunked<'a, T: 'a>(
+    provider: &'a ReadOnlyProvider,
+    retry: &ReadRetryPolicy,
+    filter: &'a Filter,
+    from: u64,
+    to: u64,
+    chunk_size: u64,
+    decode: impl Fn(&Log) -> Option<T> + 'a,
+) -> impl Stream<Item = Result<T, String>> + 'a {
+    scan_chunked(
+        from,
+        to,
+        chunk_size,
+        *retry,
+        move |start, end| {
+            let chunk = filter.clone().from_block(start).to_block(end);
+            async move { provider.get_logs(&chunk).await }
+        },
+        decode,
+    )
+}
+
+/// [`scan_logs_chunked`] over any log source: `fetch(start, end)` returns the logs of
+/// blocks `start..=end` and is retried per `retry`. The stream ends after the first
+/// chunk that still fails.
+pub fn scan_chunked<'a, T, E, F, Fut, D>(
+    from: u64,
+    to: u64,
+    chunk_size: u64,
+    retry: ReadRetryPolicy,
+    fetch: F,
+    decode: D,
+) -> impl Stream<Item = Result<T, String>> + 'a
+where
+    T: 'a,
+    E: std::fmt::Display + 'a,
+    F: Fn(u64, u64) -> Fut + 'a,
+    Fut: Future<Output = Result<Vec<Log>, E>> + 'a,
+    D: Fn(&Log) -> Option<T> + 'a,
+{
+    let chunks = log_scan_chunks(from, to, chunk_size);
+    let state = (chunks, fetch, decode, VecDeque::new(), false);
+    stream::unfold(
+        state,
+        move |(mut chunks, fetch, decode, mut pending, failed)| async move {
+            loop {
+                if let Some(item) = pending.pop_front() {
+                    return Some((Ok(item), (chunks, fetch, decode, pending, failed)));
+                }
+                if failed {
+                    return None;
+                }
+                let (start, end) = chunks.next()?;
+                match retry_read(&retry, "get_logs", || fetch(start, end)).await {
+                    Ok(logs) => pending.extend(logs.iter().filter_map(&decode)),
+                    Err(e) => {
+                        let error = format!("get_logs for blocks {start}..={end} failed: {e}");
+                        return Some((Err(error), (chunks, fetch, decode, pending, true)));
+                    }
+                }
+            }
+        },
+    )
+}
+
 // Tests moved to tests/unit_tests/transaction_events_tests.rs
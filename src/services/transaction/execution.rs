@@ -2,10 +2,24 @@
 //!
 //! This module provides helper functions for transaction execution:
 //! - `is_nonce_error`: Detect nonce-related errors in error messages
+//! - `retry_once_on_nonce_error`: Resync the nonce and resend once after a nonce error
+//! - `AttemptBudget`: Cap the total RPC attempts for one logical operation
+//! - `wait_for_receipt`: Wait for a sent transaction's receipt, polling on-chain on timeout
 //!
 //! Note: Transaction serialization is now handled by Redis-based distributed
 //! locks in the wallet module. See `WalletLock` for details.
 
+use alloy::network::Ethereum;
+use alloy::primitives::B256;
+use alloy::providers::{PendingTransactionBuilder, Provider};
+use alloy::rpc::types::TransactionReceipt;
+use std::time::Duration;
+use tokio::time::timeout;
+
+use crate::config::ConfigReader;
+use crate::models::AppState;
+use crate::services::error::BeaconError;
+
 /// Detect nonce-related errors from error messages
 ///
 /// This helper function checks if an error message indicates a nonce-related issue
@@ -46,4 +60,349 @@ pub fn is_insufficient_funds_error(error_msg: &str) -> bool {
         || error_lower.contains("gas required exceeds allowance")
 }
 
+/// Send a transaction, retrying once after a nonce error.
+///
+/// When `send` fails with an error [`is_nonce_error`] recognizes, `resync` resets the
+/// wallet's nonce to the chain's pending count (`WalletHandle::resync_nonce`) and `send`
+/// runs exactly once more. The node rejected the first attempt, so resending cannot
+/// double-submit. A nonce error on the retry resyncs again before it is returned, so the
+/// wallet's next operation starts clean.
+///
+/// # Arguments
+/// * `label` - Operation name for logs, e.g. `"registerBeacon"`
+/// * `send` - Builds and sends the transaction; called at most twice
+/// * `resync` - Resets the sending wallet's nonce
+pub async fn retry_once_on_nonce_error<T, E, S, SFut, R, RFut>(
+    label: &str,
+    mut send: S,
+    mut resync: R,
+) -> Result<T, E>
+where
+    E: std::fmt::Display,
+    S: FnMut() -> SFut,
+    SFut: Future<Output = Result<T, E>>,
+    R: FnMut() -> RFut,
+    RFut: Future<Output = ()>,
+{
+    match send().await {
+        Err(e) if is_nonce_error(&e.to_string()) => {
+            tracing::warn!("{label}: nonce error ({e}); resyncing nonce and retrying once");
+            resync().await;
+            let retried = send().await;
+            if let Err(e) = &retried
+                && is_nonce_error(&e.to_string())
+            {
+                tracing::warn!("{label}: nonce error again after resync ({e})");
+                resync().await;
+            }
+            retried
+        }
+        result => result,
+    }
+}
+
+/// Default total attempt budget: one primary `get_receipt()` plus three
+/// progressive on-chain fallback lookups — the historical 15s/30s/60s chain.
+pub const DEFAULT_RPC_MAX_TOTAL_ATTEMPTS: u32 = 4;
+
+/// Progressive per-attempt timeouts for on-chain fallback lookups. Attempts
+/// beyond the end of the schedule reuse the last entry.
+pub const FALLBACK_TIMEOUTS_SECS: [u64; 3] = [15, 30, 60];
+
+/// Total attempt budget shared across the primary and fallback RPC calls of
+/// one logical operation (send → `get_receipt()` → on-chain polling).
+///
+/// Fallback paths used to each carry their own retry counter, so nested
+/// loops could multiply the total work. Every attempt, primary or fallback,
+/// now draws from a single budget sized by `RPC_MAX_TOTAL_ATTEMPTS`.
+#[derive(Debug, Clone)]
+pub struct AttemptBudget {
+    max: u32,
+    used: u32,
+}
+
+impl AttemptBudget {
+    /// Create a budget allowing `max` attempts in total (minimum 1).
+    pub fn new(max: u32) -> Self {
+        Self {
+            max: max.max(1),
+            used: 0,
+        }
+    }
+
+    /// Record one attempt. Returns `false` (and records nothing) once the
+    /// budget is exhausted.
+    pub fn try_consume(&mut self) -> bool {
+        if self.used >= self.max {
+            return false;
+        }
+        self.used += 1;
+        true
+    }
+
+    /// Attempts recorded so far.
+    pub fn used(&self) -> u32 {
+        self.used
+    }
+
+    /// Attempts still available.
+    pub fn remaining(&self) -> u32 {
+        self.max - self.used
+    }
+
+    /// Total attempts allowed.
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+
+    /// Whether any attempts remain.
+    pub fn is_exhausted(&self) -> bool {
+        self.used >= self.max
+    }
+
+    /// Timeout for the next fallback lookup, indexed by how many fallback
+    /// attempts (excluding the primary) have already been made.
+    pub fn fallback_timeout_secs(fallback_attempt: usize) -> u64 {
+        let idx = fallback_attempt.min(FALLBACK_TIMEOUTS_SECS.len() - 1);
+        FALLBACK_TIMEOUTS_SECS[idx]
+    }
+}
+
+/// Default pause after the first on-chain receipt lookup that found nothing.
+pub const DEFAULT_RECEIPT_POLL_INTERVAL_SECS: u64 = 5;
+
+/// Default cap on the pause between receipt lookups as it doubles.
+pub const DEFAULT_RECEIPT_POLL_MAX_INTERVAL_SECS: u64 = 30;
+
+/// Default timeout of the initial `get_receipt()`; callers pick their own with
+/// [`ReceiptWaitConfig::with_initial_timeout`].
+pub const DEFAULT_RECEIPT_INITIAL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How [`wait_for_receipt`] waits for one transaction.
+#[derive(Debug, Clone)]
+pub struct ReceiptWaitConfig {
+    /// Timeout of the initial `get_receipt()` on the pending transaction.
+    pub initial_timeout: Duration,
+    /// Progressive timeouts of the on-chain lookups that follow; lookups beyond the end
+    /// reuse the last entry.
+    pub lookup_timeouts: Vec<Duration>,
+    /// Pause after the first lookup that found no receipt or timed out. Each further
+    /// pause doubles, up to `max_poll_interval`.
+    pub poll_interval: Duration,
+    /// Longest pause between lookups.
+    pub max_poll_interval: Duration,
+    /// Attempts shared by the initial wait and every lookup.
+    pub max_attempts: u32,
+}
+
+impl Default for ReceiptWaitConfig {
+    fn default() -> Self {
+        Self::new(
+            Duration::from_secs(DEFAULT_RECEIPT_POLL_INTERVAL_SECS),
+            Duration::from_secs(DEFAULT_RECEIPT_POLL_MAX_INTERVAL_SECS),
+            DEFAULT_RPC_MAX_TOTAL_ATTEMPTS,
+        )
+    }
+}
+
+impl ReceiptWaitConfig {
+    /// Wait [`DEFAULT_RECEIPT_INITIAL_TIMEOUT`] for `get_receipt()`, then poll with the
+    /// [`FALLBACK_TIMEOUTS_SECS`] schedule within `max_attempts` (minimum 1), pausing
+    /// `poll_interval` and doubling up to `max_poll_interval` (raised to the interval if
+    /// below it).
+    pub fn new(poll_interval: Duration, max_poll_interval: Duration, max_attempts: u32) -> Self {
+        Self {
+            initial_timeout: DEFAULT_RECEIPT_INITIAL_TIMEOUT,
+            lookup_timeouts: FALLBACK_TIMEOUTS_SECS
+                .iter()
+                .map(|secs| Duration::from_secs(*secs))
+                .collect(),
+            poll_interval,
+            max_poll_interval: max_poll_interval.max(poll_interval),
+            max_attempts: AttemptBudget::new(max_attempts).max(),
+        }
+    }
+
+    /// Read `RECEIPT_POLL_INTERVAL_SECS`, `RECEIPT_POLL_MAX_INTERVAL_SECS` and
+    /// `RPC_MAX_TOTAL_ATTEMPTS`, using the defaults when unset. An unparseable value is
+    /// recorded on `config` and stops startup.
+    pub fn from_config(config: &mut ConfigReader) -> Self {
+        Self::new(
+            Duration::from_secs(config.parse_or(
+                "RECEIPT_POLL_INTERVAL_SECS",
+                DEFAULT_RECEIPT_POLL_INTERVAL_SECS,
+            )),
+            Duration::from_secs(config.parse_or(
+                "RECEIPT_POLL_MAX_INTERVAL_SECS",
+                DEFAULT_RECEIPT_POLL_MAX_INTERVAL_SECS,
+            )),
+            config.parse_or("RPC_MAX_TOTAL_ATTEMPTS", DEFAULT_RPC_MAX_TOTAL_ATTEMPTS),
+        )
+    }
+
+    /// This configuration with `initial_timeout` for the first `get_receipt()`.
+    pub fn with_initial_timeout(&self, initial_timeout: Duration) -> Self {
+        Self {
+            initial_timeout,
+            ..self.clone()
+        }
+    }
+
+    /// Pause after the `retry`-th lookup that found nothing (0-based):
+    /// `poll_interval * 2^retry`, capped at `max_poll_interval`.
+    pub fn poll_delay(&self, retry: u32) -> Duration {
+        self.poll_interval
+            .checked_mul(2u32.saturating_pow(retry))
+            .unwrap_or(Duration::MAX)
+            .min(self.max_poll_interval)
+    }
+
+    /// Timeout of the `lookup_attempt`-th on-chain lookup (0-based).
+    pub fn lookup_timeout(&self, lookup_attempt: usize) -> Duration {
+        match self.lookup_timeouts.get(lookup_attempt) {
+            Some(timeout) => *timeout,
+            None => self
+                .lookup_timeouts
+                .last()
+                .copied()
+                .unwrap_or(self.initial_timeout),
+        }
+    }
+}
+
+/// Wait for the receipt of a sent transaction.
+///
+/// Waits `config.initial_timeout` on the pending transaction's `get_receipt()`. If that
+/// fails or times out the transaction may still land, so the receipt is then looked up
+/// on-chain (cache first, then the primary endpoint failing over to `RPC_URLS`) until it
+/// appears or `config.max_attempts` is spent. A reverted transaction is still a receipt;
+/// callers check `status()`.
+pub async fn wait_for_receipt(
+    state: &AppState,
+    pending_tx: PendingTransactionBuilder<Ethereum>,
+    label: &str,
+    config: &ReceiptWaitConfig,
+) -> Result<TransactionReceipt, BeaconError> {
+    let tx_hash = *pending_tx.tx_hash();
+    wait_for_receipt_with(
+        label,
+        tx_hash,
+        pending_tx.get_receipt(),
+        || fetch_receipt(state, tx_hash),
+        config,
+    )
+    .await
+}
+
+/// [`wait_for_receipt`] over any receipt source: `pending` is the initial wait and
+/// `lookup` one on-chain lookup, where `Ok(None)` means not mined yet.
+///
+/// A failed lookup ends the wait; polling an endpoint that errors would only spend the
+/// budget. Running out of attempts is [`BeaconError::Timeout`].
+pub async fn wait_for_receipt_with<R, E, P, L, LFut>(
+    label: &str,
+    tx_hash: B256,
+    pending: P,
+    lookup: L,
+    config: &ReceiptWaitConfig,
+) -> Result<R, BeaconError>
+where
+    E: std::fmt::Display,
+    P: Future<Output = Result<R, E>>,
+    L: Fn() -> LFut,
+    LFut: Future<Output = Result<Option<R>, String>>,
+{
+    wait_for_receipt_with_sleep(label, tx_hash, pending, lookup, config, tokio::time::sleep).await
+}
+
+/// [`wait_for_receipt_with`] pausing between lookups through `sleep`, so tests can
+/// observe the backoff schedule without waiting it out.
+pub async fn wait_for_receipt_with_sleep<R, E, P, L, LFut, S, SFut>(
+    label: &str,
+    tx_hash: B256,
+    pending: P,
+    lookup: L,
+    config: &ReceiptWaitConfig,
+    sleep: S,
+) -> Result<R, BeaconError>
+where
+    E: std::fmt::Display,
+    P: Future<Output = Result<R, E>>,
+    L: Fn() -> LFut,
+    LFut: Future<Output = Result<Option<R>, String>>,
+    S: Fn(Duration) -> SFut,
+    SFut: Future<Output = ()>,
+{
+    let mut budget = AttemptBudget::new(config.max_attempts);
+    budget.try_consume();
+    match timeout(config.initial_timeout, pending).await {
+        Ok(Ok(receipt)) => return Ok(receipt),
+        Ok(Err(e)) => {
+            tracing::warn!("get_receipt() failed for {label} tx {tx_hash}: {e}; polling on-chain");
+        }
+        Err(_) => {
+            tracing::warn!(
+                "get_receipt() timed out for {label} tx {tx_hash} after {:?}; polling on-chain",
+                config.initial_timeout
+            );
+        }
+    }
+
+    let mut lookup_attempt = 0usize;
+    let mut retry = 0u32;
+    while budget.try_consume() {
+        let lookup_timeout = config.lookup_timeout(lookup_attempt);
+        lookup_attempt += 1;
+        match timeout(lookup_timeout, lookup()).await {
+            Ok(Ok(Some(receipt))) => return Ok(receipt),
+            Ok(Err(e)) => {
+                let msg = format!("Failed to query {label} receipt {tx_hash}: {e}");
+                tracing::error!("{}", msg);
+                return Err(BeaconError::RpcUnavailable(msg));
+            }
+            Ok(Ok(None)) | Err(_) => {
+                if budget.is_exhausted() {
+                    break;
+                }
+                let delay = config.poll_delay(retry);
+                retry += 1;
+                tracing::warn!(
+                    "{label} tx {tx_hash} not yet confirmed (attempt {}/{}), retrying in {:?}...",
+                    budget.used(),
+                    budget.max(),
+                    delay
+                );
+                sleep(delay).await;
+            }
+        }
+    }
+
+    let msg = format!(
+        "{label} receipt {tx_hash} not found after {} attempts",
+        budget.used()
+    );
+    tracing::error!("{}", msg);
+    Err(BeaconError::Timeout(msg))
+}
+
+/// Look up a receipt once: from the shared receipt cache, else from the primary endpoint
+/// (where the transaction was sent) failing over to the `RPC_URLS` fallbacks.
+pub async fn fetch_receipt(
+    state: &AppState,
+    tx_hash: B256,
+) -> Result<Option<TransactionReceipt>, String> {
+    let endpoints = &state.provider.endpoints;
+    state
+        .provider
+        .receipts
+        .get_or_fetch(tx_hash, || {
+            endpoints
+                .sticky_with_fallback("eth_getTransactionReceipt", move |provider| async move {
+                    provider.get_transaction_receipt(tx_hash).await
+                })
+        })
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // Tests moved to tests/unit_tests/transaction_execution_tests.rs
@@ -1,5 +1,12 @@
+pub mod confirmations;
 pub mod events;
 pub mod execution;
+pub mod multicall;
+pub mod receipts;
+pub mod troubleshooting;
 
+pub use confirmations::*;
 pub use events::*;
 pub use execution::*;
+pub use receipts::ReceiptCache;
+pub use troubleshooting::*;
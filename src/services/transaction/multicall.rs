@@ -1,6 +1,269 @@
-//! DELETED — this module is intentionally empty and no longer compiled.
+//! Typed Multicall3 reads
 //!
-//! The old multicall helpers here had no production callers (the live batch
-//! path is `services/beacon/batch.rs`) and `parse_multicall_results` was a
-//! placeholder that always returned `Ok(vec![])`. The `mod` declaration was
-//! removed from `services/transaction/mod.rs`; delete this file outright.
+//! Batch many calls of one kind (`index()` on every beacon, `balanceOf` on every
+//! wallet) into a single eth_call and get one decoded result per call, in order:
+//! - [`multicall_try`] wraps `tryAggregate(requireSuccess, calls)`. With
+//!   `require_success` a single failing call reverts the whole batch; without it
+//!   failures come back per call.
+//! - [`multicall3`] wraps `aggregate3`, where each call carries its own
+//!   `allowFailure` flag (the same one for every call here).
+//!
+//! Both fail up front when no `MULTICALL3_ADDRESS` is configured, so callers can fall
+//! back to sequential reads.
+//!
+//! Before sending an `aggregate3` transaction, [`prevalidate_aggregate3`] can run each
+//! sub-call alone as an `eth_call` and leave out the ones that would revert.
+
+use std::future::Future;
+
+use alloy::primitives::Address;
+use alloy::providers::Provider;
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol_types::{Revert, SolCall, SolError};
+use alloy::transports::RpcError;
+
+use crate::routes::IMulticall3;
+use crate::services::perp::validation::try_decode_revert_reason;
+use crate::services::rpc::{ReadRetryPolicy, retry_read};
+
+/// Error for the helpers when `MULTICALL3_ADDRESS` is unset.
+pub const MULTICALL3_NOT_CONFIGURED: &str = "Multicall3 is not configured (MULTICALL3_ADDRESS)";
+
+/// `tryAggregate` entries for `calls`, one per `(target, call)` pair.
+pub fn try_aggregate_calls<C: SolCall>(calls: &[(Address, C)]) -> Vec<IMulticall3::Call> {
+    calls
+        .iter()
+        .map(|(target, call)| IMulticall3::Call {
+            target: *target,
+            callData: call.abi_encode().into(),
+        })
+        .collect()
+}
+
+/// `aggregate3` entries for `calls`, each with `allow_failure`.
+pub fn aggregate3_calls<C: SolCall>(
+    calls: &[(Address, C)],
+    allow_failure: bool,
+) -> Vec<IMulticall3::Call3> {
+    calls
+        .iter()
+        .map(|(target, call)| IMulticall3::Call3 {
+            target: *target,
+            allowFailure: allow_failure,
+            callData: call.abi_encode().into(),
+        })
+        .collect()
+}
+
+/// Decode one Multicall3 entry as the return value of `C`. A failed entry becomes an
+/// error carrying the revert reason when it is an `Error(string)`, or the raw revert data.
+pub fn decode_call_result<C: SolCall>(result: &IMulticall3::Result) -> Result<C::Return, String> {
+    if !result.success {
+        return Err(if result.returnData.is_empty() {
+            format!("{} reverted", C::SIGNATURE)
+        } else if let Ok(revert) = Revert::abi_decode(&result.returnData) {
+            format!("{} reverted: {}", C::SIGNATURE, revert.reason)
+        } else {
+            format!(
+                "{} reverted (revert data: {})",
+                C::SIGNATURE,
+                result.returnData
+            )
+        });
+    }
+    C::abi_decode_returns(&result.returnData).map_err(|e| {
+        format!(
+            "{} returned {} undecodable bytes: {e}",
+            C::SIGNATURE,
+            result.returnData.len()
+        )
+    })
+}
+
+/// Decode every entry of a Multicall3 result list, checking that there is one per call.
+pub fn decode_call_results<C: SolCall>(
+    results: &[IMulticall3::Result],
+    expected: usize,
+) -> Result<Vec<Result<C::Return, String>>, String> {
+    if results.len() != expected {
+        return Err(format!(
+            "expected {expected} multicall results, got {}",
+            results.len()
+        ));
+    }
+    Ok(results.iter().map(decode_call_result::<C>).collect())
+}
+
+/// Run `calls` through `tryAggregate(require_success, ...)` at `multicall3` as one
+/// eth_call, retried per `retry`. The outer error covers a missing address, a failed RPC
+/// call, and (with `require_success`) any reverting call; otherwise each call gets its
+/// own result.
+pub async fn multicall_try<P: Provider, C: SolCall>(
+    provider: &P,
+    retry: &ReadRetryPolicy,
+    multicall3: Option<Address>,
+    require_success: bool,
+    calls: &[(Address, C)],
+) -> Result<Vec<Result<C::Return, String>>, String> {
+    let Some(multicall3) = multicall3 else {
+        return Err(MULTICALL3_NOT_CONFIGURED.to_string());
+    };
+    if calls.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let contract = IMulticall3::new(multicall3, provider);
+    let entries = try_aggregate_calls(calls);
+    let (contract, entries) = (&contract, &entries);
+    let results = retry_read(retry, "Multicall3.tryAggregate", move || async move {
+        contract
+            .tryAggregate(require_success, entries.clone())
+            .call()
+            .await
+    })
+    .await
+    .map_err(|e| format!("tryAggregate via {multicall3} failed: {e}"))?;
+
+    decode_call_results::<C>(&results, calls.len())
+}
+
+/// Run `calls` through `aggregate3` at `multicall3` as one eth_call, each call with
+/// `allow_failure`. Same error split as [`multicall_try`]: without `allow_failure` one
+/// reverting call fails the whole batch.
+pub async fn multicall3<P: Provider, C: SolCall>(
+    provider: &P,
+    retry: &ReadRetryPolicy,
+    multicall3: Option<Address>,
+    allow_failure: bool,
+    calls: &[(Address, C)],
+) -> Result<Vec<Result<C::Return, String>>, String> {
+    let Some(multicall3) = multicall3 else {
+        return Err(MULTICALL3_NOT_CONFIGURED.to_string());
+    };
+    if calls.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let contract = IMulticall3::new(multicall3, provider);
+    let entries = aggregate3_calls(calls, allow_failure);
+    let (contract, entries) = (&contract, &entries);
+    let results = retry_read(retry, "Multicall3.aggregate3", move || async move {
+        contract.aggregate3(entries.clone()).call().await
+    })
+    .await
+    .map_err(|e| format!("aggregate3 via {multicall3} failed: {e}"))?;
+
+    decode_call_results::<C>(&results, calls.len())
+}
+
+/// An `aggregate3` batch after [`prevalidate_aggregate3`]: the calls still worth sending
+/// and what happened to the rest.
+#[derive(Debug, Clone, Default)]
+pub struct PrevalidatedBatch {
+    /// Calls to send, in their original order.
+    pub calls: Vec<IMulticall3::Call3>,
+    /// Original position of each entry in `calls`.
+    pub kept: Vec<usize>,
+    /// Calls left out because running them alone reverted: original position and reason.
+    pub predicted_reverts: Vec<(usize, String)>,
+    /// Calls kept without a prediction because their `eth_call` failed for another reason
+    /// (e.g. the RPC endpoint was down): original position and error.
+    pub unchecked: Vec<(usize, String)>,
+}
+
+impl PrevalidatedBatch {
+    /// Every call kept, nothing checked.
+    fn unvalidated(calls: Vec<IMulticall3::Call3>) -> Self {
+        Self {
+            kept: (0..calls.len()).collect(),
+            calls,
+            ..Self::default()
+        }
+    }
+}
+
+/// Run each call of an `aggregate3` batch on its own through `simulate` and drop those
+/// predicted to revert. `simulate` answers `Ok(None)` when the call would succeed,
+/// `Ok(Some(reason))` when it would revert, and `Err` when it could not tell. Without
+/// `prevalidate` nothing is simulated and every call is kept.
+pub async fn prevalidate_with<F, Fut>(
+    calls: Vec<IMulticall3::Call3>,
+    prevalidate: bool,
+    simulate: F,
+) -> PrevalidatedBatch
+where
+    F: Fn(&IMulticall3::Call3) -> Fut,
+    Fut: Future<Output = Result<Option<String>, String>>,
+{
+    if !prevalidate {
+        return PrevalidatedBatch::unvalidated(calls);
+    }
+
+    let mut batch = PrevalidatedBatch::default();
+    for (index, call) in calls.into_iter().enumerate() {
+        match simulate(&call).await {
+            Ok(None) => {}
+            Ok(Some(reason)) => {
+                tracing::warn!(
+                    "Leaving call {index} to {} out of the batch: predicted to revert ({reason})",
+                    call.target
+                );
+                batch.predicted_reverts.push((index, reason));
+                continue;
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Could not prevalidate call {index} to {} ({e}); keeping it in the batch",
+                    call.target
+                );
+                batch.unchecked.push((index, e));
+            }
+        }
+        batch.kept.push(index);
+        batch.calls.push(call);
+    }
+    batch
+}
+
+/// Run one `aggregate3` sub-call alone as an `eth_call` sent from `multicall3`, which is
+/// the `msg.sender` the call sees inside the batch. See [`prevalidate_with`] for the result.
+pub async fn simulate_call3<P: Provider>(
+    provider: &P,
+    retry: &ReadRetryPolicy,
+    multicall3: Address,
+    call: &IMulticall3::Call3,
+) -> Result<Option<String>, String> {
+    let tx = TransactionRequest::default()
+        .from(multicall3)
+        .to(call.target)
+        .input(call.callData.clone().into());
+    let tx = &tx;
+    match retry_read(retry, "eth_call (prevalidate)", move || async move {
+        provider.call(tx.clone()).await
+    })
+    .await
+    {
+        Ok(_) => Ok(None),
+        Err(RpcError::ErrorResp(payload)) => Ok(Some(
+            try_decode_revert_reason(&payload).unwrap_or_else(|| payload.message.to_string()),
+        )),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// [`prevalidate_with`] against the chain: each call is simulated with [`simulate_call3`].
+/// Costs one `eth_call` per call, in exchange for not sending batches (or sub-calls) that
+/// would revert; use it when `allowFailure` cannot absorb a failing call.
+pub async fn prevalidate_aggregate3<P: Provider>(
+    provider: &P,
+    retry: &ReadRetryPolicy,
+    multicall3: Address,
+    calls: Vec<IMulticall3::Call3>,
+    prevalidate: bool,
+) -> PrevalidatedBatch {
+    prevalidate_with(calls, prevalidate, |call| {
+        let call = call.clone();
+        async move { simulate_call3(provider, retry, multicall3, &call).await }
+    })
+    .await
+}
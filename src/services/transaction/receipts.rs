@@ -0,0 +1,153 @@
+//! Short-lived cache of confirmed transaction receipts keyed by tx hash.
+//!
+//! The on-chain fallback of `wait_for_receipt` polls `eth_getTransactionReceipt`
+//! for the same hash on every retry, and concurrent operations may check the same
+//! hash at once. Once a receipt has been observed it is served from here until it
+//! expires, and concurrent lookups for one hash share a single RPC call. Only
+//! confirmed receipts are stored: a `None` (pending or dropped) is never cached, so
+//! the next lookup asks the chain again.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use alloy::primitives::B256;
+use alloy::rpc::types::TransactionReceipt;
+use tokio::sync::Mutex;
+
+/// How long a receipt is served from the cache.
+pub const DEFAULT_RECEIPT_CACHE_TTL_SECS: u64 = 300;
+/// Maximum cached receipts.
+pub const DEFAULT_RECEIPT_CACHE_MAX_ENTRIES: usize = 1000;
+
+struct CacheEntry<R> {
+    receipt: R,
+    stored_at: Instant,
+}
+
+/// TTL + capacity bounded receipt cache with per-hash single-flight lookups.
+pub struct ReceiptCache<R = TransactionReceipt> {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<B256, CacheEntry<R>>>,
+    /// One gate per hash with a lookup in flight; waiters re-check the cache after
+    /// the first lookup finishes instead of issuing their own.
+    in_flight: Mutex<HashMap<B256, Arc<Mutex<()>>>>,
+}
+
+impl<R: Clone> ReceiptCache<R> {
+    /// Create a cache that keeps receipts for `ttl`, holding at most `max_entries` (minimum 1).
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            ttl,
+            max_entries: max_entries.max(1),
+            entries: Mutex::new(HashMap::new()),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Return the cached receipt for `tx_hash` if it has not expired.
+    pub async fn get(&self, tx_hash: &B256) -> Option<R> {
+        let mut entries = self.entries.lock().await;
+        match entries.get(tx_hash) {
+            Some(entry) if entry.stored_at.elapsed() < self.ttl => Some(entry.receipt.clone()),
+            Some(_) => {
+                entries.remove(tx_hash);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Cache `receipt` under `tx_hash`, dropping expired entries and, if still full,
+    /// the oldest entry.
+    pub async fn insert(&self, tx_hash: B256, receipt: R) {
+        let mut entries = self.entries.lock().await;
+        let ttl = self.ttl;
+        entries.retain(|_, entry| entry.stored_at.elapsed() < ttl);
+        if entries.len() >= self.max_entries && !entries.contains_key(&tx_hash) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(hash, _)| *hash);
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            tx_hash,
+            CacheEntry {
+                receipt,
+                stored_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Return the receipt for `tx_hash`, calling `fetch` only on a cache miss.
+    ///
+    /// Concurrent calls for the same hash wait for the first one and reuse its receipt.
+    /// A confirmed (`Some`) result is cached; `None` and errors are passed through
+    /// uncached.
+    pub async fn get_or_fetch<E, F, Fut>(&self, tx_hash: B256, fetch: F) -> Result<Option<R>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<R>, E>>,
+    {
+        if let Some(receipt) = self.get(&tx_hash).await {
+            return Ok(Some(receipt));
+        }
+
+        let gate = self
+            .in_flight
+            .lock()
+            .await
+            .entry(tx_hash)
+            .or_default()
+            .clone();
+        let result = {
+            let _held = gate.lock().await;
+            match self.get(&tx_hash).await {
+                Some(receipt) => Ok(Some(receipt)),
+                None => {
+                    let result = fetch().await;
+                    if let Ok(Some(receipt)) = &result {
+                        self.insert(tx_hash, receipt.clone()).await;
+                    }
+                    result
+                }
+            }
+        };
+
+        // Clones of the gate are only taken under the in_flight lock, so the count is
+        // exact here: the map's copy plus ours means nobody is waiting on it.
+        let mut in_flight = self.in_flight.lock().await;
+        if Arc::strong_count(&gate) == 2 {
+            in_flight.remove(&tx_hash);
+        }
+        result
+    }
+
+    /// Number of live (unexpired) entries.
+    pub async fn len(&self) -> usize {
+        let entries = self.entries.lock().await;
+        entries
+            .values()
+            .filter(|entry| entry.stored_at.elapsed() < self.ttl)
+            .count()
+    }
+
+    /// Whether the cache holds no live entries.
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+}
+
+impl<R: Clone> Default for ReceiptCache<R> {
+    fn default() -> Self {
+        Self::new(
+            Duration::from_secs(DEFAULT_RECEIPT_CACHE_TTL_SECS),
+            DEFAULT_RECEIPT_CACHE_MAX_ENTRIES,
+        )
+    }
+}
@@ -0,0 +1,153 @@
+//! Troubleshooting reports for failed writes
+//!
+//! Instead of a block of individual `tracing::error!` hints per failure, a failed
+//! operation builds one [`TroubleshootingReport`]: the error is classified into an
+//! [`ErrorCategory`], which selects the probable causes and suggested actions. The
+//! report is logged as a single structured event and can be returned to the caller.
+
+use std::collections::BTreeMap;
+
+use super::execution::{is_insufficient_funds_error, is_nonce_error};
+use crate::models::{ErrorCategory, TroubleshootingReport};
+use crate::services::rpc::is_transient_rpc_error;
+
+/// Classify an error message. The first matching category wins, most specific first:
+/// an out-of-gas-funds send also mentions a revert, and a receipt timeout is also a
+/// transport error.
+pub fn classify_error(error_msg: &str) -> ErrorCategory {
+    let error_lower = error_msg.to_lowercase();
+    if error_lower.contains("failed to acquire wallet") {
+        ErrorCategory::WalletUnavailable
+    } else if is_insufficient_funds_error(error_msg) {
+        ErrorCategory::InsufficientFunds
+    } else if is_nonce_error(error_msg) {
+        ErrorCategory::NonceConflict
+    } else if error_lower.contains("revert") {
+        ErrorCategory::ContractExecutionReverted
+    } else if error_lower.contains("no deployed code") || error_lower.contains("not a contract") {
+        ErrorCategory::InvalidContractAddress
+    } else if error_lower.contains("timeout waiting")
+        || (error_lower.contains("receipt") && error_lower.contains("not found after"))
+    {
+        ErrorCategory::TransactionTimeout
+    } else if is_transient_rpc_error(error_msg) {
+        ErrorCategory::RpcConnectionError
+    } else {
+        ErrorCategory::Unknown
+    }
+}
+
+/// Probable causes and suggested actions for a category.
+fn hints(category: ErrorCategory) -> (&'static [&'static str], &'static [&'static str]) {
+    match category {
+        ErrorCategory::WalletUnavailable => (
+            &[
+                "Every pool wallet is locked by in-flight operations",
+                "The beacon is designated to a wallet that is busy or excluded",
+                "Redis is unreachable, so no wallet lock can be taken",
+            ],
+            &[
+                "Retry once in-flight operations finish",
+                "Check GET /wallet_pool/status for locked wallets and designations",
+                "Add wallets to the pool if this happens under normal load",
+            ],
+        ),
+        ErrorCategory::InsufficientFunds => (
+            &["The sending wallet cannot cover gas for this transaction"],
+            &[
+                "Top up the wallet with native gas token",
+                "Check WALLET_MIN_ETH_WEI so drained wallets are skipped",
+            ],
+        ),
+        ErrorCategory::NonceConflict => (
+            &[
+                "Another transaction from this wallet used the same nonce",
+                "The RPC node's pending nonce lags behind the chain",
+            ],
+            &["Retry the request; the wallet's nonce is resynced after this error"],
+        ),
+        ErrorCategory::ContractExecutionReverted => (
+            &[
+                "The contract rejected the call (see the decoded revert reason)",
+                "Module or factory addresses do not match the deployed contracts",
+                "Call parameters fail on-chain validation",
+            ],
+            &[
+                "Read the decoded revert reason in `error`",
+                "Compare configured contract addresses with .contracts-versions",
+                "Simulate the call with the same parameters before retrying",
+            ],
+        ),
+        ErrorCategory::InvalidContractAddress => (
+            &["The address has no contract code on this chain"],
+            &[
+                "Check the address and that it was deployed on this network",
+                "Confirm RPC_URL points at the intended chain",
+            ],
+        ),
+        ErrorCategory::TransactionTimeout => (
+            &[
+                "The transaction is still pending or was dropped from the mempool",
+                "Gas price is too low for current network conditions",
+            ],
+            &[
+                "Look up the transaction hash before retrying so it is not sent twice",
+                "Retry once the network is less congested",
+            ],
+        ),
+        ErrorCategory::RpcConnectionError => (
+            &[
+                "The RPC endpoint is rate limiting or temporarily unavailable",
+                "Network connectivity to the RPC endpoint is failing",
+            ],
+            &[
+                "Retry after a short delay",
+                "Check GET /health for open RPC circuit breakers",
+                "Configure an alternate endpoint in RPC_URLS",
+            ],
+        ),
+        ErrorCategory::Unknown => (
+            &["The error did not match a known failure pattern"],
+            &["Check the full error and the request's logs (by request id)"],
+        ),
+    }
+}
+
+/// Build the report for `operation` failing with `error`, with the addresses and
+/// parameters involved as `context`.
+pub fn troubleshooting_report<K, V>(
+    operation: &str,
+    error: &str,
+    context: impl IntoIterator<Item = (K, V)>,
+) -> TroubleshootingReport
+where
+    K: Into<String>,
+    V: ToString,
+{
+    let category = classify_error(error);
+    let (causes, actions) = hints(category);
+    TroubleshootingReport {
+        operation: operation.to_string(),
+        category,
+        error: error.to_string(),
+        probable_causes: causes.iter().map(|s| s.to_string()).collect(),
+        suggested_actions: actions.iter().map(|s| s.to_string()).collect(),
+        context: context
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.to_string()))
+            .collect::<BTreeMap<_, _>>(),
+    }
+}
+
+/// Log the report as one structured error event.
+pub fn log_troubleshooting_report(report: &TroubleshootingReport) {
+    tracing::error!(
+        target: "troubleshooting",
+        operation = %report.operation,
+        category = report.category.label(),
+        report = %serde_json::to_string(report).unwrap_or_default(),
+        "{} failed: {}",
+        report.operation,
+        report.error
+    );
+}
@@ -0,0 +1,346 @@
+//! Per-recipient rolling-window caps for guest wallet funding.
+//!
+//! The per-request `USDC_TRANSFER_LIMIT` / `ETH_TRANSFER_LIMIT` only bound a
+//! single call, so a client looping `/fund_guest_wallet` against one address
+//! could still drain the pool. This limiter caps the TOTAL USDC and ETH sent to
+//! each recipient within a rolling window (24h by default).
+//!
+//! A request's grant is counted when it is admitted ([`FundingRateLimiter::reserve`]),
+//! so concurrent requests for one recipient cannot each pass the check before
+//! either is recorded; a request that ends up sending nothing gives it back
+//! ([`FundingRateLimiter::release`]).
+//!
+//! Grants are recorded in Redis (a sorted set per recipient, scored by unix
+//! time) so every instance shares one view. When Redis is not configured — test
+//! stubs, local runs without a pool — or a Redis call fails, the limiter falls
+//! back to an in-memory log, which is per instance.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use alloy::primitives::Address;
+use redis::aio::ConnectionManager;
+use tokio::sync::Mutex;
+
+/// Rolling window length when `GUEST_FUNDING_WINDOW_SECS` is unset: 24 hours.
+pub const DEFAULT_GUEST_FUNDING_WINDOW_SECS: u64 = 24 * 60 * 60;
+/// Max USDC (6 decimals) per recipient per window when unset: 2000 USDC.
+pub const DEFAULT_GUEST_FUNDING_MAX_USDC: u128 = 2_000_000_000;
+/// Max ETH (wei) per recipient per window when unset: 0.02 ETH.
+pub const DEFAULT_GUEST_FUNDING_MAX_ETH_WEI: u128 = 20_000_000_000_000_000;
+
+/// One recorded funding grant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FundingGrant {
+    /// Unix seconds at which the grant was recorded.
+    pub at: u64,
+    pub usdc: u128,
+    pub eth: u128,
+}
+
+/// A grant counted by [`FundingRateLimiter::reserve`], to hand back to
+/// [`FundingRateLimiter::release`] if the funding sends nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FundingReservation {
+    recipient: Address,
+    grant: FundingGrant,
+    /// Sorted-set member holding the grant in Redis; `None` when held in memory.
+    member: Option<String>,
+}
+
+/// Why a funding request was refused by the rolling-window cap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FundingLimitExceeded {
+    /// Seconds until enough of the window has rolled off for this request to fit.
+    pub retry_after_secs: u64,
+    /// Human-readable description of the exhausted cap.
+    pub reason: String,
+}
+
+/// Decide whether `usdc` / `eth` still fit in the window ending at `now`.
+///
+/// `grants` may contain entries older than the window; they are ignored. On refusal
+/// the returned `retry_after_secs` is the earliest time at which expiring grants free
+/// enough room, so a client that waits exactly that long is accepted.
+pub fn evaluate_window(
+    grants: &[FundingGrant],
+    usdc: u128,
+    eth: u128,
+    max_usdc: u128,
+    max_eth: u128,
+    window_secs: u64,
+    now: u64,
+) -> Result<(), FundingLimitExceeded> {
+    let window_start = now.saturating_sub(window_secs);
+    let mut live: Vec<FundingGrant> = grants
+        .iter()
+        .copied()
+        .filter(|g| g.at > window_start)
+        .collect();
+    live.sort_by_key(|g| g.at);
+
+    let mut used_usdc: u128 = live.iter().map(|g| g.usdc).sum();
+    let mut used_eth: u128 = live.iter().map(|g| g.eth).sum();
+    let fits = |used_usdc: u128, used_eth: u128| {
+        used_usdc.saturating_add(usdc) <= max_usdc && used_eth.saturating_add(eth) <= max_eth
+    };
+    if fits(used_usdc, used_eth) {
+        return Ok(());
+    }
+
+    let reason = if used_usdc.saturating_add(usdc) > max_usdc {
+        format!(
+            "USDC funding cap reached for this address: {} of {} USDC used in the last {}h",
+            used_usdc / 1_000_000,
+            max_usdc / 1_000_000,
+            window_secs / 3600
+        )
+    } else {
+        format!(
+            "ETH funding cap reached for this address: {} of {} ETH used in the last {}h",
+            alloy::primitives::utils::format_ether(alloy::primitives::U256::from(used_eth)),
+            alloy::primitives::utils::format_ether(alloy::primitives::U256::from(max_eth)),
+            window_secs / 3600
+        )
+    };
+
+    // Walk grants oldest-first: the request fits once every grant up to and including
+    // the current one has expired, i.e. at `grant.at + window_secs`. If it never fits
+    // (the request alone exceeds the cap) report a full window.
+    let mut retry_after_secs = window_secs;
+    for grant in &live {
+        used_usdc -= grant.usdc;
+        used_eth -= grant.eth;
+        if fits(used_usdc, used_eth) {
+            retry_after_secs = (grant.at + window_secs).saturating_sub(now).max(1);
+            break;
+        }
+    }
+
+    Err(FundingLimitExceeded {
+        retry_after_secs,
+        reason,
+    })
+}
+
+/// Rolling-window funding caps keyed by recipient address.
+pub struct FundingRateLimiter {
+    max_usdc: u128,
+    max_eth: u128,
+    window: Duration,
+    redis: Option<(ConnectionManager, String)>,
+    memory: Mutex<HashMap<Address, Vec<FundingGrant>>>,
+}
+
+impl FundingRateLimiter {
+    /// In-memory limiter with explicit caps.
+    pub fn new(max_usdc: u128, max_eth: u128, window: Duration) -> Self {
+        Self {
+            max_usdc,
+            max_eth,
+            window,
+            redis: None,
+            memory: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Build from `GUEST_FUNDING_MAX_USDC_PER_WINDOW`, `GUEST_FUNDING_MAX_ETH_PER_WINDOW`
+    /// and `GUEST_FUNDING_WINDOW_SECS`, falling back to the defaults when unset or
+    /// unparseable.
+    pub fn from_env() -> Self {
+        fn parse<T: std::str::FromStr>(key: &str, default: T) -> T {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.trim().parse::<T>().ok())
+                .unwrap_or(default)
+        }
+        Self::new(
+            parse(
+                "GUEST_FUNDING_MAX_USDC_PER_WINDOW",
+                DEFAULT_GUEST_FUNDING_MAX_USDC,
+            ),
+            parse(
+                "GUEST_FUNDING_MAX_ETH_PER_WINDOW",
+                DEFAULT_GUEST_FUNDING_MAX_ETH_WEI,
+            ),
+            Duration::from_secs(parse(
+                "GUEST_FUNDING_WINDOW_SECS",
+                DEFAULT_GUEST_FUNDING_WINDOW_SECS,
+            )),
+        )
+    }
+
+    /// Share grants across instances through Redis, under `<prefix>funding:<address>`.
+    pub fn with_redis(mut self, conn: ConnectionManager, prefix: &str) -> Self {
+        self.redis = Some((conn, prefix.to_string()));
+        self
+    }
+
+    pub fn max_usdc(&self) -> u128 {
+        self.max_usdc
+    }
+
+    pub fn max_eth(&self) -> u128 {
+        self.max_eth
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Current unix time in seconds.
+    pub fn now_secs() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+
+    /// Count a grant of `usdc` / `eth` to `recipient` at `now` if it stays within the
+    /// caps. Check and record are one step — a Redis transaction, or under the in-memory
+    /// lock — so concurrent requests cannot both fit into the same remaining room.
+    pub async fn reserve(
+        &self,
+        recipient: Address,
+        usdc: u128,
+        eth: u128,
+        now: u64,
+    ) -> Result<FundingReservation, FundingLimitExceeded> {
+        let grant = FundingGrant { at: now, usdc, eth };
+        if let Some((conn, prefix)) = &self.redis {
+            match self
+                .redis_reserve(conn.clone(), prefix, recipient, grant)
+                .await
+            {
+                Ok(result) => return result,
+                Err(e) => tracing::warn!(
+                    "Funding limiter: Redis reserve failed for {recipient}, using in-memory: {e}"
+                ),
+            }
+        }
+        let window_start = now.saturating_sub(self.window.as_secs());
+        let mut memory = self.memory.lock().await;
+        let entry = memory.entry(recipient).or_default();
+        entry.retain(|g| g.at > window_start);
+        self.evaluate(entry, grant)?;
+        entry.push(grant);
+        Ok(FundingReservation {
+            recipient,
+            grant,
+            member: None,
+        })
+    }
+
+    /// Give back a reserved grant whose funding sent nothing. Failures are logged: the
+    /// grant then counts until it leaves the window.
+    pub async fn release(&self, reservation: &FundingReservation) {
+        match (&reservation.member, &self.redis) {
+            (Some(member), Some((conn, prefix))) => {
+                let mut conn = conn.clone();
+                let result: redis::RedisResult<()> = redis::cmd("ZREM")
+                    .arg(Self::redis_key(prefix, reservation.recipient))
+                    .arg(member)
+                    .query_async(&mut conn)
+                    .await;
+                if let Err(e) = result {
+                    tracing::warn!(
+                        "Funding limiter: failed to release grant for {}: {e}",
+                        reservation.recipient
+                    );
+                }
+            }
+            _ => {
+                // Equal grants are interchangeable, so dropping any one of them is exact.
+                let mut memory = self.memory.lock().await;
+                if let Some(grants) = memory.get_mut(&reservation.recipient)
+                    && let Some(index) = grants.iter().position(|g| *g == reservation.grant)
+                {
+                    grants.remove(index);
+                }
+            }
+        }
+    }
+
+    /// Whether `grant` fits on top of the recipient's earlier `grants`.
+    fn evaluate(
+        &self,
+        grants: &[FundingGrant],
+        grant: FundingGrant,
+    ) -> Result<(), FundingLimitExceeded> {
+        evaluate_window(
+            grants,
+            grant.usdc,
+            grant.eth,
+            self.max_usdc,
+            self.max_eth,
+            self.window.as_secs(),
+            grant.at,
+        )
+    }
+
+    fn redis_key(prefix: &str, recipient: Address) -> String {
+        format!("{prefix}funding:{}", recipient.to_string().to_lowercase())
+    }
+
+    /// Add `grant` and read the recipient's window in one transaction, then keep the
+    /// grant only if it fits on top of the grants added before it.
+    async fn redis_reserve(
+        &self,
+        mut conn: ConnectionManager,
+        prefix: &str,
+        recipient: Address,
+        grant: FundingGrant,
+    ) -> Result<Result<FundingReservation, FundingLimitExceeded>, String> {
+        let key = Self::redis_key(prefix, recipient);
+        let window_start = grant.at.saturating_sub(self.window.as_secs());
+        let member = format!("{}:{}:{}", grant.usdc, grant.eth, uuid::Uuid::new_v4());
+        let (members,): (Vec<(String, u64)>,) = redis::pipe()
+            .atomic()
+            .cmd("ZREMRANGEBYSCORE")
+            .arg(&key)
+            .arg("-inf")
+            .arg(window_start)
+            .ignore()
+            .cmd("ZADD")
+            .arg(&key)
+            .arg(grant.at)
+            .arg(&member)
+            .ignore()
+            .cmd("ZRANGEBYSCORE")
+            .arg(&key)
+            .arg(format!("({window_start}"))
+            .arg("+inf")
+            .arg("WITHSCORES")
+            .cmd("EXPIRE")
+            .arg(&key)
+            .arg(self.window.as_secs().max(1))
+            .ignore()
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to reserve funding grant: {e}"))?;
+
+        // Grants reserved by concurrent requests after this one are not in `members`:
+        // the transaction ran before their ZADD, and they see this grant instead.
+        let earlier: Vec<FundingGrant> = members
+            .into_iter()
+            .filter(|(m, _)| *m != member)
+            .filter_map(|(m, at)| {
+                // Member layout: "<usdc>:<eth>:<nonce>" (the nonce keeps equal grants distinct).
+                let mut parts = m.split(':');
+                let usdc = parts.next()?.parse().ok()?;
+                let eth = parts.next()?.parse().ok()?;
+                Some(FundingGrant { at, usdc, eth })
+            })
+            .collect();
+        let reservation = FundingReservation {
+            recipient,
+            grant,
+            member: Some(member),
+        };
+        if let Err(exceeded) = self.evaluate(&earlier, grant) {
+            self.release(&reservation).await;
+            return Ok(Err(exceeded));
+        }
+        Ok(Ok(reservation))
+    }
+}
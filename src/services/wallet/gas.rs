@@ -0,0 +1,215 @@
+//! Gas pricing and limits for signing providers
+//!
+//! Signing providers price transactions as EIP-1559 (`maxFeePerGas` /
+//! `maxPriorityFeePerGas`) by default. Some localnet and testnet nodes only accept
+//! legacy `gasPrice` transactions; `GAS_MODE=legacy` switches every signing provider to
+//! those. Both modes go through [`GasModeFiller`], so [`crate::AlloyProvider`] stays a
+//! single type.
+//!
+//! State can change between `eth_estimateGas` and execution (seen on Base), so an
+//! estimate that was exact runs out of gas. `GAS_LIMIT_MULTIPLIER` scales every
+//! estimated gas limit for headroom, capped at [`MAX_GAS_LIMIT`]. Limits a caller sets
+//! explicitly are sent as given.
+
+use std::fmt;
+use std::str::FromStr;
+
+use alloy::network::{Network, TransactionBuilder};
+use alloy::providers::fillers::{FillerControlFlow, GasFiller, TxFiller};
+use alloy::providers::{Provider, SendableTx};
+use alloy::transports::TransportResult;
+
+/// Environment variable selecting the [`GasMode`].
+pub const GAS_MODE_ENV: &str = "GAS_MODE";
+
+/// Environment variable scaling estimated gas limits.
+pub const GAS_LIMIT_MULTIPLIER_ENV: &str = "GAS_LIMIT_MULTIPLIER";
+
+/// Default for `GAS_LIMIT_MULTIPLIER`: send the estimate unchanged.
+pub const DEFAULT_GAS_LIMIT_MULTIPLIER: f64 = 1.0;
+
+/// Largest accepted `GAS_LIMIT_MULTIPLIER`.
+pub const MAX_GAS_LIMIT_MULTIPLIER: f64 = 3.0;
+
+/// Ceiling for a scaled gas limit, well under the block gas limit of the chains we
+/// deploy to. An estimate already above it is sent unscaled rather than cut.
+pub const MAX_GAS_LIMIT: u64 = 30_000_000;
+
+/// How signing providers price transactions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GasMode {
+    /// `maxFeePerGas` / `maxPriorityFeePerGas` from the node's fee history.
+    #[default]
+    Eip1559,
+    /// `gasPrice` from `eth_gasPrice`.
+    Legacy,
+}
+
+impl FromStr for GasMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "eip1559" => Ok(Self::Eip1559),
+            "legacy" => Ok(Self::Legacy),
+            other => Err(format!(
+                "Invalid {GAS_MODE_ENV} '{other}'. Must be 'eip1559' or 'legacy'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for GasMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Eip1559 => write!(f, "eip1559"),
+            Self::Legacy => write!(f, "legacy"),
+        }
+    }
+}
+
+impl GasMode {
+    /// Read `GAS_MODE`. Unset or blank is EIP-1559; anything unrecognized is an error.
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var(GAS_MODE_ENV) {
+            Ok(v) if !v.trim().is_empty() => v.parse(),
+            _ => Ok(Self::default()),
+        }
+    }
+}
+
+/// `GAS_LIMIT_MULTIPLIER`. Unset or blank is [`DEFAULT_GAS_LIMIT_MULTIPLIER`]; a value
+/// outside `1.0..=3.0` is an error.
+pub fn gas_limit_multiplier_from_env() -> Result<f64, String> {
+    match std::env::var(GAS_LIMIT_MULTIPLIER_ENV) {
+        Ok(v) if !v.trim().is_empty() => {
+            let v = v.trim();
+            match v.parse::<f64>() {
+                Ok(m) if (1.0..=MAX_GAS_LIMIT_MULTIPLIER).contains(&m) => Ok(m),
+                _ => Err(format!(
+                    "Invalid {GAS_LIMIT_MULTIPLIER_ENV} '{v}'. Must be a number from 1.0 to \
+                     {MAX_GAS_LIMIT_MULTIPLIER:.1}"
+                )),
+            }
+        }
+        _ => Ok(DEFAULT_GAS_LIMIT_MULTIPLIER),
+    }
+}
+
+/// `estimate` scaled by `multiplier`, rounded up and capped at [`MAX_GAS_LIMIT`]. Never
+/// below `estimate`.
+pub fn apply_gas_limit_multiplier(estimate: u64, multiplier: f64) -> u64 {
+    let scaled = (estimate as f64 * multiplier).ceil() as u64;
+    scaled.min(MAX_GAS_LIMIT).max(estimate)
+}
+
+/// Gas settings for signing providers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GasConfig {
+    pub mode: GasMode,
+    /// Applied to estimated gas limits (see [`apply_gas_limit_multiplier`]).
+    pub limit_multiplier: f64,
+}
+
+impl Default for GasConfig {
+    fn default() -> Self {
+        Self {
+            mode: GasMode::default(),
+            limit_multiplier: DEFAULT_GAS_LIMIT_MULTIPLIER,
+        }
+    }
+}
+
+impl GasConfig {
+    /// Read `GAS_MODE` and `GAS_LIMIT_MULTIPLIER`, reporting the first invalid one.
+    pub fn from_env() -> Result<Self, String> {
+        Ok(Self {
+            mode: GasMode::from_env()?,
+            limit_multiplier: gas_limit_multiplier_from_env()?,
+        })
+    }
+}
+
+/// Gas filler honouring a [`GasConfig`].
+///
+/// Wraps alloy's [`GasFiller`], which picks legacy pricing whenever the request already
+/// carries a `gasPrice`. In legacy mode an unpriced request is given the node's gas
+/// price before the inner filler estimates it, so it never gets EIP-1559 fee fields.
+/// A gas limit the inner filler estimated is then scaled by the configured multiplier.
+#[derive(Debug, Clone, Default)]
+pub struct GasModeFiller {
+    config: GasConfig,
+    inner: GasFiller,
+}
+
+impl GasModeFiller {
+    pub fn new(config: GasConfig) -> Self {
+        Self {
+            config,
+            inner: GasFiller::default(),
+        }
+    }
+
+    pub fn config(&self) -> GasConfig {
+        self.config
+    }
+}
+
+impl<N: Network> TxFiller<N> for GasModeFiller {
+    /// The inner filler's values, and whether it estimated the gas limit.
+    type Fillable = (<GasFiller as TxFiller<N>>::Fillable, bool);
+
+    fn status(&self, tx: &N::TransactionRequest) -> FillerControlFlow {
+        TxFiller::<N>::status(&self.inner, tx)
+    }
+
+    fn fill_sync(&self, tx: &mut SendableTx<N>) {
+        TxFiller::<N>::fill_sync(&self.inner, tx)
+    }
+
+    async fn prepare<P>(
+        &self,
+        provider: &P,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        P: Provider<N>,
+    {
+        let estimated = tx.gas_limit().is_none();
+        let fillable = if self.config.mode == GasMode::Legacy
+            && tx.gas_price().is_none()
+            && tx.max_fee_per_gas().is_none()
+        {
+            let mut priced = tx.clone();
+            priced.set_gas_price(provider.get_gas_price().await?);
+            self.inner.prepare(provider, &priced).await?
+        } else {
+            self.inner.prepare(provider, tx).await?
+        };
+        Ok((fillable, estimated))
+    }
+
+    async fn fill(
+        &self,
+        (fillable, estimated): Self::Fillable,
+        tx: SendableTx<N>,
+    ) -> TransportResult<SendableTx<N>> {
+        let mut tx = self.inner.fill(fillable, tx).await?;
+        if estimated
+            && let Some(builder) = tx.as_mut_builder()
+            && let Some(estimate) = builder.gas_limit()
+        {
+            let limit = apply_gas_limit_multiplier(estimate, self.config.limit_multiplier);
+            if limit != estimate {
+                builder.set_gas_limit(limit);
+            }
+            tracing::debug!(
+                "Gas limit {} (estimate {} x {})",
+                limit,
+                estimate,
+                self.config.limit_multiplier
+            );
+        }
+        Ok(tx)
+    }
+}
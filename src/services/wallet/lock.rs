@@ -10,6 +10,7 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
+use super::nonce::NonceLedger;
 use crate::models::wallet::PrefixedRedisKeys;
 
 /// Lua script: extend the lock TTL only if we still hold it.
@@ -36,6 +37,8 @@ pub struct WalletLock {
     wallet_address: Address,
     instance_id: String,
     lock_key: String,
+    /// Shared nonce record for the wallet (`None` for beacon update locks)
+    nonce_key: Option<String>,
     ttl: Duration,
 }
 
@@ -67,11 +70,13 @@ impl WalletLock {
         keys: &PrefixedRedisKeys,
     ) -> Self {
         let lock_key = keys.wallet_lock(&wallet_address);
+        let nonce_key = Some(keys.wallet_nonce(&wallet_address));
         Self {
             conn,
             wallet_address,
             instance_id,
             lock_key,
+            nonce_key,
             ttl,
         }
     }
@@ -92,6 +97,7 @@ impl WalletLock {
             wallet_address: beacon_address,
             instance_id,
             lock_key,
+            nonce_key: None,
             ttl,
         }
     }
@@ -176,6 +182,7 @@ impl WalletLock {
                 Ok(WalletLockGuard {
                     conn: self.conn.clone(),
                     lock_key: self.lock_key.clone(),
+                    nonce_key: self.nonce_key.clone(),
                     instance_id: self.instance_id.clone(),
                     wallet_address: self.wallet_address,
                 })
@@ -217,6 +224,25 @@ impl WalletLock {
         Ok(holder)
     }
 
+    /// Delete the lock if it is still held by `expected_holder`, whoever that is.
+    ///
+    /// Operator escape hatch for a lock left behind by a crashed instance: the same
+    /// atomic compare-and-delete as a normal release, but against the given owner token
+    /// instead of ours, so a lock that has since changed hands is left alone. Returns
+    /// whether a lock was deleted.
+    pub async fn force_release(&self, expected_holder: &str) -> Result<bool, String> {
+        let mut conn = self.get_conn();
+
+        let deleted: i32 = redis::Script::new(RELEASE_SCRIPT)
+            .key(&self.lock_key)
+            .arg(expected_holder)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to force-release lock: {e}"))?;
+
+        Ok(deleted == 1)
+    }
+
     /// Extend the lock TTL (only if we hold the lock)
     pub async fn extend(&self, new_ttl: Duration) -> Result<bool, String> {
         let mut conn = self.get_conn();
@@ -250,6 +276,7 @@ impl WalletLock {
 pub struct WalletLockGuard {
     conn: ConnectionManager,
     lock_key: String,
+    nonce_key: Option<String>,
     instance_id: String,
     wallet_address: Address,
 }
@@ -260,6 +287,20 @@ impl WalletLockGuard {
         self.wallet_address
     }
 
+    /// The wallet's shared nonce record, usable only while this lock is held
+    /// (`None` for beacon update locks, which guard no wallet).
+    pub fn nonce_ledger(&self) -> Option<NonceLedger> {
+        self.nonce_key.as_ref().map(|nonce_key| {
+            NonceLedger::new(
+                self.conn.clone(),
+                self.wallet_address,
+                nonce_key.clone(),
+                self.lock_key.clone(),
+                self.instance_id.clone(),
+            )
+        })
+    }
+
     /// Explicitly release the lock
     pub async fn release(self) -> Result<(), String> {
         self.release_internal().await
@@ -388,6 +429,10 @@ impl WalletLockGuard {
     }
 }
 
+/// A held per-beacon update lock (see `WalletManager::acquire_beacon_update_lock`).
+/// Tuple order is drop order: the heartbeat stops before the guard releases.
+pub type BeaconUpdateLock = (LockHeartbeat, WalletLockGuard);
+
 /// Handle to a background lock-extension task (see [`WalletLockGuard::spawn_heartbeat`]).
 ///
 /// Dropping this aborts the heartbeat task. Hold it for the lifetime of the lock and
@@ -3,24 +3,31 @@
 //! This module provides the WalletManager, which coordinates wallet pool
 //! operations, locking, and beacon mappings into a unified interface.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use super::balances::BalanceTracker;
-use super::lock::LockHeartbeat;
-use super::{WalletLock, WalletLockGuard, WalletPool};
+use super::lock::{BeaconUpdateLock, LockHeartbeat};
+use super::nonce::{PoolNonceManager, endpoint_signing_provider};
+use super::{ForceUnlockOutcome, WALLET_POOL_DRAINING, WalletLock, WalletLockGuard, WalletPool};
+use crate::services::rpc::RpcEndpoints;
 use alloy::network::EthereumWallet;
 use alloy::primitives::{Address, B256, U256};
-use alloy::providers::ProviderBuilder;
 use alloy::signers::aws::AwsSigner;
 use alloy::signers::local::PrivateKeySigner;
 use alloy::signers::{Error as SignerError, Signature, Signer};
 
 use crate::AlloyProvider;
 use crate::models::wallet::{WalletInfo, WalletManagerConfig};
+use crate::models::{WalletPoolEntry, WalletPoolStatusResponse};
 use crate::services::wallet::sync::WalletSyncService;
 
+/// Upper bound on the Redis reads behind `WalletManager::pool_status`, so an unreachable
+/// Redis degrades the admin status endpoint instead of hanging it.
+const POOL_STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// A gas-payer pool signer: either a local private key (dev/CI) or an AWS KMS
 /// key (production). The pool is keyed by Ethereum address regardless of backend.
 #[derive(Clone)]
@@ -88,6 +95,9 @@ pub struct WalletHandle {
     pub signer: WalletSigner,
     /// Background lock-extension task; aborted on drop, before the lock release
     heartbeat: LockHeartbeat,
+    /// Nonce source shared by every provider built from this handle; reserves
+    /// through the wallet's Redis nonce record while the lock is held
+    nonces: PoolNonceManager,
     /// The lock guard - wallet is locked until this is dropped
     pub lock_guard: WalletLockGuard,
 }
@@ -96,9 +106,14 @@ impl WalletHandle {
     /// Create a handle and start its lock heartbeat (extends every `lock_ttl / 3`)
     fn new(signer: WalletSigner, lock_guard: WalletLockGuard, lock_ttl: Duration) -> Self {
         let heartbeat = lock_guard.spawn_heartbeat(lock_ttl);
+        let nonces = lock_guard
+            .nonce_ledger()
+            .map(PoolNonceManager::shared)
+            .unwrap_or_default();
         Self {
             signer,
             heartbeat,
+            nonces,
             lock_guard,
         }
     }
@@ -119,23 +134,42 @@ impl WalletHandle {
 
     /// Build an AlloyProvider using this wallet's signer
     ///
-    /// Creates a provider that can sign transactions using the wallet.
+    /// Creates a provider that can sign transactions using the wallet. Its nonces
+    /// are reserved from the wallet's shared Redis record (reconciled with the
+    /// chain before the first send of this lease), so instances taking turns on
+    /// the wallet never reuse one another's nonces.
     ///
     /// # Arguments
-    /// * `rpc_url` - The RPC URL to connect to
+    /// * `endpoints` - The RPC endpoints to read from and send through
     ///
     /// # Returns
     /// An AlloyProvider configured with this wallet's signer
-    pub fn build_provider(&self, rpc_url: &str) -> Result<AlloyProvider, String> {
-        let wallet = self.signer.0.ethereum_wallet();
-
-        let provider = ProviderBuilder::new().wallet(wallet).connect_http(
-            rpc_url
-                .parse()
-                .map_err(|e| format!("Invalid RPC URL '{rpc_url}': {e}"))?,
-        );
+    pub fn build_provider(&self, endpoints: &Arc<RpcEndpoints>) -> Result<AlloyProvider, String> {
+        Ok(endpoint_signing_provider(
+            self.signer.0.ethereum_wallet(),
+            endpoints,
+            self.nonces.clone(),
+        ))
+    }
 
-        Ok(provider)
+    /// Reset the wallet's shared nonce record to the chain's pending count.
+    ///
+    /// Call after a send fails with a nonce error: the record disagrees with the
+    /// chain (a transaction sent outside the pool, or a reserved nonce that never
+    /// landed), and keeping it would fail every later send from this wallet too.
+    /// Failures are logged, not returned — the caller is already handling an error.
+    pub async fn resync_nonce(&self, provider: &AlloyProvider) {
+        match self.nonces.resync(provider).await {
+            Ok(Some(next)) => {
+                tracing::warn!("Resynced nonce for wallet {} to {}", self.address(), next)
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Failed to resync nonce for wallet {}: {}",
+                self.address(),
+                e
+            ),
+        }
     }
 }
 
@@ -159,6 +193,9 @@ pub struct WalletManager {
     /// `None` in test stubs and any manager that never had one attached —
     /// selection treats that exactly like an all-missing cache (no filtering).
     balance_tracker: Option<Arc<BalanceTracker>>,
+    /// Set by `POST /maintenance/drain`: new leases are refused while held leases
+    /// finish normally.
+    draining: AtomicBool,
 }
 
 impl WalletManager {
@@ -187,6 +224,7 @@ impl WalletManager {
             is_test_stub: false,
             signers: signers_map,
             balance_tracker: None,
+            draining: AtomicBool::new(false),
         })
     }
 
@@ -211,6 +249,7 @@ impl WalletManager {
             is_test_stub: true,
             signers: HashMap::new(),
             balance_tracker: None,
+            draining: AtomicBool::new(false),
         }
     }
 
@@ -248,10 +287,12 @@ impl WalletManager {
                 lock_retry_delay: std::time::Duration::from_millis(100),
                 instance_id: None,
                 chain_id: None,
+                designation_fallback: false,
             }),
             is_test_stub: false,
             signers: signers_map,
             balance_tracker: None,
+            draining: AtomicBool::new(false),
         })
     }
 
@@ -278,31 +319,84 @@ impl WalletManager {
         })
     }
 
-    /// Acquire a wallet for a beacon update operation
+    /// Acquire a wallet for an operation on a beacon
     ///
-    /// If the beacon has a designated wallet, that wallet will be used.
-    /// Otherwise, an available wallet from the pool will be acquired.
+    /// If the beacon has a designated wallet, that wallet will be used. When it
+    /// cannot be acquired the call fails, unless `designation_fallback` is on, in
+    /// which case any other available wallet is used instead.
+    /// Beacons without a designation get an available wallet from the pool.
     ///
     /// # Arguments
-    /// * `beacon` - The beacon that needs to be updated
+    /// * `beacon` - The beacon the operation targets
     ///
     /// # Returns
     /// A WalletHandle with the locked wallet ready for use
     pub async fn acquire_for_beacon(&self, beacon: &Address) -> Result<WalletHandle, String> {
+        self.refuse_if_draining()?;
         let pool = self.require_pool();
         // Check if beacon has a designated wallet
-        if let Some(wallet_address) = pool.get_wallet_for_beacon(beacon).await? {
-            self.acquire_specific_wallet(&wallet_address).await
-        } else {
-            self.acquire_any_wallet().await
+        let Some(wallet_address) = pool.get_wallet_for_beacon(beacon).await? else {
+            return self.acquire_any_wallet().await;
+        };
+
+        match self.acquire_specific_wallet(&wallet_address).await {
+            Ok(handle) => Ok(handle),
+            Err(e) if self.require_config().designation_fallback => {
+                tracing::warn!(
+                    "Designated wallet {} for beacon {} unavailable ({}); falling back to the pool",
+                    wallet_address,
+                    beacon,
+                    e
+                );
+                self.acquire_any_wallet_excluding(&HashSet::from([wallet_address]))
+                    .await
+            }
+            Err(e) => Err(format!(
+                "Designated wallet {wallet_address} for beacon {beacon} unavailable: {e}"
+            )),
         }
     }
 
+    /// Designate `wallet` for every wallet-scoped operation on `beacon`, replacing any
+    /// previous designation. Returns the previously designated wallet, if different.
+    pub async fn designate_beacon(
+        &self,
+        beacon: &Address,
+        wallet: &Address,
+    ) -> Result<Option<Address>, String> {
+        let pool = self.require_pool();
+        if !pool.wallet_exists(wallet).await? {
+            return Err(format!("Wallet {wallet} is not in the pool"));
+        }
+
+        let previous = pool.get_wallet_for_beacon(beacon).await?;
+        if let Some(old) = previous.filter(|old| old != wallet) {
+            pool.remove_designated_beacon(&old, beacon).await?;
+        }
+        pool.add_designated_beacon(wallet, beacon).await?;
+        Ok(previous.filter(|old| old != wallet))
+    }
+
+    /// Remove `beacon`'s wallet designation. Returns the wallet it was designated to,
+    /// or `None` when it had none.
+    pub async fn clear_beacon_designation(
+        &self,
+        beacon: &Address,
+    ) -> Result<Option<Address>, String> {
+        let pool = self.require_pool();
+        let previous = pool.get_wallet_for_beacon(beacon).await?;
+        if let Some(wallet) = previous {
+            pool.remove_designated_beacon(&wallet, beacon).await?;
+        }
+        Ok(previous)
+    }
+
     /// Acquire a specific wallet by address
     ///
     /// # Arguments
     /// * `address` - The wallet address to acquire
     pub async fn acquire_specific_wallet(&self, address: &Address) -> Result<WalletHandle, String> {
+        self.refuse_if_draining()?;
         let pool = self.require_pool();
         let config = self.require_config();
 
@@ -340,7 +434,7 @@ impl WalletManager {
     pub async fn acquire_beacon_update_lock(
         &self,
         beacon: Address,
-    ) -> Result<(LockHeartbeat, WalletLockGuard), String> {
+    ) -> Result<BeaconUpdateLock, String> {
         let pool = self.require_pool();
         let config = self.require_config();
 
@@ -392,6 +486,7 @@ impl WalletManager {
         &self,
         exclude: &HashSet<Address>,
     ) -> Result<WalletHandle, String> {
+        self.refuse_if_draining()?;
         let available = self.list_available_resyncing().await?;
         if available.is_empty() {
             return Err("No available wallets in the pool".to_string());
@@ -426,6 +521,7 @@ impl WalletManager {
         min_usdc: U256,
         exclude: &HashSet<Address>,
     ) -> Result<WalletHandle, String> {
+        self.refuse_if_draining()?;
         let available = self.list_available_resyncing().await?;
         if available.is_empty() {
             return Err("No available wallets in the pool".to_string());
@@ -624,6 +720,14 @@ impl WalletManager {
             }
         }
 
+        // Every candidate stayed locked through the retries: the pool is saturated.
+        // The pool is grown by hand (new key + funding, see README "Growing the wallet
+        // pool"), so this event is the signal for an operator to do that.
+        tracing::warn!(
+            metric = "WalletPoolExhausted",
+            candidates = candidates.len(),
+            "All candidate pool wallets stayed locked; the pool may need more wallets"
+        );
         Err("Failed to acquire any wallet from the pool".to_string())
     }
 
@@ -643,6 +747,7 @@ impl WalletManager {
     /// to be in the signers map. Useful for locking wallets that are managed
     /// outside the pool (e.g., the funding wallet).
     pub async fn acquire_lock(&self, address: &Address) -> Result<WalletLockGuard, String> {
+        self.refuse_if_draining()?;
         let lock = self.create_lock(address);
         let config = self.require_config();
         lock.acquire(config.lock_retry_count, config.lock_retry_delay)
@@ -679,6 +784,159 @@ impl WalletManager {
     pub fn is_test_stub(&self) -> bool {
         self.is_test_stub
     }
+
+    /// Start (`true`) or stop (`false`) draining for maintenance. While draining every
+    /// wallet acquisition fails with [`WALLET_POOL_DRAINING`]; handles already held are
+    /// unaffected. Returns whether the manager was draining before.
+    pub fn set_draining(&self, draining: bool) -> bool {
+        self.draining.swap(draining, Ordering::SeqCst)
+    }
+
+    /// Whether the manager is draining for maintenance.
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    fn refuse_if_draining(&self) -> Result<(), String> {
+        if self.is_draining() {
+            return Err(WALLET_POOL_DRAINING.to_string());
+        }
+        Ok(())
+    }
+
+    /// Forcibly release a wallet lock left behind by a crashed instance.
+    ///
+    /// `expected_holder` is the lock's owner token (the holding instance id, as shown by
+    /// `/wallet_pool/status`). The lock is deleted only while it still carries that token,
+    /// so a lock re-acquired by a live instance in the meantime survives. Locks held by
+    /// this instance are never released: it is alive and may be mid-transaction.
+    pub async fn force_unlock(
+        &self,
+        wallet: &Address,
+        expected_holder: &str,
+    ) -> Result<ForceUnlockOutcome, String> {
+        let lock = self.create_lock(wallet);
+        let outcome = match lock.get_holder().await? {
+            None => ForceUnlockOutcome::NotLocked,
+            Some(holder) if holder == self.instance_id() => ForceUnlockOutcome::HeldByThisInstance,
+            Some(holder) if holder != expected_holder => {
+                ForceUnlockOutcome::HolderMismatch { holder }
+            }
+            Some(_) => {
+                if lock.force_release(expected_holder).await? {
+                    ForceUnlockOutcome::Released
+                } else {
+                    // Expired or changed hands between the read and the delete.
+                    match lock.get_holder().await? {
+                        None => ForceUnlockOutcome::NotLocked,
+                        Some(holder) => ForceUnlockOutcome::HolderMismatch { holder },
+                    }
+                }
+            }
+        };
+
+        if outcome == ForceUnlockOutcome::Released {
+            tracing::warn!(
+                audit = "wallet_force_unlock",
+                wallet = %wallet,
+                released_holder = expected_holder,
+                by_instance = self.instance_id(),
+                "Force-released wallet lock"
+            );
+        }
+        Ok(outcome)
+    }
+
+    /// Snapshot of the pool for the admin `/wallet_pool/status` endpoint.
+    ///
+    /// Never fails: when Redis cannot be read (or this is a test stub) the response is
+    /// marked `degraded` and only carries what this instance knows locally, its signers.
+    pub async fn pool_status(&self) -> WalletPoolStatusResponse {
+        let mut signers = self.signer_addresses();
+        signers.sort();
+
+        let mut status = WalletPoolStatusResponse {
+            degraded: false,
+            error: None,
+            instance_id: self.pool.as_ref().map(|p| p.instance_id().to_string()),
+            signer_count: signers.len(),
+            total_wallets: 0,
+            status_counts: BTreeMap::new(),
+            locked_wallets: 0,
+            unregistered_signers: Vec::new(),
+            designations: BTreeMap::new(),
+            wallets: Vec::new(),
+        };
+
+        let Some(pool) = self.pool.as_ref() else {
+            status.degraded = true;
+            status.error = Some("Wallet pool is not configured (test stub)".to_string());
+            return status;
+        };
+
+        let entries = match tokio::time::timeout(POOL_STATUS_TIMEOUT, self.pool_entries(pool)).await
+        {
+            Ok(Ok(entries)) => entries,
+            Ok(Err(e)) => {
+                tracing::warn!("Wallet pool status degraded: {}", e);
+                status.degraded = true;
+                status.error = Some(e);
+                return status;
+            }
+            Err(_) => {
+                let e = format!("Redis did not answer within {POOL_STATUS_TIMEOUT:?}");
+                tracing::warn!("Wallet pool status degraded: {}", e);
+                status.degraded = true;
+                status.error = Some(e);
+                return status;
+            }
+        };
+
+        for entry in &entries {
+            *status
+                .status_counts
+                .entry(entry.status.clone())
+                .or_default() += 1;
+            if entry.lock_holder.is_some() {
+                status.locked_wallets += 1;
+            }
+            for beacon in &entry.designated_beacons {
+                status
+                    .designations
+                    .insert(beacon.clone(), entry.address.clone());
+            }
+        }
+        let registered: HashSet<&str> = entries.iter().map(|e| e.address.as_str()).collect();
+        status.unregistered_signers = signers
+            .iter()
+            .map(|a| a.to_string())
+            .filter(|a| !registered.contains(a.as_str()))
+            .collect();
+        status.total_wallets = entries.len();
+        status.wallets = entries;
+        status
+    }
+
+    /// Per-wallet view of the Redis pool: recorded status, live lock holder, designations.
+    async fn pool_entries(&self, pool: &WalletPool) -> Result<Vec<WalletPoolEntry>, String> {
+        let mut wallets = pool.list_wallets().await?;
+        wallets.sort_by_key(|w| w.address);
+
+        let mut entries = Vec::with_capacity(wallets.len());
+        for wallet in wallets {
+            let lock_holder = self.create_lock(&wallet.address).get_holder().await?;
+            let mut beacons = pool.get_beacons_for_wallet(&wallet.address).await?;
+            beacons.sort();
+            entries.push(WalletPoolEntry {
+                address: wallet.address.to_string(),
+                status: wallet.status.label().to_string(),
+                lock_holder,
+                has_signer: self.signers.contains_key(&wallet.address),
+                designated_beacons: beacons.iter().map(|b| b.to_string()).collect(),
+            });
+        }
+        Ok(entries)
+    }
 }
 
 #[cfg(test)]
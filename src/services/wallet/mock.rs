@@ -5,12 +5,12 @@
 
 use alloy::network::EthereumWallet;
 use alloy::primitives::Address;
-use alloy::providers::ProviderBuilder;
 use alloy::signers::Signer;
 use alloy::signers::local::PrivateKeySigner;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
+use super::nonce::{PoolNonceManager, signing_provider};
 use crate::AlloyProvider;
 use crate::models::wallet::{WalletInfo, WalletStatus};
 
@@ -167,14 +167,7 @@ impl MockWalletHandle {
     /// Build an AlloyProvider using this wallet's signer
     pub fn build_provider(&self, rpc_url: &str) -> Result<AlloyProvider, String> {
         let wallet = EthereumWallet::from(self.signer.clone());
-
-        let provider = ProviderBuilder::new().wallet(wallet).connect_http(
-            rpc_url
-                .parse()
-                .map_err(|e| format!("Invalid RPC URL '{rpc_url}': {e}"))?,
-        );
-
-        Ok(provider)
+        signing_provider(wallet, rpc_url, PoolNonceManager::default())
     }
 }
 
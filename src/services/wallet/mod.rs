@@ -4,20 +4,69 @@
 //! - WalletPool: Redis-backed pool of available wallets (includes beacon->wallet mappings)
 //! - WalletLock: Distributed locking to prevent concurrent wallet use
 //! - WalletManager: Central coordinator for wallet operations
+//! - NonceLedger: Per-wallet transaction nonces shared through Redis, fenced by the lock
+//! - GasConfig: EIP-1559 or legacy gas pricing (`GAS_MODE`) and gas-limit headroom
+//!   (`GAS_LIMIT_MULTIPLIER`) for every signing provider
+//! - FundingRateLimiter: Per-recipient rolling-window caps for guest funding
+//!
+//! The pool (KMS signers, Redis locks, balance tracking) is behind the default
+//! `wallet-pool` feature. Without it, `WalletManager` is the single-signer stand-in
+//! from [`single`]: every transaction is sent from the PRIVATE_KEY wallet, serialized
+//! in-process.
 
+#[cfg(feature = "wallet-pool")]
 pub mod balances;
+pub mod funding_limits;
+pub mod gas;
+#[cfg(feature = "wallet-pool")]
 pub mod lock;
+#[cfg(feature = "wallet-pool")]
 pub mod manager;
+#[cfg(feature = "wallet-pool")]
 pub mod mock;
+pub mod nonce;
+#[cfg(feature = "wallet-pool")]
 pub mod pool;
+#[cfg(not(feature = "wallet-pool"))]
+pub mod single;
+#[cfg(feature = "wallet-pool")]
 pub mod sync;
 
+#[cfg(feature = "wallet-pool")]
 pub use balances::{BalanceTracker, WalletBalances};
-pub use lock::{LockHeartbeat, WalletLock, WalletLockGuard};
+pub use funding_limits::{FundingLimitExceeded, FundingRateLimiter, FundingReservation};
+pub use gas::{GasConfig, GasMode, GasModeFiller};
+#[cfg(feature = "wallet-pool")]
+pub use lock::{BeaconUpdateLock, LockHeartbeat, WalletLock, WalletLockGuard};
+#[cfg(feature = "wallet-pool")]
 pub use manager::{PoolSigner, WalletHandle, WalletManager, WalletSigner};
+#[cfg(feature = "wallet-pool")]
 pub use mock::{MockWalletHandle, MockWalletManager};
+pub use nonce::{NonceLedger, PoolNonceManager};
+#[cfg(feature = "wallet-pool")]
 pub use pool::WalletPool;
+#[cfg(not(feature = "wallet-pool"))]
+pub use single::{BeaconUpdateLock, WalletHandle, WalletManager, WalletSigner};
+#[cfg(feature = "wallet-pool")]
 pub use sync::{SyncResult, WalletSyncService};
 
 // Re-export model types for convenience
 pub use crate::models::wallet::{RedisKeys, WalletInfo, WalletManagerConfig, WalletStatus};
+
+/// Error every wallet lease returns while the manager is draining for maintenance
+/// (`POST /maintenance/drain`). Callers report it as the wallet being unavailable (503).
+pub const WALLET_POOL_DRAINING: &str =
+    "Wallet pool is draining for maintenance; no new wallet leases are handed out";
+
+/// Result of `WalletManager::force_unlock`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForceUnlockOutcome {
+    /// The lock was held by the expected owner and has been deleted.
+    Released,
+    /// No lock is held for the wallet.
+    NotLocked,
+    /// The lock is held by a different owner than expected (it changed hands).
+    HolderMismatch { holder: String },
+    /// The lock belongs to this instance, which is alive and may be using the wallet.
+    HeldByThisInstance,
+}
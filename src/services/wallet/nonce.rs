@@ -0,0 +1,577 @@
+//! Redis-backed transaction nonces shared across beaconator instances
+//!
+//! A provider built per lease starts from its RPC node's pending transaction count,
+//! which can lag a transaction a peer instance sent moments before releasing the
+//! wallet — the next holder then reuses that nonce. The ledger keeps each pool
+//! wallet's next nonce in Redis beside its lock (`wallet_nonce:{address}`):
+//! - reconciled once per lock acquisition, before the first send, to
+//!   `max(recorded, chain pending count)`;
+//! - reserved atomically for every transaction, and only while the caller still
+//!   holds the wallet lock (the scripts compare the lock owner first);
+//! - reserved only after gas estimation succeeded ([`GasGatedNonceFiller`]), and handed
+//!   back when the node rejects the broadcast ([`NonceReleasingTransport`]), so neither a
+//!   reverting estimate nor a rejected send skips a nonce;
+//! - dropped [`NONCE_RECORD_TTL`] after the last reservation, so a nonce reserved
+//!   for a send that never reached the chain stops being trusted and the chain
+//!   count wins again.
+
+use alloy::network::{EthereumWallet, Network, TransactionBuilder};
+use alloy::primitives::Address;
+use alloy::providers::fillers::{
+    BlobGasFiller, CachedNonceManager, ChainIdFiller, FillerControlFlow, JoinFill, NonceFiller,
+    NonceManager, TxFiller,
+};
+use alloy::providers::{Provider, ProviderBuilder, SendableTx};
+use alloy::rpc::client::{ClientBuilder, RpcClient};
+use alloy::rpc::json_rpc::{RequestPacket, ResponsePacket};
+use alloy::transports::{
+    BoxTransport, TransportError, TransportErrorKind, TransportFut, TransportResult,
+};
+use redis::aio::ConnectionManager;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tower::Service;
+
+use super::gas::{GasConfig, GasModeFiller};
+use crate::AlloyProvider;
+use crate::services::rpc::RpcEndpoints;
+
+/// How long a wallet's nonce record outlives its last reservation.
+///
+/// Long enough to cover RPC nodes lagging a peer's send; short enough that a
+/// reservation whose transaction never landed does not leave a nonce gap for long.
+pub const NONCE_RECORD_TTL: Duration = Duration::from_secs(120);
+
+/// Script result: the wallet lock is not held by the caller.
+const LOCK_NOT_HELD: i64 = -1;
+/// Script result: no nonce is recorded for the wallet (never seeded, or expired).
+const NO_RECORD: i64 = -2;
+
+/// Lua script: if we hold the wallet lock, seed the record with ARGV[2] — raised to the
+/// recorded nonce unless ARGV[4] is "1" (forced resync) — and return it.
+const SEED_SCRIPT: &str = r#"
+    if redis.call("get", KEYS[2]) ~= ARGV[1] then
+        return -1
+    end
+    local next_nonce = tonumber(ARGV[2])
+    local recorded = redis.call("get", KEYS[1])
+    if ARGV[4] ~= "1" and recorded and tonumber(recorded) > next_nonce then
+        next_nonce = tonumber(recorded)
+    end
+    redis.call("set", KEYS[1], string.format("%d", next_nonce), "PX", ARGV[3])
+    return next_nonce
+"#;
+
+/// Lua script: if we hold the wallet lock, return the recorded nonce and advance it.
+const RESERVE_SCRIPT: &str = r#"
+    if redis.call("get", KEYS[2]) ~= ARGV[1] then
+        return -1
+    end
+    local recorded = redis.call("get", KEYS[1])
+    if not recorded then
+        return -2
+    end
+    local nonce = tonumber(recorded)
+    redis.call("set", KEYS[1], string.format("%d", nonce + 1), "PX", ARGV[2])
+    return nonce
+"#;
+
+/// Lua script: if we hold the wallet lock and nothing was reserved after ARGV[2] (the
+/// record is still ARGV[2] + 1), hand ARGV[2] back. Returns 1 when released, 0 otherwise.
+const RELEASE_SCRIPT: &str = r#"
+    if redis.call("get", KEYS[2]) ~= ARGV[1] then
+        return -1
+    end
+    local recorded = redis.call("get", KEYS[1])
+    if recorded and tonumber(recorded) == tonumber(ARGV[2]) + 1 then
+        redis.call("set", KEYS[1], ARGV[2], "PX", ARGV[3])
+        return 1
+    end
+    return 0
+"#;
+
+/// One wallet's shared nonce record, fenced by the wallet lock it was created from
+/// (see [`WalletLockGuard::nonce_ledger`](super::WalletLockGuard::nonce_ledger)).
+#[derive(Clone)]
+pub struct NonceLedger {
+    conn: ConnectionManager,
+    wallet_address: Address,
+    nonce_key: String,
+    lock_key: String,
+    instance_id: String,
+}
+
+impl std::fmt::Debug for NonceLedger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NonceLedger")
+            .field("wallet_address", &self.wallet_address)
+            .field("nonce_key", &self.nonce_key)
+            .field("instance_id", &self.instance_id)
+            .finish()
+    }
+}
+
+impl NonceLedger {
+    // Only wallet locks create ledgers; unused in single-signer builds.
+    #[cfg_attr(not(feature = "wallet-pool"), allow(dead_code))]
+    pub(crate) fn new(
+        conn: ConnectionManager,
+        wallet_address: Address,
+        nonce_key: String,
+        lock_key: String,
+        instance_id: String,
+    ) -> Self {
+        Self {
+            conn,
+            wallet_address,
+            nonce_key,
+            lock_key,
+            instance_id,
+        }
+    }
+
+    /// The wallet this ledger records nonces for
+    pub fn wallet_address(&self) -> Address {
+        self.wallet_address
+    }
+
+    /// Raise the record to `chain_next` (the chain's pending transaction count) if it
+    /// is behind, keeping a higher recorded nonce from a peer's unconfirmed sends.
+    /// Returns the next nonce to use.
+    pub async fn reconcile(&self, chain_next: u64) -> Result<u64, String> {
+        self.seed(chain_next, false).await
+    }
+
+    /// Overwrite the record with `chain_next`, discarding whatever was recorded.
+    ///
+    /// For after a nonce error, when the chain has shown the record to be wrong.
+    pub async fn resync(&self, chain_next: u64) -> Result<u64, String> {
+        self.seed(chain_next, true).await
+    }
+
+    async fn seed(&self, chain_next: u64, force: bool) -> Result<u64, String> {
+        let mut conn = self.conn.clone();
+        let result: i64 = redis::Script::new(SEED_SCRIPT)
+            .key(&self.nonce_key)
+            .key(&self.lock_key)
+            .arg(&self.instance_id)
+            .arg(chain_next)
+            .arg(NONCE_RECORD_TTL.as_millis() as u64)
+            .arg(if force { "1" } else { "0" })
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to seed nonce for wallet {}: {e}",
+                    self.wallet_address
+                )
+            })?;
+
+        match self.checked(result)? {
+            Some(nonce) => Ok(nonce),
+            None => Err(format!(
+                "Nonce seed for wallet {} returned no record",
+                self.wallet_address
+            )),
+        }
+    }
+
+    /// Reserve the next nonce, advancing the record. `None` when nothing is recorded
+    /// (the caller must [`reconcile`](Self::reconcile) first).
+    pub async fn reserve(&self) -> Result<Option<u64>, String> {
+        let mut conn = self.conn.clone();
+        let result: i64 = redis::Script::new(RESERVE_SCRIPT)
+            .key(&self.nonce_key)
+            .key(&self.lock_key)
+            .arg(&self.instance_id)
+            .arg(NONCE_RECORD_TTL.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to reserve nonce for wallet {}: {e}",
+                    self.wallet_address
+                )
+            })?;
+
+        self.checked(result)
+    }
+
+    /// Hand back `nonce` after its transaction was rejected, unless a later nonce has been
+    /// reserved since. Returns whether the record moved back.
+    pub async fn release(&self, nonce: u64) -> Result<bool, String> {
+        let mut conn = self.conn.clone();
+        let result: i64 = redis::Script::new(RELEASE_SCRIPT)
+            .key(&self.nonce_key)
+            .key(&self.lock_key)
+            .arg(&self.instance_id)
+            .arg(nonce)
+            .arg(NONCE_RECORD_TTL.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to release nonce for wallet {}: {e}",
+                    self.wallet_address
+                )
+            })?;
+
+        Ok(self.checked(result)? == Some(1))
+    }
+
+    /// The recorded next nonce, if any (not fenced; for inspection only).
+    pub async fn recorded(&self) -> Result<Option<u64>, String> {
+        let mut conn = self.conn.clone();
+        let recorded: Option<u64> = redis::cmd("GET")
+            .arg(&self.nonce_key)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Failed to read nonce for wallet {}: {e}",
+                    self.wallet_address
+                )
+            })?;
+        Ok(recorded)
+    }
+
+    fn checked(&self, result: i64) -> Result<Option<u64>, String> {
+        match result {
+            LOCK_NOT_HELD => Err(format!(
+                "wallet lock for {} is not held by this instance — refusing to use its nonce",
+                self.wallet_address
+            )),
+            NO_RECORD => Ok(None),
+            nonce => Ok(Some(nonce as u64)),
+        }
+    }
+}
+
+/// Nonce source for every signing provider (`AlloyProvider`).
+///
+/// Pool wallet handles use their wallet's [`NonceLedger`]; everything else (the
+/// legacy single-key provider, mock handles) keeps alloy's per-provider cache.
+#[derive(Clone, Debug, Default)]
+pub struct PoolNonceManager {
+    ledger: Option<NonceLedger>,
+    /// Whether the ledger has been reconciled with the chain during this lease.
+    reconciled: Arc<AtomicBool>,
+    /// Shared by every clone, so a resync also resets providers built before it.
+    fallback: Arc<RwLock<CachedNonceManager>>,
+    /// The last nonce reserved from the ledger and not yet known to be broadcast.
+    last_reserved: Arc<Mutex<Option<u64>>>,
+}
+
+impl PoolNonceManager {
+    /// Reserve nonces for the ledger's wallet through Redis.
+    pub fn shared(ledger: NonceLedger) -> Self {
+        Self {
+            ledger: Some(ledger),
+            ..Self::default()
+        }
+    }
+
+    /// The ledger backing this manager, if it is a pool wallet's.
+    pub fn ledger(&self) -> Option<&NonceLedger> {
+        self.ledger.as_ref()
+    }
+
+    /// Reset the ledger to the chain's pending count. Without a ledger, drop the cached
+    /// nonces instead so the next send reads the chain.
+    pub async fn resync<P, N>(&self, provider: &P) -> Result<Option<u64>, String>
+    where
+        P: Provider<N>,
+        N: Network,
+    {
+        let Some(ledger) = &self.ledger else {
+            *self.fallback.write().unwrap() = CachedNonceManager::default();
+            return Ok(None);
+        };
+        let chain_next = provider
+            .get_transaction_count(ledger.wallet_address())
+            .pending()
+            .await
+            .map_err(|e| format!("Failed to fetch pending nonce: {e}"))?;
+        let next = ledger.resync(chain_next).await?;
+        self.reconciled.store(true, Ordering::SeqCst);
+        self.forget_reserved();
+        Ok(Some(next))
+    }
+
+    /// The node rejected the last transaction: hand its nonce back to the ledger so the
+    /// next send reuses it. Without a ledger, drop the cached nonces instead.
+    pub async fn release_rejected(&self) {
+        let Some(ledger) = &self.ledger else {
+            *self.fallback.write().unwrap() = CachedNonceManager::default();
+            return;
+        };
+        let Some(nonce) = self.last_reserved.lock().unwrap().take() else {
+            return;
+        };
+        match ledger.release(nonce).await {
+            Ok(true) => tracing::info!(
+                "Released nonce {} of wallet {} after the node rejected its transaction",
+                nonce,
+                ledger.wallet_address()
+            ),
+            Ok(false) => {}
+            Err(e) => tracing::warn!("{e}"),
+        }
+    }
+
+    /// The last reserved nonce reached the node; it is no longer ours to hand back.
+    pub fn forget_reserved(&self) {
+        self.last_reserved.lock().unwrap().take();
+    }
+
+    async fn reserve_shared<P, N>(&self, ledger: &NonceLedger, provider: &P) -> TransportResult<u64>
+    where
+        P: Provider<N>,
+        N: Network,
+    {
+        let address = ledger.wallet_address();
+        if !self.reconciled.load(Ordering::SeqCst) {
+            let chain_next = provider.get_transaction_count(address).pending().await?;
+            let next = ledger
+                .reconcile(chain_next)
+                .await
+                .map_err(|e| TransportErrorKind::custom_str(&e))?;
+            if next > chain_next {
+                tracing::info!(
+                    "Nonce for wallet {} ahead of RPC pending count ({} > {}); using the shared record",
+                    address,
+                    next,
+                    chain_next
+                );
+            }
+            self.reconciled.store(true, Ordering::SeqCst);
+        }
+
+        if let Some(nonce) = ledger
+            .reserve()
+            .await
+            .map_err(|e| TransportErrorKind::custom_str(&e))?
+        {
+            *self.last_reserved.lock().unwrap() = Some(nonce);
+            return Ok(nonce);
+        }
+
+        // The record expired mid-lease (idle past NONCE_RECORD_TTL): reseed from the chain.
+        let chain_next = provider.get_transaction_count(address).pending().await?;
+        ledger
+            .reconcile(chain_next)
+            .await
+            .map_err(|e| TransportErrorKind::custom_str(&e))?;
+        let nonce = ledger
+            .reserve()
+            .await
+            .map_err(|e| TransportErrorKind::custom_str(&e))?
+            .ok_or_else(|| {
+                TransportErrorKind::custom_str(&format!("No nonce recorded for wallet {address}"))
+            })?;
+        *self.last_reserved.lock().unwrap() = Some(nonce);
+        Ok(nonce)
+    }
+}
+
+#[async_trait::async_trait]
+impl NonceManager for PoolNonceManager {
+    async fn get_next_nonce<P, N>(&self, provider: &P, address: Address) -> TransportResult<u64>
+    where
+        P: Provider<N>,
+        N: Network,
+    {
+        match &self.ledger {
+            Some(ledger) if ledger.wallet_address() == address => {
+                self.reserve_shared(ledger, provider).await
+            }
+            _ => {
+                let fallback = self.fallback.read().unwrap().clone();
+                fallback.get_next_nonce(provider, address).await
+            }
+        }
+    }
+}
+
+/// [`NonceFiller`] over [`PoolNonceManager`] that waits for the gas limit.
+///
+/// `JoinFill` prepares every ready filler concurrently, so a plain nonce filler would
+/// reserve a nonce even when `eth_estimateGas` reverts. This one stays not-ready until
+/// the gas filler has set the limit, so nonces are only reserved for transactions that
+/// estimated.
+#[derive(Clone, Debug)]
+pub struct GasGatedNonceFiller {
+    inner: NonceFiller<PoolNonceManager>,
+}
+
+impl GasGatedNonceFiller {
+    pub fn new(nonces: PoolNonceManager) -> Self {
+        Self {
+            inner: NonceFiller::new(nonces),
+        }
+    }
+}
+
+impl<N: Network> TxFiller<N> for GasGatedNonceFiller {
+    type Fillable = <NonceFiller<PoolNonceManager> as TxFiller<N>>::Fillable;
+
+    fn status(&self, tx: &N::TransactionRequest) -> FillerControlFlow {
+        if tx.nonce().is_none() && tx.gas_limit().is_none() {
+            return FillerControlFlow::missing("GasGatedNonceFiller", vec!["gas_limit"]);
+        }
+        TxFiller::<N>::status(&self.inner, tx)
+    }
+
+    fn fill_sync(&self, tx: &mut SendableTx<N>) {
+        TxFiller::<N>::fill_sync(&self.inner, tx)
+    }
+
+    async fn prepare<P>(
+        &self,
+        provider: &P,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        P: Provider<N>,
+    {
+        self.inner.prepare(provider, tx).await
+    }
+
+    async fn fill(
+        &self,
+        fillable: Self::Fillable,
+        tx: SendableTx<N>,
+    ) -> TransportResult<SendableTx<N>> {
+        self.inner.fill(fillable, tx).await
+    }
+}
+
+/// Transport of a signing provider that hands the reserved nonce back
+/// ([`PoolNonceManager::release_rejected`]) when the node answers
+/// `eth_sendRawTransaction` with an error.
+///
+/// Only JSON-RPC error responses count as rejected. A transport error leaves the
+/// reservation alone: the transaction may have reached the node, and reusing its nonce
+/// would replace it. A node answering "already known" holds the transaction too.
+#[derive(Clone)]
+pub struct NonceReleasingTransport {
+    inner: BoxTransport,
+    nonces: PoolNonceManager,
+}
+
+impl NonceReleasingTransport {
+    pub fn new(inner: BoxTransport, nonces: PoolNonceManager) -> Self {
+        Self { inner, nonces }
+    }
+}
+
+impl Service<RequestPacket> for NonceReleasingTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // The wrapped transport is cloned per call and polled there.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let nonces = self.nonces.clone();
+        Box::pin(async move {
+            let broadcast = request
+                .method_names()
+                .any(|method| method == "eth_sendRawTransaction");
+            let response = inner.call(request).await?;
+            if broadcast {
+                match response.as_error() {
+                    Some(error) if !error.message.to_lowercase().contains("already known") => {
+                        nonces.release_rejected().await
+                    }
+                    _ => nonces.forget_reserved(),
+                }
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Build a signing provider whose nonces come from `nonces`, with gas per `GAS_MODE` and
+/// `GAS_LIMIT_MULTIPLIER`.
+///
+/// Both are validated at startup (`RpcConfig::from_env`), so an invalid value here falls
+/// back to the defaults.
+pub fn signing_provider(
+    wallet: EthereumWallet,
+    rpc_url: &str,
+    nonces: PoolNonceManager,
+) -> Result<AlloyProvider, String> {
+    signing_provider_with_gas(
+        wallet,
+        rpc_url,
+        nonces,
+        GasConfig::from_env().unwrap_or_default(),
+    )
+}
+
+/// [`signing_provider`] with explicit [`GasConfig`].
+///
+/// Same filler stack as `ProviderBuilder::new()` (gas, blob gas, nonce, chain id), with
+/// the nonce filler swapped for [`GasGatedNonceFiller`] and the gas filler for
+/// [`GasModeFiller`].
+pub fn signing_provider_with_gas(
+    wallet: EthereumWallet,
+    rpc_url: &str,
+    nonces: PoolNonceManager,
+    gas: GasConfig,
+) -> Result<AlloyProvider, String> {
+    let url = rpc_url
+        .parse()
+        .map_err(|e| format!("Invalid RPC URL '{rpc_url}': {e}"))?;
+    Ok(signing_provider_on(
+        wallet,
+        ClientBuilder::default().http(url),
+        nonces,
+        gas,
+    ))
+}
+
+/// Signing provider over the service's RPC endpoints: lookups stick to the primary
+/// and fail over, broadcasts go out once, and both respect the endpoint breakers
+/// (see [`RpcEndpoints::send_client`]). Gas config as in [`signing_provider`].
+pub fn endpoint_signing_provider(
+    wallet: EthereumWallet,
+    endpoints: &Arc<RpcEndpoints>,
+    nonces: PoolNonceManager,
+) -> AlloyProvider {
+    signing_provider_on(
+        wallet,
+        RpcEndpoints::send_client(endpoints),
+        nonces,
+        GasConfig::from_env().unwrap_or_default(),
+    )
+}
+
+fn signing_provider_on(
+    wallet: EthereumWallet,
+    client: RpcClient,
+    nonces: PoolNonceManager,
+    gas: GasConfig,
+) -> AlloyProvider {
+    let client = RpcClient::new(
+        NonceReleasingTransport::new(client.transport().clone(), nonces.clone()),
+        client.is_local(),
+    );
+    ProviderBuilder::default()
+        .filler(JoinFill::new(
+            GasModeFiller::new(gas),
+            JoinFill::new(
+                BlobGasFiller::default(),
+                JoinFill::new(GasGatedNonceFiller::new(nonces), ChainIdFiller::default()),
+            ),
+        ))
+        .wallet(wallet)
+        .connect_client(client)
+}
@@ -0,0 +1,286 @@
+//! Single-signer wallet management (built without the `wallet-pool` feature)
+//!
+//! Deployments with one funded wallet do not need KMS signers or Redis locks: the
+//! PRIVATE_KEY wallet pays gas for every transaction. This module mirrors the
+//! acquisition API of the pool `WalletManager`, so services and routes compile
+//! unchanged, but "acquiring" a wallet just takes an in-process mutex. Handles are
+//! therefore serialized within this instance only — run one instance per key.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use alloy::network::EthereumWallet;
+use alloy::primitives::{Address, B256, U256};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::signers::{Error as SignerError, Signature, Signer};
+use tokio::sync::OwnedMutexGuard;
+
+use super::nonce::{PoolNonceManager, endpoint_signing_provider};
+use super::{ForceUnlockOutcome, WALLET_POOL_DRAINING};
+use crate::AlloyProvider;
+use crate::models::WalletPoolStatusResponse;
+use crate::services::rpc::RpcEndpoints;
+
+/// How long an acquisition waits for the wallet (or a beacon update lock) to be
+/// released before failing, like the pool's lock retry budget.
+const ACQUIRE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Error for the pool-only admin operations.
+const POOL_DISABLED: &str = "Wallet pool support is not compiled in (built without the \
+                             `wallet-pool` feature)";
+
+/// A held per-beacon update lock. Released on drop.
+pub type BeaconUpdateLock = OwnedMutexGuard<()>;
+
+/// The PRIVATE_KEY signer that sends every transaction.
+#[derive(Clone)]
+pub struct WalletSigner(PrivateKeySigner);
+
+impl WalletSigner {
+    /// Get the address of the signer
+    pub fn address(&self) -> Address {
+        self.0.address()
+    }
+
+    /// Sign a hash using the underlying signer
+    pub async fn sign_hash(&self, hash: &B256) -> Result<Signature, SignerError> {
+        self.0.sign_hash(hash).await
+    }
+}
+
+/// A handle to the wallet, held exclusively until dropped.
+pub struct WalletHandle {
+    /// The signer for this wallet
+    pub signer: WalletSigner,
+    /// Shared nonce source (see [`WalletManager`]); reset on resync
+    nonces: Arc<Mutex<PoolNonceManager>>,
+    /// In-process lock - the wallet is exclusive to this handle until dropped
+    _guard: OwnedMutexGuard<()>,
+}
+
+impl WalletHandle {
+    /// Get the Ethereum address of this wallet
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    /// Always `Ok`: an in-process lock cannot expire or be taken over.
+    pub fn ensure_lock_held(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Build an AlloyProvider using this wallet's signer
+    ///
+    /// Every provider shares one cached nonce manager, so consecutive handles keep
+    /// counting from the last nonce this process sent instead of an RPC node's
+    /// possibly lagging pending count.
+    pub fn build_provider(&self, endpoints: &Arc<RpcEndpoints>) -> Result<AlloyProvider, String> {
+        let nonces = self.nonces.lock().unwrap().clone();
+        Ok(endpoint_signing_provider(
+            EthereumWallet::from(self.signer.0.clone()),
+            endpoints,
+            nonces,
+        ))
+    }
+
+    /// Drop the cached nonces so the next send, from this provider or a new one, reads
+    /// the chain's pending count.
+    ///
+    /// Call after a send fails with a nonce error.
+    pub async fn resync_nonce(&self, provider: &AlloyProvider) {
+        let nonces = self.nonces.lock().unwrap().clone();
+        // No ledger here, so this only resets the shared cache and cannot fail.
+        let _ = nonces.resync(provider).await;
+        tracing::warn!(
+            "Reset cached nonces for wallet {}; the next send reads the chain",
+            self.address()
+        );
+    }
+}
+
+/// Single-signer stand-in for the pool `WalletManager`.
+///
+/// Every acquisition returns a handle to the same wallet once the previous handle
+/// has been dropped. Exclusions and beacon designations do not apply: there is no
+/// other wallet to fall back to.
+pub struct WalletManager {
+    signer: WalletSigner,
+    /// Held by the live [`WalletHandle`], if any
+    wallet_lock: Arc<tokio::sync::Mutex<()>>,
+    /// Cached nonce manager shared by every handle's providers
+    nonces: Arc<Mutex<PoolNonceManager>>,
+    /// Per-beacon ECDSA update locks
+    beacon_locks: Mutex<HashMap<Address, Arc<tokio::sync::Mutex<()>>>>,
+    /// Whether this manager was built by [`Self::test_stub`]
+    is_test_stub: bool,
+    /// Set by `POST /maintenance/drain`: acquisitions are refused
+    draining: AtomicBool,
+}
+
+impl WalletManager {
+    /// Create a manager that sends everything from `signer`.
+    pub fn new(signer: PrivateKeySigner) -> Self {
+        Self {
+            signer: WalletSigner(signer),
+            wallet_lock: Arc::new(tokio::sync::Mutex::new(())),
+            nonces: Arc::new(Mutex::new(PoolNonceManager::default())),
+            beacon_locks: Mutex::new(HashMap::new()),
+            is_test_stub: false,
+            draining: AtomicBool::new(false),
+        }
+    }
+
+    /// Create a test stub around a random key, for test utilities that need to
+    /// construct AppState but never send.
+    pub fn test_stub() -> Self {
+        Self {
+            is_test_stub: true,
+            ..Self::new(PrivateKeySigner::random())
+        }
+    }
+
+    /// The single wallet's address
+    pub fn signer_addresses(&self) -> Vec<Address> {
+        vec![self.signer.address()]
+    }
+
+    /// Acquire the wallet, waiting for the current holder to drop its handle.
+    pub async fn acquire_any_wallet(&self) -> Result<WalletHandle, String> {
+        if self.is_draining() {
+            return Err(WALLET_POOL_DRAINING.to_string());
+        }
+        let guard = tokio::time::timeout(ACQUIRE_TIMEOUT, self.wallet_lock.clone().lock_owned())
+            .await
+            .map_err(|_| {
+                format!(
+                    "Wallet {} stayed busy for {ACQUIRE_TIMEOUT:?}",
+                    self.signer.address()
+                )
+            })?;
+        Ok(WalletHandle {
+            signer: self.signer.clone(),
+            nonces: Arc::clone(&self.nonces),
+            _guard: guard,
+        })
+    }
+
+    /// Acquire the wallet unless it is excluded.
+    pub async fn acquire_any_wallet_excluding(
+        &self,
+        exclude: &HashSet<Address>,
+    ) -> Result<WalletHandle, String> {
+        if self.is_draining() {
+            return Err(WALLET_POOL_DRAINING.to_string());
+        }
+        if exclude.contains(&self.signer.address()) {
+            return Err("No available wallets after exclusions".to_string());
+        }
+        self.acquire_any_wallet().await
+    }
+
+    /// Acquire the wallet for an operation on a beacon (designations do not apply).
+    pub async fn acquire_for_beacon(&self, _beacon: &Address) -> Result<WalletHandle, String> {
+        self.acquire_any_wallet().await
+    }
+
+    /// Acquire the wallet by address; any other address has no signer here.
+    pub async fn acquire_specific_wallet(&self, address: &Address) -> Result<WalletHandle, String> {
+        if self.is_draining() {
+            return Err(WALLET_POOL_DRAINING.to_string());
+        }
+        if *address != self.signer.address() {
+            return Err(format!("No signer available for wallet {address}"));
+        }
+        self.acquire_any_wallet().await
+    }
+
+    /// Acquire the wallet for a USDC transfer. There is no balance cache to order by;
+    /// the caller checks the on-chain balance after acquisition as with the pool.
+    pub async fn acquire_wallet_for_usdc(
+        &self,
+        _min_usdc: U256,
+        exclude: &HashSet<Address>,
+    ) -> Result<WalletHandle, String> {
+        self.acquire_any_wallet_excluding(exclude).await
+    }
+
+    /// Serialize ECDSA updates for one beacon within this instance.
+    ///
+    /// Same contract as the pool version: hold the guard from nonce generation
+    /// through receipt so updates for a beacon are strictly ordered.
+    pub async fn acquire_beacon_update_lock(
+        &self,
+        beacon: Address,
+    ) -> Result<BeaconUpdateLock, String> {
+        let lock = Arc::clone(self.beacon_locks.lock().unwrap().entry(beacon).or_default());
+        tokio::time::timeout(ACQUIRE_TIMEOUT, lock.lock_owned())
+            .await
+            .map_err(|_| format!("Failed to acquire beacon update lock for {beacon}: timed out"))
+    }
+
+    /// How long acquisitions wait for the wallet (reported where the pool reports its
+    /// lock TTL; an in-process lock never expires).
+    pub fn lock_ttl(&self) -> Duration {
+        ACQUIRE_TIMEOUT
+    }
+
+    /// Check if this is a test stub
+    pub fn is_test_stub(&self) -> bool {
+        self.is_test_stub
+    }
+
+    /// Start or stop draining for maintenance, like the pool version. Returns whether
+    /// the manager was draining before.
+    pub fn set_draining(&self, draining: bool) -> bool {
+        self.draining.swap(draining, Ordering::SeqCst)
+    }
+
+    /// Whether the manager is draining for maintenance.
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Not available without the pool: there are no distributed locks to release.
+    pub async fn force_unlock(
+        &self,
+        _wallet: &Address,
+        _expected_holder: &str,
+    ) -> Result<ForceUnlockOutcome, String> {
+        Err(POOL_DISABLED.to_string())
+    }
+
+    /// Not available without the pool: every beacon uses the single wallet.
+    pub async fn designate_beacon(
+        &self,
+        _beacon: &Address,
+        _wallet: &Address,
+    ) -> Result<Option<Address>, String> {
+        Err(POOL_DISABLED.to_string())
+    }
+
+    /// Not available without the pool: every beacon uses the single wallet.
+    pub async fn clear_beacon_designation(
+        &self,
+        _beacon: &Address,
+    ) -> Result<Option<Address>, String> {
+        Err(POOL_DISABLED.to_string())
+    }
+
+    /// Degraded status carrying only the local signer, like a pool without Redis.
+    pub async fn pool_status(&self) -> WalletPoolStatusResponse {
+        WalletPoolStatusResponse {
+            degraded: true,
+            error: Some(POOL_DISABLED.to_string()),
+            instance_id: None,
+            signer_count: 1,
+            total_wallets: 0,
+            status_counts: Default::default(),
+            locked_wallets: 0,
+            unregistered_signers: Vec::new(),
+            designations: Default::default(),
+            wallets: Vec::new(),
+        }
+    }
+}
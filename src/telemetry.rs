@@ -0,0 +1,124 @@
+//! Optional Sentry error reporting.
+//!
+//! Off unless `SENTRY_DSN` is set. Error-level tracing events are sent as Sentry events,
+//! lower levels ride along as breadcrumbs, and request spans become transactions (see
+//! [`crate::logging::init_tracing`]). Panics are reported by Sentry's panic integration.
+//!
+//! - `SENTRY_TRACES_SAMPLE_RATE`: fraction of transactions sent, clamped to `[0.0, 1.0]`
+//!   (default [`DEFAULT_TRACES_SAMPLE_RATE`])
+//! - `SENTRY_ENVIRONMENT`: environment tag, defaulting to `ENV` (mainnet / testnet /
+//!   localnet)
+//!
+//! Every event carries `chain_id`, `env_type` and `wallet_address` tags from
+//! [`configure_sentry_scope`]; events from the error catchers add the request's method,
+//! path and id (see [`crate::fairings::PanicCatcher::tag_request`]).
+
+use alloy::primitives::Address;
+
+/// Sample every transaction unless `SENTRY_TRACES_SAMPLE_RATE` says otherwise.
+pub const DEFAULT_TRACES_SAMPLE_RATE: f64 = 1.0;
+
+/// Sentry client settings read from the environment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentryConfig {
+    pub dsn: String,
+    pub environment: Option<String>,
+    pub traces_sample_rate: f64,
+}
+
+impl SentryConfig {
+    /// Read the settings; `None` when `SENTRY_DSN` is unset or blank.
+    pub fn from_env() -> Option<Self> {
+        let dsn = std::env::var("SENTRY_DSN").ok()?.trim().to_string();
+        if dsn.is_empty() {
+            return None;
+        }
+        let environment = ["SENTRY_ENVIRONMENT", "ENV"]
+            .iter()
+            .filter_map(|key| std::env::var(key).ok())
+            .map(|v| v.trim().to_lowercase())
+            .find(|v| !v.is_empty());
+        let traces_sample_rate =
+            parse_traces_sample_rate(std::env::var("SENTRY_TRACES_SAMPLE_RATE").ok().as_deref())
+                .unwrap_or_else(|e| {
+                    tracing::warn!("{e}; using {DEFAULT_TRACES_SAMPLE_RATE}");
+                    DEFAULT_TRACES_SAMPLE_RATE
+                });
+        Some(Self {
+            dsn,
+            environment,
+            traces_sample_rate,
+        })
+    }
+}
+
+/// Parse a `SENTRY_TRACES_SAMPLE_RATE` value. Unset or blank is the default; values
+/// outside `[0.0, 1.0]` are clamped. Non-numeric values (including NaN) are errors.
+pub fn parse_traces_sample_rate(value: Option<&str>) -> Result<f64, String> {
+    let Some(raw) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Ok(DEFAULT_TRACES_SAMPLE_RATE);
+    };
+    let rate = raw
+        .parse::<f64>()
+        .map_err(|e| format!("Invalid SENTRY_TRACES_SAMPLE_RATE '{raw}': {e}"))?;
+    if rate.is_nan() {
+        return Err(format!(
+            "Invalid SENTRY_TRACES_SAMPLE_RATE '{raw}': not a number"
+        ));
+    }
+    Ok(rate.clamp(0.0, 1.0))
+}
+
+/// Start the Sentry client when `SENTRY_DSN` is set. Keep the returned guard alive for
+/// the life of the process: dropping it flushes pending events and stops reporting.
+///
+/// Call after installing any custom panic hook, so Sentry's hook wraps it.
+pub fn init_sentry() -> Option<sentry::ClientInitGuard> {
+    let Some(config) = SentryConfig::from_env() else {
+        tracing::info!("Sentry disabled (SENTRY_DSN not set)");
+        return None;
+    };
+    // The DSN embeds the project key; log the parse error only.
+    let dsn = match config.dsn.parse::<sentry::types::Dsn>() {
+        Ok(dsn) => dsn,
+        Err(e) => {
+            tracing::error!("Invalid SENTRY_DSN ({e}); Sentry disabled");
+            return None;
+        }
+    };
+
+    let guard = sentry::init(sentry::ClientOptions {
+        dsn: Some(dsn),
+        release: sentry::release_name!(),
+        environment: config.environment.clone().map(Into::into),
+        traces_sample_rate: config.traces_sample_rate as f32,
+        ..Default::default()
+    });
+    tracing::info!(
+        "Sentry enabled (environment: {}, traces sample rate: {})",
+        config.environment.as_deref().unwrap_or("unset"),
+        config.traces_sample_rate
+    );
+    Some(guard)
+}
+
+/// Set the deployment tags on `scope`.
+pub fn apply_global_tags(
+    scope: &mut sentry::Scope,
+    chain_id: u64,
+    env_type: &str,
+    wallet_address: Address,
+) {
+    scope.set_tag("chain_id", chain_id);
+    scope.set_tag("env_type", env_type.trim().to_lowercase());
+    scope.set_tag("wallet_address", wallet_address);
+}
+
+/// Tag every Sentry event from this process with the chain, environment and signer
+/// wallet. Configures the main hub, which the hubs of other threads are cloned from;
+/// call it during startup, before background tasks are spawned. A no-op for reporting
+/// while Sentry is disabled.
+pub fn configure_sentry_scope(chain_id: u64, env_type: &str, wallet_address: Address) {
+    sentry::Hub::main()
+        .configure_scope(|scope| apply_global_tags(scope, chain_id, env_type, wallet_address));
+}
@@ -15,7 +15,9 @@ use std::sync::Arc;
 use the_beaconator::ReadOnlyProvider;
 use the_beaconator::services::wallet::BalanceTracker;
 
-use crate::test_utils::{AnvilManager, deploy_contract, load_contract_bytecode};
+use crate::test_utils::{
+    AnvilManager, build_test_signing_provider, deploy_contract, load_contract_bytecode,
+};
 
 sol! {
     #[sol(rpc)]
@@ -40,11 +42,7 @@ async fn setup_sweep_fixture() -> SweepFixture {
 
     let signer = anvil.deployer_signer();
     let wallet = EthereumWallet::from(signer);
-    let deploy_provider = Arc::new(
-        ProviderBuilder::new()
-            .wallet(wallet)
-            .connect_http(anvil.rpc_url().parse().expect("valid anvil url")),
-    );
+    let deploy_provider = Arc::new(build_test_signing_provider(wallet, anvil.rpc_url()));
 
     let multicall3 = deploy_contract(&deploy_provider, load_contract_bytecode("MockMulticall3"))
         .await
@@ -7,6 +7,7 @@ use the_beaconator::services::beacon::core::{
     create_identity_beacon, is_beacon_registered, is_transaction_confirmed,
     register_beacon_with_registry, update_beacon,
 };
+use the_beaconator::services::beacon::read::read_beacon_data;
 
 /// Test identity beacon creation with Anvil
 ///
@@ -30,7 +31,7 @@ async fn test_create_identity_beacon_with_anvil() {
             let is_registered = is_beacon_registered(
                 &app_state,
                 beacon_address,
-                app_state.contracts.perpcity_registry,
+                app_state.contracts.load().perpcity_registry,
             )
             .await;
             assert!(is_registered.is_ok());
@@ -64,7 +65,7 @@ async fn test_register_beacon_with_registry_integration() {
         }
     };
 
-    let registry_address = app_state.contracts.perpcity_registry;
+    let registry_address = app_state.contracts.load().perpcity_registry;
     let register_result =
         register_beacon_with_registry(&app_state, beacon_address, registry_address).await;
 
@@ -113,7 +114,10 @@ async fn test_update_beacon_integration() {
         Ok(_) => println!("Beacon update succeeded"),
         Err(e) => {
             println!("Beacon update failed (expected): {e}");
-            assert!(!e.contains("network"), "Should not be a network error: {e}");
+            assert!(
+                !e.to_string().contains("network"),
+                "Should not be a network error: {e}"
+            );
         }
     }
 }
@@ -150,7 +154,7 @@ async fn test_beacon_registration_check_integration() {
 
     let unregistered_beacon =
         Address::from_str("0x1234567890123456789012345678901234567890").unwrap();
-    let registry_address = app_state.contracts.perpcity_registry;
+    let registry_address = app_state.contracts.load().perpcity_registry;
 
     let is_registered =
         is_beacon_registered(&app_state, unregistered_beacon, registry_address).await;
@@ -236,6 +240,7 @@ async fn test_beacon_operations_error_handling() {
     assert!(
         update_result
             .unwrap_err()
+            .to_string()
             .contains("Invalid beacon address")
     );
 }
@@ -324,3 +329,53 @@ async fn test_concurrent_beacon_operations() {
         beacon_addresses.len()
     );
 }
+
+/// Test reading several beacons in one batch
+///
+/// Bare Anvil has no Multicall3, so with the default address configured the read
+/// falls back to per-beacon calls; with a Multicall3 deployed it goes out in one call.
+/// Either way every beacon should report its initial index.
+#[tokio::test]
+#[ignore] // Temporarily disabled - hangs due to real network calls
+#[serial]
+async fn test_batch_read_beacon_data_with_anvil() {
+    let (app_state, _manager) = crate::test_utils::create_isolated_test_app_state().await;
+
+    let mut beacons = Vec::new();
+    for initial_index in [100u128, 200, 300] {
+        match create_identity_beacon(&app_state, initial_index).await {
+            Ok((beacon_address, _verifier_address)) => {
+                beacons.push((beacon_address, initial_index))
+            }
+            Err(e) => {
+                println!("Skipping batch read test - beacon creation failed: {e}");
+                return;
+            }
+        }
+    }
+
+    let mut addresses: Vec<String> = beacons
+        .iter()
+        .map(|(address, _)| format!("{address:#x}"))
+        .collect();
+    // An address without code fails on its own entry
+    addresses.push(format!("{:#x}", Address::repeat_byte(0x42)));
+
+    let response = read_beacon_data(&app_state, &addresses).await;
+    println!("Batch beacon read: {response:?}");
+
+    assert_eq!(response.total_requested, 4);
+    assert_eq!(response.successful_reads, 3);
+    assert_eq!(response.failed_reads, 1);
+    assert_eq!(response.timestamp.is_some(), response.via_multicall);
+    for (result, (address, initial_index)) in response.results.iter().zip(&beacons) {
+        assert!(
+            result.success,
+            "read of {address} failed: {:?}",
+            result.error
+        );
+        assert_eq!(result.beacon_address, address.to_checksum(None));
+        assert_eq!(result.index, Some(initial_index.to_string()));
+    }
+    assert!(!response.results[3].success);
+}
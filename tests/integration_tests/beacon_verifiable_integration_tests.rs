@@ -1,7 +1,9 @@
 use alloy::primitives::Address;
 use serial_test::serial;
 
-use the_beaconator::services::beacon::core::create_identity_beacon;
+use the_beaconator::services::beacon::core::{
+    create_identity_beacon, create_identity_beacon_with_verifier,
+};
 
 /// Test identity beacon creation with Anvil
 #[tokio::test]
@@ -84,3 +86,34 @@ async fn test_concurrent_identity_beacon_operations() {
 
     println!("Concurrent identity beacon operations: {success_count} successes");
 }
+
+/// Test reusing an existing ECDSA verifier for a second identity beacon
+#[tokio::test]
+#[ignore] // Temporarily disabled - hangs due to real network calls
+#[serial]
+async fn test_create_identity_beacon_reusing_verifier() {
+    let (app_state, _manager) = crate::test_utils::create_isolated_test_app_state().await;
+    let bytecode_hex = std::fs::read_to_string("abis/IdentityBeacon.bytecode").unwrap();
+    app_state.contracts.update(|contracts| {
+        contracts.identity_beacon_bytecode =
+            hex::decode(bytecode_hex.trim().trim_start_matches("0x"))
+                .unwrap()
+                .into();
+    });
+
+    let (first_beacon, verifier) = match create_identity_beacon(&app_state, 12345).await {
+        Ok(result) => result,
+        Err(e) => {
+            println!("Identity beacon creation failed (may be expected): {e}");
+            return;
+        }
+    };
+    assert_ne!(verifier, Address::ZERO);
+
+    let (second_beacon, reused_verifier) =
+        create_identity_beacon_with_verifier(&app_state, 12345, Some(verifier))
+            .await
+            .expect("reusing a verifier we just created should succeed");
+    assert_eq!(reused_verifier, verifier);
+    assert_ne!(second_beacon, first_beacon);
+}
@@ -0,0 +1,143 @@
+//! Integration tests for `CONFIRMATION_BLOCKS` (src/services/transaction/confirmations.rs).
+//!
+//! Anvil mines a block every second; tests that need depth quickly mine extra
+//! blocks with `anvil_mine`.
+
+use std::time::{Duration, Instant};
+
+use alloy::network::{EthereumWallet, TransactionBuilder};
+use alloy::primitives::{B256, U256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::rpc::types::TransactionRequest;
+use serial_test::serial;
+use the_beaconator::ReadOnlyProvider;
+use the_beaconator::services::rpc::ReadRetryPolicy;
+use the_beaconator::services::transaction::{ConfirmationPolicy, wait_for_confirmations};
+
+use crate::test_utils::AnvilManager;
+
+fn read_provider(anvil: &AnvilManager) -> ReadOnlyProvider {
+    ProviderBuilder::new().connect_http(anvil.rpc_url().parse().unwrap())
+}
+
+/// Send a 1 wei transfer and return its hash and block.
+async fn mined_transfer(anvil: &AnvilManager) -> (B256, u64) {
+    let provider = ProviderBuilder::new()
+        .wallet(EthereumWallet::from(anvil.deployer_signer()))
+        .connect_http(anvil.rpc_url().parse().unwrap());
+    let tx = TransactionRequest::default()
+        .with_to(anvil.get_signer(1).address())
+        .with_value(U256::from(1u64));
+    let receipt = provider
+        .send_transaction(tx)
+        .await
+        .expect("send transfer")
+        .get_receipt()
+        .await
+        .expect("transfer receipt");
+    (receipt.transaction_hash, receipt.block_number.unwrap())
+}
+
+#[tokio::test]
+#[serial]
+async fn test_waits_for_configured_depth() {
+    let anvil = AnvilManager::new().await;
+    let provider = read_provider(&anvil);
+    let (tx_hash, block) = mined_transfer(&anvil).await;
+    let policy = ConfirmationPolicy::new(3, Duration::from_millis(100), Duration::from_secs(30));
+
+    let depth = wait_for_confirmations(
+        &provider,
+        &policy,
+        &ReadRetryPolicy::default(),
+        tx_hash,
+        block,
+    )
+    .await
+    .expect("should reach 3 confirmations");
+
+    assert!(depth >= 3, "returned depth {depth}");
+    let head = provider.get_block_number().await.unwrap();
+    assert!(
+        head - block >= 3,
+        "head {head} is not 3 blocks past {block}"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_returns_once_blocks_are_mined() {
+    let anvil = AnvilManager::new().await;
+    let provider = read_provider(&anvil);
+    let (tx_hash, block) = mined_transfer(&anvil).await;
+    let policy = ConfirmationPolicy::new(50, Duration::from_millis(100), Duration::from_secs(30));
+
+    let miner = read_provider(&anvil);
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let _: serde_json::Value = miner
+            .raw_request("anvil_mine".into(), (U256::from(50u64),))
+            .await
+            .expect("anvil_mine");
+    });
+
+    let started = Instant::now();
+    let depth = wait_for_confirmations(
+        &provider,
+        &policy,
+        &ReadRetryPolicy::default(),
+        tx_hash,
+        block,
+    )
+    .await
+    .expect("should reach 50 confirmations after mining");
+
+    assert!(depth >= 50, "returned depth {depth}");
+    // 50 blocks at Anvil's 1s block time would take 50s; mining is what got us here.
+    assert!(started.elapsed() < Duration::from_secs(20));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_times_out_before_depth() {
+    let anvil = AnvilManager::new().await;
+    let provider = read_provider(&anvil);
+    let (tx_hash, block) = mined_transfer(&anvil).await;
+    let policy = ConfirmationPolicy::new(1_000, Duration::from_millis(100), Duration::from_secs(1));
+
+    let err = wait_for_confirmations(
+        &provider,
+        &policy,
+        &ReadRetryPolicy::default(),
+        tx_hash,
+        block,
+    )
+    .await
+    .unwrap_err();
+
+    assert!(
+        err.contains("/1000 confirmation(s)"),
+        "unexpected error: {err}"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_unknown_transaction_fails_once_deep_enough() {
+    let anvil = AnvilManager::new().await;
+    let provider = read_provider(&anvil);
+    let policy = ConfirmationPolicy::new(1, Duration::from_millis(100), Duration::from_secs(30));
+
+    // No such tx exists, as if a reorg dropped it after its receipt was seen.
+    let err = wait_for_confirmations(
+        &provider,
+        &policy,
+        &ReadRetryPolicy::default(),
+        B256::repeat_byte(0x42),
+        0,
+    )
+    .await
+    .unwrap_err();
+
+    assert!(err.contains("disappeared"), "unexpected error: {err}");
+}
@@ -182,7 +182,7 @@ async fn test_create_and_register_no_registry() {
     assert_eq!(response.beacon_type, "lbcgbm");
     assert!(!response.registered); // No registry means not registered
     assert!(response.safe_proposal_hash.is_none());
-    assert!(response.beacon_address.contains("eeeeeeee"));
+    assert_eq!(response.beacon_address, beacon_address.to_checksum(None));
 }
 
 #[tokio::test]
@@ -212,5 +212,5 @@ async fn test_create_and_register_with_registry_fails_gracefully() {
     let response = result.unwrap();
     assert_eq!(response.beacon_type, "weighted-sum-composite");
     assert!(!response.registered); // Registration failed but beacon data returned
-    assert!(response.beacon_address.contains("eeeeeeee"));
+    assert_eq!(response.beacon_address, beacon_address.to_checksum(None));
 }
@@ -18,6 +18,7 @@
 #[cfg(test)]
 mod tests {
     use alloy::primitives::{Address, B256, U256};
+    use alloy::providers::Provider;
     use serial_test::serial;
     use std::str::FromStr;
     use the_beaconator::models::recipe::{BeaconKind, BeaconRecipe};
@@ -46,7 +47,6 @@ mod tests {
     }
 
     async fn has_code(state: &AppState, address: Address) -> bool {
-        use alloy::providers::Provider;
         state
             .provider
             .read_provider
@@ -142,22 +142,39 @@ mod tests {
         .await
         .expect("ECDSA update against real verifier");
         assert_ne!(outcome.tx_hash, B256::ZERO);
+        assert!(
+            outcome.gas_used.is_some_and(|gas| gas > 0),
+            "confirmed update must report gas used"
+        );
         let index = beacon_contract.index().call().await.expect("index()");
         assert_eq!(index, new_index_q96, "IndexUpdated must land the new value");
 
         // --- Perp deploy: real createPerp encoding + PerpCreated decode ---
-        let response = deploy_perp_for_beacon(
-            &app_state,
-            beacon,
-            pool_wallet,
-            "Fork Test Market".to_string(),
-            "FORK".to_string(),
-            "ipfs://fork-test".to_string(),
-            3600,
-            B256::from(U256::from(0xf02c_u64)),
-        )
-        .await
-        .expect("deploy perp against real factory");
+        // The beacon was created on the fork, so the existing-perp scan only needs
+        // the fork's own blocks (the upstream RPC would reject a scan from genesis).
+        let fork_head = app_state
+            .provider
+            .read_provider
+            .get_block_number()
+            .await
+            .expect("block number");
+        unsafe {
+            std::env::set_var("PERP_FACTORY_DEPLOY_BLOCK", fork_head.to_string());
+        }
+        let deploy = || {
+            deploy_perp_for_beacon(
+                &app_state,
+                beacon,
+                pool_wallet,
+                "Fork Test Market".to_string(),
+                "FORK".to_string(),
+                "ipfs://fork-test".to_string(),
+                3600,
+                B256::from(U256::from(0xf02c_u64)),
+            )
+        };
+        let response = deploy().await.expect("deploy perp against real factory");
+        assert!(!response.already_deployed);
 
         let perp = Address::from_str(&response.perp_address).expect("perp address");
         assert!(has_code(&app_state, perp).await, "perp has code");
@@ -170,5 +187,40 @@ mod tests {
             !response.pool_id.is_empty() && response.pool_id != format!("0x{}", "00".repeat(32)),
             "pool id must decode from PerpCreated"
         );
+        assert!(response.gas_used > 0, "createPerp must report gas used");
+
+        // --- Re-deploy: the existing perp comes back without a second createPerp ---
+        let again = deploy().await.expect("re-deploy returns the existing perp");
+        unsafe {
+            std::env::remove_var("PERP_FACTORY_DEPLOY_BLOCK");
+        }
+        assert!(again.already_deployed);
+        assert_eq!(again.perp_address, response.perp_address);
+        assert_eq!(again.pool_id, response.pool_id);
+        assert_eq!(again.transaction_hash, response.transaction_hash);
+        assert_eq!(again.gas_used, 0);
+
+        // --- Gas histograms: every confirmed write above was recorded ---
+        let metrics = app_state.gas_metrics.snapshot();
+        for operation in [
+            "beacon_create",
+            "beacon_register",
+            "beacon_update",
+            "perp_deploy",
+        ] {
+            let histogram = metrics
+                .operations
+                .iter()
+                .find(|h| h.operation == operation)
+                .unwrap_or_else(|| panic!("no gas histogram for {operation}"));
+            assert!(histogram.count > 0 && histogram.max > 0, "{operation}");
+        }
+        let perp_deploy = metrics
+            .operations
+            .iter()
+            .find(|h| h.operation == "perp_deploy")
+            .unwrap();
+        assert_eq!(perp_deploy.count, 1);
+        assert_eq!(perp_deploy.max, response.gas_used);
     }
 }
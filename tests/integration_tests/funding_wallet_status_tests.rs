@@ -0,0 +1,70 @@
+//! Integration tests for the admin funding wallet status reads.
+//!
+//! Requires compiled mock artifacts: `cd tests/contracts && forge build`.
+
+use alloy::network::EthereumWallet;
+use alloy::primitives::U256;
+use alloy::sol;
+use std::sync::Arc;
+use the_beaconator::routes::wallet::read_funding_wallet_status;
+
+use crate::test_utils::{
+    build_test_signing_provider, create_isolated_test_app_state, deploy_contract,
+    load_contract_bytecode,
+};
+
+sol! {
+    #[sol(rpc)]
+    interface IMockUSDC {
+        function mint(address to, uint256 amount) external;
+    }
+}
+
+#[tokio::test]
+async fn test_funding_wallet_status_reads_live_balances() {
+    let (app_state, anvil) = create_isolated_test_app_state().await;
+
+    let wallet = EthereumWallet::from(anvil.deployer_signer());
+    let deploy_provider = Arc::new(build_test_signing_provider(wallet, anvil.rpc_url()));
+    let usdc = deploy_contract(&deploy_provider, load_contract_bytecode("MockUSDC"))
+        .await
+        .expect("deploy MockUSDC");
+    app_state
+        .contracts
+        .update(|contracts| contracts.usdc = usdc);
+
+    let funded = anvil.deployer_account();
+    let unfunded = anvil.get_signer(1).address();
+    IMockUSDC::new(usdc, &*deploy_provider)
+        .mint(funded, U256::from(25_000_000u64)) // 25 USDC
+        .send()
+        .await
+        .expect("send mint")
+        .get_receipt()
+        .await
+        .expect("mint receipt");
+
+    let status = read_funding_wallet_status(&app_state, &[funded, unfunded])
+        .await
+        .expect("status reads succeed");
+
+    assert_eq!(status.wallets.len(), 2);
+    let (first, second) = (&status.wallets[0], &status.wallets[1]);
+    assert_eq!(first.address, funded.to_string());
+    assert_eq!(second.address, unfunded.to_string());
+
+    for entry in &status.wallets {
+        let eth: U256 = entry.eth_balance_wei.parse().expect("wei is a number");
+        assert!(eth > U256::ZERO, "Anvil accounts start funded");
+        assert!(!entry.below_eth_reserve);
+    }
+    assert_eq!(first.usdc_balance, "25000000");
+    assert_eq!(second.usdc_balance, "0");
+    assert_eq!(status.total_usdc, "25000000");
+    // The deployer sent the deploy and mint transactions.
+    assert!(first.nonce >= 2);
+    assert_eq!(
+        status.eth_reserve_wei,
+        app_state.wallets.faucet_reserve_eth_wei.to_string()
+    );
+}
@@ -0,0 +1,136 @@
+//! Integration tests for the offline mock mode (`BEACONATOR_MODE=mock`).
+//!
+//! Runs the assembled server with no chain, Redis or KMS behind it.
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use the_beaconator::mock_rocket;
+use the_beaconator::models::AuthConfig;
+use the_beaconator::services::mock::{
+    MOCK_RPC_URL, check_mock_mode_allowed, mock_address, mock_app_state, mock_mode,
+};
+
+fn auth() -> AuthConfig {
+    AuthConfig {
+        access_token: "mock_token".to_string(),
+        admin_token: "mock_admin_token".to_string(),
+        scoped_tokens: HashMap::new(),
+        request_signing: None,
+    }
+}
+
+async fn client() -> Client {
+    let state = mock_app_state("localnet", MOCK_RPC_URL, auth()).expect("mock state");
+    Client::tracked(mock_rocket(state))
+        .await
+        .expect("valid rocket")
+}
+
+async fn post(client: &Client, path: &str, body: Value) -> (Status, Value) {
+    let response = client
+        .post(path.to_string())
+        .header(Header::new("Authorization", "Bearer mock_token"))
+        .header(ContentType::JSON)
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    let body = response.into_json::<Value>().await.unwrap_or(Value::Null);
+    (status, body)
+}
+
+#[tokio::test]
+async fn test_mock_mode_create_beacon_returns_deterministic_address() {
+    let client = client().await;
+    let request = json!({ "beacon_type": "identity", "params": { "initial_index": 42 } });
+
+    let (status, first) = post(&client, "/create_beacon", request.clone()).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(first["success"], true);
+    let expected = mock_address("beacon", b"identity:42").to_checksum(None);
+    assert_eq!(first["data"]["beacon_address"], expected.as_str());
+    assert_eq!(first["data"]["beacon_type"], "identity");
+
+    // Same request, same beacon; a different one gets another address.
+    let (_, second) = post(&client, "/create_beacon", request).await;
+    assert_eq!(second["data"]["beacon_address"], expected.as_str());
+    let (_, other) = post(
+        &client,
+        "/create_beacon",
+        json!({ "beacon_type": "lbcgbm" }),
+    )
+    .await;
+    assert_ne!(other["data"]["beacon_address"], expected.as_str());
+}
+
+#[tokio::test]
+async fn test_mock_mode_still_requires_a_token() {
+    let client = client().await;
+    let response = client
+        .post("/create_beacon")
+        .header(ContentType::JSON)
+        .body(json!({ "beacon_type": "identity" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[tokio::test]
+async fn test_mock_mode_update_and_deploy() {
+    let client = client().await;
+    let beacon = mock_address("beacon", b"identity:0").to_checksum(None);
+
+    let (status, updated) = post(
+        &client,
+        "/update_beacon",
+        json!({ "beacon_address": beacon, "proof": "0x01", "public_signals": "0x02" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(updated["success"], true);
+
+    let deploy = json!({
+        "beacon_address": beacon,
+        "owner": "0x1111111111111111111111111111111111111111",
+        "name": "Mock Perp",
+        "symbol": "MOCK",
+        "token_uri": "",
+        "ema_window": 3600,
+    });
+    let (status, first) = post(&client, "/deploy_perp_for_beacon", deploy.clone()).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(first["data"]["mark_price"], "1");
+    assert_eq!(first["data"]["already_deployed"], false);
+    let (_, second) = post(&client, "/deploy_perp_for_beacon", deploy).await;
+    assert_eq!(
+        first["data"]["perp_address"],
+        second["data"]["perp_address"]
+    );
+
+    let (status, _) = post(
+        &client,
+        "/update_beacon",
+        json!({ "beacon_address": "not-an-address", "proof": "0x01", "public_signals": "0x02" }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+}
+
+#[test]
+fn test_mock_mode_is_refused_on_mainnet() {
+    assert!(check_mock_mode_allowed("localnet").is_ok());
+    assert!(check_mock_mode_allowed("testnet").is_ok());
+    assert!(check_mock_mode_allowed("mainnet").is_err());
+    assert!(check_mock_mode_allowed(" MAINNET ").is_err());
+    assert!(mock_app_state("mainnet", MOCK_RPC_URL, auth()).is_err());
+}
+
+#[test]
+fn test_mock_mode_values() {
+    assert_eq!(mock_mode(None), Ok(false));
+    assert_eq!(mock_mode(Some("live")), Ok(false));
+    assert_eq!(mock_mode(Some(" Mock ")), Ok(true));
+    assert!(mock_mode(Some("mocked")).is_err());
+}
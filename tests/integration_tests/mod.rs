@@ -1,16 +1,23 @@
 // Integration tests module
 
+#[cfg(feature = "wallet-pool")]
 pub mod balance_sweep_tests;
 pub mod beacon_core_integration_tests;
 pub mod beacon_verifiable_integration_tests;
+pub mod confirmation_depth_tests;
 pub mod factory_integration_tests;
+#[cfg(feature = "wallet-pool")]
 pub mod fork_tests;
+pub mod funding_wallet_status_tests;
+pub mod mock_mode_tests;
 pub mod models_test;
+#[cfg(feature = "wallet-pool")]
 pub mod nonce_conflict_tests;
 // pub mod nonce_sync_tests; // Removed - nonce management obsolete with WalletManager
 // pub mod perp_deployment_integration_tests; // Temporarily disabled during PerpManager refactor
 pub mod perp_integration_tests;
 pub mod register_beacon_integration_tests;
+pub mod rpc_failover_tests;
 pub mod touch_integration_tests;
 pub mod unregister_beacon_integration_tests;
 // pub mod transaction_execution_integration_tests; // Removed - nonce management obsolete with WalletManager
@@ -10,6 +10,7 @@ mod tests {
 
         let request = CreateBeaconWithEcdsaRequest {
             initial_index: 50_u128 << 96, // 50 scaled by 2^96
+            verifier_address: None,
         };
 
         // Test JSON serialization
@@ -28,6 +29,7 @@ mod tests {
         // Test valid request
         let valid_request = CreateBeaconWithEcdsaRequest {
             initial_index: 0, // Minimum value
+            verifier_address: None,
         };
 
         let json = serde_json::to_string(&valid_request).unwrap();
@@ -36,6 +38,7 @@ mod tests {
         // Test with maximum initial_index value
         let max_request = CreateBeaconWithEcdsaRequest {
             initial_index: u128::MAX,
+            verifier_address: None,
         };
 
         let json = serde_json::to_string(&max_request).unwrap();
@@ -92,6 +95,7 @@ mod tests {
         for (raw_value, expected_scaled) in test_values {
             let request = CreateBeaconWithEcdsaRequest {
                 initial_index: expected_scaled,
+                verifier_address: None,
             };
 
             // Verify the scaled value is correctly stored
@@ -118,6 +122,7 @@ mod tests {
         for index_value in boundary_values {
             let request = CreateBeaconWithEcdsaRequest {
                 initial_index: index_value,
+                verifier_address: None,
             };
 
             // Should serialize/deserialize without issues
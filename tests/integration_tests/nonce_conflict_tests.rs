@@ -1,7 +1,8 @@
 //! Redis-based nonce conflict prevention tests
 //!
 //! These tests verify that Redis-based wallet locking prevents nonce conflicts
-//! when multiple transactions are executed concurrently.
+//! when multiple transactions are executed concurrently, and that the shared
+//! per-wallet nonce record carries over between instances.
 //!
 //! Run with: `make test-wallet` (requires Redis container)
 
@@ -565,3 +566,187 @@ async fn test_beacon_wallet_mapping() {
 
     println!("Beacon wallet mapping test completed successfully");
 }
+
+/// Test that an operator can release a lock left behind by a crashed instance
+/// (`WalletManager::force_unlock`), and that live or mismatched owners are refused.
+#[tokio::test]
+#[serial]
+#[ignore = "requires Redis - run with make test-wallet"]
+async fn test_force_unlock_releases_stale_lock_then_reacquire() {
+    use alloy::signers::local::PrivateKeySigner;
+    use the_beaconator::services::wallet::{ForceUnlockOutcome, WalletManager};
+
+    let redis_url =
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+    let prefix = format!("test-{}:", uuid::Uuid::new_v4());
+    let signer = PrivateKeySigner::random();
+    let wallet = signer.address();
+
+    // Two instances sharing one pool: `crashed` takes the lock and never releases it.
+    let crashed = match WalletManager::test_with_mock_signers_and_prefix(
+        &redis_url,
+        vec![signer.clone()],
+        &prefix,
+    )
+    .await
+    {
+        Ok(manager) => manager,
+        Err(_) => {
+            println!("Cannot connect to Redis, skipping test");
+            return;
+        }
+    };
+    let operator =
+        WalletManager::test_with_mock_signers_and_prefix(&redis_url, vec![signer], &prefix)
+            .await
+            .expect("second manager");
+
+    let guard = crashed.acquire_lock(&wallet).await.expect("initial lock");
+    // Simulate the crash: the guard's Drop would otherwise release the lock.
+    std::mem::forget(guard);
+    let stale_holder = crashed.instance_id().to_string();
+
+    assert!(operator.acquire_lock(&wallet).await.is_err());
+
+    assert_eq!(
+        operator
+            .force_unlock(&wallet, "some-other-instance")
+            .await
+            .unwrap(),
+        ForceUnlockOutcome::HolderMismatch {
+            holder: stale_holder.clone()
+        }
+    );
+    assert_eq!(
+        crashed.force_unlock(&wallet, &stale_holder).await.unwrap(),
+        ForceUnlockOutcome::HeldByThisInstance
+    );
+    assert_eq!(
+        operator.force_unlock(&wallet, &stale_holder).await.unwrap(),
+        ForceUnlockOutcome::Released
+    );
+    assert_eq!(
+        operator.force_unlock(&wallet, &stale_holder).await.unwrap(),
+        ForceUnlockOutcome::NotLocked
+    );
+
+    let guard = operator
+        .acquire_lock(&wallet)
+        .await
+        .expect("lock is free again after force unlock");
+    guard.release().await.unwrap();
+    operator.pool().cleanup().await.unwrap();
+}
+
+/// Two instances taking turns on one wallet: the second must continue from the first
+/// one's recorded nonces even when its RPC node still reports the older pending count.
+#[tokio::test]
+#[serial]
+#[ignore = "requires Redis - run with make test-wallet"]
+async fn test_shared_nonce_survives_lagging_rpc_between_instances() {
+    use alloy::signers::local::PrivateKeySigner;
+    use the_beaconator::services::wallet::WalletManager;
+
+    let redis_url =
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+    let prefix = format!("test-{}:", uuid::Uuid::new_v4());
+    let signer = PrivateKeySigner::random();
+    let wallet = signer.address();
+
+    let instance_a = match WalletManager::test_with_mock_signers_and_prefix(
+        &redis_url,
+        vec![signer.clone()],
+        &prefix,
+    )
+    .await
+    {
+        Ok(manager) => manager,
+        Err(_) => {
+            println!("Cannot connect to Redis, skipping test");
+            return;
+        }
+    };
+    let instance_b =
+        WalletManager::test_with_mock_signers_and_prefix(&redis_url, vec![signer], &prefix)
+            .await
+            .expect("second manager");
+
+    // Instance A: chain reports 5 pending, A sends two transactions (nonces 5 and 6).
+    let guard_a = instance_a.acquire_lock(&wallet).await.expect("lock A");
+    let ledger_a = guard_a
+        .nonce_ledger()
+        .expect("wallet locks carry a nonce ledger");
+    assert_eq!(ledger_a.reconcile(5).await.unwrap(), 5);
+    assert_eq!(ledger_a.reserve().await.unwrap(), Some(5));
+    assert_eq!(ledger_a.reserve().await.unwrap(), Some(6));
+    guard_a.release().await.unwrap();
+
+    // Instance B's RPC node has not seen A's transactions yet and still reports 5.
+    let guard_b = instance_b.acquire_lock(&wallet).await.expect("lock B");
+    let ledger_b = guard_b.nonce_ledger().unwrap();
+    assert_eq!(ledger_b.reconcile(5).await.unwrap(), 7);
+    assert_eq!(ledger_b.reserve().await.unwrap(), Some(7));
+
+    // A chain count ahead of the record (a send from outside the pool) wins.
+    assert_eq!(ledger_b.reconcile(10).await.unwrap(), 10);
+    // A forced resync (after a nonce error) overrides the record either way.
+    assert_eq!(ledger_b.resync(9).await.unwrap(), 9);
+    assert_eq!(ledger_b.recorded().await.unwrap(), Some(9));
+
+    guard_b.release().await.unwrap();
+    instance_b.pool().cleanup().await.unwrap();
+}
+
+/// Nonces are reserved only under the wallet lock: a ledger outliving its lock, or
+/// belonging to an instance that does not hold the lock, cannot hand out nonces.
+#[tokio::test]
+#[serial]
+#[ignore = "requires Redis - run with make test-wallet"]
+async fn test_nonce_reservation_fenced_by_wallet_lock() {
+    use alloy::signers::local::PrivateKeySigner;
+    use the_beaconator::services::wallet::WalletManager;
+
+    let redis_url =
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+    let prefix = format!("test-{}:", uuid::Uuid::new_v4());
+    let signer = PrivateKeySigner::random();
+    let wallet = signer.address();
+
+    let instance_a = match WalletManager::test_with_mock_signers_and_prefix(
+        &redis_url,
+        vec![signer.clone()],
+        &prefix,
+    )
+    .await
+    {
+        Ok(manager) => manager,
+        Err(_) => {
+            println!("Cannot connect to Redis, skipping test");
+            return;
+        }
+    };
+    let instance_b =
+        WalletManager::test_with_mock_signers_and_prefix(&redis_url, vec![signer], &prefix)
+            .await
+            .expect("second manager");
+
+    let guard_a = instance_a.acquire_lock(&wallet).await.expect("lock A");
+    let stale_ledger = guard_a.nonce_ledger().unwrap();
+    assert_eq!(stale_ledger.reconcile(3).await.unwrap(), 3);
+    guard_a.release().await.unwrap();
+
+    // Released: A can no longer reserve or reseed.
+    let err = stale_ledger.reserve().await.unwrap_err();
+    assert!(err.contains("not held"), "unexpected error: {err}");
+    assert!(stale_ledger.reconcile(3).await.is_err());
+
+    // Held by B: still fenced for A, while B continues from the record.
+    let guard_b = instance_b.acquire_lock(&wallet).await.expect("lock B");
+    assert!(stale_ledger.reserve().await.is_err());
+    let ledger_b = guard_b.nonce_ledger().unwrap();
+    assert_eq!(ledger_b.reserve().await.unwrap(), Some(3));
+    assert_eq!(stale_ledger.recorded().await.unwrap(), Some(4));
+
+    guard_b.release().await.unwrap();
+    instance_b.pool().cleanup().await.unwrap();
+}
@@ -1,16 +1,23 @@
 // Perp integration tests - extracted from src/routes/perp.rs backup file
 
 use crate::test_utils::create_simple_test_app_state;
-use alloy::primitives::{FixedBytes, U256};
-use rocket::serde::json::Json;
+use alloy::primitives::{Address, FixedBytes, U256};
+use alloy::sol_types::SolCall;
 use rocket::{State, http::Status};
 use serial_test::serial;
 use std::str::FromStr;
-use the_beaconator::guards::ApiToken;
-use the_beaconator::models::{DeployPerpForBeaconRequest, DepositLiquidityForPerpRequest};
+use the_beaconator::guards::{ApiToken, SignedJson};
+use the_beaconator::models::{
+    BatchDepositLiquidityForPerpsRequest, CloseMakerPositionRequest, DeployPerpForBeaconRequest,
+    DepositLiquidityByPriceRequest, DepositLiquidityForPerpRequest, PreviewDepositRequest,
+};
 use the_beaconator::routes::perp::{
-    deploy_perp_for_beacon_endpoint, deposit_liquidity_for_perp_endpoint,
+    MAX_BATCH_DEPOSITS, batch_deposit_liquidity_for_perps_endpoint, close_maker_position_endpoint,
+    deploy_perp_for_beacon_endpoint, deposit_liquidity_by_price_endpoint,
+    deposit_liquidity_for_perp_endpoint, get_maker_position_endpoint, get_perp_config,
+    get_perp_endpoint, preview_create_perp, preview_deposit,
 };
+use the_beaconator::services::perp::build_create_perp_call;
 
 // Reusable builders for v0.1.0 request shapes. perpcity-contracts@v0.1.0:
 // DeployPerpForBeaconRequest takes owner/name/symbol/tokenUri/emaWindow/salt instead of
@@ -25,6 +32,7 @@ fn deploy_request(beacon_address: &str) -> DeployPerpForBeaconRequest {
         token_uri: "https://example.com/token-uri".to_string(),
         ema_window: 3600,
         salt: None,
+        verbose: false,
     }
 }
 
@@ -44,73 +52,473 @@ fn deposit_request(perp_address: &str, margin: &str) -> DepositLiquidityForPerpR
 #[tokio::test]
 #[serial]
 async fn test_deposit_liquidity_invalid_perp_address() {
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
     let app_state = create_simple_test_app_state().await;
     let state = State::from(&app_state);
 
-    let request = Json(deposit_request("not_a_hex_string", "500000000"));
+    let request = SignedJson(deposit_request("not_a_hex_string", "500000000"));
     let result = deposit_liquidity_for_perp_endpoint(request, token, state).await;
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Status::BadRequest);
+    assert_eq!(result.unwrap_err().0, Status::BadRequest);
 }
 
 #[tokio::test]
 #[serial]
 async fn test_deposit_liquidity_invalid_margin_amount() {
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
     let app_state = create_simple_test_app_state().await;
     let state = State::from(&app_state);
 
-    let request = Json(deposit_request(
+    let request = SignedJson(deposit_request(
         "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0",
         "not_a_number",
     ));
     let result = deposit_liquidity_for_perp_endpoint(request, token, state).await;
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Status::BadRequest);
+    assert_eq!(result.unwrap_err().0, Status::BadRequest);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_deposit_liquidity_invalid_max_amount() {
+    let token = ApiToken::Token("test_token".to_string());
+    let app_state = create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    let mut request = deposit_request("0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0", "500000000");
+    request.max_amt1_in = Some("-5".to_string());
+    let result = deposit_liquidity_for_perp_endpoint(SignedJson(request), token, state).await;
+    assert_eq!(result.unwrap_err().0, Status::BadRequest);
 }
 
 #[tokio::test]
 #[serial]
-#[ignore = "requires Redis - wallet operations needed before reaching on-chain validation"]
 async fn test_deposit_liquidity_zero_margin_amount() {
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
     let app_state = create_simple_test_app_state().await;
     let state = State::from(&app_state);
 
-    let request = Json(deposit_request(
+    let request = SignedJson(deposit_request(
         "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0",
         "0",
     ));
     let result = deposit_liquidity_for_perp_endpoint(request, token, state).await;
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Status::InternalServerError);
+    assert_eq!(result.unwrap_err().0, Status::BadRequest);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_deposit_liquidity_margin_below_minimum() {
+    let token = ApiToken::Token("test_token".to_string());
+    let app_state = create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    // 1 USDC, below the default 10 USDC PERP_MIN_MARGIN_USDC.
+    let request = SignedJson(deposit_request(
+        "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0",
+        "1000000",
+    ));
+    let result = deposit_liquidity_for_perp_endpoint(request, token, state).await;
+    assert_eq!(result.unwrap_err().0, Status::BadRequest);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_batch_deposit_rejects_empty_and_oversized_batches() {
+    let app_state = create_simple_test_app_state().await;
+
+    for count in [0, MAX_BATCH_DEPOSITS + 1] {
+        let request = SignedJson(BatchDepositLiquidityForPerpsRequest {
+            liquidity_deposits: vec![
+                deposit_request(
+                    "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0",
+                    "500000000"
+                );
+                count
+            ],
+        });
+        let result = batch_deposit_liquidity_for_perps_endpoint(
+            request,
+            ApiToken::Token("test_token".to_string()),
+            State::from(&app_state),
+        )
+        .await;
+        assert_eq!(result.unwrap_err(), Status::BadRequest, "count {count}");
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_batch_deposit_failure_does_not_abort_others() {
+    let app_state = create_simple_test_app_state().await;
+    // The last deposit is well-formed but its perp is not from the test factory, so it
+    // fails on-chain checks after both invalid deposits: every deposit runs and reports.
+    let request = SignedJson(BatchDepositLiquidityForPerpsRequest {
+        liquidity_deposits: vec![
+            deposit_request("not_an_address", "500000000"),
+            deposit_request("0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0", "0"),
+            deposit_request("0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0", "500000000"),
+        ],
+    });
+
+    let response = batch_deposit_liquidity_for_perps_endpoint(
+        request,
+        ApiToken::Token("test_token".to_string()),
+        State::from(&app_state),
+    )
+    .await
+    .expect("partial failures are reported in the body")
+    .into_inner();
+
+    assert!(!response.success);
+    assert_eq!(response.message, "Deposited 0 of 3 liquidity positions");
+    let data = response.data.unwrap();
+    assert_eq!(data.deposited_count, 0);
+    assert!(data.maker_position_ids.is_empty());
+    assert_eq!(data.failed_count, 3);
+    assert_eq!(data.errors.len(), 3);
+    assert!(data.errors[0].starts_with("Deposit 1 (perp not_an_address)"));
+    assert!(
+        data.errors[0].contains("perp_address"),
+        "{}",
+        data.errors[0]
+    );
+    assert!(data.errors[1].starts_with("Deposit 2"));
+    assert!(data.errors[1].contains("margin_amount_usdc: must be greater than zero"));
+    assert!(data.errors[2].starts_with("Deposit 3"));
+}
+
+#[tokio::test]
+async fn test_get_perp_config() {
+    let app_state = create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    let response = get_perp_config(state).unwrap().into_inner();
+    assert!(response.success);
+    let config = response.data.unwrap();
+
+    let expected_min = app_state.perp.calculate_minimum_margin_usdc().unwrap();
+    assert_eq!(config.min_margin_usdc, expected_min.to_string());
+    assert_eq!(
+        config.min_margin_usdc_decimal,
+        app_state.perp.minimum_margin_usdc_decimal().unwrap()
+    );
+    assert_eq!(
+        config.max_margin_usdc,
+        app_state.perp.max_margin_usdc.to_string()
+    );
+    assert_eq!(config.tick_spacing, app_state.perp.tick_spacing);
+    assert_eq!(
+        (config.tick_lower, config.tick_upper),
+        (app_state.perp.tick_lower, app_state.perp.tick_upper)
+    );
+    assert!(config.min_tick < config.tick_lower && config.tick_upper < config.max_tick);
+}
+
+fn preview_create(
+    app_state: &the_beaconator::models::AppState,
+    request: &DeployPerpForBeaconRequest,
+) -> Result<the_beaconator::models::CreatePerpPreviewResponse, Status> {
+    preview_create_perp(
+        request.beacon_address.clone(),
+        request.owner.clone(),
+        request.name.clone(),
+        request.symbol.clone(),
+        request.token_uri.clone(),
+        request.ema_window,
+        request.salt.clone(),
+        ApiToken::Token("test_token".to_string()),
+        State::from(app_state),
+    )
+    .map(|response| response.into_inner().data.unwrap())
+}
+
+#[tokio::test]
+async fn test_preview_create_perp_matches_deploy_call() {
+    let app_state = create_simple_test_app_state().await;
+    let salt = format!("0x{}", "11".repeat(32));
+    let request = DeployPerpForBeaconRequest {
+        salt: Some(salt.clone()),
+        ..deploy_request("0x5FbDB2315678afecb367f032d93F642f64180aa3")
+    };
+
+    let preview = preview_create(&app_state, &request).unwrap();
+
+    // The call the deploy path would send for the same inputs.
+    let contracts = app_state.contracts.load();
+    let expected = build_create_perp_call(
+        &contracts,
+        Address::from_str(&request.beacon_address).unwrap(),
+        Address::from_str(&request.owner).unwrap(),
+        request.name.clone(),
+        request.symbol.clone(),
+        request.token_uri.clone(),
+        request.ema_window,
+        FixedBytes::<32>::from_str(&salt).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        preview.calldata,
+        format!("0x{}", hex::encode(expected.abi_encode()))
+    );
+    assert_eq!(preview.salt, salt);
+    assert!(!preview.salt_derived);
+    assert_eq!(preview.ema_window, 3600);
+    assert_eq!(
+        preview.perp_factory_address,
+        contracts.perp_factory.to_string()
+    );
+    assert_eq!(preview.modules.fees, contracts.fees_module.to_string());
+    assert_eq!(
+        preview.modules.pricing,
+        contracts.pricing_module.to_string()
+    );
+}
+
+#[tokio::test]
+async fn test_preview_create_perp_derives_the_same_salt_each_time() {
+    let app_state = create_simple_test_app_state().await;
+    let request = deploy_request("0x5FbDB2315678afecb367f032d93F642f64180aa3");
+
+    let first = preview_create(&app_state, &request).unwrap();
+    let second = preview_create(&app_state, &request).unwrap();
+    assert!(first.salt_derived);
+    assert_eq!(first.salt, second.salt);
+    assert_eq!(first.calldata, second.calldata);
+
+    let renamed = DeployPerpForBeaconRequest {
+        name: "Other Market".to_string(),
+        ..deploy_request("0x5FbDB2315678afecb367f032d93F642f64180aa3")
+    };
+    assert_ne!(
+        preview_create(&app_state, &renamed).unwrap().salt,
+        first.salt
+    );
+}
+
+#[tokio::test]
+async fn test_preview_create_perp_rejects_bad_inputs() {
+    let app_state = create_simple_test_app_state().await;
+    let bad_owner = DeployPerpForBeaconRequest {
+        owner: "not_an_address".to_string(),
+        ..deploy_request("0x5FbDB2315678afecb367f032d93F642f64180aa3")
+    };
+    let zero_window = DeployPerpForBeaconRequest {
+        ema_window: 0,
+        ..deploy_request("0x5FbDB2315678afecb367f032d93F642f64180aa3")
+    };
+    assert_eq!(
+        preview_create(&app_state, &bad_owner).unwrap_err(),
+        Status::BadRequest
+    );
+    assert_eq!(
+        preview_create(&app_state, &zero_window).unwrap_err(),
+        Status::BadRequest
+    );
+}
+
+fn preview_request(margin: &str) -> SignedJson<PreviewDepositRequest> {
+    SignedJson(PreviewDepositRequest {
+        margin_amount_usdc: margin.to_string(),
+        tick_spacing: None,
+        tick_lower: None,
+        tick_upper: None,
+    })
+}
+
+#[tokio::test]
+async fn test_preview_deposit() {
+    let app_state = create_simple_test_app_state().await;
+
+    let token = ApiToken::Token("test_token".to_string());
+    let accepted = preview_deposit(preview_request("50000000"), token, State::from(&app_state))
+        .unwrap()
+        .into_inner()
+        .data
+        .unwrap();
+    assert!(accepted.valid);
+    assert!(accepted.reason.is_none());
+    let expected = app_state
+        .perp
+        .check_deposit(
+            50_000_000,
+            accepted.tick_spacing,
+            accepted.tick_lower,
+            accepted.tick_upper,
+        )
+        .unwrap();
+    assert_eq!(accepted.liquidity, Some(expected.to_string()));
+    assert_eq!(accepted.min_margin_usdc_decimal, "10.000000");
+
+    let token = ApiToken::Token("test_token".to_string());
+    let zero = preview_deposit(preview_request("0"), token, State::from(&app_state))
+        .unwrap()
+        .into_inner();
+    let data = zero.data.unwrap();
+    assert!(!data.valid);
+    assert!(data.liquidity.is_none());
+    assert_eq!(
+        data.reason.as_deref(),
+        Some("margin must be greater than zero")
+    );
+    assert!(zero.message.contains("would be rejected"));
+
+    let token = ApiToken::Token("test_token".to_string());
+    let result = preview_deposit(preview_request("ten"), token, State::from(&app_state));
+    assert_eq!(result.unwrap_err(), Status::BadRequest);
+}
+
+fn price_request(price_lower: f64, price_upper: f64) -> DepositLiquidityByPriceRequest {
+    DepositLiquidityByPriceRequest {
+        perp_address: "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0".to_string(),
+        margin_amount_usdc: "500000000".to_string(),
+        price_lower,
+        price_upper,
+        tick_spacing: None,
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_deposit_by_price_inverted_range() {
+    let token = ApiToken::Token("test_token".to_string());
+    let app_state = create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    let request = SignedJson(price_request(20.0, 10.0));
+    let result = deposit_liquidity_by_price_endpoint(request, token, state).await;
+    assert_eq!(result.unwrap_err(), Status::BadRequest);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_deposit_by_price_range_narrower_than_spacing() {
+    let token = ApiToken::Token("test_token".to_string());
+    let app_state = create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    // 10.0 and 10.001 are ~1 tick apart: both snap to the same multiple of 30.
+    let request = SignedJson(price_request(10.0, 10.001));
+    let result = deposit_liquidity_by_price_endpoint(request, token, state).await;
+    assert_eq!(result.unwrap_err(), Status::BadRequest);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_get_perp_invalid_address() {
+    let token = ApiToken::Token("test_token".to_string());
+    let app_state = create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    let result = get_perp_endpoint("not_an_address", token, state).await;
+    assert_eq!(result.unwrap_err(), Status::BadRequest);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_get_maker_position_invalid_perp_address() {
+    let token = ApiToken::Token("test_token".to_string());
+    let app_state = create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    let result = get_maker_position_endpoint("not_an_address", "1", token, state).await;
+    assert_eq!(result.unwrap_err(), Status::BadRequest);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_get_maker_position_invalid_position_id() {
+    let app_state = create_simple_test_app_state().await;
+
+    for bad_id in ["", "abc", "-1", "0x10"] {
+        let result = get_maker_position_endpoint(
+            "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0",
+            bad_id,
+            ApiToken::Token("test_token".to_string()),
+            State::from(&app_state),
+        )
+        .await;
+        assert_eq!(result.unwrap_err(), Status::BadRequest, "id {bad_id:?}");
+    }
+}
+
+fn close_request(perp_address: &str, maker_position_id: &str) -> CloseMakerPositionRequest {
+    CloseMakerPositionRequest {
+        perp_address: perp_address.to_string(),
+        maker_position_id: maker_position_id.to_string(),
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_close_maker_position_invalid_perp_address() {
+    let token = ApiToken::Token("test_token".to_string());
+    let app_state = create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    let request = SignedJson(close_request("not_an_address", "1"));
+    let error = close_maker_position_endpoint(request, token, state)
+        .await
+        .unwrap_err();
+    assert_eq!(error.0, Status::BadRequest);
+    assert!(error.1.message.contains("perp_address"));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_close_maker_position_invalid_position_id() {
+    let app_state = create_simple_test_app_state().await;
+
+    for bad_id in ["", "abc", "-1", "1.5", "0x10"] {
+        let request = SignedJson(close_request(
+            "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0",
+            bad_id,
+        ));
+        let error = close_maker_position_endpoint(
+            request,
+            ApiToken::Token("test_token".to_string()),
+            State::from(&app_state),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.0, Status::BadRequest, "id {bad_id:?}");
+        assert!(
+            error.1.message.contains("maker_position_id"),
+            "{}",
+            error.1.message
+        );
+    }
 }
 
 #[tokio::test]
 #[serial]
 async fn test_deploy_perp_invalid_beacon_address() {
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
     let app_state = create_simple_test_app_state().await;
     let state = State::from(&app_state);
 
-    let request = Json(deploy_request("not_a_valid_address"));
+    let request = SignedJson(deploy_request("not_a_valid_address"));
     let result = deploy_perp_for_beacon_endpoint(request, token, state).await;
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Status::BadRequest);
+    let error = result.unwrap_err();
+    assert_eq!(error.0, Status::BadRequest);
+    assert!(!error.1.success);
+    assert!(error.1.data.is_none());
 }
 
 #[tokio::test]
 #[serial]
 async fn test_deploy_perp_short_beacon_address() {
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
     let app_state = create_simple_test_app_state().await;
     let state = State::from(&app_state);
 
-    let request = Json(deploy_request("0x123456"));
+    let request = SignedJson(deploy_request("0x123456"));
     let result = deploy_perp_for_beacon_endpoint(request, token, state).await;
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), Status::BadRequest);
+    let error = result.unwrap_err();
+    assert_eq!(error.0, Status::BadRequest);
+    assert!(!error.1.success);
+    assert!(error.1.data.is_none());
 }
 
 #[test]
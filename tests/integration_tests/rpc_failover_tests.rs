@@ -0,0 +1,154 @@
+//! Integration tests for multi-endpoint RPC failover (src/services/rpc.rs RpcEndpoints).
+//!
+//! Dead endpoints point at closed local ports, so their calls fail with a real
+//! transport error; the live endpoint is a fresh Anvil instance.
+
+use alloy::providers::{Provider, ProviderBuilder};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use the_beaconator::ReadOnlyProvider;
+use the_beaconator::services::rpc::{ReadRetryPolicy, RpcEndpoints};
+use the_beaconator::verify_rpc_chain_id;
+
+use crate::test_utils::AnvilManager;
+
+const DEAD_URLS: [&str; 2] = ["http://127.0.0.1:1", "http://127.0.0.1:2"];
+
+fn read_provider(url: &str) -> Arc<ReadOnlyProvider> {
+    Arc::new(ProviderBuilder::new().connect_http(url.parse().unwrap()))
+}
+
+fn endpoints(urls: &[&str]) -> RpcEndpoints {
+    RpcEndpoints::new(
+        urls.iter().map(|u| u.to_string()).collect(),
+        urls.iter().map(|u| read_provider(u)).collect(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_write_lookup_fails_over_past_two_dead_endpoints() {
+    let anvil = AnvilManager::new().await;
+    let endpoints = endpoints(&[DEAD_URLS[0], DEAD_URLS[1], anvil.rpc_url()]);
+    let calls = &AtomicU32::new(0);
+
+    let chain_id = endpoints
+        .sticky_with_fallback("eth_chainId", move |provider| async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            provider.get_chain_id().await
+        })
+        .await
+        .expect("third endpoint should answer");
+
+    assert_eq!(chain_id, 31337);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_reads_fail_over_from_any_round_robin_start() {
+    let anvil = AnvilManager::new().await;
+    let endpoints = endpoints(&[DEAD_URLS[0], DEAD_URLS[1], anvil.rpc_url()]);
+
+    // Three consecutive reads start at endpoint 0, 1 and 2; each must reach Anvil.
+    for _ in 0..3 {
+        let block = endpoints
+            .read_with_failover("eth_blockNumber", |provider| async move {
+                provider.get_block_number().await
+            })
+            .await;
+        assert!(block.is_ok(), "read should fail over to Anvil: {block:?}");
+    }
+}
+
+#[tokio::test]
+async fn test_all_endpoints_dead_returns_last_error() {
+    let endpoints = endpoints(&[DEAD_URLS[0], DEAD_URLS[1], "http://127.0.0.1:3"]);
+    let calls = &AtomicU32::new(0);
+
+    let result = endpoints
+        .sticky_with_fallback("eth_chainId", move |provider| async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            provider.get_chain_id().await
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_read_order_rotates_and_write_order_is_sticky() {
+    let endpoints = endpoints(&DEAD_URLS);
+
+    assert_eq!(endpoints.read_order(), vec![0, 1]);
+    assert_eq!(endpoints.read_order(), vec![1, 0]);
+    assert_eq!(endpoints.read_order(), vec![0, 1]);
+
+    assert_eq!(endpoints.write_order(), vec![0, 1]);
+    assert_eq!(endpoints.write_order(), vec![0, 1]);
+}
+
+#[test]
+fn test_mismatched_urls_and_providers_rejected() {
+    let result = RpcEndpoints::new(vec!["http://127.0.0.1:1".to_string()], Vec::new());
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_startup_chain_check_rejects_mismatched_rpc() {
+    let anvil = AnvilManager::new().await;
+    let endpoints = endpoints(&[anvil.rpc_url()]);
+
+    // Anvil reports 31337; a testnet ENV expects Arbitrum Sepolia.
+    let err = verify_rpc_chain_id(
+        &endpoints,
+        &ReadRetryPolicy::default(),
+        "testnet",
+        421614,
+        &[],
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("RPC reports chain id 31337"),
+        "{err}"
+    );
+
+    let chain_id = verify_rpc_chain_id(
+        &endpoints,
+        &ReadRetryPolicy::default(),
+        "localnet",
+        421614,
+        &[31337],
+    )
+    .await
+    .expect("allowlisted local chain");
+    assert_eq!(chain_id, 31337);
+}
+
+#[tokio::test]
+async fn test_startup_chain_check_tolerates_dead_fallback_only() {
+    let anvil = AnvilManager::new().await;
+
+    let chain_id = verify_rpc_chain_id(
+        &endpoints(&[anvil.rpc_url(), DEAD_URLS[0]]),
+        &ReadRetryPolicy::default(),
+        "localnet",
+        421614,
+        &[31337],
+    )
+    .await
+    .expect("dead fallback is logged, not fatal");
+    assert_eq!(chain_id, 31337);
+
+    let err = verify_rpc_chain_id(
+        &endpoints(&[DEAD_URLS[0], anvil.rpc_url()]),
+        &ReadRetryPolicy::default(),
+        "localnet",
+        421614,
+        &[31337],
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Failed to read chain id"), "{err}");
+}
@@ -36,7 +36,7 @@ async fn test_unregister_beacon_with_anvil() {
         return;
     };
 
-    let registry_address = app_state.contracts.perpcity_registry;
+    let registry_address = app_state.contracts.load().perpcity_registry;
 
     let register_result =
         register_beacon_with_registry(&app_state, beacon_address, registry_address).await;
@@ -88,7 +88,7 @@ async fn test_unregister_unregistered_beacon_is_noop() {
     }
 
     let never_registered = Address::from_str("0x1234567890123456789012345678901234567890").unwrap();
-    let registry_address = app_state.contracts.perpcity_registry;
+    let registry_address = app_state.contracts.load().perpcity_registry;
 
     // Precondition: it is not registered.
     assert!(
@@ -116,7 +116,7 @@ async fn test_unregister_beacon_idempotency() {
         return;
     };
 
-    let registry_address = app_state.contracts.perpcity_registry;
+    let registry_address = app_state.contracts.load().perpcity_registry;
 
     assert!(
         register_beacon_with_registry(&app_state, beacon_address, registry_address)
@@ -7,10 +7,10 @@ mod tests {
     // use crate::test_utils::{TestUtils, create_test_app_state};
     use crate::test_utils::{TestUtils, create_isolated_test_app_state};
     use alloy::primitives::Address;
-    use rocket::serde::json::Json;
     use rocket::{State, http::Status};
     use serial_test::serial;
     use std::str::FromStr;
+    use the_beaconator::guards::SignedJson;
 
     #[tokio::test]
     #[serial]
@@ -19,16 +19,17 @@ mod tests {
         let (app_state, _anvil) = create_isolated_test_app_state().await;
         let state = State::from(&app_state);
 
-        let request = Json(FundGuestWalletRequest {
+        let request = SignedJson(FundGuestWalletRequest {
             wallet_address: "invalid_address".to_string(),
             usdc_amount: "100000000".to_string(), // 100 USDC
             eth_amount: "1000000000000000".to_string(), // 0.001 ETH
+            request_id: None,
         });
 
         let result = fund_guest_wallet(
             state,
             request,
-            the_beaconator::guards::ApiToken("test_token".to_string()),
+            the_beaconator::guards::ApiToken::Token("test_token".to_string()),
         )
         .await;
 
@@ -50,17 +51,18 @@ mod tests {
         let guest_address =
             Address::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f8b94b").unwrap();
 
-        let request = Json(FundGuestWalletRequest {
+        let request = SignedJson(FundGuestWalletRequest {
             wallet_address: guest_address.to_string(),
             usdc_amount: "100000000".to_string(), // 100 USDC
             eth_amount: "1000000000000000".to_string(), // 0.001 ETH
+            request_id: None,
         });
 
         // In a real test environment without actual funds, this should fail
         let result = fund_guest_wallet(
             state,
             request,
-            the_beaconator::guards::ApiToken("test_token".to_string()),
+            the_beaconator::guards::ApiToken::Token("test_token".to_string()),
         )
         .await;
 
@@ -88,16 +90,17 @@ mod tests {
             Address::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f8b94b").unwrap();
 
         // Test USDC limit exceeded
-        let request = Json(FundGuestWalletRequest {
+        let request = SignedJson(FundGuestWalletRequest {
             wallet_address: guest_address.to_string(),
             usdc_amount: "2000000000".to_string(), // 2000 USDC (exceeds default 1000 limit)
             eth_amount: "1000000000000000".to_string(), // 0.001 ETH
+            request_id: None,
         });
 
         let result = fund_guest_wallet(
             state,
             request,
-            the_beaconator::guards::ApiToken("test_token".to_string()),
+            the_beaconator::guards::ApiToken::Token("test_token".to_string()),
         )
         .await;
 
@@ -108,16 +111,17 @@ mod tests {
         assert!(response.message.contains("USDC amount exceeds limit"));
 
         // Test ETH limit exceeded
-        let request = Json(FundGuestWalletRequest {
+        let request = SignedJson(FundGuestWalletRequest {
             wallet_address: guest_address.to_string(),
             usdc_amount: "100000000".to_string(), // 100 USDC
             eth_amount: "20000000000000000".to_string(), // 0.02 ETH (exceeds default 0.01 limit)
+            request_id: None,
         });
 
         let result = fund_guest_wallet(
             state,
             request,
-            the_beaconator::guards::ApiToken("test_token".to_string()),
+            the_beaconator::guards::ApiToken::Token("test_token".to_string()),
         )
         .await;
 
@@ -139,16 +143,17 @@ mod tests {
             Address::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f8b94b").unwrap();
 
         // Test invalid USDC amount
-        let request = Json(FundGuestWalletRequest {
+        let request = SignedJson(FundGuestWalletRequest {
             wallet_address: guest_address.to_string(),
             usdc_amount: "not_a_number".to_string(),
             eth_amount: "1000000000000000".to_string(),
+            request_id: None,
         });
 
         let result = fund_guest_wallet(
             state,
             request,
-            the_beaconator::guards::ApiToken("test_token".to_string()),
+            the_beaconator::guards::ApiToken::Token("test_token".to_string()),
         )
         .await;
 
@@ -170,7 +175,7 @@ mod tests {
 
         // Verify test setup
         assert_ne!(app_state.wallets.signer_address, Address::ZERO);
-        assert_ne!(app_state.contracts.usdc, Address::ZERO);
+        assert_ne!(app_state.contracts.load().usdc, Address::ZERO);
 
         // Check that we can get the balance (even if it's zero)
         let balance_result = TestUtils::get_balance(
@@ -187,11 +192,13 @@ mod tests {
     async fn test_ierc20_interface() {
         // Test that IERC20 interface is properly defined
         let (app_state, _anvil) = create_isolated_test_app_state().await;
-        let usdc_contract =
-            IERC20::new(app_state.contracts.usdc, &*app_state.provider.read_provider);
+        let usdc_contract = IERC20::new(
+            app_state.contracts.load().usdc,
+            &*app_state.provider.read_provider,
+        );
 
         // Verify the contract instance was created
-        assert_eq!(*usdc_contract.address(), app_state.contracts.usdc);
+        assert_eq!(*usdc_contract.address(), app_state.contracts.load().usdc);
     }
 
     #[tokio::test]
@@ -205,16 +212,17 @@ mod tests {
             Address::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f8b94b").unwrap();
 
         // Test with zero amounts
-        let request = Json(FundGuestWalletRequest {
+        let request = SignedJson(FundGuestWalletRequest {
             wallet_address: guest_address.to_string(),
             usdc_amount: "0".to_string(),
             eth_amount: "0".to_string(),
+            request_id: None,
         });
 
         let result = fund_guest_wallet(
             state,
             request,
-            the_beaconator::guards::ApiToken("test_token".to_string()),
+            the_beaconator::guards::ApiToken::Token("test_token".to_string()),
         )
         .await;
 
@@ -236,16 +244,17 @@ mod tests {
             Address::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f8b94b").unwrap();
 
         // Test with negative amounts (should fail parsing)
-        let request = Json(FundGuestWalletRequest {
+        let request = SignedJson(FundGuestWalletRequest {
             wallet_address: guest_address.to_string(),
             usdc_amount: "-1000000".to_string(),
             eth_amount: "1000000000000000".to_string(),
+            request_id: None,
         });
 
         let result = fund_guest_wallet(
             state,
             request,
-            the_beaconator::guards::ApiToken("test_token".to_string()),
+            the_beaconator::guards::ApiToken::Token("test_token".to_string()),
         )
         .await;
 
@@ -267,16 +276,17 @@ mod tests {
             Address::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f8b94b").unwrap();
 
         // Test ETH limit exceeded (default limit is 0.01 ETH)
-        let request = Json(FundGuestWalletRequest {
+        let request = SignedJson(FundGuestWalletRequest {
             wallet_address: guest_address.to_string(),
             usdc_amount: "1000000".to_string(),          // 1 USDC
             eth_amount: "20000000000000000".to_string(), // 0.02 ETH (exceeds default 0.01 limit)
+            request_id: None,
         });
 
         let result = fund_guest_wallet(
             state,
             request,
-            the_beaconator::guards::ApiToken("test_token".to_string()),
+            the_beaconator::guards::ApiToken::Token("test_token".to_string()),
         )
         .await;
 
@@ -298,16 +308,17 @@ mod tests {
             Address::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f8b94b").unwrap();
 
         // Test with invalid USDC amount format
-        let request = Json(FundGuestWalletRequest {
+        let request = SignedJson(FundGuestWalletRequest {
             wallet_address: guest_address.to_string(),
             usdc_amount: "not_a_number".to_string(),
             eth_amount: "1000000000000000".to_string(),
+            request_id: None,
         });
 
         let result = fund_guest_wallet(
             state,
             request,
-            the_beaconator::guards::ApiToken("test_token".to_string()),
+            the_beaconator::guards::ApiToken::Token("test_token".to_string()),
         )
         .await;
 
@@ -318,16 +329,17 @@ mod tests {
         assert!(response.message.contains("Invalid USDC amount"));
 
         // Test with invalid ETH amount format
-        let request2 = Json(FundGuestWalletRequest {
+        let request2 = SignedJson(FundGuestWalletRequest {
             wallet_address: guest_address.to_string(),
             usdc_amount: "1000000".to_string(),
             eth_amount: "not_a_number".to_string(),
+            request_id: None,
         });
 
         let result2 = fund_guest_wallet(
             state,
             request2,
-            the_beaconator::guards::ApiToken("test_token".to_string()),
+            the_beaconator::guards::ApiToken::Token("test_token".to_string()),
         )
         .await;
 
@@ -129,17 +129,21 @@ use alloy::{
     providers::{Provider, ProviderBuilder},
     signers::{Signer, local::PrivateKeySigner},
 };
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 use the_beaconator::ReadOnlyProvider;
+#[cfg(feature = "wallet-pool")]
 use the_beaconator::models::wallet::{WalletInfo, WalletStatus};
 use the_beaconator::models::{
-    AppState, AuthConfig, ContractAddresses, ProviderConfig, Registries, WalletConfig,
+    AppState, AuthConfig, ContractAddresses, LiveContracts, PerpConfig, ProviderConfig, Registries,
+    WalletConfig,
 };
 use the_beaconator::services::beacon::BeaconTypeRegistry;
 use the_beaconator::services::beacon::ComponentFactoryRegistry;
 use the_beaconator::services::beacon::RecipeRegistry;
-use the_beaconator::services::wallet::WalletManager;
+use the_beaconator::services::rpc::RpcEndpoints;
+use the_beaconator::services::wallet::{FundingRateLimiter, WalletManager};
 use tokio::sync::OnceCell;
 
 /// Create a WalletManager - uses real Redis if REDIS_URL is set, otherwise test_stub
@@ -150,7 +154,10 @@ use tokio::sync::OnceCell;
 ///
 /// Each invocation generates a unique Redis key prefix using UUID, enabling
 /// parallel test execution without conflicts over shared Redis state.
+///
+/// Without the `wallet-pool` feature there is no pool to populate: always the stub.
 pub async fn create_test_wallet_manager() -> Arc<WalletManager> {
+    #[cfg(feature = "wallet-pool")]
     if let Ok(redis_url) = std::env::var("REDIS_URL") {
         // Generate unique prefix for test isolation
         let test_prefix = format!("test-{}:", uuid::Uuid::new_v4());
@@ -188,19 +195,18 @@ pub async fn create_test_wallet_manager() -> Arc<WalletManager> {
                     manager.signer_addresses().len(),
                     test_prefix
                 );
-                Arc::new(manager)
+                return Arc::new(manager);
             }
             Err(e) => {
                 tracing::warn!(
                     "Failed to create WalletManager with mock signers: {e}, falling back to test stub"
                 );
-                Arc::new(WalletManager::test_stub())
+                return Arc::new(WalletManager::test_stub());
             }
         }
-    } else {
-        tracing::debug!("REDIS_URL not set, using WalletManager test stub");
-        Arc::new(WalletManager::test_stub())
     }
+    tracing::debug!("REDIS_URL not set, using WalletManager test stub");
+    Arc::new(WalletManager::test_stub())
 }
 
 /// Build a read-only provider (without wallet) for test purposes
@@ -210,6 +216,19 @@ fn build_test_read_only_provider(rpc_url: &str) -> Arc<ReadOnlyProvider> {
     )
 }
 
+/// Build a signing provider (the app's `AlloyProvider` type) for test purposes
+pub fn build_test_signing_provider(
+    wallet: EthereumWallet,
+    rpc_url: &str,
+) -> the_beaconator::AlloyProvider {
+    the_beaconator::services::wallet::nonce::signing_provider(
+        wallet,
+        rpc_url,
+        the_beaconator::services::wallet::PoolNonceManager::default(),
+    )
+    .expect("Invalid RPC URL for test")
+}
+
 /// Anvil configuration and utilities
 pub struct AnvilConfig {
     pub _instance: AnvilInstance,
@@ -414,11 +433,7 @@ impl TestDeployment {
         // Create provider with deployer account
         let signer = anvil.deployer_signer();
         let wallet = EthereumWallet::from(signer);
-        let provider = Arc::new(
-            ProviderBuilder::new()
-                .wallet(wallet)
-                .connect_http(anvil.rpc_url().parse()?),
-        );
+        let provider = Arc::new(build_test_signing_provider(wallet, anvil.rpc_url()));
 
         // Check if contract artifacts exist (compiled with Foundry)
         let factory_bytecode_path =
@@ -478,11 +493,7 @@ impl TestDeployment {
         // Create provider with deployer account
         let signer = anvil.deployer_signer();
         let wallet = EthereumWallet::from(signer);
-        let provider = Arc::new(
-            ProviderBuilder::new()
-                .wallet(wallet)
-                .connect_http(anvil.rpc_url.parse()?),
-        );
+        let provider = Arc::new(build_test_signing_provider(wallet, &anvil.rpc_url));
 
         // Check if contract artifacts exist (compiled with Foundry)
         let factory_bytecode_path =
@@ -558,9 +569,16 @@ pub async fn create_test_app_state() -> AppState {
 
     AppState {
         provider: ProviderConfig {
-            read_provider,
+            read_provider: read_provider.clone(),
             rpc_url: anvil.rpc_url.clone(),
             chain_id: 31337,
+            env_type: "localnet".to_string(),
+            endpoints: Arc::new(RpcEndpoints::single(anvil.rpc_url.clone(), read_provider)),
+            receipts: Arc::new(the_beaconator::services::transaction::ReceiptCache::default()),
+            gas_prices: Arc::new(the_beaconator::services::gas_price::GasPriceCache::default()),
+            read_retry: the_beaconator::services::rpc::ReadRetryPolicy::default(),
+            receipt_wait: the_beaconator::services::transaction::ReceiptWaitConfig::default(),
+            confirmations: the_beaconator::services::transaction::ConfirmationPolicy::default(),
         },
         wallets: WalletConfig {
             manager: Arc::new(WalletManager::test_stub()),
@@ -570,8 +588,13 @@ pub async fn create_test_app_state() -> AppState {
             eth_transfer_limit: 10_000_000_000_000_000, // 0.01 ETH
             usdc_bonus_limit: 50_000_000,       // 50 USDC
             faucet_reserve_eth_wei: 20_000_000_000_000_000, // 0.02 ETH
+            funding_limiter: Arc::new(FundingRateLimiter::from_env()),
+            funding_replays: Arc::new(
+                the_beaconator::services::idempotency::IdempotencyStore::from_env(),
+            ),
+            cold_wallet_address: None,
         },
-        contracts: ContractAddresses {
+        contracts: LiveContracts::new(ContractAddresses {
             perpcity_registry: deployment.beacon_registry,
             perp_factory: deployment.perp_factory,
             usdc: Address::from_str("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap(), // Mock USDC address
@@ -593,10 +616,12 @@ pub async fn create_test_app_state() -> AppState {
                 .unwrap(),
             protocol_fee_manager: None,
             module_registry: None,
-        },
+        }),
         auth: AuthConfig {
             access_token: "test_token".to_string(),
             admin_token: "test_admin_token".to_string(),
+            scoped_tokens: HashMap::new(),
+            request_signing: None,
         },
         registries: Registries {
             beacon_types: Arc::new(BeaconTypeRegistry::test_stub()),
@@ -604,6 +629,17 @@ pub async fn create_test_app_state() -> AppState {
             recipes: Arc::new(RecipeRegistry::test_stub()),
         },
         touch: the_beaconator::services::touch::TouchDispatcher::disabled(),
+        beacon_events: Arc::new(the_beaconator::services::beacon::BeaconEventFeed::disabled()),
+        idempotency: Arc::new(the_beaconator::services::idempotency::IdempotencyStore::from_env()),
+        gas_metrics: Arc::new(the_beaconator::services::metrics::GasMetrics::from_env()),
+        history: Arc::new(the_beaconator::services::history::TransactionHistory::from_env()),
+        perp: PerpConfig::default(),
+        error_registry: Arc::new(
+            the_beaconator::services::perp::ErrorSelectorRegistry::from_bundled_abis()
+                .expect("bundled ABIs parse"),
+        ),
+        allowances: Arc::new(the_beaconator::services::perp::AllowanceCache::new()),
+        strict_address_checksum: false,
     }
 }
 
@@ -629,9 +665,19 @@ pub async fn create_isolated_test_app_state() -> (AppState, AnvilManager) {
 
     let app_state = AppState {
         provider: ProviderConfig {
-            read_provider,
+            read_provider: read_provider.clone(),
             rpc_url: anvil.rpc_url().to_string(),
             chain_id: 31337,
+            env_type: "localnet".to_string(),
+            endpoints: Arc::new(RpcEndpoints::single(
+                anvil.rpc_url().to_string(),
+                read_provider,
+            )),
+            receipts: Arc::new(the_beaconator::services::transaction::ReceiptCache::default()),
+            gas_prices: Arc::new(the_beaconator::services::gas_price::GasPriceCache::default()),
+            read_retry: the_beaconator::services::rpc::ReadRetryPolicy::default(),
+            receipt_wait: the_beaconator::services::transaction::ReceiptWaitConfig::default(),
+            confirmations: the_beaconator::services::transaction::ConfirmationPolicy::default(),
         },
         wallets: WalletConfig {
             manager: create_test_wallet_manager().await,
@@ -641,8 +687,13 @@ pub async fn create_isolated_test_app_state() -> (AppState, AnvilManager) {
             eth_transfer_limit: 10_000_000_000_000_000, // 0.01 ETH
             usdc_bonus_limit: 50_000_000,       // 50 USDC
             faucet_reserve_eth_wei: 20_000_000_000_000_000, // 0.02 ETH
+            funding_limiter: Arc::new(FundingRateLimiter::from_env()),
+            funding_replays: Arc::new(
+                the_beaconator::services::idempotency::IdempotencyStore::from_env(),
+            ),
+            cold_wallet_address: None,
         },
-        contracts: ContractAddresses {
+        contracts: LiveContracts::new(ContractAddresses {
             perpcity_registry: deployment.beacon_registry,
             perp_factory: deployment.perp_factory,
             usdc: deployment.usdc,
@@ -664,10 +715,12 @@ pub async fn create_isolated_test_app_state() -> (AppState, AnvilManager) {
                 .unwrap(),
             protocol_fee_manager: None,
             module_registry: None,
-        },
+        }),
         auth: AuthConfig {
             access_token: "test_token".to_string(),
             admin_token: "test_admin_token".to_string(),
+            scoped_tokens: HashMap::new(),
+            request_signing: None,
         },
         registries: Registries {
             beacon_types: Arc::new(BeaconTypeRegistry::test_stub()),
@@ -675,6 +728,17 @@ pub async fn create_isolated_test_app_state() -> (AppState, AnvilManager) {
             recipes: Arc::new(RecipeRegistry::test_stub()),
         },
         touch: the_beaconator::services::touch::TouchDispatcher::disabled(),
+        beacon_events: Arc::new(the_beaconator::services::beacon::BeaconEventFeed::disabled()),
+        idempotency: Arc::new(the_beaconator::services::idempotency::IdempotencyStore::from_env()),
+        gas_metrics: Arc::new(the_beaconator::services::metrics::GasMetrics::from_env()),
+        history: Arc::new(the_beaconator::services::history::TransactionHistory::from_env()),
+        perp: PerpConfig::default(),
+        error_registry: Arc::new(
+            the_beaconator::services::perp::ErrorSelectorRegistry::from_bundled_abis()
+                .expect("bundled ABIs parse"),
+        ),
+        allowances: Arc::new(the_beaconator::services::perp::AllowanceCache::new()),
+        strict_address_checksum: false,
     };
 
     (app_state, anvil)
@@ -725,9 +789,19 @@ pub async fn create_isolated_test_app_state_with_redis() -> (AppState, AnvilMana
 
     let app_state = AppState {
         provider: ProviderConfig {
-            read_provider,
+            read_provider: read_provider.clone(),
             rpc_url: anvil.rpc_url().to_string(),
             chain_id: 31337,
+            env_type: "localnet".to_string(),
+            endpoints: Arc::new(RpcEndpoints::single(
+                anvil.rpc_url().to_string(),
+                read_provider,
+            )),
+            receipts: Arc::new(the_beaconator::services::transaction::ReceiptCache::default()),
+            gas_prices: Arc::new(the_beaconator::services::gas_price::GasPriceCache::default()),
+            read_retry: the_beaconator::services::rpc::ReadRetryPolicy::default(),
+            receipt_wait: the_beaconator::services::transaction::ReceiptWaitConfig::default(),
+            confirmations: the_beaconator::services::transaction::ConfirmationPolicy::default(),
         },
         wallets: WalletConfig {
             manager: wallet_manager,
@@ -737,8 +811,13 @@ pub async fn create_isolated_test_app_state_with_redis() -> (AppState, AnvilMana
             eth_transfer_limit: 10_000_000_000_000_000,
             usdc_bonus_limit: 50_000_000,
             faucet_reserve_eth_wei: 20_000_000_000_000_000, // 0.02 ETH
+            funding_limiter: Arc::new(FundingRateLimiter::from_env()),
+            funding_replays: Arc::new(
+                the_beaconator::services::idempotency::IdempotencyStore::from_env(),
+            ),
+            cold_wallet_address: None,
         },
-        contracts: ContractAddresses {
+        contracts: LiveContracts::new(ContractAddresses {
             perpcity_registry: deployment.beacon_registry,
             perp_factory: deployment.perp_factory,
             usdc: deployment.usdc,
@@ -760,10 +839,12 @@ pub async fn create_isolated_test_app_state_with_redis() -> (AppState, AnvilMana
                 .unwrap(),
             protocol_fee_manager: None,
             module_registry: None,
-        },
+        }),
         auth: AuthConfig {
             access_token: "test_token".to_string(),
             admin_token: "test_admin_token".to_string(),
+            scoped_tokens: HashMap::new(),
+            request_signing: None,
         },
         registries: Registries {
             beacon_types: Arc::new(BeaconTypeRegistry::test_stub()),
@@ -771,6 +852,17 @@ pub async fn create_isolated_test_app_state_with_redis() -> (AppState, AnvilMana
             recipes: Arc::new(RecipeRegistry::test_stub()),
         },
         touch: the_beaconator::services::touch::TouchDispatcher::disabled(),
+        beacon_events: Arc::new(the_beaconator::services::beacon::BeaconEventFeed::disabled()),
+        idempotency: Arc::new(the_beaconator::services::idempotency::IdempotencyStore::from_env()),
+        gas_metrics: Arc::new(the_beaconator::services::metrics::GasMetrics::from_env()),
+        history: Arc::new(the_beaconator::services::history::TransactionHistory::from_env()),
+        perp: PerpConfig::default(),
+        error_registry: Arc::new(
+            the_beaconator::services::perp::ErrorSelectorRegistry::from_bundled_abis()
+                .expect("bundled ABIs parse"),
+        ),
+        allowances: Arc::new(the_beaconator::services::perp::AllowanceCache::new()),
+        strict_address_checksum: false,
     };
 
     (app_state, anvil)
@@ -793,9 +885,16 @@ pub async fn create_test_app_state_with_account(account_index: usize) -> AppStat
 
     AppState {
         provider: ProviderConfig {
-            read_provider,
+            read_provider: read_provider.clone(),
             rpc_url: anvil.rpc_url.clone(),
             chain_id: 31337,
+            env_type: "localnet".to_string(),
+            endpoints: Arc::new(RpcEndpoints::single(anvil.rpc_url.clone(), read_provider)),
+            receipts: Arc::new(the_beaconator::services::transaction::ReceiptCache::default()),
+            gas_prices: Arc::new(the_beaconator::services::gas_price::GasPriceCache::default()),
+            read_retry: the_beaconator::services::rpc::ReadRetryPolicy::default(),
+            receipt_wait: the_beaconator::services::transaction::ReceiptWaitConfig::default(),
+            confirmations: the_beaconator::services::transaction::ConfirmationPolicy::default(),
         },
         wallets: WalletConfig {
             manager: Arc::new(WalletManager::test_stub()),
@@ -805,8 +904,13 @@ pub async fn create_test_app_state_with_account(account_index: usize) -> AppStat
             eth_transfer_limit: 10_000_000_000_000_000, // 0.01 ETH
             usdc_bonus_limit: 50_000_000,       // 50 USDC
             faucet_reserve_eth_wei: 20_000_000_000_000_000, // 0.02 ETH
+            funding_limiter: Arc::new(FundingRateLimiter::from_env()),
+            funding_replays: Arc::new(
+                the_beaconator::services::idempotency::IdempotencyStore::from_env(),
+            ),
+            cold_wallet_address: None,
         },
-        contracts: ContractAddresses {
+        contracts: LiveContracts::new(ContractAddresses {
             perpcity_registry: deployment.beacon_registry,
             perp_factory: deployment.perp_factory,
             usdc: Address::from_str("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").unwrap(), // Mock USDC address
@@ -828,10 +932,12 @@ pub async fn create_test_app_state_with_account(account_index: usize) -> AppStat
                 .unwrap(),
             protocol_fee_manager: None,
             module_registry: None,
-        },
+        }),
         auth: AuthConfig {
             access_token: "test_token".to_string(),
             admin_token: "test_admin_token".to_string(),
+            scoped_tokens: HashMap::new(),
+            request_signing: None,
         },
         registries: Registries {
             beacon_types: Arc::new(BeaconTypeRegistry::test_stub()),
@@ -839,6 +945,17 @@ pub async fn create_test_app_state_with_account(account_index: usize) -> AppStat
             recipes: Arc::new(RecipeRegistry::test_stub()),
         },
         touch: the_beaconator::services::touch::TouchDispatcher::disabled(),
+        beacon_events: Arc::new(the_beaconator::services::beacon::BeaconEventFeed::disabled()),
+        idempotency: Arc::new(the_beaconator::services::idempotency::IdempotencyStore::from_env()),
+        gas_metrics: Arc::new(the_beaconator::services::metrics::GasMetrics::from_env()),
+        history: Arc::new(the_beaconator::services::history::TransactionHistory::from_env()),
+        perp: PerpConfig::default(),
+        error_registry: Arc::new(
+            the_beaconator::services::perp::ErrorSelectorRegistry::from_bundled_abis()
+                .expect("bundled ABIs parse"),
+        ),
+        allowances: Arc::new(the_beaconator::services::perp::AllowanceCache::new()),
+        strict_address_checksum: false,
     }
 }
 
@@ -910,9 +1027,19 @@ pub async fn create_simple_test_app_state() -> AppState {
 
     AppState {
         provider: ProviderConfig {
-            read_provider,
+            read_provider: read_provider.clone(),
             rpc_url: "http://localhost:8545".to_string(),
             chain_id: 31337,
+            env_type: "localnet".to_string(),
+            endpoints: Arc::new(RpcEndpoints::single(
+                "http://localhost:8545".to_string(),
+                read_provider,
+            )),
+            receipts: Arc::new(the_beaconator::services::transaction::ReceiptCache::default()),
+            gas_prices: Arc::new(the_beaconator::services::gas_price::GasPriceCache::default()),
+            read_retry: the_beaconator::services::rpc::ReadRetryPolicy::default(),
+            receipt_wait: the_beaconator::services::transaction::ReceiptWaitConfig::default(),
+            confirmations: the_beaconator::services::transaction::ConfirmationPolicy::default(),
         },
         wallets: WalletConfig {
             manager: wallet_manager,
@@ -923,8 +1050,13 @@ pub async fn create_simple_test_app_state() -> AppState {
             eth_transfer_limit: 10_000_000_000_000_000, // 0.01 ETH
             usdc_bonus_limit: 50_000_000,       // 50 USDC
             faucet_reserve_eth_wei: 20_000_000_000_000_000, // 0.02 ETH
+            funding_limiter: Arc::new(FundingRateLimiter::from_env()),
+            funding_replays: Arc::new(
+                the_beaconator::services::idempotency::IdempotencyStore::from_env(),
+            ),
+            cold_wallet_address: None,
         },
-        contracts: ContractAddresses {
+        contracts: LiveContracts::new(ContractAddresses {
             perpcity_registry: Address::from_str("0x2345678901234567890123456789012345678901")
                 .unwrap(),
             perp_factory: Address::from_str("0x3456789012345678901234567890123456789012").unwrap(),
@@ -947,10 +1079,12 @@ pub async fn create_simple_test_app_state() -> AppState {
                 .unwrap(),
             protocol_fee_manager: None,
             module_registry: None,
-        },
+        }),
         auth: AuthConfig {
             access_token: "test_token".to_string(),
             admin_token: "test_admin_token".to_string(),
+            scoped_tokens: HashMap::new(),
+            request_signing: None,
         },
         registries: Registries {
             beacon_types: Arc::new(BeaconTypeRegistry::test_stub()),
@@ -958,6 +1092,17 @@ pub async fn create_simple_test_app_state() -> AppState {
             recipes: Arc::new(RecipeRegistry::test_stub()),
         },
         touch: the_beaconator::services::touch::TouchDispatcher::disabled(),
+        beacon_events: Arc::new(the_beaconator::services::beacon::BeaconEventFeed::disabled()),
+        idempotency: Arc::new(the_beaconator::services::idempotency::IdempotencyStore::from_env()),
+        gas_metrics: Arc::new(the_beaconator::services::metrics::GasMetrics::from_env()),
+        history: Arc::new(the_beaconator::services::history::TransactionHistory::from_env()),
+        perp: PerpConfig::default(),
+        error_registry: Arc::new(
+            the_beaconator::services::perp::ErrorSelectorRegistry::from_bundled_abis()
+                .expect("bundled ABIs parse"),
+        ),
+        allowances: Arc::new(the_beaconator::services::perp::AllowanceCache::new()),
+        strict_address_checksum: false,
     }
 }
 
@@ -978,9 +1123,19 @@ pub async fn create_test_app_state_with_provider(
 
     AppState {
         provider: ProviderConfig {
-            read_provider,
+            read_provider: read_provider.clone(),
             rpc_url: "http://localhost:8545".to_string(),
             chain_id: 31337,
+            env_type: "localnet".to_string(),
+            endpoints: Arc::new(RpcEndpoints::single(
+                "http://localhost:8545".to_string(),
+                read_provider,
+            )),
+            receipts: Arc::new(the_beaconator::services::transaction::ReceiptCache::default()),
+            gas_prices: Arc::new(the_beaconator::services::gas_price::GasPriceCache::default()),
+            read_retry: the_beaconator::services::rpc::ReadRetryPolicy::default(),
+            receipt_wait: the_beaconator::services::transaction::ReceiptWaitConfig::default(),
+            confirmations: the_beaconator::services::transaction::ConfirmationPolicy::default(),
         },
         wallets: WalletConfig {
             manager: wallet_manager,
@@ -991,8 +1146,13 @@ pub async fn create_test_app_state_with_provider(
             eth_transfer_limit: 10_000_000_000_000_000, // 0.01 ETH
             usdc_bonus_limit: 50_000_000,       // 50 USDC
             faucet_reserve_eth_wei: 20_000_000_000_000_000, // 0.02 ETH
+            funding_limiter: Arc::new(FundingRateLimiter::from_env()),
+            funding_replays: Arc::new(
+                the_beaconator::services::idempotency::IdempotencyStore::from_env(),
+            ),
+            cold_wallet_address: None,
         },
-        contracts: ContractAddresses {
+        contracts: LiveContracts::new(ContractAddresses {
             perpcity_registry: Address::from_str("0x2345678901234567890123456789012345678901")
                 .unwrap(),
             perp_factory: Address::from_str("0x3456789012345678901234567890123456789012").unwrap(),
@@ -1015,10 +1175,12 @@ pub async fn create_test_app_state_with_provider(
                 .unwrap(),
             protocol_fee_manager: None,
             module_registry: None,
-        },
+        }),
         auth: AuthConfig {
             access_token: "test_token".to_string(),
             admin_token: "test_admin_token".to_string(),
+            scoped_tokens: HashMap::new(),
+            request_signing: None,
         },
         registries: Registries {
             beacon_types: Arc::new(BeaconTypeRegistry::test_stub()),
@@ -1026,6 +1188,17 @@ pub async fn create_test_app_state_with_provider(
             recipes: Arc::new(RecipeRegistry::test_stub()),
         },
         touch: the_beaconator::services::touch::TouchDispatcher::disabled(),
+        beacon_events: Arc::new(the_beaconator::services::beacon::BeaconEventFeed::disabled()),
+        idempotency: Arc::new(the_beaconator::services::idempotency::IdempotencyStore::from_env()),
+        gas_metrics: Arc::new(the_beaconator::services::metrics::GasMetrics::from_env()),
+        history: Arc::new(the_beaconator::services::history::TransactionHistory::from_env()),
+        perp: PerpConfig::default(),
+        error_registry: Arc::new(
+            the_beaconator::services::perp::ErrorSelectorRegistry::from_bundled_abis()
+                .expect("bundled ABIs parse"),
+        ),
+        allowances: Arc::new(the_beaconator::services::perp::AllowanceCache::new()),
+        strict_address_checksum: false,
     }
 }
 
@@ -1034,11 +1207,8 @@ pub fn create_mock_provider_with_network_error() -> Arc<the_beaconator::AlloyPro
     // Use a non-existent endpoint that will fail deterministically
     let signer = alloy::signers::local::PrivateKeySigner::random();
     let wallet = alloy::network::EthereumWallet::from(signer);
-    let provider = alloy::providers::ProviderBuilder::new()
-        .wallet(wallet)
-        .connect_http("http://127.0.0.1:1".parse().unwrap()); // Port 1 - guaranteed to fail
-
-    Arc::new(provider)
+    // Port 1 - guaranteed to fail
+    Arc::new(build_test_signing_provider(wallet, "http://127.0.0.1:1"))
 }
 
 #[cfg(test)]
@@ -1074,8 +1244,11 @@ mod tests {
         #[allow(deprecated)]
         let app_state = create_test_app_state().await;
         assert_ne!(app_state.wallets.signer_address, Address::ZERO);
-        assert_ne!(app_state.contracts.ecdsa_verifier_factory, Address::ZERO);
-        assert_ne!(app_state.contracts.perp_factory, Address::ZERO);
+        assert_ne!(
+            app_state.contracts.load().ecdsa_verifier_factory,
+            Address::ZERO
+        );
+        assert_ne!(app_state.contracts.load().perp_factory, Address::ZERO);
     }
 
     #[tokio::test]
@@ -1203,6 +1376,7 @@ impl AnvilManager {
 /// Everything a fork test needs: an AppState wired to the REAL deployed
 /// contracts on the fork, the fork addresses, and the single pool wallet all
 /// sends come from (one wallet so ownership handovers are deterministic).
+#[cfg(feature = "wallet-pool")]
 pub struct ForkFixture {
     pub app_state: AppState,
     pub addresses: ForkAddresses,
@@ -1215,6 +1389,7 @@ pub struct ForkFixture {
 /// Redis-backed ComponentFactoryRegistry seeded from the checked-in address
 /// file. Requires REDIS_URL (the wallet pool and factory registry are
 /// Redis-backed in production; stubs cannot acquire wallets).
+#[cfg(feature = "wallet-pool")]
 pub async fn create_fork_fixture() -> ForkFixture {
     let redis_url = std::env::var("REDIS_URL").expect("REDIS_URL must be set for fork tests");
     let addresses = load_fork_addresses("testnet");
@@ -1277,9 +1452,19 @@ pub async fn create_fork_fixture() -> ForkFixture {
 
     let app_state = AppState {
         provider: ProviderConfig {
-            read_provider,
+            read_provider: read_provider.clone(),
             rpc_url: anvil.rpc_url().to_string(),
             chain_id: anvil.chain_id(),
+            env_type: "localnet".to_string(),
+            endpoints: Arc::new(RpcEndpoints::single(
+                anvil.rpc_url().to_string(),
+                read_provider,
+            )),
+            receipts: Arc::new(the_beaconator::services::transaction::ReceiptCache::default()),
+            gas_prices: Arc::new(the_beaconator::services::gas_price::GasPriceCache::default()),
+            read_retry: the_beaconator::services::rpc::ReadRetryPolicy::default(),
+            receipt_wait: the_beaconator::services::transaction::ReceiptWaitConfig::default(),
+            confirmations: the_beaconator::services::transaction::ConfirmationPolicy::default(),
         },
         wallets: WalletConfig {
             manager: Arc::new(manager),
@@ -1289,8 +1474,13 @@ pub async fn create_fork_fixture() -> ForkFixture {
             eth_transfer_limit: 10_000_000_000_000_000,
             usdc_bonus_limit: 50_000_000,
             faucet_reserve_eth_wei: 20_000_000_000_000_000,
+            funding_limiter: Arc::new(FundingRateLimiter::from_env()),
+            funding_replays: Arc::new(
+                the_beaconator::services::idempotency::IdempotencyStore::from_env(),
+            ),
+            cold_wallet_address: None,
         },
-        contracts: ContractAddresses {
+        contracts: LiveContracts::new(ContractAddresses {
             perpcity_registry: addresses.perpcity_registry,
             perp_factory: addresses.perp_factory,
             usdc: addresses.usdc,
@@ -1305,10 +1495,12 @@ pub async fn create_fork_fixture() -> ForkFixture {
             pricing_module: addresses.pricing_module,
             protocol_fee_manager: None,
             module_registry: None,
-        },
+        }),
         auth: AuthConfig {
             access_token: "test_token".to_string(),
             admin_token: "test_admin_token".to_string(),
+            scoped_tokens: HashMap::new(),
+            request_signing: None,
         },
         registries: Registries {
             beacon_types: Arc::new(BeaconTypeRegistry::test_stub()),
@@ -1316,6 +1508,17 @@ pub async fn create_fork_fixture() -> ForkFixture {
             recipes: Arc::new(RecipeRegistry::test_stub()),
         },
         touch: the_beaconator::services::touch::TouchDispatcher::disabled(),
+        beacon_events: Arc::new(the_beaconator::services::beacon::BeaconEventFeed::disabled()),
+        idempotency: Arc::new(the_beaconator::services::idempotency::IdempotencyStore::from_env()),
+        gas_metrics: Arc::new(the_beaconator::services::metrics::GasMetrics::from_env()),
+        history: Arc::new(the_beaconator::services::history::TransactionHistory::from_env()),
+        perp: PerpConfig::default(),
+        error_registry: Arc::new(
+            the_beaconator::services::perp::ErrorSelectorRegistry::from_bundled_abis()
+                .expect("bundled ABIs parse"),
+        ),
+        allowances: Arc::new(the_beaconator::services::perp::AllowanceCache::new()),
+        strict_address_checksum: false,
     };
 
     ForkFixture {
@@ -0,0 +1,75 @@
+// Tests for the bundled contract ABIs and bytecode (src/abis.rs)
+
+use serial_test::serial;
+use the_beaconator::abis::{
+    ABI_DIR_ENV, BUNDLED_ABIS, abi_dir_from_env, identity_beacon_bytecode_from, load_abi,
+    load_bundled_abis_from,
+};
+
+const EXPECTED_CONTRACTS: [&str; 6] = [
+    "Perp",
+    "PerpFactory",
+    "BeaconRegistry",
+    "ProtocolFeeManager",
+    "ModuleRegistry",
+    "Multicall3",
+];
+
+#[test]
+fn test_every_expected_abi_parses_from_embedded_bytes() {
+    let bundled: Vec<&str> = BUNDLED_ABIS.iter().map(|(name, _)| *name).collect();
+    assert_eq!(bundled, EXPECTED_CONTRACTS);
+
+    for (contract, json) in BUNDLED_ABIS {
+        let abi = load_abi(contract, json).unwrap_or_else(|e| panic!("{e}"));
+        assert!(!abi.is_empty(), "{contract} ABI is empty");
+    }
+
+    let abis = load_bundled_abis_from(None).expect("embedded ABIs parse");
+    let names: Vec<&str> = abis.iter().map(|(name, _)| *name).collect();
+    assert_eq!(names, EXPECTED_CONTRACTS);
+
+    let bytecode = identity_beacon_bytecode_from(None).expect("embedded bytecode decodes");
+    assert!(!bytecode.is_empty());
+}
+
+#[test]
+fn test_abi_dir_override() {
+    let dir = tempfile::tempdir().unwrap();
+    for (contract, json) in BUNDLED_ABIS {
+        std::fs::write(dir.path().join(format!("{contract}.json")), json).unwrap();
+    }
+    std::fs::write(dir.path().join("IdentityBeacon.bytecode"), "0x6080\n").unwrap();
+
+    let abis = load_bundled_abis_from(Some(dir.path())).unwrap();
+    assert_eq!(abis.len(), EXPECTED_CONTRACTS.len());
+    let bytecode = identity_beacon_bytecode_from(Some(dir.path())).unwrap();
+    assert_eq!(bytecode.as_ref(), [0x60, 0x80]);
+
+    // A missing file and a malformed one are both reported, by path.
+    std::fs::remove_file(dir.path().join("Perp.json")).unwrap();
+    std::fs::write(dir.path().join("Multicall3.json"), "[{").unwrap();
+    let errors = load_bundled_abis_from(Some(dir.path())).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].file.ends_with("Perp.json"), "{}", errors[0]);
+    assert!(errors[1].file.ends_with("Multicall3.json"), "{}", errors[1]);
+
+    std::fs::remove_file(dir.path().join("IdentityBeacon.bytecode")).unwrap();
+    let error = identity_beacon_bytecode_from(Some(dir.path())).unwrap_err();
+    assert!(error.contains("IdentityBeacon.bytecode"), "{error}");
+}
+
+#[test]
+#[serial]
+fn test_abi_dir_from_env() {
+    // SAFETY: #[serial] guarantees no concurrent env access from other tests.
+    unsafe {
+        std::env::remove_var(ABI_DIR_ENV);
+        assert_eq!(abi_dir_from_env(), None);
+        std::env::set_var(ABI_DIR_ENV, "  ");
+        assert_eq!(abi_dir_from_env(), None);
+        std::env::set_var(ABI_DIR_ENV, " /opt/abis ");
+        assert_eq!(abi_dir_from_env(), Some("/opt/abis".into()));
+        std::env::remove_var(ABI_DIR_ENV);
+    }
+}
@@ -0,0 +1,131 @@
+// Tests for /batch_create_beacon limits and bounded concurrency
+// (src/services/beacon/batch_create.rs)
+
+use crate::test_utils::create_simple_test_app_state;
+use rocket::State;
+use rocket::http::Status;
+use serial_test::serial;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use the_beaconator::guards::{ApiToken, IdempotencyKey, SignedJson};
+use the_beaconator::models::BatchCreateBeaconByTypeRequest;
+use the_beaconator::routes::beacon::batch_create_beacon;
+use the_beaconator::services::beacon::{
+    DEFAULT_BATCH_CREATE_CONCURRENCY, DEFAULT_BATCH_CREATE_MAX_COUNT, batch_concurrency,
+    batch_create_concurrency_from_env, batch_create_max_count_from_env, run_bounded,
+};
+
+/// Run `count` tasks under `limit` and return the most that were ever in flight.
+async fn peak_in_flight(count: usize, limit: usize) -> (usize, Vec<usize>) {
+    let in_flight = AtomicUsize::new(0);
+    let peak = AtomicUsize::new(0);
+    let results = run_bounded(count, limit, |i| {
+        let (in_flight, peak) = (&in_flight, &peak);
+        async move {
+            let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            // Later tasks finish first, so completion order differs from index order.
+            tokio::time::sleep(Duration::from_millis(5 * (count - i) as u64)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            i
+        }
+    })
+    .await;
+    (peak.load(Ordering::SeqCst), results)
+}
+
+#[tokio::test]
+async fn test_run_bounded_respects_limit() {
+    let (peak, results) = peak_in_flight(10, 3).await;
+    assert_eq!(peak, 3);
+    assert_eq!(
+        results,
+        (0..10).collect::<Vec<_>>(),
+        "results in index order"
+    );
+}
+
+#[tokio::test]
+async fn test_run_bounded_sequential_and_wide() {
+    assert_eq!(peak_in_flight(4, 1).await.0, 1);
+    assert_eq!(peak_in_flight(4, 0).await.0, 1, "zero is treated as one");
+    assert_eq!(peak_in_flight(4, 16).await.0, 4);
+    assert!(peak_in_flight(0, 4).await.1.is_empty());
+}
+
+#[test]
+fn test_batch_concurrency_capped_by_wallets() {
+    assert_eq!(batch_concurrency(4, 10), 4);
+    assert_eq!(batch_concurrency(8, 3), 3);
+    assert_eq!(batch_concurrency(4, 1), 1);
+    assert_eq!(batch_concurrency(4, 0), 1);
+}
+
+fn set_env(key: &str, value: Option<&str>) {
+    // SAFETY: #[serial] guarantees no concurrent env access from other tests.
+    unsafe {
+        match value {
+            Some(v) => std::env::set_var(key, v),
+            None => std::env::remove_var(key),
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn test_batch_limits_from_env() {
+    set_env("BATCH_CREATE_MAX_COUNT", None);
+    set_env("BATCH_CREATE_CONCURRENCY", None);
+    assert_eq!(
+        batch_create_max_count_from_env(),
+        DEFAULT_BATCH_CREATE_MAX_COUNT
+    );
+    assert_eq!(
+        batch_create_concurrency_from_env(),
+        DEFAULT_BATCH_CREATE_CONCURRENCY
+    );
+
+    set_env("BATCH_CREATE_MAX_COUNT", Some(" 250 "));
+    set_env("BATCH_CREATE_CONCURRENCY", Some("1"));
+    assert_eq!(batch_create_max_count_from_env(), 250);
+    assert_eq!(batch_create_concurrency_from_env(), 1);
+
+    set_env("BATCH_CREATE_MAX_COUNT", Some("0"));
+    set_env("BATCH_CREATE_CONCURRENCY", Some("many"));
+    assert_eq!(
+        batch_create_max_count_from_env(),
+        DEFAULT_BATCH_CREATE_MAX_COUNT
+    );
+    assert_eq!(
+        batch_create_concurrency_from_env(),
+        DEFAULT_BATCH_CREATE_CONCURRENCY
+    );
+
+    set_env("BATCH_CREATE_MAX_COUNT", None);
+    set_env("BATCH_CREATE_CONCURRENCY", None);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_batch_create_uses_configured_max_count() {
+    let app_state = create_simple_test_app_state().await;
+    set_env("BATCH_CREATE_MAX_COUNT", Some("5"));
+
+    let call = |count| {
+        batch_create_beacon(
+            SignedJson(BatchCreateBeaconByTypeRequest {
+                beacon_type: "identity".to_string(),
+                count,
+                params: None,
+            }),
+            ApiToken::Token("test_token".to_string()),
+            IdempotencyKey(None),
+            State::from(&app_state),
+        )
+    };
+    assert_eq!(call(6).await.unwrap_err(), Status::BadRequest);
+    // Within the cap the request reaches the stub registry, which has no Redis.
+    assert_eq!(call(5).await.unwrap_err(), Status::InternalServerError);
+
+    set_env("BATCH_CREATE_MAX_COUNT", None);
+}
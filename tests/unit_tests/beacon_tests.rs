@@ -1,28 +1,35 @@
 // Beacon route tests - extracted from src/routes/beacon.rs
 
-use alloy::primitives::{Address, B256, Bytes};
+use alloy::primitives::{Address, B256, Bytes, U256};
 use rocket::State;
-use rocket::serde::json::Json;
 use std::str::FromStr;
-use the_beaconator::guards::ApiToken;
+use std::sync::Arc;
+use the_beaconator::guards::{ApiToken, SignedJson};
 use the_beaconator::models::{
-    BatchUpdateBeaconRequest, BeaconUpdateData, CreateBeaconByTypeRequest, CreateBeaconResponse,
+    BatchBeaconDataRequest, BatchUpdateBeaconRequest, BeaconUpdateData, CreateBeaconByTypeRequest,
+    CreateBeaconResponse, CreateBeaconWithEcdsaRequest,
 };
 use the_beaconator::routes::IMulticall3;
-use the_beaconator::routes::beacon::batch_update_beacon;
+use the_beaconator::routes::beacon::{
+    batch_beacon_data, batch_update_beacon, create_beacon_with_ecdsa, get_beacon_events,
+};
 use the_beaconator::services::beacon::core::{
     is_beacon_registered, is_transaction_confirmed, register_beacon_with_registry,
 };
+use the_beaconator::services::beacon::read::{MAX_BATCH_BEACON_READS, decode_index_result};
+use the_beaconator::services::beacon::{BeaconEventFeed, ObservedIndex};
 
 #[tokio::test]
 #[ignore = "requires WalletManager with Redis"]
 async fn test_batch_update_beacon_with_multicall3() {
-    let token = ApiToken("test_token".to_string());
-    let mut app_state = crate::test_utils::create_simple_test_app_state().await;
+    let token = ApiToken::Token("test_token".to_string());
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
 
     // Set multicall3 address for the test
-    app_state.contracts.multicall3 =
-        Some(Address::from_str("0xcA11bde05977b3631167028862bE2a173976CA11").unwrap());
+    app_state.contracts.update(|contracts| {
+        contracts.multicall3 =
+            Some(Address::from_str("0xcA11bde05977b3631167028862bE2a173976CA11").unwrap());
+    });
 
     let state = State::from(&app_state);
 
@@ -34,8 +41,9 @@ async fn test_batch_update_beacon_with_multicall3() {
             .unwrap(), // 100 encoded as bytes
     };
 
-    let request = Json(BatchUpdateBeaconRequest {
+    let request = SignedJson(BatchUpdateBeaconRequest {
         updates: vec![update_data],
+        prevalidate: false,
     });
 
     let result = batch_update_beacon(request, token, state).await;
@@ -56,7 +64,7 @@ async fn test_batch_update_beacon_with_multicall3() {
 #[tokio::test]
 #[ignore = "requires WalletManager with Redis"]
 async fn test_batch_update_beacon_without_multicall3() {
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
     let app_state = crate::test_utils::create_simple_test_app_state().await; // No multicall3_address set
     let state = State::from(&app_state);
 
@@ -68,8 +76,9 @@ async fn test_batch_update_beacon_without_multicall3() {
             .unwrap(),
     };
 
-    let request = Json(BatchUpdateBeaconRequest {
+    let request = SignedJson(BatchUpdateBeaconRequest {
         updates: vec![update_data],
+        prevalidate: false,
     });
 
     let result = batch_update_beacon(request, token, state).await;
@@ -77,24 +86,15 @@ async fn test_batch_update_beacon_without_multicall3() {
     assert!(result.is_ok());
     let response = result.unwrap().into_inner();
 
-    // Should fail with clear error message about missing multicall3
+    // Falls back to a sequential update, which fails for a non-beacon address; the
+    // error comes from the update itself, not from the missing Multicall3.
     assert!(!response.success);
     assert!(response.data.is_some());
     let batch_data = response.data.unwrap();
     assert_eq!(batch_data.successful_updates, 0);
     assert_eq!(batch_data.failed_updates, 1);
-    assert!(
-        batch_data.results[0]
-            .error
-            .as_ref()
-            .unwrap()
-            .contains("Multicall3")
-            || batch_data.results[0]
-                .error
-                .as_ref()
-                .unwrap()
-                .contains("multicall")
-    );
+    let error = batch_data.results[0].error.as_ref().unwrap();
+    assert!(!error.contains("require Multicall3"), "got {error}");
 }
 
 #[test]
@@ -132,11 +132,11 @@ async fn test_app_state_has_required_contract_info() {
 
     // Test that all required contract addresses are set
     assert_ne!(
-        app_state.contracts.ecdsa_verifier_factory,
+        app_state.contracts.load().ecdsa_verifier_factory,
         Address::from_str("0x0000000000000000000000000000000000000000").unwrap()
     );
     assert_ne!(
-        app_state.contracts.perpcity_registry,
+        app_state.contracts.load().perpcity_registry,
         Address::from_str("0x0000000000000000000000000000000000000000").unwrap()
     );
     assert!(!app_state.auth.access_token.is_empty());
@@ -146,7 +146,7 @@ async fn test_app_state_has_required_contract_info() {
 async fn test_register_beacon_with_registry_helper() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let beacon_address = Address::from_str("0x1111111111111111111111111111111111111111").unwrap();
-    let registry_address = app_state.contracts.perpcity_registry;
+    let registry_address = app_state.contracts.load().perpcity_registry;
 
     // This will fail without a real network, but tests the function signature
     let result = register_beacon_with_registry(&app_state, beacon_address, registry_address).await;
@@ -172,7 +172,7 @@ async fn test_transaction_confirmation_timeout_handling() {
 async fn test_beacon_registration_check() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let beacon_address = Address::from_str("0x1111111111111111111111111111111111111111").unwrap();
-    let registry_address = app_state.contracts.perpcity_registry;
+    let registry_address = app_state.contracts.load().perpcity_registry;
 
     // Test beacon registration check
     let result = is_beacon_registered(&app_state, beacon_address, registry_address).await;
@@ -203,6 +203,7 @@ fn test_create_beacon_response_serialization() {
         factory_address: "0x9876543210987654321098765432109876543210".to_string(),
         registered: true,
         safe_proposal_hash: None,
+        confirmations: None,
     };
 
     let serialized = serde_json::to_string(&response).unwrap();
@@ -212,3 +213,218 @@ fn test_create_beacon_response_serialization() {
     assert_eq!(deserialized.beacon_type, "perpcity");
     assert!(deserialized.registered);
 }
+
+#[tokio::test]
+async fn test_create_beacon_with_ecdsa_without_bytecode() {
+    let token = ApiToken::Token("test_token".to_string());
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    assert!(
+        app_state
+            .contracts
+            .load()
+            .identity_beacon_bytecode
+            .is_empty()
+    );
+    let state = State::from(&app_state);
+
+    let request = SignedJson(CreateBeaconWithEcdsaRequest {
+        initial_index: 100,
+        verifier_address: None,
+    });
+    let result = create_beacon_with_ecdsa(request, token, state).await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        rocket::http::Status::ServiceUnavailable
+    );
+}
+
+#[tokio::test]
+async fn test_create_beacon_with_ecdsa_invalid_verifier_address() {
+    let token = ApiToken::Token("test_token".to_string());
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    let request = SignedJson(CreateBeaconWithEcdsaRequest {
+        initial_index: 100,
+        verifier_address: Some("not_an_address".to_string()),
+    });
+    let result = create_beacon_with_ecdsa(request, token, state).await;
+
+    assert_eq!(result.unwrap_err(), rocket::http::Status::BadRequest);
+}
+
+#[tokio::test]
+async fn test_get_beacon_events_disabled_feed() {
+    let token = ApiToken::Token("test_token".to_string());
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    let result =
+        get_beacon_events("0x1111111111111111111111111111111111111111", token, state).await;
+
+    assert_eq!(
+        result.unwrap_err(),
+        rocket::http::Status::ServiceUnavailable
+    );
+}
+
+#[tokio::test]
+async fn test_get_beacon_events_lookup_errors() {
+    let tracked = Address::repeat_byte(0xbe);
+    let mut app_state = crate::test_utils::create_simple_test_app_state().await;
+    app_state.beacon_events = Arc::new(BeaconEventFeed::new(vec![tracked], Some(0)));
+    let state = State::from(&app_state);
+
+    let result = get_beacon_events(
+        "not_an_address",
+        ApiToken::Token("test_token".to_string()),
+        state,
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), rocket::http::Status::BadRequest);
+
+    // Untracked beacon
+    let result = get_beacon_events(
+        "0x1111111111111111111111111111111111111111",
+        ApiToken::Token("test_token".to_string()),
+        state,
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), rocket::http::Status::NotFound);
+
+    // Tracked, but no event seen yet
+    let result = get_beacon_events(
+        &format!("{tracked:#x}"),
+        ApiToken::Token("test_token".to_string()),
+        state,
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), rocket::http::Status::NotFound);
+}
+
+#[tokio::test]
+async fn test_get_beacon_events_returns_latest() {
+    let tracked = Address::repeat_byte(0xbe);
+    let mut app_state = crate::test_utils::create_simple_test_app_state().await;
+    let feed = BeaconEventFeed::new(vec![tracked], Some(120));
+    feed.apply([(
+        tracked,
+        ObservedIndex {
+            index: U256::from(777),
+            block_number: 118,
+            log_index: 0,
+            transaction_hash: B256::repeat_byte(0x22),
+        },
+    )])
+    .await;
+    app_state.beacon_events = Arc::new(feed);
+    let state = State::from(&app_state);
+
+    let response = get_beacon_events(
+        &format!("{tracked:#x}"),
+        ApiToken::Token("test_token".to_string()),
+        state,
+    )
+    .await
+    .unwrap();
+
+    let data = response.into_inner().data.unwrap();
+    assert_eq!(data.beacon_address, tracked.to_checksum(None));
+    assert_eq!(data.index, "777");
+    assert_eq!(data.block_number, 118);
+    assert_eq!(
+        data.transaction_hash,
+        format!("{:#x}", B256::repeat_byte(0x22))
+    );
+    assert_eq!(data.scanned_through_block, Some(120));
+}
+
+#[tokio::test]
+async fn test_batch_beacon_data_rejects_empty_and_oversized() {
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    let result = batch_beacon_data(
+        SignedJson(BatchBeaconDataRequest {
+            beacon_addresses: vec![],
+        }),
+        ApiToken::Token("test_token".to_string()),
+        state,
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), rocket::http::Status::BadRequest);
+
+    let result = batch_beacon_data(
+        SignedJson(BatchBeaconDataRequest {
+            beacon_addresses: vec![
+                "0x1111111111111111111111111111111111111111".to_string();
+                MAX_BATCH_BEACON_READS + 1
+            ],
+        }),
+        ApiToken::Token("test_token".to_string()),
+        state,
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), rocket::http::Status::BadRequest);
+}
+
+#[tokio::test]
+async fn test_batch_beacon_data_reports_invalid_address_per_entry() {
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    let state = State::from(&app_state);
+
+    let response = batch_beacon_data(
+        SignedJson(BatchBeaconDataRequest {
+            beacon_addresses: vec!["not_an_address".to_string()],
+        }),
+        ApiToken::Token("test_token".to_string()),
+        state,
+    )
+    .await
+    .unwrap()
+    .into_inner();
+
+    assert!(!response.success);
+    let data = response.data.unwrap();
+    assert_eq!(data.total_requested, 1);
+    assert_eq!(data.failed_reads, 1);
+    assert!(!data.via_multicall);
+    assert_eq!(data.results[0].beacon_address, "not_an_address");
+    assert!(
+        data.results[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Invalid beacon address")
+    );
+}
+
+#[test]
+fn test_decode_index_result() {
+    let ok = IMulticall3::Result {
+        success: true,
+        returnData: U256::from(12345).to_be_bytes::<32>().to_vec().into(),
+    };
+    assert_eq!(decode_index_result(&ok).unwrap(), U256::from(12345));
+
+    let reverted = IMulticall3::Result {
+        success: false,
+        returnData: Bytes::new(),
+    };
+    assert_eq!(
+        decode_index_result(&reverted).unwrap_err(),
+        "index() reverted"
+    );
+
+    // A call to an address without code succeeds with no return data
+    let no_code = IMulticall3::Result {
+        success: true,
+        returnData: Bytes::new(),
+    };
+    assert!(
+        decode_index_result(&no_code)
+            .unwrap_err()
+            .contains("not a uint256")
+    );
+}
@@ -0,0 +1,448 @@
+// Unit tests for startup configuration loading (src/config.rs)
+
+use alloy::primitives::Address;
+use serial_test::serial;
+use the_beaconator::config::{
+    ConfigError, ConfigReader, ContractRegistry, TransferLimits, chain_id_for_env,
+    check_allowed_chain_ids, parse_chain_ids, resolve_chain_id,
+};
+use the_beaconator::create_rocket;
+
+fn set_env(key: &str, value: &str) {
+    // SAFETY: #[serial] guarantees no concurrent env access from other tests.
+    unsafe {
+        std::env::set_var(key, value);
+    }
+}
+
+fn restore_env(key: &str, saved: Option<String>) {
+    // SAFETY: #[serial] guarantees no concurrent env access from other tests.
+    unsafe {
+        match saved {
+            Some(value) => std::env::set_var(key, value),
+            None => std::env::remove_var(key),
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn test_missing_required_var_is_reported() {
+    restore_env("CONFIG_TEST_TOKEN", None);
+    let mut config = ConfigReader::new();
+    assert_eq!(config.required("CONFIG_TEST_TOKEN"), "");
+
+    set_env("CONFIG_TEST_TOKEN", "   ");
+    config.required("CONFIG_TEST_TOKEN");
+
+    let err = config.finish().unwrap_err();
+    assert_eq!(
+        err.problems,
+        vec![
+            "CONFIG_TEST_TOKEN is not set".to_string(),
+            "CONFIG_TEST_TOKEN is not set".to_string(),
+        ],
+        "blank counts as missing"
+    );
+    restore_env("CONFIG_TEST_TOKEN", None);
+}
+
+#[test]
+#[serial]
+fn test_unparseable_address_is_reported() {
+    set_env("CONFIG_TEST_ADDRESS", "0xnot-an-address");
+    let mut config = ConfigReader::new();
+    assert_eq!(config.address("CONFIG_TEST_ADDRESS"), Address::ZERO);
+
+    let err = config.finish().unwrap_err();
+    assert_eq!(err.problems.len(), 1);
+    assert!(
+        err.problems[0].starts_with("CONFIG_TEST_ADDRESS is not a valid address"),
+        "{err}"
+    );
+    assert!(
+        !err.problems[0].contains("not-an-address"),
+        "raw value is not echoed"
+    );
+
+    let addr = Address::repeat_byte(0x11);
+    set_env("CONFIG_TEST_ADDRESS", &format!(" {addr} "));
+    let mut config = ConfigReader::new();
+    assert_eq!(config.address("CONFIG_TEST_ADDRESS"), addr);
+    assert!(config.finish().is_ok());
+    restore_env("CONFIG_TEST_ADDRESS", None);
+}
+
+#[test]
+#[serial]
+fn test_parse_or_collects_every_problem() {
+    restore_env("CONFIG_TEST_LIMIT", None);
+    set_env("CONFIG_TEST_OTHER_LIMIT", "lots");
+    let mut config = ConfigReader::new();
+
+    assert_eq!(config.parse_or("CONFIG_TEST_LIMIT", 7u128), 7);
+    assert_eq!(config.parse_or("CONFIG_TEST_OTHER_LIMIT", 9u128), 9);
+    config.required("CONFIG_TEST_TOKEN");
+
+    let err = config.finish().unwrap_err();
+    assert_eq!(err.problems.len(), 2);
+    assert!(err.problems[0].starts_with("Invalid CONFIG_TEST_OTHER_LIMIT 'lots'"));
+    let message = err.to_string();
+    assert!(message.starts_with("Invalid configuration (2 problems):"));
+    assert!(message.contains("\n  - CONFIG_TEST_TOKEN is not set"));
+    restore_env("CONFIG_TEST_OTHER_LIMIT", None);
+}
+
+#[test]
+#[serial]
+fn test_flag_rejects_values_that_are_not_on_or_off() {
+    let mut config = ConfigReader::new();
+    restore_env("CONFIG_TEST_FLAG", None);
+    assert!(!config.flag("CONFIG_TEST_FLAG"));
+    for (raw, expected) in [(" TRUE ", true), ("on", true), ("0", false), ("No", false)] {
+        set_env("CONFIG_TEST_FLAG", raw);
+        assert_eq!(config.flag("CONFIG_TEST_FLAG"), expected, "{raw}");
+    }
+    assert!(config.finish().is_ok());
+
+    set_env("CONFIG_TEST_FLAG", "enabled");
+    let mut config = ConfigReader::new();
+    assert!(!config.flag("CONFIG_TEST_FLAG"));
+    let err = config.finish().unwrap_err();
+    assert_eq!(
+        err.problems,
+        vec!["Invalid CONFIG_TEST_FLAG 'enabled': expected true or false".to_string()]
+    );
+    restore_env("CONFIG_TEST_FLAG", None);
+}
+
+#[test]
+fn test_single_problem_display() {
+    let err = ConfigError::new("REDIS_URL is not set");
+    assert_eq!(
+        err.to_string(),
+        "Invalid configuration: REDIS_URL is not set"
+    );
+}
+
+#[test]
+fn test_chain_id_for_env() {
+    assert_eq!(chain_id_for_env("mainnet"), Ok(42161));
+    assert_eq!(chain_id_for_env(" Testnet "), Ok(421614));
+    assert_eq!(chain_id_for_env("localnet"), Ok(421614));
+    assert!(chain_id_for_env("devnet").is_err());
+}
+
+#[test]
+fn test_resolve_chain_id_rejects_wrong_network() {
+    // Testnet config pointed at an Arbitrum One RPC.
+    let err = resolve_chain_id("testnet", 421614, &[], 42161).unwrap_err();
+    assert!(err.contains("RPC reports chain id 42161"), "{err}");
+    assert!(err.contains("ENV=testnet expects 421614"), "{err}");
+    assert!(err.contains("ALLOWED_CHAIN_IDS"), "{err}");
+}
+
+#[test]
+fn test_resolve_chain_id_accepts_expected_or_allowlisted() {
+    assert_eq!(resolve_chain_id("mainnet", 42161, &[], 42161), Ok(42161));
+    // A custom local chain replaces ENV's default once allowlisted.
+    assert_eq!(
+        resolve_chain_id("localnet", 421614, &[31337], 31337),
+        Ok(31337)
+    );
+    assert_eq!(
+        resolve_chain_id("localnet", 421614, &[31337], 421614),
+        Ok(421614)
+    );
+    assert!(resolve_chain_id("localnet", 421614, &[31337], 1).is_err());
+}
+
+#[test]
+fn test_production_chain_needs_mainnet_env() {
+    // Allowlisting Arbitrum One under ENV=testnet would give it testnet transfer limits.
+    let err = resolve_chain_id("testnet", 421614, &[42161], 42161).unwrap_err();
+    assert!(err.contains("production chain id 42161"), "{err}");
+    assert!(err.contains("ENV=testnet"), "{err}");
+    assert!(resolve_chain_id("localnet", 421614, &[8453], 8453).is_err());
+    assert_eq!(resolve_chain_id("mainnet", 42161, &[1], 1), Ok(1));
+
+    assert!(check_allowed_chain_ids("testnet", &[31337, 42161]).is_err());
+    assert!(check_allowed_chain_ids("localnet", &[31337, 1337]).is_ok());
+    assert!(check_allowed_chain_ids("mainnet", &[42161, 1]).is_ok());
+}
+
+const LIMIT_VARS: [&str; 8] = [
+    "USDC_TRANSFER_LIMIT",
+    "ETH_TRANSFER_LIMIT",
+    "USDC_BONUS_LIMIT",
+    "MAINNET_USDC_TRANSFER_LIMIT",
+    "MAINNET_ETH_TRANSFER_LIMIT",
+    "MAINNET_USDC_BONUS_LIMIT",
+    "TESTNET_USDC_TRANSFER_LIMIT",
+    "ALLOW_HIGH_MAINNET_LIMITS",
+];
+
+/// Run `f` with only `vars` of the transfer-limit variables set, restoring them after.
+fn with_limit_vars(vars: &[(&str, &str)], f: impl FnOnce()) {
+    let saved: Vec<_> = LIMIT_VARS
+        .iter()
+        .map(|key| (*key, std::env::var(key).ok()))
+        .collect();
+    for key in LIMIT_VARS {
+        restore_env(key, None);
+    }
+    for (key, value) in vars {
+        set_env(key, value);
+    }
+    f();
+    for (key, value) in saved {
+        restore_env(key, value);
+    }
+}
+
+#[test]
+#[serial]
+fn test_transfer_limits_default_per_network() {
+    with_limit_vars(&[], || {
+        let mut config = ConfigReader::new();
+        assert_eq!(
+            config.transfer_limits("mainnet"),
+            TransferLimits::MAINNET_DEFAULTS
+        );
+        assert_eq!(
+            config.transfer_limits("testnet"),
+            TransferLimits::TESTNET_DEFAULTS
+        );
+        assert_eq!(
+            config.transfer_limits("localnet"),
+            TransferLimits::TESTNET_DEFAULTS
+        );
+        assert!(config.finish().is_ok());
+    });
+    const {
+        assert!(
+            TransferLimits::MAINNET_DEFAULTS.usdc_transfer
+                < TransferLimits::TESTNET_DEFAULTS.usdc_transfer
+        );
+        assert!(
+            TransferLimits::MAINNET_DEFAULTS.eth_transfer
+                < TransferLimits::TESTNET_DEFAULTS.eth_transfer
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn test_transfer_limits_prefer_network_scoped_vars() {
+    with_limit_vars(
+        &[
+            ("USDC_TRANSFER_LIMIT", "500000000"),
+            ("TESTNET_USDC_TRANSFER_LIMIT", "200000000"),
+            ("MAINNET_USDC_TRANSFER_LIMIT", "5000000"),
+        ],
+        || {
+            let mut config = ConfigReader::new();
+            assert_eq!(config.transfer_limits("testnet").usdc_transfer, 200_000_000);
+            assert_eq!(config.transfer_limits("mainnet").usdc_transfer, 5_000_000);
+            assert!(config.finish().is_ok());
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_testnet_scale_limit_on_mainnet_refuses_startup() {
+    // A testnet env file reused for a mainnet deploy.
+    with_limit_vars(&[("USDC_TRANSFER_LIMIT", "1000000000")], || {
+        let mut config = ConfigReader::new();
+        config.transfer_limits("mainnet");
+        let err = config.finish().unwrap_err();
+        assert_eq!(err.problems.len(), 1);
+        assert!(
+            err.problems[0]
+                .starts_with("USDC_TRANSFER_LIMIT=1000000000 exceeds the mainnet default"),
+            "{err}"
+        );
+        assert!(
+            err.problems[0].contains("ALLOW_HIGH_MAINNET_LIMITS"),
+            "{err}"
+        );
+
+        // The same value is fine on testnet.
+        let mut config = ConfigReader::new();
+        config.transfer_limits("testnet");
+        assert!(config.finish().is_ok());
+    });
+}
+
+#[test]
+#[serial]
+fn test_high_mainnet_limits_need_explicit_flag() {
+    with_limit_vars(
+        &[
+            ("MAINNET_USDC_BONUS_LIMIT", "100000000"),
+            ("ALLOW_HIGH_MAINNET_LIMITS", "true"),
+        ],
+        || {
+            let mut config = ConfigReader::new();
+            assert_eq!(config.transfer_limits("mainnet").usdc_bonus, 100_000_000);
+            assert!(config.finish().is_ok());
+        },
+    );
+}
+
+#[test]
+fn test_parse_chain_ids() {
+    assert_eq!(parse_chain_ids(" 31337, 1337 ,"), Ok(vec![31337, 1337]));
+    assert_eq!(parse_chain_ids(""), Ok(vec![]));
+    let err = parse_chain_ids("31337,anvil").unwrap_err();
+    assert!(err.contains("'anvil'"), "{err}");
+}
+
+#[tokio::test]
+#[serial]
+async fn test_create_rocket_reports_config_errors_without_panicking() {
+    let saved_factory = std::env::var("PERP_FACTORY_ADDRESS").ok();
+    let saved_token = std::env::var("BEACONATOR_ACCESS_TOKEN").ok();
+    // Set rather than removed: create_rocket loads .env, which fills unset vars only.
+    set_env("PERP_FACTORY_ADDRESS", "0xnot-an-address");
+    set_env("BEACONATOR_ACCESS_TOKEN", "");
+
+    let err = create_rocket().await.unwrap_err();
+    assert!(
+        err.problems
+            .iter()
+            .any(|p| p.starts_with("PERP_FACTORY_ADDRESS is not a valid address")),
+        "{err}"
+    );
+    assert!(
+        err.problems
+            .iter()
+            .any(|p| p == "BEACONATOR_ACCESS_TOKEN is not set"),
+        "{err}"
+    );
+
+    restore_env("PERP_FACTORY_ADDRESS", saved_factory);
+    restore_env("BEACONATOR_ACCESS_TOKEN", saved_token);
+}
+
+const SAMPLE_CONTRACTS: &str = r#"{
+    "perp_factory": "0x5678901234567890123456789012345678901234",
+    "usdc": "0x75faf114eafb1BDbe2F0316DF893fd58CE46AA4d",
+    "multicall3": "0xcA11bde05977b3631167028862bE2a173976CA11"
+}"#;
+
+fn address(raw: &str) -> Address {
+    raw.parse().unwrap()
+}
+
+#[test]
+fn test_contract_registry_loads_sample_file() {
+    let path =
+        std::env::temp_dir().join(format!("beaconator-contracts-{}.json", std::process::id()));
+    std::fs::write(&path, SAMPLE_CONTRACTS).unwrap();
+    let registry = ContractRegistry::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let registry = registry.unwrap();
+    assert_eq!(registry.len(), 3);
+    assert_eq!(
+        registry.get("perp_factory"),
+        Some(address("0x5678901234567890123456789012345678901234"))
+    );
+    assert_eq!(
+        registry.get("multicall3"),
+        Some(address("0xcA11bde05977b3631167028862bE2a173976CA11"))
+    );
+    assert_eq!(registry.get("perpcity_registry"), None);
+
+    let missing = ContractRegistry::load(std::path::Path::new("/nonexistent/contracts.json"));
+    assert!(
+        missing
+            .unwrap_err()
+            .starts_with("CONTRACTS_FILE /nonexistent/contracts.json")
+    );
+}
+
+#[test]
+fn test_contract_registry_reports_every_bad_entry() {
+    let err = ContractRegistry::from_json(
+        r#"{"perp_factroy": "0x5678901234567890123456789012345678901234", "usdc": "0x1234"}"#,
+    )
+    .unwrap_err();
+    assert!(
+        err.contains("unknown contract name 'perp_factroy'"),
+        "{err}"
+    );
+    assert!(err.contains("'usdc' is not a valid address"), "{err}");
+
+    assert!(ContractRegistry::from_json("[]").is_err());
+    assert_eq!(
+        ContractRegistry::name_for_env_key("WEIGHTED_SUM_COMPOSITE_FACTORY_ADDRESS"),
+        "weighted_sum_composite_factory"
+    );
+}
+
+#[test]
+#[serial]
+fn test_env_address_takes_precedence_over_contracts_file() {
+    let keys = ["PERP_FACTORY_ADDRESS", "USDC_ADDRESS", "MULTICALL3_ADDRESS"];
+    let saved: Vec<Option<String>> = keys.iter().map(|k| std::env::var(k).ok()).collect();
+    for key in keys {
+        restore_env(key, None);
+    }
+    set_env("USDC_ADDRESS", "0xaf88d065e77c8cC2239327C5EDb3A432268e5831");
+
+    let mut config = ConfigReader::new();
+    config.set_contracts(ContractRegistry::from_json(SAMPLE_CONTRACTS).unwrap());
+    assert_eq!(
+        config.address("PERP_FACTORY_ADDRESS"),
+        address("0x5678901234567890123456789012345678901234"),
+        "unset var falls back to the file"
+    );
+    assert_eq!(
+        config.address("USDC_ADDRESS"),
+        address("0xaf88d065e77c8cC2239327C5EDb3A432268e5831"),
+        "set var wins over the file"
+    );
+    assert_eq!(
+        config.optional_address("MULTICALL3_ADDRESS"),
+        Some(address("0xcA11bde05977b3631167028862bE2a173976CA11"))
+    );
+    assert_eq!(config.optional_address("MODULE_REGISTRY_ADDRESS"), None);
+    assert!(config.finish().is_ok());
+
+    for (key, value) in keys.into_iter().zip(saved) {
+        restore_env(key, value);
+    }
+}
+
+#[test]
+#[serial]
+fn test_required_contracts_missing_from_file_are_reported_together() {
+    let keys = ["PERPCITY_REGISTRY_ADDRESS", "FEES_MODULE_ADDRESS"];
+    let saved: Vec<Option<String>> = keys.iter().map(|k| std::env::var(k).ok()).collect();
+    for key in keys {
+        restore_env(key, None);
+    }
+
+    let mut config = ConfigReader::new();
+    config.set_contracts(ContractRegistry::from_json(SAMPLE_CONTRACTS).unwrap());
+    for key in keys {
+        assert_eq!(config.address(key), Address::ZERO);
+    }
+    let err = config.finish().unwrap_err();
+    assert_eq!(
+        err.problems,
+        vec![
+            "PERPCITY_REGISTRY_ADDRESS is not set and CONTRACTS_FILE has no 'perpcity_registry' entry"
+                .to_string(),
+            "FEES_MODULE_ADDRESS is not set and CONTRACTS_FILE has no 'fees_module' entry"
+                .to_string(),
+        ]
+    );
+
+    for (key, value) in keys.into_iter().zip(saved) {
+        restore_env(key, value);
+    }
+}
@@ -0,0 +1,128 @@
+// Unit tests for DepositLiquidityForPerpRequest::validate and the structured 400 it backs.
+
+use rocket::State;
+use rocket::http::Status;
+use the_beaconator::guards::{ApiToken, SignedJson};
+use the_beaconator::models::{DepositLiquidityForPerpRequest, FieldError, PerpConfig};
+use the_beaconator::routes::perp::deposit_liquidity_for_perp_endpoint;
+
+fn valid_request() -> DepositLiquidityForPerpRequest {
+    DepositLiquidityForPerpRequest {
+        perp_address: "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0".to_string(),
+        margin_amount_usdc: "500000000".to_string(), // 500 USDC
+        holder: None,
+        max_amt0_in: None,
+        max_amt1_in: None,
+        tick_spacing: None,
+        tick_lower: None,
+        tick_upper: None,
+    }
+}
+
+fn fields(errors: &[FieldError]) -> Vec<&str> {
+    errors.iter().map(|e| e.field.as_str()).collect()
+}
+
+#[test]
+fn test_valid_request_has_no_errors() {
+    assert_eq!(
+        valid_request().validate(&PerpConfig::default(), false),
+        vec![]
+    );
+}
+
+#[test]
+fn test_every_invalid_field_is_reported_at_once() {
+    let request = DepositLiquidityForPerpRequest {
+        perp_address: "not_an_address".to_string(),
+        margin_amount_usdc: "ten".to_string(),
+        max_amt0_in: Some("-1".to_string()),
+        max_amt1_in: Some("1.5".to_string()),
+        tick_lower: Some(31),
+        ..valid_request()
+    };
+
+    let errors = request.validate(&PerpConfig::default(), false);
+    assert_eq!(
+        fields(&errors),
+        vec![
+            "perp_address",
+            "margin_amount_usdc",
+            "max_amt0_in",
+            "max_amt1_in",
+            "tick_lower"
+        ]
+    );
+    assert!(errors[4].message.contains("divisible by tick_spacing"));
+}
+
+#[test]
+fn test_margin_bounds_and_tick_range_reported_together() {
+    let config = PerpConfig::default();
+    let request = DepositLiquidityForPerpRequest {
+        margin_amount_usdc: "1000000".to_string(), // 1 USDC, below the 10 USDC minimum
+        tick_lower: Some(60),
+        tick_upper: Some(30),
+        ..valid_request()
+    };
+
+    let errors = request.validate(&config, false);
+    assert_eq!(fields(&errors), vec!["margin_amount_usdc", "tick_lower"]);
+    assert!(
+        errors[0].message.contains("outside"),
+        "{}",
+        errors[0].message
+    );
+    assert!(errors[1].message.contains("must be less than tick_upper"));
+}
+
+#[test]
+fn test_tick_spacing_checked_with_liquidity_bounds() {
+    let request = DepositLiquidityForPerpRequest {
+        margin_amount_usdc: "0".to_string(),
+        tick_spacing: Some(0),
+        ..valid_request()
+    };
+
+    let errors = request.validate(&PerpConfig::default(), false);
+    assert_eq!(fields(&errors), vec!["margin_amount_usdc", "tick_spacing"]);
+    assert_eq!(errors[0].message, "must be greater than zero");
+}
+
+#[test]
+fn test_tick_upper_out_of_range_is_attributed_to_tick_upper() {
+    let request = DepositLiquidityForPerpRequest {
+        tick_upper: Some(887_280),
+        ..valid_request()
+    };
+
+    let errors = request.validate(&PerpConfig::default(), false);
+    assert_eq!(fields(&errors), vec!["tick_upper"]);
+}
+
+#[tokio::test]
+async fn test_endpoint_returns_all_field_errors() {
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    let request = DepositLiquidityForPerpRequest {
+        perp_address: "0x123".to_string(),
+        margin_amount_usdc: "0".to_string(),
+        ..valid_request()
+    };
+
+    let error = deposit_liquidity_for_perp_endpoint(
+        SignedJson(request),
+        ApiToken::Token("test_token".to_string()),
+        State::from(&app_state),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(error.0, Status::BadRequest);
+    let body = error.1.into_inner();
+    assert!(!body.success);
+    assert_eq!(body.message, "Invalid deposit request (2 problems)");
+    assert_eq!(
+        fields(&body.data.unwrap()),
+        vec!["perp_address", "margin_amount_usdc"]
+    );
+}
@@ -0,0 +1,200 @@
+// Unit tests for POST /estimate_gas and the shared transaction builders it uses
+
+use alloy::primitives::{Address, Bytes, FixedBytes};
+use rocket::{State, http::Status};
+use std::str::FromStr;
+use the_beaconator::guards::{ApiToken, SignedJson};
+use the_beaconator::models::{
+    CreateBeaconWithEcdsaRequest, DeployPerpForBeaconRequest, EstimateGasRequest,
+    TransactionGasEstimate,
+};
+use the_beaconator::routes::estimate::estimate_gas;
+use the_beaconator::services::beacon::identity_beacon_deploy_code;
+use the_beaconator::services::estimate::summarize_estimate;
+use the_beaconator::services::perp::build_create_perp_call;
+
+fn deploy_params(beacon_address: &str, ema_window: u32) -> DeployPerpForBeaconRequest {
+    DeployPerpForBeaconRequest {
+        beacon_address: beacon_address.to_string(),
+        owner: "0x2222222222222222222222222222222222222222".to_string(),
+        name: "Test Perp".to_string(),
+        symbol: "TEST".to_string(),
+        token_uri: "https://example.com".to_string(),
+        ema_window,
+        salt: None,
+        verbose: false,
+    }
+}
+
+#[test]
+fn test_estimate_gas_request_deserializes_each_operation() {
+    let create: EstimateGasRequest =
+        serde_json::from_str(r#"{"operation": "create_beacon", "params": {"initial_index": 100}}"#)
+            .unwrap();
+    assert!(matches!(
+        create,
+        EstimateGasRequest::CreateBeacon(CreateBeaconWithEcdsaRequest {
+            initial_index: 100,
+            verifier_address: None,
+        })
+    ));
+
+    let deploy: EstimateGasRequest = serde_json::from_str(
+        r#"{"operation": "deploy_perp", "params": {
+            "beacon_address": "0x1111111111111111111111111111111111111111",
+            "owner": "0x2222222222222222222222222222222222222222",
+            "name": "Test Perp", "symbol": "TEST", "token_uri": "", "ema_window": 3600,
+            "salt": null}}"#,
+    )
+    .unwrap();
+    match deploy {
+        EstimateGasRequest::DeployPerp(params) => assert_eq!(params.ema_window, 3600),
+        other => panic!("expected deploy_perp, got {other:?}"),
+    }
+
+    let deposit: EstimateGasRequest = serde_json::from_str(
+        r#"{"operation": "deposit_liquidity", "params": {
+            "perp_address": "0x3333333333333333333333333333333333333333",
+            "margin_amount_usdc": "50000000"}}"#,
+    )
+    .unwrap();
+    match deposit {
+        EstimateGasRequest::DepositLiquidity(params) => {
+            assert_eq!(params.margin_amount_usdc, "50000000")
+        }
+        other => panic!("expected deposit_liquidity, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_estimate_gas_request_rejects_unknown_operation() {
+    let result: Result<EstimateGasRequest, _> =
+        serde_json::from_str(r#"{"operation": "fund_guest_wallet", "params": {}}"#);
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_build_create_perp_call_validates_ema_window() {
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    let beacon = Address::from_str("0x1111111111111111111111111111111111111111").unwrap();
+    let owner = Address::from_str("0x2222222222222222222222222222222222222222").unwrap();
+    let build = |ema_window| {
+        build_create_perp_call(
+            &app_state.contracts.load(),
+            beacon,
+            owner,
+            "Test Perp".to_string(),
+            "TEST".to_string(),
+            String::new(),
+            ema_window,
+            FixedBytes::ZERO,
+        )
+    };
+
+    assert!(build(0).err().unwrap().contains("must be > 0"));
+    assert!(build(0x100_0000).err().unwrap().contains("exceeds uint24"));
+
+    let call = build(3600).unwrap();
+    assert_eq!(call.owner, owner);
+    assert_eq!(call.modules.beacon, beacon);
+    assert_eq!(call.modules.fees, app_state.contracts.load().fees_module);
+    assert_eq!(
+        call.modules.pricing,
+        app_state.contracts.load().pricing_module
+    );
+}
+
+#[test]
+fn test_identity_beacon_deploy_code_appends_constructor_args() {
+    let verifier = Address::from_str("0x4444444444444444444444444444444444444444").unwrap();
+    assert!(identity_beacon_deploy_code(&Bytes::new(), verifier, 1).is_err());
+
+    let bytecode = Bytes::from(vec![0x60, 0x80, 0x60, 0x40]);
+    let code = identity_beacon_deploy_code(&bytecode, verifier, 7).unwrap();
+    assert_eq!(code.len(), bytecode.len() + 64);
+    assert_eq!(&code[..4], &bytecode[..]);
+    assert_eq!(&code[4 + 12..4 + 32], verifier.as_slice());
+    assert_eq!(code[code.len() - 1], 7);
+}
+
+#[test]
+fn test_summarize_estimate_prices_total_gas() {
+    let from = Address::from_str("0x5555555555555555555555555555555555555555").unwrap();
+    let response = summarize_estimate(
+        "deposit_liquidity",
+        from,
+        vec![
+            TransactionGasEstimate {
+                description: "USDC.approve".to_string(),
+                gas: 50_000,
+            },
+            TransactionGasEstimate {
+                description: "Perp.openMaker".to_string(),
+                gas: 450_000,
+            },
+        ],
+        1_000_000_000,
+        3_000_000_000,
+    );
+
+    assert_eq!(response.operation, "deposit_liquidity");
+    assert_eq!(response.from, from.to_checksum(None));
+    assert_eq!(response.total_gas, 500_000);
+    assert_eq!(response.estimated_fee_wei, "500000000000000");
+    assert_eq!(response.max_fee_wei, "1500000000000000");
+}
+
+#[tokio::test]
+async fn test_estimate_gas_rejects_invalid_deploy_params() {
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+
+    let bad_address = SignedJson(EstimateGasRequest::DeployPerp(deploy_params(
+        "not_an_address",
+        3600,
+    )));
+    let result = estimate_gas(
+        bad_address,
+        ApiToken::Token("test_token".to_string()),
+        State::from(&app_state),
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), Status::BadRequest);
+
+    let bad_ema = SignedJson(EstimateGasRequest::DeployPerp(deploy_params(
+        "0x1111111111111111111111111111111111111111",
+        0,
+    )));
+    let result = estimate_gas(
+        bad_ema,
+        ApiToken::Token("test_token".to_string()),
+        State::from(&app_state),
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), Status::BadRequest);
+}
+
+#[tokio::test]
+async fn test_estimate_gas_create_beacon_without_bytecode_is_unavailable() {
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    assert!(
+        app_state
+            .contracts
+            .load()
+            .identity_beacon_bytecode
+            .is_empty()
+    );
+
+    let request = SignedJson(EstimateGasRequest::CreateBeacon(
+        CreateBeaconWithEcdsaRequest {
+            initial_index: 1,
+            verifier_address: None,
+        },
+    ));
+    let result = estimate_gas(
+        request,
+        ApiToken::Token("test_token".to_string()),
+        State::from(&app_state),
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), Status::ServiceUnavailable);
+}
@@ -286,6 +286,7 @@ fn test_create_beacon_response_lbcgbm_type() {
         factory_address: "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_string(),
         registered: true,
         safe_proposal_hash: None,
+        confirmations: None,
     };
 
     let json = serde_json::to_string(&response).unwrap();
@@ -306,6 +307,7 @@ fn test_create_beacon_response_composite_unregistered() {
         factory_address: "0xdddddddddddddddddddddddddddddddddddddddd".to_string(),
         registered: false,
         safe_proposal_hash: None,
+        confirmations: None,
     };
 
     let json = serde_json::to_string(&response).unwrap();
@@ -325,6 +327,7 @@ fn test_create_beacon_response_with_safe_proposal() {
         safe_proposal_hash: Some(
             "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef".to_string(),
         ),
+        confirmations: None,
     };
 
     let json = serde_json::to_string(&response).unwrap();
@@ -145,3 +145,355 @@ fn test_kind_combinations() {
     let _shutdown = Kind::Shutdown;
     let _combined = Kind::Ignite | Kind::Liftoff | Kind::Shutdown;
 }
+
+mod request_id {
+    use rocket::http::{Header, Status};
+    use rocket::local::asynchronous::Client;
+    use rocket::{get, routes};
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use the_beaconator::fairings::{
+        REQUEST_ID_HEADER, RequestId, RequestLogger, in_request_span, is_valid_request_id,
+    };
+
+    #[get("/echo")]
+    fn echo(request_id: RequestId) -> String {
+        request_id.0
+    }
+
+    #[get("/logs")]
+    async fn logs() -> &'static str {
+        tracing::info!("handling /logs");
+        "ok"
+    }
+
+    /// Log sink shared with the subscriber a test installs.
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn client() -> Client {
+        let rocket = rocket::build()
+            .attach(RequestLogger)
+            .mount("/", routes![echo]);
+        Client::tracked(rocket).await.expect("valid rocket")
+    }
+
+    #[tokio::test]
+    async fn test_generated_request_id_round_trips() {
+        let client = client().await;
+        let response = client.get("/echo").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+
+        let header = response
+            .headers()
+            .get_one(REQUEST_ID_HEADER)
+            .expect("X-Request-Id header")
+            .to_string();
+        assert!(uuid::Uuid::parse_str(&header).is_ok(), "{header}");
+        // The handler saw the same id the response echoes.
+        assert_eq!(response.into_string().await.unwrap(), header);
+    }
+
+    #[tokio::test]
+    async fn test_each_request_gets_its_own_id() {
+        let client = client().await;
+        let first = client.get("/echo").dispatch().await;
+        let second = client.get("/echo").dispatch().await;
+        assert_ne!(
+            first.headers().get_one(REQUEST_ID_HEADER),
+            second.headers().get_one(REQUEST_ID_HEADER)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incoming_request_id_is_propagated() {
+        let client = client().await;
+        let response = client
+            .get("/echo")
+            .header(Header::new(REQUEST_ID_HEADER, "upstream-trace.42"))
+            .dispatch()
+            .await;
+        assert_eq!(
+            response.headers().get_one(REQUEST_ID_HEADER),
+            Some("upstream-trace.42")
+        );
+        assert_eq!(response.into_string().await.unwrap(), "upstream-trace.42");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_incoming_request_id_is_replaced() {
+        let client = client().await;
+        let response = client
+            .get("/echo")
+            .header(Header::new(REQUEST_ID_HEADER, "bad id; drop"))
+            .dispatch()
+            .await;
+        let header = response.headers().get_one(REQUEST_ID_HEADER).unwrap();
+        assert!(uuid::Uuid::parse_str(header).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_request_id_header_set_on_error_responses() {
+        let client = client().await;
+        let response = client.get("/missing").dispatch().await;
+        assert_eq!(response.status(), Status::NotFound);
+        assert!(response.headers().get_one(REQUEST_ID_HEADER).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_handler_events_carry_the_request_id() {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let rocket = rocket::build()
+            .attach(RequestLogger)
+            .mount("/", in_request_span(routes![logs]));
+        let client = Client::tracked(rocket).await.expect("valid rocket");
+        let response = client
+            .get("/logs")
+            .header(Header::new(REQUEST_ID_HEADER, "trace-7"))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let line = output
+            .lines()
+            .find(|line| line.contains("handling /logs"))
+            .expect("handler event logged");
+        assert!(line.contains("request_id=trace-7"), "{line}");
+    }
+
+    #[test]
+    fn test_is_valid_request_id() {
+        assert!(is_valid_request_id("abc-DEF_123.4"));
+        assert!(!is_valid_request_id(""));
+        assert!(!is_valid_request_id("has space"));
+        assert!(!is_valid_request_id(&"a".repeat(129)));
+    }
+}
+
+mod sentry_tags {
+    use rocket::http::Header;
+    use rocket::local::asynchronous::Client;
+    use the_beaconator::fairings::{PanicCatcher, REQUEST_ID_HEADER};
+
+    #[tokio::test]
+    async fn test_tag_request_attaches_method_and_path() {
+        let client = Client::untracked(rocket::build())
+            .await
+            .expect("valid rocket");
+        let request = client
+            .post("/deploy_perp_for_beacon?token=secret")
+            .header(Header::new(REQUEST_ID_HEADER, "trace-1"));
+
+        let events = sentry::test::with_captured_events(|| {
+            sentry::with_scope(
+                |scope| PanicCatcher::tag_request(scope, request.inner()),
+                || sentry::capture_message("boom", sentry::Level::Error),
+            );
+        });
+
+        let tags = &events[0].tags;
+        assert_eq!(tags["http.method"], "POST");
+        assert_eq!(tags["http.path"], "/deploy_perp_for_beacon");
+        assert_eq!(tags["request_id"], "trace-1");
+    }
+}
+
+mod cors {
+    use rocket::http::{Header, Status};
+    use rocket::local::asynchronous::Client;
+    use rocket::{get, routes};
+    use the_beaconator::fairings::{AllowedOrigins, Cors};
+
+    #[get("/ping")]
+    fn ping() -> &'static str {
+        "pong"
+    }
+
+    #[derive(rocket::Responder)]
+    struct Varied(&'static str, Header<'static>);
+
+    #[get("/varied")]
+    fn varied() -> Varied {
+        Varied("pong", Header::new("Vary", "Accept-Encoding"))
+    }
+
+    async fn client(origins: &str) -> Client {
+        let cors = Cors::parse(origins).expect("origins configured");
+        let rocket = rocket::build()
+            .attach(cors)
+            .mount("/", routes![ping, varied]);
+        Client::tracked(rocket).await.expect("valid rocket")
+    }
+
+    #[test]
+    fn test_parse_origins() {
+        assert!(Cors::parse("").is_none());
+        assert!(Cors::parse(" , ").is_none());
+        assert_eq!(
+            Cors::parse("https://a.example.com/, HTTPS://B.example.com")
+                .unwrap()
+                .origins(),
+            &AllowedOrigins::List(vec![
+                "https://a.example.com".to_string(),
+                "https://b.example.com".to_string(),
+            ])
+        );
+        assert_eq!(
+            Cors::parse("https://a.example.com,*").unwrap().origins(),
+            &AllowedOrigins::Any
+        );
+    }
+
+    #[tokio::test]
+    async fn test_allowed_origin_receives_header() {
+        let client = client("https://app.example.com").await;
+        let response = client
+            .get("/ping")
+            .header(Header::new("Origin", "https://app.example.com"))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            response.headers().get_one("Access-Control-Allow-Origin"),
+            Some("https://app.example.com")
+        );
+        assert_eq!(response.headers().get_one("Vary"), Some("Origin"));
+    }
+
+    #[tokio::test]
+    async fn test_vary_origin_is_appended_to_route_vary() {
+        let client = client("https://app.example.com").await;
+        let response = client
+            .get("/varied")
+            .header(Header::new("Origin", "https://app.example.com"))
+            .dispatch()
+            .await;
+        let vary: Vec<_> = response.headers().get("Vary").collect();
+        assert_eq!(vary, vec!["Accept-Encoding, Origin"]);
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_origin_gets_no_header() {
+        let client = client("https://app.example.com").await;
+        let response = client
+            .get("/ping")
+            .header(Header::new("Origin", "https://evil.example.com"))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        assert!(
+            response
+                .headers()
+                .get_one("Access-Control-Allow-Origin")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_allows_any_origin() {
+        let client = client("*").await;
+        let response = client
+            .get("/ping")
+            .header(Header::new("Origin", "https://anything.example.com"))
+            .dispatch()
+            .await;
+        assert_eq!(
+            response.headers().get_one("Access-Control-Allow-Origin"),
+            Some("*")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_preflight_is_answered_for_mounted_routes() {
+        let client = client("https://app.example.com").await;
+        let response = client
+            .options("/ping")
+            .header(Header::new("Origin", "https://app.example.com"))
+            .header(Header::new("Access-Control-Request-Method", "GET"))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::NoContent);
+        assert_eq!(
+            response.headers().get_one("Access-Control-Allow-Origin"),
+            Some("https://app.example.com")
+        );
+        assert!(
+            response
+                .headers()
+                .get_one("Access-Control-Allow-Methods")
+                .is_some_and(|m| m.contains("POST"))
+        );
+        assert!(
+            response
+                .headers()
+                .get_one("Access-Control-Allow-Headers")
+                .is_some_and(|h| [
+                    "Authorization",
+                    "Idempotency-Key",
+                    "X-Signature",
+                    "X-Timestamp"
+                ]
+                .iter()
+                .all(|name| h.contains(name)))
+        );
+    }
+}
+
+mod panic_catcher {
+    use rocket::http::{ContentType, Header, Status};
+    use rocket::local::asynchronous::Client;
+    use rocket::{get, routes};
+    use the_beaconator::api_catchers;
+    use the_beaconator::fairings::{PanicCatcher, REQUEST_ID_HEADER, RequestLogger};
+
+    #[get("/boom")]
+    fn boom() -> &'static str {
+        panic!("secret internal detail");
+    }
+
+    #[tokio::test]
+    async fn test_panic_returns_structured_json_without_details() {
+        let rocket = rocket::build()
+            .attach(RequestLogger)
+            .attach(PanicCatcher)
+            .mount("/", routes![boom])
+            .register("/", api_catchers());
+        let client = Client::tracked(rocket).await.expect("valid rocket");
+
+        let response = client
+            .get("/boom")
+            .header(Header::new(REQUEST_ID_HEADER, "panic-trace-1"))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::InternalServerError);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+        let body = response.into_string().await.expect("body");
+        let json: serde_json::Value = serde_json::from_str(&body).expect("JSON body");
+        assert_eq!(json["success"], false);
+        assert!(json["data"].is_null());
+        assert_eq!(json["error_code"], "internal_error");
+        assert_eq!(json["request_id"], "panic-trace-1");
+        assert_eq!(json["message"], "Internal Server Error");
+        assert!(!body.contains("secret internal detail"));
+    }
+}
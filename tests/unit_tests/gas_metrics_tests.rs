@@ -0,0 +1,105 @@
+// Tests for per-operation gas histograms (src/services/metrics.rs) and GET /metrics/gas
+
+use rocket::State;
+use the_beaconator::guards::ApiToken;
+use the_beaconator::routes::info::gas_metrics;
+use the_beaconator::services::metrics::{DEFAULT_GAS_BUCKETS, GasMetrics, GasOperation};
+
+fn metrics() -> GasMetrics {
+    GasMetrics::new(true, vec![50_000, 100_000, 500_000])
+}
+
+#[test]
+fn test_record_builds_cumulative_histogram() {
+    let metrics = metrics();
+    metrics.record(GasOperation::BeaconUpdate, 40_000);
+    metrics.record(GasOperation::BeaconUpdate, 90_000);
+    metrics.record(GasOperation::BeaconUpdate, 2_000_000);
+
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.operations.len(), 1);
+    let histogram = &snapshot.operations[0];
+    assert_eq!(histogram.operation, "beacon_update");
+    assert_eq!(histogram.count, 3);
+    assert_eq!(histogram.sum, "2130000");
+    assert_eq!(histogram.min, 40_000);
+    assert_eq!(histogram.max, 2_000_000);
+    assert_eq!(histogram.mean, 710_000);
+
+    let buckets: Vec<(&str, u64)> = histogram
+        .buckets
+        .iter()
+        .map(|b| (b.le.as_str(), b.count))
+        .collect();
+    assert_eq!(
+        buckets,
+        vec![("50000", 1), ("100000", 2), ("500000", 2), ("+Inf", 3)]
+    );
+}
+
+#[test]
+fn test_operations_are_tracked_separately() {
+    let metrics = metrics();
+    metrics.record(GasOperation::PerpDeploy, 4_000_000);
+    metrics.record(GasOperation::Deposit, 300_000);
+    metrics.record(GasOperation::Deposit, 310_000);
+
+    let snapshot = metrics.snapshot();
+    let find = |name: &str| {
+        snapshot
+            .operations
+            .iter()
+            .find(|h| h.operation == name)
+            .unwrap_or_else(|| panic!("missing {name}"))
+    };
+    assert_eq!(find("perp_deploy").count, 1);
+    assert_eq!(find("deposit").count, 2);
+    assert!(snapshot.operations.iter().all(|h| h.operation != "funding"));
+}
+
+#[test]
+fn test_bucket_bounds_are_sorted_and_deduplicated() {
+    let metrics = GasMetrics::new(true, vec![100_000, 50_000, 100_000]);
+    metrics.record(GasOperation::Funding, 60_000);
+
+    let snapshot = metrics.snapshot();
+    let les: Vec<&str> = snapshot.operations[0]
+        .buckets
+        .iter()
+        .map(|b| b.le.as_str())
+        .collect();
+    assert_eq!(les, vec!["50000", "100000", "+Inf"]);
+}
+
+#[test]
+fn test_disabled_metrics_record_nothing() {
+    let metrics = GasMetrics::new(false, DEFAULT_GAS_BUCKETS.to_vec());
+    metrics.record(GasOperation::BeaconCreate, 1_000_000);
+
+    let snapshot = metrics.snapshot();
+    assert!(!snapshot.enabled);
+    assert!(snapshot.operations.is_empty());
+}
+
+#[tokio::test]
+async fn test_gas_metrics_endpoint_serves_snapshot() {
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    app_state
+        .gas_metrics
+        .record(GasOperation::BeaconRegister, 75_000);
+
+    let response = gas_metrics(
+        ApiToken::Token("test_token".to_string()),
+        State::from(&app_state),
+    );
+    let response = response.into_inner();
+    assert!(response.success);
+    let data = response.data.expect("metrics data");
+    let histogram = data
+        .operations
+        .iter()
+        .find(|h| h.operation == "beacon_register")
+        .expect("beacon_register histogram");
+    assert_eq!(histogram.count, 1);
+    assert_eq!(histogram.max, 75_000);
+}
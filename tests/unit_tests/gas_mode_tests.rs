@@ -0,0 +1,217 @@
+// Tests for GAS_MODE, GAS_LIMIT_MULTIPLIER and the gas filler (src/services/wallet/gas.rs),
+// and the nonce filler that waits for it (src/services/wallet/nonce.rs)
+
+use alloy::network::{Ethereum, TransactionBuilder};
+use alloy::primitives::{Address, U64, U128, U256};
+use alloy::providers::fillers::{JoinFill, TxFiller};
+use alloy::providers::{ProviderBuilder, SendableTx};
+use alloy::rpc::client::RpcClient;
+use alloy::rpc::json_rpc::{
+    ErrorPayload, RequestPacket, Response, ResponsePacket, ResponsePayload,
+};
+use alloy::rpc::types::TransactionRequest;
+use alloy::transports::mock::Asserter;
+use alloy::transports::{TransportError, TransportFut};
+use serde_json::value::RawValue;
+use serial_test::serial;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll};
+use the_beaconator::services::wallet::gas::{
+    GAS_LIMIT_MULTIPLIER_ENV, GAS_MODE_ENV, GasConfig, GasMode, GasModeFiller, MAX_GAS_LIMIT,
+    apply_gas_limit_multiplier, gas_limit_multiplier_from_env,
+};
+use the_beaconator::services::wallet::nonce::{GasGatedNonceFiller, PoolNonceManager};
+use tower::Service;
+
+#[test]
+fn test_gas_mode_parsing() {
+    assert_eq!("eip1559".parse(), Ok(GasMode::Eip1559));
+    assert_eq!(" Legacy ".parse(), Ok(GasMode::Legacy));
+    let err = "london".parse::<GasMode>().unwrap_err();
+    assert!(err.contains("GAS_MODE"), "{err}");
+    assert_eq!(GasMode::default(), GasMode::Eip1559);
+    assert_eq!(GasMode::Legacy.to_string(), "legacy");
+}
+
+#[test]
+#[serial]
+fn test_gas_mode_from_env() {
+    // SAFETY: #[serial] guarantees no concurrent env access from other tests.
+    unsafe {
+        std::env::remove_var(GAS_MODE_ENV);
+        assert_eq!(GasMode::from_env(), Ok(GasMode::Eip1559));
+        std::env::set_var(GAS_MODE_ENV, " ");
+        assert_eq!(GasMode::from_env(), Ok(GasMode::Eip1559));
+        std::env::set_var(GAS_MODE_ENV, "legacy");
+        assert_eq!(GasMode::from_env(), Ok(GasMode::Legacy));
+        std::env::set_var(GAS_MODE_ENV, "fast");
+        assert!(GasMode::from_env().is_err());
+        std::env::remove_var(GAS_MODE_ENV);
+    }
+}
+
+fn legacy(limit_multiplier: f64) -> GasModeFiller {
+    GasModeFiller::new(GasConfig {
+        mode: GasMode::Legacy,
+        limit_multiplier,
+    })
+}
+
+fn transfer() -> TransactionRequest {
+    TransactionRequest::default()
+        .with_from(Address::repeat_byte(0x11))
+        .with_to(Address::repeat_byte(0x22))
+        .with_value(U256::from(1u64))
+}
+
+#[tokio::test]
+async fn test_legacy_mode_fills_gas_price_without_1559_fields() {
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::default()
+        .filler(legacy(1.0))
+        .connect_mocked_client(asserter.clone());
+    asserter.push_success(&U128::from(2_000_000_000u64)); // eth_gasPrice
+    asserter.push_success(&U64::from(21_000u64)); // eth_estimateGas
+
+    let filled: SendableTx<Ethereum> = provider.fill(transfer()).await.expect("fill succeeds");
+    let tx = filled.as_builder().expect("unsigned request");
+
+    assert_eq!(tx.gas_price, Some(2_000_000_000));
+    assert_eq!(tx.gas, Some(21_000));
+    assert!(tx.max_fee_per_gas.is_none());
+    assert!(tx.max_priority_fee_per_gas.is_none());
+}
+
+#[test]
+fn test_apply_gas_limit_multiplier() {
+    assert_eq!(apply_gas_limit_multiplier(100_000, 1.0), 100_000);
+    assert_eq!(apply_gas_limit_multiplier(100_000, 1.2), 120_000);
+    assert_eq!(apply_gas_limit_multiplier(21_001, 1.5), 31_502, "rounds up");
+    assert_eq!(
+        apply_gas_limit_multiplier(25_000_000, 2.0),
+        MAX_GAS_LIMIT,
+        "capped"
+    );
+    assert_eq!(
+        apply_gas_limit_multiplier(40_000_000, 1.2),
+        40_000_000,
+        "an estimate above the cap is never cut"
+    );
+}
+
+#[test]
+#[serial]
+fn test_gas_limit_multiplier_from_env() {
+    // SAFETY: #[serial] guarantees no concurrent env access from other tests.
+    unsafe {
+        std::env::remove_var(GAS_LIMIT_MULTIPLIER_ENV);
+        assert_eq!(gas_limit_multiplier_from_env(), Ok(1.0));
+        std::env::set_var(GAS_LIMIT_MULTIPLIER_ENV, " 1.25 ");
+        assert_eq!(gas_limit_multiplier_from_env(), Ok(1.25));
+        for bad in ["0.8", "3.5", "NaN", "lots"] {
+            std::env::set_var(GAS_LIMIT_MULTIPLIER_ENV, bad);
+            assert!(gas_limit_multiplier_from_env().is_err(), "{bad}");
+        }
+        std::env::remove_var(GAS_LIMIT_MULTIPLIER_ENV);
+    }
+}
+
+#[tokio::test]
+async fn test_multiplier_scales_the_estimate_only() {
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::default()
+        .filler(legacy(1.2))
+        .connect_mocked_client(asserter.clone());
+
+    asserter.push_success(&U128::from(1_000_000_000u64)); // eth_gasPrice
+    asserter.push_success(&U64::from(50_000u64)); // eth_estimateGas
+    let filled: SendableTx<Ethereum> = provider.fill(transfer()).await.expect("fill succeeds");
+    assert_eq!(filled.as_builder().unwrap().gas, Some(60_000));
+
+    // A limit set by the caller is not an estimate and is sent as given.
+    asserter.push_success(&U128::from(1_000_000_000u64)); // eth_gasPrice
+    let filled = provider
+        .fill(transfer().with_gas_limit(50_000))
+        .await
+        .expect("fill succeeds");
+    assert_eq!(filled.as_builder().unwrap().gas, Some(50_000));
+}
+
+fn gated(limit_multiplier: f64) -> JoinFill<GasModeFiller, GasGatedNonceFiller> {
+    JoinFill::new(
+        legacy(limit_multiplier),
+        GasGatedNonceFiller::new(PoolNonceManager::default()),
+    )
+}
+
+#[test]
+fn test_nonce_filler_waits_for_gas_limit() {
+    let filler = GasGatedNonceFiller::new(PoolNonceManager::default());
+    let tx = transfer();
+    assert!(!TxFiller::<Ethereum>::ready(&filler, &tx));
+
+    let estimated = transfer().with_gas_limit(21_000);
+    assert!(TxFiller::<Ethereum>::ready(&filler, &estimated));
+}
+
+/// Node that answers by method and counts `eth_getTransactionCount` calls; its
+/// `eth_estimateGas` always reverts.
+#[derive(Clone, Default)]
+struct RevertingNode {
+    nonce_requests: Arc<AtomicUsize>,
+}
+
+impl Service<RequestPacket> for RevertingNode {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let request = request.as_single().expect("single request").clone();
+        let nonce_requests = self.nonce_requests.clone();
+        Box::pin(async move {
+            let success = |value: &str| {
+                ResponsePayload::Success(RawValue::from_string(format!("\"{value}\"")).unwrap())
+            };
+            let payload = match request.method() {
+                "eth_gasPrice" => success("0x3b9aca00"),
+                "eth_getTransactionCount" => {
+                    nonce_requests.fetch_add(1, Ordering::SeqCst);
+                    success("0x7")
+                }
+                "eth_estimateGas" => {
+                    // Let any concurrently prepared filler issue its request first.
+                    tokio::task::yield_now().await;
+                    ResponsePayload::Failure(ErrorPayload {
+                        code: 3,
+                        message: "execution reverted".into(),
+                        data: None,
+                    })
+                }
+                other => panic!("unexpected {other}"),
+            };
+            Ok(ResponsePacket::Single(Response {
+                id: request.id().clone(),
+                payload,
+            }))
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_reverting_estimate_reserves_no_nonce() {
+    let node = RevertingNode::default();
+    let provider = ProviderBuilder::default()
+        .filler(gated(1.0))
+        .connect_client(RpcClient::new(node.clone(), true));
+
+    let filled: Result<SendableTx<Ethereum>, _> = provider.fill(transfer()).await;
+    let err = filled.unwrap_err();
+    assert!(err.to_string().contains("execution reverted"), "{err}");
+    assert_eq!(node.nonce_requests.load(Ordering::SeqCst), 0);
+}
@@ -1,22 +1,27 @@
-use the_beaconator::guards::ApiToken;
+use rocket::http::Status;
+use std::collections::HashSet;
+use the_beaconator::guards::{
+    ApiToken, authorize_api_token, granted_scopes, required_scope_for_route,
+};
+use the_beaconator::models::{AuthConfig, TokenScope};
 
 #[test]
 fn test_api_token_struct() {
     // Test the ApiToken struct itself
-    let token = ApiToken("test_token".to_string());
-    assert_eq!(token.0, "test_token");
+    let token = ApiToken::Token("test_token".to_string());
+    assert_eq!(token.bearer(), Some("test_token"));
 
     // Test with different token values
-    let token2 = ApiToken("another_token".to_string());
-    assert_eq!(token2.0, "another_token");
+    let token2 = ApiToken::Token("another_token".to_string());
+    assert_eq!(token2.bearer(), Some("another_token"));
 
     // Test with empty token
-    let empty_token = ApiToken("".to_string());
-    assert_eq!(empty_token.0, "");
+    let empty_token = ApiToken::Token("".to_string());
+    assert_eq!(empty_token.bearer(), Some(""));
 
     // Test with special characters
-    let special_token = ApiToken("token-with-special_chars.123".to_string());
-    assert_eq!(special_token.0, "token-with-special_chars.123");
+    let special_token = ApiToken::Token("token-with-special_chars.123".to_string());
+    assert_eq!(special_token.bearer(), Some("token-with-special_chars.123"));
 }
 
 #[test]
@@ -34,47 +39,567 @@ fn test_api_token_creation() {
     ];
 
     for token_str in tokens {
-        let token = ApiToken(token_str.to_string());
-        assert_eq!(token.0, token_str);
+        let token = ApiToken::Token(token_str.to_string());
+        assert_eq!(token.bearer(), Some(token_str));
     }
 }
 
 #[test]
 fn test_api_token_edge_cases() {
     // Test boundary conditions
-    let single_char = ApiToken("a".to_string());
-    assert_eq!(single_char.0, "a");
+    let single_char = ApiToken::Token("a".to_string());
+    assert_eq!(single_char.bearer(), Some("a"));
 
-    let long_token = ApiToken("a".repeat(1000));
-    assert_eq!(long_token.0.len(), 1000);
+    let long_token = ApiToken::Token("a".repeat(1000));
+    assert_eq!(long_token.bearer().map(str::len), Some(1000));
 
     // Test unicode characters
-    let unicode_token = ApiToken("token_🔑_unicode".to_string());
-    assert_eq!(unicode_token.0, "token_🔑_unicode");
+    let unicode_token = ApiToken::Token("token_🔑_unicode".to_string());
+    assert_eq!(unicode_token.bearer(), Some("token_🔑_unicode"));
 }
 
 #[test]
 fn test_api_token_memory_usage() {
     // Test that tokens don't share memory inappropriately
-    let token1 = ApiToken("token1".to_string());
-    let token2 = ApiToken("token2".to_string());
+    let token1 = ApiToken::Token("token1".to_string());
+    let token2 = ApiToken::Token("token2".to_string());
 
-    assert_ne!(token1.0, token2.0);
+    assert_ne!(token1, token2);
 
     // Modify one and ensure the other isn't affected
-    let mut token3 = ApiToken("mutable".to_string());
-    token3.0.push_str("_modified");
-    assert_eq!(token3.0, "mutable_modified");
+    let mut token3 = ApiToken::Token("mutable".to_string());
+    if let ApiToken::Token(token) = &mut token3 {
+        token.push_str("_modified");
+    }
+    assert_eq!(token3.bearer(), Some("mutable_modified"));
 }
 
 #[test]
 fn test_api_token_clone_behavior() {
-    let original = ApiToken("original_token".to_string());
-    let cloned = ApiToken(original.0.clone());
+    let original = ApiToken::Token("original_token".to_string());
+    let cloned = original.clone();
 
-    assert_eq!(original.0, cloned.0);
+    assert_eq!(original, cloned);
 
     // They should have the same content but be separate instances
-    assert_eq!(original.0, "original_token");
-    assert_eq!(cloned.0, "original_token");
+    assert_eq!(original.bearer(), Some("original_token"));
+    assert_eq!(cloned.bearer(), Some("original_token"));
+}
+
+fn scoped_auth() -> AuthConfig {
+    let scoped_tokens = AuthConfig::parse_scoped_tokens(
+        r#"{"faucet_token": ["fund"], "ops_token": ["read", "perp:deploy"]}"#,
+    )
+    .expect("valid scoped tokens JSON");
+    AuthConfig {
+        access_token: "legacy_token".to_string(),
+        admin_token: "admin_token".to_string(),
+        scoped_tokens,
+        request_signing: None,
+    }
+}
+
+#[test]
+fn test_scoped_token_with_insufficient_scope_is_forbidden() {
+    let auth = scoped_auth();
+    let required = required_scope_for_route("deploy_perp_for_beacon_endpoint");
+    assert_eq!(required, Some(TokenScope::PerpDeploy));
+    assert_eq!(
+        authorize_api_token(&auth, "faucet_token", required),
+        Err(Status::Forbidden)
+    );
+}
+
+#[test]
+fn test_scoped_token_with_sufficient_scope_is_allowed() {
+    let auth = scoped_auth();
+    assert_eq!(
+        authorize_api_token(
+            &auth,
+            "faucet_token",
+            required_scope_for_route("fund_guest_wallet")
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        authorize_api_token(
+            &auth,
+            "ops_token",
+            required_scope_for_route("deploy_perp_for_beacon_endpoint")
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_deposit_and_close_routes_require_perp_deposit() {
+    for route in [
+        "deposit_liquidity_for_perp_endpoint",
+        "deposit_liquidity_by_price_endpoint",
+        "batch_deposit_liquidity_for_perps_endpoint",
+        "close_maker_position_endpoint",
+    ] {
+        assert_eq!(
+            required_scope_for_route(route),
+            Some(TokenScope::PerpDeposit)
+        );
+    }
+}
+
+#[test]
+fn test_estimate_gas_requires_read_scope() {
+    assert_eq!(
+        required_scope_for_route("estimate_gas"),
+        Some(TokenScope::Read)
+    );
+}
+
+#[test]
+fn test_batch_beacon_data_requires_read_scope() {
+    assert_eq!(
+        required_scope_for_route("batch_beacon_data"),
+        Some(TokenScope::Read)
+    );
+}
+
+#[test]
+fn test_legacy_token_keeps_full_access() {
+    let auth = scoped_auth();
+    for route in [
+        "fund_guest_wallet",
+        "deploy_perp_for_beacon_endpoint",
+        "update_beacon",
+        "some_unmapped_route",
+    ] {
+        assert_eq!(
+            authorize_api_token(&auth, "legacy_token", required_scope_for_route(route)),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+fn test_unknown_token_is_unauthorized() {
+    let auth = scoped_auth();
+    assert_eq!(
+        authorize_api_token(&auth, "nope", Some(TokenScope::Read)),
+        Err(Status::Unauthorized)
+    );
+}
+
+#[test]
+fn test_scoped_token_denied_on_unmapped_route() {
+    let auth = scoped_auth();
+    assert_eq!(
+        authorize_api_token(&auth, "ops_token", required_scope_for_route("unmapped")),
+        Err(Status::Forbidden)
+    );
+}
+
+#[test]
+fn test_beacon_create_token_refused_on_fund_guest_wallet() {
+    let auth = AuthConfig {
+        scoped_tokens: AuthConfig::parse_scoped_tokens(r#"{"creator_token": ["beacon:create"]}"#)
+            .expect("valid scoped tokens JSON"),
+        ..scoped_auth()
+    };
+    assert_eq!(
+        authorize_api_token(
+            &auth,
+            "creator_token",
+            required_scope_for_route("fund_guest_wallet")
+        ),
+        Err(Status::Forbidden)
+    );
+    assert_eq!(
+        authorize_api_token(
+            &auth,
+            "creator_token",
+            required_scope_for_route("create_beacon")
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_api_token_exposes_granted_scopes() {
+    let auth = scoped_auth();
+    let faucet = ApiToken::Token("faucet_token".to_string());
+    assert_eq!(faucet.scopes(&auth), HashSet::from([TokenScope::Fund]));
+
+    let all = HashSet::from(TokenScope::ALL);
+    assert_eq!(
+        ApiToken::Token("legacy_token".to_string()).scopes(&auth),
+        all
+    );
+    assert_eq!(ApiToken::Signed.scopes(&auth), all);
+    assert_eq!(ApiToken::Signed.bearer(), None);
+    assert!(ApiToken::Token("nope".to_string()).scopes(&auth).is_empty());
+    assert_eq!(granted_scopes(&auth, "nope"), None);
+}
+
+#[test]
+fn test_wallet_fund_scope_accepts_old_name() {
+    let tokens =
+        AuthConfig::parse_scoped_tokens(r#"{"new": ["wallet:fund"], "old": ["fund"]}"#).unwrap();
+    assert_eq!(tokens["new"], HashSet::from([TokenScope::Fund]));
+    assert_eq!(tokens["old"], HashSet::from([TokenScope::Fund]));
+}
+
+#[test]
+fn test_parse_scoped_tokens_rejects_bad_config() {
+    assert!(AuthConfig::parse_scoped_tokens(r#"{"t": ["not-a-scope"]}"#).is_err());
+    assert!(AuthConfig::parse_scoped_tokens(r#"{"t": []}"#).is_err());
+    assert!(AuthConfig::parse_scoped_tokens(r#"{"": ["read"]}"#).is_err());
+    assert!(AuthConfig::parse_scoped_tokens("not json").is_err());
+}
+
+mod api_token_header {
+    use rocket::http::{Header, Status};
+    use rocket::local::asynchronous::Client;
+    use rocket::{get, routes};
+    use the_beaconator::guards::{ApiToken, api_token_from_header};
+
+    #[get("/whoami")]
+    fn whoami(token: ApiToken) -> String {
+        token.bearer().unwrap_or("signed").to_string()
+    }
+
+    async fn client() -> Client {
+        let app_state = crate::test_utils::create_simple_test_app_state().await;
+        let rocket = rocket::build()
+            .manage(app_state)
+            .mount("/", routes![whoami]);
+        Client::tracked(rocket).await.expect("valid rocket")
+    }
+
+    async fn call(client: &Client, authorization: Option<&str>) -> (Status, String) {
+        let mut request = client.get("/whoami");
+        if let Some(value) = authorization {
+            request = request.header(Header::new("Authorization", value.to_string()));
+        }
+        let response = request.dispatch().await;
+        let status = response.status();
+        (status, response.into_string().await.unwrap_or_default())
+    }
+
+    #[test]
+    fn test_api_token_from_header_forms() {
+        assert_eq!(api_token_from_header("Bearer abc"), "abc");
+        assert_eq!(api_token_from_header("bearer abc"), "abc");
+        assert_eq!(api_token_from_header("  Bearer   abc  "), "abc");
+        assert_eq!(api_token_from_header("abc"), "abc");
+        assert_eq!(api_token_from_header("Bearer "), "");
+        assert_eq!(api_token_from_header("Basic abc"), "Basic abc");
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token_accepted() {
+        let client = client().await;
+        assert_eq!(
+            call(&client, Some("Bearer test_token")).await,
+            (Status::Ok, "test_token".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_raw_token_accepted() {
+        let client = client().await;
+        assert_eq!(
+            call(&client, Some("test_token")).await,
+            (Status::Ok, "test_token".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wrong_token_unauthorized() {
+        let client = client().await;
+        assert_eq!(
+            call(&client, Some("Bearer wrong_token")).await.0,
+            Status::Unauthorized
+        );
+        assert_eq!(
+            call(&client, Some("wrong_token")).await.0,
+            Status::Unauthorized
+        );
+        // A prefix of the real token must not pass.
+        assert_eq!(call(&client, Some("test_")).await.0, Status::Unauthorized);
+        assert_eq!(call(&client, Some("Bearer ")).await.0, Status::Unauthorized);
+    }
+
+    #[tokio::test]
+    async fn test_missing_header_unauthorized() {
+        let client = client().await;
+        assert_eq!(call(&client, None).await.0, Status::Unauthorized);
+    }
+}
+
+mod admin_token_guard {
+    use rocket::http::{Header, Status};
+    use rocket::local::asynchronous::Client;
+    use rocket::{get, routes};
+    use the_beaconator::api_routes_and_spec;
+    use the_beaconator::guards::{AdminToken, ApiToken};
+
+    #[get("/admin_only")]
+    fn admin_only(token: AdminToken) -> String {
+        token.0
+    }
+
+    #[get("/api_only")]
+    fn api_only(token: ApiToken) -> String {
+        token.bearer().unwrap_or_default().to_string()
+    }
+
+    async fn client() -> Client {
+        let app_state = crate::test_utils::create_simple_test_app_state().await;
+        let rocket = rocket::build()
+            .manage(app_state)
+            .mount("/", routes![admin_only, api_only]);
+        Client::tracked(rocket).await.expect("valid rocket")
+    }
+
+    async fn status(client: &Client, path: &str, authorization: &str) -> Status {
+        client
+            .get(path)
+            .header(Header::new("Authorization", authorization.to_string()))
+            .dispatch()
+            .await
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_access_token_rejected_by_admin_guard() {
+        let client = client().await;
+        assert_eq!(
+            status(&client, "/admin_only", "Bearer test_token").await,
+            Status::Unauthorized
+        );
+        assert_eq!(
+            status(&client, "/admin_only", "Bearer test_admin_token").await,
+            Status::Ok
+        );
+    }
+
+    #[tokio::test]
+    async fn test_admin_token_rejected_by_api_guard() {
+        let client = client().await;
+        assert_eq!(
+            status(&client, "/api_only", "Bearer test_admin_token").await,
+            Status::Unauthorized
+        );
+    }
+
+    #[test]
+    fn test_privileged_routes_document_the_admin_credential() {
+        let (_, spec) = api_routes_and_spec();
+        for (path, operation) in [
+            ("/sweep_wallet", spec.paths["/sweep_wallet"].post.as_ref()),
+            ("/top_up_pool", spec.paths["/top_up_pool"].post.as_ref()),
+            (
+                "/admin/config_snapshot",
+                spec.paths["/admin/config_snapshot"].get.as_ref(),
+            ),
+        ] {
+            let security = operation
+                .and_then(|op| op.security.as_ref())
+                .unwrap_or_else(|| panic!("{path} has no security requirement"));
+            assert!(
+                security
+                    .iter()
+                    .all(|req| req.contains_key("adminBearerAuth")
+                        && !req.contains_key("bearerAuth")),
+                "{path} should require adminBearerAuth only"
+            );
+        }
+    }
+}
+
+mod scoped_routes {
+    use rocket::http::{ContentType, Header, Status};
+    use rocket::local::asynchronous::Client;
+    use serde_json::json;
+    use the_beaconator::models::AuthConfig;
+
+    #[tokio::test]
+    async fn test_beacon_create_token_gets_403_from_fund_guest_wallet() {
+        let mut app_state = crate::test_utils::create_simple_test_app_state().await;
+        app_state.auth.scoped_tokens =
+            AuthConfig::parse_scoped_tokens(r#"{"creator_token": ["beacon:create"]}"#).unwrap();
+        let client = Client::tracked(the_beaconator::mock_rocket(app_state))
+            .await
+            .expect("valid rocket");
+
+        let response = client
+            .post("/fund_guest_wallet")
+            .header(ContentType::JSON)
+            .header(Header::new("Authorization", "Bearer creator_token"))
+            .body(
+                json!({
+                    "wallet_address": "0x1111111111111111111111111111111111111111",
+                    "usdc_amount": "1000000",
+                    "eth_amount": "1000000000000000"
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+}
+
+mod signed_requests {
+    use rocket::http::{Header, Status};
+    use rocket::local::asynchronous::Client;
+    use rocket::{get, post, routes};
+    use serde_json::{Value, json};
+    use the_beaconator::guards::{ApiToken, SignedJson, sign_request, verify_request_signature};
+    use the_beaconator::models::RequestSigning;
+
+    const SECRET: &str = "0123456789abcdef0123456789abcdef";
+
+    fn signing() -> RequestSigning {
+        RequestSigning {
+            secret: SECRET.to_string(),
+            max_age_secs: 300,
+        }
+    }
+
+    fn now() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[get("/whoami")]
+    fn whoami(token: ApiToken) -> String {
+        token.bearer().unwrap_or("signed").to_string()
+    }
+
+    #[post("/echo", data = "<body>")]
+    fn echo(body: SignedJson<Value>, token: ApiToken) -> String {
+        format!("{}:{}", token.bearer().unwrap_or("signed"), body["value"])
+    }
+
+    async fn client() -> Client {
+        let mut app_state = crate::test_utils::create_simple_test_app_state().await;
+        app_state.auth.request_signing = Some(signing());
+        let rocket = rocket::build()
+            .manage(app_state)
+            .mount("/", routes![whoami, echo]);
+        Client::tracked(rocket).await.expect("valid rocket")
+    }
+
+    async fn post_signed(client: &Client, timestamp: u64, signed: &str, sent: &str) -> Status {
+        client
+            .post("/echo")
+            .header(Header::new("X-Timestamp", timestamp.to_string()))
+            .header(Header::new(
+                "X-Signature",
+                sign_request(SECRET, timestamp, "POST", "/echo", signed.as_bytes()),
+            ))
+            .body(sent)
+            .dispatch()
+            .await
+            .status()
+    }
+
+    #[test]
+    fn test_verify_request_signature() {
+        let now = 1_700_000_000;
+        let signature = sign_request(SECRET, now, "POST", "/update_beacon", b"{}");
+        let verify = |timestamp: &str, signature: &str, body: &[u8]| {
+            verify_request_signature(
+                &signing(),
+                timestamp,
+                signature,
+                "POST",
+                "/update_beacon",
+                body,
+                now,
+            )
+        };
+
+        assert_eq!(verify(&now.to_string(), &signature, b"{}"), Ok(()));
+        assert_eq!(
+            verify(&now.to_string(), &signature, b"{ }").unwrap_err(),
+            "Request signature does not match"
+        );
+        assert_eq!(
+            verify(&(now - 301).to_string(), &signature, b"{}").unwrap_err(),
+            "X-Timestamp is more than 300s from the server clock"
+        );
+        assert!(verify("yesterday", &signature, b"{}").is_err());
+        assert!(verify(&now.to_string(), "not-hex", b"{}").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_signed_post_accepted() {
+        let client = client().await;
+        let body = json!({"value": 7}).to_string();
+        let timestamp = now();
+        let response = client
+            .post("/echo")
+            .header(Header::new("X-Timestamp", timestamp.to_string()))
+            .header(Header::new(
+                "X-Signature",
+                sign_request(SECRET, timestamp, "POST", "/echo", body.as_bytes()),
+            ))
+            .body(body)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().await.unwrap(), "signed:7");
+    }
+
+    #[tokio::test]
+    async fn test_tampered_body_unauthorized() {
+        let client = client().await;
+        let signed = json!({"value": 7}).to_string();
+        let sent = json!({"value": 8}).to_string();
+        assert_eq!(
+            post_signed(&client, now(), &signed, &sent).await,
+            Status::Unauthorized
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_timestamp_unauthorized() {
+        let client = client().await;
+        let body = json!({"value": 7}).to_string();
+        assert_eq!(
+            post_signed(&client, now() - 600, &body, &body).await,
+            Status::Unauthorized
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signed_get_covers_the_query() {
+        let client = client().await;
+        let timestamp = now();
+        let signature = sign_request(SECRET, timestamp, "GET", "/whoami?page=1", b"");
+        let get = |path: &'static str| {
+            client
+                .get(path)
+                .header(Header::new("X-Timestamp", timestamp.to_string()))
+                .header(Header::new("X-Signature", signature.clone()))
+                .dispatch()
+        };
+        assert_eq!(get("/whoami?page=1").await.status(), Status::Ok);
+        assert_eq!(get("/whoami?page=2").await.status(), Status::Unauthorized);
+    }
+
+    #[tokio::test]
+    async fn test_token_still_accepted_with_signing_enabled() {
+        let client = client().await;
+        let response = client
+            .post("/echo")
+            .header(Header::new("Authorization", "Bearer test_token"))
+            .body(json!({"value": 1}).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().await.unwrap(), "test_token:1");
+    }
 }
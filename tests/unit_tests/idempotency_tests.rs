@@ -0,0 +1,239 @@
+// Idempotency-Key tests: the replay store and the /batch_create_beacon cache-hit path.
+
+use crate::test_utils::create_simple_test_app_state;
+use rocket::State;
+use rocket::http::Status;
+use serial_test::serial;
+use std::time::Duration;
+use the_beaconator::guards::{ApiToken, IdempotencyKey, SignedJson, validate_idempotency_key};
+use the_beaconator::models::{BatchCreateBeaconByTypeRequest, BatchCreateBeaconResponse};
+use the_beaconator::routes::beacon::batch_create_beacon;
+use the_beaconator::services::idempotency::{IdempotencyStore, Reservation};
+
+fn sample_response() -> BatchCreateBeaconResponse {
+    BatchCreateBeaconResponse {
+        beacon_type: "identity".to_string(),
+        created_count: 2,
+        beacon_addresses: vec![
+            "0x1111111111111111111111111111111111111111".to_string(),
+            "0x2222222222222222222222222222222222222222".to_string(),
+        ],
+        failed_count: 0,
+        errors: vec![],
+    }
+}
+
+fn batch_request(count: u32) -> SignedJson<BatchCreateBeaconByTypeRequest> {
+    SignedJson(BatchCreateBeaconByTypeRequest {
+        beacon_type: "identity".to_string(),
+        count,
+        params: None,
+    })
+}
+
+#[tokio::test]
+async fn test_store_returns_cached_value() {
+    let store = IdempotencyStore::new(Duration::from_secs(60), 10);
+    let key = IdempotencyStore::<BatchCreateBeaconResponse>::scoped_key(Some("token"), "abc");
+    store.insert(key.clone(), sample_response()).await;
+
+    let cached = store.get(&key).await.expect("cache hit");
+    assert_eq!(cached.created_count, 2);
+    assert_eq!(cached.beacon_addresses.len(), 2);
+}
+
+#[tokio::test]
+async fn test_store_keys_are_scoped_per_token() {
+    let a = IdempotencyStore::<BatchCreateBeaconResponse>::scoped_key(Some("token_a"), "abc");
+    let b = IdempotencyStore::<BatchCreateBeaconResponse>::scoped_key(Some("token_b"), "abc");
+    assert_ne!(a, b);
+    // The raw token never appears in the cache key.
+    assert!(!a.contains("token_a"));
+}
+
+#[tokio::test]
+async fn test_signed_requests_have_their_own_key_namespace() {
+    let signed = IdempotencyStore::<BatchCreateBeaconResponse>::scoped_key(None, "abc");
+    for token in ["signed", "signed-request", ""] {
+        assert_ne!(
+            signed,
+            IdempotencyStore::<BatchCreateBeaconResponse>::scoped_key(Some(token), "abc")
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_store_expires_entries() {
+    let store = IdempotencyStore::new(Duration::from_millis(20), 10);
+    store.insert("k".to_string(), sample_response()).await;
+    tokio::time::sleep(Duration::from_millis(40)).await;
+    assert!(store.get("k").await.is_none());
+    assert!(store.is_empty().await);
+}
+
+#[tokio::test]
+async fn test_store_evicts_oldest_at_capacity() {
+    let store = IdempotencyStore::new(Duration::from_secs(60), 2);
+    store.insert("first".to_string(), sample_response()).await;
+    tokio::time::sleep(Duration::from_millis(2)).await;
+    store.insert("second".to_string(), sample_response()).await;
+    tokio::time::sleep(Duration::from_millis(2)).await;
+    store.insert("third".to_string(), sample_response()).await;
+
+    assert_eq!(store.len().await, 2);
+    assert!(store.get("first").await.is_none());
+    assert!(store.get("second").await.is_some());
+    assert!(store.get("third").await.is_some());
+}
+
+#[tokio::test]
+async fn test_reserve_claims_key_until_stored_or_released() {
+    let store = IdempotencyStore::new(Duration::from_secs(60), 10);
+    assert!(matches!(
+        store.reserve("k").await,
+        Ok(Reservation::Reserved)
+    ));
+    assert!(matches!(
+        store.reserve("k").await,
+        Ok(Reservation::InFlight)
+    ));
+    assert!(store.get("k").await.is_none(), "a claim is not a result");
+
+    store.release("k").await;
+    assert!(matches!(
+        store.reserve("k").await,
+        Ok(Reservation::Reserved)
+    ));
+
+    store.insert("k".to_string(), sample_response()).await;
+    match store.reserve("k").await {
+        Ok(Reservation::Completed(cached)) => assert_eq!(cached.created_count, 2),
+        other => panic!("expected the stored result, got {other:?}"),
+    }
+    // Releasing a finished key keeps its result.
+    store.release("k").await;
+    assert!(store.get("k").await.is_some());
+}
+
+#[tokio::test]
+async fn test_unreleased_claim_expires() {
+    let store = IdempotencyStore::<BatchCreateBeaconResponse>::new(Duration::from_millis(20), 10);
+    assert!(matches!(
+        store.reserve("k").await,
+        Ok(Reservation::Reserved)
+    ));
+    tokio::time::sleep(Duration::from_millis(40)).await;
+    assert!(matches!(
+        store.reserve("k").await,
+        Ok(Reservation::Reserved)
+    ));
+}
+
+#[test]
+fn test_validate_idempotency_key() {
+    assert!(validate_idempotency_key("retry-2026-10-17-abc").is_ok());
+    assert!(validate_idempotency_key("").is_err());
+    assert!(validate_idempotency_key(&"a".repeat(256)).is_err());
+    assert!(validate_idempotency_key("has space").is_err());
+}
+
+#[tokio::test]
+async fn test_batch_create_replays_cached_result() {
+    let app_state = create_simple_test_app_state().await;
+    let key =
+        IdempotencyStore::<BatchCreateBeaconResponse>::scoped_key(Some("test_token"), "retry-1");
+    app_state.idempotency.insert(key, sample_response()).await;
+
+    // The test registry is a stub with no Redis: reaching it would fail, so a success here
+    // proves the cached result was replayed without re-running creation.
+    let result = batch_create_beacon(
+        batch_request(2),
+        ApiToken::Token("test_token".to_string()),
+        IdempotencyKey(Some("retry-1".to_string())),
+        State::from(&app_state),
+    )
+    .await
+    .expect("cache hit should succeed");
+
+    let response = result.into_inner();
+    assert!(response.success);
+    let data = response.data.expect("cached data");
+    assert_eq!(data.created_count, 2);
+    assert_eq!(data.beacon_addresses, sample_response().beacon_addresses);
+}
+
+#[tokio::test]
+async fn test_batch_create_cache_is_scoped_to_token() {
+    let app_state = create_simple_test_app_state().await;
+    let key =
+        IdempotencyStore::<BatchCreateBeaconResponse>::scoped_key(Some("test_token"), "retry-1");
+    app_state.idempotency.insert(key, sample_response()).await;
+
+    // Same key from a different token misses the cache and falls through to the registry.
+    let result = batch_create_beacon(
+        batch_request(2),
+        ApiToken::Token("other_token".to_string()),
+        IdempotencyKey(Some("retry-1".to_string())),
+        State::from(&app_state),
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), Status::InternalServerError);
+}
+
+#[tokio::test]
+async fn test_batch_create_in_flight_key_conflicts() {
+    let app_state = create_simple_test_app_state().await;
+    let key =
+        IdempotencyStore::<BatchCreateBeaconResponse>::scoped_key(Some("test_token"), "retry-1");
+    assert!(matches!(
+        app_state.idempotency.reserve(&key).await,
+        Ok(Reservation::Reserved)
+    ));
+
+    let result = batch_create_beacon(
+        batch_request(2),
+        ApiToken::Token("test_token".to_string()),
+        IdempotencyKey(Some("retry-1".to_string())),
+        State::from(&app_state),
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), Status::Conflict);
+}
+
+#[tokio::test]
+async fn test_batch_create_failure_releases_key() {
+    let app_state = create_simple_test_app_state().await;
+
+    // The registry lookup fails before anything is deployed.
+    let result = batch_create_beacon(
+        batch_request(2),
+        ApiToken::Token("test_token".to_string()),
+        IdempotencyKey(Some("retry-1".to_string())),
+        State::from(&app_state),
+    )
+    .await;
+    assert_eq!(result.unwrap_err(), Status::InternalServerError);
+
+    let key =
+        IdempotencyStore::<BatchCreateBeaconResponse>::scoped_key(Some("test_token"), "retry-1");
+    assert!(matches!(
+        app_state.idempotency.reserve(&key).await,
+        Ok(Reservation::Reserved)
+    ));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_batch_create_rejects_invalid_count() {
+    let app_state = create_simple_test_app_state().await;
+    for count in [0, 101] {
+        let result = batch_create_beacon(
+            batch_request(count),
+            ApiToken::Token("test_token".to_string()),
+            IdempotencyKey(None),
+            State::from(&app_state),
+        )
+        .await;
+        assert_eq!(result.unwrap_err(), Status::BadRequest);
+    }
+}
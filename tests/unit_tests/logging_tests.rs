@@ -0,0 +1,39 @@
+// Tests for LOG_FORMAT selection (src/logging.rs)
+
+use the_beaconator::logging::LogFormat;
+
+#[test]
+fn test_log_format_defaults_to_pretty() {
+    assert_eq!(LogFormat::parse(None), LogFormat::Pretty);
+    assert_eq!(LogFormat::parse(Some("")), LogFormat::Pretty);
+    assert_eq!(LogFormat::default(), LogFormat::Pretty);
+}
+
+#[test]
+fn test_log_format_selects_json_and_compact() {
+    assert_eq!(LogFormat::parse(Some("json")), LogFormat::Json);
+    assert_eq!(LogFormat::parse(Some(" JSON ")), LogFormat::Json);
+    assert_eq!(LogFormat::parse(Some("compact")), LogFormat::Compact);
+    assert_eq!(LogFormat::parse(Some("pretty")), LogFormat::Pretty);
+}
+
+#[test]
+fn test_unknown_log_format_falls_back_to_pretty() {
+    assert_eq!(LogFormat::parse(Some("xml")), LogFormat::Pretty);
+}
+
+#[test]
+fn test_log_format_from_env() {
+    // Only this test touches LOG_FORMAT.
+    unsafe { std::env::set_var("LOG_FORMAT", "json") };
+    assert_eq!(LogFormat::from_env(), LogFormat::Json);
+    unsafe { std::env::remove_var("LOG_FORMAT") };
+    assert_eq!(LogFormat::from_env(), LogFormat::Pretty);
+}
+
+#[test]
+fn test_log_format_round_trips_through_as_str() {
+    for format in [LogFormat::Pretty, LogFormat::Compact, LogFormat::Json] {
+        assert_eq!(LogFormat::parse(Some(format.as_str())), format);
+    }
+}
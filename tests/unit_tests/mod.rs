@@ -1,16 +1,46 @@
 // Unit tests module
 
+pub mod abis_tests;
+pub mod batch_create_tests;
 pub mod beacon_tests;
+pub mod config_tests;
+pub mod deposit_validation_tests;
+pub mod estimate_gas_tests;
 pub mod fairings_simple_tests;
+pub mod gas_metrics_tests;
+pub mod gas_mode_tests;
 pub mod guards_simple_tests;
+pub mod idempotency_tests;
 pub mod info_tests;
-// pub mod perp_operations_tests; // Temporarily disabled during PerpManager refactor
-// pub mod perp_route_tests; // Temporarily disabled during PerpManager refactor
+pub mod logging_tests;
+pub mod openapi_schema_tests;
+pub mod perp_config_tests;
 pub mod register_beacon_route_tests;
+pub mod rpc_breaker_tests;
+pub mod rpc_retry_tests;
+pub mod server_limits_tests;
+pub mod services_address_tests;
 pub mod services_beacon_core_tests;
+pub mod services_beacon_detect_tests;
+pub mod services_beacon_ecdsa_tests;
+pub mod services_beacon_events_tests;
 pub mod services_beacon_verifiable_tests;
+pub mod services_error_tests;
+pub mod services_gas_price_tests;
+pub mod services_perp_allowance_tests;
+pub mod services_perp_cadence_tests;
+pub mod services_perp_liquidity_tests;
+pub mod services_perp_lookup_tests;
+pub mod services_perp_params_tests;
+pub mod services_perp_slippage_tests;
 pub mod services_perp_validation_tests;
+pub mod services_transaction_confirmations_tests;
 pub mod services_transaction_events_simple_tests;
+pub mod services_transaction_log_scan_tests;
+pub mod services_transaction_multicall_tests;
+pub mod services_transaction_receipts_tests;
+pub mod services_transaction_troubleshooting_tests;
+pub mod telemetry_tests;
 pub mod unregister_beacon_route_tests;
 // pub mod services_transaction_execution_comprehensive_tests; // Removed - nonce management obsolete with WalletManager
 pub mod factory_beacon_tests;
@@ -18,4 +48,5 @@ pub mod modular_beacon_tests;
 pub mod touch_tests;
 pub mod transaction_events_tests;
 pub mod transaction_execution_tests;
+pub mod transaction_history_tests;
 pub mod wallet_route_tests;
@@ -0,0 +1,211 @@
+// Tests for the generated OpenAPI document served at /openapi.json
+
+use serde_json::Value;
+use the_beaconator::api_routes_and_spec;
+use the_beaconator::models::examples;
+use the_beaconator::models::responses::BatchDeployPerpsForBeaconsResponse;
+
+/// The spec exactly as `/openapi.json` serves it, parsed back into JSON.
+fn openapi_json() -> Value {
+    let (_, spec) = api_routes_and_spec();
+    let body = serde_json::to_string(&spec).expect("spec serializes");
+    serde_json::from_str(&body).expect("spec is valid JSON")
+}
+
+fn property<'a>(spec: &'a Value, schema: &str, field: &str) -> &'a Value {
+    let property = &spec["components"]["schemas"][schema]["properties"][field];
+    assert!(
+        property.is_object(),
+        "{schema}.{field} missing from components.schemas"
+    );
+    property
+}
+
+/// Example values, whichever of `example` / `examples` the generator emitted.
+fn examples_of(property: &Value) -> Vec<Value> {
+    match (&property["example"], &property["examples"]) {
+        (Value::Null, Value::Array(values)) => values.clone(),
+        (Value::Null, _) => Vec::new(),
+        (value, _) => vec![value.clone()],
+    }
+}
+
+#[test]
+fn test_request_field_descriptions_are_published() {
+    let spec = openapi_json();
+
+    let perp_address = property(&spec, "DepositLiquidityForPerpRequest", "perp_address");
+    let description = perp_address["description"].as_str().unwrap_or_default();
+    assert!(
+        description.contains("per-market `Perp` contract"),
+        "unexpected description: {description:?}"
+    );
+
+    let margin = property(
+        &spec,
+        "DepositLiquidityForPerpRequest",
+        "margin_amount_usdc",
+    );
+    assert!(
+        margin["description"]
+            .as_str()
+            .is_some_and(|d| d.contains("6 decimals"))
+    );
+}
+
+#[test]
+fn test_response_field_descriptions_are_published() {
+    let spec = openapi_json();
+
+    let mean = property(&spec, "GasOperationHistogram", "mean");
+    assert_eq!(mean["description"], "Mean gas used, rounded down.");
+
+    let status = &spec["components"]["schemas"]["EndpointStatus"];
+    assert!(
+        status.is_object(),
+        "EndpointStatus missing from components.schemas"
+    );
+}
+
+#[test]
+fn test_unrouted_models_still_document_their_fields() {
+    // Batch perp deployment has no route yet, so it is absent from /openapi.json; its
+    // schema must still be documented for when it is mounted.
+    let schema =
+        serde_json::to_value(schemars::schema_for!(BatchDeployPerpsForBeaconsResponse)).unwrap();
+    let description = &schema["properties"]["perp_addresses"]["description"];
+    assert!(
+        description
+            .as_str()
+            .is_some_and(|d| d.contains("Perp contract"))
+    );
+}
+
+#[test]
+fn test_address_and_amount_fields_carry_examples() {
+    let spec = openapi_json();
+
+    for (schema, field) in [
+        ("DepositLiquidityForPerpRequest", "perp_address"),
+        ("DepositLiquidityForPerpRequest", "holder"),
+        ("FundGuestWalletRequest", "wallet_address"),
+        ("RegisterBeaconRequest", "registry_address"),
+        ("DeployPerpForBeaconResponse", "perp_address"),
+    ] {
+        assert_eq!(
+            examples_of(property(&spec, schema, field)),
+            vec![Value::from(examples::address())],
+            "{schema}.{field}"
+        );
+    }
+
+    assert_eq!(
+        examples_of(property(&spec, "FundGuestWalletRequest", "usdc_amount")),
+        vec![Value::from(examples::usdc_amount())]
+    );
+    assert_eq!(
+        examples_of(property(&spec, "FundGuestWalletRequest", "eth_amount")),
+        vec![Value::from(examples::eth_amount_wei())]
+    );
+    assert_eq!(
+        examples_of(property(
+            &spec,
+            "DeployPerpForBeaconResponse",
+            "transaction_hash"
+        )),
+        vec![Value::from(examples::transaction_hash())]
+    );
+}
+
+#[test]
+fn test_spec_and_routes_cover_the_same_endpoints() {
+    let (routes, spec) = api_routes_and_spec();
+    let paths = spec.paths.len();
+    assert!(paths > 0);
+    // Several routes can share a path (different methods), never the reverse.
+    assert!(routes.len() >= paths);
+    assert!(spec.paths.contains_key("/deposit_liquidity_for_perp"));
+}
+
+mod swagger_ui {
+    use rocket::http::{ContentType, Status};
+    use rocket::local::asynchronous::Client;
+    use the_beaconator::{api_docs_enabled, api_docs_routes, api_routes_and_spec};
+
+    #[test]
+    fn test_api_docs_toggle_defaults_off() {
+        assert!(!api_docs_enabled(None));
+        assert!(!api_docs_enabled(Some("")));
+        assert!(!api_docs_enabled(Some("false")));
+        assert!(!api_docs_enabled(Some("0")));
+        assert!(api_docs_enabled(Some("true")));
+        assert!(api_docs_enabled(Some(" ON ")));
+        assert!(api_docs_enabled(Some("1")));
+    }
+
+    #[tokio::test]
+    async fn test_docs_page_loads_the_served_spec() {
+        let rocket = rocket::build().mount("/", api_docs_routes());
+        let client = Client::tracked(rocket).await.expect("valid rocket");
+
+        let response = client.get("/docs").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+        let body = response.into_string().await.unwrap();
+        assert!(body.contains(r#"url: "/openapi.json""#));
+        assert!(body.contains("persistAuthorization: true"));
+    }
+
+    #[tokio::test]
+    async fn test_docs_assets_are_served_locally() {
+        let rocket = rocket::build().mount("/", api_docs_routes());
+        let client = Client::tracked(rocket).await.expect("valid rocket");
+
+        let body = client
+            .get("/docs")
+            .dispatch()
+            .await
+            .into_string()
+            .await
+            .unwrap();
+        assert!(!body.contains("https://"), "page loads third-party assets");
+
+        for (path, content_type) in [
+            ("/docs/swagger-ui.css", ContentType::CSS),
+            ("/docs/swagger-ui-bundle.js", ContentType::JavaScript),
+        ] {
+            let response = client.get(path).dispatch().await;
+            assert_eq!(response.status(), Status::Ok, "{path}");
+            assert_eq!(response.content_type(), Some(content_type), "{path}");
+            assert!(body.contains(path), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_docs_not_part_of_the_api_routes() {
+        // /docs is mounted separately, only when API_DOCS_ENABLED is on.
+        let (routes, spec) = api_routes_and_spec();
+        assert!(routes.iter().all(|route| route.uri.path() != "/docs"));
+        assert!(!spec.paths.contains_key("/docs"));
+    }
+
+    #[test]
+    fn test_protected_routes_declare_bearer_security() {
+        // Swagger UI only shows the "Authorize" prompt for schemes declared in the spec.
+        let (_, spec) = api_routes_and_spec();
+        let schemes = &spec
+            .components
+            .as_ref()
+            .expect("components")
+            .security_schemes;
+        assert!(schemes.contains_key("bearerAuth"));
+        assert!(schemes.contains_key("adminBearerAuth"));
+
+        let create = spec.paths["/create_beacon"]
+            .post
+            .as_ref()
+            .expect("POST /create_beacon");
+        let security = create.security.as_ref().expect("security requirement");
+        assert!(security.iter().any(|req| req.contains_key("bearerAuth")));
+    }
+}
@@ -0,0 +1,198 @@
+// Unit tests for PerpConfig (src/models/perp_config.rs).
+
+use serial_test::serial;
+use the_beaconator::models::PerpConfig;
+use the_beaconator::models::perp_config::{DEFAULT_MAX_MARGIN_USDC, DEFAULT_MIN_MARGIN_USDC};
+
+const PERP_VARS: [&str; 6] = [
+    "PERP_TICK_SPACING",
+    "PERP_TICK_LOWER",
+    "PERP_TICK_UPPER",
+    "PERP_MIN_MARGIN_USDC",
+    "PERP_MAX_MARGIN_USDC",
+    "PERP_APPROVE_MAX",
+];
+
+fn clear_perp_env() {
+    // SAFETY: #[serial] guarantees no concurrent env access from other tests.
+    unsafe {
+        for key in PERP_VARS {
+            std::env::remove_var(key);
+        }
+    }
+}
+
+fn set_env(key: &str, value: &str) {
+    // SAFETY: #[serial] guarantees no concurrent env access from other tests.
+    unsafe {
+        std::env::set_var(key, value);
+    }
+}
+
+#[test]
+#[serial]
+fn test_from_env_defaults() {
+    clear_perp_env();
+    let config = PerpConfig::from_env().unwrap();
+    assert_eq!(config, PerpConfig::default());
+    assert_eq!(config.tick_spacing, 30);
+    assert_eq!(config.tick_lower, 24390);
+    assert_eq!(config.tick_upper, 53850);
+    assert_eq!(config.min_margin_usdc, DEFAULT_MIN_MARGIN_USDC);
+    assert_eq!(config.max_margin_usdc, DEFAULT_MAX_MARGIN_USDC);
+}
+
+#[test]
+#[serial]
+fn test_from_env_overrides() {
+    clear_perp_env();
+    set_env("PERP_TICK_SPACING", "60");
+    set_env("PERP_TICK_LOWER", "-600");
+    set_env("PERP_TICK_UPPER", " 1200 ");
+    set_env("PERP_MIN_MARGIN_USDC", "1000000");
+    set_env("PERP_MAX_MARGIN_USDC", "5000000000");
+    set_env("PERP_APPROVE_MAX", "true");
+
+    let config = PerpConfig::from_env().unwrap();
+    clear_perp_env();
+
+    assert_eq!(
+        config,
+        PerpConfig {
+            tick_spacing: 60,
+            tick_lower: -600,
+            tick_upper: 1200,
+            min_margin_usdc: 1_000_000,
+            max_margin_usdc: 5_000_000_000,
+            approve_max: true,
+        }
+    );
+}
+
+#[test]
+#[serial]
+fn test_from_env_rejects_min_above_max() {
+    clear_perp_env();
+    set_env("PERP_MIN_MARGIN_USDC", "2000000");
+    set_env("PERP_MAX_MARGIN_USDC", "1000000");
+
+    let err = PerpConfig::from_env().unwrap_err();
+    clear_perp_env();
+
+    assert!(
+        err.contains("must not exceed PERP_MAX_MARGIN_USDC"),
+        "got {err}"
+    );
+}
+
+#[test]
+#[serial]
+fn test_from_env_rejects_misaligned_ticks() {
+    clear_perp_env();
+    set_env("PERP_TICK_SPACING", "60");
+
+    // The default ticks (24390 / 53850) are aligned to 30, not 60.
+    let err = PerpConfig::from_env().unwrap_err();
+    clear_perp_env();
+
+    assert!(err.contains("Invalid perp tick defaults"), "got {err}");
+}
+
+#[test]
+#[serial]
+fn test_from_env_rejects_unparseable_values() {
+    clear_perp_env();
+    set_env("PERP_MAX_MARGIN_USDC", "lots");
+
+    let err = PerpConfig::from_env().unwrap_err();
+    clear_perp_env();
+
+    assert!(err.contains("Invalid PERP_MAX_MARGIN_USDC"), "got {err}");
+}
+
+#[test]
+fn test_validate_margin() {
+    let config = PerpConfig::default();
+    assert!(config.validate_margin(DEFAULT_MIN_MARGIN_USDC).is_ok());
+    assert!(config.validate_margin(DEFAULT_MAX_MARGIN_USDC).is_ok());
+    assert!(config.validate_margin(DEFAULT_MIN_MARGIN_USDC - 1).is_err());
+    assert!(config.validate_margin(DEFAULT_MAX_MARGIN_USDC + 1).is_err());
+    assert!(config.validate_margin(0).is_err());
+}
+
+#[test]
+fn test_minimum_margin_defaults_to_configured_floor() {
+    let config = PerpConfig::default();
+    assert_eq!(
+        config.calculate_minimum_margin_usdc().unwrap(),
+        DEFAULT_MIN_MARGIN_USDC
+    );
+    assert_eq!(config.minimum_margin_usdc_decimal().unwrap(), "10.000000");
+}
+
+#[test]
+fn test_minimum_margin_covers_nonzero_liquidity() {
+    // With a 1-unit floor, the tick range sets the minimum: over the default
+    // [24390, 53850] range, 12 units is the least margin with non-zero liquidity.
+    let config = PerpConfig {
+        min_margin_usdc: 1,
+        ..PerpConfig::default()
+    };
+    let minimum = config.calculate_minimum_margin_usdc().unwrap();
+    assert_eq!(minimum, 12);
+    assert_eq!(config.minimum_margin_usdc_decimal().unwrap(), "0.000012");
+
+    let liquidity = |margin| {
+        the_beaconator::services::perp::liquidity::calculate_liquidity_from_margin(
+            margin,
+            config.tick_spacing,
+            config.tick_lower,
+            config.tick_upper,
+        )
+    };
+    assert!(liquidity(minimum).is_ok());
+    assert!(liquidity(minimum - 1).is_err());
+}
+
+#[test]
+fn test_check_deposit() {
+    let config = PerpConfig::default();
+    let (spacing, lower, upper) = (config.tick_spacing, config.tick_lower, config.tick_upper);
+
+    let liquidity = config
+        .check_deposit(50_000_000, spacing, lower, upper)
+        .unwrap();
+    assert!(liquidity > 0);
+
+    let err = config.check_deposit(0, spacing, lower, upper).unwrap_err();
+    assert_eq!(err, "margin must be greater than zero");
+
+    let err = config
+        .check_deposit(DEFAULT_MIN_MARGIN_USDC - 1, spacing, lower, upper)
+        .unwrap_err();
+    assert!(err.contains("is outside"), "got {err}");
+
+    let err = config
+        .check_deposit(50_000_000, spacing, upper, lower)
+        .unwrap_err();
+    assert!(err.contains("must be less than tick_upper"), "got {err}");
+}
+
+#[test]
+fn test_usdc_approval_amount() {
+    use alloy::primitives::U256;
+
+    let margin = U256::from(50_000_000u64);
+    let exact = PerpConfig::default();
+    assert!(!exact.approve_max);
+    assert_eq!(exact.usdc_approval(U256::ZERO, margin), Some(margin));
+    assert_eq!(exact.usdc_approval(margin, margin), None);
+
+    let max = PerpConfig {
+        approve_max: true,
+        ..PerpConfig::default()
+    };
+    assert_eq!(max.usdc_approval(U256::ZERO, margin), Some(U256::MAX));
+    assert_eq!(max.usdc_approval(U256::from(1u64), margin), Some(U256::MAX));
+    assert_eq!(max.usdc_approval(U256::MAX, margin), None);
+}
@@ -1,10 +1,9 @@
 use alloy::primitives::Address;
 use rocket::State;
 use rocket::http::Status;
-use rocket::serde::json::Json;
 use std::str::FromStr;
 
-use the_beaconator::guards::ApiToken;
+use the_beaconator::guards::{ApiToken, SignedJson};
 use the_beaconator::models::RegisterBeaconRequest;
 use the_beaconator::routes::beacon::register_beacon;
 
@@ -12,9 +11,9 @@ use the_beaconator::routes::beacon::register_beacon;
 async fn test_register_beacon_invalid_beacon_address() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(RegisterBeaconRequest {
+    let request = SignedJson(RegisterBeaconRequest {
         beacon_address: "invalid_address".to_string(),
         registry_address: "0x1234567890123456789012345678901234567890".to_string(),
     });
@@ -28,9 +27,9 @@ async fn test_register_beacon_invalid_beacon_address() {
 async fn test_register_beacon_invalid_registry_address() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(RegisterBeaconRequest {
+    let request = SignedJson(RegisterBeaconRequest {
         beacon_address: "0x1234567890123456789012345678901234567890".to_string(),
         registry_address: "not_an_address".to_string(),
     });
@@ -44,9 +43,9 @@ async fn test_register_beacon_invalid_registry_address() {
 async fn test_register_beacon_both_addresses_invalid() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(RegisterBeaconRequest {
+    let request = SignedJson(RegisterBeaconRequest {
         beacon_address: "invalid".to_string(),
         registry_address: "also_invalid".to_string(),
     });
@@ -61,9 +60,9 @@ async fn test_register_beacon_zero_beacon_address() {
     let mock_provider = crate::test_utils::create_mock_provider_with_network_error();
     let app_state = crate::test_utils::create_test_app_state_with_provider(mock_provider).await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(RegisterBeaconRequest {
+    let request = SignedJson(RegisterBeaconRequest {
         beacon_address: "0x0000000000000000000000000000000000000000".to_string(),
         registry_address: "0x1234567890123456789012345678901234567890".to_string(),
     });
@@ -79,9 +78,9 @@ async fn test_register_beacon_zero_registry_address() {
     let mock_provider = crate::test_utils::create_mock_provider_with_network_error();
     let app_state = crate::test_utils::create_test_app_state_with_provider(mock_provider).await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(RegisterBeaconRequest {
+    let request = SignedJson(RegisterBeaconRequest {
         beacon_address: "0x1234567890123456789012345678901234567890".to_string(),
         registry_address: "0x0000000000000000000000000000000000000000".to_string(),
     });
@@ -97,9 +96,9 @@ async fn test_register_beacon_network_failure() {
     let mock_provider = crate::test_utils::create_mock_provider_with_network_error();
     let app_state = crate::test_utils::create_test_app_state_with_provider(mock_provider).await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(RegisterBeaconRequest {
+    let request = SignedJson(RegisterBeaconRequest {
         beacon_address: "0x1111111111111111111111111111111111111111".to_string(),
         registry_address: "0x2222222222222222222222222222222222222222".to_string(),
     });
@@ -115,18 +114,26 @@ async fn test_register_beacon_address_case_sensitivity() {
     let mock_provider = crate::test_utils::create_mock_provider_with_network_error();
     let app_state = crate::test_utils::create_test_app_state_with_provider(mock_provider).await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
     // Mixed case addresses (EIP-55 checksummed)
-    let request = Json(RegisterBeaconRequest {
-        beacon_address: "0xAbCdEf1234567890123456789012345678901234".to_string(),
+    let request = SignedJson(RegisterBeaconRequest {
+        beacon_address: "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".to_string(),
         registry_address: "0xFeDcBa9876543210987654321098765432109876".to_string(),
     });
 
-    // Should parse successfully (case insensitive), fail at network level
-    let result = register_beacon(request, token, state).await;
+    // Should parse successfully, fail at network level
+    let result = register_beacon(request, token.clone(), state).await;
     assert!(result.is_err());
     assert_eq!(result.unwrap_err(), Status::InternalServerError);
+
+    // Mixed case with a wrong checksum is refused before any RPC call
+    let request = SignedJson(RegisterBeaconRequest {
+        beacon_address: "0xAbCdEf1234567890123456789012345678901234".to_string(),
+        registry_address: "0xFeDcBa9876543210987654321098765432109876".to_string(),
+    });
+    let result = register_beacon(request, token, state).await;
+    assert_eq!(result.unwrap_err(), Status::BadRequest);
 }
 
 #[tokio::test]
@@ -134,9 +141,9 @@ async fn test_register_beacon_address_without_0x_prefix() {
     let mock_provider = crate::test_utils::create_mock_provider_with_network_error();
     let app_state = crate::test_utils::create_test_app_state_with_provider(mock_provider).await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(RegisterBeaconRequest {
+    let request = SignedJson(RegisterBeaconRequest {
         beacon_address: "1234567890123456789012345678901234567890".to_string(),
         registry_address: "0x1234567890123456789012345678901234567890".to_string(),
     });
@@ -150,9 +157,9 @@ async fn test_register_beacon_address_without_0x_prefix() {
 async fn test_register_beacon_too_short_address() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(RegisterBeaconRequest {
+    let request = SignedJson(RegisterBeaconRequest {
         beacon_address: "0x1234".to_string(),
         registry_address: "0x1234567890123456789012345678901234567890".to_string(),
     });
@@ -166,9 +173,9 @@ async fn test_register_beacon_too_short_address() {
 async fn test_register_beacon_too_long_address() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(RegisterBeaconRequest {
+    let request = SignedJson(RegisterBeaconRequest {
         beacon_address: "0x12345678901234567890123456789012345678901".to_string(), // 41 chars
         registry_address: "0x1234567890123456789012345678901234567890".to_string(),
     });
@@ -216,11 +223,11 @@ async fn test_register_beacon_same_beacon_and_registry() {
     let mock_provider = crate::test_utils::create_mock_provider_with_network_error();
     let app_state = crate::test_utils::create_test_app_state_with_provider(mock_provider).await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
     // Same address for both (edge case)
     let same_address = "0x1234567890123456789012345678901234567890".to_string();
-    let request = Json(RegisterBeaconRequest {
+    let request = SignedJson(RegisterBeaconRequest {
         beacon_address: same_address.clone(),
         registry_address: same_address,
     });
@@ -0,0 +1,347 @@
+// Tests for the per-endpoint RPC circuit breaker (src/services/rpc.rs)
+
+use alloy::providers::{Provider, ProviderBuilder};
+use serial_test::serial;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::Duration;
+use the_beaconator::ReadOnlyProvider;
+use the_beaconator::health_report;
+use the_beaconator::services::rpc::{
+    BreakerState, CircuitBreaker, CircuitBreakerPolicy, DEFAULT_RPC_BREAKER_COOLDOWN_SECS,
+    DEFAULT_RPC_BREAKER_THRESHOLD, RpcEndpoints, probe_read_provider,
+};
+
+const COOLDOWN: Duration = Duration::from_millis(50);
+
+/// The breaker tests never contact these providers: the simulated calls below only compare them.
+fn read_provider(url: &str) -> Arc<ReadOnlyProvider> {
+    Arc::new(ProviderBuilder::new().connect_http(url.parse().unwrap()))
+}
+
+/// Primary (#0) and one alternate (#1), breaking after 2 failures.
+fn endpoints() -> (RpcEndpoints, Arc<ReadOnlyProvider>) {
+    let primary = read_provider("http://127.0.0.1:1");
+    let endpoints = RpcEndpoints::new(
+        vec![
+            "http://127.0.0.1:1".to_string(),
+            "http://127.0.0.1:2".to_string(),
+        ],
+        vec![primary.clone(), read_provider("http://127.0.0.1:2")],
+    )
+    .unwrap()
+    .with_breaker_policy(CircuitBreakerPolicy::new(2, COOLDOWN));
+    (endpoints, primary)
+}
+
+/// A receipt lookup where the primary fails while `primary_down` is set. Returns which
+/// endpoint answered and counts the calls that reached the primary.
+async fn lookup(
+    endpoints: &RpcEndpoints,
+    primary: &Arc<ReadOnlyProvider>,
+    primary_down: &AtomicBool,
+    primary_calls: &AtomicU32,
+) -> Result<&'static str, String> {
+    endpoints
+        .sticky_with_fallback("eth_getTransactionReceipt", |provider| async move {
+            if !Arc::ptr_eq(&provider, primary) {
+                return Ok("alternate");
+            }
+            primary_calls.fetch_add(1, Ordering::SeqCst);
+            if primary_down.load(Ordering::SeqCst) {
+                Err("error sending request: connection refused".to_string())
+            } else {
+                Ok("primary")
+            }
+        })
+        .await
+}
+
+#[test]
+fn test_breaker_opens_after_threshold_and_probes_after_cooldown() {
+    let breaker = CircuitBreaker::new(CircuitBreakerPolicy::new(3, COOLDOWN));
+    assert_eq!(breaker.state(), BreakerState::Closed);
+
+    assert!(!breaker.record_failure());
+    assert!(!breaker.record_failure());
+    assert!(breaker.allow_request());
+    assert!(breaker.record_failure(), "third failure opens the breaker");
+    assert_eq!(breaker.state(), BreakerState::Open);
+    assert!(!breaker.allow_request());
+    assert!(breaker.remaining_cooldown().unwrap() <= COOLDOWN);
+
+    std::thread::sleep(COOLDOWN * 2);
+    assert_eq!(breaker.state(), BreakerState::HalfOpen);
+    assert!(breaker.allow_request(), "first caller gets the probe");
+    assert!(!breaker.allow_request(), "concurrent callers keep skipping");
+
+    // Failed probe: open for another cooldown.
+    assert!(breaker.record_failure());
+    assert_eq!(breaker.state(), BreakerState::Open);
+
+    std::thread::sleep(COOLDOWN * 2);
+    assert!(breaker.allow_request());
+    assert!(
+        breaker.record_success(),
+        "successful probe closes the breaker"
+    );
+    assert_eq!(breaker.state(), BreakerState::Closed);
+    assert_eq!(breaker.consecutive_failures(), 0);
+    assert_eq!(breaker.remaining_cooldown(), None);
+}
+
+#[test]
+fn test_success_resets_failure_streak() {
+    let breaker = CircuitBreaker::new(CircuitBreakerPolicy::new(2, COOLDOWN));
+    breaker.record_failure();
+    assert!(!breaker.record_success(), "breaker was not open");
+    assert!(!breaker.record_failure());
+    assert_eq!(breaker.state(), BreakerState::Closed);
+}
+
+#[test]
+fn test_zero_threshold_disables_breaker() {
+    let breaker = CircuitBreaker::new(CircuitBreakerPolicy::new(0, COOLDOWN));
+    for _ in 0..10 {
+        assert!(!breaker.record_failure());
+    }
+    assert_eq!(breaker.state(), BreakerState::Closed);
+}
+
+#[tokio::test]
+async fn test_primary_down_short_circuits_to_alternate() {
+    let (endpoints, primary) = endpoints();
+    let primary_down = AtomicBool::new(true);
+    let primary_calls = AtomicU32::new(0);
+
+    // Two failures trip the breaker; each lookup still fails over.
+    for _ in 0..2 {
+        let answer = lookup(&endpoints, &primary, &primary_down, &primary_calls).await;
+        assert_eq!(answer.unwrap(), "alternate");
+    }
+    assert_eq!(primary_calls.load(Ordering::SeqCst), 2);
+    assert_eq!(endpoints.breaker(0).state(), BreakerState::Open);
+
+    // While open, lookups go straight to the alternate.
+    for _ in 0..5 {
+        let answer = lookup(&endpoints, &primary, &primary_down, &primary_calls).await;
+        assert_eq!(answer.unwrap(), "alternate");
+    }
+    assert_eq!(primary_calls.load(Ordering::SeqCst), 2);
+    assert_eq!(endpoints.breaker(1).state(), BreakerState::Closed);
+}
+
+#[tokio::test]
+async fn test_primary_recovered_closes_breaker_after_probe() {
+    let (endpoints, primary) = endpoints();
+    let primary_down = AtomicBool::new(true);
+    let primary_calls = AtomicU32::new(0);
+
+    for _ in 0..2 {
+        lookup(&endpoints, &primary, &primary_down, &primary_calls)
+            .await
+            .unwrap();
+    }
+    assert_eq!(endpoints.breaker(0).state(), BreakerState::Open);
+
+    primary_down.store(false, Ordering::SeqCst);
+    tokio::time::sleep(COOLDOWN * 2).await;
+
+    // The first lookup after the cooldown probes the primary and closes the breaker.
+    let answer = lookup(&endpoints, &primary, &primary_down, &primary_calls).await;
+    assert_eq!(answer.unwrap(), "primary");
+    assert_eq!(endpoints.breaker(0).state(), BreakerState::Closed);
+    assert_eq!(primary_calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_failed_probe_keeps_primary_skipped() {
+    let (endpoints, primary) = endpoints();
+    let primary_down = AtomicBool::new(true);
+    let primary_calls = AtomicU32::new(0);
+
+    for _ in 0..2 {
+        lookup(&endpoints, &primary, &primary_down, &primary_calls)
+            .await
+            .unwrap();
+    }
+    tokio::time::sleep(COOLDOWN * 2).await;
+
+    // Probe fails: one call reaches the primary, then it is skipped again.
+    for _ in 0..3 {
+        let answer = lookup(&endpoints, &primary, &primary_down, &primary_calls).await;
+        assert_eq!(answer.unwrap(), "alternate");
+    }
+    assert_eq!(primary_calls.load(Ordering::SeqCst), 3);
+    assert_eq!(endpoints.breaker(0).state(), BreakerState::Open);
+}
+
+#[tokio::test]
+async fn test_open_endpoint_is_still_tried_as_last_resort() {
+    let provider = read_provider("http://127.0.0.1:1");
+    let endpoints = RpcEndpoints::single("http://127.0.0.1:1".to_string(), provider)
+        .with_breaker_policy(CircuitBreakerPolicy::new(1, Duration::from_secs(60)));
+    let calls = &AtomicU32::new(0);
+
+    for _ in 0..3 {
+        let result: Result<(), String> = endpoints
+            .read_with_failover("eth_blockNumber", move |_| async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err("503 Service Unavailable".to_string())
+            })
+            .await;
+        assert!(result.is_err());
+    }
+    assert_eq!(endpoints.breaker(0).state(), BreakerState::Open);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_deterministic_error_does_not_trip_breaker() {
+    let (endpoints, primary) = endpoints();
+    for _ in 0..5 {
+        let result: Result<(), String> = endpoints
+            .sticky_with_fallback("eth_call", |provider| {
+                let is_primary = Arc::ptr_eq(&provider, &primary);
+                async move {
+                    assert!(is_primary, "reverts must not fail over");
+                    Err("execution reverted".to_string())
+                }
+            })
+            .await;
+        assert!(result.is_err());
+    }
+    assert_eq!(endpoints.breaker(0).state(), BreakerState::Closed);
+}
+
+#[tokio::test]
+async fn test_health_report_reflects_breaker_state() {
+    let (endpoints, primary) = endpoints();
+    let report = health_report(&endpoints);
+    assert_eq!(report.status, "ok");
+    assert_eq!(report.rpc_endpoints.len(), 2);
+    assert!(report.read_provider.is_none(), "no RPC without ?rpc=true");
+    assert!(report.rpc_endpoints.iter().all(|e| e.state == "closed"));
+
+    let primary_down = AtomicBool::new(true);
+    let primary_calls = AtomicU32::new(0);
+    for _ in 0..2 {
+        lookup(&endpoints, &primary, &primary_down, &primary_calls)
+            .await
+            .unwrap();
+    }
+
+    let report = health_report(&endpoints);
+    assert_eq!(report.status, "degraded");
+    assert_eq!(report.rpc_endpoints[0].endpoint, 0);
+    assert_eq!(report.rpc_endpoints[0].state, "open");
+    assert_eq!(report.rpc_endpoints[0].consecutive_failures, 2);
+    assert!(report.rpc_endpoints[0].retry_in_ms.is_some());
+    assert_eq!(report.rpc_endpoints[1].state, "closed");
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert!(
+        !json.to_string().contains("127.0.0.1"),
+        "URLs are never exposed"
+    );
+}
+
+#[test]
+#[serial]
+fn test_breaker_policy_from_env() {
+    unsafe {
+        std::env::remove_var("RPC_BREAKER_THRESHOLD");
+        std::env::remove_var("RPC_BREAKER_COOLDOWN_SECS");
+    }
+    assert_eq!(
+        CircuitBreakerPolicy::from_env(),
+        CircuitBreakerPolicy::new(
+            DEFAULT_RPC_BREAKER_THRESHOLD,
+            Duration::from_secs(DEFAULT_RPC_BREAKER_COOLDOWN_SECS)
+        )
+    );
+
+    unsafe {
+        std::env::set_var("RPC_BREAKER_THRESHOLD", "5");
+        std::env::set_var("RPC_BREAKER_COOLDOWN_SECS", "10");
+    }
+    assert_eq!(
+        CircuitBreakerPolicy::from_env(),
+        CircuitBreakerPolicy::new(5, Duration::from_secs(10))
+    );
+
+    unsafe {
+        std::env::set_var("RPC_BREAKER_THRESHOLD", "many");
+        std::env::remove_var("RPC_BREAKER_COOLDOWN_SECS");
+    }
+    assert_eq!(
+        CircuitBreakerPolicy::from_env().failure_threshold,
+        DEFAULT_RPC_BREAKER_THRESHOLD
+    );
+    unsafe {
+        std::env::remove_var("RPC_BREAKER_THRESHOLD");
+    }
+}
+
+#[tokio::test]
+async fn test_read_probe_reports_unreachable_endpoint() {
+    // Nothing listens on port 1, so the read-only provider's eth_blockNumber fails
+    let provider = read_provider("http://127.0.0.1:1");
+    let probe = probe_read_provider(&provider, Duration::from_secs(5)).await;
+
+    assert!(!probe.ok);
+    assert_eq!(probe.block_number, None);
+    assert_eq!(probe.error.as_deref(), Some("request failed"));
+
+    let json = serde_json::to_value(&probe).unwrap();
+    assert!(
+        !json.to_string().contains("127.0.0.1"),
+        "URLs are never exposed"
+    );
+}
+
+#[tokio::test]
+async fn test_send_is_attempted_once_on_first_admitted_endpoint() {
+    let (endpoints, primary) = endpoints();
+    let calls = &AtomicU32::new(0);
+
+    // A failed broadcast is not repeated on the alternate.
+    let result: Result<(), String> = endpoints
+        .send_once("eth_sendRawTransaction", move |_| async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err("error sending request: connection reset".to_string())
+        })
+        .await;
+    assert!(result.is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Once the primary's breaker opens, broadcasts go to the alternate.
+    let _: Result<(), String> = endpoints
+        .send_once("eth_sendRawTransaction", |_| async {
+            Err("error sending request: connection reset".to_string())
+        })
+        .await;
+    assert_eq!(endpoints.breaker(0).state(), BreakerState::Open);
+    let sent_to = endpoints
+        .send_once("eth_sendRawTransaction", |provider| {
+            let is_primary = Arc::ptr_eq(&provider, &primary);
+            async move { Ok::<_, String>(if is_primary { "primary" } else { "alternate" }) }
+        })
+        .await
+        .unwrap();
+    assert_eq!(sent_to, "alternate");
+}
+
+#[tokio::test]
+async fn test_failover_read_provider_feeds_endpoint_breakers() {
+    // Nothing listens on ports 1 and 2: each read tries both endpoints and fails.
+    let (endpoints, _) = endpoints();
+    let endpoints = Arc::new(endpoints);
+    let provider = RpcEndpoints::failover_read_provider(&endpoints);
+
+    for _ in 0..2 {
+        assert!(provider.get_block_number().await.is_err());
+    }
+    assert_eq!(endpoints.breaker(0).state(), BreakerState::Open);
+    assert_eq!(endpoints.breaker(1).state(), BreakerState::Open);
+}
@@ -0,0 +1,172 @@
+// Tests for transient-read retry with backoff (src/services/rpc.rs)
+
+use alloy::providers::Provider;
+use serial_test::serial;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use the_beaconator::config::ConfigReader;
+use the_beaconator::services::rpc::{
+    DEFAULT_RPC_BACKOFF_MS, DEFAULT_RPC_MAX_RETRIES, MAX_RPC_BACKOFF_MS, ReadRetryPolicy,
+    http_status, is_transient_rpc_error, retry_read,
+};
+
+fn fast_policy(max_retries: u32) -> ReadRetryPolicy {
+    ReadRetryPolicy::new(max_retries, 1)
+}
+
+#[test]
+fn test_transient_error_detection() {
+    assert!(is_transient_rpc_error(
+        "HTTP error 429 with body: rate limited"
+    ));
+    assert!(is_transient_rpc_error("503 Service Unavailable"));
+    assert!(is_transient_rpc_error("Gateway Timeout"));
+    assert!(is_transient_rpc_error(
+        "error sending request for url (http://127.0.0.1:1/)"
+    ));
+    assert!(is_transient_rpc_error("Connection refused (os error 111)"));
+    assert!(is_transient_rpc_error("request timed out"));
+
+    assert!(!is_transient_rpc_error("execution reverted: not owner"));
+    assert!(!is_transient_rpc_error("nonce too low"));
+    assert!(!is_transient_rpc_error("could not decode output"));
+}
+
+#[test]
+fn test_status_codes_only_match_where_reported() {
+    assert_eq!(http_status("HTTP error 502 with empty body"), Some(502));
+    assert_eq!(http_status("server returned status code 504"), Some(504));
+    assert_eq!(http_status("unexpected status: 429"), Some(429));
+    assert_eq!(http_status("429 Too Many Requests"), Some(429));
+    assert_eq!(http_status("HTTP error 500 with empty body"), Some(500));
+    assert!(!is_transient_rpc_error("HTTP error 500 with empty body"));
+
+    // Digits that only look like a status are not one.
+    assert_eq!(
+        http_status("insufficient balance: have 4290000 want 5030000"),
+        None
+    );
+    assert!(!is_transient_rpc_error(
+        "insufficient balance: have 4290000 want 5030000"
+    ));
+    assert!(!is_transient_rpc_error(
+        "execution reverted at 0x5030000000000000000000000000000000000429"
+    ));
+    assert!(!is_transient_rpc_error("header not found for block 5029"));
+}
+
+#[test]
+fn test_backoff_grows_exponentially_within_jitter_bounds() {
+    let policy = ReadRetryPolicy::new(5, 100);
+    for (retry, ceiling) in [(0, 100), (1, 200), (2, 400), (3, 800)] {
+        for _ in 0..20 {
+            let delay = policy.backoff(retry).as_millis() as u64;
+            assert!(
+                delay >= ceiling / 2 && delay <= ceiling,
+                "retry {retry}: {delay}ms not in [{}, {ceiling}]",
+                ceiling / 2
+            );
+        }
+    }
+}
+
+#[test]
+fn test_backoff_is_capped() {
+    let policy = ReadRetryPolicy::new(50, 1_000);
+    assert!(policy.backoff(40) <= Duration::from_millis(MAX_RPC_BACKOFF_MS));
+}
+
+#[test]
+#[serial]
+fn test_policy_from_config() {
+    unsafe {
+        std::env::remove_var("RPC_MAX_RETRIES");
+        std::env::remove_var("RPC_BACKOFF_MS");
+    }
+    let mut config = ConfigReader::new();
+    assert_eq!(
+        ReadRetryPolicy::from_config(&mut config),
+        ReadRetryPolicy::new(DEFAULT_RPC_MAX_RETRIES, DEFAULT_RPC_BACKOFF_MS)
+    );
+    assert!(config.finish().is_ok());
+
+    unsafe {
+        std::env::set_var("RPC_MAX_RETRIES", "5");
+        std::env::set_var("RPC_BACKOFF_MS", "not-a-number");
+    }
+    let mut config = ConfigReader::new();
+    assert_eq!(ReadRetryPolicy::from_config(&mut config).max_retries, 5);
+    let err = config.finish().unwrap_err();
+    assert_eq!(err.problems.len(), 1);
+    assert!(err.problems[0].starts_with("Invalid RPC_BACKOFF_MS 'not-a-number'"));
+
+    unsafe {
+        std::env::remove_var("RPC_MAX_RETRIES");
+        std::env::remove_var("RPC_BACKOFF_MS");
+    }
+}
+
+#[tokio::test]
+async fn test_retry_read_gives_up_on_unreachable_rpc() {
+    let provider = crate::test_utils::create_mock_provider_with_network_error();
+    let provider = &*provider;
+    let attempts = &AtomicU32::new(0);
+
+    let result = retry_read(&fast_policy(2), "eth_blockNumber", move || async move {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        provider.get_block_number().await
+    })
+    .await;
+
+    assert!(result.is_err());
+    // First attempt plus two retries.
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_retry_read_recovers_after_transient_failures() {
+    let attempts = &AtomicU32::new(0);
+
+    let result: Result<u64, String> =
+        retry_read(&fast_policy(3), "test_read", move || async move {
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err("HTTP error 429: Too Many Requests".to_string())
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+
+    assert_eq!(result, Ok(42));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_retry_read_does_not_retry_deterministic_errors() {
+    let attempts = &AtomicU32::new(0);
+
+    let result: Result<u64, String> =
+        retry_read(&fast_policy(3), "test_read", move || async move {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err("execution reverted".to_string())
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_zero_retries_means_single_attempt() {
+    let attempts = &AtomicU32::new(0);
+
+    let result: Result<u64, String> =
+        retry_read(&fast_policy(0), "test_read", move || async move {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err("503 Service Unavailable".to_string())
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
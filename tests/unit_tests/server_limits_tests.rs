@@ -0,0 +1,76 @@
+// Request body limit tests for the server configuration in create_rocket
+
+use rocket::data::ToByteUnit;
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket::routes;
+use the_beaconator::routes::beacon::batch_update_beacon;
+use the_beaconator::{MAX_JSON_BODY_BYTES, server_figment};
+
+/// A `/batch_update_beacon` body of `count` updates with `proof_bytes`-byte proofs.
+fn batch_body(count: usize, proof_bytes: usize) -> String {
+    let update = serde_json::json!({
+        "beacon_address": "0x1234567890123456789012345678901234567890",
+        "proof": format!("0x{}", "ab".repeat(proof_bytes)),
+        "public_signals": format!("0x{}", "cd".repeat(1024)),
+    });
+    serde_json::json!({ "updates": vec![update; count] }).to_string()
+}
+
+async fn client() -> Client {
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    let rocket = rocket::custom(server_figment())
+        .manage(app_state)
+        .mount("/", routes![batch_update_beacon]);
+    Client::tracked(rocket).await.expect("valid rocket")
+}
+
+#[test]
+fn test_largest_legitimate_batch_fits_the_limit() {
+    let body = batch_body(100, 8 * 1024);
+    assert!((body.len() as u64) < MAX_JSON_BODY_BYTES);
+}
+
+#[test]
+#[serial_test::serial]
+fn test_operator_limits_survive_the_json_pin() {
+    unsafe {
+        std::env::set_var("ROCKET_LIMITS", "{form=\"64 KiB\", json=\"10 MiB\"}");
+    }
+    let config: rocket::Config = server_figment().extract().expect("valid config");
+    unsafe {
+        std::env::remove_var("ROCKET_LIMITS");
+    }
+
+    assert_eq!(config.limits.get("form"), Some(64.kibibytes()));
+    assert_eq!(config.limits.get("json"), Some(MAX_JSON_BODY_BYTES.bytes()));
+}
+
+#[tokio::test]
+async fn test_oversized_batch_update_is_rejected() {
+    let client = client().await;
+    let body = batch_body(100, 16 * 1024);
+    assert!(body.len() as u64 > MAX_JSON_BODY_BYTES);
+
+    let response = client
+        .post("/batch_update_beacon")
+        .header(Header::new("Authorization", "Bearer test_token"))
+        .header(rocket::http::ContentType::JSON)
+        .body(body)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+}
+
+#[tokio::test]
+async fn test_small_malformed_batch_is_not_treated_as_oversized() {
+    let client = client().await;
+    let response = client
+        .post("/batch_update_beacon")
+        .header(Header::new("Authorization", "Bearer test_token"))
+        .header(rocket::http::ContentType::JSON)
+        .body(r#"{"updates": "not-a-list"}"#)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+}
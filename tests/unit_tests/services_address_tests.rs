@@ -0,0 +1,80 @@
+// Tests for request address parsing (src/services/address.rs)
+
+use the_beaconator::services::address::{normalize_address, parse_address};
+
+// EIP-55 test vector
+const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+const LOWERCASE: &str = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+
+#[test]
+fn test_checksummed_address_accepted_in_both_modes() {
+    let lenient = parse_address(CHECKSUMMED, false).unwrap();
+    let strict = parse_address(CHECKSUMMED, true).unwrap();
+    assert_eq!(lenient, strict);
+    assert_eq!(strict.to_checksum(None), CHECKSUMMED);
+}
+
+#[test]
+fn test_lowercase_address_rejected_only_in_strict_mode() {
+    let address = parse_address(LOWERCASE, false).unwrap();
+    assert_eq!(address.to_checksum(None), CHECKSUMMED);
+
+    let error = parse_address(LOWERCASE, true).unwrap_err();
+    assert_eq!(
+        error,
+        format!("not EIP-55 checksummed (expected {CHECKSUMMED})")
+    );
+}
+
+#[test]
+fn test_uppercase_address_rejected_only_in_strict_mode() {
+    let uppercase = format!("0x{}", LOWERCASE[2..].to_uppercase());
+    let address = parse_address(&uppercase, false).unwrap();
+    assert_eq!(address.to_checksum(None), CHECKSUMMED);
+    assert!(
+        parse_address(&uppercase, true)
+            .unwrap_err()
+            .contains("not EIP-55 checksummed")
+    );
+}
+
+#[test]
+fn test_wrong_checksum_rejected_in_both_modes() {
+    // Valid hex with one letter's case flipped
+    let mistyped = "0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    for strict in [false, true] {
+        assert_eq!(
+            parse_address(mistyped, strict).unwrap_err(),
+            format!("invalid EIP-55 checksum (expected {CHECKSUMMED})")
+        );
+    }
+
+    // Without the 0x prefix the checksum still applies
+    assert!(parse_address(&CHECKSUMMED[2..], true).is_ok());
+    assert!(parse_address(&LOWERCASE[2..], true).is_err());
+}
+
+#[test]
+fn test_invalid_address_rejected_in_both_modes() {
+    for invalid in [
+        "not_an_address",
+        "0x1234",
+        "",
+        "0xZZZeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+    ] {
+        for strict in [false, true] {
+            let error = parse_address(invalid, strict).unwrap_err();
+            assert!(
+                !error.contains("EIP-55"),
+                "'{invalid}' is not an address at all: {error}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_normalize_address() {
+    assert_eq!(normalize_address(LOWERCASE), CHECKSUMMED);
+    assert_eq!(normalize_address(CHECKSUMMED), CHECKSUMMED);
+    assert_eq!(normalize_address("not_an_address"), "not_an_address");
+}
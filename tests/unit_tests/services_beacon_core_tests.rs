@@ -2,8 +2,11 @@ use alloy::primitives::{Address, B256};
 use std::str::FromStr;
 use the_beaconator::models::UpdateBeaconRequest;
 use the_beaconator::services::beacon::core::{
-    is_beacon_registered, is_transaction_confirmed, register_beacon_with_registry, update_beacon,
+    IDENTITY_BEACON_UNAVAILABLE, create_identity_beacon, is_beacon_registered, is_proof_rejection,
+    is_transaction_confirmed, register_beacon_with_registry, update_beacon,
 };
+use the_beaconator::services::error::BeaconError;
+use the_beaconator::services::perp::validation::try_decode_revert_reason;
 
 #[tokio::test]
 async fn test_update_beacon_invalid_address() {
@@ -19,7 +22,10 @@ async fn test_update_beacon_invalid_address() {
 
     let result = update_beacon(&app_state, request).await;
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("Invalid beacon address"));
+    assert!(matches!(
+        result.unwrap_err(),
+        BeaconError::InvalidAddress(msg) if msg == "Invalid beacon address"
+    ));
 }
 
 #[tokio::test]
@@ -74,7 +80,10 @@ async fn test_update_beacon_empty_address() {
 
     let result = update_beacon(&app_state, request).await;
     assert!(result.is_err());
-    assert!(result.unwrap_err().contains("Invalid beacon address"));
+    assert!(matches!(
+        result.unwrap_err(),
+        BeaconError::InvalidAddress(msg) if msg == "Invalid beacon address"
+    ));
 }
 
 #[tokio::test]
@@ -400,3 +409,37 @@ fn test_transaction_hash_edge_cases() {
         assert!(result.is_err(), "Should have failed to parse: {hash_str}");
     }
 }
+
+#[test]
+fn test_is_proof_rejection_for_decoded_verifier_reverts() {
+    let error =
+        "server returned an error response: error code 3: execution reverted, data: \"0xc9838a65\"";
+    let reason = try_decode_revert_reason(&error).unwrap();
+    assert!(is_proof_rejection(&format!(
+        "Failed to send update transaction: {reason}"
+    )));
+
+    let error =
+        "server returned an error response: error code 3: execution reverted, data: \"0x09bde339\"";
+    let reason = try_decode_revert_reason(&error).unwrap();
+    assert!(is_proof_rejection(&reason));
+}
+
+#[test]
+fn test_is_proof_rejection_ignores_infrastructure_errors() {
+    assert!(!is_proof_rejection(
+        "Failed to acquire wallet: no wallets available"
+    ));
+    assert!(!is_proof_rejection(
+        "Timeout waiting for transaction 0xabc receipt"
+    ));
+}
+
+#[tokio::test]
+async fn test_create_identity_beacon_without_bytecode() {
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+
+    let result = create_identity_beacon(&app_state, 100).await;
+
+    assert_eq!(result.unwrap_err(), IDENTITY_BEACON_UNAVAILABLE);
+}
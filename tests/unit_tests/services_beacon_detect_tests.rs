@@ -0,0 +1,123 @@
+use alloy::primitives::Address;
+use the_beaconator::services::beacon::detect::{
+    BeaconUpdateKind, ProbeOutcome, classify_beacon, is_missing_function_error,
+};
+use the_beaconator::services::error::BeaconError;
+
+fn beacon() -> Address {
+    Address::repeat_byte(0xbe)
+}
+
+#[test]
+fn test_classify_ecdsa_beacon() {
+    let kind = classify_beacon(
+        beacon(),
+        ProbeOutcome::Answered,
+        ProbeOutcome::Answered,
+        ProbeOutcome::Missing,
+    );
+    assert_eq!(kind, Ok(BeaconUpdateKind::Ecdsa));
+}
+
+#[test]
+fn test_classify_verifiable_beacon() {
+    let kind = classify_beacon(
+        beacon(),
+        ProbeOutcome::Answered,
+        ProbeOutcome::Missing,
+        ProbeOutcome::Missing,
+    );
+    assert_eq!(kind, Ok(BeaconUpdateKind::Verifiable));
+}
+
+#[test]
+fn test_classify_composite_beacon() {
+    let kind = classify_beacon(
+        beacon(),
+        ProbeOutcome::Missing,
+        ProbeOutcome::Missing,
+        ProbeOutcome::Answered,
+    );
+    assert_eq!(kind, Ok(BeaconUpdateKind::Composite));
+}
+
+#[test]
+fn test_classify_non_beacon() {
+    let err = classify_beacon(
+        beacon(),
+        ProbeOutcome::Missing,
+        ProbeOutcome::Missing,
+        ProbeOutcome::Missing,
+    )
+    .unwrap_err();
+    assert!(matches!(err, BeaconError::NotABeacon(_)));
+}
+
+#[test]
+fn test_classify_propagates_probe_failures() {
+    let failed = || ProbeOutcome::Failed("connection refused".to_string());
+
+    let err = classify_beacon(
+        beacon(),
+        failed(),
+        ProbeOutcome::Missing,
+        ProbeOutcome::Missing,
+    )
+    .unwrap_err();
+    assert!(matches!(err, BeaconError::RpcUnavailable(_)));
+    let message = err.to_string();
+    assert!(message.contains("verifier()") && message.contains("connection refused"));
+
+    let err = classify_beacon(
+        beacon(),
+        ProbeOutcome::Answered,
+        failed(),
+        ProbeOutcome::Missing,
+    )
+    .unwrap_err();
+    assert!(matches!(err, BeaconError::RpcUnavailable(_)));
+    assert!(err.to_string().contains("SIGNER()"));
+
+    let err = classify_beacon(
+        beacon(),
+        ProbeOutcome::Missing,
+        ProbeOutcome::Missing,
+        failed(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, BeaconError::RpcUnavailable(_)));
+    assert!(err.to_string().contains("index()"));
+}
+
+#[test]
+fn test_missing_function_errors() {
+    assert!(is_missing_function_error(
+        "server returned an error response: error code 3: execution reverted"
+    ));
+    assert!(is_missing_function_error(
+        "contract call to `verifier` returned no data (\"0x\"); the called address might not be a contract"
+    ));
+    assert!(!is_missing_function_error("error sending request for url"));
+    assert!(!is_missing_function_error(
+        "HTTP error 429 Too Many Requests"
+    ));
+}
+
+#[test]
+fn test_probe_outcome_from_call_error() {
+    assert_eq!(
+        ProbeOutcome::from_call_error("execution reverted"),
+        ProbeOutcome::Missing
+    );
+    assert_eq!(
+        ProbeOutcome::from_call_error("request timed out"),
+        ProbeOutcome::Failed("request timed out".to_string())
+    );
+}
+
+#[test]
+fn test_only_composite_skips_proof() {
+    assert!(BeaconUpdateKind::Verifiable.takes_proof());
+    assert!(BeaconUpdateKind::Ecdsa.takes_proof());
+    assert!(!BeaconUpdateKind::Composite.takes_proof());
+}
@@ -0,0 +1,72 @@
+use alloy::primitives::{Address, B256, Signature, U256, keccak256};
+use alloy::signers::local::PrivateKeySigner;
+use alloy::sol_types::SolType;
+use std::str::FromStr;
+use the_beaconator::services::beacon::ecdsa::{encode_ecdsa_inputs, sign_ecdsa_proof};
+
+// Anvil's first default account.
+const TEST_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const TEST_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+type InputsType = (
+    alloy::sol_types::sol_data::Array<alloy::sol_types::sol_data::Uint<256>>,
+    alloy::sol_types::sol_data::Uint<256>,
+);
+
+#[tokio::test]
+async fn test_sign_ecdsa_proof_recovers_to_signer() {
+    let signer: PrivateKeySigner = TEST_KEY.parse().unwrap();
+    let digest = keccak256(b"beacon update digest");
+
+    let proof = sign_ecdsa_proof(&signer, digest).await.unwrap();
+
+    assert_eq!(proof.len(), 65);
+    assert!(proof[64] == 27 || proof[64] == 28, "v must be 27 or 28");
+    let signature = Signature::from_raw(&proof).unwrap();
+    let recovered = signature.recover_address_from_prehash(&digest).unwrap();
+    assert_eq!(recovered, signer.address());
+    assert_eq!(recovered, Address::from_str(TEST_ADDRESS).unwrap());
+}
+
+#[tokio::test]
+async fn test_sign_ecdsa_proof_differs_per_digest() {
+    let signer: PrivateKeySigner = TEST_KEY.parse().unwrap();
+
+    let a = sign_ecdsa_proof(&signer, B256::repeat_byte(1))
+        .await
+        .unwrap();
+    let b = sign_ecdsa_proof(&signer, B256::repeat_byte(2))
+        .await
+        .unwrap();
+
+    assert_ne!(a, b);
+    // A signature over one digest must not recover to the signer for another.
+    let signature = Signature::from_raw(&a).unwrap();
+    let recovered = signature
+        .recover_address_from_prehash(&B256::repeat_byte(2))
+        .unwrap();
+    assert_ne!(recovered, signer.address());
+}
+
+#[test]
+fn test_encode_ecdsa_inputs_round_trips() {
+    let measurement = vec![U256::from(100u64), U256::from(200u64)];
+    let nonce = U256::from(1_700_000_000_000_000_000u128);
+
+    let inputs = encode_ecdsa_inputs(&measurement, nonce);
+
+    // head (offset, nonce) + array length + two elements
+    assert_eq!(inputs.len(), 5 * 32);
+    let (decoded_measurement, decoded_nonce) = InputsType::abi_decode_params(&inputs).unwrap();
+    assert_eq!(decoded_measurement, measurement);
+    assert_eq!(decoded_nonce, nonce);
+}
+
+#[test]
+fn test_encode_ecdsa_inputs_single_element() {
+    let inputs = encode_ecdsa_inputs(&[U256::from(42u64)], U256::from(7u64));
+
+    assert_eq!(inputs.len(), 4 * 32);
+    assert_eq!(U256::from_be_slice(&inputs[32..64]), U256::from(7u64));
+    assert_eq!(U256::from_be_slice(&inputs[96..128]), U256::from(42u64));
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use alloy::primitives::{Address, B256, LogData, U256};
+use alloy::rpc::types::Log;
+use alloy::sol_types::SolEvent;
+use the_beaconator::routes::IBeacon;
+use the_beaconator::services::beacon::BeaconEventFeed;
+use the_beaconator::services::beacon::events::{
+    ObservedIndex, decode_index_updated, merge_observations, next_scan_range, parse_beacon_list,
+};
+
+fn beacon() -> Address {
+    Address::repeat_byte(0xbe)
+}
+
+fn observed(index: u64, block_number: u64, log_index: u64) -> ObservedIndex {
+    ObservedIndex {
+        index: U256::from(index),
+        block_number,
+        log_index,
+        transaction_hash: B256::repeat_byte(block_number as u8),
+    }
+}
+
+fn log(address: Address, data: LogData, block_number: Option<u64>) -> Log {
+    Log {
+        inner: alloy::primitives::Log { address, data },
+        block_number,
+        log_index: Some(3),
+        transaction_hash: Some(B256::repeat_byte(0x11)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_next_scan_range_caught_up() {
+    assert_eq!(next_scan_range(100, 100, 2_000), None);
+    assert_eq!(next_scan_range(101, 100, 2_000), None);
+}
+
+#[test]
+fn test_next_scan_range_caps_width() {
+    assert_eq!(next_scan_range(100, 150, 2_000), Some((101, 150)));
+    assert_eq!(next_scan_range(100, 10_000, 2_000), Some((101, 2_100)));
+}
+
+#[test]
+fn test_next_scan_range_zero_width_scans_one_block() {
+    assert_eq!(next_scan_range(100, 150, 0), Some((101, 101)));
+}
+
+#[test]
+fn test_merge_keeps_newest_observation() {
+    let mut latest = HashMap::new();
+    let changed = merge_observations(
+        &mut latest,
+        [
+            (beacon(), observed(1, 10, 0)),
+            (beacon(), observed(3, 12, 0)),
+            (beacon(), observed(2, 11, 5)),
+        ],
+    );
+
+    assert_eq!(changed, vec![(beacon(), observed(3, 12, 0))]);
+    assert_eq!(latest[&beacon()], observed(3, 12, 0));
+}
+
+#[test]
+fn test_merge_ignores_older_than_stored() {
+    let mut latest = HashMap::from([(beacon(), observed(5, 20, 0))]);
+    let changed = merge_observations(&mut latest, [(beacon(), observed(4, 19, 9))]);
+
+    assert!(changed.is_empty());
+    assert_eq!(latest[&beacon()], observed(5, 20, 0));
+}
+
+#[test]
+fn test_merge_orders_by_log_index_within_block() {
+    let mut latest = HashMap::from([(beacon(), observed(5, 20, 1))]);
+    let changed = merge_observations(&mut latest, [(beacon(), observed(6, 20, 2))]);
+
+    assert_eq!(changed.len(), 1);
+    assert_eq!(latest[&beacon()].index, U256::from(6));
+}
+
+#[test]
+fn test_decode_index_updated() {
+    let data = IBeacon::IndexUpdated {
+        index: U256::from(42),
+    }
+    .encode_log_data();
+    let (address, observation) = decode_index_updated(&log(beacon(), data, Some(7))).unwrap();
+
+    assert_eq!(address, beacon());
+    assert_eq!(observation.index, U256::from(42));
+    assert_eq!(observation.block_number, 7);
+    assert_eq!(observation.log_index, 3);
+    assert_eq!(observation.transaction_hash, B256::repeat_byte(0x11));
+}
+
+#[test]
+fn test_decode_skips_pending_and_foreign_logs() {
+    let data = IBeacon::IndexUpdated {
+        index: U256::from(42),
+    }
+    .encode_log_data();
+    assert!(decode_index_updated(&log(beacon(), data, None)).is_none());
+
+    let foreign = LogData::new_unchecked(vec![B256::repeat_byte(0xaa)], Default::default());
+    assert!(decode_index_updated(&log(beacon(), foreign, Some(7))).is_none());
+}
+
+#[test]
+fn test_parse_beacon_list() {
+    let a = "0x1111111111111111111111111111111111111111";
+    let b = "0x2222222222222222222222222222222222222222";
+    let parsed = parse_beacon_list(&format!(" {a}, ,{b},{a},")).unwrap();
+
+    assert_eq!(
+        parsed,
+        vec![a.parse().unwrap(), b.parse::<Address>().unwrap()]
+    );
+    assert!(parse_beacon_list("").unwrap().is_empty());
+    assert!(parse_beacon_list("0x1234,nope").is_err());
+}
+
+#[tokio::test]
+async fn test_feed_ignores_untracked_beacons() {
+    let feed = BeaconEventFeed::new(vec![beacon()], Some(0));
+    let other = Address::repeat_byte(0x01);
+    let changed = feed
+        .apply([(beacon(), observed(1, 5, 0)), (other, observed(2, 6, 0))])
+        .await;
+
+    assert_eq!(changed, vec![(beacon(), observed(1, 5, 0))]);
+    assert_eq!(feed.latest(&beacon()).await, Some(observed(1, 5, 0)));
+    assert_eq!(feed.latest(&other).await, None);
+    assert_eq!(feed.scanned_through().await, Some(0));
+}
+
+#[test]
+fn test_disabled_feed() {
+    let feed = BeaconEventFeed::disabled();
+    assert!(!feed.is_enabled());
+    assert!(!feed.is_tracked(&beacon()));
+}
+
+#[tokio::test]
+async fn test_streamed_log_advances_cursor() {
+    let feed = BeaconEventFeed::new(vec![beacon()], Some(10));
+    let data = IBeacon::IndexUpdated {
+        index: U256::from(9),
+    }
+    .encode_log_data();
+    feed.observe_streamed(&log(beacon(), data, Some(20)))
+        .await
+        .unwrap();
+
+    assert_eq!(feed.latest(&beacon()).await.unwrap().index, U256::from(9));
+    // Later logs in block 20 may still arrive, so only block 19 is fully scanned.
+    assert_eq!(feed.scanned_through().await, Some(19));
+}
+
+#[tokio::test]
+async fn test_streamed_removed_log_is_ignored() {
+    let feed = BeaconEventFeed::new(vec![beacon()], Some(10));
+    let data = IBeacon::IndexUpdated {
+        index: U256::from(9),
+    }
+    .encode_log_data();
+    let mut removed = log(beacon(), data, Some(20));
+    removed.removed = true;
+    feed.observe_streamed(&removed).await.unwrap();
+
+    assert_eq!(feed.latest(&beacon()).await, None);
+    assert_eq!(feed.scanned_through().await, Some(10));
+}
@@ -6,6 +6,7 @@ use the_beaconator::models::CreateBeaconWithEcdsaRequest;
 fn test_create_beacon_with_ecdsa_request_validation() {
     let request = CreateBeaconWithEcdsaRequest {
         initial_index: 12345,
+        verifier_address: None,
     };
 
     assert_eq!(request.initial_index, 12345);
@@ -43,11 +44,15 @@ fn test_ecdsa_request_data_bounds() {
     // Test edge cases for initial_index
     let request = CreateBeaconWithEcdsaRequest {
         initial_index: u128::MAX,
+        verifier_address: None,
     };
 
     assert_eq!(request.initial_index, u128::MAX);
 
-    let request_min = CreateBeaconWithEcdsaRequest { initial_index: 0 };
+    let request_min = CreateBeaconWithEcdsaRequest {
+        initial_index: 0,
+        verifier_address: None,
+    };
 
     assert_eq!(request_min.initial_index, 0);
 }
@@ -56,6 +61,7 @@ fn test_ecdsa_request_data_bounds() {
 fn test_ecdsa_request_serialization() {
     let request = CreateBeaconWithEcdsaRequest {
         initial_index: 1000000,
+        verifier_address: None,
     };
 
     let serialized = serde_json::to_string(&request).unwrap();
@@ -116,7 +122,27 @@ async fn test_deploy_identity_beacon_empty_bytecode_in_test_state() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     // Test app state should have empty bytecode (deploy_identity_beacon would reject this)
     assert!(
-        app_state.contracts.identity_beacon_bytecode.is_empty(),
+        app_state
+            .contracts
+            .load()
+            .identity_beacon_bytecode
+            .is_empty(),
         "Test app state should have empty bytecode"
     );
 }
+
+#[test]
+fn test_ecdsa_request_verifier_address_defaults_to_none() {
+    let request: CreateBeaconWithEcdsaRequest =
+        serde_json::from_str(r#"{"initial_index": 1000}"#).unwrap();
+    assert_eq!(request.verifier_address, None);
+
+    let request: CreateBeaconWithEcdsaRequest = serde_json::from_str(
+        r#"{"initial_index": 1000, "verifier_address": "0x1234567890123456789012345678901234567890"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        request.verifier_address.as_deref(),
+        Some("0x1234567890123456789012345678901234567890")
+    );
+}
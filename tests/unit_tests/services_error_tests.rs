@@ -0,0 +1,164 @@
+// Tests for typed service errors (src/services/error.rs) and their HTTP mapping
+use alloy::contract::Error as ContractError;
+use alloy::rpc::json_rpc::ErrorPayload;
+use alloy::transports::{RpcError, TransportErrorKind};
+use rocket::http::Status;
+use serde_json::value::RawValue;
+use the_beaconator::routes::beacon_error_status;
+use the_beaconator::services::error::BeaconError;
+
+#[test]
+fn test_display_is_the_original_message() {
+    let msg = "Timeout waiting for transaction 0xabc receipt".to_string();
+    assert_eq!(BeaconError::Timeout(msg.clone()).to_string(), msg);
+
+    let reverted = BeaconError::ContractReverted {
+        message: "Failed to send update transaction: ProofAlreadyUsed()".to_string(),
+        decoded: Some("ProofAlreadyUsed()".to_string()),
+    };
+    assert_eq!(
+        reverted.to_string(),
+        "Failed to send update transaction: ProofAlreadyUsed()"
+    );
+}
+
+fn error_response(code: i64, message: &'static str, data: Option<&str>) -> ContractError {
+    ContractError::TransportError(RpcError::ErrorResp(ErrorPayload {
+        code,
+        message: message.into(),
+        data: data.map(|data| RawValue::from_string(format!("\"{data}\"")).unwrap()),
+    }))
+}
+
+#[test]
+fn test_contract_errors_are_classified_by_kind() {
+    let classify = |error: ContractError| {
+        BeaconError::from_contract_error("call failed".to_string(), &error, None).code()
+    };
+
+    assert_eq!(
+        classify(error_response(3, "execution reverted", Some("0xdeadbeef"))),
+        "contract_reverted"
+    );
+    assert_eq!(
+        classify(error_response(3, "execution reverted", None)),
+        "contract_reverted"
+    );
+    assert_eq!(
+        classify(error_response(429, "rate limited", None)),
+        "rpc_unavailable"
+    );
+    assert_eq!(
+        classify(ContractError::TransportError(
+            TransportErrorKind::custom_str("connection refused")
+        )),
+        "rpc_unavailable"
+    );
+    assert_eq!(
+        classify(ContractError::UnknownFunction("index".to_string())),
+        "internal_error"
+    );
+    // The message, not the classification, is what callers display.
+    let error = BeaconError::from_contract_error(
+        "Failed to read modules".to_string(),
+        &error_response(-32000, "header not found", None),
+        None,
+    );
+    assert_eq!(error.to_string(), "Failed to read modules");
+}
+
+#[test]
+fn test_decoded_reason_makes_a_revert() {
+    let error = BeaconError::from_contract_error(
+        "Failed to send update transaction: ProofAlreadyUsed()".to_string(),
+        &error_response(-32000, "rejected", None),
+        Some("ProofAlreadyUsed()".to_string()),
+    );
+    assert!(error.is_proof_rejection());
+}
+
+#[test]
+fn test_send_errors_report_wallet_rejections() {
+    let classify = |error: ContractError| {
+        BeaconError::from_send_error("send failed".to_string(), &error, None).code()
+    };
+
+    assert_eq!(
+        classify(error_response(-32000, "nonce too low", None)),
+        "nonce_conflict"
+    );
+    assert_eq!(
+        classify(error_response(
+            -32000,
+            "insufficient funds for gas * price + value",
+            None
+        )),
+        "insufficient_funds"
+    );
+    assert_eq!(
+        classify(error_response(3, "execution reverted", Some("0xdeadbeef"))),
+        "contract_reverted"
+    );
+}
+
+#[test]
+fn test_proof_rejection_needs_a_decoded_reason() {
+    let rejected = BeaconError::ContractReverted {
+        message: "Failed to send update transaction: InvalidProof()".to_string(),
+        decoded: Some("InvalidProof()".to_string()),
+    };
+    assert!(rejected.is_proof_rejection());
+    assert_eq!(rejected.code(), "proof_rejected");
+
+    let receipt_revert = BeaconError::ContractReverted {
+        message: "Update transaction 0x1 reverted (status: false)".to_string(),
+        decoded: None,
+    };
+    assert!(!receipt_revert.is_proof_rejection());
+    assert_eq!(receipt_revert.code(), "contract_reverted");
+}
+
+#[test]
+fn test_status_mapping() {
+    let status = |e: BeaconError| beacon_error_status(&e);
+    let msg = || "x".to_string();
+
+    assert_eq!(
+        status(BeaconError::InvalidAddress(msg())),
+        Status::BadRequest
+    );
+    assert_eq!(status(BeaconError::NotABeacon(msg())), Status::BadRequest);
+    assert_eq!(
+        status(BeaconError::ContractReverted {
+            message: msg(),
+            decoded: Some("ProofAlreadyUsed()".to_string()),
+        }),
+        Status::BadRequest
+    );
+    assert_eq!(status(BeaconError::NotRegistered(msg())), Status::NotFound);
+    assert_eq!(status(BeaconError::InvalidState(msg())), Status::Conflict);
+    assert_eq!(
+        status(BeaconError::WalletUnavailable(msg())),
+        Status::ServiceUnavailable
+    );
+    assert_eq!(
+        status(BeaconError::RpcUnavailable(msg())),
+        Status::ServiceUnavailable
+    );
+    assert_eq!(status(BeaconError::Timeout(msg())), Status::GatewayTimeout);
+    assert_eq!(
+        status(BeaconError::ContractReverted {
+            message: msg(),
+            decoded: None,
+        }),
+        Status::InternalServerError
+    );
+    assert_eq!(
+        status(BeaconError::NonceConflict(msg())),
+        Status::InternalServerError
+    );
+    assert_eq!(
+        status(BeaconError::Other(msg())),
+        Status::InternalServerError
+    );
+}
@@ -0,0 +1,99 @@
+// Tests for the GET /gas_price fee cache (src/services/gas_price.rs)
+
+use rocket::State;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use the_beaconator::guards::ApiToken;
+use the_beaconator::routes::estimate::gas_price;
+use the_beaconator::services::gas_price::{GasPriceCache, NetworkFees};
+
+fn fees(block_number: u64, gas_used_ratio: f64) -> NetworkFees {
+    NetworkFees {
+        block_number,
+        base_fee_per_gas: Some(10_000_000),
+        max_fee_per_gas: 21_000_000,
+        max_priority_fee_per_gas: 1_000_000,
+        gas_used_ratio,
+    }
+}
+
+/// Stand-in for `fetch_network_fees`: counts calls and reports the call number as the block.
+async fn mock_fetch(calls: &AtomicU32) -> Result<NetworkFees, String> {
+    let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+    Ok(fees(u64::from(n), 0.3))
+}
+
+#[tokio::test]
+async fn test_gas_price_cache_serves_within_ttl() {
+    let cache = GasPriceCache::new(Duration::from_secs(60));
+    let calls = AtomicU32::new(0);
+
+    let first = cache.get_or_fetch(|| mock_fetch(&calls)).await.unwrap();
+    let second = cache.get_or_fetch(|| mock_fetch(&calls)).await.unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(first, second);
+}
+
+#[tokio::test]
+async fn test_gas_price_cache_refreshes_after_ttl() {
+    let cache = GasPriceCache::new(Duration::from_millis(20));
+    let calls = AtomicU32::new(0);
+
+    let first = cache.get_or_fetch(|| mock_fetch(&calls)).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(40)).await;
+    let second = cache.get_or_fetch(|| mock_fetch(&calls)).await.unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(first.block_number, 1);
+    assert_eq!(second.block_number, 2);
+}
+
+#[tokio::test]
+async fn test_gas_price_cache_does_not_cache_errors() {
+    let cache = GasPriceCache::new(Duration::from_secs(60));
+    let calls = AtomicU32::new(0);
+
+    let failed = cache
+        .get_or_fetch(|| async { Err::<NetworkFees, _>("rpc down".to_string()) })
+        .await;
+    assert_eq!(failed.unwrap_err(), "rpc down");
+
+    let fetched = cache.get_or_fetch(|| mock_fetch(&calls)).await.unwrap();
+    assert_eq!(fetched.block_number, 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_congestion_is_above_base_fee_target() {
+    assert!(!fees(1, 0.3).is_congested());
+    assert!(!fees(1, 0.5).is_congested());
+    assert!(fees(1, 0.9).is_congested());
+}
+
+#[tokio::test]
+async fn test_gas_price_route_reports_cached_fees() {
+    let mut app_state = crate::test_utils::create_simple_test_app_state().await;
+    let cache = GasPriceCache::new(Duration::from_secs(60));
+    cache
+        .get_or_fetch(|| async { Ok::<_, String>(fees(1234, 0.75)) })
+        .await
+        .unwrap();
+    app_state.provider.gas_prices = Arc::new(cache);
+    let state = State::from(&app_state);
+
+    let response = gas_price(ApiToken::Token("test_token".to_string()), state)
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert!(response.success);
+    let data = response.data.unwrap();
+    assert_eq!(data.block_number, 1234);
+    assert_eq!(data.base_fee_per_gas_wei.as_deref(), Some("10000000"));
+    assert_eq!(data.max_fee_per_gas_wei, "21000000");
+    assert_eq!(data.max_priority_fee_per_gas_wei, "1000000");
+    assert_eq!(data.gas_used_ratio, 0.75);
+    assert!(data.congested);
+}
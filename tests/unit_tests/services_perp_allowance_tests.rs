@@ -0,0 +1,145 @@
+// Unit tests for the per-(owner, spender) USDC allowance cache
+
+use alloy::primitives::{Address, U256};
+use the_beaconator::models::PerpConfig;
+use the_beaconator::services::perp::AllowanceCache;
+
+fn owner() -> Address {
+    Address::repeat_byte(0x11)
+}
+
+fn perp() -> Address {
+    Address::repeat_byte(0x22)
+}
+
+#[test]
+fn test_recorded_allowance_is_a_cache_hit() {
+    let cache = AllowanceCache::new();
+    assert!(cache.is_empty());
+    assert_eq!(cache.get(owner(), perp()), None);
+
+    cache.record(owner(), perp(), U256::from(500_000_000u64));
+    assert_eq!(cache.get(owner(), perp()), Some(U256::from(500_000_000u64)));
+    assert_eq!(cache.len(), 1);
+
+    // Keyed by (owner, spender): other pairs miss.
+    assert_eq!(cache.get(perp(), owner()), None);
+    assert_eq!(cache.get(owner(), Address::repeat_byte(0x33)), None);
+}
+
+#[test]
+fn test_record_overwrites() {
+    let cache = AllowanceCache::new();
+    cache.record(owner(), perp(), U256::from(10u64));
+    cache.record(owner(), perp(), U256::from(3u64));
+    assert_eq!(cache.get(owner(), perp()), Some(U256::from(3u64)));
+}
+
+#[test]
+fn test_dropped_guard_invalidates_only_its_pair() {
+    let cache = AllowanceCache::new();
+    cache.record(owner(), perp(), U256::from(100u64));
+    cache.record(owner(), Address::repeat_byte(0x33), U256::from(7u64));
+
+    let failing_deposit = || -> Result<(), String> {
+        let _guard = cache.guard(owner(), perp());
+        Err("openMaker transaction reverted".to_string())
+    };
+    assert!(failing_deposit().is_err());
+
+    assert_eq!(cache.get(owner(), perp()), None);
+    assert_eq!(
+        cache.get(owner(), Address::repeat_byte(0x33)),
+        Some(U256::from(7u64))
+    );
+}
+
+#[test]
+fn test_guard_invalidates_after_successful_deposit() {
+    let cache = AllowanceCache::new();
+    cache.record(owner(), perp(), U256::MAX);
+
+    let succeeding_deposit = || -> Result<(), String> {
+        let _guard = cache.guard(owner(), perp());
+        Ok(())
+    };
+    assert!(succeeding_deposit().is_ok());
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_invalidate() {
+    let cache = AllowanceCache::new();
+    cache.invalidate(owner(), perp());
+    assert!(cache.is_empty());
+
+    cache.record(owner(), perp(), U256::from(1u64));
+    cache.invalidate(owner(), perp());
+    assert!(cache.is_empty());
+}
+
+/// The allowance bookkeeping of `deposit_liquidity_for_perp`, with `chain` standing in for
+/// `USDC.allowance` on chain. Returns whether an approval was sent.
+fn simulate_deposit(
+    config: &PerpConfig,
+    cache: &AllowanceCache,
+    chain: &mut U256,
+    margin: U256,
+) -> bool {
+    let allowance = match cache.get(owner(), perp()) {
+        Some(cached) => cached,
+        None => {
+            cache.record(owner(), perp(), *chain);
+            *chain
+        }
+    };
+    let _guard = cache.guard(owner(), perp());
+    let approval = config.usdc_approval(allowance, margin);
+    if let Some(amount) = approval {
+        *chain = amount;
+    }
+    if *chain != U256::MAX {
+        *chain -= margin;
+    }
+    approval.is_some()
+}
+
+#[test]
+fn test_approve_max_skips_approval_on_second_deposit() {
+    let config = PerpConfig {
+        approve_max: true,
+        ..PerpConfig::default()
+    };
+    let cache = AllowanceCache::new();
+    let mut chain = U256::ZERO;
+    let margin = U256::from(50_000_000u64);
+
+    assert!(simulate_deposit(&config, &cache, &mut chain, margin));
+    assert!(cache.is_empty());
+    assert!(!simulate_deposit(&config, &cache, &mut chain, margin));
+    assert!(!simulate_deposit(&config, &cache, &mut chain, margin));
+}
+
+#[test]
+fn test_exact_approval_repeats_every_deposit() {
+    let config = PerpConfig::default();
+    let cache = AllowanceCache::new();
+    let mut chain = U256::ZERO;
+    let margin = U256::from(50_000_000u64);
+
+    assert!(simulate_deposit(&config, &cache, &mut chain, margin));
+    assert!(simulate_deposit(&config, &cache, &mut chain, margin));
+}
+
+#[test]
+fn test_cached_read_reused_when_nothing_was_sent() {
+    let cache = AllowanceCache::new();
+    let chain = U256::from(80_000_000u64);
+
+    // First attempt reads the chain and fails before taking the guard.
+    if cache.get(owner(), perp()).is_none() {
+        cache.record(owner(), perp(), chain);
+    }
+    // The retry is a cache hit.
+    assert_eq!(cache.get(owner(), perp()), Some(chain));
+}
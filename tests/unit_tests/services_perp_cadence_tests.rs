@@ -0,0 +1,67 @@
+// Unit tests for the ema_window vs beacon update cadence pre-flight of /deploy_perp_for_beacon
+
+use alloy::primitives::Address;
+use serial_test::serial;
+use the_beaconator::services::perp::cadence::{
+    BeaconCadence, DEFAULT_BEACON_CADENCE_LOOKBACK_BLOCKS, EMA_WINDOW_TOO_SHORT,
+    beacon_cadence_lookback_blocks_from_env, check_ema_window, estimate_cadence,
+};
+
+fn beacon() -> Address {
+    Address::repeat_byte(0xbe)
+}
+
+#[test]
+fn test_estimate_cadence_averages_over_gaps() {
+    // 5 updates spanning one hour: 4 gaps of 900s.
+    assert_eq!(
+        estimate_cadence(1_700_000_000, 1_700_003_600, 5),
+        Some(BeaconCadence {
+            interval_secs: 900,
+            updates: 5
+        })
+    );
+}
+
+#[test]
+fn test_estimate_cadence_needs_two_updates() {
+    assert_eq!(estimate_cadence(1_700_000_000, 1_700_000_000, 0), None);
+    assert_eq!(estimate_cadence(1_700_000_000, 1_700_000_000, 1), None);
+}
+
+#[test]
+fn test_ema_window_shorter_than_cadence_is_rejected() {
+    let cadence = estimate_cadence(0, 3 * 3600, 4);
+    let err = check_ema_window(beacon(), 600, cadence).unwrap_err();
+    assert!(err.starts_with(EMA_WINDOW_TOO_SHORT), "{err}");
+    assert!(err.contains("at least 3600s"), "{err}");
+}
+
+#[test]
+fn test_ema_window_covering_cadence_is_accepted() {
+    let cadence = estimate_cadence(0, 3 * 3600, 4);
+    assert!(check_ema_window(beacon(), 3600, cadence).is_ok());
+    assert!(check_ema_window(beacon(), 86_400, cadence).is_ok());
+    assert!(check_ema_window(beacon(), 1, None).is_ok());
+}
+
+#[test]
+#[serial]
+fn test_lookback_blocks_from_env() {
+    unsafe {
+        std::env::remove_var("PERP_BEACON_CADENCE_LOOKBACK_BLOCKS");
+    }
+    assert_eq!(
+        beacon_cadence_lookback_blocks_from_env(),
+        DEFAULT_BEACON_CADENCE_LOOKBACK_BLOCKS
+    );
+
+    unsafe {
+        std::env::set_var("PERP_BEACON_CADENCE_LOOKBACK_BLOCKS", " 0 ");
+    }
+    assert_eq!(beacon_cadence_lookback_blocks_from_env(), 0);
+
+    unsafe {
+        std::env::remove_var("PERP_BEACON_CADENCE_LOOKBACK_BLOCKS");
+    }
+}
@@ -0,0 +1,307 @@
+// Unit tests for the maker liquidity math (src/services/perp/liquidity.rs).
+// Reference values come from Uniswap's TickMath / LiquidityAmounts.
+
+use alloy::primitives::{I256, U256};
+use std::str::FromStr;
+use the_beaconator::services::perp::liquidity::{
+    calculate_liquidity_bounds, calculate_liquidity_from_margin, liquidity_for_amount0,
+    liquidity_for_amount1, liquidity_for_amounts, price_to_sqrt_price_x96, signed_x96_to_decimal,
+    snap_tick_to_spacing, sqrt_price_x96_to_price_x96, sqrt_price_x96_to_tick,
+    tick_to_sqrt_price_x96, ticks_for_price_range, x96_to_decimal,
+};
+use the_beaconator::services::perp::validation::{
+    DEFAULT_TICK_LOWER, DEFAULT_TICK_SPACING, DEFAULT_TICK_UPPER, MAX_TICK, MIN_TICK,
+};
+
+fn q96() -> U256 {
+    U256::from(1u8) << 96
+}
+
+#[test]
+fn test_sqrt_price_matches_tick_math() {
+    assert_eq!(tick_to_sqrt_price_x96(0).unwrap(), q96());
+    assert_eq!(
+        tick_to_sqrt_price_x96(MIN_TICK).unwrap(),
+        U256::from(4295128739u64)
+    );
+    assert_eq!(
+        tick_to_sqrt_price_x96(MAX_TICK).unwrap(),
+        U256::from_str("1461446703485210103287273052203988822378723970342").unwrap()
+    );
+}
+
+#[test]
+fn test_sqrt_price_is_monotonic() {
+    let mut previous = tick_to_sqrt_price_x96(-1_000).unwrap();
+    for tick in -999..=1_000 {
+        let current = tick_to_sqrt_price_x96(tick).unwrap();
+        assert!(
+            current > previous,
+            "sqrt price not increasing at tick {tick}"
+        );
+        previous = current;
+    }
+}
+
+#[test]
+fn test_sqrt_price_rejects_out_of_range_ticks() {
+    assert!(tick_to_sqrt_price_x96(MIN_TICK - 1).is_err());
+    assert!(tick_to_sqrt_price_x96(MAX_TICK + 1).is_err());
+}
+
+#[test]
+fn test_sqrt_price_to_tick_round_trip() {
+    for tick in [
+        MIN_TICK,
+        MIN_TICK + 1,
+        -500_000,
+        -60,
+        -1,
+        0,
+        1,
+        60,
+        DEFAULT_TICK_LOWER,
+        DEFAULT_TICK_UPPER,
+        500_000,
+        MAX_TICK - 1,
+    ] {
+        let sqrt_price = tick_to_sqrt_price_x96(tick).unwrap();
+        assert_eq!(sqrt_price_x96_to_tick(sqrt_price).unwrap(), tick);
+    }
+}
+
+#[test]
+fn test_sqrt_price_to_tick_rounds_down() {
+    // A price just short of the next tick's still belongs to the lower tick.
+    let next = tick_to_sqrt_price_x96(61).unwrap();
+    assert_eq!(sqrt_price_x96_to_tick(next - U256::from(1u8)).unwrap(), 60);
+    let zero = tick_to_sqrt_price_x96(0).unwrap();
+    assert_eq!(sqrt_price_x96_to_tick(zero - U256::from(1u8)).unwrap(), -1);
+}
+
+#[test]
+fn test_sqrt_price_to_tick_rejects_out_of_range_prices() {
+    let min = tick_to_sqrt_price_x96(MIN_TICK).unwrap();
+    let max = tick_to_sqrt_price_x96(MAX_TICK).unwrap();
+    assert!(sqrt_price_x96_to_tick(min - U256::from(1u8)).is_err());
+    assert!(sqrt_price_x96_to_tick(max).is_err());
+    assert!(sqrt_price_x96_to_tick(max - U256::from(1u8)).is_ok());
+}
+
+#[test]
+fn test_liquidity_for_amount1() {
+    // Over [1.0, 2.0] in sqrt price, L = amount1 / (sqrtB - sqrtA) = amount1.
+    let amount = U256::from(1_000_000u64);
+    let liquidity = liquidity_for_amount1(q96(), q96() * U256::from(2u8), amount).unwrap();
+    assert_eq!(liquidity, amount);
+
+    // Argument order does not matter.
+    let swapped = liquidity_for_amount1(q96() * U256::from(2u8), q96(), amount).unwrap();
+    assert_eq!(swapped, amount);
+
+    assert!(liquidity_for_amount1(q96(), q96(), amount).is_err());
+}
+
+/// `encodePriceSqrt(reserve1, reserve0)` from Uniswap's test helpers.
+fn encode_price_sqrt(reserve1: u64, reserve0: u64) -> U256 {
+    ((U256::from(reserve1) << 192usize) / U256::from(reserve0)).root(2)
+}
+
+/// The `[100/110, 110/100]` range from Uniswap's LiquidityAmounts tests, funded with
+/// 100 token0 and 200 token1.
+fn reference_liquidity(sqrt_price_x96: U256) -> U256 {
+    liquidity_for_amounts(
+        sqrt_price_x96,
+        encode_price_sqrt(100, 110),
+        encode_price_sqrt(110, 100),
+        U256::from(100u8),
+        U256::from(200u8),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_liquidity_for_amounts_matches_uniswap_reference() {
+    // Price inside the range.
+    assert_eq!(
+        reference_liquidity(encode_price_sqrt(1, 1)),
+        U256::from(2148u64)
+    );
+    // Price below the range: token0 only.
+    assert_eq!(
+        reference_liquidity(encode_price_sqrt(99, 110)),
+        U256::from(1048u64)
+    );
+    // Price above the range: token1 only.
+    assert_eq!(
+        reference_liquidity(encode_price_sqrt(111, 100)),
+        U256::from(2097u64)
+    );
+    // Price on the boundaries.
+    assert_eq!(
+        reference_liquidity(encode_price_sqrt(100, 110)),
+        U256::from(1048u64)
+    );
+    assert_eq!(
+        reference_liquidity(encode_price_sqrt(110, 100)),
+        U256::from(2097u64)
+    );
+}
+
+#[test]
+fn test_liquidity_for_amount0() {
+    // Over [1.0, 2.0] in sqrt price, L = amount0 * sqrtA * sqrtB / (sqrtB - sqrtA) = 2 * amount0.
+    let amount = U256::from(1_000_000u64);
+    let liquidity = liquidity_for_amount0(q96(), q96() * U256::from(2u8), amount).unwrap();
+    assert_eq!(liquidity, amount * U256::from(2u8));
+
+    let swapped = liquidity_for_amount0(q96() * U256::from(2u8), q96(), amount).unwrap();
+    assert_eq!(swapped, liquidity);
+
+    assert!(liquidity_for_amount0(q96(), q96(), amount).is_err());
+}
+
+#[test]
+fn test_liquidity_bounds() {
+    // Uniswap's tickSpacingToMaxLiquidityPerTick(60).
+    let (min, max) = calculate_liquidity_bounds(60).unwrap();
+    assert_eq!(min, 1);
+    assert_eq!(max, 11505743598341114571880798222544994);
+
+    // Tighter spacing means more initializable ticks and a lower per-tick cap.
+    let (_, max_1) = calculate_liquidity_bounds(1).unwrap();
+    assert!(max_1 < max);
+
+    assert!(calculate_liquidity_bounds(0).is_err());
+}
+
+#[test]
+fn test_liquidity_from_margin_scales_with_margin() {
+    let ten_usdc = calculate_liquidity_from_margin(
+        10_000_000,
+        DEFAULT_TICK_SPACING,
+        DEFAULT_TICK_LOWER,
+        DEFAULT_TICK_UPPER,
+    )
+    .unwrap();
+    let hundred_usdc = calculate_liquidity_from_margin(
+        100_000_000,
+        DEFAULT_TICK_SPACING,
+        DEFAULT_TICK_LOWER,
+        DEFAULT_TICK_UPPER,
+    )
+    .unwrap();
+
+    assert!(ten_usdc > 0);
+    // Rounding down can lose at most one unit per division.
+    assert!(hundred_usdc.abs_diff(ten_usdc * 10) <= 10);
+}
+
+#[test]
+fn test_liquidity_from_margin_rejects_zero() {
+    let err = calculate_liquidity_from_margin(
+        0,
+        DEFAULT_TICK_SPACING,
+        DEFAULT_TICK_LOWER,
+        DEFAULT_TICK_UPPER,
+    )
+    .unwrap_err();
+    assert!(err.contains("rounds to zero"), "got {err}");
+}
+
+#[test]
+fn test_liquidity_from_margin_rejects_above_per_tick_cap() {
+    let err = calculate_liquidity_from_margin(u128::MAX, 60, -60, 60).unwrap_err();
+    assert!(err.contains("per-tick maximum"), "got {err}");
+}
+
+#[test]
+fn test_price_to_sqrt_price() {
+    assert_eq!(price_to_sqrt_price_x96(1.0).unwrap(), q96());
+    assert_eq!(
+        price_to_sqrt_price_x96(4.0).unwrap(),
+        q96() * U256::from(2u8)
+    );
+    assert!(price_to_sqrt_price_x96(0.0).is_err());
+    assert!(price_to_sqrt_price_x96(-1.0).is_err());
+    assert!(price_to_sqrt_price_x96(f64::NAN).is_err());
+}
+
+#[test]
+fn test_snap_tick_to_spacing() {
+    assert_eq!(snap_tick_to_spacing(44, 30).unwrap(), 30);
+    assert_eq!(snap_tick_to_spacing(45, 30).unwrap(), 60);
+    assert_eq!(snap_tick_to_spacing(-44, 30).unwrap(), -30);
+    assert_eq!(snap_tick_to_spacing(-46, 30).unwrap(), -60);
+    // Never snaps past the aligned ends of the tick range.
+    assert_eq!(snap_tick_to_spacing(MAX_TICK, 60).unwrap(), 887220);
+    assert_eq!(snap_tick_to_spacing(MIN_TICK, 60).unwrap(), -887220);
+    assert!(snap_tick_to_spacing(0, 0).is_err());
+}
+
+#[test]
+fn test_ticks_for_price_range() {
+    // 1.0001^-600 .. 1.0001^600, already aligned to 30.
+    let (lower, upper) =
+        ticks_for_price_range(1.0001f64.powi(-600), 1.0001f64.powi(600), 30).unwrap();
+    assert_eq!((lower, upper), (-600, 600));
+
+    // An unaligned range snaps to the nearest aligned ticks.
+    let (lower, upper) =
+        ticks_for_price_range(1.0001f64.powi(10), 1.0001f64.powi(100), 30).unwrap();
+    assert_eq!((lower, upper), (0, 90));
+}
+
+#[test]
+fn test_ticks_for_price_range_rejects_bad_ranges() {
+    assert!(ticks_for_price_range(2.0, 1.0, 30).is_err());
+    assert!(ticks_for_price_range(1.0, 1.0, 30).is_err());
+    // Ten ticks apart: both bounds snap to 0 at spacing 30.
+    let err = ticks_for_price_range(1.0001f64.powi(-5), 1.0001f64.powi(5), 30).unwrap_err();
+    assert!(err.contains("empty tick span"), "got {err}");
+}
+
+#[test]
+fn test_sqrt_price_x96_to_price_x96() {
+    assert_eq!(sqrt_price_x96_to_price_x96(q96()), q96());
+    assert_eq!(
+        sqrt_price_x96_to_price_x96(q96() * U256::from(2u8)),
+        q96() * U256::from(4u8)
+    );
+    assert_eq!(
+        x96_to_decimal(sqrt_price_x96_to_price_x96(q96() / U256::from(2u8))),
+        "0.25"
+    );
+    // The largest uint160 sqrt price squares past 256 bits; the price itself still fits.
+    let max_sqrt = (U256::from(1u8) << 160) - U256::from(1u8);
+    let price = sqrt_price_x96_to_price_x96(max_sqrt);
+    assert!(price > U256::from(1u8) << 223);
+    assert!(price < U256::from(1u8) << 224);
+}
+
+#[test]
+fn test_x96_to_decimal() {
+    assert_eq!(x96_to_decimal(U256::ZERO), "0");
+    assert_eq!(x96_to_decimal(q96()), "1");
+    assert_eq!(
+        x96_to_decimal(q96() * U256::from(3u8) / U256::from(2u8)),
+        "1.5"
+    );
+    assert_eq!(x96_to_decimal(q96() / U256::from(4u8)), "0.25");
+    // 1/3 truncates at 18 places.
+    assert_eq!(
+        x96_to_decimal(q96() / U256::from(3u8)),
+        "0.333333333333333333"
+    );
+    // The smallest X96 unit is below 1e-18.
+    assert_eq!(x96_to_decimal(U256::from(1u8)), "0");
+}
+
+#[test]
+fn test_signed_x96_to_decimal() {
+    let half = I256::from_raw(q96() / U256::from(2u8));
+    assert_eq!(signed_x96_to_decimal(half), "0.5");
+    assert_eq!(signed_x96_to_decimal(-half), "-0.5");
+    // A negative dust value truncates to plain zero, not "-0".
+    assert_eq!(signed_x96_to_decimal(I256::MINUS_ONE), "0");
+}
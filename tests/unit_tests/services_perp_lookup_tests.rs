@@ -0,0 +1,169 @@
+// Unit tests for the existing-perp lookup that short-circuits /deploy_perp_for_beacon
+
+use alloy::primitives::aliases::{I24, U24, U160};
+use alloy::primitives::{Address, B256, FixedBytes, U256};
+use alloy::rpc::types::Log;
+use alloy::sol_types::SolEvent;
+use serial_test::serial;
+use the_beaconator::routes::IPerpFactory;
+use the_beaconator::services::perp::{
+    already_deployed_response, first_perp_for_beacon, perp_factory_deploy_block_from_env,
+};
+use the_beaconator::services::transaction::events::decode_perp_created;
+
+fn factory() -> Address {
+    Address::repeat_byte(0xfa)
+}
+
+fn beacon() -> Address {
+    Address::repeat_byte(0xbe)
+}
+
+fn perp_created(perp: Address, beacon: Address) -> IPerpFactory::PerpCreated {
+    IPerpFactory::PerpCreated {
+        perp,
+        poolId: B256::repeat_byte(0x90),
+        modules: IPerpFactory::Modules {
+            beacon,
+            fees: Address::repeat_byte(0x01),
+            funding: Address::repeat_byte(0x02),
+            marginRatios: Address::repeat_byte(0x03),
+            priceImpact: Address::repeat_byte(0x04),
+            pricing: Address::repeat_byte(0x05),
+        },
+        initialIndex: U256::from(1_000_000u64),
+        emaWindow: U24::from(3600u32),
+        protocolFee: U256::ZERO,
+        sqrtPriceX96: U160::from(1u64) << 96,
+        tick: I24::try_from(-120i32).unwrap(),
+        owner: Address::repeat_byte(0x0e),
+        name: "Perp".to_string(),
+        symbol: "PERP".to_string(),
+        tokenUri: String::new(),
+    }
+}
+
+fn log(emitter: Address, event: &IPerpFactory::PerpCreated, block_number: Option<u64>) -> Log {
+    Log {
+        inner: alloy::primitives::Log {
+            address: emitter,
+            data: event.encode_log_data(),
+        },
+        block_number,
+        log_index: Some(0),
+        transaction_hash: Some(B256::repeat_byte(block_number.unwrap_or(0) as u8)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_decode_perp_created_reads_beacon_from_modules() {
+    let perp = Address::repeat_byte(0x22);
+    let event = decode_perp_created(&log(factory(), &perp_created(perp, beacon()), Some(5)))
+        .expect("PerpCreated decodes");
+
+    assert_eq!(event.perp, perp);
+    assert_eq!(event.beacon, beacon());
+    assert_eq!(event.pool_id, B256::repeat_byte(0x90));
+    assert_eq!(event.ema_window, 3600);
+    assert_eq!(event.tick, -120);
+    assert_eq!(event.sqrt_price_x96, U256::from(1u64) << 96);
+    assert_eq!(event.mark_price_x96, U256::from(1u64) << 96);
+}
+
+#[test]
+fn test_decode_perp_created_derives_mark_price() {
+    // sqrtPriceX96 = 1.5 * 2^96, so the mark price is 1.5^2 = 2.25.
+    let mut created = perp_created(Address::repeat_byte(0x22), beacon());
+    created.sqrtPriceX96 = (U160::from(3u64) << 96) / U160::from(2u64);
+    let event = decode_perp_created(&log(factory(), &created, Some(5))).unwrap();
+
+    assert_eq!(
+        event.mark_price_x96,
+        (U256::from(9u64) << 96) / U256::from(4u64)
+    );
+
+    let response = already_deployed_response(
+        factory(),
+        &event,
+        B256::repeat_byte(5),
+        FixedBytes::<32>::ZERO,
+    );
+    assert_eq!(response.mark_price, "2.25");
+    assert_eq!(response.sqrt_price_x96, created.sqrtPriceX96.to_string());
+}
+
+#[test]
+fn test_first_perp_for_beacon_takes_earliest_match() {
+    let first = Address::repeat_byte(0x21);
+    let second = Address::repeat_byte(0x22);
+    let logs = vec![
+        log(factory(), &perp_created(second, beacon()), Some(9)),
+        log(
+            factory(),
+            &perp_created(Address::repeat_byte(0x23), Address::repeat_byte(0xbf)),
+            Some(3),
+        ),
+        log(factory(), &perp_created(first, beacon()), Some(7)),
+    ];
+
+    let (event, tx_hash) = first_perp_for_beacon(&logs, factory(), beacon()).unwrap();
+    assert_eq!(event.perp, first);
+    assert_eq!(tx_hash, B256::repeat_byte(7));
+}
+
+#[test]
+fn test_first_perp_for_beacon_ignores_foreign_and_pending_logs() {
+    let event = perp_created(Address::repeat_byte(0x22), beacon());
+    let logs = vec![
+        // Same event from a contract that is not the configured factory.
+        log(Address::repeat_byte(0x66), &event, Some(4)),
+        // Pending log without a block position.
+        log(factory(), &event, None),
+    ];
+    assert!(first_perp_for_beacon(&logs, factory(), beacon()).is_none());
+    assert!(first_perp_for_beacon(&[], factory(), beacon()).is_none());
+}
+
+#[test]
+fn test_already_deployed_response_describes_existing_perp() {
+    let perp = Address::repeat_byte(0x22);
+    let event =
+        decode_perp_created(&log(factory(), &perp_created(perp, beacon()), Some(5))).unwrap();
+    let salt = FixedBytes::<32>::repeat_byte(0x5a);
+
+    let response = already_deployed_response(factory(), &event, B256::repeat_byte(5), salt);
+
+    assert!(response.already_deployed);
+    assert_eq!(response.perp_address, perp.to_string());
+    assert_eq!(response.perp_factory_address, factory().to_string());
+    assert_eq!(response.pool_id, format!("{:#x}", B256::repeat_byte(0x90)));
+    assert_eq!(response.ema_window, 3600);
+    assert_eq!(response.tick, -120);
+    assert_eq!(response.mark_price, "1");
+    assert_eq!(response.transaction_hash, B256::repeat_byte(5).to_string());
+    assert_eq!(response.salt, format!("{salt:#x}"));
+    assert_eq!(response.gas_used, 0);
+    assert_eq!(response.confirmations, 0);
+
+    let json = serde_json::to_value(&response).unwrap();
+    assert_eq!(json["already_deployed"], true);
+}
+
+#[test]
+#[serial]
+fn test_lookup_start_block_from_env() {
+    unsafe {
+        std::env::remove_var("PERP_FACTORY_DEPLOY_BLOCK");
+    }
+    assert_eq!(perp_factory_deploy_block_from_env(), None);
+
+    unsafe {
+        std::env::set_var("PERP_FACTORY_DEPLOY_BLOCK", " 12345678 ");
+    }
+    assert_eq!(perp_factory_deploy_block_from_env(), Some(12_345_678));
+
+    unsafe {
+        std::env::remove_var("PERP_FACTORY_DEPLOY_BLOCK");
+    }
+}
@@ -0,0 +1,115 @@
+// Unit tests for the createPerp / openMaker parameter builders
+
+use alloy::primitives::{Address, FixedBytes, U256};
+use std::str::FromStr;
+use the_beaconator::models::PerpConfig;
+use the_beaconator::services::perp::params::{
+    UINT24_MAX, build_create_perp_call, ema_window_u24, open_maker_params, perp_modules,
+    resolve_deposit_ticks, tick_i24,
+};
+
+#[test]
+fn test_resolve_deposit_ticks_fills_only_omitted_values() {
+    let config = PerpConfig::default();
+    assert_eq!(
+        resolve_deposit_ticks(&config, None, None, None),
+        (config.tick_spacing, config.tick_lower, config.tick_upper)
+    );
+    assert_eq!(
+        resolve_deposit_ticks(&config, Some(60), None, Some(120)),
+        (60, config.tick_lower, 120)
+    );
+}
+
+#[test]
+fn test_ema_window_u24_bounds() {
+    assert!(ema_window_u24(0).unwrap_err().contains("must be > 0"));
+    assert_eq!(ema_window_u24(1).unwrap().to::<u32>(), 1);
+    assert_eq!(ema_window_u24(UINT24_MAX).unwrap().to::<u32>(), UINT24_MAX);
+    assert!(
+        ema_window_u24(UINT24_MAX + 1)
+            .unwrap_err()
+            .contains("exceeds uint24")
+    );
+}
+
+#[test]
+fn test_tick_i24_bounds() {
+    assert_eq!(tick_i24("tick lower", -887_220).unwrap().as_i32(), -887_220);
+    assert_eq!(
+        tick_i24("tick upper", 8_388_607).unwrap().as_i32(),
+        8_388_607
+    );
+
+    let err = tick_i24("tick upper", 8_388_608).unwrap_err();
+    assert!(err.contains("Invalid tick upper 8388608"), "{err}");
+    assert!(tick_i24("tick lower", -8_388_609).is_err());
+}
+
+#[tokio::test]
+async fn test_build_create_perp_call_uses_configured_modules() {
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    let beacon = Address::from_str("0x1111111111111111111111111111111111111111").unwrap();
+    let owner = Address::from_str("0x2222222222222222222222222222222222222222").unwrap();
+    let salt = FixedBytes::<32>::repeat_byte(0xab);
+
+    let call = build_create_perp_call(
+        &app_state.contracts.load(),
+        beacon,
+        owner,
+        "Test Perp".to_string(),
+        "TEST".to_string(),
+        "https://example.com".to_string(),
+        3600,
+        salt,
+    )
+    .unwrap();
+
+    let modules = perp_modules(&app_state.contracts.load(), beacon);
+    assert_eq!(call.modules.beacon, modules.beacon);
+    assert_eq!(
+        call.modules.funding,
+        app_state.contracts.load().funding_module
+    );
+    assert_eq!(
+        call.modules.marginRatios,
+        app_state.contracts.load().margin_ratios_module
+    );
+    assert_eq!(
+        call.modules.priceImpact,
+        app_state.contracts.load().price_impact_module
+    );
+    assert_eq!(call.emaWindow.to::<u32>(), 3600);
+    assert_eq!(call.tokenUri, "https://example.com");
+    assert_eq!(call.salt, salt);
+}
+
+#[test]
+fn test_open_maker_params_converts_ticks() {
+    let holder = Address::from_str("0x3333333333333333333333333333333333333333").unwrap();
+    let params = open_maker_params(
+        holder,
+        50_000_000,
+        24_390,
+        53_850,
+        123_456,
+        U256::from(10u64),
+        U256::from(20u64),
+    )
+    .unwrap();
+
+    assert_eq!(params.holder, holder);
+    assert_eq!(params.margin, 50_000_000);
+    assert_eq!(params.tickLower.as_i32(), 24_390);
+    assert_eq!(params.tickUpper.as_i32(), 53_850);
+    assert_eq!(params.liquidity, 123_456);
+    assert_eq!(params.maxAmt0In, U256::from(10u64));
+    assert_eq!(params.maxAmt1In, U256::from(20u64));
+
+    assert!(
+        open_maker_params(holder, 1, 0, 9_000_000, 1, U256::ZERO, U256::ZERO)
+            .err()
+            .unwrap()
+            .contains("tick upper")
+    );
+}
@@ -0,0 +1,113 @@
+// Unit tests for deposit slippage limits (src/services/perp/slippage.rs).
+
+use alloy::primitives::U256;
+use serial_test::serial;
+use the_beaconator::services::perp::slippage::{
+    DEFAULT_DEPOSIT_SLIPPAGE_BPS, MAX_DEPOSIT_SLIPPAGE_BPS, SLIPPAGE_EXCEEDED,
+    deposit_slippage_bps_from_env, estimate_maker_amounts, is_max_amt_exceeded, max_amount_in,
+    slippage_exceeded_message,
+};
+use the_beaconator::services::perp::validation::try_decode_revert_reason;
+
+/// sqrtPriceX96 at tick 0 (price 1.0).
+fn sqrt_price_at_tick_zero() -> U256 {
+    U256::from(1u8) << 96
+}
+
+/// sqrtPriceX96 for an arbitrary tick, computed the same floating-point way.
+fn sqrt_price_at(tick: i32) -> U256 {
+    let sqrt = 1.0001f64.powf(tick as f64 / 2.0) * 2f64.powi(96);
+    U256::from(sqrt as u128)
+}
+
+#[test]
+fn test_in_range_position_needs_both_tokens() {
+    let liquidity = 1_000_000_000_000u128;
+    let (amount0, amount1) = estimate_maker_amounts(sqrt_price_at_tick_zero(), -60, 60, liquidity);
+
+    assert!(amount0 > 0.0 && amount1 > 0.0);
+    // A range symmetric around price 1.0 needs (almost) equal amounts of each token.
+    let ratio = amount0 / amount1;
+    assert!((ratio - 1.0).abs() < 1e-2, "unexpected ratio {ratio}");
+}
+
+#[test]
+fn test_out_of_range_position_needs_one_token() {
+    let liquidity = 1_000_000_000_000u128;
+
+    // Price below the range: all token0.
+    let (amount0, amount1) = estimate_maker_amounts(sqrt_price_at(-1_000), -60, 60, liquidity);
+    assert!(amount0 > 0.0);
+    assert_eq!(amount1, 0.0);
+
+    // Price above the range: all token1.
+    let (amount0, amount1) = estimate_maker_amounts(sqrt_price_at(1_000), -60, 60, liquidity);
+    assert_eq!(amount0, 0.0);
+    assert!(amount1 > 0.0);
+}
+
+#[test]
+fn test_max_amount_in_adds_buffer_and_rounds_up() {
+    // 1% over 1,000,000 = 1,010,000, plus rounding headroom.
+    assert_eq!(max_amount_in(1_000_000.0, 100), U256::from(1_010_002u64));
+    assert!(max_amount_in(1_000_000.0, 0) > U256::from(1_000_000u64));
+    // Nothing expected still leaves a unit of headroom, not zero.
+    assert_eq!(max_amount_in(0.0, 100), U256::from(1u8));
+}
+
+#[test]
+fn test_max_amount_in_saturates() {
+    assert_eq!(max_amount_in(f64::MAX, 100), U256::MAX);
+    assert_eq!(max_amount_in(f64::INFINITY, 100), U256::MAX);
+}
+
+#[test]
+fn test_max_amt_exceeded_revert_maps_to_slippage() {
+    let decoded = try_decode_revert_reason(&"execution reverted, data: 0x24f14ba6")
+        .expect("MaxAmtExceeded selector decodes");
+    assert!(is_max_amt_exceeded(&decoded));
+
+    let other = try_decode_revert_reason(&"execution reverted, data: 0x38f5e1a7")
+        .expect("MarginTooLow selector decodes");
+    assert!(!is_max_amt_exceeded(&other));
+
+    let message = slippage_exceeded_message(U256::from(10u8), U256::from(20u8));
+    assert!(message.starts_with(SLIPPAGE_EXCEEDED));
+    assert!(message.contains("max_amt0_in=10"));
+    assert!(message.contains("max_amt1_in=20"));
+}
+
+#[test]
+#[serial]
+fn test_slippage_bps_from_env() {
+    // SAFETY: #[serial] guarantees no concurrent env access from other tests.
+    unsafe {
+        std::env::remove_var("DEPOSIT_SLIPPAGE_BPS");
+    }
+    assert_eq!(
+        deposit_slippage_bps_from_env(),
+        DEFAULT_DEPOSIT_SLIPPAGE_BPS
+    );
+
+    unsafe {
+        std::env::set_var("DEPOSIT_SLIPPAGE_BPS", " 250 ");
+    }
+    assert_eq!(deposit_slippage_bps_from_env(), 250);
+
+    unsafe {
+        std::env::set_var("DEPOSIT_SLIPPAGE_BPS", "not-a-number");
+    }
+    assert_eq!(
+        deposit_slippage_bps_from_env(),
+        DEFAULT_DEPOSIT_SLIPPAGE_BPS
+    );
+
+    unsafe {
+        std::env::set_var("DEPOSIT_SLIPPAGE_BPS", "50000");
+    }
+    assert_eq!(deposit_slippage_bps_from_env(), MAX_DEPOSIT_SLIPPAGE_BPS);
+
+    unsafe {
+        std::env::remove_var("DEPOSIT_SLIPPAGE_BPS");
+    }
+}
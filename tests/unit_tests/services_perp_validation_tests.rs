@@ -1,7 +1,9 @@
 // Unit tests for the v0.1.0 perp validation / error decoder.
 // Selectors come from `cast sig "<ErrorName>()"` against perpcity-contracts@v0.1.0.
 
-use the_beaconator::services::perp::validation::{ContractErrorDecoder, try_decode_revert_reason};
+use the_beaconator::services::perp::validation::{
+    ContractErrorDecoder, resimulated_revert_reason, try_decode_revert_reason,
+};
 
 #[cfg(test)]
 mod contract_error_decoder_tests {
@@ -166,6 +168,30 @@ mod contract_error_decoder_tests {
         assert_contains("0x499fddb1", "ProtocolFeeTooHigh");
     }
 
+    // ---- Solady ERC721 (position NFTs) ----
+
+    #[test]
+    fn test_decode_token_does_not_exist() {
+        assert_contains("0xceea21b6", "TokenDoesNotExist");
+    }
+
+    #[test]
+    fn test_decode_not_owner_nor_approved() {
+        assert_contains("0x4b6e7f18", "NotOwnerNorApproved");
+    }
+
+    // ---- Beacon verifiers ----
+
+    #[test]
+    fn test_decode_proof_already_used() {
+        assert_contains("0xc9838a65", "ProofAlreadyUsed");
+    }
+
+    #[test]
+    fn test_decode_invalid_proof() {
+        assert_contains("0x09bde339", "InvalidProof");
+    }
+
     // ---- Solady SafeCastLib (parameterized) ----
 
     #[test]
@@ -208,6 +234,94 @@ mod contract_error_decoder_tests {
         assert!(result.is_none());
     }
 
+    // ---- ABI-standard Error(string) / Panic(uint256) ----
+
+    #[test]
+    fn test_decode_error_string() {
+        // revert("Insufficient balance")
+        let error_data = concat!(
+            "0x08c379a0",
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000014",
+            "496e73756666696369656e742062616c616e6365000000000000000000000000"
+        );
+        assert_eq!(
+            ContractErrorDecoder::decode_error_data(error_data).as_deref(),
+            Some("Revert reason: Insufficient balance")
+        );
+    }
+
+    #[test]
+    fn test_decode_error_string_multi_word() {
+        // OpenZeppelin ERC20 v4 allowance revert (40 bytes, spans two words)
+        let error_data = concat!(
+            "0x08c379a0",
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000028",
+            "45524332303a207472616e7366657220616d6f756e7420657863656564732061",
+            "6c6c6f77616e6365000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            ContractErrorDecoder::decode_error_data(error_data).as_deref(),
+            Some("Revert reason: ERC20: transfer amount exceeds allowance")
+        );
+    }
+
+    #[test]
+    fn test_decode_error_string_truncated() {
+        // Length word claims 0x14 bytes but the payload stops short.
+        let error_data = concat!(
+            "0x08c379a0",
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000014",
+            "496e7375"
+        );
+        assert!(ContractErrorDecoder::decode_error_data(error_data).is_none());
+    }
+
+    fn panic_payload(code: &str) -> String {
+        format!("0x4e487b71{code:0>64}")
+    }
+
+    #[test]
+    fn test_decode_panic_assert() {
+        assert_contains(&panic_payload("01"), "Panic(0x01): assertion failed");
+    }
+
+    #[test]
+    fn test_decode_panic_arithmetic_overflow() {
+        assert_contains(
+            &panic_payload("11"),
+            "Panic(0x11): arithmetic overflow or underflow",
+        );
+    }
+
+    #[test]
+    fn test_decode_panic_division_by_zero() {
+        assert_contains(
+            &panic_payload("12"),
+            "Panic(0x12): division or modulo by zero",
+        );
+    }
+
+    #[test]
+    fn test_decode_panic_array_out_of_bounds() {
+        assert_contains(
+            &panic_payload("32"),
+            "Panic(0x32): array index out of bounds",
+        );
+    }
+
+    #[test]
+    fn test_decode_panic_unknown_code() {
+        assert_contains(&panic_payload("99"), "Panic(0x99): unknown panic code");
+    }
+
+    #[test]
+    fn test_decode_panic_missing_code() {
+        assert!(ContractErrorDecoder::decode_error_data("0x4e487b71").is_none());
+    }
+
     #[test]
     fn test_parameterless_errors_work_with_trailing_data() {
         let error_data = concat!(
@@ -244,6 +358,19 @@ mod try_decode_revert_reason_tests {
         );
     }
 
+    #[test]
+    fn test_resimulated_revert_reason() {
+        let decoded =
+            resimulated_revert_reason::<(), _>(Err("execution reverted: 0x10074548"), None);
+        assert!(decoded.contains("ZeroLiquidity"), "{decoded}");
+
+        let raw = resimulated_revert_reason::<(), _>(Err("connection refused"), None);
+        assert_eq!(raw, "connection refused");
+
+        let passed = resimulated_revert_reason::<(), &str>(Ok(()), None);
+        assert!(passed.contains("re-simulation succeeded"), "{passed}");
+    }
+
     #[test]
     fn test_decode_revert_no_reason() {
         let error = "execution reverted";
@@ -287,6 +414,35 @@ mod try_decode_revert_reason_tests {
         );
     }
 
+    #[test]
+    fn test_decode_revert_with_encoded_error_string() {
+        let error = concat!(
+            "server returned an error response: error code 3: execution reverted, data: \"",
+            "0x08c379a0",
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000014",
+            "496e73756666696369656e742062616c616e6365000000000000000000000000",
+            "\""
+        );
+        assert_eq!(
+            try_decode_revert_reason(&error).as_deref(),
+            Some("Revert reason: Insufficient balance")
+        );
+    }
+
+    #[test]
+    fn test_decode_revert_with_panic() {
+        let error = concat!(
+            "execution reverted: 0x4e487b71",
+            "0000000000000000000000000000000000000000000000000000000000000011"
+        );
+        assert!(
+            try_decode_revert_reason(&error)
+                .unwrap()
+                .contains("arithmetic overflow")
+        );
+    }
+
     #[test]
     fn test_decode_revert_quoted_reason() {
         let error = "execution reverted: \"custom error message\"";
@@ -329,3 +485,266 @@ mod try_decode_revert_reason_tests {
         assert!(msg.contains("MarginTooLow"), "got {msg}");
     }
 }
+
+#[cfg(test)]
+mod error_registry_tests {
+    use alloy::json_abi::JsonAbi;
+    use alloy::primitives::Selector;
+    use the_beaconator::abis::load_abi;
+    use the_beaconator::services::perp::ErrorSelectorRegistry;
+    use the_beaconator::services::perp::validation::{
+        ContractErrorDecoder, try_decode_revert_reason_with,
+    };
+
+    fn bundled() -> ErrorSelectorRegistry {
+        ErrorSelectorRegistry::from_bundled_abis().expect("bundled ABIs parse")
+    }
+
+    /// Registry with one parameterized error not in any bundled ABI.
+    fn with_custom_error() -> ErrorSelectorRegistry {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{"type":"error","name":"InsufficientBalance","inputs":[
+                {"name":"account","type":"address","internalType":"address"},
+                {"name":"needed","type":"uint256","internalType":"uint256"}]}]"#,
+        )
+        .unwrap();
+        let mut registry = bundled();
+        registry.add_abi("Token", &abi);
+        registry
+    }
+
+    // InsufficientBalance(0x00000000000000000000000000000000000000aa, 1500000)
+    const INSUFFICIENT_BALANCE: &str = concat!(
+        "0xf6deaa04",
+        "00000000000000000000000000000000000000000000000000000000000000aa",
+        "000000000000000000000000000000000000000000000000000000000016e360"
+    );
+
+    #[test]
+    fn test_bundled_abis_register_errors() {
+        let registry = bundled();
+        assert!(!registry.is_empty());
+
+        // Unauthorized() is declared by several Ownable contracts; one entry, all contracts.
+        let unauthorized = registry
+            .lookup(Selector::from([0x82, 0xb4, 0x29, 0x00]))
+            .expect("Unauthorized() registered");
+        assert_eq!(unauthorized.signature(), "Unauthorized()");
+        assert!(unauthorized.contracts.len() > 1);
+        assert!(unauthorized.contracts.iter().any(|c| c == "BeaconRegistry"));
+    }
+
+    #[test]
+    fn test_malformed_abis_are_all_reported() {
+        let errors = ErrorSelectorRegistry::from_abis(&[
+            ("Good", r#"[{"type":"error","name":"Oops","inputs":[]}]"#),
+            ("Truncated", r#"[{"type":"error","name":"#),
+            ("NotAnAbi", r#"{"abi": 42}"#),
+        ])
+        .expect_err("malformed ABIs are errors, not panics");
+
+        let files: Vec<&str> = errors.iter().map(|e| e.file.as_str()).collect();
+        assert_eq!(files, ["abis/Truncated.json", "abis/NotAnAbi.json"]);
+        let message = errors[0].to_string();
+        assert!(
+            message.starts_with("Failed to load abis/Truncated.json: "),
+            "{message}"
+        );
+        assert!(message.contains("EOF"), "{message}");
+    }
+
+    #[test]
+    fn test_load_abi() {
+        let abi = load_abi("Good", r#"[{"type":"error","name":"Oops","inputs":[]}]"#).unwrap();
+        assert_eq!(abi.errors().count(), 1);
+        assert_eq!(load_abi("Bad", "").unwrap_err().file, "abis/Bad.json");
+    }
+
+    #[test]
+    fn test_decode_parameterless_abi_error() {
+        assert_eq!(
+            bundled().decode("0xceea21b6").as_deref(),
+            Some("TokenDoesNotExist()")
+        );
+    }
+
+    #[test]
+    fn test_decode_parameterized_abi_error() {
+        let registry = with_custom_error();
+        let known = registry
+            .lookup(Selector::from([0xf6, 0xde, 0xaa, 0x04]))
+            .unwrap();
+        assert_eq!(known.param_types, vec!["address", "uint256"]);
+        assert_eq!(
+            registry.decode(INSUFFICIENT_BALANCE).as_deref(),
+            Some(
+                "InsufficientBalance(account: 0x00000000000000000000000000000000000000AA, \
+                 needed: 1500000)"
+            )
+        );
+    }
+
+    #[test]
+    fn test_decode_unknown_or_malformed_returns_none() {
+        let registry = bundled();
+        assert!(registry.decode("0xdeadbeef").is_none());
+        assert!(registry.decode("0x12").is_none());
+        assert!(registry.decode("0xzzzzzzzz").is_none());
+        // Known selector with truncated params.
+        assert!(with_custom_error().decode("0xf6deaa0400").is_none());
+    }
+
+    #[test]
+    fn test_decoder_falls_back_to_registry_for_unknown_selectors() {
+        let registry = with_custom_error();
+        assert!(
+            ContractErrorDecoder::decode_error_data(INSUFFICIENT_BALANCE)
+                .unwrap()
+                .starts_with("Unknown contract error")
+        );
+        assert!(
+            ContractErrorDecoder::decode_error_data_with(INSUFFICIENT_BALANCE, Some(&registry))
+                .unwrap()
+                .starts_with("InsufficientBalance(account: ")
+        );
+    }
+
+    #[test]
+    fn test_hand_written_messages_take_precedence() {
+        // NotPoolManager is in the Perp ABI and in the hand-written table.
+        let decoded =
+            ContractErrorDecoder::decode_error_data_with("0xae18210a", Some(&bundled())).unwrap();
+        assert!(decoded.contains("caller is not the Uniswap V4 PoolManager"));
+    }
+
+    #[test]
+    fn test_revert_pipeline_uses_registry() {
+        let registry = with_custom_error();
+        let error = format!("execution reverted, data: \"{INSUFFICIENT_BALANCE}\"");
+        let decoded = try_decode_revert_reason_with(&error, Some(&registry)).unwrap();
+        assert!(decoded.contains("needed: 1500000"), "got {decoded}");
+    }
+}
+
+#[cfg(test)]
+mod tick_range_tests {
+    use the_beaconator::services::perp::validation::{
+        DEFAULT_TICK_LOWER, DEFAULT_TICK_SPACING, DEFAULT_TICK_UPPER, MAX_TICK, MIN_TICK,
+        validate_tick_range,
+    };
+
+    #[test]
+    fn test_defaults_are_valid() {
+        assert!(
+            validate_tick_range(DEFAULT_TICK_SPACING, DEFAULT_TICK_LOWER, DEFAULT_TICK_UPPER)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_custom_aligned_range_is_valid() {
+        assert!(validate_tick_range(60, -600, 1200).is_ok());
+    }
+
+    #[test]
+    fn test_lower_not_below_upper_rejected() {
+        let err = validate_tick_range(30, 600, 600).unwrap_err();
+        assert!(err.contains("must be less than tick_upper"), "got {err}");
+        let err = validate_tick_range(30, 900, 600).unwrap_err();
+        assert!(err.contains("must be less than tick_upper"), "got {err}");
+    }
+
+    #[test]
+    fn test_out_of_bounds_rejected() {
+        let err = validate_tick_range(1, MIN_TICK - 1, 0).unwrap_err();
+        assert!(err.contains("MIN_TICK"), "got {err}");
+        let err = validate_tick_range(1, 0, MAX_TICK + 1).unwrap_err();
+        assert!(err.contains("MAX_TICK"), "got {err}");
+        assert!(validate_tick_range(1, MIN_TICK, MAX_TICK).is_ok());
+    }
+
+    #[test]
+    fn test_misaligned_ticks_rejected() {
+        let err = validate_tick_range(30, 25, 60).unwrap_err();
+        assert!(err.contains("tick_lower (25)"), "got {err}");
+        let err = validate_tick_range(30, 30, 65).unwrap_err();
+        assert!(err.contains("tick_upper (65)"), "got {err}");
+    }
+
+    #[test]
+    fn test_non_positive_spacing_rejected() {
+        assert!(validate_tick_range(0, 0, 60).is_err());
+        assert!(validate_tick_range(-30, 0, 60).is_err());
+    }
+}
+
+#[cfg(test)]
+mod usdc_balance_tests {
+    use alloy::primitives::U256;
+    use the_beaconator::services::perp::validation::{format_usdc, validate_usdc_balance};
+
+    #[test]
+    fn test_format_usdc() {
+        assert_eq!(format_usdc(U256::ZERO), "0.000000");
+        assert_eq!(format_usdc(U256::from(1_500_000u64)), "1.500000");
+        assert_eq!(format_usdc(U256::from(42u64)), "0.000042");
+        assert_eq!(format_usdc(U256::from(10_000_000_000u64)), "10000.000000");
+    }
+
+    #[test]
+    fn test_sufficient_balance_accepted() {
+        let margin = U256::from(5_000_000u64);
+        assert!(validate_usdc_balance(margin, margin).is_ok());
+        assert!(validate_usdc_balance(margin + U256::from(1u64), margin).is_ok());
+    }
+
+    #[test]
+    fn test_insufficient_balance_rejected() {
+        let err =
+            validate_usdc_balance(U256::from(2_500_000u64), U256::from(5_000_000u64)).unwrap_err();
+        assert_eq!(
+            err,
+            "insufficient USDC balance: have 2.500000 USDC, need 5.000000 USDC"
+        );
+    }
+}
+
+#[cfg(test)]
+mod close_revert_tests {
+    use alloy::primitives::{Address, U256};
+    use the_beaconator::services::error::BeaconError;
+    use the_beaconator::services::perp::close_revert_error;
+
+    fn error(decoded: &str) -> Option<BeaconError> {
+        close_revert_error(U256::from(7u64), Address::ZERO, decoded)
+    }
+
+    #[test]
+    fn test_unknown_ids_are_not_found() {
+        for decoded in [
+            "TokenDoesNotExist: no position NFT with this id",
+            "PositionDoesNotExist()",
+        ] {
+            let err = error(decoded).unwrap();
+            assert!(matches!(err, BeaconError::NotRegistered(_)), "{err:?}");
+            assert!(err.to_string().contains("position 7"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_taker_and_foreign_positions_are_not_closable() {
+        for decoded in [
+            "NonMakerPosition: position is not a maker position",
+            "NotOwnerNorApproved()",
+            "UnauthorizedCaller: caller is not authorized for this position",
+        ] {
+            let err = error(decoded).unwrap();
+            assert!(matches!(err, BeaconError::InvalidState(_)), "{err:?}");
+        }
+    }
+
+    #[test]
+    fn test_other_reverts_are_left_to_the_caller() {
+        assert_eq!(error("MaxAmtExceeded: deposit/withdraw exceeded"), None);
+    }
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use alloy::primitives::B256;
+use alloy::providers::ProviderBuilder;
+use the_beaconator::config::ConfigReader;
+use the_beaconator::services::rpc::ReadRetryPolicy;
+use the_beaconator::services::transaction::{
+    ConfirmationPolicy, DEFAULT_CONFIRMATION_BLOCKS, confirmation_depth, wait_for_confirmations,
+};
+
+#[test]
+fn test_confirmation_depth() {
+    assert_eq!(confirmation_depth(105, 100), 5);
+    assert_eq!(confirmation_depth(100, 100), 0);
+    // A lagging endpoint may report a head below the receipt's block.
+    assert_eq!(confirmation_depth(99, 100), 0);
+}
+
+#[test]
+fn test_policy_is_final() {
+    let policy = ConfirmationPolicy::new(3, Duration::from_millis(10), Duration::from_secs(1));
+    assert!(!policy.is_final(102, 100));
+    assert!(policy.is_final(103, 100));
+    assert!(policy.is_final(110, 100));
+
+    let immediate = ConfirmationPolicy::new(0, Duration::from_millis(10), Duration::from_secs(1));
+    assert!(immediate.is_final(100, 100));
+}
+
+#[test]
+#[serial_test::serial]
+fn test_policy_from_config() {
+    unsafe {
+        std::env::remove_var("CONFIRMATION_BLOCKS");
+    }
+    let mut config = ConfigReader::new();
+    assert_eq!(
+        ConfirmationPolicy::from_config(&mut config).blocks(),
+        DEFAULT_CONFIRMATION_BLOCKS
+    );
+
+    unsafe {
+        std::env::set_var("CONFIRMATION_BLOCKS", "5");
+    }
+    assert_eq!(ConfirmationPolicy::from_config(&mut config).blocks(), 5);
+    assert!(config.finish().is_ok());
+
+    unsafe {
+        std::env::set_var("CONFIRMATION_BLOCKS", "-1");
+    }
+    let mut config = ConfigReader::new();
+    ConfirmationPolicy::from_config(&mut config);
+    let err = config.finish().unwrap_err();
+    assert!(err.problems[0].starts_with("Invalid CONFIRMATION_BLOCKS '-1'"));
+
+    unsafe {
+        std::env::remove_var("CONFIRMATION_BLOCKS");
+    }
+}
+
+#[tokio::test]
+async fn test_zero_confirmations_skip_the_provider() {
+    // Nothing listens here: any RPC call would fail.
+    let provider = ProviderBuilder::new().connect_http("http://127.0.0.1:1".parse().unwrap());
+    let policy = ConfirmationPolicy::new(0, Duration::from_millis(10), Duration::from_secs(1));
+
+    let depth = wait_for_confirmations(
+        &provider,
+        &policy,
+        &ReadRetryPolicy::default(),
+        B256::ZERO,
+        100,
+    )
+    .await;
+    assert_eq!(depth, Ok(0));
+}
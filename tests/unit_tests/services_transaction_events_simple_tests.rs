@@ -105,3 +105,67 @@ fn test_event_parsing_address_mismatch_still_not_found() {
     assert!(parse_perp_created_event(&receipt, non_emitting_factory).is_err());
     assert!(parse_maker_opened_event(&receipt, non_emitting_factory).is_err());
 }
+
+fn unrelated_log(emitter: Address) -> alloy::rpc::types::Log {
+    alloy::rpc::types::Log {
+        inner: alloy::primitives::Log::new_unchecked(
+            emitter,
+            vec![alloy::primitives::B256::repeat_byte(0xab)],
+            alloy::primitives::Bytes::new(),
+        ),
+        ..Default::default()
+    }
+}
+
+fn mock_receipt_with_logs(
+    logs: Vec<alloy::rpc::types::Log>,
+) -> alloy::rpc::types::TransactionReceipt {
+    use alloy::consensus::{Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom};
+
+    let mut receipt = create_simple_mock_receipt();
+    receipt.inner = ReceiptEnvelope::Legacy(ReceiptWithBloom {
+        receipt: Receipt {
+            status: Eip658Value::Eip658(true),
+            cumulative_gas_used: 21000u64,
+            logs,
+        },
+        logs_bloom: Default::default(),
+    });
+    receipt
+}
+
+#[test]
+fn test_event_not_found_with_empty_logs_mentions_no_logs() {
+    let receipt = create_simple_mock_receipt();
+    let expected = Address::from([1u8; 20]);
+
+    let err = parse_perp_created_event(&receipt, expected).unwrap_err();
+    assert!(err.contains("PerpCreated event not found"));
+    assert!(err.contains("no logs"), "got {err}");
+    assert!(err.contains(&expected.to_string()), "got {err}");
+}
+
+#[test]
+fn test_event_not_found_lists_unrelated_log_emitters() {
+    let emitter_a = Address::from([0xaa; 20]);
+    let emitter_b = Address::from([0xbb; 20]);
+    let receipt = mock_receipt_with_logs(vec![
+        unrelated_log(emitter_a),
+        unrelated_log(emitter_b),
+        unrelated_log(emitter_a),
+    ]);
+    let expected = Address::from([1u8; 20]);
+
+    for err in [
+        parse_index_updated_event(&receipt, expected).unwrap_err(),
+        parse_perp_created_event(&receipt, expected).unwrap_err(),
+        parse_maker_opened_event(&receipt, expected).unwrap_err(),
+    ] {
+        assert!(err.contains("event not found in transaction receipt"));
+        assert!(err.contains("3 log(s)"), "got {err}");
+        assert!(err.contains(&emitter_a.to_string()), "got {err}");
+        assert!(err.contains(&emitter_b.to_string()), "got {err}");
+        // Each emitter is listed once even when it emitted several logs.
+        assert_eq!(err.matches(&emitter_a.to_string()).count(), 1, "got {err}");
+    }
+}
@@ -0,0 +1,207 @@
+// Tests for chunked log scans (src/services/transaction/events.rs)
+
+use alloy::rpc::types::Log;
+use futures::{StreamExt, TryStreamExt};
+use serial_test::serial;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, Ordering};
+use the_beaconator::services::rpc::ReadRetryPolicy;
+use the_beaconator::services::transaction::events::{
+    DEFAULT_LOG_SCAN_CHUNK_BLOCKS, log_scan_chunk_blocks_from_env, log_scan_chunks, scan_chunked,
+};
+
+fn fast_policy(max_retries: u32) -> ReadRetryPolicy {
+    ReadRetryPolicy::new(max_retries, 1)
+}
+
+/// One log per block in `start..=end`.
+fn logs_for(start: u64, end: u64) -> Vec<Log> {
+    (start..=end)
+        .map(|block| Log {
+            block_number: Some(block),
+            ..Default::default()
+        })
+        .collect()
+}
+
+#[test]
+fn test_log_scan_chunks_splits_range() {
+    let chunks: Vec<_> = log_scan_chunks(100, 349, 100).collect();
+    assert_eq!(chunks, vec![(100, 199), (200, 299), (300, 349)]);
+
+    // Exact multiple: no trailing empty chunk.
+    let chunks: Vec<_> = log_scan_chunks(0, 199, 100).collect();
+    assert_eq!(chunks, vec![(0, 99), (100, 199)]);
+
+    let chunks: Vec<_> = log_scan_chunks(7, 7, 100).collect();
+    assert_eq!(chunks, vec![(7, 7)]);
+}
+
+#[test]
+fn test_log_scan_chunks_zero_means_one_request() {
+    let chunks: Vec<_> = log_scan_chunks(5, 1_000_000, 0).collect();
+    assert_eq!(chunks, vec![(5, 1_000_000)]);
+}
+
+#[test]
+fn test_log_scan_chunks_empty_range() {
+    assert_eq!(log_scan_chunks(10, 9, 100).count(), 0);
+}
+
+#[test]
+fn test_log_scan_chunks_near_u64_max() {
+    let chunks: Vec<_> = log_scan_chunks(u64::MAX - 2, u64::MAX, 2).collect();
+    assert_eq!(
+        chunks,
+        vec![(u64::MAX - 2, u64::MAX - 1), (u64::MAX, u64::MAX)]
+    );
+}
+
+#[tokio::test]
+async fn test_scan_chunked_streams_every_chunk_in_order() {
+    let requested = Mutex::new(Vec::new());
+    let blocks: Vec<u64> = scan_chunked(
+        1,
+        25,
+        10,
+        fast_policy(0),
+        |start, end| {
+            requested.lock().unwrap().push((start, end));
+            async move { Ok::<_, String>(logs_for(start, end)) }
+        },
+        |log| log.block_number.filter(|block| block % 2 == 0),
+    )
+    .try_collect()
+    .await
+    .unwrap();
+
+    assert_eq!(
+        *requested.lock().unwrap(),
+        vec![(1, 10), (11, 20), (21, 25)]
+    );
+    assert_eq!(blocks, (2..=24).step_by(2).collect::<Vec<u64>>());
+}
+
+#[tokio::test]
+async fn test_scan_chunked_retries_transient_chunk_errors() {
+    let failures_left = AtomicU32::new(2);
+    let calls = AtomicU32::new(0);
+    let blocks: Vec<u64> = scan_chunked(
+        0,
+        29,
+        10,
+        fast_policy(3),
+        |start, end| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            // The middle chunk is rate limited twice before succeeding.
+            let fail = start == 10
+                && failures_left
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok();
+            async move {
+                if fail {
+                    Err("HTTP error 429 with body: rate limited".to_string())
+                } else {
+                    Ok(logs_for(start, end))
+                }
+            }
+        },
+        |log| log.block_number,
+    )
+    .try_collect()
+    .await
+    .unwrap();
+
+    assert_eq!(blocks, (0..=29).collect::<Vec<u64>>());
+    assert_eq!(calls.load(Ordering::SeqCst), 5);
+}
+
+#[tokio::test]
+async fn test_scan_chunked_stops_after_failed_chunk() {
+    let calls = AtomicU32::new(0);
+    let items: Vec<Result<u64, String>> = scan_chunked(
+        0,
+        29,
+        10,
+        fast_policy(3),
+        |start, end| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if start == 10 {
+                    Err("query returned more than 10000 results".to_string())
+                } else {
+                    Ok(logs_for(start, end))
+                }
+            }
+        },
+        |log| log.block_number,
+    )
+    .collect()
+    .await;
+
+    // First chunk's logs, then the error; the non-transient error is not retried and
+    // the last chunk is never requested.
+    assert_eq!(items.len(), 11);
+    assert!(items[..10].iter().all(Result::is_ok));
+    let error = items[10].as_ref().unwrap_err();
+    assert!(error.contains("blocks 10..=19"), "{error}");
+    assert!(error.contains("more than 10000 results"), "{error}");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_scan_chunked_is_lazy() {
+    let calls = AtomicU32::new(0);
+    let first = scan_chunked(
+        0,
+        99_999,
+        1_000,
+        fast_policy(0),
+        |start, end| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async move { Ok::<_, String>(logs_for(start, end)) }
+        },
+        |log| log.block_number.filter(|block| *block >= 1_500),
+    )
+    .boxed_local()
+    .try_next()
+    .await
+    .unwrap();
+
+    assert_eq!(first, Some(1_500));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+#[serial]
+fn test_log_scan_chunk_blocks_from_env() {
+    unsafe {
+        std::env::remove_var("LOG_SCAN_CHUNK_BLOCKS");
+    }
+    assert_eq!(
+        log_scan_chunk_blocks_from_env(),
+        DEFAULT_LOG_SCAN_CHUNK_BLOCKS
+    );
+
+    unsafe {
+        std::env::set_var("LOG_SCAN_CHUNK_BLOCKS", " 500 ");
+    }
+    assert_eq!(log_scan_chunk_blocks_from_env(), 500);
+
+    unsafe {
+        std::env::set_var("LOG_SCAN_CHUNK_BLOCKS", "0");
+    }
+    assert_eq!(log_scan_chunk_blocks_from_env(), 0);
+
+    unsafe {
+        std::env::set_var("LOG_SCAN_CHUNK_BLOCKS", "lots");
+    }
+    assert_eq!(
+        log_scan_chunk_blocks_from_env(),
+        DEFAULT_LOG_SCAN_CHUNK_BLOCKS
+    );
+
+    unsafe {
+        std::env::remove_var("LOG_SCAN_CHUNK_BLOCKS");
+    }
+}
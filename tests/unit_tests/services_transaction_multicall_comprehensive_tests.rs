@@ -1,3 +0,0 @@
-//! DELETED — tests for the removed `services::transaction::multicall` module.
-//! No longer compiled (mod declaration removed from tests/unit_tests/mod.rs);
-//! delete this file outright.
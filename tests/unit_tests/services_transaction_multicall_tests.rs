@@ -0,0 +1,209 @@
+// Tests for the typed Multicall3 helpers in services/transaction/multicall.rs
+
+use alloy::primitives::{Address, Bytes, U256, address};
+use alloy::providers::ProviderBuilder;
+use alloy::sol_types::{Revert, SolCall, SolError};
+use the_beaconator::routes::{IBeacon, IERC20, IMulticall3};
+use the_beaconator::services::rpc::ReadRetryPolicy;
+use the_beaconator::services::transaction::multicall::{
+    MULTICALL3_NOT_CONFIGURED, aggregate3_calls, decode_call_result, decode_call_results,
+    multicall_try, multicall3, prevalidate_with, try_aggregate_calls,
+};
+
+const BEACON_A: Address = address!("0x00000000000000000000000000000000000000aa");
+const BEACON_B: Address = address!("0x00000000000000000000000000000000000000bb");
+const BEACON_C: Address = address!("0x00000000000000000000000000000000000000cc");
+
+fn ok(value: U256) -> IMulticall3::Result {
+    IMulticall3::Result {
+        success: true,
+        returnData: value.to_be_bytes::<32>().to_vec().into(),
+    }
+}
+
+fn failed(return_data: Vec<u8>) -> IMulticall3::Result {
+    IMulticall3::Result {
+        success: false,
+        returnData: return_data.into(),
+    }
+}
+
+#[test]
+fn test_call_entries_encode_each_call_in_order() {
+    let calls = [
+        (BEACON_A, IBeacon::indexCall {}),
+        (BEACON_B, IBeacon::indexCall {}),
+    ];
+
+    let entries = try_aggregate_calls(&calls);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].target, BEACON_A);
+    assert_eq!(entries[1].target, BEACON_B);
+    assert_eq!(
+        entries[0].callData,
+        Bytes::from(IBeacon::indexCall {}.abi_encode())
+    );
+
+    let entries = aggregate3_calls(&calls, true);
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|entry| entry.allowFailure));
+    assert_eq!(entries[1].target, BEACON_B);
+    assert!(!aggregate3_calls(&calls, false)[0].allowFailure);
+}
+
+#[test]
+fn test_decode_mixed_batch_without_require_success() {
+    // What tryAggregate(false, ...) returns when some calls revert: every entry is
+    // decoded on its own and failures do not affect their neighbours.
+    let reason = Revert {
+        reason: "ERC20: not allowed".to_string(),
+    }
+    .abi_encode();
+    let results = [
+        ok(U256::from(1_000u64)),
+        failed(Vec::new()),
+        failed(reason),
+        failed(vec![0xde, 0xad, 0xbe, 0xef]),
+        ok(U256::from(7u64)),
+    ];
+
+    let decoded = decode_call_results::<IERC20::balanceOfCall>(&results, results.len()).unwrap();
+    assert_eq!(decoded.len(), 5);
+    assert_eq!(decoded[0], Ok(U256::from(1_000u64)));
+    assert_eq!(
+        decoded[1].as_ref().unwrap_err(),
+        "balanceOf(address) reverted"
+    );
+    assert_eq!(
+        decoded[2].as_ref().unwrap_err(),
+        "balanceOf(address) reverted: ERC20: not allowed"
+    );
+    assert!(
+        decoded[3]
+            .as_ref()
+            .unwrap_err()
+            .contains("revert data: 0xdeadbeef")
+    );
+    assert_eq!(decoded[4], Ok(U256::from(7u64)));
+}
+
+#[test]
+fn test_decode_rejects_success_without_return_data() {
+    // A call to an address without code succeeds with no return data.
+    let no_code = IMulticall3::Result {
+        success: true,
+        returnData: Bytes::new(),
+    };
+    let error = decode_call_result::<IBeacon::indexCall>(&no_code).unwrap_err();
+    assert!(error.contains("index()"), "{error}");
+    assert!(error.contains("undecodable"), "{error}");
+}
+
+#[test]
+fn test_decode_rejects_result_count_mismatch() {
+    let results = [ok(U256::from(1u64))];
+    let error = decode_call_results::<IBeacon::indexCall>(&results, 2).unwrap_err();
+    assert_eq!(error, "expected 2 multicall results, got 1");
+}
+
+#[tokio::test]
+async fn test_helpers_fail_without_multicall3_address() {
+    // Nothing listens here; the helpers must fail before making any request.
+    let provider = ProviderBuilder::new().connect_http("http://127.0.0.1:1".parse().unwrap());
+    let retry = ReadRetryPolicy::default();
+    let calls = [(BEACON_A, IBeacon::indexCall {})];
+
+    let error = multicall_try(&provider, &retry, None, false, &calls)
+        .await
+        .unwrap_err();
+    assert_eq!(error, MULTICALL3_NOT_CONFIGURED);
+
+    let error = multicall3(&provider, &retry, None, true, &calls)
+        .await
+        .unwrap_err();
+    assert_eq!(error, MULTICALL3_NOT_CONFIGURED);
+}
+
+#[tokio::test]
+async fn test_helpers_skip_the_call_for_an_empty_batch() {
+    let provider = ProviderBuilder::new().connect_http("http://127.0.0.1:1".parse().unwrap());
+    let calls: [(Address, IBeacon::indexCall); 0] = [];
+    let multicall = Some(address!("0xcA11bde05977b3631167028862bE2a173976CA11"));
+    let retry = ReadRetryPolicy::default();
+
+    assert!(
+        multicall_try(&provider, &retry, multicall, true, &calls)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        multicall3(&provider, &retry, multicall, false, &calls)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+fn three_updates() -> Vec<IMulticall3::Call3> {
+    let calls = [
+        (BEACON_A, IBeacon::indexCall {}),
+        (BEACON_B, IBeacon::indexCall {}),
+        (BEACON_C, IBeacon::indexCall {}),
+    ];
+    aggregate3_calls(&calls, false)
+}
+
+#[tokio::test]
+async fn test_prevalidate_drops_the_call_predicted_to_revert() {
+    let batch = prevalidate_with(three_updates(), true, |call| {
+        let target = call.target;
+        async move { Ok((target == BEACON_B).then(|| "ProofAlreadyUsed()".to_string())) }
+    })
+    .await;
+
+    assert_eq!(batch.kept, vec![0, 2]);
+    let targets: Vec<Address> = batch.calls.iter().map(|call| call.target).collect();
+    assert_eq!(targets, vec![BEACON_A, BEACON_C]);
+    assert_eq!(
+        batch.predicted_reverts,
+        vec![(1, "ProofAlreadyUsed()".to_string())]
+    );
+    assert!(batch.unchecked.is_empty());
+}
+
+#[tokio::test]
+async fn test_prevalidate_keeps_calls_it_could_not_simulate() {
+    // An RPC failure is not a revert prediction: the call stays in the batch.
+    let batch = prevalidate_with(three_updates(), true, |call| {
+        let target = call.target;
+        async move {
+            if target == BEACON_C {
+                Err("connection refused".to_string())
+            } else {
+                Ok(None)
+            }
+        }
+    })
+    .await;
+
+    assert_eq!(batch.kept, vec![0, 1, 2]);
+    assert_eq!(batch.calls.len(), 3);
+    assert!(batch.predicted_reverts.is_empty());
+    assert_eq!(batch.unchecked, vec![(2, "connection refused".to_string())]);
+}
+
+#[tokio::test]
+async fn test_prevalidate_is_opt_in() {
+    let simulated = std::sync::atomic::AtomicUsize::new(0);
+    let batch = prevalidate_with(three_updates(), false, |_| {
+        simulated.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        async { Ok(Some("would revert".to_string())) }
+    })
+    .await;
+
+    assert_eq!(simulated.into_inner(), 0, "no eth_call without prevalidate");
+    assert_eq!(batch.kept, vec![0, 1, 2]);
+    assert_eq!(batch.calls.len(), 3);
+    assert!(batch.predicted_reverts.is_empty());
+}
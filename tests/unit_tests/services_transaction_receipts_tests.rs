@@ -0,0 +1,124 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use alloy::primitives::B256;
+use the_beaconator::services::transaction::ReceiptCache;
+
+/// Stand-in for `eth_getTransactionReceipt` that counts calls and returns `response`.
+struct CountingProvider {
+    calls: AtomicUsize,
+    response: Result<Option<u64>, String>,
+}
+
+impl CountingProvider {
+    fn new(response: Result<Option<u64>, String>) -> Self {
+        Self {
+            calls: AtomicUsize::new(0),
+            response,
+        }
+    }
+
+    async fn get_transaction_receipt(&self, _tx_hash: B256) -> Result<Option<u64>, String> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.response.clone()
+    }
+
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+fn tx_hash() -> B256 {
+    B256::repeat_byte(0xab)
+}
+
+#[tokio::test]
+async fn test_repeated_lookup_hits_rpc_once() {
+    let cache = ReceiptCache::<u64>::default();
+    let provider = CountingProvider::new(Ok(Some(7)));
+
+    for _ in 0..2 {
+        let receipt = cache
+            .get_or_fetch(tx_hash(), || provider.get_transaction_receipt(tx_hash()))
+            .await;
+        assert_eq!(receipt, Ok(Some(7)));
+    }
+    assert_eq!(provider.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_concurrent_lookups_share_one_rpc_call() {
+    let cache = Arc::new(ReceiptCache::<u64>::default());
+    let provider = Arc::new(CountingProvider::new(Ok(Some(7))));
+
+    let lookups = (0..5).map(|_| {
+        let cache = Arc::clone(&cache);
+        let provider = Arc::clone(&provider);
+        tokio::spawn(async move {
+            cache
+                .get_or_fetch(tx_hash(), || provider.get_transaction_receipt(tx_hash()))
+                .await
+        })
+    });
+    for lookup in lookups.collect::<Vec<_>>() {
+        assert_eq!(lookup.await.unwrap(), Ok(Some(7)));
+    }
+    assert_eq!(provider.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_pending_receipt_is_not_cached() {
+    let cache = ReceiptCache::<u64>::default();
+    let provider = CountingProvider::new(Ok(None));
+
+    for _ in 0..2 {
+        let receipt = cache
+            .get_or_fetch(tx_hash(), || provider.get_transaction_receipt(tx_hash()))
+            .await;
+        assert_eq!(receipt, Ok(None));
+    }
+    assert_eq!(provider.calls(), 2);
+    assert!(cache.is_empty().await);
+}
+
+#[tokio::test]
+async fn test_lookup_error_is_not_cached() {
+    let cache = ReceiptCache::<u64>::default();
+    let provider = CountingProvider::new(Err("connection reset".to_string()));
+
+    for _ in 0..2 {
+        let receipt = cache
+            .get_or_fetch(tx_hash(), || provider.get_transaction_receipt(tx_hash()))
+            .await;
+        assert!(receipt.is_err());
+    }
+    assert_eq!(provider.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_expired_receipt_is_refetched() {
+    let cache = ReceiptCache::<u64>::new(Duration::ZERO, 10);
+    let provider = CountingProvider::new(Ok(Some(7)));
+
+    for _ in 0..2 {
+        cache
+            .get_or_fetch(tx_hash(), || provider.get_transaction_receipt(tx_hash()))
+            .await
+            .unwrap();
+    }
+    assert_eq!(provider.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_capacity_evicts_oldest() {
+    let cache = ReceiptCache::<u64>::new(Duration::from_secs(60), 2);
+    cache.insert(B256::repeat_byte(1), 1).await;
+    cache.insert(B256::repeat_byte(2), 2).await;
+    cache.insert(B256::repeat_byte(3), 3).await;
+
+    assert_eq!(cache.len().await, 2);
+    assert_eq!(cache.get(&B256::repeat_byte(1)).await, None);
+    assert_eq!(cache.get(&B256::repeat_byte(3)).await, Some(3));
+}
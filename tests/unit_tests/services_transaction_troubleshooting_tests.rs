@@ -0,0 +1,117 @@
+// Unit tests for troubleshooting reports (src/services/transaction/troubleshooting.rs)
+
+use alloy::primitives::Address;
+use the_beaconator::models::{DeployPerpForBeaconRequest, ErrorCategory};
+use the_beaconator::services::transaction::troubleshooting::{
+    classify_error, troubleshooting_report,
+};
+
+#[test]
+fn test_classify_deploy_errors() {
+    let cases = [
+        (
+            "Failed to acquire wallet: No available wallets",
+            ErrorCategory::WalletUnavailable,
+        ),
+        (
+            "createPerp send failed: insufficient funds for gas * price + value",
+            ErrorCategory::InsufficientFunds,
+        ),
+        (
+            "createPerp send failed: nonce too low",
+            ErrorCategory::NonceConflict,
+        ),
+        (
+            "createPerp reverted: EmaWindowTooLow: emaWindow must be > 0 (uint24)",
+            ErrorCategory::ContractExecutionReverted,
+        ),
+        (
+            "createPerp transaction reverted: no revert reason available (tx 0xabc)",
+            ErrorCategory::ContractExecutionReverted,
+        ),
+        (
+            "Beacon address 0x01 has no deployed code (not a contract)",
+            ErrorCategory::InvalidContractAddress,
+        ),
+        (
+            "Timeout waiting for createPerp receipt",
+            ErrorCategory::TransactionTimeout,
+        ),
+        (
+            "createPerp receipt 0xabc not found after 4 attempts",
+            ErrorCategory::TransactionTimeout,
+        ),
+        (
+            "Failed to get block number: error sending request: connection refused",
+            ErrorCategory::RpcConnectionError,
+        ),
+        ("something unexpected", ErrorCategory::Unknown),
+    ];
+    for (error, expected) in cases {
+        assert_eq!(classify_error(error), expected, "{error}");
+    }
+}
+
+#[test]
+fn test_insufficient_funds_wins_over_revert() {
+    // Preflight simulation of an unfunded send reports both.
+    assert_eq!(
+        classify_error("execution reverted: insufficient funds for transfer"),
+        ErrorCategory::InsufficientFunds
+    );
+}
+
+#[test]
+fn test_report_carries_hints_and_context() {
+    let beacon = Address::repeat_byte(0xbe);
+    let report = troubleshooting_report(
+        "deploy_perp_for_beacon",
+        "createPerp reverted: StartingPriceTooLow",
+        [("beacon", beacon), ("owner", Address::ZERO)],
+    );
+
+    assert_eq!(report.operation, "deploy_perp_for_beacon");
+    assert_eq!(report.category, ErrorCategory::ContractExecutionReverted);
+    assert_eq!(report.error, "createPerp reverted: StartingPriceTooLow");
+    assert!(!report.probable_causes.is_empty());
+    assert!(!report.suggested_actions.is_empty());
+    assert_eq!(report.context["beacon"], beacon.to_string());
+    assert_eq!(report.context.len(), 2);
+}
+
+#[test]
+fn test_report_serializes_category_label() {
+    let report = troubleshooting_report(
+        "deploy_perp_for_beacon",
+        "insufficient funds",
+        [("beacon", "0x01")],
+    );
+    let json = serde_json::to_value(&report).unwrap();
+
+    assert_eq!(json["category"], "Insufficient Funds");
+    assert_eq!(json["category"], report.category.label());
+    assert!(json["probable_causes"].is_array());
+    assert!(json["suggested_actions"].is_array());
+    assert_eq!(json["context"]["beacon"], "0x01");
+}
+
+#[test]
+fn test_deploy_request_verbose_defaults_off() {
+    let body = r#"{
+        "beacon_address": "0x1111111111111111111111111111111111111111",
+        "owner": "0x2222222222222222222222222222222222222222",
+        "name": "Test",
+        "symbol": "TEST",
+        "token_uri": "",
+        "ema_window": 3600
+    }"#;
+    let request: DeployPerpForBeaconRequest = serde_json::from_str(body).unwrap();
+    assert!(!request.verbose);
+
+    let verbose = body.replace(
+        "\"ema_window\": 3600",
+        "\"ema_window\": 3600, \"verbose\": true",
+    );
+    let request: DeployPerpForBeaconRequest = serde_json::from_str(&verbose).unwrap();
+    assert!(request.verbose);
+}
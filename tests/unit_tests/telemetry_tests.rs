@@ -0,0 +1,104 @@
+// Tests for Sentry settings (src/telemetry.rs)
+
+use alloy::primitives::Address;
+use serial_test::serial;
+use the_beaconator::telemetry::{
+    DEFAULT_TRACES_SAMPLE_RATE, SentryConfig, apply_global_tags, configure_sentry_scope,
+    parse_traces_sample_rate,
+};
+
+#[test]
+fn test_traces_sample_rate_defaults_when_unset() {
+    assert_eq!(
+        parse_traces_sample_rate(None),
+        Ok(DEFAULT_TRACES_SAMPLE_RATE)
+    );
+    assert_eq!(parse_traces_sample_rate(Some("  ")), Ok(1.0));
+}
+
+#[test]
+fn test_traces_sample_rate_parses_and_clamps() {
+    assert_eq!(parse_traces_sample_rate(Some("0.25")), Ok(0.25));
+    assert_eq!(parse_traces_sample_rate(Some(" 0 ")), Ok(0.0));
+    assert_eq!(parse_traces_sample_rate(Some("1.5")), Ok(1.0));
+    assert_eq!(parse_traces_sample_rate(Some("-0.1")), Ok(0.0));
+}
+
+#[test]
+fn test_traces_sample_rate_rejects_non_numbers() {
+    assert!(parse_traces_sample_rate(Some("half")).is_err());
+    assert!(parse_traces_sample_rate(Some("NaN")).is_err());
+}
+
+fn clear_sentry_env() {
+    unsafe {
+        for key in [
+            "SENTRY_DSN",
+            "SENTRY_ENVIRONMENT",
+            "SENTRY_TRACES_SAMPLE_RATE",
+            "ENV",
+        ] {
+            std::env::remove_var(key);
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn test_sentry_config_from_env() {
+    let saved_env = std::env::var("ENV").ok();
+    clear_sentry_env();
+    assert_eq!(SentryConfig::from_env(), None, "off without SENTRY_DSN");
+
+    unsafe {
+        std::env::set_var("SENTRY_DSN", "https://key@o0.ingest.sentry.io/1");
+        std::env::set_var("ENV", "Mainnet");
+        std::env::set_var("SENTRY_TRACES_SAMPLE_RATE", "0.1");
+    }
+    let config = SentryConfig::from_env().unwrap();
+    assert_eq!(
+        config.environment.as_deref(),
+        Some("mainnet"),
+        "falls back to ENV"
+    );
+    assert_eq!(config.traces_sample_rate, 0.1);
+
+    unsafe {
+        std::env::set_var("SENTRY_ENVIRONMENT", "staging");
+        std::env::set_var("SENTRY_TRACES_SAMPLE_RATE", "lots");
+    }
+    let config = SentryConfig::from_env().unwrap();
+    assert_eq!(config.environment.as_deref(), Some("staging"));
+    assert_eq!(config.traces_sample_rate, DEFAULT_TRACES_SAMPLE_RATE);
+
+    clear_sentry_env();
+    if let Some(env) = saved_env {
+        unsafe { std::env::set_var("ENV", env) };
+    }
+}
+
+#[test]
+fn test_configure_sentry_scope_for_each_env_type() {
+    for (env_type, chain_id) in [
+        ("mainnet", 42161),
+        ("testnet", 421614),
+        ("localnet", 421614),
+    ] {
+        configure_sentry_scope(chain_id, env_type, Address::repeat_byte(0x11));
+    }
+}
+
+#[test]
+fn test_events_carry_global_tags() {
+    let wallet = Address::repeat_byte(0x11);
+    let events = sentry::test::with_captured_events(|| {
+        sentry::configure_scope(|scope| apply_global_tags(scope, 42161, " Mainnet ", wallet));
+        sentry::capture_message("boom", sentry::Level::Error);
+    });
+
+    assert_eq!(events.len(), 1);
+    let tags = &events[0].tags;
+    assert_eq!(tags["chain_id"], "42161");
+    assert_eq!(tags["env_type"], "mainnet");
+    assert_eq!(tags["wallet_address"], wallet.to_string());
+}
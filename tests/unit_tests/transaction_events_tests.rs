@@ -1,12 +1,85 @@
 // Transaction event parsing tests — extracted from src/services/transaction/events.rs.
 // Pinned to perpcity-contracts@v0.1.0 (Perp + PerpFactory architecture).
 
-use alloy::primitives::{Address, U256};
+use alloy::primitives::aliases::{I24, U24, U160};
+use alloy::primitives::{Address, B256, U256};
+use alloy::rpc::types::TransactionReceipt;
+use alloy::sol_types::SolEvent;
+use serde_json::{Value, json};
 use std::str::FromStr;
+use the_beaconator::routes::{IBeacon, IERC20, IPerp, IPerpFactory};
 use the_beaconator::services::transaction::events::{
-    PerpCreatedEvent, parse_index_updated_event, parse_maker_opened_event, parse_perp_created_event,
+    PerpCreatedEvent, ensure_receipt_succeeded, logs_emitted_by, parse_index_updated_event,
+    parse_maker_opened_event, parse_perp_created_event, sum_erc20_transfers,
 };
 
+const TX_HASH: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+
+/// A mined EIP-1559 receipt with no logs, as a reverted transaction leaves.
+fn mined_receipt(success: bool) -> TransactionReceipt {
+    receipt_with_logs(success, Vec::new())
+}
+
+/// A mined EIP-1559 receipt carrying `logs` (see [`log_json`]).
+fn receipt_with_logs(success: bool, logs: Vec<Value>) -> TransactionReceipt {
+    serde_json::from_value(json!({
+        "type": "0x2",
+        "status": if success { "0x1" } else { "0x0" },
+        "cumulativeGasUsed": "0x5208",
+        "logs": logs,
+        "logsBloom": format!("0x{}", "00".repeat(256)),
+        "transactionHash": TX_HASH,
+        "transactionIndex": "0x0",
+        "blockHash": "0x2222222222222222222222222222222222222222222222222222222222222222",
+        "blockNumber": "0x10",
+        "gasUsed": "0x5208",
+        "effectiveGasPrice": "0x3b9aca00",
+        "from": "0x3333333333333333333333333333333333333333",
+        "to": "0x4444444444444444444444444444444444444444",
+        "contractAddress": null
+    }))
+    .expect("valid receipt JSON")
+}
+
+/// RPC JSON for `event` emitted by `emitter` at position `log_index` of the receipt.
+fn log_json<E: SolEvent>(emitter: Address, event: &E, log_index: u64) -> Value {
+    let data = event.encode_log_data();
+    json!({
+        "address": emitter,
+        "topics": data.topics(),
+        "data": data.data,
+        "blockNumber": "0x10",
+        "transactionHash": TX_HASH,
+        "transactionIndex": "0x0",
+        "logIndex": format!("{log_index:#x}"),
+        "removed": false
+    })
+}
+
+fn perp_created(perp: Address) -> IPerpFactory::PerpCreated {
+    IPerpFactory::PerpCreated {
+        perp,
+        poolId: B256::repeat_byte(0x90),
+        modules: IPerpFactory::Modules {
+            beacon: Address::repeat_byte(0xbe),
+            fees: Address::repeat_byte(0x01),
+            funding: Address::repeat_byte(0x02),
+            marginRatios: Address::repeat_byte(0x03),
+            priceImpact: Address::repeat_byte(0x04),
+            pricing: Address::repeat_byte(0x05),
+        },
+        initialIndex: U256::from(1_000_000u64),
+        emaWindow: U24::from(3600u32),
+        protocolFee: U256::ZERO,
+        sqrtPriceX96: U160::from(1u64) << 96,
+        tick: I24::try_from(-120i32).unwrap(),
+        owner: Address::repeat_byte(0x0e),
+        name: "Perp".to_string(),
+        symbol: "PERP".to_string(),
+        tokenUri: String::new(),
+    }
+}
+
 #[test]
 fn test_index_updated_event_interface_compilation() {
     // Compile-time check that IBeacon::IndexUpdated exists and is decodable.
@@ -30,3 +103,111 @@ fn test_parse_maker_opened_event_signature() {
     let _function_exists = parse_maker_opened_event
         as fn(&alloy::rpc::types::TransactionReceipt, Address) -> Result<U256, String>;
 }
+
+#[test]
+fn test_ensure_receipt_succeeded() {
+    assert!(ensure_receipt_succeeded(&mined_receipt(true), "createPerp").is_ok());
+    let err = ensure_receipt_succeeded(&mined_receipt(false), "createPerp").unwrap_err();
+    assert_eq!(
+        err,
+        format!("createPerp transaction {TX_HASH} reverted (status: false)")
+    );
+}
+
+#[test]
+fn test_reverted_receipt_is_reported_as_revert_not_missing_event() {
+    let emitter = Address::repeat_byte(0x44);
+    let reverted = mined_receipt(false);
+
+    let err = parse_perp_created_event(&reverted, emitter).unwrap_err();
+    assert!(err.starts_with("createPerp transaction"), "{err}");
+    assert!(err.contains("reverted"), "{err}");
+    assert!(!err.contains("not found"), "{err}");
+
+    let err = parse_maker_opened_event(&reverted, emitter).unwrap_err();
+    assert!(err.starts_with("openMaker transaction"), "{err}");
+
+    let err = parse_index_updated_event(&reverted, emitter).unwrap_err();
+    assert!(err.starts_with("Beacon update transaction"), "{err}");
+}
+
+#[test]
+fn test_successful_receipt_without_event_still_reports_missing_event() {
+    let err =
+        parse_perp_created_event(&mined_receipt(true), Address::repeat_byte(0x44)).unwrap_err();
+    assert!(err.contains("PerpCreated event not found"), "{err}");
+}
+
+#[test]
+fn test_parsers_only_decode_logs_from_the_expected_emitter() {
+    let beacon_a = Address::repeat_byte(0xa1);
+    let beacon_b = Address::repeat_byte(0xb2);
+    let perp_x = Address::repeat_byte(0xc3);
+    let perp_y = Address::repeat_byte(0xd4);
+    let factory = Address::repeat_byte(0xfa);
+    let impostor = Address::repeat_byte(0xee);
+    let usdc = Address::repeat_byte(0x5d);
+    let wallet = Address::repeat_byte(0x77);
+
+    // A multicall receipt: every contract it touched left logs, several with the same
+    // signature, and the look-alike events come first.
+    let index = |value: u64| IBeacon::IndexUpdated {
+        index: U256::from(value),
+    };
+    let maker = |pos: u64| IPerp::MakerOpened {
+        posId: U256::from(pos),
+    };
+    let transfer = |value: u64| IERC20::Transfer {
+        from: perp_y,
+        to: wallet,
+        value: U256::from(value),
+    };
+    let receipt = receipt_with_logs(
+        true,
+        vec![
+            log_json(beacon_a, &index(1), 0),
+            log_json(impostor, &perp_created(Address::repeat_byte(0x66)), 1),
+            log_json(beacon_b, &index(2), 2),
+            log_json(perp_x, &maker(7), 3),
+            log_json(impostor, &transfer(1_000), 4),
+            log_json(perp_y, &maker(9), 5),
+            log_json(usdc, &transfer(250), 6),
+            log_json(factory, &perp_created(perp_y), 7),
+        ],
+    );
+
+    assert_eq!(
+        parse_index_updated_event(&receipt, beacon_a).unwrap(),
+        U256::from(1)
+    );
+    assert_eq!(
+        parse_index_updated_event(&receipt, beacon_b).unwrap(),
+        U256::from(2)
+    );
+    assert_eq!(
+        parse_maker_opened_event(&receipt, perp_x).unwrap(),
+        U256::from(7)
+    );
+    assert_eq!(
+        parse_maker_opened_event(&receipt, perp_y).unwrap(),
+        U256::from(9)
+    );
+    assert_eq!(
+        parse_perp_created_event(&receipt, factory).unwrap().perp,
+        perp_y
+    );
+    assert_eq!(
+        sum_erc20_transfers(&receipt, usdc, perp_y, wallet),
+        U256::from(250)
+    );
+    assert_eq!(logs_emitted_by(&receipt, impostor).count(), 2);
+
+    // An event of the right shape from the wrong contract is not a match.
+    let err = parse_index_updated_event(&receipt, perp_x).unwrap_err();
+    assert!(err.contains("IndexUpdated event not found"), "{err}");
+    assert!(err.contains("8 log(s)"), "{err}");
+    let err = parse_perp_created_event(&receipt, beacon_a).unwrap_err();
+    assert!(err.contains("PerpCreated event not found"), "{err}");
+    let err = parse_maker_opened_event(&receipt, impostor).unwrap_err();
+    assert!(err.contains("MakerOpened event not found"), "{err}");
+}
@@ -4,8 +4,17 @@
 // Transaction serialization is now handled by Redis-based distributed locks
 // in the wallet module. See `WalletLock` for details.
 
+use alloy::primitives::B256;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use the_beaconator::config::ConfigReader;
+use the_beaconator::services::error::BeaconError;
 use the_beaconator::services::transaction::execution::{
-    is_insufficient_funds_error, is_nonce_error,
+    AttemptBudget, DEFAULT_RECEIPT_POLL_INTERVAL_SECS, DEFAULT_RECEIPT_POLL_MAX_INTERVAL_SECS,
+    DEFAULT_RPC_MAX_TOTAL_ATTEMPTS, FALLBACK_TIMEOUTS_SECS, ReceiptWaitConfig,
+    is_insufficient_funds_error, is_nonce_error, retry_once_on_nonce_error, wait_for_receipt_with,
+    wait_for_receipt_with_sleep,
 };
 
 #[test]
@@ -43,3 +52,414 @@ fn test_is_insufficient_funds_error_detection() {
     assert!(!is_insufficient_funds_error("gas limit exceeded"));
     assert!(!is_insufficient_funds_error(""));
 }
+
+#[test]
+fn test_attempt_budget_never_exceeds_max() {
+    for max in 1..=10u32 {
+        let mut budget = AttemptBudget::new(max);
+        // Primary attempt, then a fallback loop that keeps asking for more —
+        // mirrors get_receipt() followed by the on-chain polling fallback.
+        let mut attempts = 0u32;
+        if budget.try_consume() {
+            attempts += 1;
+        }
+        for _ in 0..50 {
+            if budget.try_consume() {
+                attempts += 1;
+            }
+        }
+        assert_eq!(attempts, max);
+        assert_eq!(budget.used(), max);
+        assert_eq!(budget.remaining(), 0);
+        assert!(budget.is_exhausted());
+        assert!(!budget.try_consume());
+        assert_eq!(budget.used(), max);
+    }
+}
+
+#[test]
+fn test_attempt_budget_zero_is_clamped_to_one() {
+    let mut budget = AttemptBudget::new(0);
+    assert_eq!(budget.max(), 1);
+    assert!(budget.try_consume());
+    assert!(!budget.try_consume());
+}
+
+#[test]
+fn test_attempt_budget_fallback_timeouts_clamp_to_last() {
+    assert_eq!(AttemptBudget::fallback_timeout_secs(0), 15);
+    assert_eq!(AttemptBudget::fallback_timeout_secs(1), 30);
+    assert_eq!(AttemptBudget::fallback_timeout_secs(2), 60);
+    assert_eq!(AttemptBudget::fallback_timeout_secs(7), 60);
+}
+
+#[test]
+#[serial_test::serial]
+fn test_attempt_budget_from_config() {
+    unsafe {
+        std::env::remove_var("RPC_MAX_TOTAL_ATTEMPTS");
+    }
+    let mut config = ConfigReader::new();
+    assert_eq!(
+        ReceiptWaitConfig::from_config(&mut config).max_attempts,
+        DEFAULT_RPC_MAX_TOTAL_ATTEMPTS
+    );
+
+    unsafe {
+        std::env::set_var("RPC_MAX_TOTAL_ATTEMPTS", "7");
+    }
+    assert_eq!(ReceiptWaitConfig::from_config(&mut config).max_attempts, 7);
+    assert!(config.finish().is_ok());
+
+    unsafe {
+        std::env::set_var("RPC_MAX_TOTAL_ATTEMPTS", "not-a-number");
+    }
+    let mut config = ConfigReader::new();
+    ReceiptWaitConfig::from_config(&mut config);
+    let err = config.finish().unwrap_err();
+    assert_eq!(err.problems.len(), 1);
+    assert!(err.problems[0].starts_with("Invalid RPC_MAX_TOTAL_ATTEMPTS 'not-a-number'"));
+
+    unsafe {
+        std::env::remove_var("RPC_MAX_TOTAL_ATTEMPTS");
+    }
+}
+
+/// Mock send: fails with each queued error in turn, then succeeds with the attempt number.
+async fn mock_send(attempts: &AtomicU32, errors: &[&str]) -> Result<u32, String> {
+    let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+    match errors.get(attempt as usize) {
+        Some(error) => Err(error.to_string()),
+        None => Ok(attempt),
+    }
+}
+
+#[tokio::test]
+async fn test_nonce_error_resyncs_and_retries_once() {
+    let attempts = AtomicU32::new(0);
+    let resyncs = AtomicU32::new(0);
+
+    let result = retry_once_on_nonce_error(
+        "test",
+        || {
+            mock_send(
+                &attempts,
+                &["Failed to send: nonce too low: next nonce 7, tx nonce 6"],
+            )
+        },
+        || async {
+            resyncs.fetch_add(1, Ordering::SeqCst);
+        },
+    )
+    .await;
+
+    assert_eq!(result, Ok(1), "the retry's result is returned");
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    assert_eq!(resyncs.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_other_errors_are_not_retried() {
+    let attempts = AtomicU32::new(0);
+    let resyncs = AtomicU32::new(0);
+
+    let result = retry_once_on_nonce_error(
+        "test",
+        || mock_send(&attempts, &["execution reverted: InvalidProof()"]),
+        || async {
+            resyncs.fetch_add(1, Ordering::SeqCst);
+        },
+    )
+    .await;
+
+    assert_eq!(
+        result,
+        Err("execution reverted: InvalidProof()".to_string())
+    );
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    assert_eq!(resyncs.load(Ordering::SeqCst), 0);
+
+    let result = retry_once_on_nonce_error(
+        "test",
+        || mock_send(&attempts, &[]),
+        || async {
+            resyncs.fetch_add(1, Ordering::SeqCst);
+        },
+    )
+    .await;
+    assert!(result.is_ok());
+    assert_eq!(resyncs.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_retry_happens_only_once() {
+    let attempts = AtomicU32::new(0);
+    let resyncs = AtomicU32::new(0);
+
+    let result = retry_once_on_nonce_error(
+        "test",
+        || {
+            mock_send(
+                &attempts,
+                &["nonce too low", "replacement transaction underpriced"],
+            )
+        },
+        || async {
+            resyncs.fetch_add(1, Ordering::SeqCst);
+        },
+    )
+    .await;
+
+    assert_eq!(
+        result,
+        Err("replacement transaction underpriced".to_string()),
+        "the retry's error is surfaced"
+    );
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        resyncs.load(Ordering::SeqCst),
+        2,
+        "resynced after each nonce error"
+    );
+}
+
+// wait_for_receipt_with: receipts are stood in for by u32 block numbers.
+
+fn receipt_config(max_attempts: u32) -> ReceiptWaitConfig {
+    ReceiptWaitConfig {
+        initial_timeout: Duration::from_millis(20),
+        lookup_timeouts: vec![Duration::from_millis(20)],
+        poll_interval: Duration::from_millis(1),
+        max_poll_interval: Duration::from_millis(1),
+        max_attempts,
+    }
+}
+
+/// A `get_receipt()` that never resolves.
+async fn never_mined() -> Result<u32, String> {
+    std::future::pending().await
+}
+
+/// Lookup returning `None` until its `found_on`-th call (1-based), then `Some(7)`.
+async fn mock_lookup(calls: &AtomicU32, found_on: u32) -> Result<Option<u32>, String> {
+    let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+    Ok((call >= found_on).then_some(7))
+}
+
+#[test]
+fn test_receipt_wait_config_uses_fallback_schedule() {
+    let config = ReceiptWaitConfig::default().with_initial_timeout(Duration::from_secs(90));
+    assert_eq!(config.initial_timeout, Duration::from_secs(90));
+    assert_eq!(
+        config.lookup_timeout(0),
+        Duration::from_secs(FALLBACK_TIMEOUTS_SECS[0])
+    );
+    // Lookups past the end of the schedule reuse its last entry.
+    assert_eq!(config.lookup_timeout(10), Duration::from_secs(60));
+}
+
+#[tokio::test]
+async fn test_receipt_from_get_receipt_skips_lookups() {
+    let calls = AtomicU32::new(0);
+    let result = wait_for_receipt_with(
+        "test",
+        B256::ZERO,
+        async { Ok::<_, String>(3) },
+        || mock_lookup(&calls, 1),
+        &receipt_config(4),
+    )
+    .await;
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_receipt_found_on_retry_after_timeout() {
+    let calls = AtomicU32::new(0);
+    let result = wait_for_receipt_with(
+        "test",
+        B256::ZERO,
+        never_mined(),
+        || mock_lookup(&calls, 2),
+        &receipt_config(4),
+    )
+    .await;
+    assert_eq!(result.unwrap(), 7);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_receipt_found_after_get_receipt_error() {
+    let calls = AtomicU32::new(0);
+    let result = wait_for_receipt_with(
+        "test",
+        B256::ZERO,
+        async { Err::<u32, _>("connection reset".to_string()) },
+        || mock_lookup(&calls, 1),
+        &receipt_config(4),
+    )
+    .await;
+    assert_eq!(result.unwrap(), 7);
+}
+
+#[tokio::test]
+async fn test_receipt_not_found_times_out_within_budget() {
+    let calls = AtomicU32::new(0);
+    let result = wait_for_receipt_with(
+        "createPerp",
+        B256::ZERO,
+        never_mined(),
+        || mock_lookup(&calls, u32::MAX),
+        &receipt_config(4),
+    )
+    .await;
+    match result {
+        Err(BeaconError::Timeout(msg)) => {
+            assert!(msg.contains("createPerp receipt"), "{msg}");
+            assert!(msg.contains("after 4 attempts"), "{msg}");
+        }
+        other => panic!("expected Timeout, got {other:?}"),
+    }
+    // One attempt went to get_receipt(), the other three to lookups.
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_stalled_lookups_time_out() {
+    let result = wait_for_receipt_with(
+        "test",
+        B256::ZERO,
+        never_mined(),
+        || async { std::future::pending::<Result<Option<u32>, String>>().await },
+        &receipt_config(3),
+    )
+    .await;
+    assert!(matches!(result, Err(BeaconError::Timeout(_))));
+}
+
+#[tokio::test]
+async fn test_single_attempt_budget_never_looks_up() {
+    let calls = AtomicU32::new(0);
+    let result = wait_for_receipt_with(
+        "test",
+        B256::ZERO,
+        never_mined(),
+        || mock_lookup(&calls, 1),
+        &receipt_config(1),
+    )
+    .await;
+    assert!(matches!(result, Err(BeaconError::Timeout(_))));
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_lookup_error_ends_the_wait() {
+    let calls = &AtomicU32::new(0);
+    let result = wait_for_receipt_with(
+        "test",
+        B256::ZERO,
+        never_mined(),
+        || async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<Option<u32>, _>("execution reverted".to_string())
+        },
+        &receipt_config(4),
+    )
+    .await;
+    let err = result.unwrap_err();
+    assert!(
+        err.to_string().contains("Failed to query test receipt"),
+        "{err}"
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+fn backoff_config(max_attempts: u32) -> ReceiptWaitConfig {
+    ReceiptWaitConfig {
+        poll_interval: Duration::from_secs(1),
+        max_poll_interval: Duration::from_secs(5),
+        ..receipt_config(max_attempts)
+    }
+}
+
+#[test]
+fn test_poll_delay_doubles_up_to_the_cap() {
+    let config = backoff_config(4);
+    let delays: Vec<u64> = (0..6).map(|n| config.poll_delay(n).as_secs()).collect();
+    assert_eq!(delays, vec![1, 2, 4, 5, 5, 5]);
+    // No overflow however many retries.
+    assert_eq!(config.poll_delay(u32::MAX), Duration::from_secs(5));
+}
+
+#[test]
+#[serial_test::serial]
+fn test_receipt_poll_interval_from_config() {
+    unsafe {
+        std::env::remove_var("RECEIPT_POLL_INTERVAL_SECS");
+        std::env::remove_var("RECEIPT_POLL_MAX_INTERVAL_SECS");
+    }
+    let mut config = ConfigReader::new();
+    let config = ReceiptWaitConfig::from_config(&mut config);
+    assert_eq!(
+        config.poll_interval,
+        Duration::from_secs(DEFAULT_RECEIPT_POLL_INTERVAL_SECS)
+    );
+    assert_eq!(
+        config.max_poll_interval,
+        Duration::from_secs(DEFAULT_RECEIPT_POLL_MAX_INTERVAL_SECS)
+    );
+
+    // A cap below the interval is raised to it.
+    unsafe {
+        std::env::set_var("RECEIPT_POLL_INTERVAL_SECS", " 12 ");
+        std::env::set_var("RECEIPT_POLL_MAX_INTERVAL_SECS", "3");
+    }
+    let mut config = ConfigReader::new();
+    let config = ReceiptWaitConfig::from_config(&mut config);
+    assert_eq!(config.poll_interval, Duration::from_secs(12));
+    assert_eq!(config.max_poll_interval, Duration::from_secs(12));
+
+    unsafe {
+        std::env::remove_var("RECEIPT_POLL_INTERVAL_SECS");
+        std::env::remove_var("RECEIPT_POLL_MAX_INTERVAL_SECS");
+    }
+}
+
+#[tokio::test]
+async fn test_fallback_pauses_follow_backoff_schedule() {
+    // Mock clock: record each requested pause instead of sleeping.
+    let slept = &Mutex::new(Vec::new());
+    let calls = AtomicU32::new(0);
+    let result = wait_for_receipt_with_sleep(
+        "test",
+        B256::ZERO,
+        never_mined(),
+        || mock_lookup(&calls, u32::MAX),
+        &backoff_config(7),
+        |delay| async move { slept.lock().unwrap().push(delay.as_secs()) },
+    )
+    .await;
+
+    assert!(matches!(result, Err(BeaconError::Timeout(_))));
+    // Six lookups; no pause after the last one since the budget is spent.
+    assert_eq!(calls.load(Ordering::SeqCst), 6);
+    assert_eq!(*slept.lock().unwrap(), vec![1, 2, 4, 5, 5]);
+}
+
+#[tokio::test]
+async fn test_no_pause_once_receipt_is_found() {
+    let slept = &Mutex::new(Vec::new());
+    let calls = AtomicU32::new(0);
+    let result = wait_for_receipt_with_sleep(
+        "test",
+        B256::ZERO,
+        never_mined(),
+        || mock_lookup(&calls, 3),
+        &backoff_config(7),
+        |delay| async move { slept.lock().unwrap().push(delay.as_secs()) },
+    )
+    .await;
+
+    assert_eq!(result.unwrap(), 7);
+    assert_eq!(*slept.lock().unwrap(), vec![1, 2]);
+}
@@ -0,0 +1,191 @@
+// Tests for the transaction history (src/services/history.rs) and GET /transactions
+
+use alloy::consensus::{Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom};
+use alloy::primitives::{Address, B256};
+use alloy::rpc::types::TransactionReceipt;
+use rocket::State;
+use rocket::http::Status;
+use std::sync::Arc;
+use the_beaconator::guards::AdminToken;
+use the_beaconator::models::TransactionStatus;
+use the_beaconator::routes::info::transactions;
+use the_beaconator::services::history::TransactionHistory;
+use the_beaconator::services::metrics::GasOperation;
+
+fn hash(n: u8) -> Option<String> {
+    Some(format!("0x{}", format!("{n:02x}").repeat(32)))
+}
+
+#[tokio::test]
+async fn test_insert_and_query_round_trip() {
+    let history = TransactionHistory::new(true, 10);
+    history
+        .record_transaction(
+            GasOperation::BeaconUpdate,
+            &[("beacon_address", "0xabc".to_string())],
+            Ok(hash(1)),
+        )
+        .await;
+
+    let records = history.recent(10, None).await;
+    assert_eq!(records.len(), 1);
+    let record = &records[0];
+    assert_eq!(record.kind, "beacon_update");
+    assert_eq!(record.inputs.get("beacon_address").unwrap(), "0xabc");
+    assert_eq!(record.transaction_hash, hash(1));
+    assert_eq!(record.status, TransactionStatus::Success);
+    assert!(record.error.is_none());
+    assert!(record.recorded_at > 0);
+}
+
+#[tokio::test]
+async fn test_failures_are_recorded_with_error() {
+    let history = TransactionHistory::new(true, 10);
+    history
+        .record_transaction(
+            GasOperation::PerpDeploy,
+            &[("beacon", "0xabc".to_string())],
+            Err("execution reverted".to_string()),
+        )
+        .await;
+
+    let record = &history.recent(10, None).await[0];
+    assert_eq!(record.status, TransactionStatus::Failed);
+    assert_eq!(record.transaction_hash, None);
+    assert_eq!(record.error.as_deref(), Some("execution reverted"));
+}
+
+fn receipt(tx_hash: B256, status: bool) -> TransactionReceipt {
+    TransactionReceipt {
+        transaction_hash: tx_hash,
+        transaction_index: Some(0),
+        block_hash: Some(B256::ZERO),
+        block_number: Some(1000),
+        from: Address::repeat_byte(3),
+        to: Some(Address::repeat_byte(4)),
+        gas_used: 21000,
+        effective_gas_price: 1_000_000_000,
+        blob_gas_used: None,
+        blob_gas_price: None,
+        contract_address: None,
+        inner: ReceiptEnvelope::Legacy(ReceiptWithBloom {
+            receipt: Receipt {
+                status: Eip658Value::Eip658(status),
+                cumulative_gas_used: 21000,
+                logs: vec![],
+            },
+            logs_bloom: Default::default(),
+        }),
+    }
+}
+
+#[tokio::test]
+async fn test_receipts_are_recorded_by_status() {
+    let history = TransactionHistory::new(true, 10);
+    let inputs = [("asset", "USDC".to_string())];
+    history
+        .record_receipt(
+            GasOperation::Sweep,
+            &inputs,
+            &receipt(B256::repeat_byte(1), true),
+        )
+        .await;
+    history
+        .record_receipt(
+            GasOperation::Funding,
+            &inputs,
+            &receipt(B256::repeat_byte(2), false),
+        )
+        .await;
+
+    let records = history.recent(10, None).await;
+    let (reverted, mined) = (&records[0], &records[1]);
+    assert_eq!(mined.kind, "sweep");
+    assert_eq!(mined.status, TransactionStatus::Success);
+    assert_eq!(mined.transaction_hash, hash(1));
+    assert_eq!(mined.inputs.get("asset").unwrap(), "USDC");
+
+    assert_eq!(reverted.kind, "funding");
+    assert_eq!(reverted.status, TransactionStatus::Failed);
+    let error = reverted.error.as_deref().unwrap();
+    assert!(error.contains(hash(2).as_deref().unwrap()), "{error}");
+}
+
+#[tokio::test]
+async fn test_recent_is_newest_first_filtered_and_capped() {
+    let history = TransactionHistory::new(true, 3);
+    history
+        .record_transaction(GasOperation::BeaconUpdate, &[], Ok(hash(1)))
+        .await;
+    history
+        .record_transaction(GasOperation::Deposit, &[], Ok(hash(2)))
+        .await;
+    history
+        .record_transaction(GasOperation::BeaconUpdate, &[], Ok(hash(3)))
+        .await;
+    history
+        .record_transaction(GasOperation::BeaconUpdate, &[], Ok(hash(4)))
+        .await;
+
+    // The oldest record fell off the 3-entry cap.
+    let hashes: Vec<_> = history
+        .recent(10, None)
+        .await
+        .into_iter()
+        .map(|r| r.transaction_hash)
+        .collect();
+    assert_eq!(hashes, vec![hash(4), hash(3), hash(2)]);
+
+    let updates = history.recent(10, Some(GasOperation::BeaconUpdate)).await;
+    assert_eq!(updates.len(), 2);
+    assert_eq!(history.recent(1, None).await[0].transaction_hash, hash(4));
+}
+
+#[tokio::test]
+async fn test_disabled_history_records_nothing() {
+    let history = TransactionHistory::disabled();
+    assert!(!history.is_enabled());
+    history
+        .record_transaction(GasOperation::BeaconUpdate, &[], Ok(hash(1)))
+        .await;
+    assert!(history.recent(10, None).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_transactions_route_filters_by_kind() {
+    let mut app_state = crate::test_utils::create_simple_test_app_state().await;
+    let history = Arc::new(TransactionHistory::new(true, 10));
+    history
+        .record_transaction(GasOperation::BeaconRegister, &[], Ok(None))
+        .await;
+    history
+        .record_transaction(GasOperation::Deposit, &[], Ok(hash(2)))
+        .await;
+    app_state.history = history;
+
+    let response = transactions(
+        AdminToken("test_admin_token".to_string()),
+        State::from(&app_state),
+        None,
+        Some("deposit".to_string()),
+    )
+    .await
+    .expect("known kind");
+    let data = response.into_inner().data.unwrap();
+    assert!(data.enabled);
+    assert_eq!(data.transactions.len(), 1);
+    assert_eq!(data.transactions[0].kind, "deposit");
+}
+
+#[tokio::test]
+async fn test_transactions_route_rejects_unknown_kind() {
+    let app_state = crate::test_utils::create_simple_test_app_state().await;
+    let result = transactions(
+        AdminToken("test_admin_token".to_string()),
+        State::from(&app_state),
+        Some(5),
+        Some("teleport".to_string()),
+    )
+    .await;
+    assert_eq!(result.err(), Some(Status::BadRequest));
+}
@@ -1,10 +1,9 @@
 use alloy::primitives::Address;
 use rocket::State;
 use rocket::http::Status;
-use rocket::serde::json::Json;
 use std::str::FromStr;
 
-use the_beaconator::guards::ApiToken;
+use the_beaconator::guards::{ApiToken, SignedJson};
 use the_beaconator::models::UnregisterBeaconRequest;
 use the_beaconator::routes::beacon::unregister_beacon;
 
@@ -12,9 +11,9 @@ use the_beaconator::routes::beacon::unregister_beacon;
 async fn test_unregister_beacon_invalid_beacon_address() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(UnregisterBeaconRequest {
+    let request = SignedJson(UnregisterBeaconRequest {
         beacon_address: "invalid_address".to_string(),
         registry_address: Some("0x1234567890123456789012345678901234567890".to_string()),
     });
@@ -28,9 +27,9 @@ async fn test_unregister_beacon_invalid_beacon_address() {
 async fn test_unregister_beacon_invalid_registry_address() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(UnregisterBeaconRequest {
+    let request = SignedJson(UnregisterBeaconRequest {
         beacon_address: "0x1234567890123456789012345678901234567890".to_string(),
         registry_address: Some("not_an_address".to_string()),
     });
@@ -44,9 +43,9 @@ async fn test_unregister_beacon_invalid_registry_address() {
 async fn test_unregister_beacon_beacon_without_0x_prefix() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(UnregisterBeaconRequest {
+    let request = SignedJson(UnregisterBeaconRequest {
         beacon_address: "1234567890123456789012345678901234567890".to_string(),
         registry_address: Some("0x1234567890123456789012345678901234567890".to_string()),
     });
@@ -59,9 +58,9 @@ async fn test_unregister_beacon_beacon_without_0x_prefix() {
 async fn test_unregister_beacon_registry_without_0x_prefix() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(UnregisterBeaconRequest {
+    let request = SignedJson(UnregisterBeaconRequest {
         beacon_address: "0x1234567890123456789012345678901234567890".to_string(),
         registry_address: Some("1234567890123456789012345678901234567890".to_string()),
     });
@@ -74,9 +73,9 @@ async fn test_unregister_beacon_registry_without_0x_prefix() {
 async fn test_unregister_beacon_too_short_address() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(UnregisterBeaconRequest {
+    let request = SignedJson(UnregisterBeaconRequest {
         beacon_address: "0x1234".to_string(),
         registry_address: Some("0x1234567890123456789012345678901234567890".to_string()),
     });
@@ -90,9 +89,9 @@ async fn test_unregister_beacon_too_short_address() {
 async fn test_unregister_beacon_too_long_address() {
     let app_state = crate::test_utils::create_simple_test_app_state().await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(UnregisterBeaconRequest {
+    let request = SignedJson(UnregisterBeaconRequest {
         beacon_address: "0x12345678901234567890123456789012345678901".to_string(), // 41 chars
         registry_address: Some("0x1234567890123456789012345678901234567890".to_string()),
     });
@@ -111,9 +110,9 @@ async fn test_unregister_beacon_provider_failure_is_error() {
     let mock_provider = crate::test_utils::create_mock_provider_with_network_error();
     let app_state = crate::test_utils::create_test_app_state_with_provider(mock_provider).await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(UnregisterBeaconRequest {
+    let request = SignedJson(UnregisterBeaconRequest {
         beacon_address: "0x1111111111111111111111111111111111111111".to_string(),
         registry_address: Some("0x2222222222222222222222222222222222222222".to_string()),
     });
@@ -130,9 +129,9 @@ async fn test_unregister_beacon_defaults_registry_when_absent() {
     let mock_provider = crate::test_utils::create_mock_provider_with_network_error();
     let app_state = crate::test_utils::create_test_app_state_with_provider(mock_provider).await;
     let state = State::from(&app_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(UnregisterBeaconRequest {
+    let request = SignedJson(UnregisterBeaconRequest {
         beacon_address: "0x1111111111111111111111111111111111111111".to_string(),
         registry_address: None,
     });
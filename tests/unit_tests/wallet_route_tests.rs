@@ -4,7 +4,7 @@ use alloy::primitives::Address;
 use rocket::serde::json::Json;
 use rocket::{State, http::Status};
 use std::str::FromStr;
-use the_beaconator::guards::ApiToken;
+use the_beaconator::guards::{ApiToken, SignedJson};
 use the_beaconator::models::FundGuestWalletRequest;
 use the_beaconator::routes::wallet::fund_guest_wallet;
 
@@ -24,12 +24,13 @@ async fn create_state_with_chain_id(chain_id: u64) -> the_beaconator::models::Ap
 async fn test_fund_wallet_invalid_address() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "invalid_address".to_string(),
         usdc_amount: "1000000".to_string(),
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -42,12 +43,13 @@ async fn test_fund_wallet_invalid_address() {
 async fn test_fund_wallet_empty_address() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "".to_string(),
         usdc_amount: "1000000".to_string(),
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -60,12 +62,13 @@ async fn test_fund_wallet_empty_address() {
 async fn test_fund_wallet_invalid_usdc_amount() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "not_a_number".to_string(),
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -78,12 +81,13 @@ async fn test_fund_wallet_invalid_usdc_amount() {
 async fn test_fund_wallet_invalid_eth_amount() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "1000000".to_string(),
         eth_amount: "not_a_number".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -96,12 +100,13 @@ async fn test_fund_wallet_invalid_eth_amount() {
 async fn test_fund_wallet_negative_usdc() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "-1000000".to_string(),
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -114,12 +119,13 @@ async fn test_fund_wallet_negative_usdc() {
 async fn test_fund_wallet_negative_eth() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "1000000".to_string(),
         eth_amount: "-1000000000000000".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -133,12 +139,13 @@ async fn test_fund_wallet_usdc_exceeds_limit() {
     let mut state = create_test_state().await;
     state.wallets.usdc_transfer_limit = 10_000_000; // 10 USDC
     let state = State::from(&state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "20000000".to_string(), // 20 USDC
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -153,12 +160,13 @@ async fn test_fund_wallet_eth_exceeds_limit() {
     let mut state = create_test_state().await;
     state.wallets.eth_transfer_limit = 1_000_000_000_000_000; // 0.001 ETH
     let state = State::from(&state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "1000000".to_string(),
         eth_amount: "2000000000000000".to_string(), // 0.002 ETH
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -173,12 +181,13 @@ async fn test_fund_wallet_eth_exceeds_limit() {
 async fn test_fund_wallet_zero_amounts() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "0".to_string(),
         eth_amount: "0".to_string(),
+        request_id: None,
     });
 
     // Zero amounts are technically valid, but will fail at network level
@@ -192,12 +201,13 @@ async fn test_fund_wallet_zero_amounts() {
 async fn test_fund_wallet_valid_format_network_failure() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "1000000".to_string(),
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     // Valid input but should fail due to network issues in test environment
@@ -209,12 +219,13 @@ async fn test_fund_wallet_valid_format_network_failure() {
 async fn test_fund_wallet_decimal_usdc_amount() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "10.5".to_string(), // Decimals not allowed
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -227,12 +238,13 @@ async fn test_fund_wallet_decimal_usdc_amount() {
 async fn test_fund_wallet_scientific_notation() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "1e6".to_string(), // Scientific notation
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -246,13 +258,14 @@ async fn test_fund_wallet_scientific_notation() {
 async fn test_fund_wallet_address_with_mixed_case() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
     // Mixed case checksum address
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0xAbCdEf1234567890123456789012345678901234".to_string(),
         usdc_amount: "1000000".to_string(),
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     // Should parse correctly but fail at network level
@@ -264,12 +277,13 @@ async fn test_fund_wallet_address_with_mixed_case() {
 async fn test_fund_wallet_max_u128_amounts() {
     let test_state = create_test_state().await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: u128::MAX.to_string(),
         eth_amount: u128::MAX.to_string(),
+        request_id: None,
     });
 
     // Should fail due to exceeding limits
@@ -285,12 +299,13 @@ async fn test_fund_wallet_disabled_on_arbitrum_one_mainnet() {
     // before any address/amount parsing happens.
     let test_state = create_state_with_chain_id(42161).await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "1000000".to_string(),
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -308,12 +323,13 @@ async fn test_fund_wallet_disabled_on_unknown_production_chain() {
     // Base, the funding endpoint stays disabled.
     let test_state = create_state_with_chain_id(8453).await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "1000000".to_string(),
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -330,12 +346,13 @@ async fn test_fund_wallet_allowed_on_arbitrum_sepolia() {
     // Concretely we expect the request to NOT return Forbidden.
     let test_state = create_state_with_chain_id(421614).await;
     let state = State::from(&test_state);
-    let token = ApiToken("test_token".to_string());
+    let token = ApiToken::Token("test_token".to_string());
 
-    let request = Json(FundGuestWalletRequest {
+    let request = SignedJson(FundGuestWalletRequest {
         wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
         usdc_amount: "1000000".to_string(),
         eth_amount: "1000000000000000".to_string(),
+        request_id: None,
     });
 
     let result = fund_guest_wallet(state, request, token).await;
@@ -387,12 +404,51 @@ mod top_up_and_reserve {
     use alloy::providers::Provider;
     use the_beaconator::guards::AdminToken;
     use the_beaconator::models::TopUpPoolRequest;
-    use the_beaconator::routes::wallet::top_up_pool;
+    use the_beaconator::routes::wallet::{eth_send_keeps_reserve, top_up_pool};
 
     fn admin() -> AdminToken {
         AdminToken("test_admin_token".to_string())
     }
 
+    #[test]
+    fn test_eth_send_keeps_reserve_boundary() {
+        let reserve = U256::from(20_000_000_000_000_000u128); // 0.02 ETH
+        let amount = U256::from(1_000_000_000_000_000u128); // 0.001 ETH
+        let exact = amount + reserve;
+
+        assert!(eth_send_keeps_reserve(exact, amount, reserve));
+        assert!(eth_send_keeps_reserve(
+            exact + U256::from(1),
+            amount,
+            reserve
+        ));
+        assert!(!eth_send_keeps_reserve(
+            exact - U256::from(1),
+            amount,
+            reserve
+        ));
+        // Enough for the transfer alone is not enough.
+        assert!(!eth_send_keeps_reserve(amount, amount, reserve));
+    }
+
+    #[test]
+    fn test_eth_send_keeps_reserve_edge_values() {
+        // Zero reserve: the whole balance may be sent.
+        assert!(eth_send_keeps_reserve(
+            U256::from(5),
+            U256::from(5),
+            U256::ZERO
+        ));
+        // Zero amount still requires the reserve to be present.
+        assert!(!eth_send_keeps_reserve(
+            U256::from(9),
+            U256::ZERO,
+            U256::from(10)
+        ));
+        // amount + reserve overflowing is refused rather than wrapping.
+        assert!(!eth_send_keeps_reserve(U256::MAX, U256::MAX, U256::from(1)));
+    }
+
     #[tokio::test]
     async fn test_top_up_pool_refused_on_production_chain() {
         let test_state = create_state_with_chain_id(42161).await;
@@ -424,6 +480,8 @@ mod top_up_and_reserve {
         let _ = state;
     }
 
+    // Without wallet-pool the stub is the single PRIVATE_KEY wallet, never empty.
+    #[cfg(feature = "wallet-pool")]
     #[tokio::test]
     async fn test_top_up_pool_empty_pool_unavailable() {
         // test_stub manager has no signers -> pool is empty -> 503.
@@ -469,19 +527,21 @@ mod top_up_and_reserve {
         }
 
         let state = State::from(&app_state);
-        let request = Json(FundGuestWalletRequest {
+        let request = SignedJson(FundGuestWalletRequest {
             wallet_address: "0x742d35Cc6634C0532925a3b844Bc9e7595f8b94b".to_string(),
             usdc_amount: "1000000".to_string(),
             eth_amount: "1000000000000000".to_string(),
+            request_id: None,
         });
 
-        let result = fund_guest_wallet(state, request, ApiToken("test_token".to_string())).await;
+        let result =
+            fund_guest_wallet(state, request, ApiToken::Token("test_token".to_string())).await;
 
         assert!(result.is_err(), "funding must be refused below the reserve");
         let (status, response) = result.unwrap_err();
         assert_eq!(status, Status::ServiceUnavailable);
         assert!(
-            response.message.contains("reserve"),
+            response.message.contains("Faucet reserve exhausted"),
             "unexpected message: {}",
             response.message
         );
@@ -492,24 +552,24 @@ mod top_up_and_reserve {
     async fn test_top_up_pool_mints_wallets_to_target() {
         use crate::test_utils::{deploy_contract, load_contract_bytecode};
         use alloy::network::EthereumWallet;
-        use alloy::providers::ProviderBuilder;
         use the_beaconator::routes::IERC20;
 
-        let (mut app_state, anvil) =
+        let (app_state, anvil) =
             crate::test_utils::create_isolated_test_app_state_with_redis().await;
 
         // Deploy the permissionless-mint MockUSDC (same semantics as the
         // deployed testnet USDC) and point the app at it.
         let wallet = EthereumWallet::from(anvil.deployer_signer());
-        let deploy_provider = std::sync::Arc::new(
-            ProviderBuilder::new()
-                .wallet(wallet)
-                .connect_http(anvil.rpc_url().parse().expect("anvil url")),
-        );
+        let deploy_provider = std::sync::Arc::new(crate::test_utils::build_test_signing_provider(
+            wallet,
+            anvil.rpc_url(),
+        ));
         let usdc = deploy_contract(&deploy_provider, load_contract_bytecode("MockUSDC"))
             .await
             .expect("deploy MockUSDC");
-        app_state.contracts.usdc = usdc;
+        app_state
+            .contracts
+            .update(|contracts| contracts.usdc = usdc);
 
         let pool = app_state.wallets.manager.signer_addresses();
         assert!(!pool.is_empty());
@@ -534,3 +594,968 @@ mod top_up_and_reserve {
         }
     }
 }
+
+mod funding_rate_limit {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use the_beaconator::services::wallet::FundingRateLimiter;
+    use the_beaconator::services::wallet::funding_limits::{FundingGrant, evaluate_window};
+
+    const DAY: u64 = 24 * 60 * 60;
+    const T0: u64 = 1_700_000_000;
+
+    fn recipient() -> Address {
+        Address::from_str("0x1234567890123456789012345678901234567890").unwrap()
+    }
+
+    /// 100 USDC / 0.01 ETH per 24h.
+    fn limiter() -> FundingRateLimiter {
+        FundingRateLimiter::new(
+            100_000_000,
+            10_000_000_000_000_000,
+            Duration::from_secs(DAY),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_exhausting_usdc_window_locks_out_recipient() {
+        let limiter = limiter();
+        for i in 0..4 {
+            limiter
+                .reserve(recipient(), 25_000_000, 0, T0 + i * 60)
+                .await
+                .expect("within the cap");
+        }
+
+        let err = limiter
+            .reserve(recipient(), 1, 0, T0 + 3600)
+            .await
+            .expect_err("window exhausted");
+        assert!(err.reason.contains("USDC funding cap"), "{}", err.reason);
+        // The first grant (T0) rolls off at T0 + DAY, freeing enough room.
+        assert_eq!(err.retry_after_secs, DAY - 3600);
+    }
+
+    #[tokio::test]
+    async fn test_lockout_lifts_once_window_rolls_over() {
+        let limiter = limiter();
+        limiter
+            .reserve(recipient(), 100_000_000, 0, T0)
+            .await
+            .expect("within the cap");
+
+        assert!(
+            limiter
+                .reserve(recipient(), 1, 0, T0 + DAY - 1)
+                .await
+                .is_err()
+        );
+        assert!(limiter.reserve(recipient(), 1, 0, T0 + DAY).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_eth_cap_enforced_independently() {
+        let limiter = limiter();
+        limiter
+            .reserve(recipient(), 0, 10_000_000_000_000_000, T0)
+            .await
+            .expect("within the cap");
+
+        let err = limiter
+            .reserve(recipient(), 0, 1, T0 + 10)
+            .await
+            .expect_err("ETH cap exhausted");
+        assert!(err.reason.contains("ETH funding cap"), "{}", err.reason);
+        assert_eq!(err.retry_after_secs, DAY - 10);
+        // USDC-only requests still fit.
+        assert!(
+            limiter
+                .reserve(recipient(), 1_000_000, 0, T0 + 10)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_caps_are_per_recipient() {
+        let limiter = limiter();
+        let grant = limiter
+            .reserve(recipient(), 100_000_000, 0, T0)
+            .await
+            .expect("within the cap");
+
+        let other = Address::from_str("0x0000000000000000000000000000000000000001").unwrap();
+        assert!(limiter.reserve(other, 100_000_000, 0, T0).await.is_ok());
+
+        // Releasing the first recipient's grant leaves the other's in place.
+        limiter.release(&grant).await;
+        assert!(limiter.reserve(other, 1, 0, T0 + 1).await.is_err());
+        assert!(limiter.reserve(recipient(), 1, 0, T0 + 1).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reservations_cannot_overshoot_the_cap() {
+        let limiter = Arc::new(limiter());
+        let attempts = (0..8).map(|_| {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.reserve(recipient(), 25_000_000, 0, T0).await })
+        });
+        let admitted = futures::future::join_all(attempts)
+            .await
+            .into_iter()
+            .filter(|result| result.as_ref().unwrap().is_ok())
+            .count();
+        assert_eq!(admitted, 4, "100 USDC cap admits four 25 USDC grants");
+    }
+
+    #[tokio::test]
+    async fn test_released_grant_frees_its_room() {
+        let limiter = limiter();
+        let grant = limiter
+            .reserve(recipient(), 100_000_000, 0, T0)
+            .await
+            .expect("within the cap");
+        assert!(limiter.reserve(recipient(), 1, 0, T0 + 1).await.is_err());
+
+        limiter.release(&grant).await;
+        assert!(limiter.reserve(recipient(), 1, 0, T0 + 1).await.is_ok());
+    }
+
+    #[test]
+    fn test_retry_after_waits_for_enough_grants_to_expire() {
+        let grants = [
+            FundingGrant {
+                at: T0,
+                usdc: 40,
+                eth: 0,
+            },
+            FundingGrant {
+                at: T0 + 100,
+                usdc: 40,
+                eth: 0,
+            },
+            FundingGrant {
+                at: T0 + 200,
+                usdc: 20,
+                eth: 0,
+            },
+        ];
+        // 100 used of 100; a 50 request needs the first two grants gone.
+        let err = evaluate_window(&grants, 50, 0, 100, 0, DAY, T0 + 300).unwrap_err();
+        assert_eq!(err.retry_after_secs, DAY - 200);
+    }
+
+    #[test]
+    fn test_request_larger_than_cap_reports_full_window() {
+        let err = evaluate_window(&[], 101, 0, 100, 0, DAY, T0).unwrap_err();
+        assert_eq!(err.retry_after_secs, DAY);
+    }
+
+    #[tokio::test]
+    async fn test_fund_wallet_returns_429_when_window_exhausted() {
+        let mut test_state = create_test_state().await;
+        let limiter = limiter();
+        limiter
+            .reserve(recipient(), 100_000_000, 0, FundingRateLimiter::now_secs())
+            .await
+            .expect("within the cap");
+        test_state.wallets.funding_limiter = Arc::new(limiter);
+        let state = State::from(&test_state);
+
+        let request = SignedJson(FundGuestWalletRequest {
+            wallet_address: format!("{:?}", recipient()),
+            usdc_amount: "1000000".to_string(),
+            eth_amount: "0".to_string(),
+            request_id: None,
+        });
+
+        let (status, body) =
+            fund_guest_wallet(state, request, ApiToken::Token("test_token".to_string()))
+                .await
+                .unwrap_err();
+        assert_eq!(status, Status::TooManyRequests);
+        assert!(!body.success);
+        assert!(body.message.contains("Try again in"), "{}", body.message);
+    }
+}
+
+mod request_id_replay {
+    use super::*;
+    use alloy::primitives::U256;
+    use alloy::providers::Provider;
+    use the_beaconator::services::idempotency::{IdempotencyStore, Reservation};
+
+    const CACHED: &str = "Successfully funded wallet 0x1234... ETH tx: 0xaa, USDC tx: 0xbb";
+
+    /// A request the live path would refuse (USDC over the per-request limit), so a
+    /// success can only come from the replay cache.
+    fn over_limit_request(request_id: Option<&str>) -> SignedJson<FundGuestWalletRequest> {
+        SignedJson(FundGuestWalletRequest {
+            wallet_address: "0x1234567890123456789012345678901234567890".to_string(),
+            usdc_amount: u128::MAX.to_string(),
+            eth_amount: "0".to_string(),
+            request_id: request_id.map(str::to_string),
+        })
+    }
+
+    async fn state_with_cached(token: &str, request_id: &str) -> the_beaconator::models::AppState {
+        let test_state = create_test_state().await;
+        test_state
+            .wallets
+            .funding_replays
+            .insert(
+                IdempotencyStore::<String>::scoped_key(Some(token), request_id),
+                CACHED.to_string(),
+            )
+            .await;
+        test_state
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_request_id_returns_cached_result() {
+        let test_state = state_with_cached("test_token", "retry-1").await;
+        let state = State::from(&test_state);
+
+        let response = fund_guest_wallet(
+            state,
+            over_limit_request(Some("retry-1")),
+            ApiToken::Token("test_token".to_string()),
+        )
+        .await
+        .expect("replayed")
+        .into_inner();
+
+        assert!(response.success);
+        assert_eq!(response.data.as_deref(), Some(CACHED));
+        assert!(response.message.contains("repeated request_id"));
+    }
+
+    #[tokio::test]
+    async fn test_request_id_is_scoped_to_the_token() {
+        let test_state = state_with_cached("test_token", "retry-1").await;
+        let state = State::from(&test_state);
+
+        let (status, _) = fund_guest_wallet(
+            state,
+            over_limit_request(Some("retry-1")),
+            ApiToken::Token("other_token".to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, Status::BadRequest);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_request_id_conflicts() {
+        let test_state = create_test_state().await;
+        let key = IdempotencyStore::<String>::scoped_key(Some("test_token"), "retry-1");
+        assert_eq!(
+            test_state.wallets.funding_replays.reserve(&key).await,
+            Ok(Reservation::Reserved)
+        );
+        let state = State::from(&test_state);
+
+        let (status, response) = fund_guest_wallet(
+            state,
+            over_limit_request(Some("retry-1")),
+            ApiToken::Token("test_token".to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, Status::Conflict);
+        assert!(response.message.contains("still in progress"));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_request_releases_request_id() {
+        let test_state = create_test_state().await;
+        let state = State::from(&test_state);
+
+        let (status, _) = fund_guest_wallet(
+            state,
+            over_limit_request(Some("retry-1")),
+            ApiToken::Token("test_token".to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, Status::BadRequest);
+
+        // Nothing was sent, so a corrected retry with the same id runs afresh.
+        let key = IdempotencyStore::<String>::scoped_key(Some("test_token"), "retry-1");
+        assert_eq!(
+            test_state.wallets.funding_replays.reserve(&key).await,
+            Ok(Reservation::Reserved)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_request_id_rejected() {
+        let test_state = create_test_state().await;
+        let state = State::from(&test_state);
+
+        let (status, response) = fund_guest_wallet(
+            state,
+            over_limit_request(Some("")),
+            ApiToken::Token("test_token".to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, Status::BadRequest);
+        assert!(response.message.contains("Invalid request_id"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis + Anvil"]
+    async fn test_retried_request_id_sends_no_new_transfers() {
+        use crate::test_utils::{deploy_contract, load_contract_bytecode};
+        use alloy::network::EthereumWallet;
+        use the_beaconator::routes::ITestnetUSDC;
+
+        let (app_state, anvil) =
+            crate::test_utils::create_isolated_test_app_state_with_redis().await;
+
+        let wallet = EthereumWallet::from(anvil.deployer_signer());
+        let deploy_provider = std::sync::Arc::new(crate::test_utils::build_test_signing_provider(
+            wallet,
+            anvil.rpc_url(),
+        ));
+        let usdc = deploy_contract(&deploy_provider, load_contract_bytecode("MockUSDC"))
+            .await
+            .expect("deploy MockUSDC");
+        app_state
+            .contracts
+            .update(|contracts| contracts.usdc = usdc);
+        for pool_wallet in app_state.wallets.manager.signer_addresses() {
+            ITestnetUSDC::new(usdc, &*deploy_provider)
+                .mint(pool_wallet, U256::from(10_000_000u64))
+                .send()
+                .await
+                .expect("send mint")
+                .get_receipt()
+                .await
+                .expect("mint receipt");
+        }
+
+        let guest = Address::from_str("0x742d35Cc6634C0532925a3b844Bc9e7595f8b94b").unwrap();
+        let request = || {
+            SignedJson(FundGuestWalletRequest {
+                wallet_address: guest.to_string(),
+                usdc_amount: "1000000".to_string(),
+                eth_amount: "1000000000000000".to_string(),
+                request_id: Some("signup-42".to_string()),
+            })
+        };
+        let state = State::from(&app_state);
+        let token = || ApiToken::Token("test_token".to_string());
+
+        let first = fund_guest_wallet(state, request(), token())
+            .await
+            .expect("first funding succeeds")
+            .into_inner();
+        let read_provider = &app_state.provider.read_provider;
+        let eth_after_first = read_provider.get_balance(guest).await.unwrap();
+
+        let second = fund_guest_wallet(state, request(), token())
+            .await
+            .expect("retry is replayed")
+            .into_inner();
+
+        assert_eq!(second.data, first.data, "retry returns the original hashes");
+        assert_eq!(
+            read_provider.get_balance(guest).await.unwrap(),
+            eth_after_first
+        );
+        let usdc_balance = the_beaconator::routes::IERC20::new(usdc, &**read_provider)
+            .balanceOf(guest)
+            .call()
+            .await
+            .unwrap();
+        assert_eq!(usdc_balance, U256::from(1_000_000u64), "USDC sent once");
+    }
+}
+
+mod sweep {
+    use super::*;
+    use alloy::primitives::U256;
+    use alloy::providers::Provider;
+    use the_beaconator::guards::AdminToken;
+    use the_beaconator::models::SweepWalletRequest;
+    use the_beaconator::routes::wallet::{resolve_sweep_destination, sweep_wallet};
+
+    const DESTINATION: &str = "0x742d35Cc6634C0532925a3b844Bc9e7595f8b94b";
+
+    fn admin() -> AdminToken {
+        AdminToken("test_admin_token".to_string())
+    }
+
+    fn request(wallet: &str, destination: &str) -> Json<SweepWalletRequest> {
+        Json(SweepWalletRequest {
+            wallet_address: wallet.to_string(),
+            destination_address: Some(destination.to_string()),
+            confirmation: wallet.to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_sweep_rejects_invalid_addresses() {
+        let test_state = create_test_state().await;
+
+        for (wallet, destination) in [
+            ("not_an_address", DESTINATION),
+            ("0x1234567890123456789012345678901234567890", "nope"),
+            (
+                "0x1234567890123456789012345678901234567890",
+                "0x0000000000000000000000000000000000000000",
+            ),
+            (
+                "0x1234567890123456789012345678901234567890",
+                "0x1234567890123456789012345678901234567890",
+            ),
+        ] {
+            let result = sweep_wallet(
+                State::from(&test_state),
+                request(wallet, destination),
+                admin(),
+            )
+            .await;
+            let (status, _) = result.unwrap_err();
+            assert_eq!(status, Status::BadRequest, "{wallet} -> {destination}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sweep_rejects_wallet_not_controlled_by_service() {
+        let test_state = create_test_state().await;
+
+        let result = sweep_wallet(
+            State::from(&test_state),
+            request("0x1234567890123456789012345678901234567890", DESTINATION),
+            admin(),
+        )
+        .await;
+
+        let (status, response) = result.unwrap_err();
+        assert_eq!(status, Status::UnprocessableEntity);
+        assert!(
+            response.message.contains("not controlled by this service"),
+            "unexpected message: {}",
+            response.message
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis + Anvil"]
+    async fn test_sweep_moves_eth_and_skips_usdc_when_empty() {
+        let (app_state, _anvil) =
+            crate::test_utils::create_isolated_test_app_state_with_redis().await;
+        let wallet = app_state.wallets.manager.signer_addresses()[0];
+        let destination = Address::from_str(DESTINATION).unwrap();
+        let provider = &app_state.provider.read_provider;
+        let before = provider.get_balance(destination).await.unwrap();
+
+        let result = sweep_wallet(
+            State::from(&app_state),
+            request(&wallet.to_string(), DESTINATION),
+            admin(),
+        )
+        .await;
+        let response = result.expect("sweep should succeed").into_inner();
+        let data = response.data.expect("sweep data");
+
+        assert_eq!(data.usdc_swept, "0");
+        assert!(data.usdc_transaction_hash.is_none());
+        assert!(data.eth_transaction_hash.is_some());
+        let swept = U256::from_str(&data.eth_swept_wei).unwrap();
+        assert!(swept > U256::ZERO);
+        assert_eq!(
+            provider.get_balance(destination).await.unwrap(),
+            before + swept
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis + Anvil"]
+    async fn test_sweep_skips_eth_below_gas_cost() {
+        let (app_state, _anvil) =
+            crate::test_utils::create_isolated_test_app_state_with_redis().await;
+        let wallet = app_state.wallets.manager.signer_addresses()[0];
+        let _: () = app_state
+            .provider
+            .read_provider
+            .raw_request("anvil_setBalance".into(), (wallet, U256::from(1u64)))
+            .await
+            .expect("anvil_setBalance");
+
+        let result = sweep_wallet(
+            State::from(&app_state),
+            request(&wallet.to_string(), DESTINATION),
+            admin(),
+        )
+        .await;
+        let data = result
+            .expect("sweep should succeed")
+            .into_inner()
+            .data
+            .expect("sweep data");
+
+        assert_eq!(data.eth_swept_wei, "0");
+        assert!(data.eth_transaction_hash.is_none());
+        assert!(data.eth_skipped_reason.is_some());
+    }
+
+    #[test]
+    fn test_destination_override_wins_over_cold_wallet() {
+        let cold = Address::from_str("0x0000000000000000000000000000000000000c01").unwrap();
+        let destination = resolve_sweep_destination(Some(DESTINATION), Some(cold)).unwrap();
+        assert_eq!(destination, Address::from_str(DESTINATION).unwrap());
+    }
+
+    #[test]
+    fn test_cold_wallet_used_when_destination_omitted() {
+        let cold = Address::from_str("0x0000000000000000000000000000000000000c01").unwrap();
+        assert_eq!(resolve_sweep_destination(None, Some(cold)).unwrap(), cold);
+        assert_eq!(
+            resolve_sweep_destination(Some("  "), Some(cold)).unwrap(),
+            cold
+        );
+    }
+
+    #[test]
+    fn test_destination_required_without_cold_wallet() {
+        let err = resolve_sweep_destination(None, None).unwrap_err();
+        assert!(err.contains("COLD_WALLET_ADDRESS"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_sweep_requires_matching_confirmation() {
+        let mut test_state = create_test_state().await;
+        test_state.wallets.cold_wallet_address = Some(Address::from_str(DESTINATION).unwrap());
+        let wallet = "0x1234567890123456789012345678901234567890";
+
+        for confirmation in ["", "yes", "0x0000000000000000000000000000000000000001"] {
+            let request = Json(SweepWalletRequest {
+                wallet_address: wallet.to_string(),
+                destination_address: None,
+                confirmation: confirmation.to_string(),
+            });
+            let (status, response) = sweep_wallet(State::from(&test_state), request, admin())
+                .await
+                .unwrap_err();
+            assert_eq!(status, Status::BadRequest, "confirmation {confirmation:?}");
+            assert!(response.message.contains("confirmation"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sweep_without_destination_or_cold_wallet_is_rejected() {
+        let test_state = create_test_state().await;
+        let wallet = "0x1234567890123456789012345678901234567890";
+        let request = Json(SweepWalletRequest {
+            wallet_address: wallet.to_string(),
+            destination_address: None,
+            confirmation: wallet.to_string(),
+        });
+
+        let (status, response) = sweep_wallet(State::from(&test_state), request, admin())
+            .await
+            .unwrap_err();
+        assert_eq!(status, Status::BadRequest);
+        assert!(response.message.contains("COLD_WALLET_ADDRESS"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis + Anvil"]
+    async fn test_sweep_defaults_to_cold_wallet_and_honours_override() {
+        let (mut app_state, _anvil) =
+            crate::test_utils::create_isolated_test_app_state_with_redis().await;
+        let cold = Address::from_str("0x0000000000000000000000000000000000000c01").unwrap();
+        app_state.wallets.cold_wallet_address = Some(cold);
+        let pool = app_state.wallets.manager.signer_addresses();
+        assert!(pool.len() >= 2, "needs two pool wallets");
+
+        // No destination: the configured cold wallet receives the funds.
+        let default_request = Json(SweepWalletRequest {
+            wallet_address: pool[0].to_string(),
+            destination_address: None,
+            confirmation: pool[0].to_string(),
+        });
+        let data = sweep_wallet(State::from(&app_state), default_request, admin())
+            .await
+            .expect("default sweep should succeed")
+            .into_inner()
+            .data
+            .expect("sweep data");
+        assert_eq!(Address::from_str(&data.destination_address).unwrap(), cold);
+
+        // Explicit destination overrides the cold wallet.
+        let data = sweep_wallet(
+            State::from(&app_state),
+            request(&pool[1].to_string(), DESTINATION),
+            admin(),
+        )
+        .await
+        .expect("override sweep should succeed")
+        .into_inner()
+        .data
+        .expect("sweep data");
+        assert_eq!(
+            Address::from_str(&data.destination_address).unwrap(),
+            Address::from_str(DESTINATION).unwrap()
+        );
+    }
+}
+
+// --- wallet_pool/status ---
+
+mod pool_status {
+    use super::*;
+    use the_beaconator::guards::AdminToken;
+    use the_beaconator::routes::wallet::wallet_pool_status;
+
+    fn admin() -> AdminToken {
+        AdminToken("test_admin_token".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_pool_status_degraded_without_redis() {
+        // The simple fixture uses the WalletManager test stub (no Redis pool), which
+        // must yield a degraded report rather than panicking.
+        let test_state = create_test_state().await;
+        let response = wallet_pool_status(admin(), State::from(&test_state))
+            .await
+            .into_inner();
+
+        assert!(!response.success);
+        assert!(response.message.contains("degraded"));
+        let data = response.data.expect("status data");
+        assert!(data.degraded);
+        assert!(data.error.is_some());
+        assert_eq!(data.total_wallets, 0);
+        assert!(data.wallets.is_empty());
+    }
+
+    #[cfg(feature = "wallet-pool")]
+    #[tokio::test]
+    #[ignore = "requires WalletManager with Redis"]
+    async fn test_pool_status_reports_locks_and_designations() {
+        let manager = crate::test_utils::create_test_wallet_manager().await;
+        let mut signers = manager.signer_addresses();
+        signers.sort();
+        let beacon = Address::from_str("0x00000000000000000000000000000000000000bb").unwrap();
+        manager
+            .pool()
+            .add_designated_beacon(&signers[0], &beacon)
+            .await
+            .unwrap();
+        let guard = manager.acquire_lock(&signers[1]).await.unwrap();
+
+        let status = manager.pool_status().await;
+        assert!(!status.degraded, "unexpected error: {:?}", status.error);
+        assert_eq!(status.signer_count, 3);
+        assert_eq!(status.total_wallets, 3);
+        assert_eq!(status.status_counts.get("Available"), Some(&3));
+        assert_eq!(status.locked_wallets, 1);
+        assert!(status.unregistered_signers.is_empty());
+        assert_eq!(
+            status.designations.get(&beacon.to_string()),
+            Some(&signers[0].to_string())
+        );
+
+        let locked = &status.wallets[1];
+        assert_eq!(locked.address, signers[1].to_string());
+        assert_eq!(locked.lock_holder.as_deref(), Some(manager.instance_id()));
+        assert!(locked.has_signer);
+
+        guard.release().await.unwrap();
+        manager.pool().cleanup().await.unwrap();
+    }
+}
+
+// --- wallet_pool/force_unlock ---
+
+mod force_unlock {
+    use super::*;
+    use the_beaconator::guards::AdminToken;
+    use the_beaconator::models::ForceUnlockWalletRequest;
+    use the_beaconator::routes::wallet::force_unlock_wallet;
+
+    fn admin() -> AdminToken {
+        AdminToken("test_admin_token".to_string())
+    }
+
+    fn request(wallet: &str, holder: &str) -> Json<ForceUnlockWalletRequest> {
+        Json(ForceUnlockWalletRequest {
+            wallet_address: wallet.to_string(),
+            expected_holder: holder.to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_force_unlock_rejects_invalid_address() {
+        let test_state = create_test_state().await;
+        let result =
+            force_unlock_wallet(State::from(&test_state), request("nope", "i-1"), admin()).await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, Status::BadRequest);
+    }
+
+    #[tokio::test]
+    async fn test_force_unlock_requires_expected_holder() {
+        let test_state = create_test_state().await;
+        let result = force_unlock_wallet(
+            State::from(&test_state),
+            request("0x00000000000000000000000000000000000000bb", "  "),
+            admin(),
+        )
+        .await;
+        let (status, response) = result.unwrap_err();
+        assert_eq!(status, Status::BadRequest);
+        assert!(response.message.contains("expected_holder"));
+    }
+
+    #[tokio::test]
+    async fn test_force_unlock_unavailable_without_pool() {
+        let test_state = create_test_state().await;
+        let result = force_unlock_wallet(
+            State::from(&test_state),
+            request("0x00000000000000000000000000000000000000bb", "i-1"),
+            admin(),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, Status::ServiceUnavailable);
+    }
+}
+
+mod designation {
+    use super::*;
+    use the_beaconator::guards::AdminToken;
+    use the_beaconator::models::BeaconDesignationRequest;
+    use the_beaconator::routes::wallet::set_beacon_designation;
+
+    const BEACON: &str = "0x00000000000000000000000000000000000000cc";
+
+    fn admin() -> AdminToken {
+        AdminToken("test_admin_token".to_string())
+    }
+
+    fn request(beacon: &str, wallet: Option<&str>) -> Json<BeaconDesignationRequest> {
+        Json(BeaconDesignationRequest {
+            beacon_address: beacon.to_string(),
+            wallet_address: wallet.map(str::to_string),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_designation_rejects_invalid_addresses() {
+        let test_state = create_test_state().await;
+        let result =
+            set_beacon_designation(State::from(&test_state), request("nope", None), admin()).await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, Status::BadRequest);
+
+        let result = set_beacon_designation(
+            State::from(&test_state),
+            request(BEACON, Some("nope")),
+            admin(),
+        )
+        .await;
+        let (status, response) = result.unwrap_err();
+        assert_eq!(status, Status::BadRequest);
+        assert!(response.message.contains("wallet address"));
+    }
+
+    #[tokio::test]
+    async fn test_designation_unavailable_without_pool() {
+        let test_state = create_test_state().await;
+        let result = set_beacon_designation(
+            State::from(&test_state),
+            request(BEACON, Some("0x00000000000000000000000000000000000000bb")),
+            admin(),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, Status::ServiceUnavailable);
+    }
+
+    #[cfg(feature = "wallet-pool")]
+    #[tokio::test]
+    #[ignore = "requires WalletManager with Redis"]
+    async fn test_designated_beacon_locks_its_wallet() {
+        let manager = crate::test_utils::create_test_wallet_manager().await;
+        let beacon = Address::from_str(BEACON).unwrap();
+        let mut wallets = manager.signer_addresses();
+        wallets.sort();
+        let designated = wallets[1];
+
+        assert_eq!(
+            manager
+                .designate_beacon(&beacon, &designated)
+                .await
+                .unwrap(),
+            None
+        );
+        let handle = manager.acquire_for_beacon(&beacon).await.unwrap();
+        assert_eq!(handle.address(), designated);
+
+        // Fallback is off: a busy designated wallet fails the operation.
+        let err = manager.acquire_for_beacon(&beacon).await.err().unwrap();
+        assert!(err.contains("Designated wallet"), "unexpected error: {err}");
+        drop(handle);
+
+        assert_eq!(
+            manager.clear_beacon_designation(&beacon).await.unwrap(),
+            Some(designated)
+        );
+        assert_eq!(
+            manager.clear_beacon_designation(&beacon).await.unwrap(),
+            None
+        );
+        let handle = manager.acquire_for_beacon(&beacon).await.unwrap();
+        assert!(wallets.contains(&handle.address()));
+        drop(handle);
+
+        manager.pool().cleanup().await.unwrap();
+    }
+}
+
+// --- maintenance/drain, maintenance/resume ---
+
+mod maintenance {
+    use super::*;
+    use alloy::primitives::U256;
+    use rocket::http::Header;
+    use rocket::local::asynchronous::Client;
+    use serde_json::Value;
+    use std::collections::HashSet;
+    use the_beaconator::guards::AdminToken;
+    use the_beaconator::routes::beacon_error_status;
+    use the_beaconator::routes::wallet::{drain_wallets, resume_wallets};
+    use the_beaconator::services::error::BeaconError;
+    use the_beaconator::services::wallet::WALLET_POOL_DRAINING;
+
+    fn admin() -> AdminToken {
+        AdminToken("test_admin_token".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_drain_and_resume_toggle_the_flag() {
+        let test_state = create_test_state().await;
+        assert!(!test_state.wallets.manager.is_draining());
+
+        let response = drain_wallets(State::from(&test_state), admin())
+            .await
+            .into_inner();
+        assert!(response.success);
+        let data = response.data.expect("drain data");
+        assert!(data.draining);
+        assert!(!data.was_draining);
+        assert!(test_state.wallets.manager.is_draining());
+
+        // Draining again is a no-op that says so.
+        let data = drain_wallets(State::from(&test_state), admin())
+            .await
+            .into_inner()
+            .data
+            .unwrap();
+        assert!(data.draining && data.was_draining);
+
+        let data = resume_wallets(State::from(&test_state), admin())
+            .await
+            .into_inner()
+            .data
+            .unwrap();
+        assert!(!data.draining);
+        assert!(data.was_draining);
+        assert!(!test_state.wallets.manager.is_draining());
+    }
+
+    #[tokio::test]
+    async fn test_leases_are_refused_while_draining() {
+        // The test stub panics on any real acquisition, so reaching an error at all
+        // shows the drain check runs before the pool is touched.
+        let test_state = create_test_state().await;
+        let manager = &test_state.wallets.manager;
+        let wallet = Address::from_str("0x00000000000000000000000000000000000000bb").unwrap();
+        manager.set_draining(true);
+
+        let errors = [
+            manager.acquire_any_wallet().await.err(),
+            manager.acquire_for_beacon(&wallet).await.err(),
+            manager.acquire_specific_wallet(&wallet).await.err(),
+            manager
+                .acquire_any_wallet_excluding(&HashSet::new())
+                .await
+                .err(),
+            manager
+                .acquire_wallet_for_usdc(U256::ZERO, &HashSet::new())
+                .await
+                .err(),
+            #[cfg(feature = "wallet-pool")]
+            manager.acquire_lock(&wallet).await.err(),
+        ];
+        for error in errors {
+            assert_eq!(error.as_deref(), Some(WALLET_POOL_DRAINING));
+        }
+    }
+
+    #[test]
+    fn test_drain_refusal_maps_to_service_unavailable() {
+        // Services report every failed lease as WalletUnavailable.
+        let error = BeaconError::WalletUnavailable(format!(
+            "Failed to acquire wallet: {WALLET_POOL_DRAINING}"
+        ));
+        assert_eq!(error.code(), "wallet_unavailable");
+        assert_eq!(beacon_error_status(&error), Status::ServiceUnavailable);
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_not_ready_while_draining() {
+        let test_state = create_test_state().await;
+        let client = Client::tracked(the_beaconator::mock_rocket(test_state))
+            .await
+            .expect("valid rocket");
+        let health = || async {
+            let response = client.get("/health").dispatch().await;
+            assert_eq!(response.status(), Status::Ok);
+            response.into_json::<Value>().await.unwrap()
+        };
+        let admin_post = |path: &'static str| {
+            client
+                .post(path)
+                .header(Header::new("Authorization", "Bearer test_admin_token"))
+                .dispatch()
+        };
+
+        let body = health().await;
+        assert_eq!(body["live"], true);
+        assert_eq!(body["ready"], true);
+        assert_eq!(body["draining"], false);
+        assert_eq!(client.get("/ready").dispatch().await.status(), Status::Ok);
+
+        // Admin only.
+        let response = client
+            .post("/maintenance/drain")
+            .header(Header::new("Authorization", "Bearer test_token"))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        assert_eq!(admin_post("/maintenance/drain").await.status(), Status::Ok);
+        let body = health().await;
+        assert_eq!(body["live"], true);
+        assert_eq!(body["ready"], false);
+        assert_eq!(body["draining"], true);
+        let response = client.get("/ready").dispatch().await;
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+        assert_eq!(
+            response.into_json::<Value>().await.unwrap()["draining"],
+            true
+        );
+
+        assert_eq!(admin_post("/maintenance/resume").await.status(), Status::Ok);
+        assert_eq!(health().await["ready"], true);
+        assert_eq!(client.get("/ready").dispatch().await.status(), Status::Ok);
+    }
+}